@@ -13,6 +13,10 @@
 // limitations under the License.
 
 mod columns;
+mod jobs;
+mod pg_catalog;
+mod processlist;
+mod region_statistics;
 mod tables;
 
 use std::any::Any;
@@ -30,12 +34,20 @@ use table::metadata::TableType;
 use table::{Result as TableResult, Table, TableRef};
 
 use self::columns::InformationSchemaColumns;
+pub use self::jobs::{list_jobs, register_job_lister, JobInfo, JobLister};
+pub use self::pg_catalog::PgCatalogProvider;
 use crate::error::Result;
+use crate::information_schema::jobs::InformationSchemaJobs;
+use crate::information_schema::processlist::InformationSchemaProcesslist;
+use crate::information_schema::region_statistics::InformationSchemaRegionStatistics;
 use crate::information_schema::tables::InformationSchemaTables;
 use crate::CatalogManager;
 
 const TABLES: &str = "tables";
 const COLUMNS: &str = "columns";
+const PROCESSLIST: &str = "processlist";
+const JOBS: &str = "jobs";
+const REGION_STATISTICS: &str = "region_statistics";
 
 pub struct InformationSchemaProvider {
     catalog_name: String,
@@ -62,6 +74,14 @@ impl InformationSchemaProvider {
                 self.catalog_name.clone(),
                 self.catalog_manager.clone(),
             )) as _,
+            PROCESSLIST => {
+                Arc::new(InformationSchemaProcesslist::new(self.catalog_name.clone())) as _
+            }
+            JOBS => Arc::new(InformationSchemaJobs::new(self.catalog_name.clone())) as _,
+            REGION_STATISTICS => Arc::new(InformationSchemaRegionStatistics::new(
+                self.catalog_name.clone(),
+                self.catalog_manager.clone(),
+            )) as _,
             _ => {
                 return Ok(None);
             }
@@ -136,6 +156,7 @@ impl Table for InformationTable {
             schema: projected_schema,
             stream: Box::pin(stream),
             output_ordering: None,
+            metrics: None,
         };
         Ok(Box::pin(stream))
     }
@@ -0,0 +1,206 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, RwLock, Weak};
+
+use arrow_schema::SchemaRef as ArrowSchemaRef;
+use common_error::ext::BoxedError;
+use common_query::physical_plan::TaskContext;
+use common_recordbatch::adapter::RecordBatchStreamAdapter;
+use common_recordbatch::{RecordBatch, SendableRecordBatchStream};
+use datafusion::datasource::streaming::PartitionStream as DfPartitionStream;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter as DfRecordBatchStreamAdapter;
+use datafusion::physical_plan::SendableRecordBatchStream as DfSendableRecordBatchStream;
+use datatypes::prelude::{ConcreteDataType, ScalarVectorBuilder, VectorRef};
+use datatypes::schema::{ColumnSchema, Schema, SchemaRef};
+use datatypes::vectors::{StringVectorBuilder, TimestampMillisecondVectorBuilder};
+use lazy_static::lazy_static;
+use snafu::ResultExt;
+
+use crate::error::{CreateRecordBatchSnafu, InternalSnafu, Result};
+use crate::information_schema::InformationStreamBuilder;
+
+lazy_static! {
+    static ref JOB_LISTERS: RwLock<Vec<Weak<dyn JobLister>>> = RwLock::new(Vec::new());
+}
+
+/// A job shown in `information_schema.jobs`.
+#[derive(Debug, Clone)]
+pub struct JobInfo {
+    pub id: String,
+    /// Catalog of the data the job works on.
+    pub catalog: String,
+    /// Schema of the data the job works on.
+    pub schema: String,
+    pub state: String,
+    pub error: Option<String>,
+    pub start_time_ms: i64,
+}
+
+/// Lists the jobs run by a frontend, which registers its lister by [register_job_lister].
+pub trait JobLister: Send + Sync {
+    fn list_jobs(&self) -> Vec<JobInfo>;
+}
+
+/// Registers a lister of `information_schema.jobs`, which lists the jobs of all live
+/// listers in this process. The lister is unregistered once it's dropped.
+pub fn register_job_lister(lister: Weak<dyn JobLister>) {
+    let mut listers = JOB_LISTERS.write().unwrap();
+    listers.retain(|lister| lister.strong_count() > 0);
+    listers.push(lister);
+}
+
+/// Lists the jobs of all live listers in this process.
+pub fn list_jobs() -> Vec<JobInfo> {
+    JOB_LISTERS
+        .read()
+        .unwrap()
+        .iter()
+        .filter_map(Weak::upgrade)
+        .flat_map(|lister| lister.list_jobs())
+        .collect()
+}
+
+pub(super) struct InformationSchemaJobs {
+    schema: SchemaRef,
+    catalog_name: String,
+}
+
+impl InformationSchemaJobs {
+    pub(super) fn new(catalog_name: String) -> Self {
+        let schema = Arc::new(Schema::new(vec![
+            ColumnSchema::new("job_id", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("catalog", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("state", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("error", ConcreteDataType::string_datatype(), true),
+            ColumnSchema::new(
+                "start_time",
+                ConcreteDataType::timestamp_millisecond_datatype(),
+                false,
+            ),
+        ]));
+        Self {
+            schema,
+            catalog_name,
+        }
+    }
+
+    fn builder(&self) -> InformationSchemaJobsBuilder {
+        InformationSchemaJobsBuilder::new(self.schema.clone(), self.catalog_name.clone())
+    }
+}
+
+impl InformationStreamBuilder for InformationSchemaJobs {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn to_stream(&self) -> Result<SendableRecordBatchStream> {
+        let schema = self.schema.arrow_schema().clone();
+        let mut builder = self.builder();
+        let stream = Box::pin(DfRecordBatchStreamAdapter::new(
+            schema,
+            futures::stream::once(async move {
+                builder
+                    .make_jobs()
+                    .map(|x| x.into_df_record_batch())
+                    .map_err(Into::into)
+            }),
+        ));
+        Ok(Box::pin(
+            RecordBatchStreamAdapter::try_new(stream)
+                .map_err(BoxedError::new)
+                .context(InternalSnafu)?,
+        ))
+    }
+}
+
+/// Builds the `information_schema.jobs` table from the jobs of this process.
+struct InformationSchemaJobsBuilder {
+    schema: SchemaRef,
+    catalog_name: String,
+
+    ids: StringVectorBuilder,
+    catalog_names: StringVectorBuilder,
+    states: StringVectorBuilder,
+    errors: StringVectorBuilder,
+    start_times: TimestampMillisecondVectorBuilder,
+}
+
+impl InformationSchemaJobsBuilder {
+    fn new(schema: SchemaRef, catalog_name: String) -> Self {
+        Self {
+            schema,
+            catalog_name,
+            ids: StringVectorBuilder::with_capacity(42),
+            catalog_names: StringVectorBuilder::with_capacity(42),
+            states: StringVectorBuilder::with_capacity(42),
+            errors: StringVectorBuilder::with_capacity(42),
+            start_times: TimestampMillisecondVectorBuilder::with_capacity(42),
+        }
+    }
+
+    /// Construct the `information_schema.jobs` virtual table
+    fn make_jobs(&mut self) -> Result<RecordBatch> {
+        let mut jobs = list_jobs();
+        jobs.sort_unstable_by_key(|job| job.start_time_ms);
+        for job in jobs {
+            // Jobs of other catalogs are invisible.
+            if job.catalog == self.catalog_name {
+                self.add_job(&job);
+            }
+        }
+
+        self.finish()
+    }
+
+    fn add_job(&mut self, job: &JobInfo) {
+        self.ids.push(Some(&job.id));
+        self.catalog_names.push(Some(&job.catalog));
+        self.states.push(Some(&job.state));
+        self.errors.push(job.error.as_deref());
+        self.start_times.push(Some(job.start_time_ms.into()));
+    }
+
+    fn finish(&mut self) -> Result<RecordBatch> {
+        let columns: Vec<VectorRef> = vec![
+            Arc::new(self.ids.finish()),
+            Arc::new(self.catalog_names.finish()),
+            Arc::new(self.states.finish()),
+            Arc::new(self.errors.finish()),
+            Arc::new(self.start_times.finish()),
+        ];
+        RecordBatch::new(self.schema.clone(), columns).context(CreateRecordBatchSnafu)
+    }
+}
+
+impl DfPartitionStream for InformationSchemaJobs {
+    fn schema(&self) -> &ArrowSchemaRef {
+        self.schema.arrow_schema()
+    }
+
+    fn execute(&self, _: Arc<TaskContext>) -> DfSendableRecordBatchStream {
+        let schema = self.schema.arrow_schema().clone();
+        let mut builder = self.builder();
+        Box::pin(DfRecordBatchStreamAdapter::new(
+            schema,
+            futures::stream::once(async move {
+                builder
+                    .make_jobs()
+                    .map(|x| x.into_df_record_batch())
+                    .map_err(Into::into)
+            }),
+        ))
+    }
+}
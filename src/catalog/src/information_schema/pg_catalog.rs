@@ -0,0 +1,218 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An emulation of PostgreSQL's `pg_catalog` schema, so that PostgreSQL clients
+//! and BI tools can browse the schemas, tables and columns of GreptimeDB.
+//!
+//! Only the tables and columns commonly queried by those tools are provided.
+
+mod pg_attribute;
+mod pg_class;
+mod pg_namespace;
+mod pg_type;
+
+use std::future::Future;
+use std::sync::{Arc, Weak};
+
+use common_catalog::consts::{DEFAULT_SCHEMA_NAME, INFORMATION_SCHEMA_NAME, PG_CATALOG_NAME};
+use common_error::ext::BoxedError;
+use common_recordbatch::adapter::RecordBatchStreamAdapter;
+use common_recordbatch::{RecordBatch, SendableRecordBatchStream};
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter as DfRecordBatchStreamAdapter;
+use datatypes::prelude::ConcreteDataType;
+use datatypes::schema::SchemaRef;
+use datatypes::vectors::{
+    BooleanVector, Float32Vector, Int16Vector, Int32Vector, StringVector, UInt32Vector, VectorRef,
+};
+use snafu::ResultExt;
+use table::TableRef;
+
+use self::pg_attribute::PgAttribute;
+use self::pg_class::PgClass;
+use self::pg_namespace::PgNamespace;
+use self::pg_type::PgType;
+use crate::error::{InternalSnafu, Result};
+use crate::information_schema::InformationTable;
+use crate::CatalogManager;
+
+const PG_NAMESPACE: &str = "pg_namespace";
+const PG_CLASS: &str = "pg_class";
+const PG_ATTRIBUTE: &str = "pg_attribute";
+const PG_TYPE: &str = "pg_type";
+
+/// Oid of the bootstrap superuser, owner of all objects.
+const OWNER_OID: u32 = 10;
+const PG_CATALOG_NAMESPACE_OID: u32 = 11;
+const INFORMATION_SCHEMA_NAMESPACE_OID: u32 = 13000;
+const PUBLIC_NAMESPACE_OID: u32 = 2200;
+/// Oids of user objects start from this value in PostgreSQL.
+const FIRST_NORMAL_OID: u32 = 16384;
+
+pub struct PgCatalogProvider {
+    catalog_name: String,
+    catalog_manager: Weak<dyn CatalogManager>,
+}
+
+impl PgCatalogProvider {
+    pub fn new(catalog_name: String, catalog_manager: Weak<dyn CatalogManager>) -> Self {
+        Self {
+            catalog_name,
+            catalog_manager,
+        }
+    }
+
+    pub fn table(&self, name: &str) -> Result<Option<TableRef>> {
+        let stream_builder = match name.to_ascii_lowercase().as_ref() {
+            PG_NAMESPACE => Arc::new(PgNamespace::new(
+                self.catalog_name.clone(),
+                self.catalog_manager.clone(),
+            )) as _,
+            PG_CLASS => Arc::new(PgClass::new(
+                self.catalog_name.clone(),
+                self.catalog_manager.clone(),
+            )) as _,
+            PG_ATTRIBUTE => Arc::new(PgAttribute::new(
+                self.catalog_name.clone(),
+                self.catalog_manager.clone(),
+            )) as _,
+            PG_TYPE => Arc::new(PgType::new()) as _,
+            _ => {
+                return Ok(None);
+            }
+        };
+
+        Ok(Some(Arc::new(InformationTable::new(stream_builder))))
+    }
+}
+
+/// Returns the oid of a schema. Builtin schemas have the same oids as in
+/// PostgreSQL, while oids of user schemas are derived from their names so they
+/// stay stable between queries.
+fn namespace_oid(schema_name: &str) -> u32 {
+    match schema_name {
+        PG_CATALOG_NAME => PG_CATALOG_NAMESPACE_OID,
+        INFORMATION_SCHEMA_NAME => INFORMATION_SCHEMA_NAMESPACE_OID,
+        DEFAULT_SCHEMA_NAME => PUBLIC_NAMESPACE_OID,
+        _ => {
+            // FNV-1a
+            let hash = schema_name.bytes().fold(0x811c9dc5u32, |hash, b| {
+                (hash ^ b as u32).wrapping_mul(0x01000193)
+            });
+            FIRST_NORMAL_OID + hash % (u32::MAX - FIRST_NORMAL_OID)
+        }
+    }
+}
+
+/// Returns the oid of a table, derived from its table id.
+fn table_oid(table_id: u32) -> u32 {
+    FIRST_NORMAL_OID.saturating_add(table_id)
+}
+
+/// Returns the oid of the PostgreSQL type that a column of `data_type` is
+/// served as by the Postgres protocol server.
+fn type_oid(data_type: &ConcreteDataType) -> u32 {
+    match data_type {
+        ConcreteDataType::Boolean(_) => pg_type::BOOL_OID,
+        ConcreteDataType::Int8(_) | ConcreteDataType::UInt8(_) => pg_type::CHAR_OID,
+        ConcreteDataType::Int16(_) | ConcreteDataType::UInt16(_) => pg_type::INT2_OID,
+        ConcreteDataType::Int32(_) | ConcreteDataType::UInt32(_) => pg_type::INT4_OID,
+        ConcreteDataType::Int64(_) | ConcreteDataType::UInt64(_) => pg_type::INT8_OID,
+        ConcreteDataType::Float32(_) => pg_type::FLOAT4_OID,
+        ConcreteDataType::Float64(_) => pg_type::FLOAT8_OID,
+        ConcreteDataType::Binary(_) => pg_type::BYTEA_OID,
+        ConcreteDataType::String(_) => pg_type::VARCHAR_OID,
+        ConcreteDataType::Date(_) => pg_type::DATE_OID,
+        ConcreteDataType::DateTime(_) | ConcreteDataType::Timestamp(_) => pg_type::TIMESTAMP_OID,
+        ConcreteDataType::Null(_) | ConcreteDataType::List(_) | ConcreteDataType::Dictionary(_) => {
+            pg_type::TEXT_OID
+        }
+    }
+}
+
+/// Wraps the future building the whole table into a record batch stream.
+fn make_stream<F>(schema: SchemaRef, batch: F) -> Result<SendableRecordBatchStream>
+where
+    F: Future<Output = Result<RecordBatch>> + Send + 'static,
+{
+    let stream = Box::pin(DfRecordBatchStreamAdapter::new(
+        schema.arrow_schema().clone(),
+        futures::stream::once(async move {
+            batch
+                .await
+                .map(|x| x.into_df_record_batch())
+                .map_err(Into::into)
+        }),
+    ));
+    Ok(Box::pin(
+        RecordBatchStreamAdapter::try_new(stream)
+            .map_err(BoxedError::new)
+            .context(InternalSnafu)?,
+    ))
+}
+
+fn constant_u32(value: u32, rows: usize) -> VectorRef {
+    Arc::new(UInt32Vector::from_vec(vec![value; rows]))
+}
+
+fn constant_i16(value: i16, rows: usize) -> VectorRef {
+    Arc::new(Int16Vector::from_vec(vec![value; rows]))
+}
+
+fn constant_i32(value: i32, rows: usize) -> VectorRef {
+    Arc::new(Int32Vector::from_vec(vec![value; rows]))
+}
+
+fn constant_f32(value: f32, rows: usize) -> VectorRef {
+    Arc::new(Float32Vector::from_vec(vec![value; rows]))
+}
+
+fn constant_bool(value: bool, rows: usize) -> VectorRef {
+    Arc::new(BooleanVector::from(vec![value; rows]))
+}
+
+fn constant_string(value: Option<&str>, rows: usize) -> VectorRef {
+    Arc::new(StringVector::from(vec![value; rows]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_namespace_oid() {
+        assert_eq!(11, namespace_oid("pg_catalog"));
+        assert_eq!(2200, namespace_oid("public"));
+
+        let oid = namespace_oid("greptime_private");
+        assert!(oid >= FIRST_NORMAL_OID);
+        assert_eq!(oid, namespace_oid("greptime_private"));
+        assert_ne!(oid, namespace_oid("greptime_public"));
+    }
+
+    #[test]
+    fn test_type_oid() {
+        assert_eq!(
+            pg_type::INT8_OID,
+            type_oid(&ConcreteDataType::int64_datatype())
+        );
+        assert_eq!(
+            pg_type::TIMESTAMP_OID,
+            type_oid(&ConcreteDataType::timestamp_millisecond_datatype())
+        );
+        assert_eq!(
+            pg_type::VARCHAR_OID,
+            type_oid(&ConcreteDataType::string_datatype())
+        );
+    }
+}
@@ -0,0 +1,185 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, Weak};
+
+use common_catalog::consts::INFORMATION_SCHEMA_NAME;
+use common_recordbatch::{RecordBatch, SendableRecordBatchStream};
+use datatypes::prelude::{ConcreteDataType, MutableVector, ScalarVectorBuilder, VectorRef};
+use datatypes::schema::{ColumnSchema, Schema, SchemaRef};
+use datatypes::vectors::{
+    BooleanVectorBuilder, Int16VectorBuilder, StringVectorBuilder, UInt32VectorBuilder,
+};
+use snafu::{OptionExt, ResultExt};
+
+use super::{
+    constant_bool, constant_i32, constant_string, constant_u32, make_stream, pg_type, table_oid,
+    type_oid,
+};
+use crate::error::{CreateRecordBatchSnafu, Result, UpgradeWeakCatalogManagerRefSnafu};
+use crate::information_schema::InformationStreamBuilder;
+use crate::CatalogManager;
+
+/// The `pg_catalog.pg_attribute` table, listing the columns of all tables.
+///
+/// Columns are based on <https://www.postgresql.org/docs/current/catalog-pg-attribute.html>
+pub(super) struct PgAttribute {
+    schema: SchemaRef,
+    catalog_name: String,
+    catalog_manager: Weak<dyn CatalogManager>,
+}
+
+impl PgAttribute {
+    pub(super) fn new(catalog_name: String, catalog_manager: Weak<dyn CatalogManager>) -> Self {
+        let schema = Arc::new(Schema::new(vec![
+            ColumnSchema::new("attrelid", ConcreteDataType::uint32_datatype(), false),
+            ColumnSchema::new("attname", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("atttypid", ConcreteDataType::uint32_datatype(), false),
+            ColumnSchema::new("attstattarget", ConcreteDataType::int32_datatype(), false),
+            ColumnSchema::new("attlen", ConcreteDataType::int16_datatype(), false),
+            ColumnSchema::new("attnum", ConcreteDataType::int16_datatype(), false),
+            ColumnSchema::new("attndims", ConcreteDataType::int32_datatype(), false),
+            ColumnSchema::new("atttypmod", ConcreteDataType::int32_datatype(), false),
+            ColumnSchema::new("attbyval", ConcreteDataType::boolean_datatype(), false),
+            ColumnSchema::new("attnotnull", ConcreteDataType::boolean_datatype(), false),
+            ColumnSchema::new("atthasdef", ConcreteDataType::boolean_datatype(), false),
+            ColumnSchema::new("attidentity", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("attgenerated", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("attisdropped", ConcreteDataType::boolean_datatype(), false),
+            ColumnSchema::new("attislocal", ConcreteDataType::boolean_datatype(), false),
+            ColumnSchema::new("attinhcount", ConcreteDataType::int32_datatype(), false),
+            ColumnSchema::new("attcollation", ConcreteDataType::uint32_datatype(), false),
+        ]));
+        Self {
+            schema,
+            catalog_name,
+            catalog_manager,
+        }
+    }
+
+    fn builder(&self) -> PgAttributeBuilder {
+        PgAttributeBuilder {
+            schema: self.schema.clone(),
+            catalog_name: self.catalog_name.clone(),
+            catalog_manager: self.catalog_manager.clone(),
+            rel_ids: UInt32VectorBuilder::with_capacity(42),
+            names: StringVectorBuilder::with_capacity(42),
+            type_ids: UInt32VectorBuilder::with_capacity(42),
+            lens: Int16VectorBuilder::with_capacity(42),
+            nums: Int16VectorBuilder::with_capacity(42),
+            by_vals: BooleanVectorBuilder::with_capacity(42),
+            not_nulls: BooleanVectorBuilder::with_capacity(42),
+            has_defaults: BooleanVectorBuilder::with_capacity(42),
+        }
+    }
+}
+
+impl InformationStreamBuilder for PgAttribute {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn to_stream(&self) -> Result<SendableRecordBatchStream> {
+        let mut builder = self.builder();
+        make_stream(self.schema.clone(), async move {
+            builder.make_attributes().await
+        })
+    }
+}
+
+struct PgAttributeBuilder {
+    schema: SchemaRef,
+    catalog_name: String,
+    catalog_manager: Weak<dyn CatalogManager>,
+
+    rel_ids: UInt32VectorBuilder,
+    names: StringVectorBuilder,
+    type_ids: UInt32VectorBuilder,
+    lens: Int16VectorBuilder,
+    nums: Int16VectorBuilder,
+    by_vals: BooleanVectorBuilder,
+    not_nulls: BooleanVectorBuilder,
+    has_defaults: BooleanVectorBuilder,
+}
+
+impl PgAttributeBuilder {
+    async fn make_attributes(&mut self) -> Result<RecordBatch> {
+        let catalog_name = self.catalog_name.clone();
+        let catalog_manager = self
+            .catalog_manager
+            .upgrade()
+            .context(UpgradeWeakCatalogManagerRefSnafu)?;
+
+        for schema_name in catalog_manager.schema_names(&catalog_name).await? {
+            if schema_name == INFORMATION_SCHEMA_NAME {
+                continue;
+            }
+
+            for table_name in catalog_manager
+                .table_names(&catalog_name, &schema_name)
+                .await?
+            {
+                let Some(table) = catalog_manager.table(&catalog_name, &schema_name, &table_name).await? else { continue };
+                let table_info = table.table_info();
+                let rel_id = table_oid(table_info.ident.table_id);
+                for (idx, column) in table_info.meta.schema.column_schemas().iter().enumerate() {
+                    self.add_attribute(rel_id, idx as i16 + 1, column);
+                }
+            }
+        }
+
+        self.finish()
+    }
+
+    fn add_attribute(&mut self, rel_id: u32, num: i16, column: &ColumnSchema) {
+        let type_id = type_oid(&column.data_type);
+        let len = pg_type::type_len(type_id);
+        self.rel_ids.push(Some(rel_id));
+        self.names.push(Some(&column.name));
+        self.type_ids.push(Some(type_id));
+        self.lens.push(Some(len));
+        self.nums.push(Some(num));
+        self.by_vals.push(Some(len > 0 && len <= 8));
+        self.not_nulls.push(Some(!column.is_nullable()));
+        self.has_defaults
+            .push(Some(column.default_constraint().is_some()));
+    }
+
+    fn finish(&mut self) -> Result<RecordBatch> {
+        let rows = self.rel_ids.len();
+        let columns: Vec<VectorRef> = vec![
+            Arc::new(self.rel_ids.finish()),
+            Arc::new(self.names.finish()),
+            Arc::new(self.type_ids.finish()),
+            // attstattarget
+            constant_i32(-1, rows),
+            Arc::new(self.lens.finish()),
+            Arc::new(self.nums.finish()),
+            // attndims, atttypmod
+            constant_i32(0, rows),
+            constant_i32(-1, rows),
+            Arc::new(self.by_vals.finish()),
+            Arc::new(self.not_nulls.finish()),
+            Arc::new(self.has_defaults.finish()),
+            // attidentity, attgenerated, attisdropped, attislocal, attinhcount, attcollation
+            constant_string(Some(""), rows),
+            constant_string(Some(""), rows),
+            constant_bool(false, rows),
+            constant_bool(true, rows),
+            constant_i32(0, rows),
+            constant_u32(0, rows),
+        ];
+        RecordBatch::new(self.schema.clone(), columns).context(CreateRecordBatchSnafu)
+    }
+}
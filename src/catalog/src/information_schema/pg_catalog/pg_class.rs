@@ -0,0 +1,203 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, Weak};
+
+use common_catalog::consts::INFORMATION_SCHEMA_NAME;
+use common_recordbatch::{RecordBatch, SendableRecordBatchStream};
+use datatypes::prelude::{ConcreteDataType, MutableVector, ScalarVectorBuilder, VectorRef};
+use datatypes::schema::{ColumnSchema, Schema, SchemaRef};
+use datatypes::vectors::{
+    BooleanVectorBuilder, Int16VectorBuilder, StringVectorBuilder, UInt32VectorBuilder,
+};
+use snafu::{OptionExt, ResultExt};
+use table::metadata::TableType;
+
+use super::{
+    constant_bool, constant_f32, constant_i16, constant_i32, constant_string, constant_u32,
+    make_stream, namespace_oid, table_oid, OWNER_OID,
+};
+use crate::error::{CreateRecordBatchSnafu, Result, UpgradeWeakCatalogManagerRefSnafu};
+use crate::information_schema::InformationStreamBuilder;
+use crate::CatalogManager;
+
+/// The `pg_catalog.pg_class` table, listing all tables of the catalog.
+///
+/// Columns are based on <https://www.postgresql.org/docs/current/catalog-pg-class.html>
+pub(super) struct PgClass {
+    schema: SchemaRef,
+    catalog_name: String,
+    catalog_manager: Weak<dyn CatalogManager>,
+}
+
+impl PgClass {
+    pub(super) fn new(catalog_name: String, catalog_manager: Weak<dyn CatalogManager>) -> Self {
+        let oid = ConcreteDataType::uint32_datatype;
+        let string = ConcreteDataType::string_datatype;
+        let boolean = ConcreteDataType::boolean_datatype;
+        let schema = Arc::new(Schema::new(vec![
+            ColumnSchema::new("oid", oid(), false),
+            ColumnSchema::new("relname", string(), false),
+            ColumnSchema::new("relnamespace", oid(), false),
+            ColumnSchema::new("reltype", oid(), false),
+            ColumnSchema::new("relowner", oid(), false),
+            ColumnSchema::new("relam", oid(), false),
+            ColumnSchema::new("relfilenode", oid(), false),
+            ColumnSchema::new("reltablespace", oid(), false),
+            ColumnSchema::new("relpages", ConcreteDataType::int32_datatype(), false),
+            ColumnSchema::new("reltuples", ConcreteDataType::float32_datatype(), false),
+            ColumnSchema::new("relhasindex", boolean(), false),
+            ColumnSchema::new("relisshared", boolean(), false),
+            ColumnSchema::new("relpersistence", string(), false),
+            ColumnSchema::new("relkind", string(), false),
+            ColumnSchema::new("relnatts", ConcreteDataType::int16_datatype(), false),
+            ColumnSchema::new("relchecks", ConcreteDataType::int16_datatype(), false),
+            ColumnSchema::new("relhasrules", boolean(), false),
+            ColumnSchema::new("relhastriggers", boolean(), false),
+            ColumnSchema::new("relhassubclass", boolean(), false),
+            ColumnSchema::new("relrowsecurity", boolean(), false),
+            ColumnSchema::new("relforcerowsecurity", boolean(), false),
+            ColumnSchema::new("relispopulated", boolean(), false),
+            ColumnSchema::new("relreplident", string(), false),
+            ColumnSchema::new("relispartition", boolean(), false),
+            ColumnSchema::new("relacl", string(), true),
+            ColumnSchema::new("reloptions", string(), true),
+            ColumnSchema::new("relpartbound", string(), true),
+        ]));
+        Self {
+            schema,
+            catalog_name,
+            catalog_manager,
+        }
+    }
+
+    fn builder(&self) -> PgClassBuilder {
+        PgClassBuilder {
+            schema: self.schema.clone(),
+            catalog_name: self.catalog_name.clone(),
+            catalog_manager: self.catalog_manager.clone(),
+            oids: UInt32VectorBuilder::with_capacity(42),
+            names: StringVectorBuilder::with_capacity(42),
+            namespaces: UInt32VectorBuilder::with_capacity(42),
+            has_indexes: BooleanVectorBuilder::with_capacity(42),
+            kinds: StringVectorBuilder::with_capacity(42),
+            natts: Int16VectorBuilder::with_capacity(42),
+        }
+    }
+}
+
+impl InformationStreamBuilder for PgClass {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn to_stream(&self) -> Result<SendableRecordBatchStream> {
+        let mut builder = self.builder();
+        make_stream(
+            self.schema.clone(),
+            async move { builder.make_classes().await },
+        )
+    }
+}
+
+struct PgClassBuilder {
+    schema: SchemaRef,
+    catalog_name: String,
+    catalog_manager: Weak<dyn CatalogManager>,
+
+    oids: UInt32VectorBuilder,
+    names: StringVectorBuilder,
+    namespaces: UInt32VectorBuilder,
+    has_indexes: BooleanVectorBuilder,
+    kinds: StringVectorBuilder,
+    natts: Int16VectorBuilder,
+}
+
+impl PgClassBuilder {
+    async fn make_classes(&mut self) -> Result<RecordBatch> {
+        let catalog_name = self.catalog_name.clone();
+        let catalog_manager = self
+            .catalog_manager
+            .upgrade()
+            .context(UpgradeWeakCatalogManagerRefSnafu)?;
+
+        for schema_name in catalog_manager.schema_names(&catalog_name).await? {
+            if schema_name == INFORMATION_SCHEMA_NAME {
+                continue;
+            }
+
+            for table_name in catalog_manager
+                .table_names(&catalog_name, &schema_name)
+                .await?
+            {
+                let Some(table) = catalog_manager.table(&catalog_name, &schema_name, &table_name).await? else { continue };
+                let table_info = table.table_info();
+                self.oids.push(Some(table_oid(table_info.ident.table_id)));
+                self.names.push(Some(&table_name));
+                self.namespaces.push(Some(namespace_oid(&schema_name)));
+                self.has_indexes
+                    .push(Some(!table_info.meta.primary_key_indices.is_empty()));
+                self.kinds.push(Some(match table.table_type() {
+                    TableType::View => "v",
+                    TableType::Base | TableType::Temporary => "r",
+                }));
+                self.natts
+                    .push(Some(table_info.meta.schema.column_schemas().len() as i16));
+            }
+        }
+
+        self.finish()
+    }
+
+    fn finish(&mut self) -> Result<RecordBatch> {
+        let rows = self.oids.len();
+        let columns: Vec<VectorRef> = vec![
+            Arc::new(self.oids.finish()),
+            Arc::new(self.names.finish()),
+            Arc::new(self.namespaces.finish()),
+            // reltype, relowner, relam, relfilenode, reltablespace
+            constant_u32(0, rows),
+            constant_u32(OWNER_OID, rows),
+            constant_u32(0, rows),
+            constant_u32(0, rows),
+            constant_u32(0, rows),
+            // relpages, reltuples
+            constant_i32(0, rows),
+            constant_f32(-1.0, rows),
+            Arc::new(self.has_indexes.finish()),
+            // relisshared, relpersistence
+            constant_bool(false, rows),
+            constant_string(Some("p"), rows),
+            Arc::new(self.kinds.finish()),
+            Arc::new(self.natts.finish()),
+            // relchecks, relhasrules, relhastriggers, relhassubclass,
+            // relrowsecurity, relforcerowsecurity, relispopulated,
+            // relreplident, relispartition
+            constant_i16(0, rows),
+            constant_bool(false, rows),
+            constant_bool(false, rows),
+            constant_bool(false, rows),
+            constant_bool(false, rows),
+            constant_bool(false, rows),
+            constant_bool(true, rows),
+            constant_string(Some("d"), rows),
+            constant_bool(false, rows),
+            // relacl, reloptions, relpartbound
+            constant_string(None, rows),
+            constant_string(None, rows),
+            constant_string(None, rows),
+        ];
+        RecordBatch::new(self.schema.clone(), columns).context(CreateRecordBatchSnafu)
+    }
+}
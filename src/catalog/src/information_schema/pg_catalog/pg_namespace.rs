@@ -0,0 +1,119 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, Weak};
+
+use common_catalog::consts::PG_CATALOG_NAME;
+use common_recordbatch::{RecordBatch, SendableRecordBatchStream};
+use datatypes::prelude::{ConcreteDataType, MutableVector, ScalarVectorBuilder, VectorRef};
+use datatypes::schema::{ColumnSchema, Schema, SchemaRef};
+use datatypes::vectors::{StringVectorBuilder, UInt32VectorBuilder};
+use snafu::{OptionExt, ResultExt};
+
+use super::{constant_string, constant_u32, make_stream, namespace_oid, OWNER_OID};
+use crate::error::{CreateRecordBatchSnafu, Result, UpgradeWeakCatalogManagerRefSnafu};
+use crate::information_schema::InformationStreamBuilder;
+use crate::CatalogManager;
+
+/// The `pg_catalog.pg_namespace` table, listing all schemas of the catalog.
+///
+/// Columns are based on <https://www.postgresql.org/docs/current/catalog-pg-namespace.html>
+pub(super) struct PgNamespace {
+    schema: SchemaRef,
+    catalog_name: String,
+    catalog_manager: Weak<dyn CatalogManager>,
+}
+
+impl PgNamespace {
+    pub(super) fn new(catalog_name: String, catalog_manager: Weak<dyn CatalogManager>) -> Self {
+        let schema = Arc::new(Schema::new(vec![
+            ColumnSchema::new("oid", ConcreteDataType::uint32_datatype(), false),
+            ColumnSchema::new("nspname", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("nspowner", ConcreteDataType::uint32_datatype(), false),
+            ColumnSchema::new("nspacl", ConcreteDataType::string_datatype(), true),
+        ]));
+        Self {
+            schema,
+            catalog_name,
+            catalog_manager,
+        }
+    }
+
+    fn builder(&self) -> PgNamespaceBuilder {
+        PgNamespaceBuilder {
+            schema: self.schema.clone(),
+            catalog_name: self.catalog_name.clone(),
+            catalog_manager: self.catalog_manager.clone(),
+            oids: UInt32VectorBuilder::with_capacity(8),
+            names: StringVectorBuilder::with_capacity(8),
+        }
+    }
+}
+
+impl InformationStreamBuilder for PgNamespace {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn to_stream(&self) -> Result<SendableRecordBatchStream> {
+        let mut builder = self.builder();
+        make_stream(self.schema.clone(), async move {
+            builder.make_namespaces().await
+        })
+    }
+}
+
+struct PgNamespaceBuilder {
+    schema: SchemaRef,
+    catalog_name: String,
+    catalog_manager: Weak<dyn CatalogManager>,
+
+    oids: UInt32VectorBuilder,
+    names: StringVectorBuilder,
+}
+
+impl PgNamespaceBuilder {
+    async fn make_namespaces(&mut self) -> Result<RecordBatch> {
+        let catalog_manager = self
+            .catalog_manager
+            .upgrade()
+            .context(UpgradeWeakCatalogManagerRefSnafu)?;
+
+        self.add_namespace(PG_CATALOG_NAME);
+        for schema_name in catalog_manager.schema_names(&self.catalog_name).await? {
+            if schema_name == PG_CATALOG_NAME {
+                continue;
+            }
+            self.add_namespace(&schema_name);
+        }
+
+        self.finish()
+    }
+
+    fn add_namespace(&mut self, schema_name: &str) {
+        self.oids.push(Some(namespace_oid(schema_name)));
+        self.names.push(Some(schema_name));
+    }
+
+    fn finish(&mut self) -> Result<RecordBatch> {
+        let rows = self.oids.len();
+        let columns: Vec<VectorRef> = vec![
+            Arc::new(self.oids.finish()),
+            Arc::new(self.names.finish()),
+            constant_u32(OWNER_OID, rows),
+            constant_string(None, rows),
+        ];
+        RecordBatch::new(self.schema.clone(), columns).context(CreateRecordBatchSnafu)
+    }
+}
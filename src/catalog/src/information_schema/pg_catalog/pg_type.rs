@@ -0,0 +1,169 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_recordbatch::{RecordBatch, SendableRecordBatchStream};
+use datatypes::prelude::{ConcreteDataType, ScalarVector, VectorRef};
+use datatypes::schema::{ColumnSchema, Schema, SchemaRef};
+use datatypes::vectors::{BooleanVector, Int16Vector, StringVector, UInt32Vector};
+use snafu::ResultExt;
+
+use super::{
+    constant_bool, constant_i32, constant_string, constant_u32, make_stream, OWNER_OID,
+    PG_CATALOG_NAMESPACE_OID,
+};
+use crate::error::{CreateRecordBatchSnafu, Result};
+use crate::information_schema::InformationStreamBuilder;
+
+pub(super) const BOOL_OID: u32 = 16;
+pub(super) const BYTEA_OID: u32 = 17;
+pub(super) const CHAR_OID: u32 = 18;
+pub(super) const INT8_OID: u32 = 20;
+pub(super) const INT2_OID: u32 = 21;
+pub(super) const INT4_OID: u32 = 23;
+pub(super) const TEXT_OID: u32 = 25;
+pub(super) const FLOAT4_OID: u32 = 700;
+pub(super) const FLOAT8_OID: u32 = 701;
+pub(super) const VARCHAR_OID: u32 = 1043;
+pub(super) const DATE_OID: u32 = 1082;
+pub(super) const TIMESTAMP_OID: u32 = 1114;
+
+/// A builtin PostgreSQL type, as (oid, name, length, category, array type oid).
+type PgTypeDef = (u32, &'static str, i16, &'static str, u32);
+
+/// The types that may be sent by the Postgres protocol server, plus a few
+/// commonly used by clients in their catalog queries.
+const TYPES: &[PgTypeDef] = &[
+    (BOOL_OID, "bool", 1, "B", 1000),
+    (BYTEA_OID, "bytea", -1, "U", 1001),
+    (CHAR_OID, "char", 1, "S", 1002),
+    (19, "name", 64, "S", 1003),
+    (INT8_OID, "int8", 8, "N", 1016),
+    (INT2_OID, "int2", 2, "N", 1005),
+    (INT4_OID, "int4", 4, "N", 1007),
+    (TEXT_OID, "text", -1, "S", 1009),
+    (26, "oid", 4, "N", 1028),
+    (FLOAT4_OID, "float4", 4, "N", 1021),
+    (FLOAT8_OID, "float8", 8, "N", 1022),
+    (VARCHAR_OID, "varchar", -1, "S", 1015),
+    (DATE_OID, "date", 4, "D", 1182),
+    (1083, "time", 8, "D", 1183),
+    (TIMESTAMP_OID, "timestamp", 8, "D", 1115),
+    (1184, "timestamptz", 8, "D", 1185),
+];
+
+/// Returns the `typlen` of the type, -1 for variable length or unknown types.
+pub(super) fn type_len(oid: u32) -> i16 {
+    TYPES
+        .iter()
+        .find(|(type_oid, ..)| *type_oid == oid)
+        .map(|(_, _, len, ..)| *len)
+        .unwrap_or(-1)
+}
+
+/// The `pg_catalog.pg_type` table, listing the builtin types.
+///
+/// Columns are based on <https://www.postgresql.org/docs/current/catalog-pg-type.html>
+pub(super) struct PgType {
+    schema: SchemaRef,
+}
+
+impl PgType {
+    pub(super) fn new() -> Self {
+        let schema = Arc::new(Schema::new(vec![
+            ColumnSchema::new("oid", ConcreteDataType::uint32_datatype(), false),
+            ColumnSchema::new("typname", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("typnamespace", ConcreteDataType::uint32_datatype(), false),
+            ColumnSchema::new("typowner", ConcreteDataType::uint32_datatype(), false),
+            ColumnSchema::new("typlen", ConcreteDataType::int16_datatype(), false),
+            ColumnSchema::new("typbyval", ConcreteDataType::boolean_datatype(), false),
+            ColumnSchema::new("typtype", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("typcategory", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("typisdefined", ConcreteDataType::boolean_datatype(), false),
+            ColumnSchema::new("typdelim", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("typrelid", ConcreteDataType::uint32_datatype(), false),
+            ColumnSchema::new("typelem", ConcreteDataType::uint32_datatype(), false),
+            ColumnSchema::new("typarray", ConcreteDataType::uint32_datatype(), false),
+            ColumnSchema::new("typnotnull", ConcreteDataType::boolean_datatype(), false),
+            ColumnSchema::new("typbasetype", ConcreteDataType::uint32_datatype(), false),
+            ColumnSchema::new("typtypmod", ConcreteDataType::int32_datatype(), false),
+            ColumnSchema::new("typndims", ConcreteDataType::int32_datatype(), false),
+        ]));
+        Self { schema }
+    }
+
+    fn make_types(schema: SchemaRef) -> Result<RecordBatch> {
+        let rows = TYPES.len();
+        let columns: Vec<VectorRef> = vec![
+            Arc::new(UInt32Vector::from_iter_values(TYPES.iter().map(|t| t.0))),
+            Arc::new(StringVector::from_iterator(TYPES.iter().map(|t| t.1))),
+            constant_u32(PG_CATALOG_NAMESPACE_OID, rows),
+            constant_u32(OWNER_OID, rows),
+            Arc::new(Int16Vector::from_iter_values(TYPES.iter().map(|t| t.2))),
+            Arc::new(BooleanVector::from(
+                TYPES
+                    .iter()
+                    .map(|t| t.2 > 0 && t.2 <= 8)
+                    .collect::<Vec<_>>(),
+            )),
+            // typtype
+            constant_string(Some("b"), rows),
+            Arc::new(StringVector::from_iterator(TYPES.iter().map(|t| t.3))),
+            // typisdefined, typdelim, typrelid, typelem
+            constant_bool(true, rows),
+            constant_string(Some(","), rows),
+            constant_u32(0, rows),
+            constant_u32(0, rows),
+            Arc::new(UInt32Vector::from_iter_values(TYPES.iter().map(|t| t.4))),
+            // typnotnull, typbasetype, typtypmod, typndims
+            constant_bool(false, rows),
+            constant_u32(0, rows),
+            constant_i32(-1, rows),
+            constant_i32(0, rows),
+        ];
+        RecordBatch::new(schema, columns).context(CreateRecordBatchSnafu)
+    }
+}
+
+impl InformationStreamBuilder for PgType {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn to_stream(&self) -> Result<SendableRecordBatchStream> {
+        let schema = self.schema.clone();
+        make_stream(self.schema.clone(), async move { Self::make_types(schema) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_type_len() {
+        assert_eq!(8, type_len(INT8_OID));
+        assert_eq!(-1, type_len(VARCHAR_OID));
+        assert_eq!(-1, type_len(0));
+    }
+
+    #[test]
+    fn test_make_types() {
+        let pg_type = PgType::new();
+        let batch = PgType::make_types(pg_type.schema()).unwrap();
+        assert_eq!(TYPES.len(), batch.num_rows());
+        assert_eq!(17, batch.num_columns());
+    }
+}
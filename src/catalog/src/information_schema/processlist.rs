@@ -0,0 +1,187 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use arrow_schema::SchemaRef as ArrowSchemaRef;
+use common_error::ext::BoxedError;
+use common_query::physical_plan::TaskContext;
+use common_recordbatch::adapter::RecordBatchStreamAdapter;
+use common_recordbatch::{RecordBatch, SendableRecordBatchStream};
+use datafusion::datasource::streaming::PartitionStream as DfPartitionStream;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter as DfRecordBatchStreamAdapter;
+use datafusion::physical_plan::SendableRecordBatchStream as DfSendableRecordBatchStream;
+use datatypes::prelude::{ConcreteDataType, ScalarVectorBuilder, VectorRef};
+use datatypes::schema::{ColumnSchema, Schema, SchemaRef};
+use datatypes::vectors::{
+    StringVectorBuilder, TimestampMillisecondVectorBuilder, UInt32VectorBuilder,
+};
+use session::process::{ProcessInfo, ProcessManager};
+use snafu::ResultExt;
+
+use crate::error::{CreateRecordBatchSnafu, InternalSnafu, Result};
+use crate::information_schema::InformationStreamBuilder;
+
+pub(super) struct InformationSchemaProcesslist {
+    schema: SchemaRef,
+    catalog_name: String,
+}
+
+impl InformationSchemaProcesslist {
+    pub(super) fn new(catalog_name: String) -> Self {
+        let schema = Arc::new(Schema::new(vec![
+            ColumnSchema::new("id", ConcreteDataType::uint32_datatype(), false),
+            ColumnSchema::new("user", ConcreteDataType::string_datatype(), true),
+            ColumnSchema::new("host", ConcreteDataType::string_datatype(), true),
+            ColumnSchema::new("catalog", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("db", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("state", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new(
+                "start_time",
+                ConcreteDataType::timestamp_millisecond_datatype(),
+                false,
+            ),
+            ColumnSchema::new("info", ConcreteDataType::string_datatype(), false),
+        ]));
+        Self {
+            schema,
+            catalog_name,
+        }
+    }
+
+    fn builder(&self) -> InformationSchemaProcesslistBuilder {
+        InformationSchemaProcesslistBuilder::new(self.schema.clone(), self.catalog_name.clone())
+    }
+}
+
+impl InformationStreamBuilder for InformationSchemaProcesslist {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn to_stream(&self) -> Result<SendableRecordBatchStream> {
+        let schema = self.schema.arrow_schema().clone();
+        let mut builder = self.builder();
+        let stream = Box::pin(DfRecordBatchStreamAdapter::new(
+            schema,
+            futures::stream::once(async move {
+                builder
+                    .make_processlist()
+                    .map(|x| x.into_df_record_batch())
+                    .map_err(Into::into)
+            }),
+        ));
+        Ok(Box::pin(
+            RecordBatchStreamAdapter::try_new(stream)
+                .map_err(BoxedError::new)
+                .context(InternalSnafu)?,
+        ))
+    }
+}
+
+/// Builds the `information_schema.processlist` table from the queries running in
+/// this process.
+struct InformationSchemaProcesslistBuilder {
+    schema: SchemaRef,
+    catalog_name: String,
+
+    ids: UInt32VectorBuilder,
+    users: StringVectorBuilder,
+    hosts: StringVectorBuilder,
+    catalog_names: StringVectorBuilder,
+    schema_names: StringVectorBuilder,
+    states: StringVectorBuilder,
+    start_times: TimestampMillisecondVectorBuilder,
+    queries: StringVectorBuilder,
+}
+
+impl InformationSchemaProcesslistBuilder {
+    fn new(schema: SchemaRef, catalog_name: String) -> Self {
+        Self {
+            schema,
+            catalog_name,
+            ids: UInt32VectorBuilder::with_capacity(42),
+            users: StringVectorBuilder::with_capacity(42),
+            hosts: StringVectorBuilder::with_capacity(42),
+            catalog_names: StringVectorBuilder::with_capacity(42),
+            schema_names: StringVectorBuilder::with_capacity(42),
+            states: StringVectorBuilder::with_capacity(42),
+            start_times: TimestampMillisecondVectorBuilder::with_capacity(42),
+            queries: StringVectorBuilder::with_capacity(42),
+        }
+    }
+
+    /// Construct the `information_schema.processlist` virtual table
+    fn make_processlist(&mut self) -> Result<RecordBatch> {
+        for process in ProcessManager::global().list() {
+            // Queries of other catalogs are invisible.
+            if process.catalog() == self.catalog_name {
+                self.add_process(&process);
+            }
+        }
+
+        self.finish()
+    }
+
+    fn add_process(&mut self, process: &ProcessInfo) {
+        let state = if process.is_cancelled() {
+            "Killed".to_string()
+        } else {
+            process.stage().to_string()
+        };
+
+        self.ids.push(Some(process.id()));
+        self.users.push(process.user());
+        self.hosts.push(process.client());
+        self.catalog_names.push(Some(process.catalog()));
+        self.schema_names.push(Some(process.schema()));
+        self.states.push(Some(&state));
+        self.start_times.push(Some(process.start_time_ms().into()));
+        self.queries.push(Some(process.query()));
+    }
+
+    fn finish(&mut self) -> Result<RecordBatch> {
+        let columns: Vec<VectorRef> = vec![
+            Arc::new(self.ids.finish()),
+            Arc::new(self.users.finish()),
+            Arc::new(self.hosts.finish()),
+            Arc::new(self.catalog_names.finish()),
+            Arc::new(self.schema_names.finish()),
+            Arc::new(self.states.finish()),
+            Arc::new(self.start_times.finish()),
+            Arc::new(self.queries.finish()),
+        ];
+        RecordBatch::new(self.schema.clone(), columns).context(CreateRecordBatchSnafu)
+    }
+}
+
+impl DfPartitionStream for InformationSchemaProcesslist {
+    fn schema(&self) -> &ArrowSchemaRef {
+        self.schema.arrow_schema()
+    }
+
+    fn execute(&self, _: Arc<TaskContext>) -> DfSendableRecordBatchStream {
+        let schema = self.schema.arrow_schema().clone();
+        let mut builder = self.builder();
+        Box::pin(DfRecordBatchStreamAdapter::new(
+            schema,
+            futures::stream::once(async move {
+                builder
+                    .make_processlist()
+                    .map(|x| x.into_df_record_batch())
+                    .map_err(Into::into)
+            }),
+        ))
+    }
+}
@@ -18,7 +18,7 @@ use std::sync::Arc;
 
 use common_catalog::consts::{
     DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, INFORMATION_SCHEMA_NAME, MIN_USER_TABLE_ID,
-    MITO_ENGINE, NUMBERS_TABLE_ID, SYSTEM_CATALOG_NAME, SYSTEM_CATALOG_TABLE_ID,
+    MITO_ENGINE, NUMBERS_TABLE_ID, PG_CATALOG_NAME, SYSTEM_CATALOG_NAME, SYSTEM_CATALOG_TABLE_ID,
     SYSTEM_CATALOG_TABLE_NAME,
 };
 use common_catalog::format_full_table_name;
@@ -43,7 +43,7 @@ use crate::error::{
     SystemCatalogTypeMismatchSnafu, TableEngineNotFoundSnafu, TableExistsSnafu, TableNotExistSnafu,
     TableNotFoundSnafu, UnimplementedSnafu,
 };
-use crate::information_schema::InformationSchemaProvider;
+use crate::information_schema::{InformationSchemaProvider, PgCatalogProvider};
 use crate::local::memory::MemoryCatalogManager;
 use crate::system::{
     decode_system_catalog, Entry, SystemCatalogTable, TableEntry, ENTRY_TYPE_INDEX, KEY_INDEX,
@@ -554,6 +554,12 @@ impl CatalogManager for LocalCatalogManager {
                 InformationSchemaProvider::new(catalog_name.to_string(), Arc::downgrade(&manager));
             return provider.table(table_name);
         }
+        if schema_name == PG_CATALOG_NAME {
+            let manager: CatalogManagerRef = self.catalogs.clone() as _;
+            let provider =
+                PgCatalogProvider::new(catalog_name.to_string(), Arc::downgrade(&manager));
+            return provider.table(table_name);
+        }
 
         self.catalogs
             .table(catalog_name, schema_name, table_name)
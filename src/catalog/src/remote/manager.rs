@@ -16,7 +16,7 @@ use std::any::Any;
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use common_catalog::consts::MITO_ENGINE;
+use common_catalog::consts::{INFORMATION_SCHEMA_NAME, MITO_ENGINE};
 use common_meta::helper::{
     build_catalog_prefix, build_schema_prefix, build_table_global_prefix, CatalogKey, SchemaKey,
     TableGlobalKey, TableGlobalValue, TableRegionalKey, TableRegionalValue,
@@ -40,11 +40,13 @@ use crate::error::{
     TableEngineNotFoundSnafu, TableExistsSnafu, TableMetadataManagerSnafu, TableNotFoundSnafu,
     UnimplementedSnafu,
 };
+use crate::information_schema::InformationSchemaProvider;
 use crate::local::MemoryCatalogManager;
 use crate::remote::region_alive_keeper::RegionAliveKeepers;
 use crate::{
-    handle_system_table_request, CatalogManager, DeregisterSchemaRequest, DeregisterTableRequest,
-    RegisterSchemaRequest, RegisterSystemTableRequest, RegisterTableRequest, RenameTableRequest,
+    handle_system_table_request, CatalogManager, CatalogManagerRef, DeregisterSchemaRequest,
+    DeregisterTableRequest, RegisterSchemaRequest, RegisterSystemTableRequest,
+    RegisterTableRequest, RenameTableRequest,
 };
 
 /// Catalog manager based on metasrv.
@@ -633,6 +635,15 @@ impl CatalogManager for RemoteCatalogManager {
         schema_name: &str,
         table_name: &str,
     ) -> Result<Option<TableRef>> {
+        if schema_name == INFORMATION_SCHEMA_NAME {
+            // Lists the tables opened in this node, e.g. the frontend reads committed
+            // sequences of regions from `information_schema.region_statistics`.
+            let manager: CatalogManagerRef = self.memory_catalog_manager.clone() as _;
+            let provider =
+                InformationSchemaProvider::new(catalog_name.to_string(), Arc::downgrade(&manager));
+            return provider.table(table_name);
+        }
+
         self.memory_catalog_manager
             .table(catalog_name, schema_name, table_name)
             .await
@@ -15,7 +15,7 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use common_catalog::consts::INFORMATION_SCHEMA_NAME;
+use common_catalog::consts::{INFORMATION_SCHEMA_NAME, PG_CATALOG_NAME, PRIVATE_SCHEMA_NAME};
 use common_catalog::format_full_table_name;
 use datafusion::common::{ResolvedTableReference, TableReference};
 use datafusion::datasource::provider_as_source;
@@ -54,13 +54,27 @@ impl DfTableSourceProvider {
         &'a self,
         table_ref: TableReference<'a>,
     ) -> Result<ResolvedTableReference<'a>> {
+        // Tables of the private schema, e.g. the slow query log, are only
+        // accessible from sessions using the schema.
+        if let TableReference::Partial { schema, .. } | TableReference::Full { schema, .. } =
+            &table_ref
+        {
+            ensure!(
+                schema.as_ref() != PRIVATE_SCHEMA_NAME
+                    || self.default_schema == PRIVATE_SCHEMA_NAME,
+                QueryAccessDeniedSnafu {
+                    catalog: &self.default_catalog,
+                    schema: schema.as_ref(),
+                }
+            );
+        }
+
         if self.disallow_cross_schema_query {
             match &table_ref {
                 TableReference::Bare { .. } => (),
                 TableReference::Partial { schema, .. } => {
                     ensure!(
-                        schema.as_ref() == self.default_schema
-                            || schema.as_ref() == INFORMATION_SCHEMA_NAME,
+                        schema.as_ref() == self.default_schema || is_system_schema(schema.as_ref()),
                         QueryAccessDeniedSnafu {
                             catalog: &self.default_catalog,
                             schema: schema.as_ref(),
@@ -73,7 +87,7 @@ impl DfTableSourceProvider {
                     ensure!(
                         catalog.as_ref() == self.default_catalog
                             && (schema.as_ref() == self.default_schema
-                                || schema.as_ref() == INFORMATION_SCHEMA_NAME),
+                                || is_system_schema(schema.as_ref())),
                         QueryAccessDeniedSnafu {
                             catalog: catalog.as_ref(),
                             schema: schema.as_ref()
@@ -116,6 +130,11 @@ impl DfTableSourceProvider {
     }
 }
 
+/// Schemas that can be queried from any other schema of the same catalog.
+fn is_system_schema(schema: &str) -> bool {
+    schema == INFORMATION_SCHEMA_NAME || schema == PG_CATALOG_NAME
+}
+
 #[cfg(test)]
 mod tests {
     use std::borrow::Cow;
@@ -187,5 +206,35 @@ mod tests {
             table: Cow::Borrowed("columns"),
         };
         assert!(table_provider.resolve_table_ref(table_ref).is_err());
+
+        let table_ref = TableReference::Partial {
+            schema: Cow::Borrowed("pg_catalog"),
+            table: Cow::Borrowed("pg_class"),
+        };
+        let _ = table_provider.resolve_table_ref(table_ref).unwrap();
+    }
+
+    #[test]
+    fn test_validate_private_schema_ref() {
+        let private_ref = || TableReference::Partial {
+            schema: Cow::Borrowed(PRIVATE_SCHEMA_NAME),
+            table: Cow::Borrowed("slow_queries"),
+        };
+
+        let query_ctx = &QueryContext::with("greptime", "public");
+        let table_provider =
+            DfTableSourceProvider::new(Arc::new(MemoryCatalogManager::default()), false, query_ctx);
+        assert!(table_provider.resolve_table_ref(private_ref()).is_err());
+        let table_ref = TableReference::Full {
+            catalog: Cow::Borrowed("greptime"),
+            schema: Cow::Borrowed(PRIVATE_SCHEMA_NAME),
+            table: Cow::Borrowed("slow_queries"),
+        };
+        assert!(table_provider.resolve_table_ref(table_ref).is_err());
+
+        let query_ctx = &QueryContext::with("greptime", PRIVATE_SCHEMA_NAME);
+        let table_provider =
+            DfTableSourceProvider::new(Arc::new(MemoryCatalogManager::default()), true, query_ctx);
+        let _ = table_provider.resolve_table_ref(private_ref()).unwrap();
     }
 }
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::pin::Pin;
+
 use api::v1::auth_header::AuthScheme;
 use api::v1::ddl_request::Expr as DdlExpr;
 use api::v1::greptime_request::Request;
@@ -22,13 +24,20 @@ use api::v1::{
     PromRangeQuery, QueryRequest, RequestHeader,
 };
 use arrow_flight::{FlightData, Ticket};
+use async_stream::try_stream;
 use common_error::ext::{BoxedError, ErrorExt};
 use common_grpc::flight::{flight_messages_to_recordbatches, FlightDecoder, FlightMessage};
 use common_query::Output;
+use common_recordbatch::error::{ExternalSnafu, Result as RecordBatchResult};
+use common_recordbatch::{
+    RecordBatch, RecordBatchMetrics, RecordBatchStreamAdaptor, RecordBatches,
+    SharedRecordBatchMetrics,
+};
 use common_telemetry::{logging, timer};
-use futures_util::{TryFutureExt, TryStreamExt};
+use futures_util::{Stream, TryStreamExt};
 use prost::Message;
 use snafu::{ensure, OptionExt, ResultExt};
+use tonic::{Status, Streaming};
 
 use crate::error::{
     ConvertFlightDataSnafu, IllegalDatabaseResponseSnafu, IllegalFlightMessagesSnafu,
@@ -177,9 +186,11 @@ impl Database {
         .await
     }
 
+    /// Executes the substrait logical plan. Record batches of the result are streamed
+    /// back as the server produces them.
     pub async fn logical_plan(&self, logical_plan: Vec<u8>) -> Result<Output> {
         let _timer = timer!(metrics::METRIC_GRPC_LOGICAL_PLAN);
-        self.do_get(Request::Query(QueryRequest {
+        self.do_get_stream(Request::Query(QueryRequest {
             query: Some(Query::LogicalPlan(logical_plan)),
         }))
         .await
@@ -247,41 +258,12 @@ impl Database {
     async fn do_get(&self, request: Request) -> Result<Output> {
         // FIXME(paomian): should be added some labels for metrics
         let _timer = timer!(metrics::METRIC_GRPC_DO_GET);
-        let request = self.to_rpc_request(request);
-        let request = Ticket {
-            ticket: request.encode_to_vec().into(),
-        };
+        let (flight_data, addr) = self.flight_data_stream(request).await?;
 
-        let mut client = self.client.make_flight_client()?;
-
-        let flight_data: Vec<FlightData> = client
-            .mut_inner()
-            .do_get(request)
-            .and_then(|response| response.into_inner().try_collect())
+        let flight_data: Vec<FlightData> = flight_data
+            .try_collect()
             .await
-            .map_err(|e| {
-                let tonic_code = e.code();
-                let e: error::Error = e.into();
-                let code = e.status_code();
-                let msg = e.to_string();
-                error::ServerSnafu { code, msg }
-                    .fail::<()>()
-                    .map_err(BoxedError::new)
-                    .context(error::FlightGetSnafu {
-                        tonic_code,
-                        addr: client.addr(),
-                    })
-                    .map_err(|error| {
-                        logging::error!(
-                            "Failed to do Flight get, addr: {}, code: {}, source: {}",
-                            client.addr(),
-                            tonic_code,
-                            error
-                        );
-                        error
-                    })
-                    .unwrap_err()
-            })?;
+            .map_err(|e| flight_get_error(e, &addr))?;
 
         let decoder = &mut FlightDecoder::default();
         let flight_messages = flight_data
@@ -304,6 +286,134 @@ impl Database {
         };
         Ok(output)
     }
+
+    /// Like [Database::do_get], but returns query results as an [Output::Stream] that
+    /// decodes the record batches as they arrive. The Flight data is only read from the
+    /// server when the returned stream is polled, so a slow consumer holds the server
+    /// back, and dropping the stream cancels the request. The metrics the server sends after
+    /// the record batches are reported by the stream once it's exhausted.
+    async fn do_get_stream(&self, request: Request) -> Result<Output> {
+        // The timer is moved into the stream, so reading the whole result is timed.
+        let timer = timer!(metrics::METRIC_GRPC_DO_GET);
+        let (mut flight_data, addr) = self.flight_data_stream(request).await?;
+
+        let mut decoder = FlightDecoder::default();
+        let Some(first) = flight_data
+            .message()
+            .await
+            .map_err(|e| flight_get_error(e, &addr))? else {
+            return Ok(Output::RecordBatches(RecordBatches::empty()));
+        };
+        let schema = match decoder.try_decode(first).context(ConvertFlightDataSnafu)? {
+            FlightMessage::Schema(schema) => schema,
+            FlightMessage::AffectedRows(rows) => {
+                let next = flight_data
+                    .message()
+                    .await
+                    .map_err(|e| flight_get_error(e, &addr))?;
+                ensure!(
+                    next.is_none(),
+                    IllegalFlightMessagesSnafu {
+                        reason: "Expect 'AffectedRows' Flight messages to be one and only!"
+                    }
+                );
+                return Ok(Output::AffectedRows(rows));
+            }
+            FlightMessage::Recordbatch(_) | FlightMessage::Metrics(_) => {
+                return IllegalFlightMessagesSnafu {
+                    reason: "First Flight Message must be schema!",
+                }
+                .fail()
+            }
+        };
+
+        let metrics = SharedRecordBatchMetrics::default();
+        let stream_metrics = metrics.clone();
+
+        let stream: Pin<Box<dyn Stream<Item = RecordBatchResult<RecordBatch>> + Send>> = Box::pin(
+            try_stream! {
+                let _timer = timer;
+                while let Some(data) = flight_data
+                    .message()
+                    .await
+                    .map_err(|e| flight_get_error(e, &addr))
+                    .map_err(BoxedError::new)
+                    .context(ExternalSnafu)?
+                {
+                    let message = decoder
+                        .try_decode(data)
+                        .context(ConvertFlightDataSnafu)
+                        .map_err(BoxedError::new)
+                        .context(ExternalSnafu)?;
+                    match message {
+                        FlightMessage::Recordbatch(recordbatch) => yield recordbatch,
+                        FlightMessage::Metrics(s) => match serde_json::from_str::<RecordBatchMetrics>(&s) {
+                            Ok(m) => *stream_metrics.lock().unwrap() = m,
+                            Err(e) => logging::warn!("Failed to decode metrics, addr: {addr}, err: {e}"),
+                        },
+                        _ => {
+                            Err(BoxedError::new(
+                                IllegalFlightMessagesSnafu {
+                                    reason: "Expect the following Flight Messages are all Recordbatches!",
+                                }
+                                .build(),
+                            ))
+                            .context(ExternalSnafu)?;
+                        }
+                    }
+                }
+            },
+        );
+        Ok(Output::Stream(Box::pin(RecordBatchStreamAdaptor {
+            schema,
+            stream,
+            output_ordering: None,
+            metrics: Some(metrics),
+        })))
+    }
+
+    /// Sends the request by Flight "do_get", returns the response stream and the
+    /// address of the server.
+    async fn flight_data_stream(
+        &self,
+        request: Request,
+    ) -> Result<(Streaming<FlightData>, String)> {
+        let request = self.to_rpc_request(request);
+        let request = Ticket {
+            ticket: request.encode_to_vec().into(),
+        };
+
+        let mut client = self.client.make_flight_client()?;
+        let addr = client.addr().to_string();
+        let response = client
+            .mut_inner()
+            .do_get(request)
+            .await
+            .map_err(|e| flight_get_error(e, &addr))?;
+        Ok((response.into_inner(), addr))
+    }
+}
+
+fn flight_get_error(e: Status, addr: &str) -> error::Error {
+    let tonic_code = e.code();
+    let e: error::Error = e.into();
+    let code = e.status_code();
+    let msg = e.to_string();
+    let error = error::ServerSnafu { code, msg }
+        .fail::<()>()
+        .map_err(BoxedError::new)
+        .context(error::FlightGetSnafu {
+            tonic_code,
+            addr: addr.to_string(),
+        })
+        .unwrap_err();
+    logging::error!(
+        "Failed to do Flight get, addr: {}, code: {}, source: {}",
+        addr,
+        tonic_code,
+        error
+    );
+    error
 }
 
 #[derive(Default, Debug, Clone)]
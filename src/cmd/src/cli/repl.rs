@@ -34,6 +34,7 @@ use query::datafusion::DatafusionQueryEngine;
 use query::logical_optimizer::LogicalOptimizer;
 use query::parser::QueryLanguageParser;
 use query::plan::LogicalPlan;
+use query::query_engine::options::QueryConfig;
 use query::query_engine::QueryEngineState;
 use query::QueryEngine;
 use rustyline::error::ReadlineError;
@@ -276,6 +277,7 @@ async fn create_query_engine(meta_addr: &str) -> Result<DatafusionQueryEngine> {
         None,
         None,
         plugins.clone(),
+        &QueryConfig::default(),
     ));
 
     Ok(DatafusionQueryEngine::new(state, plugins))
@@ -22,7 +22,7 @@ use servers::Mode;
 use snafu::ResultExt;
 
 use crate::error::{MissingConfigSnafu, Result, ShutdownDatanodeSnafu, StartDatanodeSnafu};
-use crate::options::{Options, TopLevelOptions};
+use crate::options::{check_spill_dir, Options, TopLevelOptions};
 
 pub struct Instance {
     datanode: Datanode,
@@ -170,6 +170,8 @@ impl StartCommand {
         logging::info!("Datanode start command: {:#?}", self);
         logging::info!("Datanode options: {:#?}", opts);
 
+        check_spill_dir(&opts.query)?;
+
         let datanode = Datanode::new(opts, Default::default())
             .await
             .context(StartDatanodeSnafu)?;
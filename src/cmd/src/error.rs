@@ -71,6 +71,13 @@ pub enum Error {
     #[snafu(display("Illegal config: {}", msg))]
     IllegalConfig { msg: String, location: Location },
 
+    #[snafu(display("Invalid spill dir {}, source: {}", dir, source))]
+    InvalidSpillDir {
+        dir: String,
+        location: Location,
+        source: std::io::Error,
+    },
+
     #[snafu(display("Illegal auth config: {}", source))]
     IllegalAuthConfig {
         location: Location,
@@ -172,6 +179,7 @@ impl ErrorExt for Error {
             Error::MissingConfig { .. }
             | Error::LoadLayeredConfig { .. }
             | Error::IllegalConfig { .. }
+            | Error::InvalidSpillDir { .. }
             | Error::InvalidReplCommand { .. }
             | Error::IllegalAuthConfig { .. } => StatusCode::InvalidArguments,
             Error::ReplCreation { .. } | Error::Readline { .. } => StatusCode::Internal,
@@ -27,7 +27,7 @@ use servers::{auth, Mode};
 use snafu::ResultExt;
 
 use crate::error::{self, IllegalAuthConfigSnafu, Result, StartCatalogManagerSnafu};
-use crate::options::{Options, TopLevelOptions};
+use crate::options::{check_spill_dir, Options, TopLevelOptions};
 
 pub struct Instance {
     frontend: FeInstance,
@@ -206,6 +206,8 @@ impl StartCommand {
         logging::info!("Frontend start command: {:#?}", self);
         logging::info!("Frontend options: {:#?}", opts);
 
+        check_spill_dir(&opts.query)?;
+
         let plugins = Arc::new(load_frontend_plugins(&self.user_provider)?);
 
         let mut instance = FeInstance::try_new_distributed(&opts, plugins.clone())
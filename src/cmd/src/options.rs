@@ -17,10 +17,11 @@ use config::{Config, Environment, File, FileFormat};
 use datanode::datanode::DatanodeOptions;
 use frontend::frontend::FrontendOptions;
 use meta_srv::metasrv::MetaSrvOptions;
+use query::query_engine::options::QueryConfig;
 use serde::{Deserialize, Serialize};
 use snafu::ResultExt;
 
-use crate::error::{LoadLayeredConfigSnafu, Result};
+use crate::error::{InvalidSpillDirSnafu, LoadLayeredConfigSnafu, Result};
 
 pub const ENV_VAR_SEP: &str = "__";
 pub const ENV_LIST_SEP: &str = ",";
@@ -45,6 +46,16 @@ pub struct TopLevelOptions {
     pub log_level: Option<String>,
 }
 
+/// Fails startup if the spill dir of the query engine can't be used, instead
+/// of spilling somewhere else.
+pub fn check_spill_dir(config: &QueryConfig) -> Result<()> {
+    config
+        .check_spill_dir()
+        .with_context(|_| InvalidSpillDirSnafu {
+            dir: config.spill_dir.clone().unwrap_or_default(),
+        })
+}
+
 impl Options {
     pub fn logging_options(&self) -> &LoggingOptions {
         match self {
@@ -18,14 +18,18 @@ use clap::Parser;
 use common_base::Plugins;
 use common_telemetry::info;
 use common_telemetry::logging::LoggingOptions;
-use datanode::datanode::{Datanode, DatanodeOptions, ProcedureConfig, StorageConfig, WalConfig};
+use datanode::datanode::{
+    Datanode, DatanodeOptions, ProcedureConfig, QueryConfig, StorageConfig, WalConfig,
+};
 use datanode::instance::InstanceRef;
 use frontend::frontend::FrontendOptions;
 use frontend::instance::{FrontendInstance, Instance as FeInstance};
+use frontend::result_cache::ResultCacheOptions;
 use frontend::service_config::{
-    GrpcOptions, InfluxdbOptions, MysqlOptions, OpentsdbOptions, PostgresOptions, PromStoreOptions,
-    PrometheusOptions,
+    ElasticsearchOptions, GrpcOptions, InfluxdbOptions, LokiOptions, MysqlOptions, OpentsdbOptions,
+    OtlpOptions, PostgresOptions, PromStoreOptions, PrometheusOptions,
 };
+use frontend::slow_query::SlowQueryOptions;
 use serde::{Deserialize, Serialize};
 use servers::http::HttpOptions;
 use servers::tls::{TlsMode, TlsOption};
@@ -37,7 +41,7 @@ use crate::error::{
     StartFrontendSnafu,
 };
 use crate::frontend::load_frontend_plugins;
-use crate::options::{MixOptions, Options, TopLevelOptions};
+use crate::options::{check_spill_dir, MixOptions, Options, TopLevelOptions};
 
 #[derive(Parser)]
 pub struct Command {
@@ -90,10 +94,16 @@ pub struct StandaloneOptions {
     pub opentsdb_options: Option<OpentsdbOptions>,
     pub influxdb_options: Option<InfluxdbOptions>,
     pub prom_store_options: Option<PromStoreOptions>,
+    pub otlp_options: Option<OtlpOptions>,
+    pub loki_options: Option<LokiOptions>,
+    pub elasticsearch_options: Option<ElasticsearchOptions>,
     pub prometheus_options: Option<PrometheusOptions>,
     pub wal: WalConfig,
     pub storage: StorageConfig,
     pub procedure: ProcedureConfig,
+    pub query: QueryConfig,
+    pub slow_query: SlowQueryOptions,
+    pub result_cache: ResultCacheOptions,
     pub logging: LoggingOptions,
 }
 
@@ -109,10 +119,16 @@ impl Default for StandaloneOptions {
             opentsdb_options: Some(OpentsdbOptions::default()),
             influxdb_options: Some(InfluxdbOptions::default()),
             prom_store_options: Some(PromStoreOptions::default()),
+            otlp_options: Some(OtlpOptions::default()),
+            loki_options: Some(LokiOptions::default()),
+            elasticsearch_options: Some(ElasticsearchOptions::default()),
             prometheus_options: Some(PrometheusOptions::default()),
             wal: WalConfig::default(),
             storage: StorageConfig::default(),
             procedure: ProcedureConfig::default(),
+            query: QueryConfig::default(),
+            slow_query: SlowQueryOptions::default(),
+            result_cache: ResultCacheOptions::default(),
             logging: LoggingOptions::default(),
         }
     }
@@ -129,8 +145,13 @@ impl StandaloneOptions {
             opentsdb_options: self.opentsdb_options,
             influxdb_options: self.influxdb_options,
             prom_store_options: self.prom_store_options,
+            otlp_options: self.otlp_options,
+            loki_options: self.loki_options,
+            elasticsearch_options: self.elasticsearch_options,
             prometheus_options: self.prometheus_options,
             meta_client_options: None,
+            slow_query: self.slow_query,
+            result_cache: self.result_cache,
             logging: self.logging,
             ..Default::default()
         }
@@ -142,6 +163,7 @@ impl StandaloneOptions {
             wal: self.wal,
             storage: self.storage,
             procedure: self.procedure,
+            query: self.query,
             ..Default::default()
         }
     }
@@ -309,6 +331,8 @@ impl StartCommand {
             fe_opts, dn_opts
         );
 
+        check_spill_dir(&dn_opts.query)?;
+
         let datanode = Datanode::new(dn_opts.clone(), Default::default())
             .await
             .context(StartDatanodeSnafu)?;
@@ -37,6 +37,11 @@ pub struct LazyBufferedWriter<T, U, F> {
 
 pub trait DfRecordBatchEncoder {
     fn write(&mut self, batch: &RecordBatch) -> Result<()>;
+
+    /// Writes the trailing data of the format after all batches are written.
+    fn finish(&mut self) -> Result<()> {
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -117,6 +122,14 @@ impl<
         Ok(())
     }
 
+    /// Finishes the encoder, the buffer then contains all encoded data.
+    pub fn finish_encoder(&mut self) -> Result<()> {
+        self.encoder
+            .as_mut()
+            .context(error::BufferedWriterClosedSnafu)?
+            .finish()
+    }
+
     pub async fn try_flush(&mut self, all: bool) -> Result<u64> {
         let mut bytes_written: u64 = 0;
 
@@ -146,6 +146,62 @@ pub enum Error {
         location: Location,
     },
 
+    #[snafu(display("Failed to infer avro schema, source: {}", source))]
+    InferAvroSchema {
+        location: Location,
+        source: datafusion::error::DataFusionError,
+    },
+
+    #[snafu(display("Failed to encode avro, source: {}", source))]
+    EncodeAvro {
+        location: Location,
+        source: apache_avro::Error,
+    },
+
+    #[snafu(display("Unsupported data type for avro: {}", data_type))]
+    UnsupportedAvroType {
+        data_type: String,
+        location: Location,
+    },
+
+    #[snafu(display("UInt64 value {} is out of the range of avro long", value))]
+    AvroLongOverflow { value: u64, location: Location },
+
+    #[snafu(display("Unsupported data type for orc: {}", data_type))]
+    UnsupportedOrcType {
+        data_type: String,
+        location: Location,
+    },
+
+    #[snafu(display("UInt64 value {} is out of the range of orc long", value))]
+    OrcLongOverflow { value: u64, location: Location },
+
+    #[snafu(display("Failed to write orc, source: {}", source))]
+    WriteOrc {
+        location: Location,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("Null value in non-nullable column: {}", column))]
+    NullInNonNullableColumn { column: String, location: Location },
+
+    #[snafu(display(
+        "Partitions of file {} are inconsistent with columns: {}",
+        path,
+        columns
+    ))]
+    InconsistentPartitions {
+        path: String,
+        columns: String,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to prune partitions: {}", source))]
+    PrunePartitions {
+        location: Location,
+        source: datafusion::error::DataFusionError,
+    },
+
     #[snafu(display("Buffered writer closed"))]
     BufferedWriterClosed { location: Location },
 }
@@ -172,13 +228,23 @@ impl ErrorExt for Error {
             | ReadParquetSnafu { .. }
             | ParquetToSchema { .. }
             | ParseFormat { .. }
-            | MergeSchema { .. } => StatusCode::InvalidArguments,
+            | MergeSchema { .. }
+            | InferAvroSchema { .. }
+            | UnsupportedAvroType { .. }
+            | AvroLongOverflow { .. }
+            | UnsupportedOrcType { .. }
+            | OrcLongOverflow { .. }
+            | InconsistentPartitions { .. } => StatusCode::InvalidArguments,
 
             JoinHandle { .. }
             | ReadRecordBatch { .. }
             | WriteRecordBatch { .. }
             | EncodeRecordBatch { .. }
             | BufferedWriterClosed { .. }
+            | EncodeAvro { .. }
+            | WriteOrc { .. }
+            | NullInNonNullableColumn { .. }
+            | PrunePartitions { .. }
             | OrcReader { .. } => StatusCode::Unexpected,
         }
     }
@@ -206,6 +272,16 @@ impl ErrorExt for Error {
             AsyncWrite { location, .. } => Some(*location),
             EncodeRecordBatch { location, .. } => Some(*location),
             BufferedWriterClosed { location, .. } => Some(*location),
+            InferAvroSchema { location, .. } => Some(*location),
+            EncodeAvro { location, .. } => Some(*location),
+            UnsupportedAvroType { location, .. } => Some(*location),
+            AvroLongOverflow { location, .. } => Some(*location),
+            UnsupportedOrcType { location, .. } => Some(*location),
+            OrcLongOverflow { location, .. } => Some(*location),
+            WriteOrc { location, .. } => Some(*location),
+            NullInNonNullableColumn { location, .. } => Some(*location),
+            InconsistentPartitions { location, .. } => Some(*location),
+            PrunePartitions { location, .. } => Some(*location),
 
             UnsupportedBackendProtocol { location, .. } => Some(*location),
             EmptyHostPath { location, .. } => Some(*location),
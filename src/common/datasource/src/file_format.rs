@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod arrow_ipc;
+pub mod avro;
 pub mod csv;
 pub mod json;
 pub mod orc;
@@ -22,12 +24,15 @@ pub mod tests;
 pub const DEFAULT_SCHEMA_INFER_MAX_RECORD: usize = 1000;
 
 use std::collections::HashMap;
+use std::io::{BufReader, Read};
 use std::result;
 use std::sync::Arc;
 use std::task::Poll;
 
+use arrow::array::new_null_array;
+use arrow::compute::cast;
 use arrow::record_batch::RecordBatch;
-use arrow_schema::{ArrowError, Schema as ArrowSchema};
+use arrow_schema::{ArrowError, Schema as ArrowSchema, SchemaRef};
 use async_trait::async_trait;
 use bytes::{Buf, Bytes};
 use datafusion::error::{DataFusionError, Result as DataFusionResult};
@@ -36,7 +41,10 @@ use datafusion::physical_plan::SendableRecordBatchStream;
 use futures::StreamExt;
 use object_store::ObjectStore;
 use snafu::ResultExt;
+use tokio_util::io::SyncIoBridge;
 
+use self::arrow_ipc::{ArrowIpcFormat, IpcKind};
+use self::avro::AvroFormat;
 use self::csv::CsvFormat;
 use self::json::JsonFormat;
 use self::orc::OrcFormat;
@@ -59,6 +67,8 @@ pub enum Format {
     Json(JsonFormat),
     Parquet(ParquetFormat),
     Orc(OrcFormat),
+    Avro(AvroFormat),
+    ArrowIpc(ArrowIpcFormat),
 }
 
 impl Format {
@@ -68,6 +78,11 @@ impl Format {
             Format::Json(_) => ".json",
             Format::Parquet(_) => ".parquet",
             &Format::Orc(_) => ".orc",
+            Format::Avro(_) => ".avro",
+            Format::ArrowIpc(format) => match format.kind {
+                IpcKind::File => ".arrow",
+                IpcKind::Stream => ".arrows",
+            },
         }
     }
 }
@@ -86,6 +101,9 @@ impl TryFrom<&HashMap<String, String>> for Format {
             "JSON" => Ok(Self::Json(JsonFormat::try_from(options)?)),
             "PARQUET" => Ok(Self::Parquet(ParquetFormat::default())),
             "ORC" => Ok(Self::Orc(OrcFormat)),
+            "AVRO" => Ok(Self::Avro(AvroFormat)),
+            "ARROW" => Ok(Self::ArrowIpc(ArrowIpcFormat::new(IpcKind::File))),
+            "ARROW_STREAM" => Ok(Self::ArrowIpc(ArrowIpcFormat::new(IpcKind::Stream))),
             _ => error::UnsupportedFormatSnafu { format: &format }.fail(),
         }
     }
@@ -176,6 +194,77 @@ pub fn open_with_decoder<T: ArrowDecoder, F: Fn() -> DataFusionResult<T>>(
     }))
 }
 
+/// Opens a file with a blocking reader, e.g. readers of Arrow IPC and Avro files.
+///
+/// The reader runs in the blocking thread pool, and the batches it reads are projected
+/// to `projected_schema`.
+pub fn open_with_blocking_reader<I, F>(
+    object_store: Arc<ObjectStore>,
+    path: String,
+    projected_schema: SchemaRef,
+    reader_factory: F,
+) -> DataFusionResult<FileOpenFuture>
+where
+    I: Iterator<Item = result::Result<RecordBatch, ArrowError>>,
+    F: FnOnce(Box<dyn Read + Send>) -> result::Result<I, ArrowError> + Send + 'static,
+{
+    Ok(Box::pin(async move {
+        let reader = object_store
+            .reader(&path)
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+        let (tx, rx) = tokio::sync::mpsc::channel(1);
+        let _handle = common_runtime::spawn_blocking_read(move || {
+            let reader = Box::new(BufReader::new(SyncIoBridge::new(reader)));
+            let batches = match reader_factory(reader) {
+                Ok(batches) => batches,
+                Err(e) => {
+                    let _ = tx.blocking_send(Err(e));
+                    return;
+                }
+            };
+            for batch in batches {
+                let batch = batch.and_then(|batch| project_batch(&batch, &projected_schema));
+                // The receiver is dropped, no one cares about the remaining batches.
+                if tx.blocking_send(batch).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let stream = futures::stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|batch| (batch, rx))
+        });
+
+        Ok(stream.boxed())
+    }))
+}
+
+/// Projects `batch` to `schema` by column name, casting columns to the types in `schema`.
+///
+/// Nullable columns absent from `batch` are filled with nulls.
+pub fn project_batch(
+    batch: &RecordBatch,
+    schema: &SchemaRef,
+) -> result::Result<RecordBatch, ArrowError> {
+    let columns = schema
+        .fields()
+        .iter()
+        .map(|field| match batch.column_by_name(field.name()) {
+            Some(column) if column.data_type() == field.data_type() => Ok(column.clone()),
+            Some(column) => cast(column, field.data_type()),
+            None if field.is_nullable() => Ok(new_null_array(field.data_type(), batch.num_rows())),
+            None => Err(ArrowError::SchemaError(format!(
+                "column {} not found in file",
+                field.name()
+            ))),
+        })
+        .collect::<result::Result<Vec<_>, _>>()?;
+
+    RecordBatch::try_new(schema.clone(), columns)
+}
+
 pub async fn infer_schemas(
     store: &ObjectStore,
     files: &[String],
@@ -188,7 +277,10 @@ pub async fn infer_schemas(
     ArrowSchema::try_merge(schemas).context(error::MergeSchemaSnafu)
 }
 
-pub async fn stream_to_file<T: DfRecordBatchEncoder, U: Fn(SharedBuffer) -> T>(
+pub async fn stream_to_file<
+    T: DfRecordBatchEncoder,
+    U: FnOnce(SharedBuffer, SchemaRef) -> Result<T>,
+>(
     mut stream: SendableRecordBatchStream,
     store: ObjectStore,
     path: &str,
@@ -196,7 +288,7 @@ pub async fn stream_to_file<T: DfRecordBatchEncoder, U: Fn(SharedBuffer) -> T>(
     encoder_factory: U,
 ) -> Result<usize> {
     let buffer = SharedBuffer::with_capacity(threshold);
-    let encoder = encoder_factory(buffer.clone());
+    let encoder = encoder_factory(buffer.clone(), stream.schema())?;
     let mut writer = LazyBufferedWriter::new(threshold, buffer, encoder, path, |path| async {
         store
             .writer(&path)
@@ -212,6 +304,9 @@ pub async fn stream_to_file<T: DfRecordBatchEncoder, U: Fn(SharedBuffer) -> T>(
         rows += batch.num_rows();
     }
 
+    // Writes the trailing data of the format, e.g. footer of Arrow IPC file.
+    writer.finish_encoder()?;
+
     // Flushes all pending writes
     let _ = writer.try_flush(true).await?;
     writer.close_inner_writer().await?;
@@ -0,0 +1,165 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::{Chain, Cursor, Read};
+use std::result;
+use std::sync::Arc;
+
+use arrow::ipc::reader::StreamReader;
+use arrow::ipc::writer::{FileWriter, StreamWriter};
+use arrow::record_batch::RecordBatch;
+use arrow_schema::{ArrowError, Schema, SchemaRef};
+use async_trait::async_trait;
+use datafusion::error::Result as DataFusionResult;
+use datafusion::physical_plan::file_format::{FileMeta, FileOpenFuture, FileOpener};
+use datafusion::physical_plan::SendableRecordBatchStream;
+use object_store::ObjectStore;
+use snafu::ResultExt;
+use tokio_util::io::SyncIoBridge;
+
+use super::stream_to_file;
+use crate::buffered_writer::DfRecordBatchEncoder;
+use crate::error::{self, Result};
+use crate::file_format::{open_with_blocking_reader, FileFormat};
+use crate::share_buffer::SharedBuffer;
+
+const ARROW_FILE_MAGIC: &[u8; 6] = b"ARROW1";
+
+/// Layout of Arrow IPC data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IpcKind {
+    /// The random access file format, with magic and footer.
+    #[default]
+    File,
+    /// The streaming format.
+    Stream,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ArrowIpcFormat {
+    /// Layout of written files. Both layouts can be read.
+    pub kind: IpcKind,
+}
+
+impl ArrowIpcFormat {
+    pub fn new(kind: IpcKind) -> Self {
+        Self { kind }
+    }
+}
+
+/// Returns a stream reader of Arrow IPC data in either layout.
+///
+/// The file format embeds the streaming format between its magic and footer, and the stream
+/// reader stops at the end-of-stream marker before the footer.
+fn new_ipc_stream_reader<R: Read>(
+    mut reader: R,
+) -> result::Result<StreamReader<Chain<Cursor<Vec<u8>>, R>>, ArrowError> {
+    // Magic is padded to 8 bytes.
+    let mut prefix = vec![0; 8];
+    reader.read_exact(&mut prefix)?;
+    if prefix.starts_with(ARROW_FILE_MAGIC) {
+        prefix.clear();
+    }
+
+    StreamReader::try_new(Cursor::new(prefix).chain(reader), None)
+}
+
+#[async_trait]
+impl FileFormat for ArrowIpcFormat {
+    async fn infer_schema(&self, store: &ObjectStore, path: &str) -> Result<Schema> {
+        let reader = store
+            .reader(path)
+            .await
+            .context(error::ReadObjectSnafu { path })?;
+
+        common_runtime::spawn_blocking_read(move || {
+            let reader = new_ipc_stream_reader(SyncIoBridge::new(reader))
+                .context(error::InferSchemaSnafu)?;
+
+            Ok(reader.schema().as_ref().clone())
+        })
+        .await
+        .context(error::JoinHandleSnafu)?
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ArrowIpcOpener {
+    projected_schema: SchemaRef,
+    object_store: Arc<ObjectStore>,
+}
+
+impl ArrowIpcOpener {
+    /// Return a new [`ArrowIpcOpener`]. Columns are read by the names in `projected_schema`.
+    pub fn new(projected_schema: SchemaRef, object_store: ObjectStore) -> Self {
+        Self {
+            projected_schema,
+            object_store: Arc::new(object_store),
+        }
+    }
+}
+
+impl FileOpener for ArrowIpcOpener {
+    fn open(&self, meta: FileMeta) -> DataFusionResult<FileOpenFuture> {
+        open_with_blocking_reader(
+            self.object_store.clone(),
+            meta.location().to_string(),
+            self.projected_schema.clone(),
+            new_ipc_stream_reader,
+        )
+    }
+}
+
+pub async fn stream_to_arrow_ipc(
+    stream: SendableRecordBatchStream,
+    store: ObjectStore,
+    path: &str,
+    threshold: usize,
+    kind: IpcKind,
+) -> Result<usize> {
+    match kind {
+        IpcKind::File => {
+            stream_to_file(stream, store, path, threshold, |buffer, schema| {
+                FileWriter::try_new(buffer, &schema).context(error::WriteRecordBatchSnafu)
+            })
+            .await
+        }
+        IpcKind::Stream => {
+            stream_to_file(stream, store, path, threshold, |buffer, schema| {
+                StreamWriter::try_new(buffer, &schema).context(error::WriteRecordBatchSnafu)
+            })
+            .await
+        }
+    }
+}
+
+impl DfRecordBatchEncoder for FileWriter<SharedBuffer> {
+    fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        self.write(batch).context(error::WriteRecordBatchSnafu)
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.finish().context(error::WriteRecordBatchSnafu)
+    }
+}
+
+impl DfRecordBatchEncoder for StreamWriter<SharedBuffer> {
+    fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        self.write(batch).context(error::WriteRecordBatchSnafu)
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.finish().context(error::WriteRecordBatchSnafu)
+    }
+}
@@ -0,0 +1,295 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+use std::sync::Arc;
+
+use apache_avro::types::Value;
+use apache_avro::{Schema as AvroSchema, Writer};
+use arrow::array::{Array, ArrayRef, AsArray};
+use arrow::datatypes::{
+    DataType, Date32Type, Field, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type,
+    Int8Type, TimeUnit, TimestampMicrosecondType, TimestampMillisecondType,
+    TimestampNanosecondType, TimestampSecondType, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+};
+use arrow::record_batch::RecordBatch;
+use arrow_schema::{ArrowError, Schema, SchemaRef};
+use async_trait::async_trait;
+use datafusion::datasource::avro_to_arrow::{read_avro_schema_from_reader, Reader};
+use datafusion::error::Result as DataFusionResult;
+use datafusion::physical_plan::file_format::{FileMeta, FileOpenFuture, FileOpener};
+use datafusion::physical_plan::SendableRecordBatchStream;
+use object_store::ObjectStore;
+use serde_json::json;
+use snafu::{ensure, OptionExt, ResultExt};
+use tokio_util::io::SyncIoBridge;
+
+use super::stream_to_file;
+use crate::buffered_writer::DfRecordBatchEncoder;
+use crate::error::{self, Result};
+use crate::file_format::{open_with_blocking_reader, FileFormat};
+use crate::share_buffer::SharedBuffer;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AvroFormat;
+
+#[async_trait]
+impl FileFormat for AvroFormat {
+    async fn infer_schema(&self, store: &ObjectStore, path: &str) -> Result<Schema> {
+        let reader = store
+            .reader(path)
+            .await
+            .context(error::ReadObjectSnafu { path })?;
+
+        common_runtime::spawn_blocking_read(move || {
+            let mut reader = SyncIoBridge::new(reader);
+
+            read_avro_schema_from_reader(&mut reader).context(error::InferAvroSchemaSnafu)
+        })
+        .await
+        .context(error::JoinHandleSnafu)?
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AvroOpener {
+    batch_size: usize,
+    projected_schema: SchemaRef,
+    object_store: Arc<ObjectStore>,
+}
+
+impl AvroOpener {
+    /// Return a new [`AvroOpener`]. Columns are read by the names in `projected_schema`.
+    pub fn new(batch_size: usize, projected_schema: SchemaRef, object_store: ObjectStore) -> Self {
+        Self {
+            batch_size,
+            projected_schema,
+            object_store: Arc::new(object_store),
+        }
+    }
+}
+
+impl FileOpener for AvroOpener {
+    fn open(&self, meta: FileMeta) -> DataFusionResult<FileOpenFuture> {
+        let schema = self.projected_schema.clone();
+        let batch_size = self.batch_size;
+        open_with_blocking_reader(
+            self.object_store.clone(),
+            meta.location().to_string(),
+            self.projected_schema.clone(),
+            move |reader| {
+                Reader::try_new(reader, schema, batch_size, None)
+                    .map_err(|e| ArrowError::ExternalError(Box::new(e)))
+            },
+        )
+    }
+}
+
+pub async fn stream_to_avro(
+    stream: SendableRecordBatchStream,
+    store: ObjectStore,
+    path: &str,
+    threshold: usize,
+) -> Result<usize> {
+    // The avro writer borrows its schema, so the schema must outlive the whole write.
+    let avro_schema = avro_schema(&stream.schema())?;
+    stream_to_file(stream, store, path, threshold, |buffer, _| {
+        Ok(AvroWriter::new(buffer, &avro_schema))
+    })
+    .await
+}
+
+/// Converts arrow schema to Avro schema. Nullable columns are unions with null.
+pub fn avro_schema(schema: &Schema) -> Result<AvroSchema> {
+    let schema_json = avro_schema_json(schema)?.to_string();
+    AvroSchema::parse_str(&schema_json).context(error::EncodeAvroSnafu)
+}
+
+/// Writes record batches to an Avro object container file without compression.
+///
+/// Each record batch is written as a data block.
+pub struct AvroWriter<'a, W: Write> {
+    /// None stands for [`AvroWriter`] finished.
+    writer: Option<Writer<'a, W>>,
+}
+
+impl<'a, W: Write> AvroWriter<'a, W> {
+    pub fn new(writer: W, schema: &'a AvroSchema) -> Self {
+        Self {
+            writer: Some(Writer::new(schema, writer)),
+        }
+    }
+
+    pub fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        let writer = self
+            .writer
+            .as_mut()
+            .context(error::BufferedWriterClosedSnafu)?;
+
+        let schema = batch.schema();
+        for row in 0..batch.num_rows() {
+            let fields = schema
+                .fields()
+                .iter()
+                .zip(batch.columns())
+                .map(|(field, column)| Ok((field.name().clone(), field_value(field, column, row)?)))
+                .collect::<Result<Vec<_>>>()?;
+            let _ = writer
+                .append(Value::Record(fields))
+                .context(error::EncodeAvroSnafu)?;
+        }
+        let _ = writer.flush().context(error::EncodeAvroSnafu)?;
+        Ok(())
+    }
+
+    /// Writes the header if no row's been written and flushes pending rows.
+    pub fn finish(&mut self) -> Result<()> {
+        if let Some(writer) = self.writer.take() {
+            let _ = writer.into_inner().context(error::EncodeAvroSnafu)?;
+        }
+        Ok(())
+    }
+}
+
+impl DfRecordBatchEncoder for AvroWriter<'_, SharedBuffer> {
+    fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        self.write(batch)
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.finish()
+    }
+}
+
+fn avro_schema_json(schema: &Schema) -> Result<serde_json::Value> {
+    let fields = schema
+        .fields()
+        .iter()
+        .map(|field| {
+            let avro_type = avro_type(field.data_type())?;
+            let avro_type = if field.is_nullable() {
+                json!(["null", avro_type])
+            } else {
+                avro_type
+            };
+            Ok(json!({ "name": field.name(), "type": avro_type }))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(json!({
+        "type": "record",
+        "name": "record",
+        "fields": fields,
+    }))
+}
+
+fn avro_type(data_type: &DataType) -> Result<serde_json::Value> {
+    let avro_type = match data_type {
+        DataType::Boolean => json!("boolean"),
+        DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::UInt8 | DataType::UInt16 => {
+            json!("int")
+        }
+        // Avro has no unsigned types, UInt64 values beyond the range of long are rejected
+        // on writing.
+        DataType::Int64 | DataType::UInt32 | DataType::UInt64 => json!("long"),
+        DataType::Float32 => json!("float"),
+        DataType::Float64 => json!("double"),
+        DataType::Utf8 | DataType::LargeUtf8 => json!("string"),
+        DataType::Binary | DataType::LargeBinary => json!("bytes"),
+        DataType::Date32 => json!({ "type": "int", "logicalType": "date" }),
+        DataType::Timestamp(TimeUnit::Second | TimeUnit::Millisecond, _) => {
+            json!({ "type": "long", "logicalType": "timestamp-millis" })
+        }
+        // There is no nanosecond logical type in the Avro spec we support, so nanosecond
+        // timestamps are rounded down to microseconds.
+        DataType::Timestamp(TimeUnit::Microsecond | TimeUnit::Nanosecond, _) => {
+            json!({ "type": "long", "logicalType": "timestamp-micros" })
+        }
+        _ => {
+            return error::UnsupportedAvroTypeSnafu {
+                data_type: data_type.to_string(),
+            }
+            .fail()
+        }
+    };
+    Ok(avro_type)
+}
+
+fn field_value(field: &Field, array: &ArrayRef, row: usize) -> Result<Value> {
+    if !field.is_nullable() {
+        ensure!(
+            !array.is_null(row),
+            error::NullInNonNullableColumnSnafu {
+                column: field.name(),
+            }
+        );
+        return value(array, row);
+    }
+
+    if array.is_null(row) {
+        Ok(Value::Union(0, Box::new(Value::Null)))
+    } else {
+        Ok(Value::Union(1, Box::new(value(array, row)?)))
+    }
+}
+
+fn value(array: &ArrayRef, row: usize) -> Result<Value> {
+    let value = match array.data_type() {
+        DataType::Boolean => Value::Boolean(array.as_boolean().value(row)),
+        DataType::Int8 => Value::Int(array.as_primitive::<Int8Type>().value(row) as i32),
+        DataType::Int16 => Value::Int(array.as_primitive::<Int16Type>().value(row) as i32),
+        DataType::Int32 => Value::Int(array.as_primitive::<Int32Type>().value(row)),
+        DataType::UInt8 => Value::Int(array.as_primitive::<UInt8Type>().value(row) as i32),
+        DataType::UInt16 => Value::Int(array.as_primitive::<UInt16Type>().value(row) as i32),
+        DataType::Int64 => Value::Long(array.as_primitive::<Int64Type>().value(row)),
+        DataType::UInt32 => Value::Long(array.as_primitive::<UInt32Type>().value(row) as i64),
+        DataType::UInt64 => {
+            let value = array.as_primitive::<UInt64Type>().value(row);
+            Value::Long(
+                i64::try_from(value)
+                    .ok()
+                    .context(error::AvroLongOverflowSnafu { value })?,
+            )
+        }
+        DataType::Float32 => Value::Float(array.as_primitive::<Float32Type>().value(row)),
+        DataType::Float64 => Value::Double(array.as_primitive::<Float64Type>().value(row)),
+        DataType::Utf8 => Value::String(array.as_string::<i32>().value(row).to_string()),
+        DataType::LargeUtf8 => Value::String(array.as_string::<i64>().value(row).to_string()),
+        DataType::Binary => Value::Bytes(array.as_binary::<i32>().value(row).to_vec()),
+        DataType::LargeBinary => Value::Bytes(array.as_binary::<i64>().value(row).to_vec()),
+        DataType::Date32 => Value::Date(array.as_primitive::<Date32Type>().value(row)),
+        DataType::Timestamp(TimeUnit::Second, _) => {
+            Value::TimestampMillis(array.as_primitive::<TimestampSecondType>().value(row) * 1000)
+        }
+        DataType::Timestamp(TimeUnit::Millisecond, _) => {
+            Value::TimestampMillis(array.as_primitive::<TimestampMillisecondType>().value(row))
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, _) => {
+            Value::TimestampMicros(array.as_primitive::<TimestampMicrosecondType>().value(row))
+        }
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => Value::TimestampMicros(
+            array
+                .as_primitive::<TimestampNanosecondType>()
+                .value(row)
+                .div_euclid(1000),
+        ),
+        data_type => {
+            return error::UnsupportedAvroTypeSnafu {
+                data_type: data_type.to_string(),
+            }
+            .fail()
+        }
+    };
+    Ok(value)
+}
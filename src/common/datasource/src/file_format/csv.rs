@@ -194,8 +194,8 @@ pub async fn stream_to_csv(
     path: &str,
     threshold: usize,
 ) -> Result<usize> {
-    stream_to_file(stream, store, path, threshold, |buffer| {
-        csv::Writer::new(buffer)
+    stream_to_file(stream, store, path, threshold, |buffer, _| {
+        Ok(csv::Writer::new(buffer))
     })
     .await
 }
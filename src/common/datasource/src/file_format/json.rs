@@ -153,8 +153,8 @@ pub async fn stream_to_json(
     path: &str,
     threshold: usize,
 ) -> Result<usize> {
-    stream_to_file(stream, store, path, threshold, |buffer| {
-        json::LineDelimitedWriter::new(buffer)
+    stream_to_file(stream, store, path, threshold, |buffer, _| {
+        Ok(json::LineDelimitedWriter::new(buffer))
     })
     .await
 }
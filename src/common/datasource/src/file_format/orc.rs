@@ -12,26 +12,37 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io::Write;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
+use arrow::array::{Array, ArrayRef, AsArray};
 use arrow::compute::cast;
+use arrow::datatypes::{
+    ArrowPrimitiveType, DataType, Date32Type, Float32Type, Float64Type, Int16Type, Int32Type,
+    Int64Type, Int8Type, TimeUnit, TimestampMicrosecondType, TimestampMillisecondType,
+    TimestampNanosecondType, TimestampSecondType, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+};
+use arrow::record_batch::RecordBatch;
 use arrow_schema::{Schema, SchemaRef};
 use async_trait::async_trait;
 use datafusion::arrow::record_batch::RecordBatch as DfRecordBatch;
 use datafusion::error::{DataFusionError, Result as DfResult};
-use datafusion::physical_plan::RecordBatchStream;
+use datafusion::physical_plan::{RecordBatchStream, SendableRecordBatchStream};
 use futures::Stream;
 use object_store::ObjectStore;
 use orc_rust::arrow_reader::{create_arrow_schema, Cursor};
 use orc_rust::async_arrow_reader::ArrowStreamReader;
 pub use orc_rust::error::Error as OrcError;
 use orc_rust::reader::Reader;
-use snafu::ResultExt;
+use snafu::{ensure, OptionExt, ResultExt};
 use tokio::io::{AsyncRead, AsyncSeek};
 
+use super::stream_to_file;
+use crate::buffered_writer::DfRecordBatchEncoder;
 use crate::error::{self, Result};
 use crate::file_format::FileFormat;
+use crate::share_buffer::SharedBuffer;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub struct OrcFormat;
@@ -123,3 +134,631 @@ impl FileFormat for OrcFormat {
         Ok(schema)
     }
 }
+
+pub async fn stream_to_orc(
+    stream: SendableRecordBatchStream,
+    store: ObjectStore,
+    path: &str,
+    threshold: usize,
+) -> Result<usize> {
+    stream_to_file(stream, store, path, threshold, |buffer, schema| {
+        OrcWriter::try_new(buffer, &schema)
+    })
+    .await
+}
+
+/// Magic bytes at the beginning of an ORC file and in its postscript.
+const ORC_MAGIC: &[u8] = b"ORC";
+/// Seconds from the unix epoch to 2015-01-01 00:00:00 UTC, which ORC timestamps
+/// are relative to.
+const ORC_TIMESTAMP_BASE_SECONDS: i64 = 1_420_070_400;
+/// Max number of values in a run of integer run length encoding v2.
+const MAX_RLE_V2_RUN: usize = 512;
+/// Max number of values in a literal run of byte run length encoding.
+const MAX_BYTE_RLE_LITERALS: usize = 128;
+/// Version of the ORC file format we write, which is 0.12.
+const ORC_VERSION: [u64; 2] = [0, 12];
+/// Writer version in the postscript, readers use it to work around bugs of old writers.
+const ORC_WRITER_VERSION: u64 = 6;
+
+/// Type kinds of `orc_proto.proto` we write.
+#[derive(Debug, Clone, Copy)]
+enum TypeKind {
+    Boolean = 0,
+    Byte = 1,
+    Short = 2,
+    Int = 3,
+    Long = 4,
+    Float = 5,
+    Double = 6,
+    String = 7,
+    Binary = 8,
+    Timestamp = 9,
+    Struct = 12,
+    Date = 15,
+}
+
+impl TypeKind {
+    fn try_from_arrow(data_type: &DataType) -> Result<Self> {
+        let kind = match data_type {
+            DataType::Boolean => TypeKind::Boolean,
+            DataType::Int8 => TypeKind::Byte,
+            DataType::Int16 | DataType::UInt8 => TypeKind::Short,
+            DataType::Int32 | DataType::UInt16 => TypeKind::Int,
+            // ORC has no unsigned types, UInt64 values beyond the range of long are rejected
+            // on writing.
+            DataType::Int64 | DataType::UInt32 | DataType::UInt64 => TypeKind::Long,
+            DataType::Float32 => TypeKind::Float,
+            DataType::Float64 => TypeKind::Double,
+            DataType::Utf8 | DataType::LargeUtf8 => TypeKind::String,
+            DataType::Binary | DataType::LargeBinary => TypeKind::Binary,
+            DataType::Date32 => TypeKind::Date,
+            DataType::Timestamp(_, _) => TypeKind::Timestamp,
+            _ => {
+                return error::UnsupportedOrcTypeSnafu {
+                    data_type: data_type.to_string(),
+                }
+                .fail()
+            }
+        };
+        Ok(kind)
+    }
+
+    fn encoding(&self) -> ColumnEncoding {
+        match self {
+            TypeKind::Boolean
+            | TypeKind::Byte
+            | TypeKind::Float
+            | TypeKind::Double
+            | TypeKind::Struct => ColumnEncoding::Direct,
+            _ => ColumnEncoding::DirectV2,
+        }
+    }
+}
+
+/// Stream kinds of `orc_proto.proto` we write.
+#[derive(Debug, Clone, Copy)]
+enum StreamKind {
+    Present = 0,
+    Data = 1,
+    Length = 2,
+    Secondary = 5,
+}
+
+/// Column encodings of `orc_proto.proto` we write.
+#[derive(Debug, Clone, Copy)]
+enum ColumnEncoding {
+    Direct = 0,
+    DirectV2 = 2,
+}
+
+struct StripeInfo {
+    offset: u64,
+    data_length: u64,
+    footer_length: u64,
+    num_rows: u64,
+}
+
+#[derive(Default)]
+struct ColumnStatistics {
+    num_values: u64,
+    has_null: bool,
+}
+
+/// Writes record batches to an ORC file without compression and row indexes.
+///
+/// Each record batch is written as a stripe, the column 0 of the file is a struct
+/// of all the columns.
+pub struct OrcWriter<W: Write> {
+    writer: W,
+    field_names: Vec<String>,
+    kinds: Vec<TypeKind>,
+    /// Bytes written, stripes are located by their offsets in the footer.
+    offset: u64,
+    stripes: Vec<StripeInfo>,
+    /// Statistics of all columns, including the root struct.
+    statistics: Vec<ColumnStatistics>,
+    num_rows: u64,
+    finished: bool,
+}
+
+impl<W: Write> OrcWriter<W> {
+    pub fn try_new(writer: W, schema: &Schema) -> Result<Self> {
+        let kinds = schema
+            .fields()
+            .iter()
+            .map(|field| TypeKind::try_from_arrow(field.data_type()))
+            .collect::<Result<Vec<_>>>()?;
+        let statistics = (0..=kinds.len())
+            .map(|_| ColumnStatistics::default())
+            .collect();
+
+        Ok(Self {
+            writer,
+            field_names: schema.fields().iter().map(|f| f.name().clone()).collect(),
+            kinds,
+            offset: 0,
+            stripes: Vec::new(),
+            statistics,
+            num_rows: 0,
+            finished: false,
+        })
+    }
+
+    pub fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        ensure!(!self.finished, error::BufferedWriterClosedSnafu);
+        if batch.num_rows() == 0 {
+            return Ok(());
+        }
+        self.write_header()?;
+
+        let mut streams = Vec::new();
+        let mut encodings = vec![ColumnEncoding::Direct];
+        for (i, (array, kind)) in batch.columns().iter().zip(&self.kinds).enumerate() {
+            let column = i as u32 + 1;
+            if array.null_count() > 0 {
+                let present = (0..array.len()).map(|row| array.is_valid(row));
+                streams.push((StreamKind::Present, column, encode_booleans(present)));
+            }
+            for (kind, stream) in encode_values(array)? {
+                streams.push((kind, column, stream));
+            }
+            encodings.push(kind.encoding());
+
+            let statistics = &mut self.statistics[i + 1];
+            statistics.num_values += (array.len() - array.null_count()) as u64;
+            statistics.has_null |= array.null_count() > 0;
+        }
+        self.statistics[0].num_values += batch.num_rows() as u64;
+
+        let mut footer = ProtoBuf::default();
+        let mut data_length = 0;
+        for (kind, column, stream) in &streams {
+            let mut message = ProtoBuf::default();
+            let _ = message
+                .uint(1, *kind as u64)
+                .uint(2, u64::from(*column))
+                .uint(3, stream.len() as u64);
+            let _ = footer.message(1, &message);
+            data_length += stream.len() as u64;
+        }
+        for encoding in encodings {
+            let _ = footer.message(2, ProtoBuf::default().uint(1, encoding as u64));
+        }
+        // Timestamps are relative to the base time in the writer's timezone.
+        let _ = footer.bytes(3, b"UTC");
+
+        let stripe = StripeInfo {
+            offset: self.offset,
+            data_length,
+            footer_length: footer.0.len() as u64,
+            num_rows: batch.num_rows() as u64,
+        };
+        for (_, _, stream) in &streams {
+            self.write_all(stream)?;
+        }
+        self.write_all(&footer.0)?;
+        self.stripes.push(stripe);
+        self.num_rows += batch.num_rows() as u64;
+
+        Ok(())
+    }
+
+    /// Writes the footer and the postscript.
+    pub fn finish(&mut self) -> Result<()> {
+        ensure!(!self.finished, error::BufferedWriterClosedSnafu);
+        self.write_header()?;
+
+        let mut footer = ProtoBuf::default();
+        let _ = footer.uint(1, ORC_MAGIC.len() as u64).uint(2, self.offset);
+        for stripe in &self.stripes {
+            let mut message = ProtoBuf::default();
+            let _ = message
+                .uint(1, stripe.offset)
+                .uint(2, 0)
+                .uint(3, stripe.data_length)
+                .uint(4, stripe.footer_length)
+                .uint(5, stripe.num_rows);
+            let _ = footer.message(3, &message);
+        }
+        let mut root = ProtoBuf::default();
+        let subtypes = (1..=self.kinds.len() as u64).collect::<Vec<_>>();
+        let _ = root.uint(1, TypeKind::Struct as u64).packed(2, &subtypes);
+        for name in &self.field_names {
+            let _ = root.bytes(3, name.as_bytes());
+        }
+        let _ = footer.message(4, &root);
+        for kind in &self.kinds {
+            let _ = footer.message(4, ProtoBuf::default().uint(1, *kind as u64));
+        }
+        let _ = footer.uint(6, self.num_rows);
+        for statistics in &self.statistics {
+            let mut message = ProtoBuf::default();
+            let _ = message
+                .uint(1, statistics.num_values)
+                .uint(10, u64::from(statistics.has_null));
+            let _ = footer.message(7, &message);
+        }
+        let _ = footer.uint(8, 0);
+
+        let mut postscript = ProtoBuf::default();
+        let _ = postscript
+            .uint(1, footer.0.len() as u64)
+            // No compression.
+            .uint(2, 0)
+            .packed(4, &ORC_VERSION)
+            .uint(5, 0)
+            .uint(6, ORC_WRITER_VERSION)
+            .bytes(8000, ORC_MAGIC);
+
+        self.write_all(&footer.0)?;
+        self.write_all(&postscript.0)?;
+        // The postscript is always shorter than 256 bytes.
+        self.write_all(&[postscript.0.len() as u8])?;
+        self.finished = true;
+
+        Ok(())
+    }
+
+    fn write_header(&mut self) -> Result<()> {
+        if self.offset == 0 {
+            self.write_all(ORC_MAGIC)?;
+        }
+        Ok(())
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        self.writer.write_all(buf).context(error::WriteOrcSnafu)?;
+        self.offset += buf.len() as u64;
+        Ok(())
+    }
+}
+
+impl DfRecordBatchEncoder for OrcWriter<SharedBuffer> {
+    fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        self.write(batch)
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.finish()
+    }
+}
+
+/// Encodes non-null values of the array to ORC streams.
+fn encode_values(array: &ArrayRef) -> Result<Vec<(StreamKind, Vec<u8>)>> {
+    let streams = match array.data_type() {
+        DataType::Boolean => {
+            let values = array.as_boolean().iter().flatten();
+            vec![(StreamKind::Data, encode_booleans(values))]
+        }
+        DataType::Int8 => {
+            let values = array
+                .as_primitive::<Int8Type>()
+                .iter()
+                .flatten()
+                .map(|v| v as u8)
+                .collect::<Vec<_>>();
+            vec![(StreamKind::Data, encode_bytes(&values))]
+        }
+        DataType::Int16 => encode_integers::<Int16Type>(array),
+        DataType::Int32 => encode_integers::<Int32Type>(array),
+        DataType::Int64 => encode_integers::<Int64Type>(array),
+        DataType::UInt8 => encode_integers::<UInt8Type>(array),
+        DataType::UInt16 => encode_integers::<UInt16Type>(array),
+        DataType::UInt32 => encode_integers::<UInt32Type>(array),
+        DataType::UInt64 => {
+            let values = array
+                .as_primitive::<UInt64Type>()
+                .iter()
+                .flatten()
+                .map(|value| {
+                    i64::try_from(value)
+                        .ok()
+                        .context(error::OrcLongOverflowSnafu { value })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            vec![(StreamKind::Data, encode_signed(&values))]
+        }
+        DataType::Date32 => encode_integers::<Date32Type>(array),
+        DataType::Float32 => {
+            let values = array.as_primitive::<Float32Type>().iter().flatten();
+            vec![(
+                StreamKind::Data,
+                values.flat_map(|v| v.to_le_bytes()).collect(),
+            )]
+        }
+        DataType::Float64 => {
+            let values = array.as_primitive::<Float64Type>().iter().flatten();
+            vec![(
+                StreamKind::Data,
+                values.flat_map(|v| v.to_le_bytes()).collect(),
+            )]
+        }
+        DataType::Utf8 => {
+            encode_binaries(array.as_string::<i32>().iter().flatten().map(str::as_bytes))
+        }
+        DataType::LargeUtf8 => {
+            encode_binaries(array.as_string::<i64>().iter().flatten().map(str::as_bytes))
+        }
+        DataType::Binary => encode_binaries(array.as_binary::<i32>().iter().flatten()),
+        DataType::LargeBinary => encode_binaries(array.as_binary::<i64>().iter().flatten()),
+        DataType::Timestamp(TimeUnit::Second, _) => {
+            encode_timestamps::<TimestampSecondType>(array, 1)
+        }
+        DataType::Timestamp(TimeUnit::Millisecond, _) => {
+            encode_timestamps::<TimestampMillisecondType>(array, 1_000)
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, _) => {
+            encode_timestamps::<TimestampMicrosecondType>(array, 1_000_000)
+        }
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+            encode_timestamps::<TimestampNanosecondType>(array, 1_000_000_000)
+        }
+        data_type => {
+            return error::UnsupportedOrcTypeSnafu {
+                data_type: data_type.to_string(),
+            }
+            .fail()
+        }
+    };
+    Ok(streams)
+}
+
+fn encode_integers<T>(array: &ArrayRef) -> Vec<(StreamKind, Vec<u8>)>
+where
+    T: ArrowPrimitiveType,
+    T::Native: Into<i64>,
+{
+    let values = array
+        .as_primitive::<T>()
+        .iter()
+        .flatten()
+        .map(Into::into)
+        .collect::<Vec<i64>>();
+    vec![(StreamKind::Data, encode_signed(&values))]
+}
+
+fn encode_binaries<'a>(values: impl Iterator<Item = &'a [u8]>) -> Vec<(StreamKind, Vec<u8>)> {
+    let mut data = Vec::new();
+    let mut lengths = Vec::new();
+    for value in values {
+        data.extend_from_slice(value);
+        lengths.push(value.len() as u64);
+    }
+    vec![
+        (StreamKind::Data, data),
+        (StreamKind::Length, encode_unsigned(&lengths)),
+    ]
+}
+
+/// Encodes timestamps to seconds relative to the ORC base time and nanoseconds.
+fn encode_timestamps<T>(array: &ArrayRef, units_per_second: i64) -> Vec<(StreamKind, Vec<u8>)>
+where
+    T: ArrowPrimitiveType<Native = i64>,
+{
+    let (seconds, nanos): (Vec<_>, Vec<_>) = array
+        .as_primitive::<T>()
+        .iter()
+        .flatten()
+        .map(|value| {
+            let seconds = value.div_euclid(units_per_second);
+            let nanos =
+                (value.rem_euclid(units_per_second) * (1_000_000_000 / units_per_second)) as u64;
+            // Readers subtract one second from negative seconds with more than 999_999
+            // nanoseconds (ORC-763), so these seconds are written one second later.
+            let seconds = if seconds < 0 && nanos > 999_999 {
+                seconds + 1
+            } else {
+                seconds
+            };
+            (seconds - ORC_TIMESTAMP_BASE_SECONDS, format_nanos(nanos))
+        })
+        .unzip();
+    vec![
+        (StreamKind::Data, encode_signed(&seconds)),
+        (StreamKind::Secondary, encode_unsigned(&nanos)),
+    ]
+}
+
+/// Formats nanoseconds with the number of trailing decimal zeros in the lowest 3 bits,
+/// as the ORC spec requires.
+fn format_nanos(nanos: u64) -> u64 {
+    if nanos == 0 || nanos % 100 != 0 {
+        return nanos << 3;
+    }
+    let mut nanos = nanos / 100;
+    let mut trailing_zeros = 1;
+    while nanos % 10 == 0 && trailing_zeros < 7 {
+        nanos /= 10;
+        trailing_zeros += 1;
+    }
+    (nanos << 3) | trailing_zeros
+}
+
+/// Encodes booleans with boolean run length encoding, bits are packed from the most
+/// significant bit of each byte.
+fn encode_booleans(values: impl Iterator<Item = bool>) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for (i, value) in values.enumerate() {
+        if i % 8 == 0 {
+            bytes.push(0);
+        }
+        if value {
+            *bytes.last_mut().unwrap() |= 0x80 >> (i % 8);
+        }
+    }
+    encode_bytes(&bytes)
+}
+
+/// Encodes bytes with byte run length encoding, using literal runs only.
+fn encode_bytes(values: &[u8]) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(values.len() + values.len() / MAX_BYTE_RLE_LITERALS + 1);
+    for literals in values.chunks(MAX_BYTE_RLE_LITERALS) {
+        // A negative header is the number of literals that follow.
+        buf.push((-(literals.len() as i32)) as u8);
+        buf.extend_from_slice(literals);
+    }
+    buf
+}
+
+/// Encodes signed integers with run length encoding v2 after zigzag encoding.
+fn encode_signed(values: &[i64]) -> Vec<u8> {
+    let values = values
+        .iter()
+        .map(|v| ((v << 1) ^ (v >> 63)) as u64)
+        .collect::<Vec<_>>();
+    encode_unsigned(&values)
+}
+
+/// Encodes unsigned integers with run length encoding v2, using direct runs only.
+fn encode_unsigned(values: &[u64]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    for run in values.chunks(MAX_RLE_V2_RUN) {
+        let max = run.iter().copied().max().unwrap_or_default();
+        let (width, width_code) = aligned_bit_width(64 - max.leading_zeros());
+        let len = run.len() - 1;
+        // Header: 2 bits of the direct encoding, 5 bits of the width and 9 bits of length - 1.
+        buf.push(0x40 | (width_code << 1) | ((len >> 8) as u8 & 0x01));
+        buf.push(len as u8);
+
+        // Values are packed from the most significant bit.
+        let mut current = 0u8;
+        let mut bits_left = 8;
+        for value in run {
+            let mut remaining = width;
+            while remaining > 0 {
+                let bits = remaining.min(bits_left);
+                remaining -= bits;
+                let part = ((value >> remaining) & ((1 << bits) - 1)) as u8;
+                bits_left -= bits;
+                current |= part << bits_left;
+                if bits_left == 0 {
+                    buf.push(current);
+                    current = 0;
+                    bits_left = 8;
+                }
+            }
+        }
+        if bits_left < 8 {
+            buf.push(current);
+        }
+    }
+    buf
+}
+
+/// Rounds the bit width up to an aligned width of run length encoding v2, returns the
+/// width and its code in the run header.
+fn aligned_bit_width(bits: u32) -> (u32, u8) {
+    match bits {
+        0 | 1 => (1, 0),
+        2 => (2, 1),
+        3 | 4 => (4, 3),
+        5..=8 => (8, 7),
+        9..=16 => (16, 15),
+        17..=24 => (24, 23),
+        25..=32 => (32, 27),
+        33..=40 => (40, 28),
+        41..=48 => (48, 29),
+        49..=56 => (56, 30),
+        _ => (64, 31),
+    }
+}
+
+/// Encodes protobuf messages of the ORC file metadata.
+#[derive(Default)]
+struct ProtoBuf(Vec<u8>);
+
+impl ProtoBuf {
+    fn uint(&mut self, field: u64, value: u64) -> &mut Self {
+        self.varint(field << 3);
+        self.varint(value);
+        self
+    }
+
+    fn bytes(&mut self, field: u64, value: &[u8]) -> &mut Self {
+        self.varint(field << 3 | 2);
+        self.varint(value.len() as u64);
+        self.0.extend_from_slice(value);
+        self
+    }
+
+    fn message(&mut self, field: u64, message: &ProtoBuf) -> &mut Self {
+        self.bytes(field, &message.0)
+    }
+
+    fn packed(&mut self, field: u64, values: &[u64]) -> &mut Self {
+        let mut packed = ProtoBuf::default();
+        for value in values {
+            packed.varint(*value);
+        }
+        self.bytes(field, &packed.0)
+    }
+
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.0.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        self.0.push(value as u8);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_unsigned() {
+        // Direct run of width 1.
+        assert_eq!(vec![0x40, 0x02, 0b1010_0000], encode_unsigned(&[1, 0, 1]));
+        // Direct run of width 16.
+        assert_eq!(
+            vec![0x5e, 0x01, 0x01, 0x00, 0x00, 0x02],
+            encode_unsigned(&[256, 2])
+        );
+        // Runs are split every 512 values.
+        let encoded = encode_unsigned(&[7; 513]);
+        assert_eq!(&[0x47, 0xff], &encoded[..2]);
+        assert_eq!(&[0x46, 0x00, 0x70], &encoded[2 + 256..]);
+    }
+
+    #[test]
+    fn test_encode_signed() {
+        // Zigzag: 0 -> 0, -1 -> 1, 1 -> 2, -2 -> 3.
+        assert_eq!(
+            encode_unsigned(&[0, 1, 2, 3]),
+            encode_signed(&[0, -1, 1, -2])
+        );
+    }
+
+    #[test]
+    fn test_encode_booleans() {
+        let values = [true, false, true, true, false, false, false, false, true];
+        assert_eq!(
+            vec![0xfe, 0b1011_0000, 0b1000_0000],
+            encode_booleans(values.into_iter())
+        );
+    }
+
+    #[test]
+    fn test_format_nanos() {
+        assert_eq!(0, format_nanos(0));
+        assert_eq!(123 << 3, format_nanos(123));
+        // 1000 has 3 trailing zeros, stored as 1 with 2.
+        assert_eq!(1 << 3 | 2, format_nanos(1_000));
+        // At most 8 trailing zeros are formatted.
+        assert_eq!(1 << 3 | 7, format_nanos(100_000_000));
+    }
+
+    #[test]
+    fn test_proto_buf() {
+        let mut buf = ProtoBuf::default();
+        let _ = buf.uint(1, 300).bytes(8000, b"ORC").packed(4, &[0, 12]);
+        assert_eq!(
+            vec![
+                0x08, 0xac, 0x02, 0x82, 0xf4, 0x03, 0x03, b'O', b'R', b'C', 0x22, 0x02, 0x00, 0x0c
+            ],
+            buf.0
+        );
+    }
+}
@@ -17,22 +17,34 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::vec;
 
+use arrow::array::{
+    AsArray, BooleanArray, Float64Array, Int64Array, StringArray, TimestampMillisecondArray,
+    TimestampNanosecondArray, UInt64Array,
+};
+use arrow::datatypes::{DataType, Field, Int64Type, Schema, TimeUnit, TimestampMicrosecondType};
+use arrow::record_batch::RecordBatch;
 use datafusion::assert_batches_eq;
 use datafusion::execution::context::TaskContext;
 use datafusion::physical_plan::file_format::{FileOpener, FileScanConfig, FileStream, ParquetExec};
 use datafusion::physical_plan::metrics::ExecutionPlanMetricsSet;
-use datafusion::physical_plan::ExecutionPlan;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{ExecutionPlan, SendableRecordBatchStream};
 use datafusion::prelude::SessionContext;
 use futures::StreamExt;
 
 use super::FORMAT_TYPE;
 use crate::compression::CompressionType;
 use crate::error;
+use crate::file_format::arrow_ipc::{stream_to_arrow_ipc, ArrowIpcFormat, ArrowIpcOpener, IpcKind};
+use crate::file_format::avro::{stream_to_avro, AvroFormat, AvroOpener};
 use crate::file_format::csv::{CsvConfigBuilder, CsvOpener};
 use crate::file_format::json::JsonOpener;
+use crate::file_format::orc::{
+    new_orc_stream_reader, stream_to_orc, OrcArrowStreamReaderAdapter, OrcFormat,
+};
 use crate::file_format::parquet::DefaultParquetFileReaderFactory;
-use crate::file_format::Format;
-use crate::test_util::{self, scan_config, test_basic_schema, test_store};
+use crate::file_format::{FileFormat, Format};
+use crate::test_util::{self, scan_config, test_basic_schema, test_store, test_tmp_store};
 
 struct Test<'a, T: FileOpener> {
     config: FileScanConfig,
@@ -153,6 +165,284 @@ async fn test_csv_opener() {
     }
 }
 
+fn test_basic_stream() -> SendableRecordBatchStream {
+    let schema = test_basic_schema();
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![
+            Arc::new(Int64Array::from(vec![5, 2, 4])),
+            Arc::new(StringArray::from(vec!["test", "hello", "foo"])),
+        ],
+    )
+    .unwrap();
+
+    Box::pin(RecordBatchStreamAdapter::new(
+        schema,
+        futures::stream::iter(vec![Ok(batch)]),
+    ))
+}
+
+const BASIC_BATCHES: [&str; 7] = [
+    "+-----+-------+",
+    "| num | str   |",
+    "+-----+-------+",
+    "| 5   | test  |",
+    "| 2   | hello |",
+    "| 4   | foo   |",
+    "+-----+-------+",
+];
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_arrow_ipc_write_and_read() {
+    for kind in [IpcKind::File, IpcKind::Stream] {
+        let (store, dir) = test_tmp_store("test_arrow_ipc_write_and_read");
+        let path = format!("{}/{}", dir.path().display(), "output");
+
+        let rows = stream_to_arrow_ipc(test_basic_stream(), store.clone(), &path, 1024, kind)
+            .await
+            .unwrap();
+        assert_eq!(3, rows);
+
+        let schema = ArrowIpcFormat::new(kind)
+            .infer_schema(&store, &path)
+            .await
+            .unwrap();
+        assert_eq!(test_basic_schema().as_ref(), &schema);
+
+        let schema = Arc::new(schema);
+        Test {
+            config: scan_config(schema.clone(), None, &path),
+            opener: ArrowIpcOpener::new(schema, store),
+            expected: BASIC_BATCHES.to_vec(),
+        }
+        .run()
+        .await;
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_avro_write_and_read() {
+    let (store, dir) = test_tmp_store("test_avro_write_and_read");
+    let path = format!("{}/{}", dir.path().display(), "output");
+
+    let rows = stream_to_avro(test_basic_stream(), store.clone(), &path, 1024)
+        .await
+        .unwrap();
+    assert_eq!(3, rows);
+
+    let schema = AvroFormat.infer_schema(&store, &path).await.unwrap();
+    assert_eq!(
+        vec!["num: Int64: NOT NULL", "str: Utf8: NOT NULL"],
+        test_util::format_schema(schema.clone())
+    );
+
+    let schema = Arc::new(schema);
+    Test {
+        config: scan_config(schema.clone(), None, &path),
+        opener: AvroOpener::new(test_util::TEST_BATCH_SIZE, schema, store),
+        expected: BASIC_BATCHES.to_vec(),
+    }
+    .run()
+    .await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_avro_write_unsigned_and_nanosecond() {
+    let (store, dir) = test_tmp_store("test_avro_write_unsigned_and_nanosecond");
+    let path = format!("{}/{}", dir.path().display(), "output");
+
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("num", DataType::UInt64, true),
+        Field::new("ts", DataType::Timestamp(TimeUnit::Nanosecond, None), false),
+    ]));
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![
+            Arc::new(UInt64Array::from(vec![
+                Some(1),
+                None,
+                Some(i64::MAX as u64),
+            ])),
+            Arc::new(TimestampNanosecondArray::from(vec![
+                1_000_001_000,
+                1_000_002_999,
+                -1,
+            ])),
+        ],
+    )
+    .unwrap();
+    let stream = Box::pin(RecordBatchStreamAdapter::new(
+        schema,
+        futures::stream::iter(vec![Ok(batch)]),
+    ));
+
+    let rows = stream_to_avro(stream, store.clone(), &path, 1024)
+        .await
+        .unwrap();
+    assert_eq!(3, rows);
+
+    // UInt64 is written as avro long, nanosecond timestamps are rounded down to
+    // microseconds.
+    let schema = AvroFormat.infer_schema(&store, &path).await.unwrap();
+    assert_eq!(&DataType::Int64, schema.field(0).data_type());
+    assert_matches!(
+        schema.field(1).data_type(),
+        DataType::Timestamp(TimeUnit::Microsecond, _)
+    );
+
+    let schema = Arc::new(schema);
+    let config = scan_config(schema.clone(), None, &path);
+    let opener = AvroOpener::new(test_util::TEST_BATCH_SIZE, schema, store);
+    let batches = FileStream::new(&config, 0, opener, &ExecutionPlanMetricsSet::new())
+        .unwrap()
+        .map(|b| b.unwrap())
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(1, batches.len());
+    let nums = batches[0].column(0).as_primitive::<Int64Type>();
+    assert_eq!(
+        vec![Some(1), None, Some(i64::MAX)],
+        nums.iter().collect::<Vec<_>>()
+    );
+    let ts = batches[0]
+        .column(1)
+        .as_primitive::<TimestampMicrosecondType>();
+    assert_eq!(vec![1_000_001, 1_000_002, -1], ts.values().to_vec());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_avro_write_uint64_overflow() {
+    let (store, dir) = test_tmp_store("test_avro_write_uint64_overflow");
+    let path = format!("{}/{}", dir.path().display(), "output");
+
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "num",
+        DataType::UInt64,
+        false,
+    )]));
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![Arc::new(UInt64Array::from(vec![u64::MAX]))],
+    )
+    .unwrap();
+    let stream = Box::pin(RecordBatchStreamAdapter::new(
+        schema,
+        futures::stream::iter(vec![Ok(batch)]),
+    ));
+
+    let err = stream_to_avro(stream, store, &path, 1024)
+        .await
+        .unwrap_err();
+    assert_matches!(err, error::Error::AvroLongOverflow { .. });
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_orc_write_and_read() {
+    let (store, dir) = test_tmp_store("test_orc_write_and_read");
+    let path = format!("{}/{}", dir.path().display(), "output");
+
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("num", DataType::Int64, true),
+        Field::new("str", DataType::Utf8, true),
+        Field::new("flag", DataType::Boolean, false),
+        Field::new("value", DataType::Float64, false),
+        Field::new(
+            "ts",
+            DataType::Timestamp(TimeUnit::Millisecond, None),
+            false,
+        ),
+    ]));
+    let batches = vec![
+        RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int64Array::from(vec![Some(5), None])),
+                Arc::new(StringArray::from(vec![Some("test"), None])),
+                Arc::new(BooleanArray::from(vec![true, false])),
+                Arc::new(Float64Array::from(vec![1.5, -2.0])),
+                Arc::new(TimestampMillisecondArray::from(vec![
+                    1_688_000_000_123,
+                    1_688_000_001_456,
+                ])),
+            ],
+        )
+        .unwrap(),
+        RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int64Array::from(vec![Some(-300)])),
+                Arc::new(StringArray::from(vec![Some("foo")])),
+                Arc::new(BooleanArray::from(vec![true])),
+                Arc::new(Float64Array::from(vec![0.25])),
+                Arc::new(TimestampMillisecondArray::from(vec![1_688_000_002_789])),
+            ],
+        )
+        .unwrap(),
+    ];
+    let stream = Box::pin(RecordBatchStreamAdapter::new(
+        schema.clone(),
+        futures::stream::iter(batches.into_iter().map(Ok)),
+    ));
+
+    let rows = stream_to_orc(stream, store.clone(), &path, 1024)
+        .await
+        .unwrap();
+    assert_eq!(3, rows);
+
+    let inferred = OrcFormat.infer_schema(&store, &path).await.unwrap();
+    assert_eq!(
+        vec!["num", "str", "flag", "value", "ts"],
+        inferred
+            .fields()
+            .iter()
+            .map(|f| f.name().as_str())
+            .collect::<Vec<_>>()
+    );
+
+    let reader = store.reader(&path).await.unwrap();
+    let stream = new_orc_stream_reader(reader).await.unwrap();
+    let batches = OrcArrowStreamReaderAdapter::new(schema, stream)
+        .map(|b| b.unwrap())
+        .collect::<Vec<_>>()
+        .await;
+    assert_batches_eq!(
+        vec![
+            "+------+------+-------+-------+-------------------------+",
+            "| num  | str  | flag  | value | ts                      |",
+            "+------+------+-------+-------+-------------------------+",
+            "| 5    | test | true  | 1.5   | 2023-06-29T00:53:20.123 |",
+            "|      |      | false | -2.0  | 2023-06-29T00:53:21.456 |",
+            "| -300 | foo  | true  | 0.25  | 2023-06-29T00:53:22.789 |",
+            "+------+------+-------+-------+-------------------------+",
+        ],
+        &batches
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_orc_write_uint64_overflow() {
+    let (store, dir) = test_tmp_store("test_orc_write_uint64_overflow");
+    let path = format!("{}/{}", dir.path().display(), "output");
+
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "num",
+        DataType::UInt64,
+        false,
+    )]));
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![Arc::new(UInt64Array::from(vec![u64::MAX]))],
+    )
+    .unwrap();
+    let stream = Box::pin(RecordBatchStreamAdapter::new(
+        schema,
+        futures::stream::iter(vec![Ok(batch)]),
+    ));
+
+    let err = stream_to_orc(stream, store, &path, 1024).await.unwrap_err();
+    assert_matches!(err, error::Error::OrcLongOverflow { .. });
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_parquet_exec() {
     let store = test_store("/");
@@ -213,6 +503,34 @@ fn test_format() {
 
     assert_matches!(Format::try_from(&value).unwrap(), Format::Json(_));
 
+    let value = [(FORMAT_TYPE.to_string(), "avro".to_string())]
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+
+    assert_matches!(Format::try_from(&value).unwrap(), Format::Avro(_));
+
+    let value = [(FORMAT_TYPE.to_string(), "Arrow".to_string())]
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+
+    assert_matches!(
+        Format::try_from(&value).unwrap(),
+        Format::ArrowIpc(ArrowIpcFormat {
+            kind: IpcKind::File
+        })
+    );
+
+    let value = [(FORMAT_TYPE.to_string(), "arrow_stream".to_string())]
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+
+    assert_matches!(
+        Format::try_from(&value).unwrap(),
+        Format::ArrowIpc(ArrowIpcFormat {
+            kind: IpcKind::Stream
+        })
+    );
+
     let value = [(FORMAT_TYPE.to_string(), "Foobar".to_string())]
         .into_iter()
         .collect::<HashMap<_, _>>();
@@ -20,6 +20,7 @@ pub mod error;
 pub mod file_format;
 pub mod lister;
 pub mod object_store;
+pub mod partition;
 pub mod share_buffer;
 #[cfg(test)]
 pub mod test_util;
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use futures::{future, TryStreamExt};
+use futures::TryStreamExt;
 use object_store::{Entry, ObjectStore};
 use regex::Regex;
 use snafu::ResultExt;
@@ -29,6 +29,8 @@ pub struct Lister {
     source: Source,
     path: String,
     regex: Option<Regex>,
+    /// Whether to list files in Hive-style partition directories like `dt=2023-07-01/`.
+    partition_dirs: bool,
 }
 
 impl Lister {
@@ -43,30 +45,36 @@ impl Lister {
             source,
             path,
             regex,
+            partition_dirs: false,
         }
     }
 
+    /// Lists files in partition directories recursively, instead of returning the directories.
+    pub fn with_partition_dirs(mut self, partition_dirs: bool) -> Self {
+        self.partition_dirs = partition_dirs;
+        self
+    }
+
     pub async fn list(&self) -> Result<Vec<Entry>> {
         match &self.source {
             Source::Dir => {
-                let streamer = self
-                    .object_store
-                    .list(&self.path)
-                    .await
-                    .context(error::ListObjectsSnafu { path: &self.path })?;
-
-                streamer
-                    .try_filter(|f| {
-                        let res = self
+                let mut entries = vec![];
+                let mut dirs = vec![self.path.clone()];
+                while let Some(dir) = dirs.pop() {
+                    for entry in self.list_dir(&dir).await? {
+                        if self.partition_dirs && is_partition_dir(&entry) {
+                            dirs.push(entry.path().to_string());
+                        } else if self
                             .regex
                             .as_ref()
-                            .map(|x| x.is_match(f.name()))
-                            .unwrap_or(true);
-                        future::ready(res)
-                    })
-                    .try_collect::<Vec<_>>()
-                    .await
-                    .context(error::ListObjectsSnafu { path: &self.path })
+                            .map(|x| x.is_match(entry.name()))
+                            .unwrap_or(true)
+                        {
+                            entries.push(entry);
+                        }
+                    }
+                }
+                Ok(entries)
             }
             Source::Filename(filename) => {
                 // make sure this file exists
@@ -80,4 +88,18 @@ impl Lister {
             }
         }
     }
+
+    async fn list_dir(&self, path: &str) -> Result<Vec<Entry>> {
+        self.object_store
+            .list(path)
+            .await
+            .context(error::ListObjectsSnafu { path })?
+            .try_collect::<Vec<_>>()
+            .await
+            .context(error::ListObjectsSnafu { path })
+    }
+}
+
+fn is_partition_dir(entry: &Entry) -> bool {
+    entry.path().ends_with('/') && entry.name().trim_end_matches('/').contains('=')
 }
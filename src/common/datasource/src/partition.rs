@@ -0,0 +1,301 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hive-style partitions, like `dt=2023-07-01/region=us/part-0.parquet`.
+//!
+//! Each `key=value` directory segment of a file path below the table location gives the
+//! value of partition column `key` to all rows in the file. Segments of the location itself
+//! are not partitions.
+
+use std::sync::Arc;
+
+use arrow::array::{Array, AsArray};
+use arrow::record_batch::RecordBatch;
+use arrow_schema::{DataType, Field, Schema};
+use datafusion::common::{ScalarValue, ToDFSchema};
+use datafusion::error::Result as DataFusionResult;
+use datafusion::logical_expr::Expr;
+use datafusion::optimizer::utils::conjunction;
+use datafusion::physical_expr::create_physical_expr;
+use datafusion::physical_expr::execution_props::ExecutionProps;
+use snafu::{ensure, ResultExt};
+
+use crate::error::{self, Result};
+use crate::object_store::parse_url;
+use crate::util::find_dir_and_filename;
+
+/// Value of a partition whose column is null.
+pub const HIVE_DEFAULT_PARTITION: &str = "__HIVE_DEFAULT_PARTITION__";
+
+/// Returns the directory of the table `location`, under which partition directories are
+/// parsed.
+pub fn partition_base_dir(location: &str) -> Result<String> {
+    let (_, _, path) = parse_url(location)?;
+    Ok(find_dir_and_filename(&path).0)
+}
+
+/// Returns the `key=value` directory segments of `path` below `base_dir`, the file name is
+/// ignored.
+pub fn parse_partitions<'a>(base_dir: &str, path: &'a str) -> Vec<(&'a str, &'a str)> {
+    // Listed paths may not have the leading slash of the location.
+    let path = path
+        .trim_start_matches('/')
+        .strip_prefix(base_dir.trim_start_matches('/'))
+        .unwrap_or(path);
+    let dir = path
+        .rsplit_once('/')
+        .map(|(dir, _)| dir)
+        .unwrap_or_default();
+    dir.split('/')
+        .filter_map(|segment| segment.split_once('='))
+        .filter(|(key, _)| !key.is_empty())
+        .collect()
+}
+
+/// Infers partition columns from `files` below `base_dir`, all of them must have the same
+/// partition keys.
+pub fn infer_partition_columns(base_dir: &str, files: &[String]) -> Result<Vec<String>> {
+    let Some(first) = files.first() else {
+        return Ok(vec![]);
+    };
+    let columns = parse_partitions(base_dir, first)
+        .into_iter()
+        .map(|(key, _)| key.to_string())
+        .collect::<Vec<_>>();
+
+    for file in files {
+        let keys = parse_partitions(base_dir, file);
+        ensure!(
+            keys.len() == columns.len()
+                && keys
+                    .iter()
+                    .zip(columns.iter())
+                    .all(|((key, _), c)| key == c),
+            error::InconsistentPartitionsSnafu {
+                path: file,
+                columns: columns.join(", "),
+            }
+        );
+    }
+
+    Ok(columns)
+}
+
+/// Returns values of partition columns `fields` in `path` below `base_dir`, casting to the
+/// type of each field.
+pub fn partition_values(
+    base_dir: &str,
+    path: &str,
+    fields: &[Field],
+) -> DataFusionResult<Vec<ScalarValue>> {
+    let partitions = parse_partitions(base_dir, path);
+    fields
+        .iter()
+        .map(|field| {
+            match partitions
+                .iter()
+                .find(|(key, _)| *key == field.name())
+                .map(|(_, value)| *value)
+            {
+                Some(value) if value != HIVE_DEFAULT_PARTITION => {
+                    ScalarValue::try_from_string(value.to_string(), field.data_type())
+                }
+                _ => ScalarValue::try_from(field.data_type()),
+            }
+        })
+        .collect()
+}
+
+/// Prunes `files` below `base_dir` with the filters only referencing partition columns
+/// `fields`, so pruned files are never opened.
+pub fn prune_files(
+    base_dir: &str,
+    files: &[String],
+    fields: &[Field],
+    filters: &[Expr],
+) -> Result<Vec<String>> {
+    let filters = filters
+        .iter()
+        .filter(|filter| {
+            filter.to_columns().map_or(false, |columns| {
+                columns
+                    .iter()
+                    .all(|column| fields.iter().any(|field| field.name() == &column.name))
+            })
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+    let Some(predicate) = conjunction(filters) else {
+        return Ok(files.to_vec());
+    };
+    if files.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mask = evaluate_partition_predicate(base_dir, files, fields, &predicate)
+        .context(error::PrunePartitionsSnafu)?;
+
+    Ok(files
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| mask.is_valid(*i) && mask.value(*i))
+        .map(|(_, file)| file.clone())
+        .collect())
+}
+
+fn evaluate_partition_predicate(
+    base_dir: &str,
+    files: &[String],
+    fields: &[Field],
+    predicate: &Expr,
+) -> DataFusionResult<arrow::array::BooleanArray> {
+    let values = files
+        .iter()
+        .map(|file| partition_values(base_dir, file, fields))
+        .collect::<DataFusionResult<Vec<_>>>()?;
+    let columns = (0..fields.len())
+        .map(|i| ScalarValue::iter_to_array(values.iter().map(|v| v[i].clone())))
+        .collect::<DataFusionResult<Vec<_>>>()?;
+
+    let schema = Arc::new(Schema::new(fields.to_vec()));
+    let batch = RecordBatch::try_new(schema.clone(), columns)?;
+    let df_schema = schema.clone().to_dfschema_ref()?;
+    let predicate = create_physical_expr(predicate, &df_schema, &schema, &ExecutionProps::new())?;
+
+    let mask = predicate.evaluate(&batch)?.into_array(batch.num_rows());
+    Ok(mask.as_boolean().clone())
+}
+
+/// Returns the field of a partition column, whose values are strings.
+pub fn partition_field(name: &str) -> Field {
+    Field::new(name, DataType::Utf8, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::prelude::{col, lit};
+
+    use super::*;
+
+    fn test_files() -> Vec<String> {
+        vec![
+            "/lake/t/dt=2023-07-01/region=us/part-0.parquet".to_string(),
+            "/lake/t/dt=2023-07-01/region=eu/part-0.parquet".to_string(),
+            "/lake/t/dt=2023-07-02/region=us/part-0.parquet".to_string(),
+            format!("/lake/t/dt=2023-07-02/region={HIVE_DEFAULT_PARTITION}/part-0.parquet"),
+        ]
+    }
+
+    #[test]
+    fn test_parse_partitions() {
+        assert_eq!(
+            vec![("dt", "2023-07-01"), ("region", "us")],
+            parse_partitions("/lake/t/", "/lake/t/dt=2023-07-01/region=us/part-0.parquet")
+        );
+        assert!(parse_partitions("/", "a=1.parquet").is_empty());
+        assert!(parse_partitions("/lake/t/", "/lake/t/part-0.parquet").is_empty());
+
+        // Segments of the location aren't partitions, even without the leading slash.
+        assert_eq!(
+            vec![("dt", "2023-07-01")],
+            parse_partitions("/lake/env=prod/t/", "lake/env=prod/t/dt=2023-07-01/0.csv")
+        );
+        assert!(parse_partitions("/lake/env=prod/t/", "/lake/env=prod/t/0.csv").is_empty());
+    }
+
+    #[test]
+    fn test_partition_base_dir() {
+        assert_eq!(
+            "/lake/env=prod/t/",
+            partition_base_dir("/lake/env=prod/t/").unwrap()
+        );
+        assert_eq!(
+            "/lake/env=prod/",
+            partition_base_dir("/lake/env=prod/0.csv").unwrap()
+        );
+        assert_eq!(
+            "/lake/env=prod/t/",
+            partition_base_dir("s3://bucket/lake/env=prod/t/").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_infer_partition_columns() {
+        assert_eq!(
+            vec!["dt", "region"],
+            infer_partition_columns("/lake/t/", &test_files()).unwrap()
+        );
+        assert!(infer_partition_columns("/lake/t/", &[]).unwrap().is_empty());
+
+        let mut files = test_files();
+        files.push("/lake/t/dt=2023-07-03/part-0.parquet".to_string());
+        assert!(infer_partition_columns("/lake/t/", &files).is_err());
+    }
+
+    #[test]
+    fn test_partition_values() {
+        let fields = [
+            Field::new("dt", DataType::Utf8, true),
+            Field::new("hour", DataType::Int64, true),
+            Field::new("region", DataType::Utf8, true),
+        ];
+        assert_eq!(
+            vec![
+                ScalarValue::Utf8(Some("2023-07-01".to_string())),
+                ScalarValue::Int64(Some(8)),
+                ScalarValue::Utf8(None),
+            ],
+            partition_values(
+                "/t/",
+                &format!("/t/dt=2023-07-01/hour=8/region={HIVE_DEFAULT_PARTITION}/0.csv"),
+                &fields
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_prune_files() {
+        let files = test_files();
+        let fields = [partition_field("dt"), partition_field("region")];
+
+        let pruned = prune_files(
+            "/lake/t/",
+            &files,
+            &fields,
+            &[col("dt").eq(lit("2023-07-02"))],
+        )
+        .unwrap();
+        assert_eq!(files[2..].to_vec(), pruned);
+
+        let pruned = prune_files(
+            "/lake/t/",
+            &files,
+            &fields,
+            &[
+                col("dt").gt_eq(lit("2023-07-01")),
+                col("region").eq(lit("us")),
+            ],
+        )
+        .unwrap();
+        assert_eq!(vec![files[0].clone(), files[2].clone()], pruned);
+
+        let pruned = prune_files("/lake/t/", &files, &fields, &[col("region").is_null()]).unwrap();
+        assert_eq!(vec![files[3].clone()], pruned);
+
+        // Filters on other columns can't prune files.
+        let pruned = prune_files("/lake/t/", &files, &fields, &[col("host").eq(lit("a"))]).unwrap();
+        assert_eq!(files, pruned);
+    }
+}
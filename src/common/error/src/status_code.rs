@@ -47,6 +47,8 @@ pub enum StatusCode {
     PlanQuery = 3000,
     /// The query engine fail to execute query.
     EngineExecuteQuery = 3001,
+    /// The query exceeds its memory limit.
+    ResourcesExhausted = 3002,
     // ====== End of query related status code =========
 
     // ====== Begin of catalog related status code =====
@@ -109,6 +111,7 @@ impl StatusCode {
             | StatusCode::InvalidSyntax
             | StatusCode::PlanQuery
             | StatusCode::EngineExecuteQuery
+            | StatusCode::ResourcesExhausted
             | StatusCode::TableAlreadyExists
             | StatusCode::TableNotFound
             | StatusCode::TableColumnNotFound
@@ -140,6 +143,7 @@ impl StatusCode {
             StatusCode::Success
             | StatusCode::InvalidArguments
             | StatusCode::InvalidSyntax
+            | StatusCode::ResourcesExhausted
             | StatusCode::TableAlreadyExists
             | StatusCode::TableNotFound
             | StatusCode::TableColumnNotFound
@@ -14,10 +14,15 @@
 
 pub mod aggregate;
 pub mod expression;
+pub mod fulltext;
 pub mod function;
 pub mod function_registry;
+pub mod geo;
+pub mod ip;
 pub mod math;
 pub mod numpy;
+pub mod postgres;
+pub mod sketch;
 #[cfg(test)]
 pub(crate) mod test;
 mod timestamp;
@@ -12,26 +12,36 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod approx_percentile;
 mod argmax;
 mod argmin;
 mod diff;
+mod hll;
+mod hll_merge;
 mod mean;
 mod percentile;
 mod polyval;
 mod scipy_stats_norm_cdf;
 mod scipy_stats_norm_pdf;
+mod tdigest;
+mod tdigest_merge;
 
 use std::sync::Arc;
 
+pub use approx_percentile::ApproxPercentileAccumulatorCreator;
 pub use argmax::ArgmaxAccumulatorCreator;
 pub use argmin::ArgminAccumulatorCreator;
 use common_query::logical_plan::AggregateFunctionCreatorRef;
 pub use diff::DiffAccumulatorCreator;
+pub use hll::HllAccumulatorCreator;
+pub use hll_merge::HllMergeAccumulatorCreator;
 pub use mean::MeanAccumulatorCreator;
 pub use percentile::PercentileAccumulatorCreator;
 pub use polyval::PolyvalAccumulatorCreator;
 pub use scipy_stats_norm_cdf::ScipyStatsNormCdfAccumulatorCreator;
 pub use scipy_stats_norm_pdf::ScipyStatsNormPdfAccumulatorCreator;
+pub use tdigest::TDigestAccumulatorCreator;
+pub use tdigest_merge::TDigestMergeAccumulatorCreator;
 
 use crate::scalars::FunctionRegistry;
 
@@ -94,5 +104,10 @@ impl AggregateFunctions {
         register_aggr_func!("percentile", 2, PercentileAccumulatorCreator);
         register_aggr_func!("scipystatsnormcdf", 2, ScipyStatsNormCdfAccumulatorCreator);
         register_aggr_func!("scipystatsnormpdf", 2, ScipyStatsNormPdfAccumulatorCreator);
+        register_aggr_func!("hll", 1, HllAccumulatorCreator);
+        register_aggr_func!("hll_merge", 1, HllMergeAccumulatorCreator);
+        register_aggr_func!("tdigest", 1, TDigestAccumulatorCreator);
+        register_aggr_func!("tdigest_merge", 1, TDigestMergeAccumulatorCreator);
+        register_aggr_func!("approx_percentile", 2, ApproxPercentileAccumulatorCreator);
     }
 }
@@ -0,0 +1,234 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_function_macro::{as_aggr_func_creator, AggrFuncTypeStore};
+use common_query::error::{
+    BadAccumulatorImplSnafu, CreateAccumulatorSnafu, DowncastVectorSnafu, InvalidFuncArgsSnafu,
+    InvalidInputColSnafu, InvalidInputStateSnafu, Result,
+};
+use common_query::logical_plan::{Accumulator, AggregateFunctionCreator};
+use common_query::prelude::*;
+use datatypes::prelude::*;
+use datatypes::value::OrderedFloat;
+use datatypes::vectors::Float64Vector;
+use snafu::{ensure, OptionExt};
+
+use crate::scalars::function::binary_at;
+use crate::scalars::sketch::TDigest;
+
+/// Estimates the `p`-th percentile, `p` is in range `[0, 100]`, by a t-digest. Unlike
+/// `percentile`, the state is the digest, so the memory used is bounded.
+#[derive(Debug, Default)]
+pub struct ApproxPercentile {
+    digest: TDigest,
+    p: Option<f64>,
+}
+
+impl ApproxPercentile {
+    fn set_p(&mut self, p: f64) -> Result<()> {
+        ensure!(
+            (0.0..=100.0).contains(&p),
+            InvalidFuncArgsSnafu {
+                err_msg: format!("The percentile must be in range [0, 100], have: {p}"),
+            }
+        );
+        if let Some(old) = self.p {
+            ensure!(old == p, InvalidInputColSnafu);
+        } else {
+            self.p = Some(p);
+        }
+        Ok(())
+    }
+}
+
+impl Accumulator for ApproxPercentile {
+    fn state(&self) -> Result<Vec<Value>> {
+        Ok(vec![Value::from(self.digest.to_bytes()), self.p.into()])
+    }
+
+    fn update_batch(&mut self, values: &[VectorRef]) -> Result<()> {
+        if values.is_empty() {
+            return Ok(());
+        }
+        ensure!(values.len() == 2, InvalidInputStateSnafu);
+        ensure!(values[0].len() == values[1].len(), InvalidInputStateSnafu);
+
+        if values[0].len() == 0 {
+            return Ok(());
+        }
+
+        // The percentile is expected to be a float64 constant.
+        let x = &values[1];
+        // `get(0)` is safe because we have checked `values[1].len() == values[0].len() != 0`
+        let first = x.get(0);
+        for i in 1..x.len() {
+            ensure!(first == x.get(i), InvalidInputColSnafu);
+        }
+        let Value::Float64(OrderedFloat(p)) = first else {
+            return InvalidFuncArgsSnafu {
+                err_msg: "expecting \"APPROX_PERCENTILE\" function's second argument to be float64",
+            }
+            .fail();
+        };
+        self.set_p(p)?;
+
+        let column = &values[0];
+        for i in 0..column.len() {
+            self.digest.add_value(column.get_ref(i));
+        }
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[VectorRef]) -> Result<()> {
+        if states.is_empty() {
+            return Ok(());
+        }
+        ensure!(
+            states.len() == 2,
+            BadAccumulatorImplSnafu {
+                err_msg: "expect 2 states in `merge_batch`",
+            }
+        );
+
+        let p = &states[1];
+        let p = p
+            .as_any()
+            .downcast_ref::<Float64Vector>()
+            .with_context(|| DowncastVectorSnafu {
+                err_msg: format!(
+                    "expect float64vector, got vector type {}",
+                    p.vector_type_name()
+                ),
+            })?;
+        let digests = &states[0];
+        for i in 0..digests.len() {
+            let (Some(bytes), Value::Float64(OrderedFloat(p))) = (binary_at(digests, i), p.get(i))
+            else {
+                continue;
+            };
+            self.set_p(p)?;
+            self.digest.merge(&TDigest::from_bytes(bytes)?);
+        }
+        Ok(())
+    }
+
+    fn evaluate(&self) -> Result<Value> {
+        let Some(p) = self.p else {
+            return Ok(Value::Null);
+        };
+        Ok(self.digest.quantile(p / 100.0).into())
+    }
+}
+
+#[as_aggr_func_creator]
+#[derive(Debug, Default, AggrFuncTypeStore)]
+pub struct ApproxPercentileAccumulatorCreator {}
+
+impl AggregateFunctionCreator for ApproxPercentileAccumulatorCreator {
+    fn creator(&self) -> AccumulatorCreatorFunction {
+        let creator: AccumulatorCreatorFunction = Arc::new(move |types: &[ConcreteDataType]| {
+            let input_type = &types[0];
+            ensure!(
+                input_type.is_signed() || input_type.is_unsigned() || input_type.is_float(),
+                CreateAccumulatorSnafu {
+                    err_msg: format!(
+                        "\"APPROX_PERCENTILE\" aggregate function not support data type {:?}",
+                        input_type.logical_type_id(),
+                    ),
+                }
+            );
+            Ok(Box::new(ApproxPercentile::default()))
+        });
+        creator
+    }
+
+    fn output_type(&self) -> Result<ConcreteDataType> {
+        let input_types = self.input_types()?;
+        ensure!(input_types.len() == 2, InvalidInputStateSnafu);
+        Ok(ConcreteDataType::float64_datatype())
+    }
+
+    fn state_types(&self) -> Result<Vec<ConcreteDataType>> {
+        let input_types = self.input_types()?;
+        ensure!(input_types.len() == 2, InvalidInputStateSnafu);
+        Ok(vec![
+            ConcreteDataType::binary_datatype(),
+            ConcreteDataType::float64_datatype(),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use datatypes::vectors::{BinaryVector, ConstantVector, Int64Vector};
+
+    use super::*;
+
+    fn p_vector(p: f64, len: usize) -> VectorRef {
+        Arc::new(ConstantVector::new(
+            Arc::new(Float64Vector::from_vec(vec![p])),
+            len,
+        ))
+    }
+
+    #[test]
+    fn test_update_batch() {
+        let mut approx_percentile = ApproxPercentile::default();
+        approx_percentile.update_batch(&[]).unwrap();
+        assert_eq!(Value::Null, approx_percentile.evaluate().unwrap());
+
+        let v: Vec<VectorRef> = vec![
+            Arc::new(Int64Vector::from(vec![Some(-1), None, Some(1), Some(2)])),
+            p_vector(0.0, 4),
+        ];
+        approx_percentile.update_batch(&v).unwrap();
+        assert_eq!(Value::from(-1.0), approx_percentile.evaluate().unwrap());
+
+        // the percentile can't be changed
+        let v: Vec<VectorRef> = vec![Arc::new(Int64Vector::from_vec(vec![3])), p_vector(50.0, 1)];
+        assert!(approx_percentile.update_batch(&v).is_err());
+
+        let mut approx_percentile = ApproxPercentile::default();
+        let v: Vec<VectorRef> = vec![Arc::new(Int64Vector::from_vec(vec![1])), p_vector(101.0, 1)];
+        assert!(approx_percentile.update_batch(&v).is_err());
+    }
+
+    #[test]
+    fn test_merge_batch() {
+        let mut approx_percentile = ApproxPercentile::default();
+        let v: Vec<VectorRef> = vec![
+            Arc::new(Int64Vector::from_vec((0..1000).collect())),
+            p_vector(90.0, 1000),
+        ];
+        approx_percentile.update_batch(&v).unwrap();
+        let state = approx_percentile.state().unwrap();
+        let Value::Binary(digest) = &state[0] else {
+            unreachable!()
+        };
+
+        let mut merged = ApproxPercentile::default();
+        let states: Vec<VectorRef> = vec![
+            Arc::new(BinaryVector::from(vec![Some(digest.to_vec()), None])),
+            Arc::new(Float64Vector::from(vec![Some(90.0), None])),
+        ];
+        merged.update_batch(&[]).unwrap();
+        merged.merge_batch(&states).unwrap();
+        let Value::Float64(OrderedFloat(p90)) = merged.evaluate().unwrap() else {
+            unreachable!()
+        };
+        assert!((p90 - 899.1).abs() < 10.0);
+    }
+}
@@ -0,0 +1,156 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_function_macro::{as_aggr_func_creator, AggrFuncTypeStore};
+use common_query::error::{
+    BadAccumulatorImplSnafu, CreateAccumulatorSnafu, InvalidInputStateSnafu, Result,
+};
+use common_query::logical_plan::{Accumulator, AggregateFunctionCreator};
+use common_query::prelude::*;
+use datatypes::prelude::*;
+use snafu::ensure;
+
+use crate::scalars::function::binary_at;
+use crate::scalars::sketch::HyperLogLog;
+
+/// Builds a HyperLogLog sketch of the values, the sketch is returned as binary so it
+/// could be stored and merged by `hll_merge` later. `hll_count` reads the estimated
+/// number of distinct values from the sketch.
+#[derive(Debug, Default)]
+pub struct Hll {
+    hll: HyperLogLog,
+}
+
+impl Accumulator for Hll {
+    fn state(&self) -> Result<Vec<Value>> {
+        Ok(vec![Value::from(self.hll.to_bytes())])
+    }
+
+    fn update_batch(&mut self, values: &[VectorRef]) -> Result<()> {
+        if values.is_empty() {
+            return Ok(());
+        }
+        ensure!(values.len() == 1, InvalidInputStateSnafu);
+
+        let column = &values[0];
+        for i in 0..column.len() {
+            self.hll.add(column.get_ref(i));
+        }
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[VectorRef]) -> Result<()> {
+        if states.is_empty() {
+            return Ok(());
+        }
+        ensure!(
+            states.len() == 1,
+            BadAccumulatorImplSnafu {
+                err_msg: "expect 1 states in `merge_batch`",
+            }
+        );
+
+        let sketches = &states[0];
+        for i in 0..sketches.len() {
+            if let Some(bytes) = binary_at(sketches, i) {
+                self.hll.merge(&HyperLogLog::from_bytes(bytes)?);
+            }
+        }
+        Ok(())
+    }
+
+    fn evaluate(&self) -> Result<Value> {
+        Ok(Value::from(self.hll.to_bytes()))
+    }
+}
+
+#[as_aggr_func_creator]
+#[derive(Debug, Default, AggrFuncTypeStore)]
+pub struct HllAccumulatorCreator {}
+
+impl AggregateFunctionCreator for HllAccumulatorCreator {
+    fn creator(&self) -> AccumulatorCreatorFunction {
+        let creator: AccumulatorCreatorFunction = Arc::new(move |types: &[ConcreteDataType]| {
+            let input_type = &types[0];
+            ensure!(
+                !matches!(input_type, ConcreteDataType::List(_)),
+                CreateAccumulatorSnafu {
+                    err_msg: format!(
+                        "\"HLL\" aggregate function not support data type {:?}",
+                        input_type.logical_type_id(),
+                    ),
+                }
+            );
+            Ok(Box::new(Hll::default()))
+        });
+        creator
+    }
+
+    fn output_type(&self) -> Result<ConcreteDataType> {
+        let input_types = self.input_types()?;
+        ensure!(input_types.len() == 1, InvalidInputStateSnafu);
+        Ok(ConcreteDataType::binary_datatype())
+    }
+
+    fn state_types(&self) -> Result<Vec<ConcreteDataType>> {
+        let input_types = self.input_types()?;
+        ensure!(input_types.len() == 1, InvalidInputStateSnafu);
+        Ok(vec![ConcreteDataType::binary_datatype()])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use datatypes::vectors::{BinaryVector, StringVector};
+
+    use super::*;
+
+    fn count(value: Value) -> u64 {
+        let Value::Binary(bytes) = value else {
+            unreachable!()
+        };
+        HyperLogLog::from_bytes(&bytes).unwrap().count()
+    }
+
+    #[test]
+    fn test_update_and_merge_batch() {
+        let mut hll = Hll::default();
+        hll.update_batch(&[]).unwrap();
+        assert_eq!(0, count(hll.evaluate().unwrap()));
+
+        let v: Vec<VectorRef> = vec![Arc::new(StringVector::from(vec![
+            Some("a"),
+            None,
+            Some("b"),
+            Some("a"),
+        ]))];
+        hll.update_batch(&v).unwrap();
+        assert_eq!(2, count(hll.evaluate().unwrap()));
+
+        let mut other = Hll::default();
+        let v: Vec<VectorRef> = vec![Arc::new(StringVector::from(vec!["b", "c"]))];
+        other.update_batch(&v).unwrap();
+        let Value::Binary(state) = other.state().unwrap().remove(0) else {
+            unreachable!()
+        };
+        let states: Vec<VectorRef> = vec![Arc::new(BinaryVector::from(vec![
+            Some(state.to_vec()),
+            None,
+        ]))];
+        hll.merge_batch(&states).unwrap();
+        assert_eq!(3, count(hll.evaluate().unwrap()));
+    }
+}
@@ -0,0 +1,145 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_function_macro::{as_aggr_func_creator, AggrFuncTypeStore};
+use common_query::error::{
+    BadAccumulatorImplSnafu, CreateAccumulatorSnafu, InvalidInputStateSnafu, Result,
+};
+use common_query::logical_plan::{Accumulator, AggregateFunctionCreator};
+use common_query::prelude::*;
+use datatypes::prelude::*;
+use snafu::ensure;
+
+use crate::scalars::function::binary_at;
+use crate::scalars::sketch::HyperLogLog;
+
+/// Merges HyperLogLog sketches built by `hll`, e.g. to roll up sketches stored in
+/// a table into a coarser granularity.
+#[derive(Debug, Default)]
+pub struct HllMerge {
+    hll: HyperLogLog,
+}
+
+impl HllMerge {
+    fn merge_sketches(&mut self, sketches: &VectorRef) -> Result<()> {
+        for i in 0..sketches.len() {
+            if let Some(bytes) = binary_at(sketches, i) {
+                self.hll.merge(&HyperLogLog::from_bytes(bytes)?);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Accumulator for HllMerge {
+    fn state(&self) -> Result<Vec<Value>> {
+        Ok(vec![Value::from(self.hll.to_bytes())])
+    }
+
+    fn update_batch(&mut self, values: &[VectorRef]) -> Result<()> {
+        if values.is_empty() {
+            return Ok(());
+        }
+        ensure!(values.len() == 1, InvalidInputStateSnafu);
+        self.merge_sketches(&values[0])
+    }
+
+    fn merge_batch(&mut self, states: &[VectorRef]) -> Result<()> {
+        if states.is_empty() {
+            return Ok(());
+        }
+        ensure!(
+            states.len() == 1,
+            BadAccumulatorImplSnafu {
+                err_msg: "expect 1 states in `merge_batch`",
+            }
+        );
+        self.merge_sketches(&states[0])
+    }
+
+    fn evaluate(&self) -> Result<Value> {
+        Ok(Value::from(self.hll.to_bytes()))
+    }
+}
+
+#[as_aggr_func_creator]
+#[derive(Debug, Default, AggrFuncTypeStore)]
+pub struct HllMergeAccumulatorCreator {}
+
+impl AggregateFunctionCreator for HllMergeAccumulatorCreator {
+    fn creator(&self) -> AccumulatorCreatorFunction {
+        let creator: AccumulatorCreatorFunction = Arc::new(move |types: &[ConcreteDataType]| {
+            let input_type = &types[0];
+            ensure!(
+                matches!(input_type, ConcreteDataType::Binary(_)),
+                CreateAccumulatorSnafu {
+                    err_msg: format!(
+                        "\"HLL_MERGE\" aggregate function not support data type {:?}",
+                        input_type.logical_type_id(),
+                    ),
+                }
+            );
+            Ok(Box::new(HllMerge::default()))
+        });
+        creator
+    }
+
+    fn output_type(&self) -> Result<ConcreteDataType> {
+        let input_types = self.input_types()?;
+        ensure!(input_types.len() == 1, InvalidInputStateSnafu);
+        Ok(ConcreteDataType::binary_datatype())
+    }
+
+    fn state_types(&self) -> Result<Vec<ConcreteDataType>> {
+        let input_types = self.input_types()?;
+        ensure!(input_types.len() == 1, InvalidInputStateSnafu);
+        Ok(vec![ConcreteDataType::binary_datatype()])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use datatypes::value::ValueRef;
+    use datatypes::vectors::BinaryVector;
+
+    use super::*;
+
+    #[test]
+    fn test_update_batch() {
+        let sketch = |values: &[&str]| {
+            let mut hll = HyperLogLog::default();
+            for v in values {
+                hll.add(ValueRef::String(v));
+            }
+            Some(hll.to_bytes())
+        };
+
+        let mut hll_merge = HllMerge::default();
+        let v: Vec<VectorRef> = vec![Arc::new(BinaryVector::from(vec![
+            sketch(&["a", "b"]),
+            None,
+            sketch(&["b", "c", "d"]),
+        ]))];
+        hll_merge.update_batch(&v).unwrap();
+        let Value::Binary(bytes) = hll_merge.evaluate().unwrap() else {
+            unreachable!()
+        };
+        assert_eq!(4, HyperLogLog::from_bytes(&bytes).unwrap().count());
+
+        let v: Vec<VectorRef> = vec![Arc::new(BinaryVector::from(vec![Some(vec![0u8])]))];
+        assert!(hll_merge.update_batch(&v).is_err());
+    }
+}
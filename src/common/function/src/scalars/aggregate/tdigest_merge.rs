@@ -0,0 +1,147 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_function_macro::{as_aggr_func_creator, AggrFuncTypeStore};
+use common_query::error::{
+    BadAccumulatorImplSnafu, CreateAccumulatorSnafu, InvalidInputStateSnafu, Result,
+};
+use common_query::logical_plan::{Accumulator, AggregateFunctionCreator};
+use common_query::prelude::*;
+use datatypes::prelude::*;
+use snafu::ensure;
+
+use crate::scalars::function::binary_at;
+use crate::scalars::sketch::TDigest;
+
+/// Merges t-digests built by `tdigest`, e.g. to roll up digests stored in a table
+/// into a coarser granularity.
+#[derive(Debug, Default)]
+pub struct TDigestMerge {
+    digest: TDigest,
+}
+
+impl TDigestMerge {
+    fn merge_digests(&mut self, digests: &VectorRef) -> Result<()> {
+        for i in 0..digests.len() {
+            if let Some(bytes) = binary_at(digests, i) {
+                self.digest.merge(&TDigest::from_bytes(bytes)?);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Accumulator for TDigestMerge {
+    fn state(&self) -> Result<Vec<Value>> {
+        Ok(vec![Value::from(self.digest.to_bytes())])
+    }
+
+    fn update_batch(&mut self, values: &[VectorRef]) -> Result<()> {
+        if values.is_empty() {
+            return Ok(());
+        }
+        ensure!(values.len() == 1, InvalidInputStateSnafu);
+        self.merge_digests(&values[0])
+    }
+
+    fn merge_batch(&mut self, states: &[VectorRef]) -> Result<()> {
+        if states.is_empty() {
+            return Ok(());
+        }
+        ensure!(
+            states.len() == 1,
+            BadAccumulatorImplSnafu {
+                err_msg: "expect 1 states in `merge_batch`",
+            }
+        );
+        self.merge_digests(&states[0])
+    }
+
+    fn evaluate(&self) -> Result<Value> {
+        Ok(Value::from(self.digest.to_bytes()))
+    }
+}
+
+#[as_aggr_func_creator]
+#[derive(Debug, Default, AggrFuncTypeStore)]
+pub struct TDigestMergeAccumulatorCreator {}
+
+impl AggregateFunctionCreator for TDigestMergeAccumulatorCreator {
+    fn creator(&self) -> AccumulatorCreatorFunction {
+        let creator: AccumulatorCreatorFunction = Arc::new(move |types: &[ConcreteDataType]| {
+            let input_type = &types[0];
+            ensure!(
+                matches!(input_type, ConcreteDataType::Binary(_)),
+                CreateAccumulatorSnafu {
+                    err_msg: format!(
+                        "\"TDIGEST_MERGE\" aggregate function not support data type {:?}",
+                        input_type.logical_type_id(),
+                    ),
+                }
+            );
+            Ok(Box::new(TDigestMerge::default()))
+        });
+        creator
+    }
+
+    fn output_type(&self) -> Result<ConcreteDataType> {
+        let input_types = self.input_types()?;
+        ensure!(input_types.len() == 1, InvalidInputStateSnafu);
+        Ok(ConcreteDataType::binary_datatype())
+    }
+
+    fn state_types(&self) -> Result<Vec<ConcreteDataType>> {
+        let input_types = self.input_types()?;
+        ensure!(input_types.len() == 1, InvalidInputStateSnafu);
+        Ok(vec![ConcreteDataType::binary_datatype()])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use datatypes::vectors::BinaryVector;
+
+    use super::*;
+
+    #[test]
+    fn test_update_batch() {
+        let digest = |values: std::ops::Range<i32>| {
+            let mut digest = TDigest::default();
+            for v in values {
+                digest.add(v as f64);
+            }
+            Some(digest.to_bytes())
+        };
+
+        let mut tdigest_merge = TDigestMerge::default();
+        let v: Vec<VectorRef> = vec![Arc::new(BinaryVector::from(vec![
+            digest(0..500),
+            None,
+            digest(500..1000),
+        ]))];
+        tdigest_merge.update_batch(&v).unwrap();
+        let Value::Binary(bytes) = tdigest_merge.evaluate().unwrap() else {
+            unreachable!()
+        };
+        let digest = TDigest::from_bytes(&bytes).unwrap();
+        assert_eq!(Some(0.0), digest.quantile(0.0));
+        assert_eq!(Some(999.0), digest.quantile(1.0));
+        assert!((digest.quantile(0.5).unwrap() - 499.5).abs() < 10.0);
+
+        let v: Vec<VectorRef> = vec![Arc::new(BinaryVector::from(vec![Some(vec![0u8])]))];
+        assert!(tdigest_merge.update_batch(&v).is_err());
+    }
+}
@@ -0,0 +1,171 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::sync::Arc;
+
+use common_query::error::{InvalidFuncArgsSnafu, Result};
+use common_query::prelude::{Signature, TypeSignature, Volatility};
+use datatypes::prelude::ConcreteDataType;
+use datatypes::schema::FulltextAnalyzer;
+use datatypes::vectors::{BooleanVector, VectorRef};
+use snafu::ensure;
+
+use crate::scalars::function::{string_at, Function, FunctionContext};
+
+const NAME: &str = "matches";
+
+/// `matches(text, query[, analyzer])` returns whether the text contains all terms
+/// of the query, both are split into terms by the analyzer, `English` by default.
+///
+/// Row groups and rows of SST files can be skipped by the full-text index of the
+/// column if the analyzer is the one the column is indexed with.
+#[derive(Clone, Debug, Default)]
+pub struct MatchesFunction;
+
+impl Function for MatchesFunction {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn return_type(&self, _input_types: &[ConcreteDataType]) -> Result<ConcreteDataType> {
+        Ok(ConcreteDataType::boolean_datatype())
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::one_of(
+            vec![
+                TypeSignature::Exact(vec![
+                    ConcreteDataType::string_datatype(),
+                    ConcreteDataType::string_datatype(),
+                ]),
+                TypeSignature::Exact(vec![
+                    ConcreteDataType::string_datatype(),
+                    ConcreteDataType::string_datatype(),
+                    ConcreteDataType::string_datatype(),
+                ]),
+            ],
+            Volatility::Immutable,
+        )
+    }
+
+    fn eval(&self, _func_ctx: FunctionContext, columns: &[VectorRef]) -> Result<VectorRef> {
+        ensure!(
+            columns.len() == 2 || columns.len() == 3,
+            InvalidFuncArgsSnafu {
+                err_msg: format!(
+                    "The length of the args is not correct, expect two or three, have: {}",
+                    columns.len()
+                ),
+            }
+        );
+
+        // The query is usually a constant, so terms of the last query are reused.
+        let mut last_query: Option<(String, FulltextAnalyzer, Vec<String>)> = None;
+        let mut results = Vec::with_capacity(columns[0].len());
+        for i in 0..columns[0].len() {
+            let (Some(text), Some(query)) = (
+                string_at(&columns[0], i),
+                string_at(&columns[1], i),
+            ) else {
+                results.push(None);
+                continue;
+            };
+            let analyzer = match columns.get(2) {
+                Some(analyzers) => match string_at(analyzers, i) {
+                    Some(name) => name
+                        .parse::<FulltextAnalyzer>()
+                        .map_err(|err_msg| InvalidFuncArgsSnafu { err_msg }.build())?,
+                    None => {
+                        results.push(None);
+                        continue;
+                    }
+                },
+                None => FulltextAnalyzer::default(),
+            };
+
+            let cached = matches!(&last_query, Some((q, a, _)) if q == query && *a == analyzer);
+            if !cached {
+                last_query = Some((query.to_string(), analyzer, analyzer.tokenize(query)));
+            }
+            let terms = &last_query.as_ref().unwrap().2;
+            results.push(Some(analyzer.matches(text, terms)));
+        }
+        Ok(Arc::new(BooleanVector::from(results)))
+    }
+}
+
+impl fmt::Display for MatchesFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MATCHES")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datatypes::prelude::Value;
+    use datatypes::vectors::StringVector;
+
+    use super::*;
+
+    #[test]
+    fn test_matches() {
+        let f = MatchesFunction::default();
+        assert_eq!("matches", f.name());
+        assert_eq!(
+            ConcreteDataType::boolean_datatype(),
+            f.return_type(&[]).unwrap()
+        );
+
+        let texts: VectorRef = Arc::new(StringVector::from(vec![
+            Some("Connection refused by host"),
+            Some("connection reset"),
+            None,
+            Some("Disk is full"),
+        ]));
+        let queries: VectorRef = Arc::new(StringVector::from(vec![
+            "connection refused",
+            "connection refused",
+            "connection",
+            "Disk",
+        ]));
+        let vector = f
+            .eval(
+                FunctionContext::default(),
+                &[texts.clone(), queries.clone()],
+            )
+            .unwrap();
+        assert_eq!(Value::Boolean(true), vector.get(0));
+        assert_eq!(Value::Boolean(false), vector.get(1));
+        assert_eq!(Value::Null, vector.get(2));
+        assert_eq!(Value::Boolean(true), vector.get(3));
+
+        let analyzers: VectorRef = Arc::new(StringVector::from(vec!["whitespace"; 4]));
+        let vector = f
+            .eval(
+                FunctionContext::default(),
+                &[texts.clone(), queries.clone(), analyzers],
+            )
+            .unwrap();
+        assert_eq!(Value::Boolean(false), vector.get(0));
+        assert_eq!(Value::Boolean(false), vector.get(1));
+        assert_eq!(Value::Null, vector.get(2));
+        assert_eq!(Value::Boolean(true), vector.get(3));
+
+        let analyzers: VectorRef = Arc::new(StringVector::from(vec!["unknown"; 4]));
+        assert!(f
+            .eval(FunctionContext::default(), &[texts, queries, analyzers])
+            .is_err());
+    }
+}
@@ -19,6 +19,7 @@ use chrono_tz::Tz;
 use common_query::error::Result;
 use common_query::prelude::Signature;
 use datatypes::data_type::ConcreteDataType;
+use datatypes::value::ValueRef;
 use datatypes::vectors::VectorRef;
 
 #[derive(Clone)]
@@ -49,3 +50,19 @@ pub trait Function: fmt::Display + Sync + Send {
 }
 
 pub type FunctionRef = Arc<dyn Function>;
+
+/// Returns the string in the row, or `None` if the row is null.
+pub(crate) fn string_at(vector: &VectorRef, row: usize) -> Option<&str> {
+    match vector.get_ref(row) {
+        ValueRef::String(s) => Some(s),
+        _ => None,
+    }
+}
+
+/// Returns the bytes in the row, or `None` if the row is null.
+pub(crate) fn binary_at(vector: &VectorRef, row: usize) -> Option<&[u8]> {
+    match vector.get_ref(row) {
+        ValueRef::Binary(b) => Some(b),
+        _ => None,
+    }
+}
@@ -19,9 +19,14 @@ use std::sync::{Arc, RwLock};
 use once_cell::sync::Lazy;
 
 use crate::scalars::aggregate::{AggregateFunctionMetaRef, AggregateFunctions};
+use crate::scalars::fulltext::FulltextFunction;
 use crate::scalars::function::FunctionRef;
+use crate::scalars::geo::GeoFunction;
+use crate::scalars::ip::IpFunction;
 use crate::scalars::math::MathFunction;
 use crate::scalars::numpy::NumpyFunction;
+use crate::scalars::postgres::PostgresFunction;
+use crate::scalars::sketch::SketchFunction;
 use crate::scalars::timestamp::TimestampFunction;
 
 #[derive(Default)]
@@ -75,6 +80,11 @@ pub static FUNCTION_REGISTRY: Lazy<Arc<FunctionRegistry>> = Lazy::new(|| {
     MathFunction::register(&function_registry);
     NumpyFunction::register(&function_registry);
     TimestampFunction::register(&function_registry);
+    PostgresFunction::register(&function_registry);
+    FulltextFunction::register(&function_registry);
+    IpFunction::register(&function_registry);
+    GeoFunction::register(&function_registry);
+    SketchFunction::register(&function_registry);
 
     AggregateFunctions::register(&function_registry);
 
@@ -0,0 +1,299 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::sync::Arc;
+
+use common_query::error::{InvalidFuncArgsSnafu, Result};
+use common_query::prelude::{Signature, Volatility};
+use datatypes::prelude::ConcreteDataType;
+use datatypes::value::Value;
+use datatypes::vectors::{Float64Vector, StringVector, VectorRef};
+use snafu::ensure;
+
+use crate::scalars::function::{string_at, Function, FunctionContext};
+
+const BASE32: &[u8] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+const MAX_PRECISION: i64 = 12;
+
+/// Encodes the point into a geohash of `precision` characters, returns `None` if the
+/// point is out of range.
+fn encode(lat: f64, lng: f64, precision: usize) -> Option<String> {
+    if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lng) {
+        return None;
+    }
+
+    let (mut lat_range, mut lng_range) = ((-90.0, 90.0), (-180.0, 180.0));
+    let mut hash = String::with_capacity(precision);
+    // Bits alternate between longitude and latitude, starting from longitude.
+    let mut is_lng = true;
+    while hash.len() < precision {
+        let mut index = 0;
+        for _ in 0..5 {
+            let (range, value) = if is_lng {
+                (&mut lng_range, lng)
+            } else {
+                (&mut lat_range, lat)
+            };
+            let mid = (range.0 + range.1) / 2.0;
+            index <<= 1;
+            if value >= mid {
+                index |= 1;
+                range.0 = mid;
+            } else {
+                range.1 = mid;
+            }
+            is_lng = !is_lng;
+        }
+        hash.push(BASE32[index] as char);
+    }
+    Some(hash)
+}
+
+/// Decodes the geohash into the center `(lat, lng)` of its cell, returns `None` if the
+/// geohash is empty or contains invalid characters.
+fn decode(hash: &str) -> Option<(f64, f64)> {
+    if hash.is_empty() {
+        return None;
+    }
+
+    let (mut lat_range, mut lng_range) = ((-90.0, 90.0), (-180.0, 180.0));
+    let mut is_lng = true;
+    for c in hash.bytes() {
+        let index = BASE32.iter().position(|b| *b == c.to_ascii_lowercase())?;
+        for bit in (0..5).rev() {
+            let range = if is_lng {
+                &mut lng_range
+            } else {
+                &mut lat_range
+            };
+            let mid = (range.0 + range.1) / 2.0;
+            if (index >> bit) & 1 == 1 {
+                range.0 = mid;
+            } else {
+                range.1 = mid;
+            }
+            is_lng = !is_lng;
+        }
+    }
+    Some((
+        (lat_range.0 + lat_range.1) / 2.0,
+        (lng_range.0 + lng_range.1) / 2.0,
+    ))
+}
+
+fn f64_at(vector: &VectorRef, row: usize) -> Option<f64> {
+    match vector.get(row) {
+        Value::Float64(v) => Some(v.into_inner()),
+        _ => None,
+    }
+}
+
+/// `geohash_encode(lat, lng, precision)` encodes the point into a geohash of
+/// `precision` characters, which is in range `[1, 12]`. Returns null if the point
+/// is out of range.
+#[derive(Clone, Debug, Default)]
+pub struct GeohashEncodeFunction;
+
+impl Function for GeohashEncodeFunction {
+    fn name(&self) -> &str {
+        "geohash_encode"
+    }
+
+    fn return_type(&self, _input_types: &[ConcreteDataType]) -> Result<ConcreteDataType> {
+        Ok(ConcreteDataType::string_datatype())
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::exact(
+            vec![
+                ConcreteDataType::float64_datatype(),
+                ConcreteDataType::float64_datatype(),
+                ConcreteDataType::int64_datatype(),
+            ],
+            Volatility::Immutable,
+        )
+    }
+
+    fn eval(&self, _func_ctx: FunctionContext, columns: &[VectorRef]) -> Result<VectorRef> {
+        ensure!(
+            columns.len() == 3,
+            InvalidFuncArgsSnafu {
+                err_msg: format!(
+                    "The length of the args is not correct, expect exactly three, have: {}",
+                    columns.len()
+                ),
+            }
+        );
+
+        let mut results = Vec::with_capacity(columns[0].len());
+        for i in 0..columns[0].len() {
+            let precision = match columns[2].get(i) {
+                Value::Int64(precision) => precision,
+                _ => {
+                    results.push(None);
+                    continue;
+                }
+            };
+            ensure!(
+                (1..=MAX_PRECISION).contains(&precision),
+                InvalidFuncArgsSnafu {
+                    err_msg: format!(
+                        "The precision of geohash must be in range [1, {MAX_PRECISION}], have: {precision}"
+                    ),
+                }
+            );
+            let hash = match (f64_at(&columns[0], i), f64_at(&columns[1], i)) {
+                (Some(lat), Some(lng)) => encode(lat, lng, precision as usize),
+                _ => None,
+            };
+            results.push(hash);
+        }
+        Ok(Arc::new(StringVector::from(results)))
+    }
+}
+
+impl fmt::Display for GeohashEncodeFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "GEOHASH_ENCODE")
+    }
+}
+
+/// `geohash_lat(geohash)` and `geohash_lng(geohash)` decode the latitude and longitude
+/// of the center of the geohash cell. Returns null if the geohash is invalid.
+#[derive(Clone, Debug)]
+pub struct GeohashDecodeFunction {
+    name: &'static str,
+    latitude: bool,
+}
+
+impl GeohashDecodeFunction {
+    pub fn latitude() -> Self {
+        Self {
+            name: "geohash_lat",
+            latitude: true,
+        }
+    }
+
+    pub fn longitude() -> Self {
+        Self {
+            name: "geohash_lng",
+            latitude: false,
+        }
+    }
+}
+
+impl Function for GeohashDecodeFunction {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn return_type(&self, _input_types: &[ConcreteDataType]) -> Result<ConcreteDataType> {
+        Ok(ConcreteDataType::float64_datatype())
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::exact(
+            vec![ConcreteDataType::string_datatype()],
+            Volatility::Immutable,
+        )
+    }
+
+    fn eval(&self, _func_ctx: FunctionContext, columns: &[VectorRef]) -> Result<VectorRef> {
+        ensure!(
+            columns.len() == 1,
+            InvalidFuncArgsSnafu {
+                err_msg: format!(
+                    "The length of the args is not correct, expect exactly one, have: {}",
+                    columns.len()
+                ),
+            }
+        );
+
+        let results = (0..columns[0].len())
+            .map(|i| {
+                let (lat, lng) = decode(string_at(&columns[0], i)?)?;
+                Some(if self.latitude { lat } else { lng })
+            })
+            .collect::<Vec<_>>();
+        Ok(Arc::new(Float64Vector::from(results)))
+    }
+}
+
+impl fmt::Display for GeohashDecodeFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name.to_ascii_uppercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datatypes::scalars::ScalarVector;
+    use datatypes::vectors::Int64Vector;
+
+    use super::*;
+
+    #[test]
+    fn test_encode_decode() {
+        assert_eq!(Some("wx4g0bm6".to_string()), encode(39.9042, 116.4074, 8));
+        assert_eq!(
+            Some("u4pruydqqvj".to_string()),
+            encode(57.64911, 10.40744, 11)
+        );
+        assert_eq!(None, encode(91.0, 0.0, 8));
+
+        let (lat, lng) = decode("u4pruydqqvj").unwrap();
+        assert!((lat - 57.64911).abs() < 1e-5);
+        assert!((lng - 10.40744).abs() < 1e-5);
+        assert_eq!(decode("u4pruydqqvj"), decode("U4PRUYDQQVJ"));
+        assert_eq!(None, decode(""));
+        assert_eq!(None, decode("u4pa"));
+    }
+
+    #[test]
+    fn test_geohash_functions() {
+        let f = GeohashEncodeFunction::default();
+        assert_eq!("geohash_encode", f.name());
+        let args: Vec<VectorRef> = vec![
+            Arc::new(Float64Vector::from(vec![Some(57.64911), None, Some(100.0)])),
+            Arc::new(Float64Vector::from_slice([10.40744, 10.40744, 10.40744])),
+            Arc::new(Int64Vector::from_slice([11, 11, 11])),
+        ];
+        let vector = f.eval(FunctionContext::default(), &args).unwrap();
+        assert_eq!(Value::from("u4pruydqqvj"), vector.get(0));
+        assert_eq!(Value::Null, vector.get(1));
+        assert_eq!(Value::Null, vector.get(2));
+
+        let args: Vec<VectorRef> = vec![
+            Arc::new(Float64Vector::from_slice([57.64911])),
+            Arc::new(Float64Vector::from_slice([10.40744])),
+            Arc::new(Int64Vector::from_slice([13])),
+        ];
+        assert!(f.eval(FunctionContext::default(), &args).is_err());
+
+        let hashes: Vec<VectorRef> = vec![Arc::new(StringVector::from_slice(&["wx4g0bm6", "!"]))];
+        let lat = GeohashDecodeFunction::latitude();
+        assert_eq!("geohash_lat", lat.name());
+        let vector = lat.eval(FunctionContext::default(), &hashes).unwrap();
+        let Value::Float64(v) = vector.get(0) else { unreachable!() };
+        assert!((v.into_inner() - 39.9042).abs() < 1e-3);
+        assert_eq!(Value::Null, vector.get(1));
+
+        let lng = GeohashDecodeFunction::longitude();
+        assert_eq!("geohash_lng", lng.name());
+        let vector = lng.eval(FunctionContext::default(), &hashes).unwrap();
+        let Value::Float64(v) = vector.get(0) else { unreachable!() };
+        assert!((v.into_inner() - 116.4074).abs() < 1e-3);
+    }
+}
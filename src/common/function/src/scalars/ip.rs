@@ -0,0 +1,31 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! IP address functions.
+
+mod ip_in_cidr;
+
+use std::sync::Arc;
+
+pub use ip_in_cidr::IpInCidrFunction;
+
+use crate::scalars::function_registry::FunctionRegistry;
+
+pub(crate) struct IpFunction;
+
+impl IpFunction {
+    pub fn register(registry: &FunctionRegistry) {
+        registry.register(Arc::new(IpInCidrFunction::default()));
+    }
+}
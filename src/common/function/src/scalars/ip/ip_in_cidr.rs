@@ -0,0 +1,159 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use common_query::error::{InvalidFuncArgsSnafu, Result};
+use common_query::prelude::{Signature, Volatility};
+use datatypes::prelude::ConcreteDataType;
+use datatypes::vectors::{BooleanVector, VectorRef};
+use snafu::ensure;
+
+use crate::scalars::function::{string_at, Function, FunctionContext};
+
+const NAME: &str = "ip_in_cidr";
+
+/// `ip_in_cidr(ip, cidr)` returns whether the IP address is in the CIDR block, like
+/// `192.168.0.0/16` or `2001:db8::/32`. An IPv4 address is never in an IPv6 block and
+/// vice versa. Returns null if either of them is invalid.
+#[derive(Clone, Debug, Default)]
+pub struct IpInCidrFunction;
+
+/// Parses the CIDR block into its network address and prefix length.
+fn parse_cidr(cidr: &str) -> Option<(IpAddr, u32)> {
+    let (addr, prefix_len) = cidr.split_once('/')?;
+    let addr = addr.trim().parse::<IpAddr>().ok()?;
+    let prefix_len = prefix_len.trim().parse::<u32>().ok()?;
+    let max_len = if addr.is_ipv4() { 32 } else { 128 };
+    (prefix_len <= max_len).then_some((addr, prefix_len))
+}
+
+fn ip_in_cidr(ip: &str, cidr: &str) -> Option<bool> {
+    let ip = ip.trim().parse::<IpAddr>().ok()?;
+    let (network, prefix_len) = parse_cidr(cidr)?;
+    let matched = match (ip, network) {
+        (IpAddr::V4(ip), IpAddr::V4(network)) => {
+            let mask = u32::MAX.checked_shl(32 - prefix_len).unwrap_or(0);
+            u32::from(ip) & mask == u32::from(network) & mask
+        }
+        (IpAddr::V6(ip), IpAddr::V6(network)) => {
+            let mask = u128::MAX.checked_shl(128 - prefix_len).unwrap_or(0);
+            u128::from(ip) & mask == u128::from(network) & mask
+        }
+        _ => false,
+    };
+    Some(matched)
+}
+
+impl Function for IpInCidrFunction {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn return_type(&self, _input_types: &[ConcreteDataType]) -> Result<ConcreteDataType> {
+        Ok(ConcreteDataType::boolean_datatype())
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::exact(
+            vec![
+                ConcreteDataType::string_datatype(),
+                ConcreteDataType::string_datatype(),
+            ],
+            Volatility::Immutable,
+        )
+    }
+
+    fn eval(&self, _func_ctx: FunctionContext, columns: &[VectorRef]) -> Result<VectorRef> {
+        ensure!(
+            columns.len() == 2,
+            InvalidFuncArgsSnafu {
+                err_msg: format!(
+                    "The length of the args is not correct, expect exactly two, have: {}",
+                    columns.len()
+                ),
+            }
+        );
+
+        let results = (0..columns[0].len())
+            .map(
+                |i| match (string_at(&columns[0], i), string_at(&columns[1], i)) {
+                    (Some(ip), Some(cidr)) => ip_in_cidr(ip, cidr),
+                    _ => None,
+                },
+            )
+            .collect::<Vec<_>>();
+        Ok(Arc::new(BooleanVector::from(results)))
+    }
+}
+
+impl fmt::Display for IpInCidrFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "IP_IN_CIDR")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datatypes::value::Value;
+    use datatypes::vectors::StringVector;
+
+    use super::*;
+
+    #[test]
+    fn test_ip_in_cidr() {
+        assert_eq!(Some(true), ip_in_cidr("192.168.1.10", "192.168.0.0/16"));
+        assert_eq!(Some(false), ip_in_cidr("192.169.1.10", "192.168.0.0/16"));
+        assert_eq!(Some(true), ip_in_cidr("10.0.0.1", "0.0.0.0/0"));
+        assert_eq!(Some(true), ip_in_cidr("10.0.0.1", "10.0.0.1/32"));
+        assert_eq!(Some(true), ip_in_cidr("2001:db8::1", "2001:db8::/32"));
+        assert_eq!(Some(false), ip_in_cidr("2001:db9::1", "2001:db8::/32"));
+        assert_eq!(Some(false), ip_in_cidr("192.168.1.10", "2001:db8::/32"));
+        assert_eq!(None, ip_in_cidr("192.168.1", "192.168.0.0/16"));
+        assert_eq!(None, ip_in_cidr("192.168.1.10", "192.168.0.0/33"));
+        assert_eq!(None, ip_in_cidr("192.168.1.10", "192.168.0.0"));
+    }
+
+    #[test]
+    fn test_ip_in_cidr_function() {
+        let f = IpInCidrFunction::default();
+        assert_eq!("ip_in_cidr", f.name());
+        assert_eq!(
+            ConcreteDataType::boolean_datatype(),
+            f.return_type(&[]).unwrap()
+        );
+
+        let args: Vec<VectorRef> = vec![
+            Arc::new(StringVector::from(vec![
+                Some("192.168.1.10"),
+                Some("10.0.0.1"),
+                None,
+                Some("invalid"),
+            ])),
+            Arc::new(StringVector::from(vec![
+                Some("192.168.0.0/16"),
+                Some("192.168.0.0/16"),
+                Some("192.168.0.0/16"),
+                Some("192.168.0.0/16"),
+            ])),
+        ];
+        let vector = f.eval(FunctionContext::default(), &args).unwrap();
+        assert_eq!(Value::Boolean(true), vector.get(0));
+        assert_eq!(Value::Boolean(false), vector.get(1));
+        assert_eq!(Value::Null, vector.get(2));
+        assert_eq!(Value::Null, vector.get(3));
+    }
+}
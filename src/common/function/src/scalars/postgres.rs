@@ -0,0 +1,39 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Functions to make PostgreSQL clients and BI tools happy.
+
+mod current_schemas;
+mod format_type;
+mod pg_get_expr;
+mod version;
+
+use std::sync::Arc;
+
+pub use current_schemas::CurrentSchemasFunction;
+pub use format_type::FormatTypeFunction;
+pub use pg_get_expr::PgGetExprFunction;
+pub use version::VersionFunction;
+
+use crate::scalars::function_registry::FunctionRegistry;
+
+pub(crate) struct PostgresFunction;
+
+impl PostgresFunction {
+    pub fn register(registry: &FunctionRegistry) {
+        registry.register(Arc::new(FormatTypeFunction::default()));
+        registry.register(Arc::new(PgGetExprFunction::default()));
+        registry.register(Arc::new(VersionFunction::default()));
+    }
+}
@@ -0,0 +1,118 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::sync::Arc;
+
+use common_query::error::{InvalidFuncArgsSnafu, Result};
+use common_query::prelude::{Signature, Volatility};
+use datatypes::prelude::{ConcreteDataType, Value};
+use datatypes::vectors::{StringVector, VectorRef};
+use snafu::ensure;
+
+use crate::scalars::function::{Function, FunctionContext};
+
+const NAME: &str = "current_schemas";
+
+const PG_CATALOG: &str = "pg_catalog";
+
+/// `current_schemas(include_implicit)` returns the schemas in the search path of
+/// current session. It's bound to the session's current schema, so it's created
+/// by the query planner instead of being registered globally.
+///
+/// The result is formatted as a PostgreSQL array literal, e.g. `{pg_catalog,public}`.
+#[derive(Clone, Debug)]
+pub struct CurrentSchemasFunction {
+    current_schema: String,
+}
+
+impl CurrentSchemasFunction {
+    pub fn new(current_schema: String) -> Self {
+        Self { current_schema }
+    }
+
+    fn schemas(&self, include_implicit: bool) -> String {
+        if include_implicit {
+            format!("{{{},{}}}", PG_CATALOG, self.current_schema)
+        } else {
+            format!("{{{}}}", self.current_schema)
+        }
+    }
+}
+
+impl Function for CurrentSchemasFunction {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn return_type(&self, _input_types: &[ConcreteDataType]) -> Result<ConcreteDataType> {
+        Ok(ConcreteDataType::string_datatype())
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::exact(
+            vec![ConcreteDataType::boolean_datatype()],
+            Volatility::Stable,
+        )
+    }
+
+    fn eval(&self, _func_ctx: FunctionContext, columns: &[VectorRef]) -> Result<VectorRef> {
+        ensure!(
+            columns.len() == 1,
+            InvalidFuncArgsSnafu {
+                err_msg: format!(
+                    "The length of the args is not correct, expect exactly one, have: {}",
+                    columns.len()
+                ),
+            }
+        );
+
+        let schemas = (0..columns[0].len())
+            .map(|i| match columns[0].get(i) {
+                Value::Boolean(include_implicit) => Some(self.schemas(include_implicit)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        Ok(Arc::new(StringVector::from(schemas)))
+    }
+}
+
+impl fmt::Display for CurrentSchemasFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CURRENT_SCHEMAS")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datatypes::vectors::BooleanVector;
+
+    use super::*;
+
+    #[test]
+    fn test_current_schemas() {
+        let f = CurrentSchemasFunction::new("public".to_string());
+        assert_eq!("current_schemas", f.name());
+
+        let args: Vec<VectorRef> = vec![Arc::new(BooleanVector::from(vec![
+            Some(true),
+            Some(false),
+            None,
+        ]))];
+        let vector = f.eval(FunctionContext::default(), &args).unwrap();
+        assert_eq!(Value::from("{pg_catalog,public}"), vector.get(0));
+        assert_eq!(Value::from("{public}"), vector.get(1));
+        assert_eq!(Value::Null, vector.get(2));
+    }
+}
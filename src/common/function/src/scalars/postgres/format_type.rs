@@ -0,0 +1,133 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::sync::Arc;
+
+use common_query::error::{InvalidFuncArgsSnafu, Result};
+use common_query::prelude::{Signature, Volatility};
+use datatypes::prelude::{ConcreteDataType, Value};
+use datatypes::vectors::{StringVector, VectorRef};
+use snafu::ensure;
+
+use crate::scalars::function::{Function, FunctionContext};
+
+const NAME: &str = "format_type";
+
+/// `format_type(type_oid, typemod)` returns the SQL name of a PostgreSQL data type.
+#[derive(Clone, Debug, Default)]
+pub struct FormatTypeFunction;
+
+/// Returns the SQL name of the type, only types GreptimeDB maps to are known.
+fn type_name(oid: i64) -> &'static str {
+    match oid {
+        16 => "boolean",
+        17 => "bytea",
+        18 => "\"char\"",
+        19 => "name",
+        20 => "bigint",
+        21 => "smallint",
+        23 => "integer",
+        25 => "text",
+        26 => "oid",
+        700 => "real",
+        701 => "double precision",
+        1043 => "character varying",
+        1082 => "date",
+        1083 => "time without time zone",
+        1114 => "timestamp without time zone",
+        1184 => "timestamp with time zone",
+        _ => "???",
+    }
+}
+
+/// Converts an integer value to i64, the oid argument may be of any integer type.
+pub(super) fn value_to_i64(value: Value) -> Option<i64> {
+    match value {
+        Value::Int8(v) => Some(v as i64),
+        Value::Int16(v) => Some(v as i64),
+        Value::Int32(v) => Some(v as i64),
+        Value::Int64(v) => Some(v),
+        Value::UInt8(v) => Some(v as i64),
+        Value::UInt16(v) => Some(v as i64),
+        Value::UInt32(v) => Some(v as i64),
+        Value::UInt64(v) => Some(v as i64),
+        _ => None,
+    }
+}
+
+impl Function for FormatTypeFunction {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn return_type(&self, _input_types: &[ConcreteDataType]) -> Result<ConcreteDataType> {
+        Ok(ConcreteDataType::string_datatype())
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::any(2, Volatility::Immutable)
+    }
+
+    fn eval(&self, _func_ctx: FunctionContext, columns: &[VectorRef]) -> Result<VectorRef> {
+        ensure!(
+            columns.len() == 2,
+            InvalidFuncArgsSnafu {
+                err_msg: format!(
+                    "The length of the args is not correct, expect exactly two, have: {}",
+                    columns.len()
+                ),
+            }
+        );
+
+        let names = (0..columns[0].len())
+            .map(|i| value_to_i64(columns[0].get(i)).map(type_name))
+            .collect::<Vec<_>>();
+        Ok(Arc::new(StringVector::from(names)))
+    }
+}
+
+impl fmt::Display for FormatTypeFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FORMAT_TYPE")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datatypes::vectors::{Int32Vector, UInt32Vector};
+
+    use super::*;
+
+    #[test]
+    fn test_format_type() {
+        let f = FormatTypeFunction::default();
+        assert_eq!("format_type", f.name());
+
+        let args: Vec<VectorRef> = vec![
+            Arc::new(UInt32Vector::from(vec![
+                Some(23),
+                Some(1114),
+                Some(9999),
+                None,
+            ])),
+            Arc::new(Int32Vector::from_slice([-1, -1, -1, -1])),
+        ];
+        let vector = f.eval(FunctionContext::default(), &args).unwrap();
+        assert_eq!(Value::from("integer"), vector.get(0));
+        assert_eq!(Value::from("timestamp without time zone"), vector.get(1));
+        assert_eq!(Value::from("???"), vector.get(2));
+        assert_eq!(Value::Null, vector.get(3));
+    }
+}
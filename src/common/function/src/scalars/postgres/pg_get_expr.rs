@@ -0,0 +1,96 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::sync::Arc;
+
+use common_query::error::{InvalidFuncArgsSnafu, Result};
+use common_query::prelude::{Signature, TypeSignature, Volatility};
+use datatypes::prelude::{ConcreteDataType, Value};
+use datatypes::vectors::{StringVector, VectorRef};
+use snafu::ensure;
+
+use crate::scalars::function::{Function, FunctionContext};
+
+const NAME: &str = "pg_get_expr";
+
+/// `pg_get_expr(pg_node_tree, relation_oid[, pretty])` decompiles the internal
+/// form of an expression. GreptimeDB keeps expressions as SQL text, so the text
+/// is returned as is.
+#[derive(Clone, Debug, Default)]
+pub struct PgGetExprFunction;
+
+impl Function for PgGetExprFunction {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn return_type(&self, _input_types: &[ConcreteDataType]) -> Result<ConcreteDataType> {
+        Ok(ConcreteDataType::string_datatype())
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::one_of(
+            vec![TypeSignature::Any(2), TypeSignature::Any(3)],
+            Volatility::Immutable,
+        )
+    }
+
+    fn eval(&self, _func_ctx: FunctionContext, columns: &[VectorRef]) -> Result<VectorRef> {
+        ensure!(
+            columns.len() == 2 || columns.len() == 3,
+            InvalidFuncArgsSnafu {
+                err_msg: format!(
+                    "The length of the args is not correct, expect two or three, have: {}",
+                    columns.len()
+                ),
+            }
+        );
+
+        let exprs = (0..columns[0].len())
+            .map(|i| match columns[0].get(i) {
+                Value::String(expr) => Some(expr.as_utf8().to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        Ok(Arc::new(StringVector::from(exprs)))
+    }
+}
+
+impl fmt::Display for PgGetExprFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PG_GET_EXPR")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datatypes::vectors::UInt32Vector;
+
+    use super::*;
+
+    #[test]
+    fn test_pg_get_expr() {
+        let f = PgGetExprFunction::default();
+        assert_eq!("pg_get_expr", f.name());
+
+        let args: Vec<VectorRef> = vec![
+            Arc::new(StringVector::from(vec![Some("now()"), None])),
+            Arc::new(UInt32Vector::from_slice([16384, 16384])),
+        ];
+        let vector = f.eval(FunctionContext::default(), &args).unwrap();
+        assert_eq!(Value::from("now()"), vector.get(0));
+        assert_eq!(Value::Null, vector.get(1));
+    }
+}
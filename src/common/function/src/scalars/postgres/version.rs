@@ -0,0 +1,77 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::sync::Arc;
+
+use common_query::error::Result;
+use common_query::prelude::{Signature, Volatility};
+use datatypes::prelude::ConcreteDataType;
+use datatypes::vectors::{StringVector, VectorRef};
+
+use crate::scalars::function::{Function, FunctionContext};
+
+const NAME: &str = "version";
+
+/// `version()` returns the server version in the form PostgreSQL clients expect,
+/// they usually parse the leading `PostgreSQL <major>.<minor>`.
+#[derive(Clone, Debug, Default)]
+pub struct VersionFunction;
+
+pub(crate) fn version() -> String {
+    format!("PostgreSQL 15.3 (GreptimeDB {})", env!("CARGO_PKG_VERSION"))
+}
+
+impl Function for VersionFunction {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn return_type(&self, _input_types: &[ConcreteDataType]) -> Result<ConcreteDataType> {
+        Ok(ConcreteDataType::string_datatype())
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::exact(vec![], Volatility::Immutable)
+    }
+
+    fn eval(&self, _func_ctx: FunctionContext, columns: &[VectorRef]) -> Result<VectorRef> {
+        let rows = columns.first().map(|c| c.len()).unwrap_or(1);
+        Ok(Arc::new(StringVector::from(vec![version(); rows])))
+    }
+}
+
+impl fmt::Display for VersionFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VERSION")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datatypes::prelude::Value;
+
+    use super::*;
+
+    #[test]
+    fn test_version() {
+        let f = VersionFunction::default();
+        assert_eq!("version", f.name());
+
+        let vector = f.eval(FunctionContext::default(), &[]).unwrap();
+        assert_eq!(1, vector.len());
+        assert_eq!(Value::from(version()), vector.get(0));
+        assert!(version().starts_with("PostgreSQL "));
+    }
+}
@@ -0,0 +1,39 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Mergeable sketches for approximate aggregates, and functions reading the
+//! serialized sketches.
+
+mod hll;
+mod hll_count;
+mod tdigest;
+mod tdigest_percentile;
+
+use std::sync::Arc;
+
+pub use hll::HyperLogLog;
+pub use hll_count::HllCountFunction;
+pub use tdigest::TDigest;
+pub use tdigest_percentile::TDigestPercentileFunction;
+
+use crate::scalars::function_registry::FunctionRegistry;
+
+pub(crate) struct SketchFunction;
+
+impl SketchFunction {
+    pub fn register(registry: &FunctionRegistry) {
+        registry.register(Arc::new(HllCountFunction::default()));
+        registry.register(Arc::new(TDigestPercentileFunction::default()));
+    }
+}
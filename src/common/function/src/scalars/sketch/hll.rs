@@ -0,0 +1,172 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::hash::Hasher;
+
+use common_query::error::{InvalidFuncArgsSnafu, Result};
+use datatypes::value::ValueRef;
+use snafu::ensure;
+use twox_hash::XxHash64;
+
+/// Number of bits of the hash to index registers.
+const PRECISION: u8 = 12;
+const NUM_REGISTERS: usize = 1 << PRECISION;
+
+/// A HyperLogLog sketch estimating the number of distinct values. Sketches are
+/// mergeable, so they could be stored in rollup tables and merged later.
+///
+/// The sketch is serialized as the precision followed by its registers, values are
+/// hashed by xxHash64 so serialized sketches are portable between nodes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl Default for HyperLogLog {
+    fn default() -> Self {
+        Self {
+            registers: vec![0; NUM_REGISTERS],
+        }
+    }
+}
+
+impl HyperLogLog {
+    /// Adds the value to the sketch, nulls and lists are ignored.
+    pub fn add(&mut self, value: ValueRef) {
+        if let Some(hash) = hash_value(value) {
+            self.add_hash(hash);
+        }
+    }
+
+    fn add_hash(&mut self, hash: u64) {
+        let index = (hash >> (64 - PRECISION)) as usize;
+        // Sets the lowest bit of the remaining bits to bound the rank.
+        let remaining = (hash << PRECISION) | (1 << (PRECISION - 1));
+        let rank = remaining.leading_zeros() as u8 + 1;
+        if rank > self.registers[index] {
+            self.registers[index] = rank;
+        }
+    }
+
+    pub fn merge(&mut self, other: &HyperLogLog) {
+        for (register, other) in self.registers.iter_mut().zip(other.registers.iter()) {
+            *register = (*register).max(*other);
+        }
+    }
+
+    /// Returns the estimated number of distinct values.
+    pub fn count(&self) -> u64 {
+        let m = NUM_REGISTERS as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|register| 2f64.powi(-(*register as i32)))
+            .sum();
+        let estimate = alpha * m * m / sum;
+
+        let zeros = self.registers.iter().filter(|r| **r == 0).count();
+        if estimate <= 2.5 * m && zeros > 0 {
+            // Linear counting is more accurate for small cardinalities.
+            (m * (m / zeros as f64).ln()).round() as u64
+        } else {
+            estimate.round() as u64
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(1 + NUM_REGISTERS);
+        bytes.push(PRECISION);
+        bytes.extend_from_slice(&self.registers);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        ensure!(
+            bytes.len() == 1 + NUM_REGISTERS && bytes[0] == PRECISION,
+            InvalidFuncArgsSnafu {
+                err_msg: "Invalid HyperLogLog sketch",
+            }
+        );
+        Ok(Self {
+            registers: bytes[1..].to_vec(),
+        })
+    }
+}
+
+fn hash_value(value: ValueRef) -> Option<u64> {
+    let mut hasher = XxHash64::with_seed(0);
+    match value {
+        ValueRef::Null | ValueRef::List(_) => return None,
+        ValueRef::Boolean(v) => hasher.write_u8(v as u8),
+        ValueRef::UInt8(v) => hasher.write_u64(v as u64),
+        ValueRef::UInt16(v) => hasher.write_u64(v as u64),
+        ValueRef::UInt32(v) => hasher.write_u64(v as u64),
+        ValueRef::UInt64(v) => hasher.write_u64(v),
+        ValueRef::Int8(v) => hasher.write_i64(v as i64),
+        ValueRef::Int16(v) => hasher.write_i64(v as i64),
+        ValueRef::Int32(v) => hasher.write_i64(v as i64),
+        ValueRef::Int64(v) => hasher.write_i64(v),
+        ValueRef::Float32(v) => hasher.write_u64((v.0 as f64).to_bits()),
+        ValueRef::Float64(v) => hasher.write_u64(v.0.to_bits()),
+        ValueRef::String(v) => hasher.write(v.as_bytes()),
+        ValueRef::Binary(v) => hasher.write(v),
+        ValueRef::Date(v) => hasher.write_i64(v.val() as i64),
+        ValueRef::DateTime(v) => hasher.write_i64(v.val()),
+        ValueRef::Timestamp(v) => hasher.write_i64(v.value()),
+    }
+    Some(hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hll_count() {
+        let mut hll = HyperLogLog::default();
+        assert_eq!(0, hll.count());
+
+        for i in 0..100_000i64 {
+            // Duplicated values are counted once.
+            hll.add(ValueRef::Int64(i % 50_000));
+        }
+        hll.add(ValueRef::Null);
+        let count = hll.count() as f64;
+        assert!((count - 50_000.0).abs() / 50_000.0 < 0.05, "count: {count}");
+
+        let mut small = HyperLogLog::default();
+        for s in ["a", "b", "c", "a"] {
+            small.add(ValueRef::String(s));
+        }
+        assert_eq!(3, small.count());
+    }
+
+    #[test]
+    fn test_hll_merge_and_serde() {
+        let mut left = HyperLogLog::default();
+        let mut right = HyperLogLog::default();
+        for i in 0..1000u64 {
+            left.add(ValueRef::UInt64(i));
+            right.add(ValueRef::UInt64(i + 500));
+        }
+        left.merge(&right);
+        let count = left.count() as f64;
+        assert!((count - 1500.0).abs() / 1500.0 < 0.05, "count: {count}");
+
+        let decoded = HyperLogLog::from_bytes(&left.to_bytes()).unwrap();
+        assert_eq!(left, decoded);
+        assert!(HyperLogLog::from_bytes(&[PRECISION, 0, 0]).is_err());
+    }
+}
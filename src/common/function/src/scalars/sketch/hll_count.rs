@@ -0,0 +1,108 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::sync::Arc;
+
+use common_query::error::{InvalidFuncArgsSnafu, Result};
+use common_query::prelude::{Signature, Volatility};
+use datatypes::prelude::ConcreteDataType;
+use datatypes::vectors::{UInt64Vector, VectorRef};
+use snafu::ensure;
+
+use crate::scalars::function::{binary_at, Function, FunctionContext};
+use crate::scalars::sketch::HyperLogLog;
+
+const NAME: &str = "hll_count";
+
+/// `hll_count(sketch)` returns the estimated number of distinct values of the
+/// HyperLogLog sketch built by the `hll` or `hll_merge` aggregate function.
+#[derive(Clone, Debug, Default)]
+pub struct HllCountFunction;
+
+impl Function for HllCountFunction {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn return_type(&self, _input_types: &[ConcreteDataType]) -> Result<ConcreteDataType> {
+        Ok(ConcreteDataType::uint64_datatype())
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::exact(
+            vec![ConcreteDataType::binary_datatype()],
+            Volatility::Immutable,
+        )
+    }
+
+    fn eval(&self, _func_ctx: FunctionContext, columns: &[VectorRef]) -> Result<VectorRef> {
+        ensure!(
+            columns.len() == 1,
+            InvalidFuncArgsSnafu {
+                err_msg: format!(
+                    "The length of the args is not correct, expect exactly one, have: {}",
+                    columns.len()
+                ),
+            }
+        );
+
+        let mut results = Vec::with_capacity(columns[0].len());
+        for i in 0..columns[0].len() {
+            let count = match binary_at(&columns[0], i) {
+                Some(bytes) => Some(HyperLogLog::from_bytes(bytes)?.count()),
+                None => None,
+            };
+            results.push(count);
+        }
+        Ok(Arc::new(UInt64Vector::from(results)))
+    }
+}
+
+impl fmt::Display for HllCountFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HLL_COUNT")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datatypes::value::{Value, ValueRef};
+    use datatypes::vectors::BinaryVector;
+
+    use super::*;
+
+    #[test]
+    fn test_hll_count() {
+        let f = HllCountFunction::default();
+        assert_eq!("hll_count", f.name());
+
+        let mut hll = HyperLogLog::default();
+        for s in ["a", "b", "c"] {
+            hll.add(ValueRef::String(s));
+        }
+        let args: Vec<VectorRef> = vec![Arc::new(BinaryVector::from(vec![
+            Some(hll.to_bytes()),
+            None,
+            Some(HyperLogLog::default().to_bytes()),
+        ]))];
+        let vector = f.eval(FunctionContext::default(), &args).unwrap();
+        assert_eq!(Value::UInt64(3), vector.get(0));
+        assert_eq!(Value::Null, vector.get(1));
+        assert_eq!(Value::UInt64(0), vector.get(2));
+
+        let args: Vec<VectorRef> = vec![Arc::new(BinaryVector::from(vec![Some(vec![1, 2])]))];
+        assert!(f.eval(FunctionContext::default(), &args).is_err());
+    }
+}
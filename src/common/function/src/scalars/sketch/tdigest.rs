@@ -0,0 +1,378 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use common_query::error::{InvalidFuncArgsSnafu, Result};
+use datatypes::value::ValueRef;
+use snafu::ensure;
+
+/// Bounds the number of centroids, a larger compression is more accurate.
+const COMPRESSION: f64 = 100.0;
+/// Values are buffered and merged into centroids in batches.
+const BUFFER_SIZE: usize = 500;
+const VERSION: u8 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+/// A merging t-digest estimating percentiles. Digests are mergeable, so they could be
+/// stored in rollup tables and merged later.
+///
+/// Centroids near both tails are kept small, so extreme percentiles are more accurate
+/// than the median.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TDigest {
+    centroids: Vec<Centroid>,
+    buffer: Vec<f64>,
+    min: f64,
+    max: f64,
+}
+
+impl TDigest {
+    /// Adds the numeric value to the digest, nulls and non-numeric values are ignored.
+    pub fn add_value(&mut self, value: ValueRef) {
+        let value = match value {
+            ValueRef::UInt8(v) => v as f64,
+            ValueRef::UInt16(v) => v as f64,
+            ValueRef::UInt32(v) => v as f64,
+            ValueRef::UInt64(v) => v as f64,
+            ValueRef::Int8(v) => v as f64,
+            ValueRef::Int16(v) => v as f64,
+            ValueRef::Int32(v) => v as f64,
+            ValueRef::Int64(v) => v as f64,
+            ValueRef::Float32(v) => v.0 as f64,
+            ValueRef::Float64(v) => v.0,
+            _ => return,
+        };
+        self.add(value);
+    }
+
+    pub fn add(&mut self, value: f64) {
+        if value.is_nan() {
+            return;
+        }
+        if self.is_empty() {
+            self.min = value;
+            self.max = value;
+        } else {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+        self.buffer.push(value);
+        if self.buffer.len() >= BUFFER_SIZE {
+            self.compress();
+        }
+    }
+
+    pub fn merge(&mut self, other: &TDigest) {
+        if other.is_empty() {
+            return;
+        }
+        if self.is_empty() {
+            self.min = other.min;
+            self.max = other.max;
+        } else {
+            self.min = self.min.min(other.min);
+            self.max = self.max.max(other.max);
+        }
+        self.centroids.extend_from_slice(&other.centroids);
+        self.buffer.extend_from_slice(&other.buffer);
+        self.compress();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.centroids.is_empty() && self.buffer.is_empty()
+    }
+
+    /// Merges buffered values and centroids into new centroids. Adjacent centroids
+    /// are merged until the merged one spans one unit of the `k1` scale function, which
+    /// is narrow near both tails.
+    fn compress(&mut self) {
+        if self.buffer.is_empty() && self.centroids.len() as f64 <= COMPRESSION {
+            return;
+        }
+
+        let mut centroids = std::mem::take(&mut self.centroids);
+        centroids.extend(self.buffer.drain(..).map(|value| Centroid {
+            mean: value,
+            weight: 1.0,
+        }));
+        centroids.sort_by(|a, b| a.mean.total_cmp(&b.mean));
+        let total: f64 = centroids.iter().map(|c| c.weight).sum();
+
+        let mut merged = Vec::with_capacity(COMPRESSION as usize);
+        let mut iter = centroids.into_iter();
+        // Safety: there is at least one value in the buffer or the centroids.
+        let mut current = iter.next().unwrap();
+        let mut weight_so_far = 0.0;
+        let mut weight_limit = total * k_to_q(q_to_k(0.0) + 1.0);
+        for next in iter {
+            if weight_so_far + current.weight + next.weight <= weight_limit {
+                let weight = current.weight + next.weight;
+                current.mean += (next.mean - current.mean) * next.weight / weight;
+                current.weight = weight;
+            } else {
+                weight_so_far += current.weight;
+                merged.push(current);
+                current = next;
+                weight_limit = total * k_to_q(q_to_k(weight_so_far / total) + 1.0);
+            }
+        }
+        merged.push(current);
+        self.centroids = merged;
+    }
+
+    /// Returns the estimated value at the quantile `q` in range `[0, 1]`, or `None` if
+    /// the digest is empty.
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut digest = self.clone();
+        digest.compress();
+        let centroids = &digest.centroids;
+        if q <= 0.0 {
+            return Some(self.min);
+        }
+        if q >= 1.0 {
+            return Some(self.max);
+        }
+
+        let total: f64 = centroids.iter().map(|c| c.weight).sum();
+        let target = q * total;
+        // Each centroid is assumed to be centered at the middle of its weight.
+        let first = centroids[0];
+        if target < first.weight / 2.0 {
+            return Some(interpolate(
+                self.min,
+                first.mean,
+                target / (first.weight / 2.0),
+            ));
+        }
+        let mut cumulative = 0.0;
+        for pair in centroids.windows(2) {
+            let left = cumulative + pair[0].weight / 2.0;
+            let right = cumulative + pair[0].weight + pair[1].weight / 2.0;
+            if target <= right {
+                return Some(interpolate(
+                    pair[0].mean,
+                    pair[1].mean,
+                    (target - left) / (right - left),
+                ));
+            }
+            cumulative += pair[0].weight;
+        }
+        // Safety: centroids are not empty.
+        let last = centroids.last().unwrap();
+        let left = total - last.weight / 2.0;
+        Some(interpolate(
+            last.mean,
+            self.max,
+            (target - left) / (last.weight / 2.0),
+        ))
+    }
+
+    /// Serializes the digest as version, min, max, the number of centroids and the
+    /// mean and weight of each centroid, numbers are in little endian.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut digest = self.clone();
+        digest.compress();
+
+        let mut bytes = Vec::with_capacity(21 + digest.centroids.len() * 16);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&digest.min.to_le_bytes());
+        bytes.extend_from_slice(&digest.max.to_le_bytes());
+        bytes.extend_from_slice(&(digest.centroids.len() as u32).to_le_bytes());
+        for centroid in &digest.centroids {
+            bytes.extend_from_slice(&centroid.mean.to_le_bytes());
+            bytes.extend_from_slice(&centroid.weight.to_le_bytes());
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = BytesReader { bytes };
+        ensure!(
+            reader.read::<1>()? == [VERSION],
+            InvalidFuncArgsSnafu {
+                err_msg: "Unsupported version of t-digest sketch",
+            }
+        );
+        let min = f64::from_le_bytes(reader.read()?);
+        let max = f64::from_le_bytes(reader.read()?);
+        // Also rejects NaN.
+        ensure!(
+            min <= max,
+            InvalidFuncArgsSnafu {
+                err_msg: "Invalid range of t-digest sketch",
+            }
+        );
+        let len = u32::from_le_bytes(reader.read()?) as usize;
+        // Checks the length before allocating, as it's read from user input.
+        ensure!(
+            reader.bytes.len() / 16 >= len,
+            InvalidFuncArgsSnafu {
+                err_msg: "Truncated t-digest sketch",
+            }
+        );
+        let mut centroids = Vec::with_capacity(len);
+        for _ in 0..len {
+            let mean = f64::from_le_bytes(reader.read()?);
+            let weight = f64::from_le_bytes(reader.read()?);
+            // Quantiles are computed assuming sorted centroids of positive weights.
+            let sorted = centroids
+                .last()
+                .map_or(true, |last: &Centroid| last.mean <= mean);
+            ensure!(
+                mean.is_finite() && sorted && weight.is_finite() && weight > 0.0,
+                InvalidFuncArgsSnafu {
+                    err_msg: "Invalid centroid of t-digest sketch",
+                }
+            );
+            centroids.push(Centroid { mean, weight });
+        }
+        Ok(Self {
+            centroids,
+            buffer: Vec::new(),
+            min,
+            max,
+        })
+    }
+}
+
+/// The `k1` scale function, maps the quantile to range `[-COMPRESSION / 4, COMPRESSION / 4]`.
+fn q_to_k(q: f64) -> f64 {
+    COMPRESSION / (2.0 * PI) * (2.0 * q - 1.0).clamp(-1.0, 1.0).asin()
+}
+
+fn k_to_q(k: f64) -> f64 {
+    ((k * 2.0 * PI / COMPRESSION).min(FRAC_PI_2).sin() + 1.0) / 2.0
+}
+
+fn interpolate(from: f64, to: f64, ratio: f64) -> f64 {
+    from + (to - from) * ratio.clamp(0.0, 1.0)
+}
+
+struct BytesReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl BytesReader<'_> {
+    fn read<const N: usize>(&mut self) -> Result<[u8; N]> {
+        ensure!(
+            self.bytes.len() >= N,
+            InvalidFuncArgsSnafu {
+                err_msg: "Truncated t-digest sketch",
+            }
+        );
+        let (head, tail) = self.bytes.split_at(N);
+        self.bytes = tail;
+        // Safety: the length of `head` is N.
+        Ok(head.try_into().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tdigest_quantile() {
+        let mut digest = TDigest::default();
+        assert_eq!(None, digest.quantile(0.5));
+
+        digest.add(42.0);
+        assert_eq!(Some(42.0), digest.quantile(0.5));
+
+        let mut digest = TDigest::default();
+        // Adds values in a shuffled order.
+        for i in 0..10_000 {
+            digest.add(((i * 7919) % 10_000) as f64);
+        }
+        assert_eq!(Some(0.0), digest.quantile(0.0));
+        assert_eq!(Some(9999.0), digest.quantile(1.0));
+        for q in [0.01, 0.1, 0.5, 0.9, 0.99] {
+            let estimate = digest.quantile(q).unwrap();
+            let expected = q * 9999.0;
+            assert!(
+                (estimate - expected).abs() < 10_000.0 * 0.01,
+                "q: {q}, estimate: {estimate}"
+            );
+        }
+        assert!(digest.centroids.len() as f64 <= COMPRESSION);
+    }
+
+    #[test]
+    fn test_tdigest_merge_and_serde() {
+        let mut left = TDigest::default();
+        let mut right = TDigest::default();
+        for i in 0..1000 {
+            left.add(i as f64);
+            right.add((i + 1000) as f64);
+        }
+        left.merge(&right);
+        let median = left.quantile(0.5).unwrap();
+        assert!((median - 999.5).abs() < 20.0, "median: {median}");
+
+        let decoded = TDigest::from_bytes(&left.to_bytes()).unwrap();
+        let p90 = decoded.quantile(0.9).unwrap();
+        assert!((p90 - 1799.1).abs() < 20.0, "p90: {p90}");
+        assert!(TDigest::from_bytes(&[VERSION, 0, 0]).is_err());
+        assert!(TDigest::from_bytes(&[]).is_err());
+
+        // A huge number of centroids without the bytes of them.
+        let mut bytes = vec![VERSION];
+        bytes.extend_from_slice(&[0; 16]);
+        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+        assert!(TDigest::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_tdigest_from_invalid_bytes() {
+        let encode = |min: f64, max: f64, centroids: &[(f64, f64)]| {
+            let mut bytes = vec![VERSION];
+            bytes.extend_from_slice(&min.to_le_bytes());
+            bytes.extend_from_slice(&max.to_le_bytes());
+            bytes.extend_from_slice(&(centroids.len() as u32).to_le_bytes());
+            for (mean, weight) in centroids {
+                bytes.extend_from_slice(&mean.to_le_bytes());
+                bytes.extend_from_slice(&weight.to_le_bytes());
+            }
+            bytes
+        };
+
+        let digest = TDigest::from_bytes(&encode(1.0, 3.0, &[(1.0, 1.0), (3.0, 2.0)])).unwrap();
+        assert_eq!(Some(3.0), digest.quantile(1.0));
+        let _ = TDigest::from_bytes(&encode(0.0, 0.0, &[])).unwrap();
+
+        let invalid = [
+            encode(3.0, 1.0, &[(1.0, 1.0), (3.0, 2.0)]),
+            encode(f64::NAN, 3.0, &[(1.0, 1.0)]),
+            encode(1.0, 3.0, &[(3.0, 1.0), (1.0, 2.0)]),
+            encode(1.0, 3.0, &[(f64::NAN, 1.0)]),
+            encode(1.0, 3.0, &[(f64::INFINITY, 1.0)]),
+            encode(1.0, 3.0, &[(1.0, 0.0)]),
+            encode(1.0, 3.0, &[(1.0, -1.0)]),
+            encode(1.0, 3.0, &[(1.0, f64::NAN)]),
+            encode(1.0, 3.0, &[(1.0, f64::INFINITY)]),
+        ];
+        for bytes in invalid {
+            assert!(TDigest::from_bytes(&bytes).is_err(), "bytes: {bytes:?}");
+        }
+    }
+}
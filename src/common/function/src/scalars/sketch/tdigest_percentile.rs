@@ -0,0 +1,130 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::sync::Arc;
+
+use common_query::error::{InvalidFuncArgsSnafu, Result};
+use common_query::prelude::{Signature, Volatility};
+use datatypes::prelude::ConcreteDataType;
+use datatypes::value::Value;
+use datatypes::vectors::{Float64Vector, VectorRef};
+use snafu::ensure;
+
+use crate::scalars::function::{binary_at, Function, FunctionContext};
+use crate::scalars::sketch::TDigest;
+
+const NAME: &str = "tdigest_percentile";
+
+/// `tdigest_percentile(sketch, p)` returns the estimated `p`-th percentile, `p` is in
+/// range `[0, 100]`, of the t-digest sketch built by the `tdigest` or `tdigest_merge`
+/// aggregate function.
+#[derive(Clone, Debug, Default)]
+pub struct TDigestPercentileFunction;
+
+impl Function for TDigestPercentileFunction {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn return_type(&self, _input_types: &[ConcreteDataType]) -> Result<ConcreteDataType> {
+        Ok(ConcreteDataType::float64_datatype())
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::exact(
+            vec![
+                ConcreteDataType::binary_datatype(),
+                ConcreteDataType::float64_datatype(),
+            ],
+            Volatility::Immutable,
+        )
+    }
+
+    fn eval(&self, _func_ctx: FunctionContext, columns: &[VectorRef]) -> Result<VectorRef> {
+        ensure!(
+            columns.len() == 2,
+            InvalidFuncArgsSnafu {
+                err_msg: format!(
+                    "The length of the args is not correct, expect exactly two, have: {}",
+                    columns.len()
+                ),
+            }
+        );
+
+        let mut results = Vec::with_capacity(columns[0].len());
+        for i in 0..columns[0].len() {
+            let (Some(bytes), Value::Float64(p)) =
+                (binary_at(&columns[0], i), columns[1].get(i))
+            else {
+                results.push(None);
+                continue;
+            };
+            let p = p.into_inner();
+            ensure!(
+                (0.0..=100.0).contains(&p),
+                InvalidFuncArgsSnafu {
+                    err_msg: format!("The percentile must be in range [0, 100], have: {p}"),
+                }
+            );
+            results.push(TDigest::from_bytes(bytes)?.quantile(p / 100.0));
+        }
+        Ok(Arc::new(Float64Vector::from(results)))
+    }
+}
+
+impl fmt::Display for TDigestPercentileFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TDIGEST_PERCENTILE")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datatypes::scalars::ScalarVector;
+    use datatypes::vectors::BinaryVector;
+
+    use super::*;
+
+    #[test]
+    fn test_tdigest_percentile() {
+        let f = TDigestPercentileFunction::default();
+        assert_eq!("tdigest_percentile", f.name());
+
+        let mut digest = TDigest::default();
+        for i in 0..=100 {
+            digest.add(i as f64);
+        }
+        let args: Vec<VectorRef> = vec![
+            Arc::new(BinaryVector::from(vec![
+                Some(digest.to_bytes()),
+                Some(digest.to_bytes()),
+                None,
+                Some(TDigest::default().to_bytes()),
+            ])),
+            Arc::new(Float64Vector::from_slice([0.0, 100.0, 50.0, 50.0])),
+        ];
+        let vector = f.eval(FunctionContext::default(), &args).unwrap();
+        assert_eq!(Value::from(0.0), vector.get(0));
+        assert_eq!(Value::from(100.0), vector.get(1));
+        assert_eq!(Value::Null, vector.get(2));
+        assert_eq!(Value::Null, vector.get(3));
+
+        let args: Vec<VectorRef> = vec![
+            Arc::new(BinaryVector::from(vec![Some(digest.to_bytes())])),
+            Arc::new(Float64Vector::from_slice([101.0])),
+        ];
+        assert!(f.eval(FunctionContext::default(), &args).is_err());
+    }
+}
@@ -12,8 +12,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 use std::sync::Arc;
+mod date_format;
+mod str_to_date;
+mod timezone;
 mod to_unixtime;
 
+use chrono_tz::Tz;
+use common_query::error::{InvalidFuncArgsSnafu, Result};
+use common_time::timestamp::TimeUnit;
+use datatypes::prelude::ConcreteDataType;
+use datatypes::vectors::{
+    TimestampMicrosecondVector, TimestampMillisecondVector, TimestampNanosecondVector,
+    TimestampSecondVector, VectorRef,
+};
+use date_format::DateFormatFunction;
+use str_to_date::StrToDateFunction;
+use timezone::TimezoneFunction;
 use to_unixtime::ToUnixtimeFunction;
 
 use crate::scalars::function_registry::FunctionRegistry;
@@ -23,5 +37,38 @@ pub(crate) struct TimestampFunction;
 impl TimestampFunction {
     pub fn register(registry: &FunctionRegistry) {
         registry.register(Arc::new(ToUnixtimeFunction::default()));
+        registry.register(Arc::new(DateFormatFunction::default()));
+        registry.register(Arc::new(TimezoneFunction::default()));
+        registry.register(Arc::new(StrToDateFunction::default()));
+    }
+}
+
+/// Timestamp types of all time units.
+fn timestamp_types() -> Vec<ConcreteDataType> {
+    vec![
+        ConcreteDataType::timestamp_second_datatype(),
+        ConcreteDataType::timestamp_millisecond_datatype(),
+        ConcreteDataType::timestamp_microsecond_datatype(),
+        ConcreteDataType::timestamp_nanosecond_datatype(),
+    ]
+}
+
+/// Parses the IANA name of a timezone, like `Asia/Shanghai`.
+fn parse_timezone(name: &str) -> Result<Tz> {
+    name.parse::<Tz>().map_err(|e| {
+        InvalidFuncArgsSnafu {
+            err_msg: format!("Invalid timezone: {name}, {e}"),
+        }
+        .build()
+    })
+}
+
+/// Builds a timestamp vector of the unit from the values.
+fn timestamp_vector(unit: TimeUnit, values: Vec<Option<i64>>) -> VectorRef {
+    match unit {
+        TimeUnit::Second => Arc::new(TimestampSecondVector::from(values)),
+        TimeUnit::Millisecond => Arc::new(TimestampMillisecondVector::from(values)),
+        TimeUnit::Microsecond => Arc::new(TimestampMicrosecondVector::from(values)),
+        TimeUnit::Nanosecond => Arc::new(TimestampNanosecondVector::from(values)),
     }
 }
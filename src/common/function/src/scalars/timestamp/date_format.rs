@@ -0,0 +1,177 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::{self, Write};
+use std::sync::Arc;
+
+use chrono::TimeZone;
+use chrono_tz::Tz;
+use common_query::error::{InvalidFuncArgsSnafu, Result};
+use common_query::prelude::{Signature, TypeSignature, Volatility};
+use common_time::Timestamp;
+use datatypes::prelude::ConcreteDataType;
+use datatypes::vectors::{StringVector, VectorRef};
+use snafu::ensure;
+
+use crate::scalars::function::{string_at, Function, FunctionContext};
+use crate::scalars::timestamp::{parse_timezone, timestamp_types};
+
+const NAME: &str = "date_format";
+
+/// `date_format(ts, format[, timezone])` formats the timestamp by the `strftime` like
+/// format, e.g. `%Y-%m-%d %H:%M:%S`, in the timezone, which is the timezone of the
+/// function context by default. Returns null if the format is invalid.
+#[derive(Clone, Debug, Default)]
+pub struct DateFormatFunction;
+
+fn format_timestamp(ts: Timestamp, format: &str, tz: Tz) -> Option<String> {
+    let datetime = tz.from_utc_datetime(&ts.to_chrono_datetime()?);
+    let mut formatted = String::new();
+    // Formatting fails on invalid specifiers.
+    write!(formatted, "{}", datetime.format(format)).ok()?;
+    Some(formatted)
+}
+
+impl Function for DateFormatFunction {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn return_type(&self, _input_types: &[ConcreteDataType]) -> Result<ConcreteDataType> {
+        Ok(ConcreteDataType::string_datatype())
+    }
+
+    fn signature(&self) -> Signature {
+        let mut signatures = Vec::new();
+        for ts_type in timestamp_types() {
+            signatures.push(TypeSignature::Exact(vec![
+                ts_type.clone(),
+                ConcreteDataType::string_datatype(),
+            ]));
+            signatures.push(TypeSignature::Exact(vec![
+                ts_type,
+                ConcreteDataType::string_datatype(),
+                ConcreteDataType::string_datatype(),
+            ]));
+        }
+        Signature::one_of(signatures, Volatility::Immutable)
+    }
+
+    fn eval(&self, func_ctx: FunctionContext, columns: &[VectorRef]) -> Result<VectorRef> {
+        ensure!(
+            columns.len() == 2 || columns.len() == 3,
+            InvalidFuncArgsSnafu {
+                err_msg: format!(
+                    "The length of the args is not correct, expect two or three, have: {}",
+                    columns.len()
+                ),
+            }
+        );
+
+        let mut results = Vec::with_capacity(columns[0].len());
+        for i in 0..columns[0].len() {
+            let (Some(ts), Some(format)) = (
+                columns[0].get(i).as_timestamp(),
+                string_at(&columns[1], i),
+            ) else {
+                results.push(None);
+                continue;
+            };
+            let tz = match columns.get(2) {
+                Some(timezones) => match string_at(timezones, i) {
+                    Some(name) => parse_timezone(name)?,
+                    None => {
+                        results.push(None);
+                        continue;
+                    }
+                },
+                None => func_ctx.tz,
+            };
+            results.push(format_timestamp(ts, format, tz));
+        }
+        Ok(Arc::new(StringVector::from(results)))
+    }
+}
+
+impl fmt::Display for DateFormatFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DATE_FORMAT")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datatypes::scalars::ScalarVector;
+    use datatypes::value::Value;
+    use datatypes::vectors::TimestampMillisecondVector;
+
+    use super::*;
+
+    #[test]
+    fn test_date_format() {
+        let f = DateFormatFunction::default();
+        assert_eq!("date_format", f.name());
+        assert_eq!(
+            ConcreteDataType::string_datatype(),
+            f.return_type(&[]).unwrap()
+        );
+
+        // 2023-03-01T06:35:02.123Z
+        let args: Vec<VectorRef> = vec![
+            Arc::new(TimestampMillisecondVector::from(vec![
+                Some(1677652502123),
+                Some(1677652502123),
+                Some(1677652502123),
+                None,
+            ])),
+            Arc::new(StringVector::from(vec![
+                Some("%Y-%m-%d %H:%M:%S%.3f"),
+                None,
+                Some("%Q"),
+                Some("%Y"),
+            ])),
+        ];
+        let vector = f.eval(FunctionContext::default(), &args).unwrap();
+        assert_eq!(Value::from("2023-03-01 06:35:02.123"), vector.get(0));
+        assert_eq!(Value::Null, vector.get(1));
+        assert_eq!(Value::Null, vector.get(2));
+        assert_eq!(Value::Null, vector.get(3));
+    }
+
+    #[test]
+    fn test_date_format_with_timezone() {
+        let f = DateFormatFunction::default();
+        let args: Vec<VectorRef> = vec![
+            Arc::new(TimestampMillisecondVector::from_vec(vec![
+                1677652502123,
+                1677652502123,
+            ])),
+            Arc::new(StringVector::from_slice(&["%Y-%m-%d %H:%M:%S %z"; 2])),
+            Arc::new(StringVector::from_slice(&[
+                "Asia/Shanghai",
+                "America/New_York",
+            ])),
+        ];
+        let vector = f.eval(FunctionContext::default(), &args).unwrap();
+        assert_eq!(Value::from("2023-03-01 14:35:02 +0800"), vector.get(0));
+        assert_eq!(Value::from("2023-03-01 01:35:02 -0500"), vector.get(1));
+
+        let args: Vec<VectorRef> = vec![
+            Arc::new(TimestampMillisecondVector::from_vec(vec![0])),
+            Arc::new(StringVector::from_slice(&["%Y"])),
+            Arc::new(StringVector::from_slice(&["Mars/Olympus_Mons"])),
+        ];
+        assert!(f.eval(FunctionContext::default(), &args).is_err());
+    }
+}
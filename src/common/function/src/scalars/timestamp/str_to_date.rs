@@ -0,0 +1,148 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::sync::Arc;
+
+use chrono::{NaiveDate, NaiveDateTime, TimeZone};
+use chrono_tz::Tz;
+use common_query::error::{InvalidFuncArgsSnafu, Result};
+use common_query::prelude::{Signature, Volatility};
+use datatypes::prelude::ConcreteDataType;
+use datatypes::vectors::{TimestampMillisecondVector, VectorRef};
+use snafu::ensure;
+
+use crate::scalars::function::{string_at, Function, FunctionContext};
+
+const NAME: &str = "str_to_date";
+
+/// `str_to_date(str, format)` parses the string by the `strftime` like format into a
+/// millisecond timestamp. The string is the wall clock time of the timezone of the
+/// function context, and a format without time parses the start of the day.
+/// Returns null if the string doesn't match the format.
+#[derive(Clone, Debug, Default)]
+pub struct StrToDateFunction;
+
+fn parse_timestamp_millis(s: &str, format: &str, tz: Tz) -> Option<i64> {
+    let datetime = NaiveDateTime::parse_from_str(s, format).ok().or_else(|| {
+        NaiveDate::parse_from_str(s, format)
+            .ok()
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+    })?;
+    tz.from_local_datetime(&datetime)
+        .earliest()
+        .map(|datetime| datetime.timestamp_millis())
+}
+
+impl Function for StrToDateFunction {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn return_type(&self, _input_types: &[ConcreteDataType]) -> Result<ConcreteDataType> {
+        Ok(ConcreteDataType::timestamp_millisecond_datatype())
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::exact(
+            vec![
+                ConcreteDataType::string_datatype(),
+                ConcreteDataType::string_datatype(),
+            ],
+            Volatility::Immutable,
+        )
+    }
+
+    fn eval(&self, func_ctx: FunctionContext, columns: &[VectorRef]) -> Result<VectorRef> {
+        ensure!(
+            columns.len() == 2,
+            InvalidFuncArgsSnafu {
+                err_msg: format!(
+                    "The length of the args is not correct, expect exactly two, have: {}",
+                    columns.len()
+                ),
+            }
+        );
+
+        let results = (0..columns[0].len())
+            .map(
+                |i| match (string_at(&columns[0], i), string_at(&columns[1], i)) {
+                    (Some(s), Some(format)) => parse_timestamp_millis(s, format, func_ctx.tz),
+                    _ => None,
+                },
+            )
+            .collect::<Vec<_>>();
+        Ok(Arc::new(TimestampMillisecondVector::from(results)))
+    }
+}
+
+impl fmt::Display for StrToDateFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "STR_TO_DATE")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_time::Timestamp;
+    use datatypes::value::Value;
+    use datatypes::vectors::StringVector;
+
+    use super::*;
+
+    #[test]
+    fn test_str_to_date() {
+        let f = StrToDateFunction::default();
+        assert_eq!("str_to_date", f.name());
+        assert_eq!(
+            ConcreteDataType::timestamp_millisecond_datatype(),
+            f.return_type(&[]).unwrap()
+        );
+
+        let args: Vec<VectorRef> = vec![
+            Arc::new(StringVector::from(vec![
+                Some("2023-03-01 06:35:02"),
+                Some("01/03/2023"),
+                Some("2023-03-01"),
+                None,
+            ])),
+            Arc::new(StringVector::from(vec![
+                Some("%Y-%m-%d %H:%M:%S"),
+                Some("%d/%m/%Y"),
+                Some("%d/%m/%Y"),
+                Some("%Y"),
+            ])),
+        ];
+        let vector = f.eval(FunctionContext::default(), &args).unwrap();
+        assert_eq!(
+            Value::Timestamp(Timestamp::new_millisecond(1677652502000)),
+            vector.get(0)
+        );
+        assert_eq!(
+            Value::Timestamp(Timestamp::new_millisecond(1677628800000)),
+            vector.get(1)
+        );
+        assert_eq!(Value::Null, vector.get(2));
+        assert_eq!(Value::Null, vector.get(3));
+
+        let func_ctx = FunctionContext {
+            tz: "Asia/Shanghai".parse().unwrap(),
+        };
+        let vector = f.eval(func_ctx, &args).unwrap();
+        assert_eq!(
+            Value::Timestamp(Timestamp::new_millisecond(1677652502000 - 8 * 3600 * 1000)),
+            vector.get(0)
+        );
+    }
+}
@@ -0,0 +1,169 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use chrono::{Offset, TimeZone};
+use common_query::error::{InvalidFuncArgsSnafu, Result, UnsupportedInputDataTypeSnafu};
+use common_query::prelude::{Signature, TypeSignature, Volatility};
+use common_time::timestamp::TimeUnit;
+use common_time::Timestamp;
+use datatypes::prelude::ConcreteDataType;
+use datatypes::vectors::VectorRef;
+use snafu::ensure;
+
+use crate::scalars::function::{string_at, Function, FunctionContext};
+use crate::scalars::timestamp::{parse_timezone, timestamp_types, timestamp_vector};
+
+const NAME: &str = "timezone";
+
+/// `timezone(ts, timezone)` converts the timestamp to the wall clock time of the
+/// timezone, e.g. `2023-01-01 00:00:00Z` is converted to `2023-01-01 08:00:00` in
+/// `Asia/Shanghai`. The result has the same time unit as the input.
+#[derive(Clone, Debug, Default)]
+pub struct TimezoneFunction;
+
+fn convert_timestamp(ts: Timestamp, tz_name: &str) -> Result<Option<i64>> {
+    let tz = parse_timezone(tz_name)?;
+    let Some(datetime) = ts.to_chrono_datetime() else {
+        return Ok(None);
+    };
+    let offset_secs = tz
+        .offset_from_utc_datetime(&datetime)
+        .fix()
+        .local_minus_utc() as i64;
+    let unit_per_sec = (TimeUnit::Second.factor() / ts.unit().factor()) as i64;
+    Ok(offset_secs
+        .checked_mul(unit_per_sec)
+        .and_then(|offset| ts.value().checked_add(offset)))
+}
+
+impl Function for TimezoneFunction {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn return_type(&self, input_types: &[ConcreteDataType]) -> Result<ConcreteDataType> {
+        match input_types.first() {
+            Some(ts_type @ ConcreteDataType::Timestamp(_)) => Ok(ts_type.clone()),
+            _ => UnsupportedInputDataTypeSnafu {
+                function: NAME,
+                datatypes: input_types.to_vec(),
+            }
+            .fail(),
+        }
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::one_of(
+            timestamp_types()
+                .into_iter()
+                .map(|ts_type| {
+                    TypeSignature::Exact(vec![ts_type, ConcreteDataType::string_datatype()])
+                })
+                .collect(),
+            Volatility::Immutable,
+        )
+    }
+
+    fn eval(&self, _func_ctx: FunctionContext, columns: &[VectorRef]) -> Result<VectorRef> {
+        ensure!(
+            columns.len() == 2,
+            InvalidFuncArgsSnafu {
+                err_msg: format!(
+                    "The length of the args is not correct, expect exactly two, have: {}",
+                    columns.len()
+                ),
+            }
+        );
+        let ConcreteDataType::Timestamp(ts_type) = columns[0].data_type() else {
+            return UnsupportedInputDataTypeSnafu {
+                function: NAME,
+                datatypes: columns.iter().map(|c| c.data_type()).collect::<Vec<_>>(),
+            }
+            .fail();
+        };
+
+        let mut results = Vec::with_capacity(columns[0].len());
+        for i in 0..columns[0].len() {
+            let value = match (columns[0].get(i).as_timestamp(), string_at(&columns[1], i)) {
+                (Some(ts), Some(tz_name)) => convert_timestamp(ts, tz_name)?,
+                _ => None,
+            };
+            results.push(value);
+        }
+        Ok(timestamp_vector(ts_type.unit(), results))
+    }
+}
+
+impl fmt::Display for TimezoneFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TIMEZONE")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use datatypes::value::Value;
+    use datatypes::vectors::{StringVector, TimestampSecondVector};
+
+    use super::*;
+
+    #[test]
+    fn test_timezone() {
+        let f = TimezoneFunction::default();
+        assert_eq!("timezone", f.name());
+        assert_eq!(
+            ConcreteDataType::timestamp_second_datatype(),
+            f.return_type(&[
+                ConcreteDataType::timestamp_second_datatype(),
+                ConcreteDataType::string_datatype()
+            ])
+            .unwrap()
+        );
+
+        // 2023-01-01T00:00:00Z
+        let args: Vec<VectorRef> = vec![
+            Arc::new(TimestampSecondVector::from(vec![
+                Some(1672531200),
+                Some(1672531200),
+                Some(1672531200),
+                None,
+            ])),
+            Arc::new(StringVector::from(vec![
+                Some("Asia/Shanghai"),
+                Some("America/New_York"),
+                None,
+                Some("UTC"),
+            ])),
+        ];
+        let vector = f.eval(FunctionContext::default(), &args).unwrap();
+        assert_eq!(
+            ConcreteDataType::timestamp_second_datatype(),
+            vector.data_type()
+        );
+        assert_eq!(
+            Value::Timestamp(Timestamp::new_second(1672531200 + 8 * 3600)),
+            vector.get(0)
+        );
+        assert_eq!(
+            Value::Timestamp(Timestamp::new_second(1672531200 - 5 * 3600)),
+            vector.get(1)
+        );
+        assert_eq!(Value::Null, vector.get(2));
+        assert_eq!(Value::Null, vector.get(3));
+    }
+}
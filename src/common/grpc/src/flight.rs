@@ -38,6 +38,8 @@ pub enum FlightMessage {
     Schema(SchemaRef),
     Recordbatch(RecordBatch),
     AffectedRows(usize),
+    /// The metrics of the sender's stream in JSON, sent after its record batches.
+    Metrics(String),
 }
 
 pub struct FlightEncoder {
@@ -93,6 +95,12 @@ impl FlightEncoder {
                     vec![],
                 )
             }
+            FlightMessage::Metrics(metrics) => FlightData::new(
+                None,
+                IpcMessage(build_none_flight_msg().into()),
+                FlightMetadata::default().encode_to_vec(),
+                metrics.into_bytes(),
+            ),
         }
     }
 }
@@ -118,6 +126,16 @@ impl FlightDecoder {
                 if let Some(AffectedRows { value }) = metadata.affected_rows {
                     return Ok(FlightMessage::AffectedRows(value as _));
                 }
+                if !flight_data.data_body.is_empty() {
+                    let metrics =
+                        String::from_utf8(flight_data.data_body.to_vec()).map_err(|e| {
+                            InvalidFlightDataSnafu {
+                                reason: e.to_string(),
+                            }
+                            .build()
+                        })?;
+                    return Ok(FlightMessage::Metrics(metrics));
+                }
                 InvalidFlightDataSnafu {
                     reason: "Expecting FlightMetadata have some meaningful content.",
                 }
@@ -185,6 +203,7 @@ pub fn flight_messages_to_recordbatches(messages: Vec<FlightMessage>) -> Result<
         for message in messages.into_iter().skip(1) {
             match message {
                 FlightMessage::Recordbatch(recordbatch) => recordbatches.push(recordbatch),
+                FlightMessage::Metrics(_) => {}
                 _ => {
                     return InvalidFlightDataSnafu {
                         reason: "Expect the following Flight Messages are all Recordbatches!",
@@ -278,6 +297,21 @@ mod test {
         assert_eq!(actual_batch, batch2);
     }
 
+    #[test]
+    fn test_encode_decode_metrics() {
+        let metrics = r#"{"counters":[["rows_scanned",3]],"plan":null}"#.to_string();
+        let flight_data = FlightEncoder::default().encode(FlightMessage::Metrics(metrics.clone()));
+
+        let decoder = &mut FlightDecoder::default();
+        let message = decoder.try_decode(flight_data).unwrap();
+        let FlightMessage::Metrics(actual) = message else { unreachable!() };
+        assert_eq!(actual, metrics);
+
+        let flight_data = FlightEncoder::default().encode(FlightMessage::AffectedRows(1));
+        let message = decoder.try_decode(flight_data).unwrap();
+        assert!(matches!(message, FlightMessage::AffectedRows(1)));
+    }
+
     #[test]
     fn test_flight_messages_to_recordbatches() {
         let schema = Arc::new(Schema::new(vec![ColumnSchema::new(
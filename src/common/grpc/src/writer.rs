@@ -66,6 +66,29 @@ impl LinesWriter {
         Ok(())
     }
 
+    /// Writes a nanosecond timestamp as is, without truncating it to milliseconds
+    /// like [LinesWriter::write_ts].
+    pub fn write_ts_nanosecond(&mut self, column_name: &str, value: i64) -> Result<()> {
+        let (idx, column) = self.mut_column(
+            column_name,
+            ColumnDataType::TimestampNanosecond,
+            SemanticType::Timestamp,
+        );
+        ensure!(
+            column.datatype == ColumnDataType::TimestampNanosecond as i32,
+            TypeMismatchSnafu {
+                column_name,
+                expected: "timestamp(ns)",
+                actual: format!("{:?}", column.datatype)
+            }
+        );
+        // It is safe to use unwrap here, because values has been initialized in mut_column()
+        let values = column.values.as_mut().unwrap();
+        values.ts_nanosecond_values.push(value);
+        self.null_masks[idx].push(false);
+        Ok(())
+    }
+
     pub fn write_tag(&mut self, column_name: &str, value: &str) -> Result<()> {
         let (idx, column) = self.mut_column(column_name, ColumnDataType::String, SemanticType::Tag);
         ensure!(
@@ -361,6 +384,28 @@ mod tests {
         verify_null_mask(&column.null_mask, vec![true, true, false]);
     }
 
+    #[test]
+    fn test_write_ts_nanosecond() {
+        let mut writer = LinesWriter::with_lines(2);
+        writer.write_ts_nanosecond("ts", 1_000_000_001).unwrap();
+        writer.commit();
+        writer.write_ts_nanosecond("ts", 1_000_000_002).unwrap();
+        writer.commit();
+        assert!(writer
+            .write_ts("ts", (1000, Precision::Millisecond))
+            .is_err());
+
+        let (columns, row_count) = writer.finish();
+        assert_eq!(2, row_count);
+        let column = &columns[0];
+        assert_eq!(ColumnDataType::TimestampNanosecond as i32, column.datatype);
+        assert_eq!(SemanticType::Timestamp as i32, column.semantic_type);
+        assert_eq!(
+            vec![1_000_000_001, 1_000_000_002],
+            column.values.as_ref().unwrap().ts_nanosecond_values
+        );
+    }
+
     fn verify_null_mask(data: &[u8], expected: Vec<bool>) {
         let bitvec = BitVec::from_slice(data);
         for (idx, b) in expected.iter().enumerate() {
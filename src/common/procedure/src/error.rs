@@ -123,6 +123,12 @@ pub enum Error {
         location: Location,
     },
 
+    #[snafu(display("Procedure {} is cancelled", procedure_id))]
+    ProcedureCancelled {
+        procedure_id: ProcedureId,
+        location: Location,
+    },
+
     #[snafu(display("Subprocedure {} failed, source: {}", subprocedure_id, source))]
     SubprocedureFailed {
         subprocedure_id: ProcedureId,
@@ -151,6 +157,7 @@ impl ErrorExt for Error {
                 StatusCode::InvalidArguments
             }
             Error::ProcedurePanic { .. } | Error::CorruptedData { .. } => StatusCode::Unexpected,
+            Error::ProcedureCancelled { .. } => StatusCode::Cancelled,
             Error::ProcedureExec { source, .. } => source.status_code(),
             Error::StartRemoveOutdatedMetaTask { source, .. }
             | Error::StopRemoveOutdatedMetaTask { source, .. } => source.status_code(),
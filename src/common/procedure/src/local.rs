@@ -16,8 +16,9 @@ mod lock;
 mod runner;
 
 use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use async_trait::async_trait;
 use backon::ExponentialBuilder;
@@ -36,8 +37,8 @@ use crate::local::runner::Runner;
 use crate::procedure::BoxedProcedureLoader;
 use crate::store::{ProcedureMessage, ProcedureStore, StateStoreRef};
 use crate::{
-    BoxedProcedure, ContextProvider, LockKey, ProcedureId, ProcedureManager, ProcedureState,
-    ProcedureWithId, Watcher,
+    BoxedProcedure, ContextProvider, LockKey, ProcedureId, ProcedureInfo, ProcedureManager,
+    ProcedureState, ProcedureWithId, StateRecord, Watcher,
 };
 
 /// The expired time of a procedure's metadata.
@@ -59,6 +60,8 @@ pub(crate) struct ProcedureMeta {
     lock_notify: Notify,
     /// Parent procedure id.
     parent_id: Option<ProcedureId>,
+    /// Type name of the procedure.
+    type_name: String,
     /// Notify to wait for subprocedures.
     child_notify: Notify,
     /// Lock required by this procedure.
@@ -69,20 +72,40 @@ pub(crate) struct ProcedureMeta {
     state_receiver: Receiver<ProcedureState>,
     /// Id of child procedures.
     children: Mutex<Vec<ProcedureId>>,
+    /// Whether users request to cancel this procedure.
+    cancelled: AtomicBool,
+    /// Milliseconds since the unix epoch when the procedure is submitted.
+    start_time_ms: i64,
+    /// States the procedure has turned into.
+    state_history: Mutex<Vec<StateRecord>>,
 }
 
 impl ProcedureMeta {
-    fn new(id: ProcedureId, parent_id: Option<ProcedureId>, lock_key: LockKey) -> ProcedureMeta {
+    fn new(
+        id: ProcedureId,
+        parent_id: Option<ProcedureId>,
+        type_name: String,
+        lock_key: LockKey,
+    ) -> ProcedureMeta {
         let (state_sender, state_receiver) = watch::channel(ProcedureState::Running);
+        let start_time_ms = current_time_ms();
+        let state_history = vec![StateRecord {
+            state: ProcedureState::Running,
+            time_ms: start_time_ms,
+        }];
         ProcedureMeta {
             id,
             lock_notify: Notify::new(),
             parent_id,
+            type_name,
             child_notify: Notify::new(),
             lock_key,
             state_sender,
             state_receiver,
             children: Mutex::new(Vec::new()),
+            cancelled: AtomicBool::new(false),
+            start_time_ms,
+            state_history: Mutex::new(state_history),
         }
     }
 
@@ -91,8 +114,35 @@ impl ProcedureMeta {
         self.state_receiver.borrow().clone()
     }
 
+    /// Marks the procedure as cancelled.
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns true if users request to cancel the procedure.
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Returns [ProcedureInfo] of the procedure.
+    fn info(&self) -> ProcedureInfo {
+        ProcedureInfo {
+            id: self.id,
+            type_name: self.type_name.clone(),
+            parent_id: self.parent_id,
+            lock_key: self.lock_key.clone(),
+            state: self.state(),
+            state_history: self.state_history.lock().unwrap().clone(),
+            start_time_ms: self.start_time_ms,
+        }
+    }
+
     /// Update current [ProcedureState].
     fn set_state(&self, state: ProcedureState) {
+        self.state_history.lock().unwrap().push(StateRecord {
+            state: state.clone(),
+            time_ms: current_time_ms(),
+        });
         // Safety: ProcedureMeta also holds the receiver, so `send()` should never fail.
         self.state_sender.send(state).unwrap();
     }
@@ -118,6 +168,14 @@ impl ProcedureMeta {
 /// Reference counted pointer to [ProcedureMeta].
 type ProcedureMetaRef = Arc<ProcedureMeta>;
 
+/// Returns milliseconds since the unix epoch.
+fn current_time_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default()
+}
+
 /// Procedure loaded from store.
 struct LoadedProcedure {
     procedure: BoxedProcedure,
@@ -135,6 +193,9 @@ pub(crate) struct ManagerContext {
     messages: Mutex<HashMap<ProcedureId, ProcedureMessage>>,
     /// Ids and finished time of finished procedures.
     finished_procedures: Mutex<VecDeque<(ProcedureId, Instant)>>,
+    /// Failed root procedures and ids of their procedure trees. States of these
+    /// procedures are kept in the store for retrying until their metadata is outdated.
+    failed_procedures: Mutex<HashMap<ProcedureId, Vec<ProcedureId>>>,
 }
 
 #[async_trait]
@@ -153,6 +214,7 @@ impl ManagerContext {
             procedures: RwLock::new(HashMap::new()),
             messages: Mutex::new(HashMap::new()),
             finished_procedures: Mutex::new(VecDeque::new()),
+            failed_procedures: Mutex::new(HashMap::new()),
         }
     }
 
@@ -184,6 +246,72 @@ impl ManagerContext {
         procedures.get(&procedure_id).map(|meta| meta.state())
     }
 
+    /// Returns [ProcedureInfo] of all procedures in the context.
+    fn list_procedures(&self) -> Vec<ProcedureInfo> {
+        let procedures = self.procedures.read().unwrap();
+        procedures.values().map(|meta| meta.info()).collect()
+    }
+
+    /// Returns [ProcedureInfo] of specific `procedure_id`.
+    fn procedure_info(&self, procedure_id: ProcedureId) -> Option<ProcedureInfo> {
+        let procedures = self.procedures.read().unwrap();
+        procedures.get(&procedure_id).map(|meta| meta.info())
+    }
+
+    /// Keeps the failed root procedure with specific `procedure_id` and ids of its
+    /// procedure tree, so users can retry it.
+    fn insert_failed_procedure(&self, procedure_id: ProcedureId, procedure_ids: Vec<ProcedureId>) {
+        let mut failed_procedures = self.failed_procedures.lock().unwrap();
+        let _ = failed_procedures.insert(procedure_id, procedure_ids);
+    }
+
+    /// Returns ids of the procedure tree of the failed root procedure with specific
+    /// `procedure_id`.
+    fn failed_procedure_tree(&self, procedure_id: ProcedureId) -> Option<Vec<ProcedureId>> {
+        let failed_procedures = self.failed_procedures.lock().unwrap();
+        failed_procedures.get(&procedure_id).cloned()
+    }
+
+    /// Takes the failed root procedure with specific `procedure_id` and removes
+    /// metadata of its procedure tree, so the procedure can be submitted again.
+    ///
+    /// Returns the state history of the root procedure, or `None` if the procedure
+    /// is not failed anymore.
+    fn take_failed_procedure(&self, procedure_id: ProcedureId) -> Option<Vec<StateRecord>> {
+        let ids = {
+            let mut failed_procedures = self.failed_procedures.lock().unwrap();
+            failed_procedures.remove(&procedure_id)?
+        };
+        {
+            let mut finished_procedures = self.finished_procedures.lock().unwrap();
+            finished_procedures.retain(|(id, _)| !ids.contains(id));
+        }
+        let mut procedures = self.procedures.write().unwrap();
+        let state_history = procedures
+            .get(&procedure_id)
+            .map(|meta| meta.state_history.lock().unwrap().clone())
+            .unwrap_or_default();
+        for id in ids {
+            let _ = procedures.remove(&id);
+        }
+
+        Some(state_history)
+    }
+
+    /// Marks the running procedure with specific `procedure_id` as cancelled.
+    ///
+    /// Returns false if the procedure doesn't exist or is already finished.
+    fn cancel_procedure(&self, procedure_id: ProcedureId) -> bool {
+        let procedures = self.procedures.read().unwrap();
+        match procedures.get(&procedure_id) {
+            Some(meta) if !meta.state().is_done() && !meta.state().is_failed() => {
+                meta.cancel();
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Returns the [Watcher] of specific `procedure_id`.
     fn watcher(&self, procedure_id: ProcedureId) -> Option<Watcher> {
         let procedures = self.procedures.read().unwrap();
@@ -308,11 +436,14 @@ impl ManagerContext {
     }
 
     /// Remove metadata of outdated procedures.
-    fn remove_outdated_meta(&self, ttl: Duration) {
+    ///
+    /// Returns ids of outdated failed procedures, whose states should be removed
+    /// from the store.
+    fn remove_outdated_meta(&self, ttl: Duration) -> Vec<ProcedureId> {
         let ids = {
             let mut finished_procedures = self.finished_procedures.lock().unwrap();
             if finished_procedures.is_empty() {
-                return;
+                return Vec::new();
             }
 
             let mut ids_to_remove = Vec::new();
@@ -330,13 +461,25 @@ impl ManagerContext {
         };
 
         if ids.is_empty() {
-            return;
+            return Vec::new();
+        }
+
+        let mut failed_ids = Vec::new();
+        {
+            let mut failed_procedures = self.failed_procedures.lock().unwrap();
+            for id in &ids {
+                if let Some(tree) = failed_procedures.remove(id) {
+                    failed_ids.extend(tree);
+                }
+            }
         }
 
         let mut procedures = self.procedures.write().unwrap();
         for id in ids {
             let _ = procedures.remove(&id);
         }
+
+        failed_ids
     }
 }
 
@@ -375,16 +518,18 @@ impl LocalManager {
     /// Create a new [LocalManager] with specific `config`.
     pub fn new(config: ManagerConfig, state_store: StateStoreRef) -> LocalManager {
         let manager_ctx = Arc::new(ManagerContext::new());
+        let procedure_store = Arc::new(ProcedureStore::new(&config.parent_path, state_store));
         let remove_outdated_meta_task = RepeatedTask::new(
             config.remove_outdated_meta_task_interval,
             Box::new(RemoveOutdatedMetaFunction {
                 manager_ctx: manager_ctx.clone(),
+                procedure_store: procedure_store.clone(),
                 ttl: config.remove_outdated_meta_ttl,
             }),
         );
         LocalManager {
             manager_ctx,
-            procedure_store: Arc::new(ProcedureStore::new(&config.parent_path, state_store)),
+            procedure_store,
             max_retry_times: config.max_retry_times,
             retry_delay: config.retry_delay,
             remove_outdated_meta_task,
@@ -398,7 +543,12 @@ impl LocalManager {
         step: u32,
         procedure: BoxedProcedure,
     ) -> Result<Watcher> {
-        let meta = Arc::new(ProcedureMeta::new(procedure_id, None, procedure.lock_key()));
+        let meta = Arc::new(ProcedureMeta::new(
+            procedure_id,
+            None,
+            procedure.type_name().to_string(),
+            procedure.lock_key(),
+        ));
         let runner = Runner {
             meta: meta.clone(),
             procedure,
@@ -524,10 +674,94 @@ impl ProcedureManager for LocalManager {
     fn procedure_watcher(&self, procedure_id: ProcedureId) -> Option<Watcher> {
         self.manager_ctx.watcher(procedure_id)
     }
+
+    fn list_procedures(&self) -> Vec<ProcedureInfo> {
+        self.manager_ctx.list_procedures()
+    }
+
+    fn procedure_info(&self, procedure_id: ProcedureId) -> Option<ProcedureInfo> {
+        self.manager_ctx.procedure_info(procedure_id)
+    }
+
+    async fn cancel(&self, procedure_id: ProcedureId) -> Result<bool> {
+        let cancelled = self.manager_ctx.cancel_procedure(procedure_id);
+        if cancelled {
+            logging::info!("Procedure {} is requested to cancel", procedure_id);
+        }
+        Ok(cancelled)
+    }
+
+    async fn retry(&self, procedure_id: ProcedureId) -> Result<bool> {
+        let Some(procedure_ids) = self.manager_ctx.failed_procedure_tree(procedure_id) else {
+            return Ok(false);
+        };
+
+        // The failed step may have changed the procedure in memory, so we reload
+        // procedures from their last persisted steps.
+        let mut messages = HashMap::with_capacity(procedure_ids.len());
+        for id in &procedure_ids {
+            if let Some(message) = self.procedure_store.load_procedure(*id).await? {
+                let _ = messages.insert(*id, message);
+            }
+        }
+        let Some(loaded_procedure) = messages
+            .remove(&procedure_id)
+            .and_then(|message| {
+                self.manager_ctx
+                    .load_one_procedure_from_message(procedure_id, &message)
+            }) else {
+            logging::warn!(
+                "Procedure {} can't be retried as it has no persisted step",
+                procedure_id
+            );
+            return Ok(false);
+        };
+
+        // Otherwise the rollback keys would mark the procedures as finished while
+        // recovering.
+        for id in &procedure_ids {
+            self.procedure_store.clear_rollback(*id).await?;
+        }
+
+        let Some(state_history) = self.manager_ctx.take_failed_procedure(procedure_id) else {
+            // Another retry takes the procedure.
+            return Ok(false);
+        };
+        // Subprocedures are loaded from the messages when the root procedure submits them.
+        self.manager_ctx.messages.lock().unwrap().extend(messages);
+
+        logging::info!(
+            "Retry procedure {}-{}, step: {}",
+            loaded_procedure.procedure.type_name(),
+            procedure_id,
+            loaded_procedure.step
+        );
+
+        let _ = self.submit_root(
+            procedure_id,
+            loaded_procedure.step,
+            loaded_procedure.procedure,
+        )?;
+
+        // Keeps states of previous attempts in the history.
+        if let Some(meta) = self
+            .manager_ctx
+            .procedures
+            .read()
+            .unwrap()
+            .get(&procedure_id)
+        {
+            let mut history = meta.state_history.lock().unwrap();
+            let _ = history.splice(0..0, state_history);
+        }
+
+        Ok(true)
+    }
 }
 
 struct RemoveOutdatedMetaFunction {
     manager_ctx: Arc<ManagerContext>,
+    procedure_store: Arc<ProcedureStore>,
     ttl: Duration,
 }
 
@@ -538,7 +772,13 @@ impl TaskFunction<Error> for RemoveOutdatedMetaFunction {
     }
 
     async fn call(&mut self) -> Result<()> {
-        self.manager_ctx.remove_outdated_meta(self.ttl);
+        let failed_ids = self.manager_ctx.remove_outdated_meta(self.ttl);
+        // Failed procedures can't be retried anymore.
+        for procedure_id in failed_ids {
+            if let Err(e) = self.procedure_store.delete_procedure(procedure_id).await {
+                logging::error!(e; "Failed to delete procedure {}", procedure_id);
+            }
+        }
         Ok(())
     }
 }
@@ -553,7 +793,12 @@ mod test_util {
     use super::*;
 
     pub(crate) fn procedure_meta_for_test() -> ProcedureMeta {
-        ProcedureMeta::new(ProcedureId::random(), None, LockKey::default())
+        ProcedureMeta::new(
+            ProcedureId::random(),
+            None,
+            "test".to_string(),
+            LockKey::default(),
+        )
     }
 
     pub(crate) fn new_object_store(dir: &TempDir) -> ObjectStore {
@@ -566,6 +811,8 @@ mod test_util {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::atomic::AtomicU32;
+
     use common_error::mock::MockError;
     use common_error::status_code::StatusCode;
     use common_test_util::temp_dir::create_temp_dir;
@@ -787,6 +1034,15 @@ mod tests {
         watcher.changed().await.unwrap();
         assert!(watcher.borrow().is_done());
 
+        let procedures = manager.list_procedures();
+        assert_eq!(1, procedures.len());
+        assert_eq!(procedure_id, procedures[0].id);
+        assert_eq!("ProcedureToLoad", procedures[0].type_name);
+        assert!(procedures[0].state.is_done());
+        // A finished procedure can't be cancelled.
+        assert!(!manager.cancel(procedure_id).await.unwrap());
+        assert!(!manager.cancel(ProcedureId::random()).await.unwrap());
+
         // Try to submit procedure with same id again.
         let err = manager
             .submit(ProcedureWithId {
@@ -860,6 +1116,118 @@ mod tests {
         check_procedure(MockProcedure { panic: true }).await;
     }
 
+    #[tokio::test]
+    async fn test_retry_failed_procedure() {
+        let dir = create_temp_dir("retry_failed");
+        let config = ManagerConfig {
+            parent_path: "data/".to_string(),
+            max_retry_times: 3,
+            retry_delay: Duration::from_millis(500),
+            ..Default::default()
+        };
+        let state_store = Arc::new(ObjectStateStore::new(test_util::new_object_store(&dir)));
+        let manager = LocalManager::new(config, state_store);
+
+        #[derive(Debug)]
+        struct FailOnceProcedure {
+            counter: u32,
+            failed: Arc<AtomicBool>,
+            result: Arc<AtomicU32>,
+        }
+
+        #[async_trait]
+        impl Procedure for FailOnceProcedure {
+            fn type_name(&self) -> &str {
+                "FailOnceProcedure"
+            }
+
+            async fn execute(&mut self, _ctx: &Context) -> Result<Status> {
+                if self.counter == 0 {
+                    self.counter = 1;
+                    return Ok(Status::executing(true));
+                }
+
+                // Mutates the state before failing.
+                self.counter += 10;
+                if self.failed.swap(true, Ordering::Relaxed) {
+                    self.result.store(self.counter, Ordering::Relaxed);
+                    Ok(Status::Done)
+                } else {
+                    Err(Error::external(MockError::new(StatusCode::Unexpected)))
+                }
+            }
+
+            fn dump(&self) -> Result<String> {
+                Ok(self.counter.to_string())
+            }
+
+            fn lock_key(&self) -> LockKey {
+                LockKey::single("test.retry")
+            }
+        }
+
+        let failed = Arc::new(AtomicBool::new(false));
+        let result = Arc::new(AtomicU32::new(0));
+        let (loader_failed, loader_result) = (failed.clone(), result.clone());
+        let loader = move |data: &str| -> Result<BoxedProcedure> {
+            let procedure = FailOnceProcedure {
+                counter: data.parse().unwrap(),
+                failed: loader_failed.clone(),
+                result: loader_result.clone(),
+            };
+            Ok(Box::new(procedure))
+        };
+        manager
+            .register_loader("FailOnceProcedure", Box::new(loader))
+            .unwrap();
+
+        let procedure_id = ProcedureId::random();
+        let mut watcher = manager
+            .submit(ProcedureWithId {
+                id: procedure_id,
+                procedure: Box::new(FailOnceProcedure {
+                    counter: 0,
+                    failed,
+                    result: result.clone(),
+                }),
+            })
+            .await
+            .unwrap();
+        watcher.changed().await.unwrap();
+        assert!(watcher.borrow().is_failed());
+
+        // Unknown procedures can't be retried.
+        assert!(!manager.retry(ProcedureId::random()).await.unwrap());
+
+        // The runner keeps the procedure after notifying the state.
+        let mut retried = false;
+        for _ in 0..100 {
+            if manager.retry(procedure_id).await.unwrap() {
+                retried = true;
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(retried);
+
+        let mut watcher = manager.procedure_watcher(procedure_id).unwrap();
+        watcher.changed().await.unwrap();
+        assert!(watcher.borrow().is_done());
+        // The procedure reruns from the persisted state instead of the mutated one.
+        assert_eq!(11, result.load(Ordering::Relaxed));
+        // Done procedures can't be retried.
+        assert!(!manager.retry(procedure_id).await.unwrap());
+
+        let info = manager.procedure_info(procedure_id).unwrap();
+        assert_eq!("FailOnceProcedure", info.type_name);
+        let states: Vec<_> = info
+            .state_history
+            .iter()
+            .map(|record| record.state.as_str())
+            .collect();
+        assert_eq!(vec!["Running", "Failed", "Running", "Done"], states);
+    }
+
     #[tokio::test]
     async fn test_remove_outdated_meta_task() {
         let dir = create_temp_dir("remove_outdated_meta_task");
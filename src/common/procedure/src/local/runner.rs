@@ -19,7 +19,7 @@ use backon::{BackoffBuilder, ExponentialBuilder};
 use common_telemetry::logging;
 use tokio::time;
 
-use crate::error::{ProcedurePanicSnafu, Result};
+use crate::error::{ProcedureCancelledSnafu, ProcedurePanicSnafu, Result};
 use crate::local::{ManagerContext, ProcedureMeta, ProcedureMetaRef};
 use crate::store::ProcedureStore;
 use crate::ProcedureState::Retrying;
@@ -102,7 +102,6 @@ impl Drop for ProcedureGuard {
     }
 }
 
-// TODO(yingwen): Support cancellation.
 pub(crate) struct Runner {
     pub(crate) meta: ProcedureMetaRef,
     pub(crate) procedure: BoxedProcedure,
@@ -152,16 +151,13 @@ impl Runner {
             let procedure_ids = self.manager_ctx.procedures_in_tree(&self.meta);
             // Clean resources.
             self.manager_ctx.on_procedures_finish(&procedure_ids);
-            for id in procedure_ids {
-                if let Err(e) = self.store.delete_procedure(id).await {
-                    logging::error!(
-                        e;
-                        "Runner {}-{} failed to delete procedure {}",
-                        self.procedure.type_name(),
-                        self.meta.id,
-                        id,
-                    );
-                }
+            if self.meta.state().is_failed() {
+                // Keeps states of the failed procedure so users can retry it from the
+                // last persisted step later.
+                self.manager_ctx
+                    .insert_failed_procedure(self.meta.id, procedure_ids);
+            } else {
+                self.delete_procedures(procedure_ids).await;
             }
         }
 
@@ -172,6 +168,21 @@ impl Runner {
         );
     }
 
+    /// Deletes states of procedures with specific `procedure_ids` from the store.
+    async fn delete_procedures(&self, procedure_ids: Vec<ProcedureId>) {
+        for id in procedure_ids {
+            if let Err(e) = self.store.delete_procedure(id).await {
+                logging::error!(
+                    e;
+                    "Runner {}-{} failed to delete procedure {}",
+                    self.procedure.type_name(),
+                    self.meta.id,
+                    id,
+                );
+            }
+        }
+    }
+
     async fn execute_procedure_in_loop(&mut self) {
         let ctx = Context {
             procedure_id: self.meta.id,
@@ -228,6 +239,20 @@ impl Runner {
             let err = state.error().unwrap();
             return self.rollback(err.clone()).await;
         }
+        // Cancellation takes effect between steps, so the procedure always stops at
+        // a persisted state.
+        if self.meta.is_cancelled() {
+            logging::info!(
+                "Procedure {}-{} is cancelled",
+                self.procedure.type_name(),
+                self.meta.id,
+            );
+            let err = ProcedureCancelledSnafu {
+                procedure_id: self.meta.id,
+            }
+            .build();
+            return self.rollback(Arc::new(err)).await;
+        }
         match self.procedure.execute(ctx).await {
             Ok(status) => {
                 logging::debug!(
@@ -305,6 +330,7 @@ impl Runner {
         let meta = Arc::new(ProcedureMeta::new(
             procedure_id,
             Some(self.meta.id),
+            procedure.type_name().to_string(),
             procedure.lock_key(),
         ));
         let runner = Runner {
@@ -450,6 +476,7 @@ impl Runner {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
     use std::sync::Arc;
 
     use async_trait::async_trait;
@@ -802,6 +829,42 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn test_execute_cancelled() {
+        let exec_fn = |_| async { Ok(Status::executing(true)) }.boxed();
+        let cancelled = ProcedureAdapter {
+            data: "cancelled".to_string(),
+            lock_key: LockKey::single("catalog.schema.table"),
+            exec_fn,
+        };
+
+        let dir = create_temp_dir("cancelled");
+        let meta = cancelled.new_meta(ROOT_ID);
+        let ctx = context_without_provider(meta.id);
+        let object_store = test_util::new_object_store(&dir);
+        let procedure_store = Arc::new(ProcedureStore::from_object_store(object_store.clone()));
+        let mut runner = new_runner(meta.clone(), Box::new(cancelled), procedure_store.clone());
+
+        let res = runner.execute_once(&ctx).await;
+        assert!(res.is_continue(), "{res:?}");
+
+        meta.cancel();
+        let res = runner.execute_once(&ctx).await;
+        assert!(res.is_failed(), "{res:?}");
+        let state = meta.state();
+        assert!(matches!(
+            state.error().unwrap().as_ref(),
+            Error::ProcedureCancelled { .. }
+        ));
+        check_files(
+            &object_store,
+            &procedure_store,
+            ctx.procedure_id,
+            &["0000000000.step", "0000000001.rollback"],
+        )
+        .await;
+    }
+
     #[tokio::test]
     async fn test_execute_on_retry_later_error() {
         let mut times = 0;
@@ -936,17 +999,42 @@ mod tests {
 
         let object_store = test_util::new_object_store(&dir);
         let procedure_store = Arc::new(ProcedureStore::from_object_store(object_store.clone()));
-        let mut runner = new_runner(meta.clone(), Box::new(parent), procedure_store);
+        let mut runner = new_runner(meta.clone(), Box::new(parent), procedure_store.clone());
 
         let manager_ctx = Arc::new(ManagerContext::new());
         // Manually add this procedure to the manager ctx.
         assert!(manager_ctx.try_insert_procedure(meta.clone()));
         // Replace the manager ctx.
-        runner.manager_ctx = manager_ctx;
+        runner.manager_ctx = manager_ctx.clone();
 
         // Run the runner and execute the procedure.
         runner.run().await;
         let err = meta.state().error().unwrap().to_string();
         assert!(err.contains("subprocedure failed"), "{err}");
+
+        // States of the failed procedure tree are kept for retrying.
+        let ids: HashSet<_> = manager_ctx
+            .failed_procedure_tree(meta.id)
+            .unwrap()
+            .into_iter()
+            .collect();
+        let expect = HashSet::from([meta.id, child_id]);
+        assert_eq!(expect, ids);
+        check_files(
+            &object_store,
+            &procedure_store,
+            meta.id,
+            &["0000000000.step", "0000000001.rollback"],
+        )
+        .await;
+
+        // Outdated failed procedures can't be retried.
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        let ids: HashSet<_> = manager_ctx
+            .remove_outdated_meta(Duration::from_millis(1))
+            .into_iter()
+            .collect();
+        assert_eq!(expect, ids);
+        assert!(manager_ctx.failed_procedure_tree(meta.id).is_none());
     }
 }
@@ -17,7 +17,8 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use serde::{Deserialize, Serialize};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
 use smallvec::{smallvec, SmallVec};
 use snafu::{ResultExt, Snafu};
 use uuid::Uuid;
@@ -270,6 +271,65 @@ impl ProcedureState {
             _ => None,
         }
     }
+
+    /// Returns the name of the state.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProcedureState::Running => "Running",
+            ProcedureState::Done => "Done",
+            ProcedureState::Retrying { .. } => "Retrying",
+            ProcedureState::Failed { .. } => "Failed",
+        }
+    }
+}
+
+/// Serializes [ProcedureState] as its name and error message.
+fn serialize_state<S: Serializer>(
+    state: &ProcedureState,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    let mut s = serializer.serialize_struct("ProcedureState", 2)?;
+    s.serialize_field("status", state.as_str())?;
+    s.serialize_field("error", &state.error().map(|e| e.to_string()))?;
+    s.end()
+}
+
+fn serialize_lock_key<S: Serializer>(
+    lock_key: &LockKey,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.collect_seq(lock_key.keys_to_lock())
+}
+
+/// A [ProcedureState] the procedure turned into.
+#[derive(Debug, Clone, Serialize)]
+pub struct StateRecord {
+    #[serde(serialize_with = "serialize_state")]
+    pub state: ProcedureState,
+    /// Time in milliseconds since the unix epoch when the procedure turned into the state.
+    pub time_ms: i64,
+}
+
+/// Information of a procedure in the [ProcedureManager].
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcedureInfo {
+    /// Id of the procedure.
+    pub id: ProcedureId,
+    /// Type name of the procedure.
+    pub type_name: String,
+    /// Parent procedure id.
+    pub parent_id: Option<ProcedureId>,
+    /// Keys locked by the procedure.
+    #[serde(serialize_with = "serialize_lock_key")]
+    pub lock_key: LockKey,
+    /// Current state of the procedure.
+    #[serde(serialize_with = "serialize_state")]
+    pub state: ProcedureState,
+    /// States the procedure has turned into, in order.
+    pub state_history: Vec<StateRecord>,
+    /// Time in milliseconds since the unix epoch when the procedure was submitted
+    /// to (or recovered by) the manager.
+    pub start_time_ms: i64,
 }
 
 // TODO(yingwen): Shutdown
@@ -300,6 +360,27 @@ pub trait ProcedureManager: Send + Sync + 'static {
 
     /// Returns a [Watcher] to watch [ProcedureState] of specific procedure.
     fn procedure_watcher(&self, procedure_id: ProcedureId) -> Option<Watcher>;
+
+    /// Returns [ProcedureInfo] of all procedures in the manager, including
+    /// finished procedures whose metadata is not removed yet.
+    fn list_procedures(&self) -> Vec<ProcedureInfo>;
+
+    /// Requests to cancel the procedure with specific `procedure_id`.
+    ///
+    /// The procedure stops before executing its next step and then turns into
+    /// [ProcedureState::Failed]. Returns `Ok(false)` if the procedure doesn't
+    /// exist or is already finished.
+    async fn cancel(&self, procedure_id: ProcedureId) -> Result<bool>;
+
+    /// Returns [ProcedureInfo] of the procedure with specific `procedure_id`.
+    fn procedure_info(&self, procedure_id: ProcedureId) -> Option<ProcedureInfo>;
+
+    /// Reruns the failed root procedure with specific `procedure_id` from its last
+    /// persisted step.
+    ///
+    /// Returns `Ok(false)` if the procedure doesn't exist, isn't a failed root
+    /// procedure, or has no persisted state to reload.
+    async fn retry(&self, procedure_id: ProcedureId) -> Result<bool>;
 }
 
 /// Ref-counted pointer to the [ProcedureManager].
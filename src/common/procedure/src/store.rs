@@ -176,6 +176,56 @@ impl ProcedureStore {
         Ok(())
     }
 
+    /// Load the message of the last step of the procedure with specific `procedure_id`.
+    ///
+    /// Returns `None` if the procedure is committed or has no step.
+    pub(crate) async fn load_procedure(
+        &self,
+        procedure_id: ProcedureId,
+    ) -> Result<Option<ProcedureMessage>> {
+        let path = proc_path!(self, "{procedure_id}/");
+        let mut last_step: Option<(ParsedKey, Vec<u8>)> = None;
+        let mut key_values = self.store.walk_top_down(&path).await?;
+        while let Some((key, value)) = key_values.try_next().await? {
+            let Some(curr_key) = ParsedKey::parse_str(&self.proc_path, &key) else {
+                logging::warn!("Unknown key while loading procedure, key: {}", key);
+                continue;
+            };
+            match curr_key.key_type {
+                KeyType::Commit => return Ok(None),
+                KeyType::Rollback => (),
+                KeyType::Step => {
+                    if last_step
+                        .as_ref()
+                        .map(|(last_key, _)| last_key.step < curr_key.step)
+                        .unwrap_or(true)
+                    {
+                        last_step = Some((curr_key, value));
+                    }
+                }
+            }
+        }
+
+        Ok(last_step.and_then(|(key, value)| self.load_one_message(&key, &value)))
+    }
+
+    /// Delete rollback flags of the procedure with specific `procedure_id` from the storage.
+    pub(crate) async fn clear_rollback(&self, procedure_id: ProcedureId) -> Result<()> {
+        let path = proc_path!(self, "{procedure_id}/");
+        let mut key_values = self.store.walk_top_down(&path).await?;
+        let mut rollback_keys = Vec::new();
+        while let Some((key, _)) = key_values.try_next().await? {
+            let Some(curr_key) = ParsedKey::parse_str(&self.proc_path, &key) else {
+                continue;
+            };
+            if curr_key.key_type == KeyType::Rollback {
+                rollback_keys.push(key);
+            }
+        }
+
+        self.store.batch_delete(rollback_keys.as_slice()).await
+    }
+
     /// Load procedures from the storage. Returns a map of uncommitted procedures and a list
     /// of finished procedures' ids.
     pub(crate) async fn load_messages(
@@ -538,6 +588,37 @@ mod tests {
         assert_eq!(&[procedure_id], &finished[..]);
     }
 
+    #[tokio::test]
+    async fn test_load_rolled_back_procedure() {
+        let dir = create_temp_dir("load_rolled_back_procedure");
+        let store = procedure_store_for_test(&dir);
+
+        let procedure_id = ProcedureId::random();
+        let procedure: BoxedProcedure = Box::new(MockProcedure::new("step 0"));
+        store
+            .store_procedure(procedure_id, 0, &procedure, None)
+            .await
+            .unwrap();
+        let procedure: BoxedProcedure = Box::new(MockProcedure::new("step 1"));
+        store
+            .store_procedure(procedure_id, 1, &procedure, None)
+            .await
+            .unwrap();
+        store.rollback_procedure(procedure_id, 2).await.unwrap();
+
+        let msg = store.load_procedure(procedure_id).await.unwrap().unwrap();
+        assert_eq!("step 1", msg.data);
+        assert_eq!(1, msg.step);
+
+        store.clear_rollback(procedure_id).await.unwrap();
+        let (messages, finished) = store.load_messages().await.unwrap();
+        assert!(finished.is_empty());
+        assert_eq!("step 1", messages.get(&procedure_id).unwrap().data);
+
+        store.commit_procedure(procedure_id, 2).await.unwrap();
+        assert!(store.load_procedure(procedure_id).await.unwrap().is_none());
+    }
+
     #[tokio::test]
     async fn test_delete_procedure() {
         let dir = create_temp_dir("delete_procedure");
@@ -48,6 +48,25 @@ impl Debug for Output {
 
 pub use datafusion::physical_plan::ExecutionPlan as DfPhysicalPlan;
 
+/// When a script runs by itself and where its output goes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScriptSchedule {
+    /// Cron expression to run the script periodically.
+    pub cron: Option<String>,
+    /// Table whose new data triggers the script.
+    pub source_table: Option<String>,
+    /// Table the output of scheduled runs is written into, the output is
+    /// discarded if absent.
+    pub target_table: Option<String>,
+}
+
+impl ScriptSchedule {
+    /// Returns whether the script is run by the scheduler.
+    pub fn is_scheduled(&self) -> bool {
+        self.cron.is_some() || self.source_table.is_some()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AddColumnLocation {
     First,
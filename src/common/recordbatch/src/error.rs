@@ -17,6 +17,7 @@ use std::any::Any;
 
 use common_error::ext::{BoxedError, ErrorExt};
 use common_error::status_code::StatusCode;
+use datafusion::error::DataFusionError;
 use datatypes::prelude::ConcreteDataType;
 use snafu::{Location, Snafu};
 
@@ -54,7 +55,7 @@ pub enum Error {
 
     #[snafu(display("Failed to poll stream, source: {}", source))]
     PollStream {
-        source: datafusion::error::DataFusionError,
+        source: DataFusionError,
         location: Location,
     },
 
@@ -109,9 +110,13 @@ impl ErrorExt for Error {
         match self {
             Error::NewDfRecordBatch { .. } => StatusCode::InvalidArguments,
 
+            Error::PollStream { source, .. } => match source {
+                DataFusionError::ResourcesExhausted(_) => StatusCode::ResourcesExhausted,
+                _ => StatusCode::Internal,
+            },
+
             Error::DataTypes { .. }
             | Error::CreateRecordBatches { .. }
-            | Error::PollStream { .. }
             | Error::Format { .. }
             | Error::InitRecordbatchStream { .. }
             | Error::ColumnNotExists { .. }
@@ -18,7 +18,7 @@ mod recordbatch;
 pub mod util;
 
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use datafusion::physical_plan::memory::MemoryStream;
 pub use datafusion::physical_plan::SendableRecordBatchStream as DfSendableRecordBatchStream;
@@ -31,6 +31,7 @@ use error::Result;
 use futures::task::{Context, Poll};
 use futures::{Stream, TryStreamExt};
 pub use recordbatch::RecordBatch;
+use serde::{Deserialize, Serialize};
 use snafu::{ensure, ResultExt};
 
 pub trait RecordBatchStream: Stream<Item = Result<RecordBatch>> {
@@ -39,10 +40,27 @@ pub trait RecordBatchStream: Stream<Item = Result<RecordBatch>> {
     fn output_ordering(&self) -> Option<&[OrderOption]> {
         None
     }
+
+    /// Returns the metrics of this stream, which are complete once the stream is exhausted.
+    fn metrics(&self) -> Option<RecordBatchMetrics> {
+        None
+    }
 }
 
 pub type SendableRecordBatchStream = Pin<Box<dyn RecordBatchStream + Send>>;
 
+/// Execution metrics of a [RecordBatchStream].
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordBatchMetrics {
+    /// Named counters, like the number of rows read from memtables.
+    pub counters: Vec<(String, usize)>,
+    /// The execution plan producing the stream, annotated with its metrics.
+    pub plan: Option<String>,
+}
+
+/// [RecordBatchMetrics] shared by a stream and the producer of its record batches.
+pub type SharedRecordBatchMetrics = Arc<Mutex<RecordBatchMetrics>>;
+
 #[derive(Debug, Clone)]
 pub struct OrderOption {
     pub name: String,
@@ -206,6 +224,7 @@ pub struct RecordBatchStreamAdaptor {
     pub schema: SchemaRef,
     pub stream: Pin<Box<dyn Stream<Item = Result<RecordBatch>> + Send>>,
     pub output_ordering: Option<Vec<OrderOption>>,
+    pub metrics: Option<SharedRecordBatchMetrics>,
 }
 
 impl RecordBatchStream for RecordBatchStreamAdaptor {
@@ -216,6 +235,12 @@ impl RecordBatchStream for RecordBatchStreamAdaptor {
     fn output_ordering(&self) -> Option<&[OrderOption]> {
         self.output_ordering.as_deref()
     }
+
+    fn metrics(&self) -> Option<RecordBatchMetrics> {
+        self.metrics
+            .as_ref()
+            .map(|metrics| metrics.lock().unwrap().clone())
+    }
 }
 
 impl Stream for RecordBatchStreamAdaptor {
@@ -208,6 +208,18 @@ impl Timestamp {
         let (sec, nsec) = self.split();
         NaiveDateTime::from_timestamp_opt(sec, nsec)
     }
+
+    /// Converts timestamp to the wall clock datetime of given timezone.
+    /// When timezone is None, using local time by default.
+    pub fn to_timezone_aware_chrono_datetime(&self, tz: Option<TimeZone>) -> Option<NaiveDateTime> {
+        let v = self.to_chrono_datetime()?;
+        let datetime = match tz {
+            Some(TimeZone::Offset(offset)) => offset.from_utc_datetime(&v).naive_local(),
+            Some(TimeZone::Named(tz)) => tz.from_utc_datetime(&v).naive_local(),
+            None => Local {}.from_utc_datetime(&v).naive_local(),
+        };
+        Some(datetime)
+    }
 }
 
 impl FromStr for Timestamp {
@@ -1010,4 +1022,27 @@ mod tests {
                 .to_timezone_aware_string(TimeZone::from_tz_string("Europe/Moscow").unwrap())
         );
     }
+
+    #[test]
+    fn test_to_timezone_aware_chrono_datetime() {
+        let ts = Timestamp::new(1, TimeUnit::Millisecond);
+        let expected = chrono::NaiveDate::from_ymd_opt(1970, 1, 1)
+            .unwrap()
+            .and_hms_milli_opt(8, 0, 0, 1)
+            .unwrap();
+        assert_eq!(
+            Some(expected),
+            ts.to_timezone_aware_chrono_datetime(TimeZone::from_tz_string("+08:00").unwrap())
+        );
+        assert_eq!(
+            Some(expected),
+            ts.to_timezone_aware_chrono_datetime(
+                TimeZone::from_tz_string("Asia/Shanghai").unwrap()
+            )
+        );
+        assert_eq!(
+            ts.to_chrono_datetime(),
+            ts.to_timezone_aware_chrono_datetime(TimeZone::from_tz_string("UTC").unwrap())
+        );
+    }
 }
@@ -24,6 +24,7 @@ pub use common_procedure::options::ProcedureConfig;
 use common_telemetry::info;
 use common_telemetry::logging::LoggingOptions;
 use meta_client::MetaClientOptions;
+pub use query::query_engine::options::QueryConfig;
 use secrecy::SecretString;
 use serde::{Deserialize, Serialize};
 use servers::heartbeat_options::HeartbeatOptions;
@@ -361,6 +362,7 @@ pub struct DatanodeOptions {
     pub wal: WalConfig,
     pub storage: StorageConfig,
     pub procedure: ProcedureConfig,
+    pub query: QueryConfig,
     pub logging: LoggingOptions,
 }
 
@@ -378,6 +380,7 @@ impl Default for DatanodeOptions {
             wal: WalConfig::default(),
             storage: StorageConfig::default(),
             procedure: ProcedureConfig::default(),
+            query: QueryConfig::default(),
             logging: LoggingOptions::default(),
             heartbeat: HeartbeatOptions::default(),
         }
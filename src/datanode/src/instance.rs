@@ -273,6 +273,7 @@ impl Instance {
             None,
             None,
             plugins,
+            &opts.query,
         );
         let query_engine = factory.query_engine();
 
@@ -393,6 +394,10 @@ impl Instance {
     pub fn query_engine(&self) -> QueryEngineRef {
         self.query_engine.clone()
     }
+
+    pub fn procedure_manager(&self) -> ProcedureManagerRef {
+        self.procedure_manager.clone()
+    }
 }
 
 fn create_compaction_scheduler<S: LogStore>(opts: &DatanodeOptions) -> CompactionSchedulerRef<S> {
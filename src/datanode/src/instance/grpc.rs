@@ -23,6 +23,7 @@ use async_trait::async_trait;
 use catalog::CatalogManagerRef;
 use common_grpc_expr::insert::to_table_insert_request;
 use common_query::Output;
+use common_recordbatch::RecordBatch;
 use datafusion::catalog::catalog::{
     CatalogList, CatalogProvider, MemoryCatalogList, MemoryCatalogProvider,
 };
@@ -38,7 +39,7 @@ use snafu::prelude::*;
 use sql::statements::statement::Statement;
 use substrait::{DFLogicalSubstraitConvertor, SubstraitPlan};
 use table::engine::TableReference;
-use table::requests::CreateDatabaseRequest;
+use table::requests::{CreateDatabaseRequest, InsertRequest};
 use table::table::adapter::DfTableProviderAdapter;
 
 use crate::error::{
@@ -222,6 +223,44 @@ impl GrpcQueryHandler for Instance {
             Request::Ddl(request) => self.handle_ddl(request, ctx).await,
         }
     }
+
+    async fn put_record_batch(
+        &self,
+        table_name: &str,
+        record_batch: RecordBatch,
+        ctx: QueryContextRef,
+    ) -> Result<usize> {
+        let catalog = ctx.current_catalog();
+        let schema = ctx.current_schema();
+        let full_table_name = common_catalog::format_full_table_name(&catalog, &schema, table_name);
+        let table = self
+            .catalog_manager
+            .table(&catalog, &schema, table_name)
+            .await
+            .context(CatalogSnafu)?
+            .with_context(|| TableNotFoundSnafu {
+                table_name: &full_table_name,
+            })?;
+
+        let columns_values = record_batch
+            .schema
+            .column_schemas()
+            .iter()
+            .map(|column_schema| column_schema.name.clone())
+            .zip(record_batch.columns().iter().cloned())
+            .collect();
+        let request = InsertRequest {
+            catalog_name: catalog,
+            schema_name: schema,
+            table_name: table_name.to_string(),
+            columns_values,
+            region_number: 0,
+        };
+
+        table.insert(request).await.context(InsertSnafu {
+            table_name: full_table_name,
+        })
+    }
 }
 
 struct DummySchemaProvider {
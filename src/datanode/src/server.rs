@@ -52,7 +52,8 @@ impl Services {
 
         Ok(Self {
             grpc_server: GrpcServer::new(
-                ServerGrpcQueryHandlerAdaptor::arc(instance),
+                ServerGrpcQueryHandlerAdaptor::arc(instance.clone()),
+                None,
                 None,
                 None,
                 grpc_runtime,
@@ -60,6 +61,7 @@ impl Services {
             http_server: HttpServerBuilder::new(opts.http_opts.clone())
                 .with_metrics_handler(MetricsHandler)
                 .with_greptime_config_options(opts.to_toml_string())
+                .with_procedure_manager(instance.procedure_manager())
                 .build(),
         })
     }
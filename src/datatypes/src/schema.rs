@@ -14,6 +14,7 @@
 
 mod column_schema;
 mod constraint;
+mod fulltext;
 mod raw;
 
 use std::collections::HashMap;
@@ -25,8 +26,11 @@ use snafu::{ensure, ResultExt};
 
 use crate::data_type::DataType;
 use crate::error::{self, Error, ProjectArrowSchemaSnafu, Result};
-pub use crate::schema::column_schema::{ColumnSchema, Metadata, COMMENT_KEY, TIME_INDEX_KEY};
+pub use crate::schema::column_schema::{
+    ColumnSchema, Metadata, COMMENT_KEY, FULLTEXT_KEY, TIME_INDEX_KEY,
+};
 pub use crate::schema::constraint::ColumnDefaultConstraint;
+pub use crate::schema::fulltext::FulltextAnalyzer;
 pub use crate::schema::raw::RawSchema;
 
 /// Key used to store version number of the schema in metadata.
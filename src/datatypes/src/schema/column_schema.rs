@@ -21,6 +21,7 @@ use snafu::{ensure, ResultExt};
 use crate::data_type::{ConcreteDataType, DataType};
 use crate::error::{self, Error, Result};
 use crate::schema::constraint::ColumnDefaultConstraint;
+use crate::schema::fulltext::FulltextAnalyzer;
 use crate::value::Value;
 use crate::vectors::VectorRef;
 
@@ -29,6 +30,8 @@ pub type Metadata = HashMap<String, String>;
 /// Key used to store whether the column is time index in arrow field's metadata.
 pub const TIME_INDEX_KEY: &str = "greptime:time_index";
 pub const COMMENT_KEY: &str = "greptime:storage:comment";
+/// Key used to store the analyzer of a full-text indexed column.
+pub const FULLTEXT_KEY: &str = "greptime:fulltext";
 /// Key used to store default constraint in arrow field's metadata.
 const DEFAULT_CONSTRAINT_KEY: &str = "greptime:default_constraint";
 
@@ -108,6 +111,25 @@ impl ColumnSchema {
         Ok(self)
     }
 
+    /// Returns the analyzer if the column is full-text indexed.
+    pub fn fulltext_analyzer(&self) -> Option<FulltextAnalyzer> {
+        self.metadata
+            .get(FULLTEXT_KEY)
+            .and_then(|analyzer| analyzer.parse().ok())
+    }
+
+    /// Enables or disables the full-text index of the column.
+    pub fn with_fulltext(mut self, analyzer: Option<FulltextAnalyzer>) -> Self {
+        if let Some(analyzer) = analyzer {
+            let _ = self
+                .metadata
+                .insert(FULLTEXT_KEY.to_string(), analyzer.to_string());
+        } else {
+            let _ = self.metadata.remove(FULLTEXT_KEY);
+        }
+        self
+    }
+
     /// Creates a new [`ColumnSchema`] with given metadata.
     pub fn with_metadata(mut self, metadata: Metadata) -> Self {
         self.metadata = metadata;
@@ -286,6 +308,27 @@ mod tests {
         assert_eq!(column_schema, new_column_schema);
     }
 
+    #[test]
+    fn test_column_schema_with_fulltext() {
+        let column_schema = ColumnSchema::new("test", ConcreteDataType::string_datatype(), true);
+        assert!(column_schema.fulltext_analyzer().is_none());
+
+        let column_schema = column_schema.with_fulltext(Some(FulltextAnalyzer::Whitespace));
+        assert_eq!(
+            Some(FulltextAnalyzer::Whitespace),
+            column_schema.fulltext_analyzer()
+        );
+        let field = Field::try_from(&column_schema).unwrap();
+        let new_column_schema = ColumnSchema::try_from(&field).unwrap();
+        assert_eq!(
+            Some(FulltextAnalyzer::Whitespace),
+            new_column_schema.fulltext_analyzer()
+        );
+
+        let column_schema = column_schema.with_fulltext(None);
+        assert!(column_schema.fulltext_analyzer().is_none());
+    }
+
     #[test]
     fn test_column_schema_with_duplicate_metadata() {
         let metadata = Metadata::from([(DEFAULT_CONSTRAINT_KEY.to_string(), "v1".to_string())]);
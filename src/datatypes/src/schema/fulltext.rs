@@ -0,0 +1,140 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Common English words that are not indexed by [FulltextAnalyzer::English].
+const ENGLISH_STOP_WORDS: [&str; 33] = [
+    "a", "an", "and", "are", "as", "at", "be", "but", "by", "for", "if", "in", "into", "is", "it",
+    "no", "not", "of", "on", "or", "such", "that", "the", "their", "then", "there", "these",
+    "they", "this", "to", "was", "will", "with",
+];
+
+/// Analyzer that splits text of a full-text indexed column into terms.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FulltextAnalyzer {
+    /// Splits text on non-alphanumeric characters, lowercases the terms and
+    /// skips English stop words.
+    #[default]
+    English,
+    /// Splits text on whitespaces, terms are case sensitive.
+    Whitespace,
+}
+
+impl FulltextAnalyzer {
+    /// Splits the text into terms, a term may appear more than once.
+    pub fn tokenize(&self, text: &str) -> Vec<String> {
+        match self {
+            FulltextAnalyzer::English => text
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|term| !term.is_empty())
+                .map(|term| term.to_lowercase())
+                .filter(|term| ENGLISH_STOP_WORDS.binary_search(&term.as_str()).is_err())
+                .collect(),
+            FulltextAnalyzer::Whitespace => text
+                .split_whitespace()
+                .map(|term| term.to_string())
+                .collect(),
+        }
+    }
+
+    /// Returns whether the text contains all terms of the query. Returns false if
+    /// the query has no terms.
+    pub fn matches(&self, text: &str, query: &[String]) -> bool {
+        if query.is_empty() {
+            return false;
+        }
+        let terms = self.tokenize(text);
+        query.iter().all(|term| terms.contains(term))
+    }
+}
+
+impl fmt::Display for FulltextAnalyzer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FulltextAnalyzer::English => write!(f, "English"),
+            FulltextAnalyzer::Whitespace => write!(f, "Whitespace"),
+        }
+    }
+}
+
+impl FromStr for FulltextAnalyzer {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("english") {
+            Ok(FulltextAnalyzer::English)
+        } else if s.eq_ignore_ascii_case("whitespace") {
+            Ok(FulltextAnalyzer::Whitespace)
+        } else {
+            Err(format!("unknown fulltext analyzer: {s}"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stop_words_sorted() {
+        let mut words = ENGLISH_STOP_WORDS.to_vec();
+        words.sort();
+        assert_eq!(ENGLISH_STOP_WORDS.to_vec(), words);
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            vec!["error", "connecting", "db", "1", "timed", "out"],
+            FulltextAnalyzer::English.tokenize("Error connecting to db-1: timed out!")
+        );
+        assert_eq!(
+            vec!["Error", "connecting", "to", "db-1:", "timed", "out!"],
+            FulltextAnalyzer::Whitespace.tokenize("Error connecting to db-1: timed out!")
+        );
+    }
+
+    #[test]
+    fn test_matches() {
+        let analyzer = FulltextAnalyzer::English;
+        let query = analyzer.tokenize("TIMED out");
+        assert!(analyzer.matches("Error connecting to db-1: timed out!", &query));
+        assert!(!analyzer.matches("Error connecting to db-1: refused", &query));
+        assert!(!analyzer.matches("anything", &analyzer.tokenize("the")));
+    }
+
+    #[test]
+    fn test_parse_analyzer() {
+        assert_eq!(
+            FulltextAnalyzer::English,
+            "english".parse::<FulltextAnalyzer>().unwrap()
+        );
+        assert_eq!(
+            FulltextAnalyzer::Whitespace,
+            "Whitespace".parse::<FulltextAnalyzer>().unwrap()
+        );
+        assert!("chinese".parse::<FulltextAnalyzer>().is_err());
+        assert_eq!(
+            FulltextAnalyzer::Whitespace,
+            FulltextAnalyzer::Whitespace
+                .to_string()
+                .parse::<FulltextAnalyzer>()
+                .unwrap()
+        );
+    }
+}
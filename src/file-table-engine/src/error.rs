@@ -178,6 +178,19 @@ pub enum Error {
         location: Location,
     },
 
+    #[snafu(display("Failed to prune partitions: {}", source))]
+    PrunePartitions {
+        location: Location,
+        source: common_datasource::error::Error,
+    },
+
+    #[snafu(display("Failed to parse partition values of file {}: {}", path, source))]
+    ParsePartitionValues {
+        path: String,
+        source: DataFusionError,
+        location: Location,
+    },
+
     #[snafu(display("Unsupported format: {:?}", format))]
     UnsupportedFormat { format: Format, location: Location },
 }
@@ -202,6 +215,8 @@ impl ErrorExt for Error {
             BuildBackend { source, .. } => source.status_code(),
             BuildStreamAdapter { source, .. } => source.status_code(),
             ParseFileFormat { source, .. } => source.status_code(),
+            PrunePartitions { source, .. } => source.status_code(),
+            ParsePartitionValues { .. } => StatusCode::InvalidArguments,
 
             WriteTableManifest { .. }
             | DeleteTableManifest { .. }
@@ -14,10 +14,13 @@
 
 use std::sync::Arc;
 
+use common_datasource::file_format::arrow_ipc::ArrowIpcOpener;
+use common_datasource::file_format::avro::AvroOpener;
 use common_datasource::file_format::csv::{CsvConfigBuilder, CsvFormat, CsvOpener};
 use common_datasource::file_format::json::{JsonFormat, JsonOpener};
 use common_datasource::file_format::parquet::{DefaultParquetFileReaderFactory, ParquetFormat};
 use common_datasource::file_format::Format;
+use common_datasource::partition::{partition_values, prune_files};
 use common_query::prelude::Expr;
 use common_query::DfPhysicalPlan;
 use common_recordbatch::adapter::RecordBatchStreamAdapter;
@@ -25,13 +28,14 @@ use common_recordbatch::SendableRecordBatchStream;
 use datafusion::common::ToDFSchema;
 use datafusion::datasource::listing::PartitionedFile;
 use datafusion::datasource::object_store::ObjectStoreUrl;
+use datafusion::logical_expr::Expr as DfExpr;
 use datafusion::optimizer::utils::conjunction;
 use datafusion::physical_expr::create_physical_expr;
 use datafusion::physical_expr::execution_props::ExecutionProps;
 use datafusion::physical_plan::file_format::{FileOpener, FileScanConfig, FileStream, ParquetExec};
 use datafusion::physical_plan::metrics::ExecutionPlanMetricsSet;
 use datafusion::prelude::SessionContext;
-use datatypes::arrow::datatypes::Schema as ArrowSchema;
+use datatypes::arrow::datatypes::{Field, Schema as ArrowSchema};
 use datatypes::schema::SchemaRef;
 use object_store::ObjectStore;
 use snafu::ResultExt;
@@ -43,15 +47,160 @@ const DEFAULT_BATCH_SIZE: usize = 8192;
 #[derive(Debug, Clone, Copy, Default)]
 pub struct CreateScanPlanContext {}
 
-fn build_csv_opener(
+/// How columns of the table are laid out in [FileScanConfig]: columns in files,
+/// followed by partition columns.
+struct ScanLayout {
+    /// Schema of columns in files.
     file_schema: Arc<ArrowSchema>,
+    partition_fields: Vec<Field>,
+    /// Projection over columns in files and partition columns.
+    projection: Option<Vec<usize>>,
+    /// Projection over columns in files, for openers.
+    file_projection: Option<Vec<usize>>,
+    files: Vec<PartitionedFile>,
+    /// Filters only referencing columns in files.
+    file_filters: Vec<DfExpr>,
+}
+
+impl ScanLayout {
+    fn try_new(config: &ScanPlanConfig) -> Result<Self> {
+        let table_schema = config.file_schema.arrow_schema();
+        let filters = config
+            .filters
+            .iter()
+            .map(|f| f.df_expr().clone())
+            .collect::<Vec<_>>();
+
+        if config.partition_columns.is_empty() {
+            let files = config
+                .files
+                .iter()
+                .map(|filename| PartitionedFile::new(filename.to_string(), 0))
+                .collect();
+            return Ok(Self {
+                file_schema: table_schema.clone(),
+                partition_fields: vec![],
+                projection: config.projection.cloned(),
+                file_projection: config.projection.cloned(),
+                files,
+                file_filters: filters,
+            });
+        }
+
+        let is_partition = |name: &String| config.partition_columns.contains(name);
+        let (file_indices, partition_indices): (Vec<_>, Vec<_>) = (0..table_schema.fields().len())
+            .partition(|i| !is_partition(table_schema.field(*i).name()));
+        let file_schema = Arc::new(
+            table_schema
+                .project(&file_indices)
+                .context(error::ProjectSchemaSnafu)?,
+        );
+        let partition_fields = partition_indices
+            .iter()
+            .map(|i| table_schema.field(*i).clone())
+            .collect::<Vec<_>>();
+
+        // Maps indices of the table schema to the layout.
+        let layout_index = |i: usize| {
+            file_indices
+                .iter()
+                .position(|idx| *idx == i)
+                .or_else(|| {
+                    partition_indices
+                        .iter()
+                        .position(|idx| *idx == i)
+                        .map(|pos| file_indices.len() + pos)
+                })
+                // Safety: each index of table schema is either in files or a partition column.
+                .unwrap()
+        };
+        let projection = config
+            .projection
+            .cloned()
+            .unwrap_or_else(|| (0..table_schema.fields().len()).collect())
+            .into_iter()
+            .map(layout_index)
+            .collect::<Vec<_>>();
+        let file_projection = projection
+            .iter()
+            .copied()
+            .filter(|i| *i < file_indices.len())
+            .collect();
+
+        let files = prune_files(config.base_dir, config.files, &partition_fields, &filters)
+            .context(error::PrunePartitionsSnafu)?
+            .into_iter()
+            .map(|path| {
+                let partition_values = partition_values(config.base_dir, &path, &partition_fields)
+                    .context(error::ParsePartitionValuesSnafu { path: &path })?;
+                let mut file = PartitionedFile::new(path, 0);
+                file.partition_values = partition_values;
+                Ok(file)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Filters on partition columns have been applied by pruning.
+        let file_filters = filters
+            .into_iter()
+            .filter(|filter| {
+                filter.to_columns().map_or(false, |columns| {
+                    columns
+                        .iter()
+                        .all(|c| file_schema.field_with_name(&c.name).is_ok())
+                })
+            })
+            .collect();
+
+        Ok(Self {
+            file_schema,
+            partition_fields,
+            projection: Some(projection),
+            file_projection: Some(file_projection),
+            files,
+            file_filters,
+        })
+    }
+
+    fn file_scan_config(&self, limit: Option<usize>) -> FileScanConfig {
+        FileScanConfig {
+            object_store_url: ObjectStoreUrl::parse("empty://").unwrap(), // won't be used
+            file_schema: self.file_schema.clone(),
+            file_groups: vec![self.files.clone()],
+            statistics: Default::default(),
+            projection: self.projection.clone(),
+            limit,
+            table_partition_cols: self
+                .partition_fields
+                .iter()
+                .map(|f| (f.name().clone(), f.data_type().clone()))
+                .collect(),
+            output_ordering: None,
+            infinite_source: false,
+        }
+    }
+
+    fn projected_file_schema(&self) -> Result<Arc<ArrowSchema>> {
+        if let Some(projection) = &self.file_projection {
+            Ok(Arc::new(
+                self.file_schema
+                    .project(projection)
+                    .context(error::ProjectSchemaSnafu)?,
+            ))
+        } else {
+            Ok(self.file_schema.clone())
+        }
+    }
+}
+
+fn build_csv_opener(
+    layout: &ScanLayout,
     config: &ScanPlanConfig,
     format: &CsvFormat,
 ) -> Result<CsvOpener> {
     let csv_config = CsvConfigBuilder::default()
         .batch_size(DEFAULT_BATCH_SIZE)
-        .file_schema(file_schema)
-        .file_projection(config.projection.cloned())
+        .file_schema(layout.file_schema.clone())
+        .file_projection(layout.file_projection.clone())
         .delimiter(format.delimiter)
         .has_header(format.has_header)
         .build()
@@ -64,22 +213,13 @@ fn build_csv_opener(
 }
 
 fn build_json_opener(
-    file_schema: Arc<ArrowSchema>,
+    layout: &ScanLayout,
     config: &ScanPlanConfig,
     format: &JsonFormat,
 ) -> Result<JsonOpener> {
-    let projected_schema = if let Some(projection) = config.projection {
-        Arc::new(
-            file_schema
-                .project(projection)
-                .context(error::ProjectSchemaSnafu)?,
-        )
-    } else {
-        file_schema
-    };
     Ok(JsonOpener::new(
         DEFAULT_BATCH_SIZE,
-        projected_schema,
+        layout.projected_file_schema()?,
         config.store.clone(),
         format.compression_type,
     ))
@@ -87,26 +227,11 @@ fn build_json_opener(
 
 fn build_record_batch_stream<T: FileOpener + Send + 'static>(
     opener: T,
-    file_schema: Arc<ArrowSchema>,
-    files: &[String],
-    projection: Option<&Vec<usize>>,
+    layout: &ScanLayout,
     limit: Option<usize>,
 ) -> Result<SendableRecordBatchStream> {
     let stream = FileStream::new(
-        &FileScanConfig {
-            object_store_url: ObjectStoreUrl::parse("empty://").unwrap(), // won't be used
-            file_schema,
-            file_groups: vec![files
-                .iter()
-                .map(|filename| PartitionedFile::new(filename.to_string(), 0))
-                .collect::<Vec<_>>()],
-            statistics: Default::default(),
-            projection: projection.cloned(),
-            limit,
-            table_partition_cols: vec![],
-            output_ordering: None,
-            infinite_source: false,
-        },
+        &layout.file_scan_config(limit),
         0, // partition: hard-code
         opener,
         &ExecutionPlanMetricsSet::new(),
@@ -119,73 +244,59 @@ fn build_record_batch_stream<T: FileOpener + Send + 'static>(
 
 fn new_csv_stream(
     _ctx: &CreateScanPlanContext,
+    layout: &ScanLayout,
     config: &ScanPlanConfig,
     format: &CsvFormat,
 ) -> Result<SendableRecordBatchStream> {
-    let file_schema = config.file_schema.arrow_schema().clone();
-    let opener = build_csv_opener(file_schema.clone(), config, format)?;
-    build_record_batch_stream(
-        opener,
-        file_schema,
-        config.files,
-        config.projection,
-        config.limit,
-    )
+    let opener = build_csv_opener(layout, config, format)?;
+    build_record_batch_stream(opener, layout, config.limit)
 }
 
 fn new_json_stream(
     _ctx: &CreateScanPlanContext,
+    layout: &ScanLayout,
     config: &ScanPlanConfig,
     format: &JsonFormat,
 ) -> Result<SendableRecordBatchStream> {
-    let file_schema = config.file_schema.arrow_schema().clone();
-    let opener = build_json_opener(file_schema.clone(), config, format)?;
-    build_record_batch_stream(
-        opener,
-        file_schema,
-        config.files,
-        config.projection,
-        config.limit,
-    )
+    let opener = build_json_opener(layout, config, format)?;
+    build_record_batch_stream(opener, layout, config.limit)
+}
+
+fn new_arrow_ipc_stream(
+    _ctx: &CreateScanPlanContext,
+    layout: &ScanLayout,
+    config: &ScanPlanConfig,
+) -> Result<SendableRecordBatchStream> {
+    let opener = ArrowIpcOpener::new(layout.projected_file_schema()?, config.store.clone());
+    build_record_batch_stream(opener, layout, config.limit)
+}
+
+fn new_avro_stream(
+    _ctx: &CreateScanPlanContext,
+    layout: &ScanLayout,
+    config: &ScanPlanConfig,
+) -> Result<SendableRecordBatchStream> {
+    let opener = AvroOpener::new(
+        DEFAULT_BATCH_SIZE,
+        layout.projected_file_schema()?,
+        config.store.clone(),
+    );
+    build_record_batch_stream(opener, layout, config.limit)
 }
 
 fn new_parquet_stream_with_exec_plan(
     _ctx: &CreateScanPlanContext,
+    layout: &ScanLayout,
     config: &ScanPlanConfig,
     _format: &ParquetFormat,
 ) -> Result<SendableRecordBatchStream> {
-    let file_schema = config.file_schema.arrow_schema().clone();
-    let ScanPlanConfig {
-        files,
-        projection,
-        limit,
-        filters,
-        store,
-        ..
-    } = config;
+    let file_schema = layout.file_schema.clone();
 
     // construct config for ParquetExec
-    let scan_config = FileScanConfig {
-        object_store_url: ObjectStoreUrl::parse("empty://").unwrap(), // won't be used
-        file_schema: file_schema.clone(),
-        file_groups: vec![files
-            .iter()
-            .map(|filename| PartitionedFile::new(filename.to_string(), 0))
-            .collect::<Vec<_>>()],
-        statistics: Default::default(),
-        projection: projection.cloned(),
-        limit: *limit,
-        table_partition_cols: vec![],
-        output_ordering: None,
-        infinite_source: false,
-    };
+    let scan_config = layout.file_scan_config(config.limit);
 
-    // build predicate filter
-    let filters = filters
-        .iter()
-        .map(|f| f.df_expr().clone())
-        .collect::<Vec<_>>();
-    let filters = if let Some(expr) = conjunction(filters) {
+    // build predicate filter, which prunes row groups by their statistics
+    let filters = if let Some(expr) = conjunction(layout.file_filters.clone()) {
         let df_schema = file_schema
             .clone()
             .to_dfschema_ref()
@@ -202,7 +313,7 @@ fn new_parquet_stream_with_exec_plan(
     let task_ctx = SessionContext::default().task_ctx();
     let parquet_exec = ParquetExec::new(scan_config, filters, None)
         .with_parquet_file_reader_factory(Arc::new(DefaultParquetFileReaderFactory::new(
-            store.clone(),
+            config.store.clone(),
         )));
     let stream = parquet_exec
         .execute(0, task_ctx)
@@ -217,6 +328,10 @@ fn new_parquet_stream_with_exec_plan(
 pub struct ScanPlanConfig<'a> {
     pub file_schema: SchemaRef,
     pub files: &'a Vec<String>,
+    /// Directory of the table location, partition directories of files are below it.
+    pub base_dir: &'a str,
+    /// Columns of `file_schema` whose values come from partition directories of files.
+    pub partition_columns: &'a [String],
     pub projection: Option<&'a Vec<usize>>,
     pub filters: &'a [Expr],
     pub limit: Option<usize>,
@@ -228,10 +343,77 @@ pub fn create_stream(
     ctx: &CreateScanPlanContext,
     config: &ScanPlanConfig,
 ) -> Result<SendableRecordBatchStream> {
+    let layout = ScanLayout::try_new(config)?;
     match format {
-        Format::Csv(format) => new_csv_stream(ctx, config, format),
-        Format::Json(format) => new_json_stream(ctx, config, format),
-        Format::Parquet(format) => new_parquet_stream_with_exec_plan(ctx, config, format),
+        Format::Csv(format) => new_csv_stream(ctx, &layout, config, format),
+        Format::Json(format) => new_json_stream(ctx, &layout, config, format),
+        Format::Parquet(format) => new_parquet_stream_with_exec_plan(ctx, &layout, config, format),
+        Format::ArrowIpc(_) => new_arrow_ipc_stream(ctx, &layout, config),
+        Format::Avro(_) => new_avro_stream(ctx, &layout, config),
         _ => error::UnsupportedFormatSnafu { format: *format }.fail(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use common_datasource::file_format::csv::CsvFormat;
+    use common_recordbatch::util::collect_batches;
+    use datafusion::prelude::{col, lit};
+    use datatypes::prelude::ConcreteDataType;
+    use datatypes::schema::{ColumnSchema, Schema};
+
+    use super::*;
+    use crate::test_util::new_test_object_store;
+
+    #[tokio::test]
+    async fn test_scan_partitioned_files() {
+        let (dir, store) = new_test_object_store("test_scan_partitioned_files");
+        let mut files = vec![];
+        for (dt, rows) in [("2023-07-01", "host1,1.0\n"), ("2023-07-02", "host2,2.0\n")] {
+            let path = format!("t/dt={dt}/part-0.csv");
+            std::fs::create_dir_all(dir.path().join(format!("t/dt={dt}"))).unwrap();
+            std::fs::write(dir.path().join(&path), format!("host,cpu\n{rows}")).unwrap();
+            files.push(path);
+        }
+
+        let schema = Arc::new(Schema::new(vec![
+            ColumnSchema::new("host", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("dt", ConcreteDataType::string_datatype(), true),
+            ColumnSchema::new("cpu", ConcreteDataType::float64_datatype(), true),
+        ]));
+        let partition_columns = vec!["dt".to_string()];
+        let filters = vec![Expr::from(col("dt").eq(lit("2023-07-02")))];
+        let projection = vec![1, 0];
+
+        let config = ScanPlanConfig {
+            file_schema: schema,
+            files: &files,
+            base_dir: "t/",
+            partition_columns: &partition_columns,
+            projection: Some(&projection),
+            filters: &filters,
+            limit: None,
+            store,
+        };
+        let layout = ScanLayout::try_new(&config).unwrap();
+        assert_eq!(1, layout.files.len());
+        assert_eq!(Some(vec![2, 0]), layout.projection);
+        assert_eq!(Some(vec![0]), layout.file_projection);
+        assert!(layout.file_filters.is_empty());
+
+        let stream = create_stream(
+            &Format::Csv(CsvFormat::default()),
+            &CreateScanPlanContext::default(),
+            &config,
+        )
+        .unwrap();
+        let batches = collect_batches(stream).await.unwrap();
+        let expected = "\
++------------+-------+
+| dt         | host  |
++------------+-------+
+| 2023-07-02 | host2 |
++------------+-------+";
+        assert_eq!(expected, batches.pretty_print().unwrap());
+    }
+}
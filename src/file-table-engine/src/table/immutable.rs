@@ -18,6 +18,7 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use common_datasource::file_format::Format;
 use common_datasource::object_store::build_backend;
+use common_datasource::partition::partition_base_dir;
 use common_error::ext::BoxedError;
 use common_recordbatch::SendableRecordBatchStream;
 use datatypes::schema::SchemaRef;
@@ -41,6 +42,8 @@ use crate::table::format::{CreateScanPlanContext, ScanPlanConfig};
 #[serde(default)]
 pub struct ImmutableFileTableOptions {
     pub files: Vec<String>,
+    /// Columns whose values come from Hive-style partition directories of files.
+    pub partition_columns: Vec<String>,
 }
 
 pub struct ImmutableFileTable {
@@ -49,6 +52,9 @@ pub struct ImmutableFileTable {
     table_info: Arc<TableInfo>,
     object_store: ObjectStore,
     files: Vec<String>,
+    /// Directory of the table location, see [ScanPlanConfig].
+    base_dir: String,
+    partition_columns: Vec<String>,
     format: Format,
 }
 
@@ -81,6 +87,8 @@ impl Table for ImmutableFileTable {
             &ScanPlanConfig {
                 file_schema: self.schema(),
                 files: &self.files,
+                base_dir: &self.base_dir,
+                partition_columns: &self.partition_columns,
                 projection: request.projection.as_ref(),
                 filters: &request.filters,
                 limit: request.limit,
@@ -128,12 +136,15 @@ impl ImmutableFileTable {
         let format = Format::try_from(options).context(error::ParseFileFormatSnafu)?;
 
         let object_store = build_backend(url, options).context(error::BuildBackendSnafu)?;
+        let base_dir = partition_base_dir(url).context(error::BuildBackendSnafu)?;
 
         Ok(Self {
             metadata,
             table_info,
             object_store,
             files: meta.files,
+            base_dir,
+            partition_columns: meta.partition_columns,
             format,
         })
     }
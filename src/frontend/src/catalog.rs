@@ -21,14 +21,16 @@ use catalog::error::{
     self as catalog_err, InternalSnafu, InvalidCatalogValueSnafu, InvalidSystemTableDefSnafu,
     Result as CatalogResult, TableMetadataManagerSnafu, UnimplementedSnafu,
 };
-use catalog::information_schema::InformationSchemaProvider;
+use catalog::information_schema::{InformationSchemaProvider, PgCatalogProvider};
 use catalog::remote::KvCacheInvalidatorRef;
 use catalog::{
     CatalogManager, DeregisterSchemaRequest, DeregisterTableRequest, RegisterSchemaRequest,
     RegisterSystemTableRequest, RegisterTableRequest, RenameTableRequest,
 };
 use client::client_manager::DatanodeClients;
-use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, INFORMATION_SCHEMA_NAME};
+use common_catalog::consts::{
+    DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, INFORMATION_SCHEMA_NAME, PG_CATALOG_NAME,
+};
 use common_error::ext::BoxedError;
 use common_meta::helper::{
     build_catalog_prefix, build_schema_prefix, CatalogKey, SchemaKey, TableGlobalKey,
@@ -406,7 +408,7 @@ impl CatalogManager for FrontendCatalogManager {
             return Ok(Some(Arc::new(NumbersTable::default())));
         }
 
-        if schema == INFORMATION_SCHEMA_NAME {
+        if schema == INFORMATION_SCHEMA_NAME || schema == PG_CATALOG_NAME {
             // hack: use existing cyclin reference to get Arc<Self>.
             // This can be remove by refactoring the struct into something like Arc<Inner>
             let manager = if let Some(instance) = self.dist_instance.as_ref() {
@@ -415,6 +417,11 @@ impl CatalogManager for FrontendCatalogManager {
                 return Ok(None);
             };
 
+            if schema == PG_CATALOG_NAME {
+                let provider =
+                    PgCatalogProvider::new(catalog.to_string(), Arc::downgrade(&manager));
+                return provider.table(table_name);
+            }
             let provider =
                 InformationSchemaProvider::new(catalog.to_string(), Arc::downgrade(&manager));
             return provider.table(table_name);
@@ -14,9 +14,11 @@
 
 use std::any::Any;
 
+use common_catalog::consts::PRIVATE_SCHEMA_NAME;
 use common_datasource::file_format::Format;
 use common_error::ext::{BoxedError, ErrorExt};
 use common_error::status_code::StatusCode;
+use common_procedure::ProcedureId;
 use datafusion::parquet;
 use datatypes::arrow::error::ArrowError;
 use datatypes::value::Value;
@@ -578,6 +580,117 @@ pub enum Error {
         source: common_meta::error::Error,
         location: Location,
     },
+
+    #[snafu(display("Failed to renew script runner lease, source: {}", source))]
+    RenewScriptRunnerLease {
+        source: common_meta::error::Error,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to start script runner lease, source: {}", source))]
+    StartScriptRunnerLease {
+        source: common_runtime::error::Error,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to submit procedure {}, source: {}", procedure_id, source))]
+    SubmitProcedure {
+        procedure_id: ProcedureId,
+        location: Location,
+        source: common_procedure::error::Error,
+    },
+
+    #[snafu(display("Failed to wait procedure {} done, source: {}", procedure_id, source))]
+    WaitProcedure {
+        procedure_id: ProcedureId,
+        location: Location,
+        source: common_procedure::error::Error,
+    },
+
+    #[snafu(display("Failed to cancel job {}, source: {}", job_id, source))]
+    CancelJob {
+        job_id: String,
+        location: Location,
+        source: common_procedure::error::Error,
+    },
+
+    #[snafu(display("Invalid job id: {}", job_id))]
+    InvalidJobId { job_id: String, location: Location },
+
+    #[snafu(display("Job not found: {}", job_id))]
+    JobNotFound { job_id: String, location: Location },
+
+    #[snafu(display("Failed to build the in-memory store of jobs, source: {}", source))]
+    BuildJobStore {
+        location: Location,
+        source: object_store::Error,
+    },
+
+    #[snafu(display("Failed to start the procedure manager of jobs, source: {}", source))]
+    StartJobManager {
+        location: Location,
+        source: common_procedure::error::Error,
+    },
+
+    #[snafu(display("Failed to recover jobs, source: {}", source))]
+    RecoverJobs {
+        location: Location,
+        source: common_procedure::error::Error,
+    },
+
+    #[snafu(display("Failed to create record batch, source: {}", source))]
+    CreateRecordBatch {
+        location: Location,
+        source: common_recordbatch::error::Error,
+    },
+
+    #[snafu(display("Failed to collect record batches, source: {}", source))]
+    CollectRecordbatch {
+        location: Location,
+        source: common_recordbatch::error::Error,
+    },
+
+    #[snafu(display("Failed to filter cached result, source: {}", source))]
+    FilterCachedResult {
+        location: Location,
+        source: datatypes::arrow::error::ArrowError,
+    },
+
+    #[snafu(display("Query {} is killed", id))]
+    QueryCancelled { id: u32, location: Location },
+
+    #[snafu(display("Unknown process id: {}", id))]
+    ProcessNotFound { id: u32, location: Location },
+
+    #[snafu(display("Query {} is run by another user", id))]
+    KillQueryDenied { id: u32, location: Location },
+
+    #[snafu(display(
+        "Tables of schema {} are only accessible from sessions using it",
+        PRIVATE_SCHEMA_NAME
+    ))]
+    PrivateSchemaAccessDenied { location: Location },
+
+    #[snafu(display("Access denied to schema {}.{}, source: {}", catalog, schema, source))]
+    SchemaAccessDenied {
+        catalog: String,
+        schema: String,
+        location: Location,
+        source: servers::auth::Error,
+    },
+
+    #[snafu(display("Function already exists: {}", name))]
+    FunctionExists { name: String, location: Location },
+
+    #[snafu(display("Function not found: {}", name))]
+    FunctionNotFound { name: String, location: Location },
+
+    #[snafu(display("Failed to create function {}, source: {}", name, source))]
+    CreateFunction {
+        name: String,
+        location: Location,
+        source: query::error::Error,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -702,7 +815,32 @@ impl ErrorExt for Error {
 
             Error::WriteParquet { source, .. } => source.status_code(),
             Error::InvalidCopyParameter { .. } => StatusCode::InvalidArguments,
-            Error::TableMetadataManager { source, .. } => source.status_code(),
+            Error::TableMetadataManager { source, .. }
+            | Error::RenewScriptRunnerLease { source, .. } => source.status_code(),
+            Error::StartScriptRunnerLease { source, .. } => source.status_code(),
+
+            Error::SubmitProcedure { source, .. }
+            | Error::WaitProcedure { source, .. }
+            | Error::CancelJob { source, .. } => source.status_code(),
+            Error::InvalidJobId { .. } | Error::JobNotFound { .. } => StatusCode::InvalidArguments,
+            Error::BuildJobStore { .. } => StatusCode::Internal,
+            Error::StartJobManager { source, .. } | Error::RecoverJobs { source, .. } => {
+                source.status_code()
+            }
+            Error::CreateRecordBatch { source, .. } | Error::CollectRecordbatch { source, .. } => {
+                source.status_code()
+            }
+            Error::FilterCachedResult { .. } => StatusCode::Internal,
+            Error::QueryCancelled { .. } => StatusCode::Cancelled,
+            Error::ProcessNotFound { .. } => StatusCode::InvalidArguments,
+            Error::KillQueryDenied { .. } | Error::PrivateSchemaAccessDenied { .. } => {
+                StatusCode::AccessDenied
+            }
+            Error::SchemaAccessDenied { source, .. } => source.status_code(),
+            Error::FunctionExists { .. } | Error::FunctionNotFound { .. } => {
+                StatusCode::InvalidArguments
+            }
+            Error::CreateFunction { source, .. } => source.status_code(),
         }
     }
 
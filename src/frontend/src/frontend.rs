@@ -14,20 +14,24 @@
 
 use common_telemetry::logging::LoggingOptions;
 use meta_client::MetaClientOptions;
+use query::query_engine::options::QueryConfig;
 use serde::{Deserialize, Serialize};
 use servers::heartbeat_options::HeartbeatOptions;
 use servers::http::HttpOptions;
 use servers::Mode;
 
+use crate::result_cache::ResultCacheOptions;
 use crate::service_config::{
-    GrpcOptions, InfluxdbOptions, MysqlOptions, OpentsdbOptions, PostgresOptions, PromStoreOptions,
-    PrometheusOptions,
+    ElasticsearchOptions, GrpcOptions, InfluxdbOptions, LokiOptions, MysqlOptions, OpentsdbOptions,
+    OtlpOptions, PostgresOptions, PromStoreOptions, PrometheusOptions,
 };
+use crate::slow_query::SlowQueryOptions;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(default)]
 pub struct FrontendOptions {
     pub mode: Mode,
+    pub node_id: Option<u64>,
     pub heartbeat: HeartbeatOptions,
     pub http_options: Option<HttpOptions>,
     pub grpc_options: Option<GrpcOptions>,
@@ -36,8 +40,14 @@ pub struct FrontendOptions {
     pub opentsdb_options: Option<OpentsdbOptions>,
     pub influxdb_options: Option<InfluxdbOptions>,
     pub prom_store_options: Option<PromStoreOptions>,
+    pub otlp_options: Option<OtlpOptions>,
+    pub loki_options: Option<LokiOptions>,
+    pub elasticsearch_options: Option<ElasticsearchOptions>,
     pub prometheus_options: Option<PrometheusOptions>,
     pub meta_client_options: Option<MetaClientOptions>,
+    pub query: QueryConfig,
+    pub slow_query: SlowQueryOptions,
+    pub result_cache: ResultCacheOptions,
     pub logging: LoggingOptions,
 }
 
@@ -45,6 +55,7 @@ impl Default for FrontendOptions {
     fn default() -> Self {
         Self {
             mode: Mode::Standalone,
+            node_id: None,
             heartbeat: HeartbeatOptions::default(),
             http_options: Some(HttpOptions::default()),
             grpc_options: Some(GrpcOptions::default()),
@@ -53,8 +64,14 @@ impl Default for FrontendOptions {
             opentsdb_options: Some(OpentsdbOptions::default()),
             influxdb_options: Some(InfluxdbOptions::default()),
             prom_store_options: Some(PromStoreOptions::default()),
+            otlp_options: Some(OtlpOptions::default()),
+            loki_options: Some(LokiOptions::default()),
+            elasticsearch_options: Some(ElasticsearchOptions::default()),
             prometheus_options: Some(PrometheusOptions::default()),
             meta_client_options: None,
+            query: QueryConfig::default(),
+            slow_query: SlowQueryOptions::default(),
+            result_cache: ResultCacheOptions::default(),
             logging: LoggingOptions::default(),
         }
     }
@@ -0,0 +1,422 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Functions defined by `CREATE FUNCTION` belong to the schema they are created
+//! in, and are stored in the `greptime_private.functions` table of its catalog.
+//! Every frontend registers them into its query engine on start, and reloads
+//! them periodically to pick up changes made through other frontends.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use catalog::CatalogManagerRef;
+use common_catalog::consts::PRIVATE_SCHEMA_NAME;
+use common_function::scalars::FUNCTION_REGISTRY;
+use common_recordbatch::util as record_util;
+use common_telemetry::{info, warn};
+use common_time::util::current_time_millis;
+use datafusion_expr::{AggregateFunction, BuiltinScalarFunction};
+use datatypes::prelude::VectorRef;
+use datatypes::value::Value;
+use datatypes::vectors::{StringVector, TimestampMillisecondVector};
+use query::query_engine::SqlStatementExecutorRef;
+use query::sql_udf::normalize_ident;
+use query::QueryEngineRef;
+use session::context::QueryContext;
+use snafu::{ensure, OptionExt, ResultExt};
+use sql::dialect::GreptimeDbDialect;
+use sql::parser::ParserContext;
+use sql::statements::function::CreateFunction;
+use sql::statements::statement::Statement;
+use store_api::storage::ScanRequest;
+use table::requests::{DeleteRequest, InsertRequest};
+use table::TableRef;
+use tokio::sync::Mutex;
+
+use crate::error::{
+    CatalogSnafu, CollectRecordbatchSnafu, CreateFunctionSnafu, ExecuteStatementSnafu,
+    FunctionExistsSnafu, FunctionNotFoundSnafu, InvalidSqlSnafu, ParseSqlSnafu, Result,
+    TableNotFoundSnafu, TableSnafu,
+};
+
+pub const FUNCTIONS_TABLE_NAME: &str = "functions";
+
+/// Interval to reload functions changed through other frontends.
+const RELOAD_INTERVAL: Duration = Duration::from_secs(10);
+
+const SCHEMA_COLUMN: &str = "schema_name";
+const NAME_COLUMN: &str = "function_name";
+const DEFINITION_COLUMN: &str = "definition";
+const TS_COLUMN: &str = "ts";
+const GMT_CREATED_COLUMN: &str = "gmt_created";
+
+/// The time index is always 0, so a function is keyed by its schema and name only.
+const CREATE_FUNCTIONS_TABLE: &str = r#"CREATE TABLE IF NOT EXISTS functions (
+    schema_name STRING,
+    function_name STRING,
+    definition STRING,
+    gmt_created TIMESTAMP,
+    ts TIMESTAMP TIME INDEX,
+    PRIMARY KEY (schema_name, function_name)
+)"#;
+
+/// Identifies a function by its schema and name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct FunctionKey {
+    catalog: String,
+    schema: String,
+    name: String,
+}
+
+impl FunctionKey {
+    fn new(catalog: &str, schema: &str, name: &str) -> Self {
+        Self {
+            catalog: catalog.to_string(),
+            schema: schema.to_string(),
+            name: name.to_string(),
+        }
+    }
+}
+
+/// A function in the functions table.
+#[derive(Debug, Clone)]
+pub(crate) struct StoredFunction {
+    pub(crate) definition: CreateFunction,
+    pub(crate) gmt_created: i64,
+}
+
+pub(crate) type FunctionManagerRef = Arc<FunctionManager>;
+
+/// Stores functions and keeps the query engine in sync with them.
+pub(crate) struct FunctionManager {
+    catalog_manager: CatalogManagerRef,
+    query_engine: QueryEngineRef,
+    sql_stmt_executor: SqlStatementExecutorRef,
+    /// Functions registered into the query engine. Operations hold the lock until
+    /// they are done, so a reload doesn't see half made changes.
+    registered: Mutex<HashMap<FunctionKey, StoredFunction>>,
+}
+
+impl FunctionManager {
+    pub(crate) fn new(
+        catalog_manager: CatalogManagerRef,
+        query_engine: QueryEngineRef,
+        sql_stmt_executor: SqlStatementExecutorRef,
+    ) -> Self {
+        Self {
+            catalog_manager,
+            query_engine,
+            sql_stmt_executor,
+            registered: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Registers stored functions and starts reloading them in background.
+    pub(crate) async fn start(self: &Arc<Self>) {
+        if let Err(e) = self.reload().await {
+            warn!(e; "Failed to load functions");
+        }
+
+        let manager = Arc::downgrade(self);
+        let _handle = common_runtime::spawn_bg(async move {
+            let mut interval = tokio::time::interval(RELOAD_INTERVAL);
+            // The first tick completes immediately.
+            let _ = interval.tick().await;
+            loop {
+                let _ = interval.tick().await;
+                let Some(manager) = manager.upgrade() else { break };
+                if let Err(e) = manager.reload().await {
+                    warn!(e; "Failed to reload functions");
+                }
+            }
+        });
+    }
+
+    /// Stores the function in the schema and registers it.
+    pub(crate) async fn create(
+        &self,
+        catalog: &str,
+        schema: &str,
+        definition: CreateFunction,
+    ) -> Result<()> {
+        let mut registered = self.registered.lock().await;
+        // The function may be created through other frontends.
+        self.reload_locked(&mut registered).await?;
+
+        let name = normalize_ident(&definition.name);
+        let key = FunctionKey::new(catalog, schema, &name);
+        ensure!(
+            !registered.contains_key(&key) && !is_builtin_function(&name),
+            FunctionExistsSnafu { name }
+        );
+        let udf = self
+            .query_engine
+            .create_sql_udf(catalog, schema, &definition)
+            .context(CreateFunctionSnafu { name: &name })?;
+
+        let function = StoredFunction {
+            definition,
+            gmt_created: current_time_millis(),
+        };
+        let table = self.create_table(catalog).await?;
+        let _ = table
+            .insert(InsertRequest {
+                catalog_name: catalog.to_string(),
+                schema_name: PRIVATE_SCHEMA_NAME.to_string(),
+                table_name: FUNCTIONS_TABLE_NAME.to_string(),
+                columns_values: HashMap::from([
+                    (SCHEMA_COLUMN.to_string(), string_vector(schema)),
+                    (NAME_COLUMN.to_string(), string_vector(&name)),
+                    (
+                        DEFINITION_COLUMN.to_string(),
+                        string_vector(&function.definition.to_string()),
+                    ),
+                    (
+                        GMT_CREATED_COLUMN.to_string(),
+                        Arc::new(TimestampMillisecondVector::from_slice([
+                            function.gmt_created
+                        ])) as _,
+                    ),
+                    (TS_COLUMN.to_string(), ts_vector()),
+                ]),
+                region_number: 0,
+            })
+            .await
+            .context(TableSnafu)?;
+
+        self.query_engine.register_schema_udf(catalog, schema, udf);
+        let _ = registered.insert(key, function);
+        Ok(())
+    }
+
+    /// Removes the function from the schema, returns whether it exists.
+    pub(crate) async fn remove(
+        &self,
+        catalog: &str,
+        schema: &str,
+        name: &str,
+        if_exists: bool,
+    ) -> Result<bool> {
+        let mut registered = self.registered.lock().await;
+        self.reload_locked(&mut registered).await?;
+
+        let key = FunctionKey::new(catalog, schema, name);
+        if !registered.contains_key(&key) {
+            ensure!(if_exists, FunctionNotFoundSnafu { name });
+            return Ok(false);
+        }
+
+        let table = self
+            .table(catalog)
+            .await?
+            .with_context(|| TableNotFoundSnafu {
+                table_name: FUNCTIONS_TABLE_NAME,
+            })?;
+        let _ = table
+            .delete(DeleteRequest {
+                key_column_values: HashMap::from([
+                    (SCHEMA_COLUMN.to_string(), string_vector(schema)),
+                    (NAME_COLUMN.to_string(), string_vector(name)),
+                    (TS_COLUMN.to_string(), ts_vector()),
+                ]),
+            })
+            .await
+            .context(TableSnafu)?;
+
+        let _ = self
+            .query_engine
+            .deregister_schema_udf(catalog, schema, name);
+        let _ = registered.remove(&key);
+        Ok(true)
+    }
+
+    /// Lists stored functions of the schema, ordered by name.
+    pub(crate) async fn list(
+        &self,
+        catalog: &str,
+        schema: &str,
+    ) -> Result<Vec<(String, StoredFunction)>> {
+        let mut registered = self.registered.lock().await;
+        self.reload_locked(&mut registered).await?;
+
+        let mut functions = registered
+            .iter()
+            .filter(|(key, _)| key.catalog == catalog && key.schema == schema)
+            .map(|(key, function)| (key.name.clone(), function.clone()))
+            .collect::<Vec<_>>();
+        functions.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        Ok(functions)
+    }
+
+    async fn reload(&self) -> Result<()> {
+        let mut registered = self.registered.lock().await;
+        self.reload_locked(&mut registered).await
+    }
+
+    /// Syncs registered functions with the functions table.
+    async fn reload_locked(
+        &self,
+        registered: &mut HashMap<FunctionKey, StoredFunction>,
+    ) -> Result<()> {
+        let mut stored = Vec::new();
+        for catalog in self
+            .catalog_manager
+            .catalog_names()
+            .await
+            .context(CatalogSnafu)?
+        {
+            stored.extend(self.scan(&catalog).await?);
+        }
+
+        registered.retain(|key, function| {
+            let unchanged = stored
+                .iter()
+                .any(|(k, f)| k == key && f.gmt_created == function.gmt_created);
+            if !unchanged {
+                let _ =
+                    self.query_engine
+                        .deregister_schema_udf(&key.catalog, &key.schema, &key.name);
+            }
+            unchanged
+        });
+
+        // Functions may call functions created before them.
+        stored.sort_unstable_by_key(|(_, function)| function.gmt_created);
+        for (key, function) in stored {
+            if registered.contains_key(&key) {
+                continue;
+            }
+            let FunctionKey {
+                catalog,
+                schema,
+                name,
+            } = &key;
+            match self
+                .query_engine
+                .create_sql_udf(catalog, schema, &function.definition)
+            {
+                Ok(udf) => {
+                    self.query_engine.register_schema_udf(catalog, schema, udf);
+                    info!(
+                        "Registered function {catalog}.{schema}.{name}: {}",
+                        function.definition
+                    );
+                    let _ = registered.insert(key, function);
+                }
+                Err(e) => warn!(e; "Failed to register function {catalog}.{schema}.{name}"),
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads functions from the functions table of the catalog, skipping invalid
+    /// definitions.
+    async fn scan(&self, catalog: &str) -> Result<Vec<(FunctionKey, StoredFunction)>> {
+        let Some(table) = self.table(catalog).await? else { return Ok(vec![]) };
+        let stream = table
+            .scan_to_stream(ScanRequest::default())
+            .await
+            .context(TableSnafu)?;
+        let batches = record_util::collect(stream)
+            .await
+            .context(CollectRecordbatchSnafu)?;
+
+        let mut functions = Vec::new();
+        for batch in batches {
+            let (Some(schemas), Some(names), Some(definitions), Some(created)) = (
+                batch.column_by_name(SCHEMA_COLUMN),
+                batch.column_by_name(NAME_COLUMN),
+                batch.column_by_name(DEFINITION_COLUMN),
+                batch.column_by_name(GMT_CREATED_COLUMN),
+            ) else { continue };
+            for i in 0..batch.num_rows() {
+                let Value::String(schema) = schemas.get(i) else { continue };
+                let Value::String(name) = names.get(i) else { continue };
+                let Value::String(definition) = definitions.get(i) else { continue };
+                let Value::Timestamp(gmt_created) = created.get(i) else { continue };
+                match parse_definition(definition.as_utf8()) {
+                    Ok(definition) => functions.push((
+                        FunctionKey::new(catalog, schema.as_utf8(), name.as_utf8()),
+                        StoredFunction {
+                            definition,
+                            gmt_created: gmt_created.value(),
+                        },
+                    )),
+                    Err(e) => warn!(e; "Invalid definition of function {}", name.as_utf8()),
+                }
+            }
+        }
+        Ok(functions)
+    }
+
+    async fn table(&self, catalog: &str) -> Result<Option<TableRef>> {
+        self.catalog_manager
+            .table(catalog, PRIVATE_SCHEMA_NAME, FUNCTIONS_TABLE_NAME)
+            .await
+            .context(CatalogSnafu)
+    }
+
+    async fn create_table(&self, catalog: &str) -> Result<TableRef> {
+        if let Some(table) = self.table(catalog).await? {
+            return Ok(table);
+        }
+
+        let query_ctx = Arc::new(QueryContext::with(catalog, PRIVATE_SCHEMA_NAME));
+        let create_schema = format!("CREATE DATABASE IF NOT EXISTS {PRIVATE_SCHEMA_NAME}");
+        for sql in [create_schema.as_str(), CREATE_FUNCTIONS_TABLE] {
+            let mut stmts = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {})
+                .context(ParseSqlSnafu)?;
+            let _ = self
+                .sql_stmt_executor
+                .execute_sql(stmts.remove(0), query_ctx.clone())
+                .await
+                .context(ExecuteStatementSnafu)?;
+        }
+
+        self.table(catalog)
+            .await?
+            .with_context(|| TableNotFoundSnafu {
+                table_name: FUNCTIONS_TABLE_NAME,
+            })
+    }
+}
+
+fn parse_definition(definition: &str) -> Result<CreateFunction> {
+    let mut stmts = ParserContext::create_with_dialect(definition, &GreptimeDbDialect {})
+        .context(ParseSqlSnafu)?;
+    match stmts.pop() {
+        Some(Statement::CreateFunction(definition)) if stmts.is_empty() => Ok(definition),
+        _ => InvalidSqlSnafu {
+            err_msg: format!("not a function definition: {definition}"),
+        }
+        .fail(),
+    }
+}
+
+/// Built-in functions take precedence over user defined ones in SQL.
+fn is_builtin_function(name: &str) -> bool {
+    BuiltinScalarFunction::from_str(name).is_ok()
+        || AggregateFunction::from_str(name).is_ok()
+        || FUNCTION_REGISTRY.get_function(name).is_some()
+}
+
+fn string_vector(value: &str) -> VectorRef {
+    Arc::new(StringVector::from(vec![value]))
+}
+
+fn ts_vector() -> VectorRef {
+    Arc::new(TimestampMillisecondVector::from_slice([0]))
+}
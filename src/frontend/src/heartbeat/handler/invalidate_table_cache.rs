@@ -27,10 +27,13 @@ use common_meta::table_name::TableName;
 use common_telemetry::{error, info};
 use partition::manager::TableRouteCacheInvalidatorRef;
 
+use crate::schema_version::SchemaVersionRef;
+
 #[derive(Clone)]
 pub struct InvalidateTableCacheHandler {
     backend_cache_invalidator: KvCacheInvalidatorRef,
     table_route_cache_invalidator: TableRouteCacheInvalidatorRef,
+    schema_version: SchemaVersionRef,
 }
 
 #[async_trait]
@@ -75,10 +78,12 @@ impl InvalidateTableCacheHandler {
     pub fn new(
         backend_cache_invalidator: KvCacheInvalidatorRef,
         table_route_cache_invalidator: TableRouteCacheInvalidatorRef,
+        schema_version: SchemaVersionRef,
     ) -> Self {
         Self {
             backend_cache_invalidator,
             table_route_cache_invalidator,
+            schema_version,
         }
     }
 
@@ -104,5 +109,8 @@ impl InvalidateTableCacheHandler {
                 table_ident.table,
             ))
             .await;
+
+        // The table may be altered or dropped by another frontend.
+        self.schema_version.increase();
     }
 }
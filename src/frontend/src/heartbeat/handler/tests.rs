@@ -30,6 +30,7 @@ use partition::manager::TableRouteCacheInvalidator;
 use tokio::sync::mpsc;
 
 use super::invalidate_table_cache::InvalidateTableCacheHandler;
+use crate::schema_version::SchemaVersionRef;
 
 #[derive(Default)]
 pub struct MockKvCacheInvalidator {
@@ -72,8 +73,13 @@ async fn test_invalidate_table_cache_handler() {
         inner: Mutex::new(inner),
     });
 
+    let schema_version = SchemaVersionRef::default();
     let executor = Arc::new(HandlerGroupExecutor::new(vec![Arc::new(
-        InvalidateTableCacheHandler::new(backend.clone(), table_route.clone()),
+        InvalidateTableCacheHandler::new(
+            backend.clone(),
+            table_route.clone(),
+            schema_version.clone(),
+        ),
     )]));
 
     let (tx, mut rx) = mpsc::channel(8);
@@ -115,6 +121,7 @@ async fn test_invalidate_table_cache_handler() {
         .lock()
         .unwrap()
         .contains_key(&table_name.to_string()));
+    assert_eq!(schema_version.get(), 1);
 
     // removes a invalid key
     handle_instruction(
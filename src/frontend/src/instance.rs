@@ -13,14 +13,19 @@
 // limitations under the License.
 
 pub mod distributed;
+mod elasticsearch;
 mod grpc;
 mod influxdb;
+mod loki;
 mod opentsdb;
+mod otlp;
 mod prom_store;
+mod protocol_query;
 mod script;
 mod standalone;
 
 use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -30,11 +35,11 @@ use api::v1::greptime_request::Request;
 use api::v1::meta::Role;
 use api::v1::{AddColumns, AlterExpr, Column, DdlRequest, InsertRequest, InsertRequests};
 use async_trait::async_trait;
-use catalog::remote::CachedMetaKvBackend;
+use catalog::remote::{CachedMetaKvBackend, MetaKvBackend};
 use catalog::CatalogManagerRef;
 use client::client_manager::DatanodeClients;
 use common_base::Plugins;
-use common_catalog::consts::MITO_ENGINE;
+use common_catalog::consts::{MITO_ENGINE, PRIVATE_SCHEMA_NAME};
 use common_error::ext::BoxedError;
 use common_grpc::channel_manager::{ChannelConfig, ChannelManager};
 use common_meta::heartbeat::handler::parse_mailbox_message::ParseMailboxMessageHandler;
@@ -44,7 +49,7 @@ use common_query::Output;
 use common_telemetry::logging::{debug, info};
 use common_telemetry::timer;
 use datafusion::sql::sqlparser::ast::ObjectName;
-use datanode::instance::sql::table_idents_to_full_name;
+use datanode::instance::sql::{idents_to_full_database_name, table_idents_to_full_name};
 use datanode::instance::InstanceRef as DnInstanceRef;
 use datatypes::schema::Schema;
 use distributed::DistInstance;
@@ -56,6 +61,7 @@ use query::plan::LogicalPlan;
 use query::query_engine::options::{validate_catalog_and_schema, QueryOptions};
 use query::query_engine::DescribeResult;
 use query::{QueryEngineFactory, QueryEngineRef};
+use servers::auth::UserProviderRef;
 use servers::error as server_error;
 use servers::error::{ExecuteQuerySnafu, ParsePromQLSnafu};
 use servers::interceptor::{
@@ -65,9 +71,11 @@ use servers::prometheus::PrometheusHandler;
 use servers::query_handler::grpc::{GrpcQueryHandler, GrpcQueryHandlerRef};
 use servers::query_handler::sql::SqlQueryHandler;
 use servers::query_handler::{
-    InfluxdbLineProtocolHandler, OpentsdbProtocolHandler, PromStoreProtocolHandler, ScriptHandler,
+    ElasticsearchProtocolHandler, InfluxdbLineProtocolHandler, LokiProtocolHandler,
+    OpenTelemetryProtocolHandler, OpentsdbProtocolHandler, PromStoreProtocolHandler, ScriptHandler,
 };
-use session::context::QueryContextRef;
+use session::context::{QueryContextRef, UserInfo};
+use session::process::ProcessManager;
 use snafu::prelude::*;
 use sql::dialect::Dialect;
 use sql::parser::ParserContext;
@@ -77,8 +85,8 @@ use sql::statements::statement::Statement;
 use crate::catalog::FrontendCatalogManager;
 use crate::error::{
     self, Error, ExecLogicalPlanSnafu, ExecutePromqlSnafu, ExternalSnafu,
-    InvalidInsertRequestSnafu, MissingMetasrvOptsSnafu, ParseSqlSnafu, PlanStatementSnafu, Result,
-    SqlExecInterceptedSnafu,
+    InvalidInsertRequestSnafu, MissingMetasrvOptsSnafu, ParseSqlSnafu, PlanStatementSnafu,
+    PrivateSchemaAccessDeniedSnafu, Result, SchemaAccessDeniedSnafu, SqlExecInterceptedSnafu,
 };
 use crate::expr_factory::{CreateExprFactoryRef, DefaultCreateExprFactory};
 use crate::frontend::FrontendOptions;
@@ -86,9 +94,12 @@ use crate::heartbeat::handler::invalidate_table_cache::InvalidateTableCacheHandl
 use crate::heartbeat::HeartbeatTask;
 use crate::instance::standalone::StandaloneGrpcQueryHandler;
 use crate::metrics;
-use crate::script::ScriptExecutor;
+use crate::result_cache::{CatalogRegionSequences, ResultCache, ResultCacheRef};
+use crate::schema_version::{is_schema_changing, SchemaVersionRef};
+use crate::script::{ScriptExecutor, ScriptRunnerElection};
 use crate::server::{start_server, ServerHandlers, Services};
-use crate::statement::StatementExecutor;
+use crate::slow_query::{SlowQueryRecorder, SlowQueryRecorderRef};
+use crate::statement::{new_distributed_job_manager, track_query, StatementExecutor};
 
 #[async_trait]
 pub trait FrontendInstance:
@@ -97,6 +108,9 @@ pub trait FrontendInstance:
     + OpentsdbProtocolHandler
     + InfluxdbLineProtocolHandler
     + PromStoreProtocolHandler
+    + OpenTelemetryProtocolHandler
+    + LokiProtocolHandler
+    + ElasticsearchProtocolHandler
     + ScriptHandler
     + PrometheusHandler
     + Send
@@ -126,6 +140,12 @@ pub struct Instance {
     servers: Arc<ServerHandlers>,
 
     heartbeat_task: Option<HeartbeatTask>,
+
+    slow_query_recorder: Option<SlowQueryRecorderRef>,
+
+    result_cache: Option<ResultCacheRef>,
+
+    schema_version: SchemaVersionRef,
 }
 
 impl Instance {
@@ -175,25 +195,47 @@ impl Instance {
             Some(partition_manager.clone()),
             Some(datanode_clients),
             plugins.clone(),
+            &opts.query,
         )
         .query_engine();
 
-        let script_executor =
-            Arc::new(ScriptExecutor::new(catalog_manager.clone(), query_engine.clone()).await?);
+        let script_executor = Arc::new(
+            ScriptExecutor::new(
+                catalog_manager.clone(),
+                query_engine.clone(),
+                Some(ScriptRunnerElection {
+                    kv_backend: Arc::new(MetaKvBackend {
+                        client: meta_client.clone(),
+                    }),
+                    node_id: opts.node_id,
+                }),
+            )
+            .await?,
+        );
 
         let statement_executor = Arc::new(StatementExecutor::new(
             catalog_manager.clone(),
             query_engine.clone(),
             dist_instance.clone(),
+            new_distributed_job_manager(
+                Arc::new(MetaKvBackend {
+                    client: meta_client.clone(),
+                }),
+                opts.node_id,
+            )?,
         ));
+        statement_executor.register_jobs();
+        statement_executor.recover_jobs().await?;
 
         plugins.insert::<StatementExecutorRef>(statement_executor.clone());
 
+        let schema_version = SchemaVersionRef::default();
         let handlers_executor = HandlerGroupExecutor::new(vec![
             Arc::new(ParseMailboxMessageHandler::default()),
             Arc::new(InvalidateTableCacheHandler::new(
                 meta_backend,
                 partition_manager,
+                schema_version.clone(),
             )),
         ]);
 
@@ -213,6 +255,9 @@ impl Instance {
             plugins: plugins.clone(),
             servers: Arc::new(HashMap::new()),
             heartbeat_task,
+            slow_query_recorder: None,
+            result_cache: None,
+            schema_version,
         })
     }
 
@@ -256,14 +301,19 @@ impl Instance {
     pub async fn try_new_standalone(dn_instance: DnInstanceRef) -> Result<Self> {
         let catalog_manager = dn_instance.catalog_manager();
         let query_engine = dn_instance.query_engine();
-        let script_executor =
-            Arc::new(ScriptExecutor::new(catalog_manager.clone(), query_engine.clone()).await?);
+        let script_executor = Arc::new(
+            ScriptExecutor::new(catalog_manager.clone(), query_engine.clone(), None).await?,
+        );
 
+        let procedure_manager = dn_instance.procedure_manager();
         let statement_executor = Arc::new(StatementExecutor::new(
             catalog_manager.clone(),
             query_engine.clone(),
             dn_instance.clone(),
+            procedure_manager,
         ));
+        // Loaders must be registered before the datanode instance recovers procedures.
+        statement_executor.register_jobs();
 
         Ok(Instance {
             catalog_manager: catalog_manager.clone(),
@@ -275,10 +325,29 @@ impl Instance {
             plugins: Default::default(),
             servers: Arc::new(HashMap::new()),
             heartbeat_task: None,
+            slow_query_recorder: None,
+            result_cache: None,
+            schema_version: SchemaVersionRef::default(),
         })
     }
 
     pub async fn build_servers(&mut self, opts: &FrontendOptions) -> Result<()> {
+        // The recorder must be set before the servers take their copies of the instance.
+        if opts.slow_query.enable {
+            self.slow_query_recorder = Some(Arc::new(SlowQueryRecorder::start(
+                &opts.slow_query,
+                self.clone(),
+            )));
+        }
+
+        if opts.result_cache.enable {
+            let sequences = CatalogRegionSequences::new(self.catalog_manager.clone());
+            self.result_cache = Some(Arc::new(ResultCache::new(
+                &opts.result_cache,
+                Arc::new(sequences),
+            )));
+        }
+
         let servers = Services::build(opts, Arc::new(self.clone()), self.plugins.clone()).await?;
         self.servers = Arc::new(servers);
 
@@ -331,6 +400,7 @@ impl Instance {
                 let _ = self
                     .create_table_by_columns(ctx, table_name, columns, MITO_ENGINE)
                     .await?;
+                self.schema_version.increase();
                 info!(
                     "Successfully created table on insertion: {}.{}.{}",
                     catalog_name, schema_name, table_name
@@ -351,6 +421,7 @@ impl Instance {
                     let _ = self
                         .add_new_columns_to_table(ctx, table_name, add_columns)
                         .await?;
+                    self.schema_version.increase();
                     info!(
                         "Successfully altered table on insertion: {}.{}.{}",
                         catalog_name, schema_name, table_name
@@ -450,6 +521,11 @@ impl FrontendInstance for Instance {
             heartbeat_task.start().await?;
         }
 
+        self.script_executor.start_scheduler(self.clone())?;
+
+        // Functions are registered before serving queries.
+        self.statement_executor.function_manager().start().await;
+
         futures::future::try_join_all(self.servers.values().map(start_server))
             .await
             .context(error::StartServerSnafu)
@@ -464,9 +540,81 @@ fn parse_stmt(sql: &str, dialect: &(dyn Dialect + Send + Sync)) -> Result<Vec<St
 impl Instance {
     async fn query_statement(&self, stmt: Statement, query_ctx: QueryContextRef) -> Result<Output> {
         check_permission(self.plugins.clone(), &stmt, &query_ctx)?;
+        match &stmt {
+            Statement::Use(db) if db == PRIVATE_SCHEMA_NAME => {
+                self.authorize_schema(&query_ctx.current_catalog(), db, &query_ctx)
+                    .await?;
+            }
+            // Functions are created in and dropped from the current schema.
+            Statement::CreateFunction(_) | Statement::DropFunction(_) => {
+                self.authorize_schema(
+                    &query_ctx.current_catalog(),
+                    &query_ctx.current_schema(),
+                    &query_ctx,
+                )
+                .await?;
+            }
+            _ => {}
+        }
+
+        let schema_changing = is_schema_changing(&stmt);
 
         let stmt = QueryStatement::Sql(stmt);
-        self.statement_executor.execute_stmt(stmt, query_ctx).await
+        let output = self.statement_executor.execute_stmt(stmt, query_ctx).await;
+        // The statement may have partially changed the schema even if it fails.
+        if schema_changing {
+            self.schema_version.increase();
+        }
+        output
+    }
+
+    /// Checks the current user may access the schema by the user provider, if
+    /// there is one.
+    async fn authorize_schema(
+        &self,
+        catalog: &str,
+        schema: &str,
+        query_ctx: &QueryContextRef,
+    ) -> Result<()> {
+        let Some(user_provider) = self.plugins.get::<UserProviderRef>() else {
+            return Ok(());
+        };
+        let user_info = query_ctx
+            .current_user()
+            .map(UserInfo::new)
+            .unwrap_or_default();
+        user_provider
+            .authorize(catalog, schema, &user_info)
+            .await
+            .context(SchemaAccessDeniedSnafu { catalog, schema })
+    }
+
+    /// Executes the query as a tracked process, and records it into the slow query
+    /// log if it's slow.
+    async fn execute_query<F>(
+        &self,
+        query: &str,
+        query_ctx: &QueryContextRef,
+        execute: F,
+    ) -> Result<Output>
+    where
+        F: Future<Output = Result<Output>>,
+    {
+        let execute = async {
+            let guard = self
+                .slow_query_recorder
+                .as_ref()
+                .map(|recorder| recorder.track(query, query_ctx));
+            match (execute.await, guard) {
+                (Ok(output), Some(guard)) => Ok(guard.watch(output)),
+                (Err(e), Some(guard)) => {
+                    guard.fail(&e);
+                    Err(e)
+                }
+                (result, None) => result,
+            }
+        };
+        track_query(query, query_ctx, execute).await
     }
 }
 
@@ -496,7 +644,14 @@ impl SqlQueryHandler for Instance {
                         results.push(Err(e));
                         break;
                     }
-                    match self.query_statement(stmt, query_ctx.clone()).await {
+                    let output = self
+                        .execute_query(
+                            query.as_ref(),
+                            &query_ctx,
+                            self.query_statement(stmt, query_ctx.clone()),
+                        )
+                        .await;
+                    match output {
                         Ok(output) => {
                             let output_result =
                                 query_interceptor.post_execute(output, query_ctx.clone());
@@ -568,6 +723,10 @@ impl SqlQueryHandler for Instance {
             .await
             .context(error::CatalogSnafu)
     }
+
+    fn schema_version(&self) -> Option<u64> {
+        Some(self.schema_version.get())
+    }
 }
 
 #[async_trait]
@@ -586,9 +745,27 @@ impl PrometheusHandler for Instance {
             query: query.clone(),
         })?;
 
+        let execute = async {
+            match (&self.result_cache, stmt) {
+                (Some(result_cache), QueryStatement::Promql(stmt)) => {
+                    let statement_executor = &self.statement_executor;
+                    let ctx = &query_ctx;
+                    result_cache
+                        .query(stmt, &query_ctx, move |stmt| {
+                            statement_executor
+                                .execute_stmt(QueryStatement::Promql(stmt), ctx.clone())
+                        })
+                        .await
+                }
+                (_, stmt) => {
+                    self.statement_executor
+                        .execute_stmt(stmt, query_ctx.clone())
+                        .await
+                }
+            }
+        };
         let output = self
-            .statement_executor
-            .execute_stmt(stmt, query_ctx.clone())
+            .execute_query(&query.query, &query_ctx, execute)
             .await
             .map_err(BoxedError::new)
             .with_context(|_| ExecuteQuerySnafu {
@@ -604,6 +781,8 @@ pub fn check_permission(
     stmt: &Statement,
     query_ctx: &QueryContextRef,
 ) -> Result<()> {
+    validate_private_schema(stmt, query_ctx)?;
+
     let need_validate = plugins
         .get::<QueryOptions>()
         .map(|opts| opts.disallow_cross_schema_query)
@@ -618,6 +797,13 @@ pub fn check_permission(
         Statement::Query(_) | Statement::Explain(_) | Statement::Tql(_) | Statement::Delete(_) => {}
         // database ops won't be checked
         Statement::CreateDatabase(_) | Statement::ShowDatabases(_) | Statement::Use(_) => {}
+        // jobs are filtered by the current catalog
+        Statement::ShowJobs(_) => {}
+        // processes are filtered by the current catalog
+        Statement::ShowProcesslist(_) => {}
+        // functions belong to the current schema, which is authorized in `query_statement`
+        Statement::CreateFunction(_) | Statement::DropFunction(_) | Statement::ShowFunctions(_) => {
+        }
         // show create table and alter are not supported yet
         Statement::ShowCreateTable(_) | Statement::CreateExternalTable(_) | Statement::Alter(_) => {
         }
@@ -653,7 +839,84 @@ pub fn check_permission(
         Statement::TruncateTable(stmt) => {
             validate_param(stmt.table_name(), query_ctx)?;
         }
+        Statement::CancelJob(_) | Statement::PauseJob(_) | Statement::ResumeJob(_) => {
+            let job_id = match stmt {
+                Statement::CancelJob(stmt) => stmt.job_id(),
+                Statement::PauseJob(stmt) => stmt.job_id(),
+                Statement::ResumeJob(stmt) => stmt.job_id(),
+                _ => unreachable!(),
+            };
+            // only jobs in the current schema can be controlled
+            if let Some(job) = catalog::information_schema::list_jobs()
+                .into_iter()
+                .find(|job| job.id == job_id)
+            {
+                validate_catalog_and_schema(&job.catalog, &job.schema, query_ctx)
+                    .map_err(BoxedError::new)
+                    .context(SqlExecInterceptedSnafu)?;
+            }
+        }
+        Statement::KillQuery(stmt) => {
+            // only queries in the current schema can be killed
+            if let Some(process) = ProcessManager::global()
+                .list()
+                .into_iter()
+                .find(|process| process.id() == stmt.id())
+            {
+                validate_catalog_and_schema(process.catalog(), process.schema(), query_ctx)
+                    .map_err(BoxedError::new)
+                    .context(SqlExecInterceptedSnafu)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Tables of the private schema, e.g. the slow query log whose queries may contain
+/// credentials, are only accessible from sessions using the schema. Queries are
+/// checked by the query engine.
+fn validate_private_schema(stmt: &Statement, query_ctx: &QueryContextRef) -> Result<()> {
+    if query_ctx.current_schema() == PRIVATE_SCHEMA_NAME {
+        return Ok(());
     }
+
+    let table_name = match stmt {
+        Statement::Insert(insert) => Some(insert.table_name()),
+        Statement::CreateTable(stmt) => Some(&stmt.name),
+        Statement::CreateExternalTable(stmt) => Some(&stmt.name),
+        Statement::DropTable(stmt) => Some(stmt.table_name()),
+        Statement::Alter(stmt) => Some(stmt.table_name()),
+        Statement::DescribeTable(stmt) => Some(stmt.name()),
+        Statement::ShowCreateTable(stmt) => Some(&stmt.table_name),
+        Statement::TruncateTable(stmt) => Some(stmt.table_name()),
+        Statement::Copy(sql::statements::copy::Copy::CopyTable(CopyTable::To(stmt))) => {
+            Some(&stmt.table_name)
+        }
+        Statement::Copy(sql::statements::copy::Copy::CopyTable(CopyTable::From(stmt))) => {
+            Some(&stmt.table_name)
+        }
+        _ => None,
+    };
+    let schema = match (table_name, stmt) {
+        (Some(table_name), _) => Some(
+            table_idents_to_full_name(table_name, query_ctx.clone())
+                .map_err(BoxedError::new)
+                .context(ExternalSnafu)?
+                .1,
+        ),
+        (None, Statement::ShowTables(stmt)) => stmt.database.clone(),
+        (None, Statement::Copy(sql::statements::copy::Copy::CopyDatabase(stmt))) => Some(
+            idents_to_full_database_name(&stmt.database_name, query_ctx)
+                .map_err(BoxedError::new)
+                .context(ExternalSnafu)?
+                .1,
+        ),
+        _ => None,
+    };
+    ensure!(
+        schema.as_deref() != Some(PRIVATE_SCHEMA_NAME),
+        PrivateSchemaAccessDeniedSnafu
+    );
     Ok(())
 }
 
@@ -876,4 +1139,36 @@ mod tests {
         let sql = "DESC TABLE {catalog}{schema}demo;";
         replace_test(sql, plugins, &query_ctx);
     }
+
+    #[test]
+    fn test_validate_private_schema() {
+        // The private schema is checked even if cross schema queries are allowed.
+        let plugins = Arc::new(Plugins::new());
+        let public_ctx = Arc::new(QueryContext::new());
+        let private_ctx = Arc::new(QueryContext::with("greptime", PRIVATE_SCHEMA_NAME));
+
+        let sql = format!(
+            "SELECT * FROM {PRIVATE_SCHEMA_NAME}.slow_queries;
+            INSERT INTO {PRIVATE_SCHEMA_NAME}.slow_queries(query) VALUES ('select 1');
+            DROP TABLE greptime.{PRIVATE_SCHEMA_NAME}.slow_queries;
+            DESC TABLE {PRIVATE_SCHEMA_NAME}.slow_queries;
+            SHOW TABLES FROM {PRIVATE_SCHEMA_NAME};
+            TRUNCATE {PRIVATE_SCHEMA_NAME}.slow_queries;"
+        );
+        let stmts = parse_stmt(&sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(6, stmts.len());
+        // Queries are checked by the query engine.
+        check_permission(plugins.clone(), &stmts[0], &public_ctx).unwrap();
+        for stmt in &stmts[1..] {
+            let err = check_permission(plugins.clone(), stmt, &public_ctx).unwrap_err();
+            assert!(matches!(err, Error::PrivateSchemaAccessDenied { .. }));
+        }
+        for stmt in &stmts {
+            check_permission(plugins.clone(), stmt, &private_ctx).unwrap();
+        }
+
+        let stmts = parse_stmt("DROP TABLE slow_queries", &GreptimeDbDialect {}).unwrap();
+        check_permission(plugins.clone(), &stmts[0], &public_ctx).unwrap();
+        check_permission(plugins, &stmts[0], &private_ctx).unwrap();
+    }
 }
@@ -39,6 +39,7 @@ use common_meta::rpc::router::{Partition as MetaPartition, RouteRequest};
 use common_meta::rpc::store::CompareAndPutRequest;
 use common_meta::table_name::TableName;
 use common_query::Output;
+use common_recordbatch::RecordBatch;
 use common_telemetry::{debug, info};
 use datanode::instance::sql::table_idents_to_full_name;
 use datanode::sql::SqlHandler;
@@ -72,6 +73,7 @@ use crate::error::{
 };
 use crate::expr_factory;
 use crate::instance::distributed::inserter::DistInserter;
+use crate::table::insert::insert_record_batch;
 use crate::table::DistTable;
 
 const MAX_VALUE: &str = "MAXVALUE";
@@ -666,6 +668,21 @@ impl GrpcQueryHandler for DistInstance {
             }
         }
     }
+
+    async fn put_record_batch(
+        &self,
+        table_name: &str,
+        record_batch: RecordBatch,
+        ctx: QueryContextRef,
+    ) -> Result<usize> {
+        insert_record_batch(
+            self.catalog_manager.as_ref(),
+            table_name,
+            record_batch,
+            &ctx,
+        )
+        .await
+    }
 }
 
 fn create_partitions_stmt(partitions: Vec<PartitionInfo>) -> Result<Option<Partitions>> {
@@ -0,0 +1,40 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use common_error::ext::BoxedError;
+use metrics::counter;
+use servers::elasticsearch::{self, BulkRequest};
+use servers::error::{self, Result as ServerResult};
+use servers::query_handler::ElasticsearchProtocolHandler;
+use session::context::QueryContextRef;
+use snafu::ResultExt;
+
+use crate::instance::Instance;
+use crate::metrics::ELASTICSEARCH_LOGS_ROWS;
+
+#[async_trait]
+impl ElasticsearchProtocolHandler for Instance {
+    async fn bulk(&self, request: &BulkRequest, ctx: QueryContextRef) -> ServerResult<()> {
+        let (requests, rows) = elasticsearch::to_grpc_insert_requests(request)?;
+        let _ = self
+            .handle_inserts(requests, ctx)
+            .await
+            .map_err(BoxedError::new)
+            .context(error::ExecuteGrpcQuerySnafu)?;
+
+        counter!(ELASTICSEARCH_LOGS_ROWS, rows as u64);
+        Ok(())
+    }
+}
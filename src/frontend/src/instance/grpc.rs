@@ -16,6 +16,7 @@ use api::v1::greptime_request::Request;
 use api::v1::query_request::Query;
 use async_trait::async_trait;
 use common_query::Output;
+use common_recordbatch::RecordBatch;
 use query::parser::PromQuery;
 use servers::interceptor::{GrpcQueryInterceptor, GrpcQueryInterceptorRef};
 use servers::query_handler::grpc::GrpcQueryHandler;
@@ -23,8 +24,9 @@ use servers::query_handler::sql::SqlQueryHandler;
 use session::context::QueryContextRef;
 use snafu::{ensure, OptionExt};
 
-use crate::error::{Error, IncompleteGrpcResultSnafu, NotSupportedSnafu, Result};
+use crate::error::{Error, IncompleteGrpcResultSnafu, NotSupportedSnafu, Result, UnexpectedSnafu};
 use crate::instance::Instance;
+use crate::table::insert::insert_record_batch;
 
 #[async_trait]
 impl GrpcQueryHandler for Instance {
@@ -77,13 +79,50 @@ impl GrpcQueryHandler for Instance {
                     }
                 }
             }
-            Request::Ddl(_) | Request::Delete(_) => {
+            Request::Delete(_) => {
                 GrpcQueryHandler::do_query(self.grpc_query_handler.as_ref(), request, ctx.clone())
                     .await?
             }
+            Request::Ddl(_) => {
+                let output = GrpcQueryHandler::do_query(
+                    self.grpc_query_handler.as_ref(),
+                    request,
+                    ctx.clone(),
+                )
+                .await;
+                self.schema_version.increase();
+                output?
+            }
         };
 
         let output = interceptor.post_execute(output, ctx)?;
         Ok(output)
     }
+
+    async fn put_record_batch(
+        &self,
+        table_name: &str,
+        record_batch: RecordBatch,
+        ctx: QueryContextRef,
+    ) -> Result<usize> {
+        let interceptor_ref = self.plugins.get::<GrpcQueryInterceptorRef<Error>>();
+        let interceptor = interceptor_ref.as_ref();
+        interceptor.pre_put_record_batch(table_name, &record_batch, ctx.clone())?;
+
+        let rows = insert_record_batch(
+            self.catalog_manager.as_ref(),
+            table_name,
+            record_batch,
+            &ctx,
+        )
+        .await?;
+
+        match interceptor.post_execute(Output::AffectedRows(rows), ctx)? {
+            Output::AffectedRows(rows) => Ok(rows),
+            Output::Stream(_) | Output::RecordBatches(_) => UnexpectedSnafu {
+                violated: "inserts should return affected rows",
+            }
+            .fail(),
+        }
+    }
 }
@@ -14,11 +14,17 @@
 
 use async_trait::async_trait;
 use common_error::ext::BoxedError;
+use common_time::util::current_time_millis;
+use servers::error as server_error;
+use servers::influxdb::influxql::Statement;
+use servers::influxdb::query::{self as influxdb_query, Epoch, InfluxdbTable, SelectQuery, Series};
 use servers::influxdb::InfluxdbRequest;
 use servers::query_handler::InfluxdbLineProtocolHandler;
 use session::context::QueryContextRef;
 use snafu::ResultExt;
+use table::TableRef;
 
+use crate::instance::protocol_query::execute_query_error;
 use crate::instance::Instance;
 
 #[async_trait]
@@ -36,4 +42,106 @@ impl InfluxdbLineProtocolHandler for Instance {
             .context(servers::error::ExecuteGrpcQuerySnafu)?;
         Ok(())
     }
+
+    async fn query(
+        &self,
+        statement: Statement,
+        epoch: Option<Epoch>,
+        ctx: QueryContextRef,
+    ) -> server_error::Result<Vec<Series>> {
+        let query = format!("{statement:?}");
+        let now = current_time_millis();
+
+        match statement {
+            Statement::Select(select) => {
+                // Like InfluxDB, querying an absent measurement returns no series.
+                let Some(table) = self
+                    .current_schema_table(&ctx, &select.from)
+                    .await
+                    .map_err(execute_query_error(&query))?
+                else {
+                    return Ok(vec![]);
+                };
+                let Some(influxdb_table) = InfluxdbTable::try_new(&table) else {
+                    return Ok(vec![]);
+                };
+
+                let select_query = SelectQuery::try_new(&select, &influxdb_table, now)?;
+                let dataframe = self
+                    .read_table(table)
+                    .map_err(execute_query_error(&query))?;
+                let plan = select_query.to_plan(dataframe)?;
+                let recordbatches = self
+                    .execute_query_plan(plan, &ctx)
+                    .await
+                    .map_err(execute_query_error(&query))?;
+                select_query.recordbatches_to_series(recordbatches, epoch)
+            }
+            Statement::ShowMeasurements(show) => {
+                let tables = self
+                    .current_schema_tables(&ctx, |_| true)
+                    .await
+                    .map_err(execute_query_error(&query))?;
+                influxdb_query::show_measurements(&show, &to_influxdb_tables(&tables))
+            }
+            Statement::ShowTagKeys(show) => {
+                let tables = self
+                    .current_schema_tables(&ctx, |_| true)
+                    .await
+                    .map_err(execute_query_error(&query))?;
+                influxdb_query::show_tag_keys(&show, &to_influxdb_tables(&tables))
+            }
+            Statement::ShowFieldKeys(show) => {
+                let tables = self
+                    .current_schema_tables(&ctx, |_| true)
+                    .await
+                    .map_err(execute_query_error(&query))?;
+                influxdb_query::show_field_keys(&show, &to_influxdb_tables(&tables))
+            }
+            Statement::ShowTagValues { show, keys } => {
+                let tables = self
+                    .current_schema_tables(&ctx, |_| true)
+                    .await
+                    .map_err(execute_query_error(&query))?;
+                let influxdb_tables = to_influxdb_tables(&tables);
+
+                let mut series = vec![];
+                for influxdb_table in influxdb_query::filter_measurements(&show, &influxdb_tables)?
+                {
+                    // Safety: influxdb tables are converted from the tables.
+                    let table = tables
+                        .iter()
+                        .find(|t| t.table_info().name == influxdb_table.measurement)
+                        .unwrap();
+                    let dataframe = self
+                        .read_table(table.clone())
+                        .map_err(execute_query_error(&query))?;
+                    let Some(plan) = influxdb_query::tag_values_to_plan(
+                        dataframe,
+                        influxdb_table,
+                        &show,
+                        &keys,
+                        now,
+                    )?
+                    else {
+                        continue;
+                    };
+                    let recordbatches = self
+                        .execute_query_plan(plan, &ctx)
+                        .await
+                        .map_err(execute_query_error(&query))?;
+                    series.extend(influxdb_query::recordbatches_to_tag_values(
+                        influxdb_table,
+                        &show,
+                        recordbatches,
+                    ));
+                }
+                Ok(series)
+            }
+        }
+    }
+}
+
+fn to_influxdb_tables(tables: &[TableRef]) -> Vec<InfluxdbTable> {
+    tables.iter().filter_map(InfluxdbTable::try_new).collect()
 }
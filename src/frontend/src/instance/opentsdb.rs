@@ -15,12 +15,22 @@
 use api::v1::InsertRequests;
 use async_trait::async_trait;
 use common_error::ext::BoxedError;
+use common_time::util::current_time_millis;
 use servers::error as server_error;
-use servers::opentsdb::codec::DataPoint;
+use servers::opentsdb::codec::{
+    DataPoint, OPENTSDB_FIELD_COLUMN_NAME, OPENTSDB_TIMESTAMP_COLUMN_NAME,
+};
+use servers::opentsdb::query::{
+    self as opentsdb_query, LookupRequest, LookupResult, QueryRequest, QueryResponse,
+    SuggestRequest, SuggestType,
+};
 use servers::query_handler::OpentsdbProtocolHandler;
 use session::context::QueryContextRef;
 use snafu::prelude::*;
+use table::TableRef;
 
+use crate::error::Result;
+use crate::instance::protocol_query::execute_query_error;
 use crate::instance::Instance;
 
 #[async_trait]
@@ -38,4 +48,155 @@ impl OpentsdbProtocolHandler for Instance {
             })?;
         Ok(())
     }
+
+    async fn query(
+        &self,
+        request: QueryRequest,
+        ctx: QueryContextRef,
+    ) -> server_error::Result<Vec<QueryResponse>> {
+        let (start, end) = request.time_range(current_time_millis())?;
+
+        let mut responses = Vec::with_capacity(request.queries.len());
+        for sub_query in &request.queries {
+            let query = format!("{sub_query:?}");
+            let table = self
+                .opentsdb_table(&ctx, &sub_query.metric)
+                .await
+                .map_err(execute_query_error(&query))?
+                .with_context(|| no_such_metric(&sub_query.metric))?;
+            let dataframe = self
+                .read_table(table)
+                .map_err(execute_query_error(&query))?;
+
+            let plan = opentsdb_query::query_to_plan(dataframe, sub_query, start, end)?;
+            let recordbatches = self
+                .execute_query_plan(plan, &ctx)
+                .await
+                .map_err(execute_query_error(&query))?;
+
+            responses.extend(opentsdb_query::recordbatches_to_responses(
+                sub_query,
+                recordbatches,
+                start,
+                end,
+                request.ms_resolution,
+            )?);
+        }
+        Ok(responses)
+    }
+
+    async fn suggest(
+        &self,
+        request: SuggestRequest,
+        ctx: QueryContextRef,
+    ) -> server_error::Result<Vec<String>> {
+        let query = format!("{request:?}");
+        let tables = self
+            .current_schema_tables(&ctx, is_opentsdb_table)
+            .await
+            .map_err(execute_query_error(&query))?;
+
+        let mut names = match request.suggest_type {
+            SuggestType::Metrics => tables
+                .iter()
+                .map(|table| table.table_info().name.clone())
+                .filter(|name| name.starts_with(&request.q))
+                .collect::<Vec<_>>(),
+            SuggestType::Tagk => tables
+                .iter()
+                .flat_map(tag_columns)
+                .filter(|name| name.starts_with(&request.q))
+                .collect(),
+            SuggestType::Tagv => {
+                let mut values = vec![];
+                for table in tables {
+                    for tagk in tag_columns(&table) {
+                        let dataframe = self
+                            .read_table(table.clone())
+                            .map_err(execute_query_error(&query))?;
+                        let plan =
+                            opentsdb_query::tag_values_to_plan(dataframe, &tagk, &request.q)?;
+                        let recordbatches = self
+                            .execute_query_plan(plan, &ctx)
+                            .await
+                            .map_err(execute_query_error(&query))?;
+                        values.extend(opentsdb_query::recordbatches_to_strings(recordbatches));
+                    }
+                }
+                values
+            }
+        };
+
+        names.sort();
+        names.dedup();
+        names.truncate(request.max);
+        Ok(names)
+    }
+
+    async fn lookup(
+        &self,
+        request: LookupRequest,
+        ctx: QueryContextRef,
+    ) -> server_error::Result<Vec<LookupResult>> {
+        let query = format!("{request:?}");
+        let table = self
+            .opentsdb_table(&ctx, &request.metric)
+            .await
+            .map_err(execute_query_error(&query))?
+            .with_context(|| no_such_metric(&request.metric))?;
+        let dataframe = self
+            .read_table(table)
+            .map_err(execute_query_error(&query))?;
+
+        let plan = opentsdb_query::lookup_to_plan(dataframe, &request)?;
+        let recordbatches = self
+            .execute_query_plan(plan, &ctx)
+            .await
+            .map_err(execute_query_error(&query))?;
+        Ok(opentsdb_query::recordbatches_to_lookup_results(
+            &request.metric,
+            recordbatches,
+        ))
+    }
+}
+
+impl Instance {
+    /// Returns the table of the metric, or `None` if the table does not exist or is
+    /// not written by OpenTSDB.
+    async fn opentsdb_table(
+        &self,
+        ctx: &QueryContextRef,
+        metric: &str,
+    ) -> Result<Option<TableRef>> {
+        let table = self.current_schema_table(ctx, metric).await?;
+        Ok(table.filter(is_opentsdb_table))
+    }
+}
+
+/// Tables written by OpenTSDB have a timestamp column and a value column, besides
+/// the tag columns.
+fn is_opentsdb_table(table: &TableRef) -> bool {
+    let schema = table.schema();
+    schema
+        .column_schema_by_name(OPENTSDB_TIMESTAMP_COLUMN_NAME)
+        .is_some()
+        && schema
+            .column_schema_by_name(OPENTSDB_FIELD_COLUMN_NAME)
+            .is_some()
+}
+
+fn tag_columns(table: &TableRef) -> Vec<String> {
+    table
+        .schema()
+        .column_schemas()
+        .iter()
+        .map(|column| column.name.clone())
+        .filter(|name| name != OPENTSDB_TIMESTAMP_COLUMN_NAME && name != OPENTSDB_FIELD_COLUMN_NAME)
+        .collect()
+}
+
+fn no_such_metric(metric: &str) -> server_error::InvalidOpentsdbQuerySnafu<String> {
+    server_error::InvalidOpentsdbQuerySnafu {
+        reason: format!("No such name for 'metrics': '{metric}'"),
+    }
 }
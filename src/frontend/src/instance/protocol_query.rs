@@ -0,0 +1,104 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers shared by the query APIs of the OpenTSDB and InfluxDB protocols, which
+//! build their plans by reading tables of the current schema directly.
+
+use common_error::ext::BoxedError;
+use common_query::Output;
+use common_recordbatch::RecordBatches;
+use query::dataframe::DataFrame;
+use query::plan::LogicalPlan;
+use servers::error as server_error;
+use session::context::QueryContextRef;
+use snafu::{IntoError, ResultExt};
+use table::TableRef;
+
+use crate::error::{
+    CatalogSnafu, CollectRecordbatchSnafu, Error, ExecLogicalPlanSnafu, ReadTableSnafu, Result,
+    UnexpectedSnafu,
+};
+use crate::instance::Instance;
+
+impl Instance {
+    /// Returns the table `table_name` of the current schema.
+    pub(super) async fn current_schema_table(
+        &self,
+        ctx: &QueryContextRef,
+        table_name: &str,
+    ) -> Result<Option<TableRef>> {
+        self.catalog_manager
+            .table(&ctx.current_catalog(), &ctx.current_schema(), table_name)
+            .await
+            .context(CatalogSnafu)
+    }
+
+    /// Returns the tables of the current schema accepted by `filter`.
+    pub(super) async fn current_schema_tables(
+        &self,
+        ctx: &QueryContextRef,
+        filter: impl Fn(&TableRef) -> bool,
+    ) -> Result<Vec<TableRef>> {
+        let table_names = self
+            .catalog_manager
+            .table_names(&ctx.current_catalog(), &ctx.current_schema())
+            .await
+            .context(CatalogSnafu)?;
+
+        let mut tables = Vec::with_capacity(table_names.len());
+        for table_name in table_names {
+            if let Some(table) = self.current_schema_table(ctx, &table_name).await? {
+                if filter(&table) {
+                    tables.push(table);
+                }
+            }
+        }
+        Ok(tables)
+    }
+
+    pub(super) fn read_table(&self, table: TableRef) -> Result<DataFrame> {
+        let table_name = table.table_info().name.clone();
+        self.query_engine
+            .read_table(table)
+            .context(ReadTableSnafu { table_name })
+    }
+
+    /// Executes the query `plan` and collects its results.
+    pub(super) async fn execute_query_plan(
+        &self,
+        plan: LogicalPlan,
+        ctx: &QueryContextRef,
+    ) -> Result<RecordBatches> {
+        let output = self
+            .query_engine
+            .execute(plan, ctx.clone())
+            .await
+            .context(ExecLogicalPlanSnafu)?;
+
+        match output {
+            Output::Stream(stream) => RecordBatches::try_collect(stream)
+                .await
+                .context(CollectRecordbatchSnafu),
+            Output::RecordBatches(recordbatches) => Ok(recordbatches),
+            Output::AffectedRows(_) => UnexpectedSnafu {
+                violated: "query plan returns affected rows",
+            }
+            .fail(),
+        }
+    }
+}
+
+pub(super) fn execute_query_error(query: &str) -> impl FnOnce(Error) -> server_error::Error + '_ {
+    move |e| server_error::ExecuteQuerySnafu { query }.into_error(BoxedError::new(e))
+}
@@ -15,7 +15,7 @@
 use std::collections::HashMap;
 
 use async_trait::async_trait;
-use common_query::Output;
+use common_query::{Output, ScriptSchedule};
 use common_telemetry::timer;
 use servers::query_handler::ScriptHandler;
 
@@ -29,10 +29,11 @@ impl ScriptHandler for Instance {
         schema: &str,
         name: &str,
         script: &str,
+        schedule: ScriptSchedule,
     ) -> servers::error::Result<()> {
         let _timer = timer!(metrics::METRIC_HANDLE_SCRIPTS_ELAPSED);
         self.script_executor
-            .insert_script(schema, name, script)
+            .insert_script(schema, name, script, schedule)
             .await
     }
 
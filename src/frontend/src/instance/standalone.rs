@@ -17,6 +17,7 @@ use std::sync::Arc;
 use api::v1::greptime_request::Request;
 use async_trait::async_trait;
 use common_query::Output;
+use common_recordbatch::RecordBatch;
 use datanode::error::Error as DatanodeError;
 use servers::query_handler::grpc::{GrpcQueryHandler, GrpcQueryHandlerRef};
 use session::context::QueryContextRef;
@@ -42,4 +43,16 @@ impl GrpcQueryHandler for StandaloneGrpcQueryHandler {
             .await
             .context(error::InvokeDatanodeSnafu)
     }
+
+    async fn put_record_batch(
+        &self,
+        table_name: &str,
+        record_batch: RecordBatch,
+        ctx: QueryContextRef,
+    ) -> Result<usize> {
+        self.0
+            .put_record_batch(table_name, record_batch, ctx)
+            .await
+            .context(error::InvokeDatanodeSnafu)
+    }
 }
@@ -19,11 +19,15 @@ pub mod catalog;
 pub mod error;
 pub mod expr_factory;
 pub mod frontend;
+pub mod function;
 pub mod heartbeat;
 pub mod instance;
 pub(crate) mod metrics;
+pub mod result_cache;
+pub mod schema_version;
 mod script;
 mod server;
 pub mod service_config;
+pub mod slow_query;
 pub mod statement;
 pub mod table;
@@ -16,6 +16,8 @@ pub(crate) const METRIC_HANDLE_SQL_ELAPSED: &str = "frontend.handle_sql_elapsed"
 pub(crate) const METRIC_EXEC_PLAN_ELAPSED: &str = "frontend.exec_plan_elapsed";
 pub(crate) const METRIC_HANDLE_SCRIPTS_ELAPSED: &str = "frontend.handle_scripts_elapsed";
 pub(crate) const METRIC_RUN_SCRIPT_ELAPSED: &str = "frontend.run_script_elapsed";
+pub(crate) const METRIC_RESULT_CACHE_HIT: &str = "frontend.result_cache.hit";
+pub(crate) const METRIC_RESULT_CACHE_MISS: &str = "frontend.result_cache.miss";
 
 /// frontend metrics
 /// Metrics for creating table in dist mode.
@@ -24,3 +26,8 @@ pub const DIST_INGEST_ROW_COUNT: &str = "frontend.dist.ingest_rows";
 
 /// The samples count of Prometheus remote write.
 pub const PROM_STORE_REMOTE_WRITE_SAMPLES: &str = "frontend.prometheus.remote_write.samples";
+
+/// The rows count of OTLP metrics.
+pub const OTLP_METRICS_ROWS: &str = "frontend.otlp.metrics.rows";
+pub const LOKI_LOGS_ROWS: &str = "frontend.loki.logs.rows";
+pub const ELASTICSEARCH_LOGS_ROWS: &str = "frontend.elasticsearch.logs.rows";
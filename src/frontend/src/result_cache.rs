@@ -0,0 +1,694 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Caches results of PromQL range queries, e.g. the ones refreshed periodically by
+//! dashboards. Results are cached by steps, so a query whose range moves forward
+//! reuses the cached steps and only computes the new ones.
+//!
+//! A cached result is reused only while the committed sequences of the regions of
+//! the queried tables are unchanged, so a write through any frontend invalidates
+//! the results reading the table. The sequences are read from the regions before
+//! the query is executed, see [RegionSequences].
+//!
+//! Only PromQL range queries are cached, results of SQL queries are not.
+
+use std::collections::BTreeSet;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use catalog::CatalogManagerRef;
+use common_meta::table_name::TableName;
+use common_query::Output;
+use common_recordbatch::{RecordBatch, RecordBatches};
+use common_telemetry::warn;
+use datatypes::arrow::array::BooleanArray;
+use datatypes::arrow::compute;
+use datatypes::data_type::ConcreteDataType;
+use datatypes::scalars::ScalarVector;
+use datatypes::schema::SchemaRef;
+use datatypes::types::TimestampType;
+use datatypes::vectors::TimestampMillisecondVector;
+use metrics::increment_counter;
+use moka::sync::Cache;
+use promql_parser::label::{MatchOp, METRIC_NAME};
+use promql_parser::parser::{
+    AggregateExpr, BinaryExpr, Call, EvalStmt, Expr, MatrixSelector, Offset, ParenExpr, UnaryExpr,
+    VectorSelector,
+};
+use serde::{Deserialize, Serialize};
+use session::context::QueryContext;
+use snafu::ResultExt;
+
+use crate::error::{
+    CatalogSnafu, CollectRecordbatchSnafu, CreateRecordBatchSnafu, FilterCachedResultSnafu, Result,
+};
+use crate::metrics::{METRIC_RESULT_CACHE_HIT, METRIC_RESULT_CACHE_MISS};
+use crate::table::DistTable;
+
+/// Options of the query result cache.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResultCacheOptions {
+    pub enable: bool,
+    /// Max number of cached results.
+    pub capacity: u64,
+    /// Cached steps are computed again after this duration.
+    #[serde(with = "humantime_serde")]
+    pub time_to_live: Duration,
+}
+
+impl Default for ResultCacheOptions {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            capacity: 1024,
+            time_to_live: Duration::from_secs(600),
+        }
+    }
+}
+
+/// Committed sequences of regions by region ids.
+type Watermarks = Vec<(u64, u64)>;
+
+/// Provides the committed sequences of the regions of tables, a region's
+/// sequence increases after each write.
+#[async_trait]
+pub trait RegionSequences: Send + Sync {
+    /// Returns the sorted committed sequences of the regions of the table by
+    /// region ids, or `None` if they are unknown.
+    async fn committed_sequences(&self, table: &TableName) -> Result<Option<Watermarks>>;
+}
+
+pub type RegionSequencesRef = Arc<dyn RegionSequences>;
+
+/// Reads the committed sequences from the regions of the tables in the catalog.
+pub(crate) struct CatalogRegionSequences {
+    catalog_manager: CatalogManagerRef,
+}
+
+impl CatalogRegionSequences {
+    pub(crate) fn new(catalog_manager: CatalogManagerRef) -> Self {
+        Self { catalog_manager }
+    }
+}
+
+#[async_trait]
+impl RegionSequences for CatalogRegionSequences {
+    async fn committed_sequences(&self, table: &TableName) -> Result<Option<Watermarks>> {
+        let Some(table) = self
+            .catalog_manager
+            .table(&table.catalog_name, &table.schema_name, &table.table_name)
+            .await
+            .context(CatalogSnafu)?
+        else {
+            return Ok(None);
+        };
+        // Regions of distributed tables are stored in datanodes.
+        if let Some(table) = table.as_any().downcast_ref::<DistTable>() {
+            return table.region_sequences().await.map(Some);
+        }
+        let Ok(stats) = table.region_stats() else {
+            return Ok(None);
+        };
+        let mut sequences = stats
+            .into_iter()
+            .map(|stat| (stat.region_id, stat.committed_sequence))
+            .collect::<Vec<_>>();
+        sequences.sort_unstable();
+        Ok(Some(sequences))
+    }
+}
+
+pub type ResultCacheRef = Arc<ResultCache>;
+
+pub struct ResultCache {
+    cache: Cache<CacheKey, Arc<CachedResult>>,
+    sequences: RegionSequencesRef,
+    time_to_live: Duration,
+}
+
+impl ResultCache {
+    pub fn new(opts: &ResultCacheOptions, sequences: RegionSequencesRef) -> Self {
+        Self {
+            cache: Cache::builder()
+                .max_capacity(opts.capacity)
+                .time_to_live(opts.time_to_live)
+                .build(),
+            sequences,
+            time_to_live: opts.time_to_live,
+        }
+    }
+
+    /// Executes the PromQL query `stmt` by `execute`, reusing the cached steps if
+    /// possible.
+    pub(crate) async fn query<F, Fut>(
+        &self,
+        stmt: EvalStmt,
+        query_ctx: &QueryContext,
+        execute: F,
+    ) -> Result<Output>
+    where
+        F: Fn(EvalStmt) -> Fut,
+        Fut: Future<Output = Result<Output>>,
+    {
+        let Some(range) = RangeQuery::try_new(&stmt, query_ctx) else {
+            return execute(stmt).await;
+        };
+        // Reads the sequences before executing the query, so writes done during
+        // the execution invalidate the result.
+        let watermarks = match self.watermarks(&range.tables).await {
+            Ok(Some(watermarks)) => watermarks,
+            Ok(None) => return execute(stmt).await,
+            Err(e) => {
+                warn!(e; "Failed to read committed sequences of tables {:?}", range.tables);
+                return execute(stmt).await;
+            }
+        };
+        let mut computed_at = Instant::now();
+
+        let batches = match self.lookup(&range, &watermarks) {
+            Some(cached) if cached.end >= range.end => {
+                increment_counter!(METRIC_RESULT_CACHE_HIT);
+                return Ok(Output::RecordBatches(cached.slice(range.start, range.end)?));
+            }
+            Some(cached) => {
+                increment_counter!(METRIC_RESULT_CACHE_HIT);
+                let mut tail = stmt.clone();
+                tail.start = to_system_time(cached.end + range.step);
+                let tail = collect(execute(tail).await?).await?;
+                if tail.schema() == cached.batches.schema() {
+                    // The result still contains the cached steps, it expires
+                    // as early as them.
+                    computed_at = cached.computed_at;
+                    let mut batches = cached.slice(range.start, cached.end)?.take();
+                    batches.extend(tail.take());
+                    RecordBatches::try_new(cached.batches.schema(), batches)
+                        .context(CreateRecordBatchSnafu)?
+                } else {
+                    // The schema of the table has changed.
+                    collect(execute(stmt).await?).await?
+                }
+            }
+            None => {
+                increment_counter!(METRIC_RESULT_CACHE_MISS);
+                collect(execute(stmt).await?).await?
+            }
+        };
+
+        self.insert(range, watermarks, computed_at, &batches)?;
+        Ok(Output::RecordBatches(batches))
+    }
+
+    /// Returns the committed sequences of the regions of all the tables, or
+    /// `None` if the sequences of any table are unknown.
+    async fn watermarks(&self, tables: &[TableName]) -> Result<Option<Watermarks>> {
+        let mut watermarks = Vec::new();
+        for table in tables {
+            let Some(sequences) = self.sequences.committed_sequences(table).await? else {
+                return Ok(None);
+            };
+            watermarks.extend(sequences);
+        }
+        Ok(Some(watermarks))
+    }
+
+    /// Returns the cached result of the query, if no region of the queried
+    /// tables has committed writes since the result is computed.
+    fn lookup(&self, range: &RangeQuery, watermarks: &Watermarks) -> Option<Arc<CachedResult>> {
+        let cached = self.cache.get(&range.key)?;
+        let valid = cached.start <= range.start
+            && cached.end >= range.start
+            && cached.watermarks == *watermarks
+            && cached.computed_at.elapsed() < self.time_to_live;
+        valid.then_some(cached)
+    }
+
+    fn insert(
+        &self,
+        range: RangeQuery,
+        watermarks: Watermarks,
+        computed_at: Instant,
+        batches: &RecordBatches,
+    ) -> Result<()> {
+        let Some(ts_index) = timestamp_index(&batches.schema()) else {
+            return Ok(());
+        };
+
+        let batches = slice(batches, ts_index, range.start, range.end)?;
+        let cached = CachedResult {
+            start: range.start,
+            end: range.end,
+            watermarks,
+            computed_at,
+            ts_index,
+            batches,
+        };
+        self.cache.insert(range.key, Arc::new(cached));
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    catalog: String,
+    schema: String,
+    /// The parsed expression, so queries only differ in formatting share the
+    /// result.
+    expr: String,
+    step: i64,
+    lookback: i64,
+    /// Only results of the aligned steps could be reused.
+    alignment: i64,
+}
+
+#[derive(Debug)]
+struct CachedResult {
+    /// Timestamps of the first and the last cached steps.
+    start: i64,
+    end: i64,
+    /// Committed sequences of the regions when the result is computed.
+    watermarks: Watermarks,
+    /// When the earliest cached steps are computed.
+    computed_at: Instant,
+    ts_index: usize,
+    batches: RecordBatches,
+}
+
+impl CachedResult {
+    fn slice(&self, start: i64, end: i64) -> Result<RecordBatches> {
+        slice(&self.batches, self.ts_index, start, end)
+    }
+}
+
+/// A PromQL range query whose result could be cached.
+#[derive(Debug)]
+struct RangeQuery {
+    key: CacheKey,
+    tables: Vec<TableName>,
+    /// Timestamps of the first and the last steps.
+    start: i64,
+    end: i64,
+    step: i64,
+}
+
+impl RangeQuery {
+    fn try_new(stmt: &EvalStmt, query_ctx: &QueryContext) -> Option<Self> {
+        let start = to_millis(stmt.start)?;
+        let end = to_millis(stmt.end)?;
+        let step = stmt.interval.as_millis() as i64;
+        if step <= 0 || start >= end {
+            return None;
+        }
+
+        let mut tables = BTreeSet::new();
+        collect_tables(&stmt.expr, &mut tables)?;
+        let catalog = query_ctx.current_catalog();
+        let schema = query_ctx.current_schema();
+        let tables = tables
+            .into_iter()
+            .map(|table| TableName::new(&catalog, &schema, table))
+            .collect();
+
+        let key = CacheKey {
+            catalog,
+            schema,
+            expr: format!("{:?}", stmt.expr),
+            step,
+            lookback: stmt.lookback_delta.as_millis() as i64,
+            alignment: start.rem_euclid(step),
+        };
+        Some(Self {
+            key,
+            tables,
+            start,
+            end: start + (end - start) / step * step,
+            step,
+        })
+    }
+}
+
+/// Collects tables queried by the expression, returns `None` if the result of
+/// the expression can't be cached.
+fn collect_tables(expr: &Expr, tables: &mut BTreeSet<String>) -> Option<()> {
+    match expr {
+        Expr::Aggregate(AggregateExpr { expr, .. })
+        | Expr::Unary(UnaryExpr { expr })
+        | Expr::Paren(ParenExpr { expr }) => collect_tables(expr, tables),
+        Expr::Binary(BinaryExpr { lhs, rhs, .. }) => {
+            collect_tables(lhs, tables)?;
+            collect_tables(rhs, tables)
+        }
+        Expr::Call(Call { args, .. }) => args
+            .args
+            .iter()
+            .try_for_each(|arg| collect_tables(arg, tables)),
+        Expr::VectorSelector(selector)
+        | Expr::MatrixSelector(MatrixSelector {
+            vector_selector: selector,
+            ..
+        }) => {
+            let table = selector_table(selector)?;
+            let _ = tables.insert(table);
+            Some(())
+        }
+        Expr::NumberLiteral(_) | Expr::StringLiteral(_) => Some(()),
+        // Explanations and subqueries are not cached.
+        Expr::Subquery(_) | Expr::Extension(_) => None,
+    }
+}
+
+/// Returns the table of the selector. Returns `None` if the metric name isn't
+/// specified, or the selector reads data after the evaluated timestamp.
+fn selector_table(selector: &VectorSelector) -> Option<String> {
+    if selector.at.is_some() || matches!(selector.offset, Some(Offset::Neg(_))) {
+        return None;
+    }
+    selector
+        .matchers
+        .matchers
+        .iter()
+        .find(|matcher| matcher.name == METRIC_NAME && matches!(matcher.op, MatchOp::Equal))
+        .map(|matcher| matcher.value.clone())
+}
+
+/// Returns the index of the millisecond timestamp column, the same as the one
+/// the Prometheus HTTP API reads.
+fn timestamp_index(schema: &SchemaRef) -> Option<usize> {
+    schema.column_schemas().iter().position(|column| {
+        matches!(
+            column.data_type,
+            ConcreteDataType::Timestamp(TimestampType::Millisecond(_))
+        )
+    })
+}
+
+/// Returns rows whose timestamps are in range `[start, end]`.
+fn slice(batches: &RecordBatches, ts_index: usize, start: i64, end: i64) -> Result<RecordBatches> {
+    let schema = batches.schema();
+    let mut sliced = Vec::new();
+    for batch in batches.iter() {
+        let Some(ts_column) = batch
+            .column(ts_index)
+            .as_any()
+            .downcast_ref::<TimestampMillisecondVector>()
+        else {
+            continue;
+        };
+        let filter = BooleanArray::from_iter(ts_column.iter_data().map(|ts| {
+            ts.map(|ts| {
+                let ts: i64 = ts.into();
+                start <= ts && ts <= end
+            })
+        }));
+        let df_record_batch = compute::filter_record_batch(batch.df_record_batch(), &filter)
+            .context(FilterCachedResultSnafu)?;
+        if df_record_batch.num_rows() > 0 {
+            sliced.push(
+                RecordBatch::try_from_df_record_batch(schema.clone(), df_record_batch)
+                    .context(CreateRecordBatchSnafu)?,
+            );
+        }
+    }
+    RecordBatches::try_new(schema, sliced).context(CreateRecordBatchSnafu)
+}
+
+async fn collect(output: Output) -> Result<RecordBatches> {
+    match output {
+        Output::RecordBatches(batches) => Ok(batches),
+        Output::Stream(stream) => RecordBatches::try_collect(stream)
+            .await
+            .context(CollectRecordbatchSnafu),
+        Output::AffectedRows(_) => Ok(RecordBatches::empty()),
+    }
+}
+
+fn to_millis(time: SystemTime) -> Option<i64> {
+    time.duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_millis() as i64)
+}
+
+fn to_system_time(millis: i64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_millis(millis as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Mutex as StdMutex;
+
+    use datatypes::schema::{ColumnSchema, Schema};
+    use datatypes::vectors::Float64Vector;
+
+    use super::*;
+
+    fn new_stmt(query: &str, start: u64, end: u64) -> EvalStmt {
+        EvalStmt {
+            expr: promql_parser::parser::parse(query).unwrap(),
+            start: UNIX_EPOCH + Duration::from_secs(start),
+            end: UNIX_EPOCH + Duration::from_secs(end),
+            interval: Duration::from_secs(10),
+            lookback_delta: Duration::from_secs(300),
+        }
+    }
+
+    /// Returns the value of each step, which is the timestamp in seconds.
+    fn evaluate(stmt: &EvalStmt) -> RecordBatches {
+        let schema = Arc::new(Schema::new(vec![
+            ColumnSchema::new(
+                "ts",
+                ConcreteDataType::timestamp_millisecond_datatype(),
+                false,
+            ),
+            ColumnSchema::new("value", ConcreteDataType::float64_datatype(), true),
+        ]));
+        let start = to_millis(stmt.start).unwrap();
+        let end = to_millis(stmt.end).unwrap();
+        let ts = (start..=end).step_by(10_000).collect::<Vec<_>>();
+        let values = ts.iter().map(|ts| *ts as f64 / 1000.0).collect();
+        let batch = RecordBatch::new(
+            schema.clone(),
+            vec![
+                Arc::new(TimestampMillisecondVector::from_vec(ts)) as _,
+                Arc::new(Float64Vector::from_vec(values)) as _,
+            ],
+        )
+        .unwrap();
+        RecordBatches::try_new(schema, vec![batch]).unwrap()
+    }
+
+    fn values(output: Output) -> Vec<f64> {
+        let Output::RecordBatches(batches) = output else {
+            unreachable!()
+        };
+        batches
+            .iter()
+            .flat_map(|batch| {
+                let values = batch
+                    .column(1)
+                    .as_any()
+                    .downcast_ref::<Float64Vector>()
+                    .unwrap();
+                values.iter_data().flatten().collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Committed sequences of the tables, each table has one region.
+    #[derive(Default)]
+    struct MockRegionSequences {
+        tables: StdMutex<HashMap<String, u64>>,
+    }
+
+    impl MockRegionSequences {
+        fn write(&self, table: &str) {
+            *self
+                .tables
+                .lock()
+                .unwrap()
+                .entry(table.to_string())
+                .or_default() += 1;
+        }
+    }
+
+    #[async_trait]
+    impl RegionSequences for MockRegionSequences {
+        async fn committed_sequences(&self, table: &TableName) -> Result<Option<Watermarks>> {
+            let tables = self.tables.lock().unwrap();
+            Ok(tables
+                .get(&table.table_name)
+                .map(|sequence| vec![(0, *sequence)]))
+        }
+    }
+
+    #[test]
+    fn test_range_query() {
+        let query_ctx = QueryContext::with("greptime", "public");
+
+        let stmt = new_stmt("rate(cpu[5m]) + mem offset 5m", 5, 100);
+        let range = RangeQuery::try_new(&stmt, &query_ctx).unwrap();
+        assert_eq!(5000, range.start);
+        assert_eq!(95000, range.end);
+        assert_eq!(5000, range.key.alignment);
+        assert_eq!(
+            vec![
+                TableName::new("greptime", "public", "cpu"),
+                TableName::new("greptime", "public", "mem")
+            ],
+            range.tables
+        );
+
+        // Queries only differ in formatting share the key.
+        let key = |query| {
+            let stmt = new_stmt(query, 0, 100);
+            RangeQuery::try_new(&stmt, &query_ctx).unwrap().key
+        };
+        assert_eq!(
+            key("sum by (host) (rate(cpu[5m]))"),
+            key(" sum by(host)\n  (rate(cpu[5m])) ")
+        );
+        assert_ne!(
+            key("sum by (host) (rate(cpu[5m]))"),
+            key("sum by (host) (rate(cpu[1m]))")
+        );
+
+        // Instant queries, negative offsets, subqueries and unknown metrics are not cached.
+        for (query, start, end) in [
+            ("cpu", 100, 100),
+            ("cpu offset -5m", 0, 100),
+            ("max_over_time(cpu[5m:1m])", 0, 100),
+            ("{__name__=~\"cpu.*\"}", 0, 100),
+        ] {
+            let stmt = new_stmt(query, start, end);
+            assert!(RangeQuery::try_new(&stmt, &query_ctx).is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_query_result_cache() {
+        let sequences = Arc::new(MockRegionSequences::default());
+        sequences.write("cpu");
+        sequences.write("mem");
+        let cache = ResultCache::new(
+            &ResultCacheOptions {
+                enable: true,
+                capacity: 16,
+                time_to_live: Duration::from_secs(3600),
+            },
+            sequences.clone(),
+        );
+        let query_ctx = QueryContext::with("greptime", "public");
+        let executed = StdMutex::new(Vec::new());
+        let execute = |stmt: EvalStmt| {
+            executed
+                .lock()
+                .unwrap()
+                .push(to_millis(stmt.start).unwrap() / 1000);
+            let output = Output::RecordBatches(evaluate(&stmt));
+            async move { Ok(output) }
+        };
+        let query = |query, start, end| {
+            let stmt = new_stmt(query, start, end);
+            cache.query(stmt, &query_ctx, execute)
+        };
+
+        let expected = |start: u64, end: u64| {
+            (start..=end)
+                .step_by(10)
+                .map(|ts| ts as f64)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            expected(0, 100),
+            values(query("cpu", 0, 100).await.unwrap())
+        );
+        assert_eq!(vec![0], *executed.lock().unwrap());
+
+        // All steps are cached.
+        assert_eq!(
+            expected(0, 100),
+            values(query(" cpu ", 0, 100).await.unwrap())
+        );
+        assert_eq!(vec![0], *executed.lock().unwrap());
+
+        // Only new steps are computed.
+        assert_eq!(
+            expected(20, 130),
+            values(query("cpu", 20, 130).await.unwrap())
+        );
+        assert_eq!(vec![0, 110], *executed.lock().unwrap());
+
+        // Results of the written table are computed again.
+        sequences.write("cpu");
+        assert_eq!(
+            expected(20, 130),
+            values(query("cpu", 20, 135).await.unwrap())
+        );
+        assert_eq!(vec![0, 110, 20], *executed.lock().unwrap());
+
+        // Results of other tables are not affected.
+        sequences.write("mem");
+        assert_eq!(
+            expected(30, 130),
+            values(query("cpu", 30, 130).await.unwrap())
+        );
+        assert_eq!(vec![0, 110, 20], *executed.lock().unwrap());
+
+        // Steps are not aligned.
+        assert_eq!(
+            expected(35, 95),
+            values(query("cpu", 35, 95).await.unwrap())
+        );
+        assert_eq!(vec![0, 110, 20, 35], *executed.lock().unwrap());
+
+        // Results of tables whose sequences are unknown are not cached.
+        for _ in 0..2 {
+            let _ = query("disk", 0, 100).await.unwrap();
+        }
+        assert_eq!(vec![0, 110, 20, 35, 0, 0], *executed.lock().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_result_cache_expired() {
+        let sequences = Arc::new(MockRegionSequences::default());
+        sequences.write("cpu");
+        let cache = ResultCache::new(
+            &ResultCacheOptions {
+                enable: true,
+                capacity: 16,
+                time_to_live: Duration::ZERO,
+            },
+            sequences,
+        );
+        let query_ctx = QueryContext::with("greptime", "public");
+        let executed = StdMutex::new(Vec::new());
+        let execute = |stmt: EvalStmt| {
+            executed
+                .lock()
+                .unwrap()
+                .push(to_millis(stmt.start).unwrap() / 1000);
+            let output = Output::RecordBatches(evaluate(&stmt));
+            async move { Ok(output) }
+        };
+
+        // Expired results are computed again, though no write is seen.
+        for _ in 0..2 {
+            let stmt = new_stmt("cpu", 0, 100);
+            let _ = cache.query(stmt, &query_ctx, execute).await.unwrap();
+        }
+        assert_eq!(vec![0, 0], *executed.lock().unwrap());
+    }
+}
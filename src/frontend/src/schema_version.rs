@@ -0,0 +1,53 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use sql::statements::statement::Statement;
+
+pub type SchemaVersionRef = Arc<SchemaVersion>;
+
+/// Version of the table schemas known by the frontend.
+///
+/// It increases after every statement that may change table schemas, and after the
+/// table caches are invalidated by DDL from other frontends, so plans cached under an
+/// older version can be told stale.
+#[derive(Debug, Default)]
+pub struct SchemaVersion(AtomicU64);
+
+impl SchemaVersion {
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Acquire)
+    }
+
+    pub fn increase(&self) {
+        let _ = self.0.fetch_add(1, Ordering::AcqRel);
+    }
+}
+
+/// Returns true if the statement may change the schema of tables or the functions
+/// a plan may refer to.
+pub(crate) fn is_schema_changing(stmt: &Statement) -> bool {
+    matches!(
+        stmt,
+        Statement::CreateTable(_)
+            | Statement::CreateExternalTable(_)
+            | Statement::DropTable(_)
+            | Statement::Alter(_)
+            | Statement::TruncateTable(_)
+            | Statement::CreateFunction(_)
+            | Statement::DropFunction(_)
+    )
+}
@@ -15,10 +15,23 @@
 use std::collections::HashMap;
 
 use catalog::CatalogManagerRef;
-use common_query::Output;
+use common_meta::kv_backend::KvBackendRef;
+use common_query::{Output, ScriptSchedule};
 use query::QueryEngineRef;
 
 use crate::error::Result;
+use crate::instance::Instance;
+
+#[cfg(feature = "python")]
+mod lease;
+
+/// Elects the frontend running scheduled scripts in distributed mode, by a
+/// lease in the kv backend of meta-srv.
+#[cfg_attr(not(feature = "python"), allow(dead_code))]
+pub(crate) struct ScriptRunnerElection {
+    pub(crate) kv_backend: KvBackendRef,
+    pub(crate) node_id: Option<u64>,
+}
 
 #[cfg(not(feature = "python"))]
 mod dummy {
@@ -30,15 +43,21 @@ mod dummy {
         pub async fn new(
             _catalog_manager: CatalogManagerRef,
             _query_engine: QueryEngineRef,
+            _election: Option<ScriptRunnerElection>,
         ) -> Result<Self> {
             Ok(Self {})
         }
 
+        pub fn start_scheduler(&self, _instance: Instance) -> Result<()> {
+            Ok(())
+        }
+
         pub async fn insert_script(
             &self,
             _schema: &str,
             _name: &str,
             _script: &str,
+            _schedule: ScriptSchedule,
         ) -> servers::error::Result<()> {
             servers::error::NotSupportedSnafu { feat: "script" }.fail()
         }
@@ -56,38 +75,111 @@ mod dummy {
 
 #[cfg(feature = "python")]
 mod python {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use api::v1::ddl_request::Expr as DdlExpr;
+    use api::v1::greptime_request::Request;
+    use api::v1::{CreateDatabaseExpr, DdlRequest, InsertRequest, InsertRequests};
+    use async_trait::async_trait;
+    use common_catalog::consts::{DEFAULT_CATALOG_NAME, PRIVATE_SCHEMA_NAME};
     use common_error::ext::BoxedError;
-    use common_telemetry::logging::error;
+    use common_grpc::writer::{LinesWriter, Precision};
+    use common_telemetry::logging::{error, warn};
     use script::manager::ScriptManager;
+    use script::scheduler::{
+        RunnerElectionRef, SchedulerConfig, ScriptRun, ScriptRunRecorder, ScriptScheduler,
+    };
+    use servers::query_handler::grpc::GrpcQueryHandler;
+    use session::context::QueryContext;
     use snafu::ResultExt;
+    use uuid::Uuid;
 
+    use super::lease::ScriptRunnerLease;
     use super::*;
 
+    /// Table in `greptime_private` keeping the history of scheduled script runs.
+    pub const SCRIPT_RUNS_TABLE_NAME: &str = "script_runs";
+
     pub struct ScriptExecutor {
-        script_manager: ScriptManager,
+        catalog_manager: CatalogManagerRef,
+        query_engine: QueryEngineRef,
+        script_manager: Arc<ScriptManager>,
+        /// Lease to run scheduled scripts, only one frontend holds it in
+        /// distributed mode. The only frontend in standalone mode always runs
+        /// them.
+        lease: Option<Arc<ScriptRunnerLease>>,
+        scheduler: Mutex<Option<ScriptScheduler>>,
     }
 
     impl ScriptExecutor {
         pub async fn new(
             catalog_manager: CatalogManagerRef,
             query_engine: QueryEngineRef,
+            election: Option<ScriptRunnerElection>,
         ) -> Result<Self> {
-            Ok(Self {
-                script_manager: ScriptManager::new(catalog_manager, query_engine)
+            let script_manager = Arc::new(
+                ScriptManager::new(catalog_manager.clone(), query_engine.clone())
                     .await
                     .context(crate::error::StartScriptManagerSnafu)?,
+            );
+            let lease = election.map(|election| {
+                // Frontends without node id are told apart by a random holder.
+                let holder = election
+                    .node_id
+                    .map(|node_id| node_id.to_string())
+                    .unwrap_or_else(|| Uuid::new_v4().to_string());
+                Arc::new(ScriptRunnerLease::new(election.kv_backend, holder))
+            });
+            Ok(Self {
+                catalog_manager,
+                query_engine,
+                script_manager,
+                lease,
+                scheduler: Mutex::new(None),
             })
         }
 
+        /// Starts running scheduled scripts, their runs are written through the
+        /// `instance`. Does nothing if the scheduler is started.
+        pub fn start_scheduler(&self, instance: Instance) -> Result<()> {
+            let mut scheduler = self.scheduler.lock().unwrap();
+            if scheduler.is_some() {
+                return Ok(());
+            }
+            if let Some(lease) = &self.lease {
+                lease.start()?;
+            }
+
+            let recorder = Arc::new(ScriptRunWriter {
+                instance,
+                schema_created: AtomicBool::new(false),
+            });
+            let new_scheduler = ScriptScheduler::new(
+                self.script_manager.clone(),
+                self.catalog_manager.clone(),
+                self.query_engine.clone(),
+                recorder,
+                self.lease.clone().map(|lease| lease as RunnerElectionRef),
+                SchedulerConfig::default(),
+            );
+            new_scheduler
+                .start()
+                .context(crate::error::StartScriptManagerSnafu)?;
+            *scheduler = Some(new_scheduler);
+            Ok(())
+        }
+
         pub async fn insert_script(
             &self,
             schema: &str,
             name: &str,
             script: &str,
+            schedule: ScriptSchedule,
         ) -> servers::error::Result<()> {
             let _s = self
                 .script_manager
-                .insert_and_compile(schema, name, script)
+                .insert_and_compile(schema, name, script, schedule)
                 .await
                 .map_err(|e| {
                     error!(e; "Instance failed to insert script");
@@ -114,6 +206,77 @@ mod python {
                 .context(servers::error::ExecuteScriptSnafu { name })
         }
     }
+
+    /// Writes script runs into the `greptime_private.script_runs` table.
+    struct ScriptRunWriter {
+        instance: Instance,
+        schema_created: AtomicBool,
+    }
+
+    #[async_trait]
+    impl ScriptRunRecorder for ScriptRunWriter {
+        async fn record(&self, run: ScriptRun) {
+            let mut writer = LinesWriter::with_lines(1);
+            if let Err(e) = write_script_run(&mut writer, &run) {
+                warn!(e; "Failed to write script run: {:?}", run);
+                return;
+            }
+            if let Err(e) = self.insert(writer).await {
+                warn!(e; "Failed to record run of script {}.{}", run.schema, run.name);
+            }
+        }
+    }
+
+    impl ScriptRunWriter {
+        async fn insert(&self, writer: LinesWriter) -> Result<()> {
+            let query_ctx = Arc::new(QueryContext::with(
+                DEFAULT_CATALOG_NAME,
+                PRIVATE_SCHEMA_NAME,
+            ));
+            if !self.schema_created.load(Ordering::Relaxed) {
+                let request = Request::Ddl(DdlRequest {
+                    expr: Some(DdlExpr::CreateDatabase(CreateDatabaseExpr {
+                        database_name: PRIVATE_SCHEMA_NAME.to_string(),
+                        create_if_not_exists: true,
+                    })),
+                });
+                let _ =
+                    GrpcQueryHandler::do_query(&self.instance, request, query_ctx.clone()).await?;
+                self.schema_created.store(true, Ordering::Relaxed);
+            }
+
+            let (columns, row_count) = writer.finish();
+            let requests = InsertRequests {
+                inserts: vec![InsertRequest {
+                    table_name: SCRIPT_RUNS_TABLE_NAME.to_string(),
+                    columns,
+                    row_count,
+                    region_number: 0,
+                }],
+            };
+            let _ = self.instance.handle_inserts(requests, query_ctx).await?;
+            Ok(())
+        }
+    }
+
+    fn write_script_run(
+        writer: &mut LinesWriter,
+        run: &ScriptRun,
+    ) -> common_grpc::error::Result<()> {
+        writer.write_ts("ts", (run.start_time, Precision::Millisecond))?;
+        writer.write_tag("schema", &run.schema)?;
+        writer.write_tag("name", &run.name)?;
+        writer.write_string("trigger", &run.trigger)?;
+        writer.write_i64("elapsed_ms", run.elapsed_ms)?;
+        if let Some(rows) = run.rows {
+            writer.write_i64("rows", rows)?;
+        }
+        if let Some(error) = &run.error {
+            writer.write_string("error", error)?;
+        }
+        writer.commit();
+        Ok(())
+    }
 }
 
 #[cfg(not(feature = "python"))]
@@ -0,0 +1,225 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Lease in the kv backend of meta-srv electing the frontend that runs
+//! scheduled scripts.
+//!
+//! The lease is a `{holder, expire_at}` value updated by compare-and-put. The
+//! holder renews it periodically, and other frontends take it over once it
+//! expires, so frontends are expected to have roughly synchronized clocks. A
+//! holder stops running scripts as soon as its lease expires, even if it fails
+//! to reach meta-srv.
+
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use common_meta::kv_backend::KvBackendRef;
+use common_meta::rpc::store::CompareAndPutRequest;
+use common_runtime::{RepeatedTask, TaskFunction};
+use common_telemetry::logging;
+use common_time::util::current_time_millis;
+use script::scheduler::RunnerElection;
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+
+use crate::error::{
+    EncodeJsonSnafu, Error, RenewScriptRunnerLeaseSnafu, Result, StartScriptRunnerLeaseSnafu,
+};
+
+const SCRIPT_RUNNER_LEASE_KEY: &str = "/__script_runner_lease__";
+
+/// Time a lease lasts without renewal.
+const LEASE_TTL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct LeaseValue {
+    holder: String,
+    /// Expire time of the lease in milliseconds.
+    expire_at: i64,
+}
+
+/// Lease to run scheduled scripts, renewed in the background until dropped.
+pub(crate) struct ScriptRunnerLease {
+    inner: Arc<LeaseInner>,
+    task: RepeatedTask<Error>,
+}
+
+impl ScriptRunnerLease {
+    pub(crate) fn new(kv_backend: KvBackendRef, holder: String) -> Self {
+        let inner = Arc::new(LeaseInner::new(kv_backend, holder, LEASE_TTL));
+        let task = RepeatedTask::new(
+            LEASE_TTL / 3,
+            Box::new(RenewLeaseFunction {
+                inner: inner.clone(),
+            }),
+        );
+        Self { inner, task }
+    }
+
+    pub(crate) fn start(&self) -> Result<()> {
+        self.task
+            .start(common_runtime::bg_runtime())
+            .context(StartScriptRunnerLeaseSnafu)
+    }
+}
+
+impl RunnerElection for ScriptRunnerLease {
+    fn is_runner(&self) -> bool {
+        self.inner.is_held()
+    }
+}
+
+struct LeaseInner {
+    kv_backend: KvBackendRef,
+    holder: String,
+    ttl: Duration,
+    /// Expire time in milliseconds of the lease held by this frontend, 0 if
+    /// the lease is held by others.
+    expire_at: AtomicI64,
+}
+
+impl LeaseInner {
+    fn new(kv_backend: KvBackendRef, holder: String, ttl: Duration) -> Self {
+        Self {
+            kv_backend,
+            holder,
+            ttl,
+            expire_at: AtomicI64::new(0),
+        }
+    }
+
+    fn is_held(&self) -> bool {
+        self.expire_at.load(Ordering::Relaxed) > current_time_millis()
+    }
+
+    /// Acquires or renews the lease, returns whether this frontend holds it.
+    async fn renew(&self) -> Result<bool> {
+        let now = current_time_millis();
+        let current = self
+            .kv_backend
+            .get(SCRIPT_RUNNER_LEASE_KEY.as_bytes())
+            .await
+            .context(RenewScriptRunnerLeaseSnafu)?;
+        // An empty expected value only puts the lease if it's absent.
+        let expect = match current {
+            Some(kv) => {
+                // A lease that can't be decoded is taken over.
+                let held_by_others = serde_json::from_slice::<LeaseValue>(&kv.value)
+                    .map(|lease| lease.holder != self.holder && lease.expire_at > now)
+                    .unwrap_or(false);
+                if held_by_others {
+                    self.expire_at.store(0, Ordering::Relaxed);
+                    return Ok(false);
+                }
+                kv.value
+            }
+            None => Vec::new(),
+        };
+
+        let expire_at = now + self.ttl.as_millis() as i64;
+        let value = serde_json::to_vec(&LeaseValue {
+            holder: self.holder.clone(),
+            expire_at,
+        })
+        .context(EncodeJsonSnafu)?;
+        let resp = self
+            .kv_backend
+            .compare_and_put(
+                CompareAndPutRequest::new()
+                    .with_key(SCRIPT_RUNNER_LEASE_KEY)
+                    .with_expect(expect)
+                    .with_value(value),
+            )
+            .await
+            .context(RenewScriptRunnerLeaseSnafu)?;
+
+        let held = resp.is_success();
+        self.expire_at
+            .store(if held { expire_at } else { 0 }, Ordering::Relaxed);
+        Ok(held)
+    }
+}
+
+struct RenewLeaseFunction {
+    inner: Arc<LeaseInner>,
+}
+
+#[async_trait]
+impl TaskFunction<Error> for RenewLeaseFunction {
+    async fn call(&mut self) -> Result<()> {
+        let was_held = self.inner.is_held();
+        let held = self.inner.renew().await?;
+        if held != was_held {
+            logging::info!(
+                "Frontend {} {} the script runner lease",
+                self.inner.holder,
+                if held { "acquires" } else { "loses" }
+            );
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "ScriptRunnerLease"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_meta::error::Error as MetaError;
+    use common_meta::kv_backend::memory::MemoryKvBackend;
+    use common_meta::rpc::store::PutRequest;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_renew_lease() {
+        let kv_backend: KvBackendRef = Arc::new(MemoryKvBackend::<MetaError>::default());
+        let ttl = Duration::from_millis(200);
+        let first = LeaseInner::new(kv_backend.clone(), "1".to_string(), ttl);
+        let second = LeaseInner::new(kv_backend.clone(), "2".to_string(), ttl);
+        assert!(!first.is_held());
+
+        assert!(first.renew().await.unwrap());
+        assert!(first.is_held());
+        assert!(!second.renew().await.unwrap());
+        assert!(!second.is_held());
+
+        // The holder renews its own lease.
+        assert!(first.renew().await.unwrap());
+        assert!(!second.renew().await.unwrap());
+
+        // The expired lease is taken over.
+        tokio::time::sleep(ttl * 2).await;
+        assert!(!first.is_held());
+        assert!(second.renew().await.unwrap());
+        assert!(second.is_held());
+        assert!(!first.renew().await.unwrap());
+        assert!(!first.is_held());
+
+        // A corrupted lease is taken over.
+        let _ = kv_backend
+            .put(
+                PutRequest::new()
+                    .with_key(SCRIPT_RUNNER_LEASE_KEY)
+                    .with_value(b"corrupted".to_vec()),
+            )
+            .await
+            .unwrap();
+        assert!(first.renew().await.unwrap());
+        assert!(!second.renew().await.unwrap());
+    }
+}
@@ -38,7 +38,9 @@ use crate::error::Error::StartServer;
 use crate::error::{self, Result};
 use crate::frontend::FrontendOptions;
 use crate::instance::FrontendInstance;
-use crate::service_config::{InfluxdbOptions, PromStoreOptions};
+use crate::service_config::{
+    ElasticsearchOptions, InfluxdbOptions, LokiOptions, OtlpOptions, PromStoreOptions,
+};
 
 pub(crate) struct Services;
 
@@ -57,6 +59,7 @@ impl Services {
     {
         let mut result = Vec::<ServerHandler>::with_capacity(plugins.len());
         let user_provider = plugins.get::<UserProviderRef>();
+        let enable_otlp = matches!(opts.otlp_options, Some(OtlpOptions { enable: true }));
 
         if let Some(opts) = &opts.grpc_options {
             let grpc_addr = parse_addr(&opts.addr)?;
@@ -72,6 +75,7 @@ impl Services {
             let grpc_server = GrpcServer::new(
                 ServerGrpcQueryHandlerAdaptor::arc(instance.clone()),
                 Some(instance.clone()),
+                enable_otlp.then(|| instance.clone() as _),
                 user_provider.clone(),
                 grpc_runtime,
             );
@@ -178,6 +182,21 @@ impl Services {
                 let _ = http_server_builder.with_prom_handler(instance.clone());
             }
 
+            if enable_otlp {
+                let _ = http_server_builder.with_otlp_handler(instance.clone());
+            }
+
+            if matches!(opts.loki_options, Some(LokiOptions { enable: true })) {
+                let _ = http_server_builder.with_loki_handler(instance.clone());
+            }
+
+            if matches!(
+                opts.elasticsearch_options,
+                Some(ElasticsearchOptions { enable: true })
+            ) {
+                let _ = http_server_builder.with_elasticsearch_handler(instance.clone());
+            }
+
             let http_server = http_server_builder
                 .with_metrics_handler(MetricsHandler)
                 .with_script_handler(instance.clone())
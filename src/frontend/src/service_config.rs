@@ -12,18 +12,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod elasticsearch;
 pub mod grpc;
 pub mod influxdb;
+pub mod loki;
 pub mod mysql;
 pub mod opentsdb;
+pub mod otlp;
 pub mod postgres;
 pub mod prom_store;
 pub mod prometheus;
 
+pub use elasticsearch::ElasticsearchOptions;
 pub use grpc::GrpcOptions;
 pub use influxdb::InfluxdbOptions;
+pub use loki::LokiOptions;
 pub use mysql::MysqlOptions;
 pub use opentsdb::OpentsdbOptions;
+pub use otlp::OtlpOptions;
 pub use postgres::PostgresOptions;
 pub use prom_store::PromStoreOptions;
 pub use prometheus::PrometheusOptions;
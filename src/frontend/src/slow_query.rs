@@ -0,0 +1,590 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Records slow queries into the `greptime_private.slow_queries` table of the
+//! catalog where they are executed.
+//!
+//! The query text is recorded as is and may contain credentials, e.g. of
+//! external tables. Tables of `greptime_private` are only accessible from
+//! sessions using the schema, which the user provider has to authorize.
+
+use std::collections::{HashMap, HashSet};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use api::v1::ddl_request::Expr as DdlExpr;
+use api::v1::greptime_request::Request;
+use api::v1::{CreateDatabaseExpr, DdlRequest, InsertRequest, InsertRequests};
+use common_catalog::consts::PRIVATE_SCHEMA_NAME;
+use common_error::ext::ErrorExt;
+use common_error::status_code::StatusCode;
+use common_grpc::writer::{LinesWriter, Precision};
+use common_query::Output;
+use common_recordbatch::error::Result as RecordBatchResult;
+use common_recordbatch::{
+    OrderOption, RecordBatch, RecordBatchMetrics, RecordBatchStream, SendableRecordBatchStream,
+};
+use common_telemetry::{debug, warn};
+use common_time::util::current_time_millis;
+use datatypes::schema::SchemaRef;
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use servers::query_handler::grpc::GrpcQueryHandler;
+use session::context::{Channel, QueryContext, QueryContextRef};
+use tokio::sync::mpsc;
+
+use crate::instance::Instance;
+
+pub const SLOW_QUERY_TABLE_NAME: &str = "slow_queries";
+
+/// Slow queries waiting to be written, new ones are dropped once it's full.
+const SLOW_QUERY_QUEUE_SIZE: usize = 1024;
+/// Max number of slow queries written in one insertion.
+const MAX_WRITE_BATCH_SIZE: usize = 128;
+/// Length of the plan kept in the summary.
+const MAX_PLAN_LEN: usize = 4096;
+
+const TS_COLUMN: &str = "ts";
+const QUERY_COLUMN: &str = "query";
+const USER_COLUMN: &str = "user";
+const CHANNEL_COLUMN: &str = "channel";
+const ELAPSED_COLUMN: &str = "elapsed_ms";
+const ROWS_COLUMN: &str = "rows";
+const PLAN_COLUMN: &str = "plan";
+const STATUS_COLUMN: &str = "status";
+const ERROR_COLUMN: &str = "error";
+
+/// How a slow query ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QueryStatus {
+    Success,
+    /// The query or its output stream fails.
+    Failed,
+    /// The query or its output stream is dropped before it finishes, e.g. the
+    /// client is gone.
+    Cancelled,
+}
+
+impl QueryStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            QueryStatus::Success => "success",
+            QueryStatus::Failed => "failed",
+            QueryStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+/// Options of the slow query log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SlowQueryOptions {
+    pub enable: bool,
+    /// Queries taking longer than the threshold are recorded.
+    #[serde(with = "humantime_serde")]
+    pub threshold: Duration,
+    /// Ratio of slow queries to record, in range `[0, 1]`.
+    pub sample_ratio: f64,
+}
+
+impl Default for SlowQueryOptions {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            threshold: Duration::from_secs(5),
+            sample_ratio: 1.0,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct SlowQuery {
+    catalog: String,
+    query: String,
+    user: Option<String>,
+    channel: Channel,
+    elapsed: Duration,
+    rows: usize,
+    plan: Option<String>,
+    status: QueryStatus,
+    /// Status code of the error if the query fails. The error message isn't
+    /// kept as it may quote the query.
+    error: Option<StatusCode>,
+}
+
+pub type SlowQueryRecorderRef = Arc<SlowQueryRecorder>;
+
+/// Picks out slow queries and sends them to a background task, which writes
+/// them into the slow query table.
+pub struct SlowQueryRecorder {
+    threshold: Duration,
+    sample_ratio: f64,
+    sender: mpsc::Sender<SlowQuery>,
+}
+
+impl SlowQueryRecorder {
+    /// Creates the recorder and starts the background task writing slow queries
+    /// through the `instance`.
+    pub(crate) fn start(opts: &SlowQueryOptions, instance: Instance) -> Self {
+        let (sender, receiver) = mpsc::channel(SLOW_QUERY_QUEUE_SIZE);
+        let _handle = common_runtime::spawn_bg(write_slow_queries(instance, receiver));
+
+        Self {
+            threshold: opts.threshold,
+            sample_ratio: opts.sample_ratio,
+            sender,
+        }
+    }
+
+    /// Starts tracking the query. The query is recorded once the returned guard
+    /// finishes watching its output, or once the guard is dropped, so queries
+    /// that fail or are cancelled are recorded as well.
+    pub(crate) fn track(
+        self: &Arc<Self>,
+        query: &str,
+        query_ctx: &QueryContextRef,
+    ) -> SlowQueryGuard {
+        SlowQueryGuard {
+            recorder: self.clone(),
+            start: Instant::now(),
+            slow_query: Some(SlowQuery {
+                catalog: query_ctx.current_catalog(),
+                query: query.to_string(),
+                user: query_ctx.current_user(),
+                channel: query_ctx.channel(),
+                elapsed: Duration::ZERO,
+                rows: 0,
+                plan: None,
+                status: QueryStatus::Cancelled,
+                error: None,
+            }),
+        }
+    }
+
+    fn should_record(&self, elapsed: Duration) -> bool {
+        elapsed >= self.threshold
+            && (self.sample_ratio >= 1.0 || rand::random::<f64>() < self.sample_ratio)
+    }
+}
+
+/// Records the tracked query at most once, when it finishes or is dropped.
+pub(crate) struct SlowQueryGuard {
+    recorder: SlowQueryRecorderRef,
+    start: Instant,
+    slow_query: Option<SlowQuery>,
+}
+
+impl SlowQueryGuard {
+    /// Watches the output of the query. A stream output is recorded once it's
+    /// exhausted, fails or is dropped, so the time and rows of consuming it are
+    /// counted in.
+    pub(crate) fn watch(mut self, output: Output) -> Output {
+        match output {
+            Output::Stream(stream) => Output::Stream(Box::pin(SlowQueryStream {
+                stream,
+                guard: self,
+            })),
+            Output::RecordBatches(batches) => {
+                self.add_rows(batches.iter().map(|batch| batch.num_rows()).sum());
+                self.set_status(QueryStatus::Success, None);
+                self.finish(None);
+                Output::RecordBatches(batches)
+            }
+            Output::AffectedRows(rows) => {
+                self.add_rows(rows);
+                self.set_status(QueryStatus::Success, None);
+                self.finish(None);
+                Output::AffectedRows(rows)
+            }
+        }
+    }
+
+    /// Records the query as failed with the `error`.
+    pub(crate) fn fail(mut self, error: &impl ErrorExt) {
+        self.set_status(QueryStatus::Failed, Some(error.status_code()));
+        self.finish(None);
+    }
+
+    fn set_status(&mut self, status: QueryStatus, error: Option<StatusCode>) {
+        if let Some(slow_query) = &mut self.slow_query {
+            slow_query.status = status;
+            slow_query.error = error;
+        }
+    }
+
+    fn add_rows(&mut self, rows: usize) {
+        if let Some(slow_query) = &mut self.slow_query {
+            slow_query.rows += rows;
+        }
+    }
+
+    /// Sends the query to the background writer if it's slow. The plan is taken
+    /// from the metrics of the output `stream` if there is one.
+    fn finish(&mut self, stream: Option<&SendableRecordBatchStream>) {
+        let Some(mut slow_query) = self.slow_query.take() else { return };
+        let elapsed = self.start.elapsed();
+        if !self.recorder.should_record(elapsed) {
+            return;
+        }
+        slow_query.elapsed = elapsed;
+        slow_query.plan = stream
+            .and_then(|stream| stream.metrics())
+            .and_then(|metrics| metrics.plan)
+            .map(|plan| plan.chars().take(MAX_PLAN_LEN).collect());
+        if let Err(e) = self.recorder.sender.try_send(slow_query) {
+            debug!("Failed to record slow query: {e}");
+        }
+    }
+}
+
+impl Drop for SlowQueryGuard {
+    fn drop(&mut self) {
+        self.finish(None);
+    }
+}
+
+/// Counts rows of the query output and records the query once it's finished.
+struct SlowQueryStream {
+    stream: SendableRecordBatchStream,
+    guard: SlowQueryGuard,
+}
+
+impl RecordBatchStream for SlowQueryStream {
+    fn schema(&self) -> SchemaRef {
+        self.stream.schema()
+    }
+
+    fn output_ordering(&self) -> Option<&[OrderOption]> {
+        self.stream.output_ordering()
+    }
+
+    fn metrics(&self) -> Option<RecordBatchMetrics> {
+        self.stream.metrics()
+    }
+}
+
+impl Stream for SlowQueryStream {
+    type Item = RecordBatchResult<RecordBatch>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let poll = this.stream.as_mut().poll_next(cx);
+        match &poll {
+            Poll::Ready(Some(Ok(batch))) => this.guard.add_rows(batch.num_rows()),
+            Poll::Ready(Some(Err(e))) => {
+                this.guard
+                    .set_status(QueryStatus::Failed, Some(e.status_code()));
+                this.guard.finish(Some(&this.stream));
+            }
+            Poll::Ready(None) => {
+                this.guard.set_status(QueryStatus::Success, None);
+                this.guard.finish(Some(&this.stream));
+            }
+            Poll::Pending => {}
+        }
+        poll
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+impl Drop for SlowQueryStream {
+    fn drop(&mut self) {
+        // The stream is dropped before it's exhausted, e.g. the client is gone.
+        self.guard.finish(Some(&self.stream));
+    }
+}
+
+async fn write_slow_queries(instance: Instance, mut receiver: mpsc::Receiver<SlowQuery>) {
+    let mut writer = SlowQueryWriter {
+        instance,
+        created_catalogs: HashSet::new(),
+        last_ts: 0,
+    };
+
+    while let Some(slow_query) = receiver.recv().await {
+        let mut slow_queries = vec![slow_query];
+        while slow_queries.len() < MAX_WRITE_BATCH_SIZE {
+            match receiver.try_recv() {
+                Ok(slow_query) => slow_queries.push(slow_query),
+                Err(_) => break,
+            }
+        }
+        writer.write(slow_queries).await;
+    }
+}
+
+struct SlowQueryWriter {
+    instance: Instance,
+    /// Catalogs where the private schema has been created.
+    created_catalogs: HashSet<String>,
+    /// Timestamp of the last written slow query. Timestamps are made distinct so
+    /// that slow queries finished in the same millisecond don't overwrite each other.
+    last_ts: i64,
+}
+
+impl SlowQueryWriter {
+    async fn write(&mut self, slow_queries: Vec<SlowQuery>) {
+        let mut writers: HashMap<String, LinesWriter> = HashMap::new();
+        for slow_query in slow_queries {
+            let ts = current_time_millis().max(self.last_ts + 1);
+            self.last_ts = ts;
+
+            let writer = writers
+                .entry(slow_query.catalog.clone())
+                .or_insert_with(|| LinesWriter::with_lines(MAX_WRITE_BATCH_SIZE));
+            if let Err(e) = write_slow_query(writer, ts, &slow_query) {
+                warn!(e; "Failed to write slow query: {:?}", slow_query);
+            }
+        }
+
+        for (catalog, writer) in writers {
+            let query_ctx = Arc::new(QueryContext::with(&catalog, PRIVATE_SCHEMA_NAME));
+            if !self.created_catalogs.contains(&catalog) {
+                if let Err(e) = self.create_private_schema(query_ctx.clone()).await {
+                    warn!(e; "Failed to create schema {PRIVATE_SCHEMA_NAME} in catalog {catalog}");
+                    continue;
+                }
+                let _ = self.created_catalogs.insert(catalog.clone());
+            }
+
+            let (columns, row_count) = writer.finish();
+            let requests = InsertRequests {
+                inserts: vec![InsertRequest {
+                    table_name: SLOW_QUERY_TABLE_NAME.to_string(),
+                    columns,
+                    row_count,
+                    region_number: 0,
+                }],
+            };
+            if let Err(e) = self.instance.handle_inserts(requests, query_ctx).await {
+                warn!(e; "Failed to write slow queries into catalog {catalog}");
+            }
+        }
+    }
+
+    async fn create_private_schema(&self, query_ctx: QueryContextRef) -> crate::error::Result<()> {
+        let request = Request::Ddl(DdlRequest {
+            expr: Some(DdlExpr::CreateDatabase(CreateDatabaseExpr {
+                database_name: PRIVATE_SCHEMA_NAME.to_string(),
+                create_if_not_exists: true,
+            })),
+        });
+        let _ = GrpcQueryHandler::do_query(&self.instance, request, query_ctx).await?;
+        Ok(())
+    }
+}
+
+fn write_slow_query(
+    writer: &mut LinesWriter,
+    ts: i64,
+    slow_query: &SlowQuery,
+) -> common_grpc::error::Result<()> {
+    writer.write_ts(TS_COLUMN, (ts, Precision::Millisecond))?;
+    writer.write_tag(CHANNEL_COLUMN, &slow_query.channel.to_string())?;
+    writer.write_tag(STATUS_COLUMN, slow_query.status.as_str())?;
+    writer.write_string(QUERY_COLUMN, &slow_query.query)?;
+    if let Some(user) = &slow_query.user {
+        writer.write_string(USER_COLUMN, user)?;
+    }
+    writer.write_u64(ELAPSED_COLUMN, slow_query.elapsed.as_millis() as u64)?;
+    writer.write_u64(ROWS_COLUMN, slow_query.rows as u64)?;
+    if let Some(plan) = &slow_query.plan {
+        writer.write_string(PLAN_COLUMN, plan)?;
+    }
+    if let Some(error) = slow_query.error {
+        writer.write_string(ERROR_COLUMN, &error.to_string())?;
+    }
+    writer.commit();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use common_recordbatch::RecordBatches;
+    use datatypes::prelude::ConcreteDataType;
+    use datatypes::schema::{ColumnSchema, Schema};
+    use datatypes::vectors::UInt32Vector;
+    use futures::StreamExt;
+
+    use super::*;
+    use crate::error::TableNotFoundSnafu;
+
+    fn new_recorder(threshold: Duration) -> (SlowQueryRecorderRef, mpsc::Receiver<SlowQuery>) {
+        let (sender, receiver) = mpsc::channel(8);
+        let recorder = SlowQueryRecorder {
+            threshold,
+            sample_ratio: 1.0,
+            sender,
+        };
+        (Arc::new(recorder), receiver)
+    }
+
+    fn new_batches() -> RecordBatches {
+        let schema = Arc::new(Schema::new(vec![ColumnSchema::new(
+            "n",
+            ConcreteDataType::uint32_datatype(),
+            false,
+        )]));
+        let batch = RecordBatch::new(
+            schema.clone(),
+            vec![Arc::new(UInt32Vector::from_slice([1, 2, 3])) as _],
+        )
+        .unwrap();
+        RecordBatches::try_new(schema, vec![batch.clone(), batch]).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_record_slow_query_stream() {
+        let (recorder, mut receiver) = new_recorder(Duration::ZERO);
+        let query_ctx = Arc::new(QueryContext::new().with_channel(Channel::Http));
+        query_ctx.set_current_user("root");
+
+        let output = recorder
+            .track("select * from numbers", &query_ctx)
+            .watch(Output::Stream(new_batches().as_stream()));
+        let Output::Stream(mut stream) = output else { unreachable!() };
+        assert!(stream.next().await.is_some());
+        // Not recorded until the stream is exhausted.
+        assert!(receiver.try_recv().is_err());
+        while stream.next().await.is_some() {}
+
+        let slow_query = receiver.try_recv().unwrap();
+        assert_eq!("greptime", slow_query.catalog);
+        assert_eq!("select * from numbers", slow_query.query);
+        assert_eq!(Some("root"), slow_query.user.as_deref());
+        assert_eq!(Channel::Http, slow_query.channel);
+        assert_eq!(6, slow_query.rows);
+        assert_eq!(QueryStatus::Success, slow_query.status);
+        assert_eq!(None, slow_query.error);
+    }
+
+    #[tokio::test]
+    async fn test_skip_fast_query() {
+        let (recorder, mut receiver) = new_recorder(Duration::from_secs(3600));
+        let query_ctx = Arc::new(QueryContext::new());
+
+        let output = recorder
+            .track("select 1", &query_ctx)
+            .watch(Output::RecordBatches(new_batches()));
+        assert!(matches!(output, Output::RecordBatches(_)));
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_record_dropped_stream() {
+        let (recorder, mut receiver) = new_recorder(Duration::ZERO);
+        let query_ctx = Arc::new(QueryContext::new());
+
+        let output = recorder
+            .track("select * from numbers", &query_ctx)
+            .watch(Output::Stream(new_batches().as_stream()));
+        let Output::Stream(mut stream) = output else { unreachable!() };
+        assert!(stream.next().await.is_some());
+        drop(stream);
+
+        let slow_query = receiver.try_recv().unwrap();
+        assert_eq!("select * from numbers", slow_query.query);
+        assert_eq!(3, slow_query.rows);
+        assert_eq!(QueryStatus::Cancelled, slow_query.status);
+        // Recorded only once.
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_record_failed_query() {
+        let (recorder, mut receiver) = new_recorder(Duration::ZERO);
+        let query_ctx = Arc::new(QueryContext::new());
+
+        // The query fails before producing any output.
+        let error = TableNotFoundSnafu {
+            table_name: "not_exist",
+        }
+        .build();
+        recorder
+            .track("select * from not_exist", &query_ctx)
+            .fail(&error);
+
+        let slow_query = receiver.try_recv().unwrap();
+        assert_eq!("select * from not_exist", slow_query.query);
+        assert_eq!(0, slow_query.rows);
+        assert_eq!(QueryStatus::Failed, slow_query.status);
+        assert_eq!(Some(StatusCode::TableNotFound), slow_query.error);
+
+        // The query is cancelled before producing any output.
+        drop(recorder.track("select * from numbers", &query_ctx));
+        let slow_query = receiver.try_recv().unwrap();
+        assert_eq!(QueryStatus::Cancelled, slow_query.status);
+        assert_eq!(None, slow_query.error);
+    }
+
+    #[test]
+    fn test_write_slow_query() {
+        let mut writer = LinesWriter::with_lines(2);
+        let mut slow_query = SlowQuery {
+            catalog: "greptime".to_string(),
+            query: "select 1".to_string(),
+            user: None,
+            channel: Channel::Mysql,
+            elapsed: Duration::from_millis(1500),
+            rows: 1,
+            plan: None,
+            status: QueryStatus::Success,
+            error: None,
+        };
+        write_slow_query(&mut writer, 1, &slow_query).unwrap();
+        slow_query.user = Some("root".to_string());
+        slow_query.status = QueryStatus::Failed;
+        slow_query.error = Some(StatusCode::TableNotFound);
+        write_slow_query(&mut writer, 2, &slow_query).unwrap();
+
+        let (columns, row_count) = writer.finish();
+        assert_eq!(2, row_count);
+        let names = columns
+            .iter()
+            .map(|column| column.column_name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "ts",
+                "channel",
+                "status",
+                "query",
+                "elapsed_ms",
+                "rows",
+                "user",
+                "error"
+            ],
+            names
+        );
+        let user = columns
+            .iter()
+            .find(|column| column.column_name == "user")
+            .unwrap();
+        // The user of the first slow query is null.
+        assert_eq!(vec![0b01], user.null_mask);
+        let status = columns
+            .iter()
+            .find(|column| column.column_name == "status")
+            .unwrap();
+        assert_eq!(
+            vec!["success", "failed"],
+            status.values.as_ref().unwrap().string_values
+        );
+    }
+}
@@ -13,17 +13,24 @@
 // limitations under the License.
 
 mod backup;
+mod copy_job;
 mod copy_table_from;
 mod copy_table_to;
 mod describe;
+mod function;
+mod job;
+mod job_store;
+mod process;
 mod show;
 mod tql;
 
 use std::collections::HashMap;
 use std::str::FromStr;
+use std::sync::Arc;
 
 use catalog::CatalogManagerRef;
 use common_error::ext::BoxedError;
+use common_procedure::ProcedureManagerRef;
 use common_query::Output;
 use common_recordbatch::RecordBatches;
 use common_time::range::TimestampRange;
@@ -45,13 +52,23 @@ use crate::error::{
     CatalogSnafu, ExecLogicalPlanSnafu, ExecuteStatementSnafu, ExternalSnafu, PlanStatementSnafu,
     Result, SchemaNotFoundSnafu, TableNotFoundSnafu,
 };
+use crate::function::{FunctionManager, FunctionManagerRef};
 use crate::statement::backup::{COPY_DATABASE_TIME_END_KEY, COPY_DATABASE_TIME_START_KEY};
+pub(crate) use crate::statement::copy_job::new_distributed_job_manager;
+use crate::statement::copy_job::{CopyJobRequest, PausedJobs};
+use crate::statement::job::CopyJobLister;
+pub(crate) use crate::statement::process::track_query;
 
 #[derive(Clone)]
 pub struct StatementExecutor {
     catalog_manager: CatalogManagerRef,
     query_engine: QueryEngineRef,
     sql_stmt_executor: SqlStatementExecutorRef,
+    /// Procedure manager to run `COPY` statements as jobs.
+    procedure_manager: ProcedureManagerRef,
+    paused_jobs: Arc<PausedJobs>,
+    job_lister: Arc<CopyJobLister>,
+    function_manager: FunctionManagerRef,
 }
 
 impl StatementExecutor {
@@ -59,14 +76,33 @@ impl StatementExecutor {
         catalog_manager: CatalogManagerRef,
         query_engine: QueryEngineRef,
         sql_stmt_executor: SqlStatementExecutorRef,
+        procedure_manager: ProcedureManagerRef,
     ) -> Self {
+        let function_manager = Arc::new(FunctionManager::new(
+            catalog_manager.clone(),
+            query_engine.clone(),
+            sql_stmt_executor.clone(),
+        ));
+        let paused_jobs = Arc::new(PausedJobs::default());
+        let job_lister = Arc::new(CopyJobLister::new(
+            procedure_manager.clone(),
+            paused_jobs.clone(),
+        ));
         Self {
             catalog_manager,
             query_engine,
             sql_stmt_executor,
+            procedure_manager,
+            paused_jobs,
+            job_lister,
+            function_manager,
         }
     }
 
+    pub(crate) fn function_manager(&self) -> &FunctionManagerRef {
+        &self.function_manager
+    }
+
     pub async fn execute_stmt(
         &self,
         stmt: QueryStatement,
@@ -110,21 +146,34 @@ impl StatementExecutor {
 
             Statement::Copy(sql::statements::copy::Copy::CopyTable(stmt)) => {
                 let req = to_copy_table_request(stmt, query_ctx)?;
-                match req.direction {
-                    CopyDirection::Export => {
-                        self.copy_table_to(req).await.map(Output::AffectedRows)
-                    }
-                    CopyDirection::Import => {
-                        self.copy_table_from(req).await.map(Output::AffectedRows)
-                    }
-                }
+                self.run_copy_job(&self.procedure_manager, CopyJobRequest::Table(req))
+                    .await
             }
 
             Statement::Copy(sql::statements::copy::Copy::CopyDatabase(arg)) => {
-                self.copy_database(to_copy_database_request(arg, &query_ctx)?)
+                let req = to_copy_database_request(arg, &query_ctx)?;
+                self.run_copy_job(&self.procedure_manager, CopyJobRequest::Database(req))
                     .await
             }
 
+            Statement::ShowJobs(_) => self.show_jobs(query_ctx),
+
+            Statement::CancelJob(stmt) => self.cancel_job(stmt, query_ctx).await,
+
+            Statement::PauseJob(stmt) => self.pause_job(stmt, query_ctx),
+
+            Statement::ResumeJob(stmt) => self.resume_job(stmt, query_ctx),
+
+            Statement::ShowProcesslist(stmt) => self.show_processlist(stmt, query_ctx),
+
+            Statement::KillQuery(stmt) => self.kill_query(stmt, query_ctx),
+
+            Statement::CreateFunction(stmt) => self.create_function(stmt, query_ctx).await,
+
+            Statement::DropFunction(stmt) => self.drop_function(stmt, query_ctx).await,
+
+            Statement::ShowFunctions(_) => self.show_functions(query_ctx).await,
+
             Statement::CreateDatabase(_)
             | Statement::CreateTable(_)
             | Statement::CreateExternalTable(_)
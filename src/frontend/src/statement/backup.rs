@@ -13,7 +13,6 @@
 // limitations under the License.
 
 use common_datasource::file_format::Format;
-use common_query::Output;
 use common_telemetry::info;
 use snafu::{ensure, ResultExt};
 use table::requests::{CopyDatabaseRequest, CopyDirection, CopyTableRequest};
@@ -26,13 +25,17 @@ pub(crate) const COPY_DATABASE_TIME_START_KEY: &str = "start_time";
 pub(crate) const COPY_DATABASE_TIME_END_KEY: &str = "end_time";
 
 impl StatementExecutor {
-    pub(crate) async fn copy_database(&self, req: CopyDatabaseRequest) -> error::Result<Output> {
+    /// Splits the [CopyDatabaseRequest] into requests to export each table to a file.
+    pub(crate) async fn copy_database_requests(
+        &self,
+        req: &CopyDatabaseRequest,
+    ) -> error::Result<Vec<CopyTableRequest>> {
         // location must end with / so that every table is exported to a file.
         ensure!(
             req.location.ends_with('/'),
             InvalidCopyParameterSnafu {
                 key: "location",
-                value: req.location,
+                value: &req.location,
             }
         );
 
@@ -50,7 +53,7 @@ impl StatementExecutor {
             .context(error::ParseFileFormatSnafu)?
             .suffix();
 
-        let mut exported_rows = 0;
+        let mut requests = Vec::with_capacity(table_names.len());
         for table_name in table_names {
             // TODO(hl): remove this hardcode once we've removed numbers table.
             if table_name == "numbers" {
@@ -64,21 +67,18 @@ impl StatementExecutor {
                 req.catalog_name, req.schema_name, table_name, table_file
             );
 
-            let exported = self
-                .copy_table_to(CopyTableRequest {
-                    catalog_name: req.catalog_name.clone(),
-                    schema_name: req.schema_name.clone(),
-                    table_name,
-                    location: table_file,
-                    with: req.with.clone(),
-                    connection: req.connection.clone(),
-                    pattern: None,
-                    direction: CopyDirection::Export,
-                    timestamp_range: req.time_range,
-                })
-                .await?;
-            exported_rows += exported;
+            requests.push(CopyTableRequest {
+                catalog_name: req.catalog_name.clone(),
+                schema_name: req.schema_name.clone(),
+                table_name,
+                location: table_file,
+                with: req.with.clone(),
+                connection: req.connection.clone(),
+                pattern: None,
+                direction: CopyDirection::Export,
+                timestamp_range: req.time_range,
+            });
         }
-        Ok(Output::AffectedRows(exported_rows))
+        Ok(requests)
     }
 }
@@ -0,0 +1,424 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `COPY` statements running as procedures, so they survive client disconnections.
+//!
+//! Jobs are persisted after each finished task, and are resumed after the server
+//! restarts. In standalone mode, they are kept in the procedure store of the datanode.
+//! In distributed mode, they are kept in meta-srv if the frontend has a `node_id`,
+//! otherwise in memory and lost when the frontend restarts.
+//!
+//! A job could be paused between tasks by `PAUSE JOB`, and resumed by `RESUME JOB`.
+
+use std::collections::{HashSet, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use common_meta::kv_backend::KvBackendRef;
+use common_procedure::error::{FromJsonSnafu, ToJsonSnafu};
+use common_procedure::local::{LocalManager, ManagerConfig};
+use common_procedure::store::state_store::ObjectStateStore;
+use common_procedure::{
+    watcher, Context, Error as ProcedureError, LockKey, Procedure, ProcedureId, ProcedureManager,
+    ProcedureManagerRef, ProcedureWithId, Result as ProcedureResult, Status,
+};
+use common_query::Output;
+use common_telemetry::logging;
+use object_store::services::Memory;
+use object_store::ObjectStore;
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+use table::requests::{CopyDatabaseRequest, CopyDirection, CopyTableRequest};
+use tokio::sync::Notify;
+
+use crate::error::{
+    BuildJobStoreSnafu, Result, StartJobManagerSnafu, SubmitProcedureSnafu, WaitProcedureSnafu,
+};
+use crate::statement::job_store::KvJobStateStore;
+use crate::statement::StatementExecutor;
+
+/// How long a paused job waits before checking whether it's cancelled.
+const PAUSE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Creates the procedure manager of copy jobs for a frontend in distributed mode, which
+/// has no storage of its own.
+///
+/// The jobs are kept in `kv_backend` of meta-srv under `node_id`, so they are resumed
+/// after a frontend with the same `node_id` restarts. Without `node_id`, the jobs are
+/// kept in memory and lost if the frontend restarts. Either way, jobs are only listed
+/// and controlled by the frontend running them.
+pub(crate) fn new_distributed_job_manager(
+    kv_backend: KvBackendRef,
+    node_id: Option<u64>,
+) -> Result<ProcedureManagerRef> {
+    let manager = match node_id {
+        Some(node_id) => {
+            let state_store = Arc::new(KvJobStateStore::new(kv_backend, node_id));
+            LocalManager::new(ManagerConfig::default(), state_store)
+        }
+        None => {
+            logging::warn!(
+                "Frontend has no node_id, COPY jobs are kept in memory and lost on restart"
+            );
+            let object_store = ObjectStore::new(Memory::default())
+                .context(BuildJobStoreSnafu)?
+                .finish();
+            let state_store = Arc::new(ObjectStateStore::new(object_store));
+            LocalManager::new(ManagerConfig::default(), state_store)
+        }
+    };
+    manager.start().context(StartJobManagerSnafu)?;
+    Ok(Arc::new(manager))
+}
+
+/// Ids of the paused copy jobs.
+#[derive(Default)]
+pub(crate) struct PausedJobs {
+    ids: Mutex<HashSet<ProcedureId>>,
+    resumed: Notify,
+}
+
+impl PausedJobs {
+    /// Pauses the job, returns false if it's already paused.
+    pub(crate) fn pause(&self, id: ProcedureId) -> bool {
+        self.ids.lock().unwrap().insert(id)
+    }
+
+    /// Resumes the job, returns false if it's not paused.
+    pub(crate) fn resume(&self, id: ProcedureId) -> bool {
+        let resumed = self.ids.lock().unwrap().remove(&id);
+        if resumed {
+            self.resumed.notify_waiters();
+        }
+        resumed
+    }
+
+    pub(crate) fn is_paused(&self, id: ProcedureId) -> bool {
+        self.ids.lock().unwrap().contains(&id)
+    }
+
+    /// Waits until any job is resumed, or `timeout` elapses.
+    async fn wait(&self, timeout: Duration) {
+        let _ = tokio::time::timeout(timeout, self.resumed.notified()).await;
+    }
+}
+
+/// Request of a copy job.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum CopyJobRequest {
+    /// `COPY <table> FROM/TO`.
+    Table(CopyTableRequest),
+    /// `COPY DATABASE <database> TO`.
+    Database(CopyDatabaseRequest),
+}
+
+/// A unit of work of the copy job. The job persists its progress after each task
+/// is finished, so a recovered job resumes from the first unfinished task.
+#[derive(Debug, Serialize, Deserialize)]
+enum CopyTask {
+    /// Imports the file at this path into the table of the request.
+    ImportFile(String),
+    /// Exports a table.
+    ExportTable(CopyTableRequest),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+enum CopyJobState {
+    /// Splits the request into tasks.
+    Prepare,
+    /// Runs tasks one by one.
+    Copy,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CopyJobData {
+    state: CopyJobState,
+    request: CopyJobRequest,
+    /// Unfinished tasks.
+    pending: VecDeque<CopyTask>,
+    /// Number of finished tasks.
+    finished: usize,
+    /// Rows copied by finished tasks.
+    rows: usize,
+    /// Whether the job is paused.
+    #[serde(default)]
+    paused: bool,
+}
+
+/// Procedure to run `COPY` statements.
+///
+/// Importing a file is not atomic: if the job is interrupted in the middle of a file,
+/// the whole file is imported again after recovery. Rows with the same primary key
+/// and timestamp overwrite each other, so this is safe for tables with primary keys.
+pub(crate) struct CopyJobProcedure {
+    data: CopyJobData,
+    executor: StatementExecutor,
+    /// Rows copied so far, shared with the client that submits the job.
+    copied_rows: Arc<AtomicUsize>,
+    /// Whether the job is recovered and its pause state is not restored yet.
+    recovered: bool,
+}
+
+#[async_trait]
+impl Procedure for CopyJobProcedure {
+    fn type_name(&self) -> &str {
+        Self::TYPE_NAME
+    }
+
+    async fn execute(&mut self, ctx: &Context) -> ProcedureResult<Status> {
+        match self.data.state {
+            CopyJobState::Prepare => self.on_prepare().await,
+            CopyJobState::Copy => self.on_copy(ctx.procedure_id).await,
+        }
+    }
+
+    fn dump(&self) -> ProcedureResult<String> {
+        let json = serde_json::to_string(&self.data).context(ToJsonSnafu)?;
+        Ok(json)
+    }
+
+    fn lock_key(&self) -> LockKey {
+        match &self.data.request {
+            // We lock the whole table.
+            CopyJobRequest::Table(req) => LockKey::single(format!(
+                "{}.{}.{}",
+                req.catalog_name, req.schema_name, req.table_name
+            )),
+            CopyJobRequest::Database(req) => {
+                LockKey::single(format!("{}.{}", req.catalog_name, req.schema_name))
+            }
+        }
+    }
+}
+
+impl CopyJobProcedure {
+    pub(crate) const TYPE_NAME: &str = "frontend::CopyJobProcedure";
+
+    fn new(request: CopyJobRequest, executor: StatementExecutor) -> Self {
+        Self {
+            data: CopyJobData {
+                state: CopyJobState::Prepare,
+                request,
+                pending: VecDeque::new(),
+                finished: 0,
+                rows: 0,
+                paused: false,
+            },
+            executor,
+            copied_rows: Arc::new(AtomicUsize::new(0)),
+            recovered: false,
+        }
+    }
+
+    /// Register the loader of this procedure to the `procedure_manager`.
+    ///
+    /// # Panics
+    /// Panics on error.
+    pub(crate) fn register_loader(
+        executor: StatementExecutor,
+        procedure_manager: &dyn ProcedureManager,
+    ) {
+        procedure_manager
+            .register_loader(
+                Self::TYPE_NAME,
+                Box::new(move |data| {
+                    Self::from_json(data, executor.clone()).map(|p| Box::new(p) as _)
+                }),
+            )
+            .unwrap()
+    }
+
+    /// Recover the procedure from json.
+    fn from_json(json: &str, executor: StatementExecutor) -> ProcedureResult<Self> {
+        let data: CopyJobData = serde_json::from_str(json).context(FromJsonSnafu)?;
+        let copied_rows = Arc::new(AtomicUsize::new(data.rows));
+
+        Ok(CopyJobProcedure {
+            data,
+            executor,
+            copied_rows,
+            recovered: true,
+        })
+    }
+
+    async fn on_prepare(&mut self) -> ProcedureResult<Status> {
+        let tasks = match &self.data.request {
+            CopyJobRequest::Table(req) => match req.direction {
+                CopyDirection::Import => self
+                    .executor
+                    .list_copy_from_files(req)
+                    .await
+                    .map_err(ProcedureError::from_error_ext)?
+                    .into_iter()
+                    .map(CopyTask::ImportFile)
+                    .collect(),
+                CopyDirection::Export => VecDeque::from([CopyTask::ExportTable(req.clone())]),
+            },
+            CopyJobRequest::Database(req) => self
+                .executor
+                .copy_database_requests(req)
+                .await
+                .map_err(ProcedureError::from_error_ext)?
+                .into_iter()
+                .map(CopyTask::ExportTable)
+                .collect(),
+        };
+
+        logging::info!(
+            "Copy job {:?} is split into {} tasks",
+            self.data.request,
+            tasks.len()
+        );
+
+        self.data.pending = tasks;
+        self.data.state = CopyJobState::Copy;
+
+        Ok(Status::executing(true))
+    }
+
+    async fn on_copy(&mut self, id: ProcedureId) -> ProcedureResult<Status> {
+        let paused_jobs = &self.executor.paused_jobs;
+        if self.recovered {
+            self.recovered = false;
+            if self.data.paused {
+                let _ = paused_jobs.pause(id);
+            }
+        }
+        let paused = paused_jobs.is_paused(id);
+        if paused != self.data.paused {
+            // Persists the pause state, so the job is still paused after recovery.
+            logging::info!(
+                "Copy job {} is {}",
+                id,
+                if paused { "paused" } else { "resumed" }
+            );
+            self.data.paused = paused;
+            return Ok(Status::executing(true));
+        }
+        if paused {
+            // Returns between waits, so the job could still be cancelled.
+            paused_jobs.wait(PAUSE_CHECK_INTERVAL).await;
+            return Ok(Status::executing(false));
+        }
+
+        let Some(task) = self.data.pending.front() else {
+            return Ok(Status::Done);
+        };
+
+        let rows = match (task, &self.data.request) {
+            (CopyTask::ImportFile(path), CopyJobRequest::Table(req)) => {
+                self.executor.copy_file_from(req, path).await
+            }
+            (CopyTask::ExportTable(req), _) => self.executor.copy_table_to(req.clone()).await,
+            (CopyTask::ImportFile(path), CopyJobRequest::Database(_)) => {
+                unreachable!("Copy database never imports file {path}")
+            }
+        }
+        .map_err(ProcedureError::from_error_ext)?;
+
+        let _ = self.data.pending.pop_front();
+        self.data.finished += 1;
+        self.data.rows += rows;
+        self.copied_rows.store(self.data.rows, Ordering::Relaxed);
+
+        if self.data.pending.is_empty() {
+            // The job may be paused while running its last task.
+            let _ = self.executor.paused_jobs.resume(id);
+            Ok(Status::Done)
+        } else {
+            Ok(Status::executing(true))
+        }
+    }
+}
+
+impl StatementExecutor {
+    /// Runs the copy request as a procedure and waits until it's done.
+    ///
+    /// The job keeps running if the client goes away before it's done.
+    pub(super) async fn run_copy_job(
+        &self,
+        procedure_manager: &ProcedureManagerRef,
+        request: CopyJobRequest,
+    ) -> Result<Output> {
+        let procedure = CopyJobProcedure::new(request, self.clone());
+        let copied_rows = procedure.copied_rows.clone();
+        let procedure_with_id = ProcedureWithId::with_random_id(Box::new(procedure));
+        let procedure_id = procedure_with_id.id;
+
+        logging::info!("Submit copy job {}", procedure_id);
+
+        let mut watcher = procedure_manager
+            .submit(procedure_with_id)
+            .await
+            .context(SubmitProcedureSnafu { procedure_id })?;
+        watcher::wait(&mut watcher)
+            .await
+            .context(WaitProcedureSnafu { procedure_id })?;
+
+        Ok(Output::AffectedRows(copied_rows.load(Ordering::Relaxed)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_job_data_serialization() {
+        let data = CopyJobData {
+            state: CopyJobState::Copy,
+            request: CopyJobRequest::Table(CopyTableRequest {
+                catalog_name: "greptime".to_string(),
+                schema_name: "public".to_string(),
+                table_name: "demo".to_string(),
+                location: "/tmp/data/".to_string(),
+                with: Default::default(),
+                connection: Default::default(),
+                pattern: None,
+                direction: CopyDirection::Import,
+                timestamp_range: None,
+            }),
+            pending: VecDeque::from([CopyTask::ImportFile("/tmp/data/b.parquet".to_string())]),
+            finished: 1,
+            rows: 42,
+            paused: true,
+        };
+
+        let json = serde_json::to_string(&data).unwrap();
+        let parsed: CopyJobData = serde_json::from_str(&json).unwrap();
+        assert!(matches!(parsed.state, CopyJobState::Copy));
+        assert_eq!(1, parsed.finished);
+        assert_eq!(42, parsed.rows);
+        assert!(parsed.paused);
+        assert_eq!(1, parsed.pending.len());
+        assert!(
+            matches!(&parsed.pending[0], CopyTask::ImportFile(path) if path == "/tmp/data/b.parquet")
+        );
+    }
+
+    #[test]
+    fn test_paused_jobs() {
+        let paused_jobs = PausedJobs::default();
+        let id = ProcedureId::random();
+        assert!(!paused_jobs.is_paused(id));
+        assert!(paused_jobs.pause(id));
+        assert!(!paused_jobs.pause(id));
+        assert!(paused_jobs.is_paused(id));
+        assert!(paused_jobs.resume(id));
+        assert!(!paused_jobs.resume(id));
+        assert!(!paused_jobs.is_paused(id));
+    }
+}
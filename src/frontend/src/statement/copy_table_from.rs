@@ -18,6 +18,8 @@ use std::sync::Arc;
 
 use async_compat::CompatExt;
 use common_base::readable_size::ReadableSize;
+use common_datasource::file_format::arrow_ipc::ArrowIpcOpener;
+use common_datasource::file_format::avro::AvroOpener;
 use common_datasource::file_format::csv::{CsvConfigBuilder, CsvOpener};
 use common_datasource::file_format::json::JsonOpener;
 use common_datasource::file_format::orc::{
@@ -100,6 +102,18 @@ impl StatementExecutor {
                     .await
                     .context(error::InferSchemaSnafu { path })?,
             )),
+            Format::Avro(format) => Ok(Arc::new(
+                format
+                    .infer_schema(&object_store, path)
+                    .await
+                    .context(error::InferSchemaSnafu { path })?,
+            )),
+            Format::ArrowIpc(format) => Ok(Arc::new(
+                format
+                    .infer_schema(&object_store, path)
+                    .await
+                    .context(error::InferSchemaSnafu { path })?,
+            )),
             Format::Parquet(_) => {
                 let reader = object_store
                     .reader(path)
@@ -198,6 +212,34 @@ impl StatementExecutor {
                 )
                 .await
             }
+            Format::Avro(_) => {
+                let projected_schema = Arc::new(
+                    schema
+                        .project(&projection)
+                        .context(error::ProjectSchemaSnafu)?,
+                );
+
+                self.build_file_stream(
+                    AvroOpener::new(DEFAULT_BATCH_SIZE, projected_schema, object_store),
+                    path,
+                    schema,
+                )
+                .await
+            }
+            Format::ArrowIpc(_) => {
+                let projected_schema = Arc::new(
+                    schema
+                        .project(&projection)
+                        .context(error::ProjectSchemaSnafu)?,
+                );
+
+                self.build_file_stream(
+                    ArrowIpcOpener::new(projected_schema, object_store),
+                    path,
+                    schema,
+                )
+                .await
+            }
             Format::Parquet(_) => {
                 let reader = object_store
                     .reader(path)
@@ -231,17 +273,48 @@ impl StatementExecutor {
         }
     }
 
-    pub async fn copy_table_from(&self, req: CopyTableRequest) -> Result<usize> {
-        let table_ref = TableReference {
-            catalog: &req.catalog_name,
-            schema: &req.schema_name,
-            table: &req.table_name,
-        };
-        let table = self.get_table(&table_ref).await?;
+    /// Checks a file to copy from against the table schema.
+    async fn prepare_copy_from_file(
+        &self,
+        format: &Format,
+        object_store: ObjectStore,
+        path: &str,
+        table_schema: &SchemaRef,
+    ) -> Result<CopyFromFile> {
+        let file_schema = self
+            .infer_schema(format, object_store.clone(), path)
+            .await?;
+        let (file_schema_projection, table_schema_projection, compat_schema) =
+            generated_schema_projection_and_compatible_file_schema(&file_schema, table_schema);
+
+        let projected_file_schema = Arc::new(
+            file_schema
+                .project(&file_schema_projection)
+                .context(error::ProjectSchemaSnafu)?,
+        );
+        let projected_table_schema = Arc::new(
+            table_schema
+                .project(&table_schema_projection)
+                .context(error::ProjectSchemaSnafu)?,
+        );
+
+        ensure_schema_compatible(&projected_file_schema, &projected_table_schema)?;
+
+        Ok(CopyFromFile {
+            compat_schema: Arc::new(compat_schema),
+            file_schema_projection,
+            projected_table_schema,
+        })
+    }
+
+    /// Lists files to copy from, and ensures all of them are compatible with
+    /// the table schema.
+    pub(crate) async fn list_copy_from_files(&self, req: &CopyTableRequest) -> Result<Vec<String>> {
+        let table = self.get_table(&copy_table_ref(req)).await?;
 
         let format = Format::try_from(&req.with).context(error::ParseFileFormatSnafu)?;
 
-        let (object_store, entries) = self.list_copy_from_entries(&req).await?;
+        let (object_store, entries) = self.list_copy_from_entries(req).await?;
 
         let mut files = Vec::with_capacity(entries.len());
         let table_schema = table.schema().arrow_schema().clone();
@@ -255,94 +328,119 @@ impl StatementExecutor {
                 continue;
             }
             let path = entry.path();
-            let file_schema = self
-                .infer_schema(&format, object_store.clone(), path)
+            let _ = self
+                .prepare_copy_from_file(&format, object_store.clone(), path, &table_schema)
                 .await?;
-            let (file_schema_projection, table_schema_projection, compat_schema) =
-                generated_schema_projection_and_compatible_file_schema(&file_schema, &table_schema);
-
-            let projected_file_schema = Arc::new(
-                file_schema
-                    .project(&file_schema_projection)
-                    .context(error::ProjectSchemaSnafu)?,
-            );
-            let projected_table_schema = Arc::new(
-                table_schema
-                    .project(&table_schema_projection)
-                    .context(error::ProjectSchemaSnafu)?,
-            );
-
-            ensure_schema_compatible(&projected_file_schema, &projected_table_schema)?;
-
-            files.push((
-                Arc::new(compat_schema),
-                file_schema_projection,
-                projected_table_schema,
-                path,
-            ))
+
+            files.push(path.to_string());
         }
 
+        Ok(files)
+    }
+
+    /// Copies one file listed by [StatementExecutor::list_copy_from_files] into the table.
+    pub(crate) async fn copy_file_from(&self, req: &CopyTableRequest, path: &str) -> Result<usize> {
+        let table = self.get_table(&copy_table_ref(req)).await?;
+
+        let format = Format::try_from(&req.with).context(error::ParseFileFormatSnafu)?;
+
+        let object_store =
+            build_backend(&req.location, &req.connection).context(error::BuildBackendSnafu)?;
+
+        let table_schema = table.schema().arrow_schema().clone();
+        let CopyFromFile {
+            compat_schema,
+            file_schema_projection,
+            projected_table_schema,
+        } = self
+            .prepare_copy_from_file(&format, object_store.clone(), path, &table_schema)
+            .await?;
+
+        let mut stream = self
+            .build_read_stream(
+                &format,
+                object_store,
+                path,
+                compat_schema,
+                file_schema_projection,
+            )
+            .await?;
+
+        let fields = projected_table_schema
+            .fields()
+            .iter()
+            .map(|f| f.name().to_string())
+            .collect::<Vec<_>>();
+
+        // TODO(hl): make this configurable through options.
+        let pending_mem_threshold = ReadableSize::mb(32).as_bytes();
+        let mut pending_mem_size = 0;
+        let mut pending = vec![];
         let mut rows_inserted = 0;
-        for (schema, file_schema_projection, projected_table_schema, path) in files {
-            let mut stream = self
-                .build_read_stream(
-                    &format,
-                    object_store.clone(),
-                    path,
-                    schema,
-                    file_schema_projection,
-                )
-                .await?;
 
-            let fields = projected_table_schema
-                .fields()
-                .iter()
-                .map(|f| f.name().to_string())
-                .collect::<Vec<_>>();
-
-            // TODO(hl): make this configurable through options.
-            let pending_mem_threshold = ReadableSize::mb(32).as_bytes();
-            let mut pending_mem_size = 0;
-            let mut pending = vec![];
-
-            while let Some(r) = stream.next().await {
-                let record_batch = r.context(error::ReadRecordBatchSnafu)?;
-                let vectors =
-                    Helper::try_into_vectors(record_batch.columns()).context(IntoVectorsSnafu)?;
-
-                pending_mem_size += vectors.iter().map(|v| v.memory_size()).sum::<usize>();
-
-                let columns_values = fields
-                    .iter()
-                    .cloned()
-                    .zip(vectors.into_iter())
-                    .collect::<HashMap<_, _>>();
-
-                pending.push(table.insert(InsertRequest {
-                    catalog_name: req.catalog_name.to_string(),
-                    schema_name: req.schema_name.to_string(),
-                    table_name: req.table_name.to_string(),
-                    columns_values,
-                    //TODO: support multi-regions
-                    region_number: 0,
-                }));
-
-                if pending_mem_size as u64 >= pending_mem_threshold {
-                    rows_inserted +=
-                        batch_insert(&mut pending, &mut pending_mem_size, &req.table_name).await?;
-                }
-            }
+        while let Some(r) = stream.next().await {
+            let record_batch = r.context(error::ReadRecordBatchSnafu)?;
+            let vectors =
+                Helper::try_into_vectors(record_batch.columns()).context(IntoVectorsSnafu)?;
 
-            if !pending.is_empty() {
+            pending_mem_size += vectors.iter().map(|v| v.memory_size()).sum::<usize>();
+
+            let columns_values = fields
+                .iter()
+                .cloned()
+                .zip(vectors.into_iter())
+                .collect::<HashMap<_, _>>();
+
+            pending.push(table.insert(InsertRequest {
+                catalog_name: req.catalog_name.to_string(),
+                schema_name: req.schema_name.to_string(),
+                table_name: req.table_name.to_string(),
+                columns_values,
+                //TODO: support multi-regions
+                region_number: 0,
+            }));
+
+            if pending_mem_size as u64 >= pending_mem_threshold {
                 rows_inserted +=
                     batch_insert(&mut pending, &mut pending_mem_size, &req.table_name).await?;
             }
         }
 
+        if !pending.is_empty() {
+            rows_inserted +=
+                batch_insert(&mut pending, &mut pending_mem_size, &req.table_name).await?;
+        }
+
+        Ok(rows_inserted)
+    }
+
+    pub async fn copy_table_from(&self, req: CopyTableRequest) -> Result<usize> {
+        let files = self.list_copy_from_files(&req).await?;
+
+        let mut rows_inserted = 0;
+        for path in files {
+            rows_inserted += self.copy_file_from(&req, &path).await?;
+        }
+
         Ok(rows_inserted)
     }
 }
 
+/// A file to copy from and the projections to adapt it to the table schema.
+struct CopyFromFile {
+    compat_schema: SchemaRef,
+    file_schema_projection: Vec<usize>,
+    projected_table_schema: SchemaRef,
+}
+
+fn copy_table_ref(req: &CopyTableRequest) -> TableReference<'_> {
+    TableReference {
+        catalog: &req.catalog_name,
+        schema: &req.schema_name,
+        table: &req.table_name,
+    }
+}
+
 /// Executes all pending inserts all at once, drain pending requests and reset pending bytes.
 async fn batch_insert(
     pending: &mut Vec<impl Future<Output = table::error::Result<usize>>>,
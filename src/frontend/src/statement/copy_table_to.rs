@@ -13,8 +13,11 @@
 // limitations under the License.
 
 use common_base::readable_size::ReadableSize;
+use common_datasource::file_format::arrow_ipc::stream_to_arrow_ipc;
+use common_datasource::file_format::avro::stream_to_avro;
 use common_datasource::file_format::csv::stream_to_csv;
 use common_datasource::file_format::json::stream_to_json;
+use common_datasource::file_format::orc::stream_to_orc;
 use common_datasource::file_format::Format;
 use common_datasource::object_store::{build_backend, parse_url};
 use common_recordbatch::adapter::DfRecordBatchStreamAdapter;
@@ -57,6 +60,23 @@ impl StatementExecutor {
             )
             .await
             .context(error::WriteStreamToFileSnafu { path }),
+            Format::Avro(_) => stream_to_avro(
+                Box::pin(DfRecordBatchStreamAdapter::new(stream)),
+                object_store,
+                path,
+                threshold,
+            )
+            .await
+            .context(error::WriteStreamToFileSnafu { path }),
+            Format::ArrowIpc(format) => stream_to_arrow_ipc(
+                Box::pin(DfRecordBatchStreamAdapter::new(stream)),
+                object_store,
+                path,
+                threshold,
+                format.kind,
+            )
+            .await
+            .context(error::WriteStreamToFileSnafu { path }),
             Format::Parquet(_) => {
                 let writer = ParquetWriter::new(path, Source::Stream(stream), object_store);
                 let rows_copied = writer
@@ -68,7 +88,14 @@ impl StatementExecutor {
 
                 Ok(rows_copied)
             }
-            _ => error::UnsupportedFormatSnafu { format: *format }.fail(),
+            Format::Orc(_) => stream_to_orc(
+                Box::pin(DfRecordBatchStreamAdapter::new(stream)),
+                object_store,
+                path,
+                threshold,
+            )
+            .await
+            .context(error::WriteStreamToFileSnafu { path }),
         }
     }
 
@@ -0,0 +1,119 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_query::Output;
+use common_recordbatch::RecordBatches;
+use datatypes::prelude::ConcreteDataType;
+use datatypes::schema::{ColumnSchema, Schema};
+use datatypes::vectors::{StringVector, TimestampMillisecondVector};
+use itertools::Itertools;
+use query::sql_udf::normalize_ident;
+use session::context::QueryContextRef;
+use snafu::ResultExt;
+use sql::statements::function::{CreateFunction, DropFunction};
+
+use crate::error::{CreateRecordBatchSnafu, Result};
+use crate::statement::StatementExecutor;
+
+impl StatementExecutor {
+    pub(super) async fn create_function(
+        &self,
+        stmt: CreateFunction,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output> {
+        self.function_manager
+            .create(
+                &query_ctx.current_catalog(),
+                &query_ctx.current_schema(),
+                stmt,
+            )
+            .await?;
+        Ok(Output::AffectedRows(0))
+    }
+
+    pub(super) async fn drop_function(
+        &self,
+        stmt: DropFunction,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output> {
+        let dropped = self
+            .function_manager
+            .remove(
+                &query_ctx.current_catalog(),
+                &query_ctx.current_schema(),
+                &normalize_ident(&stmt.name),
+                stmt.if_exists,
+            )
+            .await?;
+        Ok(Output::AffectedRows(usize::from(dropped)))
+    }
+
+    /// Lists functions created by `CREATE FUNCTION` in the current schema.
+    pub(super) async fn show_functions(&self, query_ctx: QueryContextRef) -> Result<Output> {
+        let functions = self
+            .function_manager
+            .list(&query_ctx.current_catalog(), &query_ctx.current_schema())
+            .await?;
+
+        let schema = Arc::new(Schema::new(vec![
+            ColumnSchema::new("Function", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("Arguments", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("Returns", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("Body", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new(
+                "Created",
+                ConcreteDataType::timestamp_millisecond_datatype(),
+                false,
+            ),
+        ]));
+        let columns = vec![
+            Arc::new(StringVector::from(
+                functions
+                    .iter()
+                    .map(|(name, _)| name.clone())
+                    .collect::<Vec<_>>(),
+            )) as _,
+            Arc::new(StringVector::from(
+                functions
+                    .iter()
+                    .map(|(_, function)| function.definition.args.iter().join(", "))
+                    .collect::<Vec<_>>(),
+            )) as _,
+            Arc::new(StringVector::from(
+                functions
+                    .iter()
+                    .map(|(_, function)| function.definition.return_type.to_string())
+                    .collect::<Vec<_>>(),
+            )) as _,
+            Arc::new(StringVector::from(
+                functions
+                    .iter()
+                    .map(|(_, function)| function.definition.body.to_string())
+                    .collect::<Vec<_>>(),
+            )) as _,
+            Arc::new(TimestampMillisecondVector::from_vec(
+                functions
+                    .iter()
+                    .map(|(_, function)| function.gmt_created)
+                    .collect(),
+            )) as _,
+        ];
+        let records =
+            RecordBatches::try_from_columns(schema, columns).context(CreateRecordBatchSnafu)?;
+
+        Ok(Output::RecordBatches(records))
+    }
+}
@@ -0,0 +1,219 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, Weak};
+
+use catalog::information_schema::{register_job_lister, JobInfo, JobLister};
+use common_procedure::{ProcedureId, ProcedureInfo, ProcedureManagerRef, ProcedureState};
+use common_query::Output;
+use common_recordbatch::RecordBatches;
+use datatypes::prelude::ConcreteDataType;
+use datatypes::schema::{ColumnSchema, Schema};
+use datatypes::vectors::{StringVector, TimestampMillisecondVector};
+use session::context::QueryContextRef;
+use snafu::{OptionExt, ResultExt};
+use sql::statements::cancel::CancelJob;
+use sql::statements::pause::{PauseJob, ResumeJob};
+
+use crate::error::{
+    CancelJobSnafu, CreateRecordBatchSnafu, InvalidJobIdSnafu, JobNotFoundSnafu, RecoverJobsSnafu,
+    Result,
+};
+use crate::statement::copy_job::{CopyJobProcedure, PausedJobs};
+use crate::statement::StatementExecutor;
+
+impl StatementExecutor {
+    /// Registers the loader of copy jobs, and lists the jobs in `information_schema.jobs`.
+    pub(crate) fn register_jobs(&self) {
+        CopyJobProcedure::register_loader(self.clone(), &*self.procedure_manager);
+        let lister: Weak<dyn JobLister> = Arc::downgrade(&self.job_lister);
+        register_job_lister(lister);
+    }
+
+    /// Resumes the unfinished copy jobs, the loader must be registered first.
+    pub(crate) async fn recover_jobs(&self) -> Result<()> {
+        self.procedure_manager
+            .recover()
+            .await
+            .context(RecoverJobsSnafu)
+    }
+
+    /// Lists copy jobs in the current catalog, including finished jobs whose states
+    /// are not expired yet.
+    pub(super) fn show_jobs(&self, query_ctx: QueryContextRef) -> Result<Output> {
+        let catalog = query_ctx.current_catalog();
+        let mut jobs = self
+            .procedure_manager
+            .list_procedures()
+            .into_iter()
+            .filter(|job| is_job(job) && job_catalog(job) == catalog)
+            .collect::<Vec<_>>();
+        jobs.sort_unstable_by_key(|job| job.start_time_ms);
+
+        let schema = Arc::new(Schema::new(vec![
+            ColumnSchema::new("Job Id", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("State", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("Error", ConcreteDataType::string_datatype(), true),
+            ColumnSchema::new(
+                "Start Time",
+                ConcreteDataType::timestamp_millisecond_datatype(),
+                false,
+            ),
+        ]));
+        let columns = vec![
+            Arc::new(StringVector::from(
+                jobs.iter()
+                    .map(|job| job.id.to_string())
+                    .collect::<Vec<_>>(),
+            )) as _,
+            Arc::new(StringVector::from(
+                jobs.iter()
+                    .map(|job| job_state(job, &self.paused_jobs))
+                    .collect::<Vec<_>>(),
+            )) as _,
+            Arc::new(StringVector::from(
+                jobs.iter()
+                    .map(|job| job.state.error().map(|e| e.to_string()))
+                    .collect::<Vec<_>>(),
+            )) as _,
+            Arc::new(TimestampMillisecondVector::from_vec(
+                jobs.iter().map(|job| job.start_time_ms).collect(),
+            )) as _,
+        ];
+        let records =
+            RecordBatches::try_from_columns(schema, columns).context(CreateRecordBatchSnafu)?;
+
+        Ok(Output::RecordBatches(records))
+    }
+
+    /// Cancels a running copy job. The job stops after its current task is finished.
+    pub(super) async fn cancel_job(
+        &self,
+        stmt: CancelJob,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output> {
+        let job_id = stmt.job_id();
+        let procedure_id = self.find_job(job_id, &query_ctx)?;
+
+        let cancelled = self
+            .procedure_manager
+            .cancel(procedure_id)
+            .await
+            .context(CancelJobSnafu { job_id })?;
+        // Wakes up the job if it's paused, so it stops now.
+        let _ = self.paused_jobs.resume(procedure_id);
+
+        Ok(Output::AffectedRows(usize::from(cancelled)))
+    }
+
+    /// Pauses a running copy job. The job pauses after its current task is finished.
+    pub(super) fn pause_job(&self, stmt: PauseJob, query_ctx: QueryContextRef) -> Result<Output> {
+        let procedure_id = self.find_job(stmt.job_id(), &query_ctx)?;
+        let running = self
+            .procedure_manager
+            .procedure_info(procedure_id)
+            .map_or(false, |job| !job.state.is_done() && !job.state.is_failed());
+        let paused = running && self.paused_jobs.pause(procedure_id);
+        Ok(Output::AffectedRows(usize::from(paused)))
+    }
+
+    /// Resumes a paused copy job.
+    pub(super) fn resume_job(&self, stmt: ResumeJob, query_ctx: QueryContextRef) -> Result<Output> {
+        let procedure_id = self.find_job(stmt.job_id(), &query_ctx)?;
+        let resumed = self.paused_jobs.resume(procedure_id);
+        Ok(Output::AffectedRows(usize::from(resumed)))
+    }
+
+    /// Finds the copy job in the current catalog.
+    fn find_job(&self, job_id: &str, query_ctx: &QueryContextRef) -> Result<ProcedureId> {
+        let procedure_id = ProcedureId::parse_str(job_id)
+            .ok()
+            .context(InvalidJobIdSnafu { job_id })?;
+        let catalog = query_ctx.current_catalog();
+        self.procedure_manager
+            .procedure_info(procedure_id)
+            .filter(|job| is_job(job) && job_catalog(job) == catalog)
+            .map(|job| job.id)
+            .context(JobNotFoundSnafu { job_id })
+    }
+}
+
+/// Lists copy jobs for `information_schema.jobs`.
+pub(super) struct CopyJobLister {
+    procedure_manager: ProcedureManagerRef,
+    paused_jobs: Arc<PausedJobs>,
+}
+
+impl CopyJobLister {
+    pub(super) fn new(
+        procedure_manager: ProcedureManagerRef,
+        paused_jobs: Arc<PausedJobs>,
+    ) -> Self {
+        Self {
+            procedure_manager,
+            paused_jobs,
+        }
+    }
+}
+
+impl JobLister for CopyJobLister {
+    fn list_jobs(&self) -> Vec<JobInfo> {
+        self.procedure_manager
+            .list_procedures()
+            .into_iter()
+            .filter(is_job)
+            .map(|job| JobInfo {
+                id: job.id.to_string(),
+                catalog: job_catalog(&job).to_string(),
+                schema: job_schema(&job).to_string(),
+                state: job_state(&job, &self.paused_jobs).to_string(),
+                error: job.state.error().map(|e| e.to_string()),
+                start_time_ms: job.start_time_ms,
+            })
+            .collect()
+    }
+}
+
+/// Returns the catalog a job works on, which is the first part of its lock key
+/// (see [CopyJobProcedure]'s `lock_key`).
+fn job_catalog(job: &ProcedureInfo) -> &str {
+    lock_key_part(job, 0)
+}
+
+/// Returns the schema a job works on, which is the second part of its lock key.
+fn job_schema(job: &ProcedureInfo) -> &str {
+    lock_key_part(job, 1)
+}
+
+fn lock_key_part(job: &ProcedureInfo, index: usize) -> &str {
+    job.lock_key
+        .keys_to_lock()
+        .next()
+        .and_then(|key| key.split('.').nth(index))
+        .unwrap_or_default()
+}
+
+/// Returns the state of the job, a running job may be paused.
+fn job_state<'a>(job: &'a ProcedureInfo, paused_jobs: &PausedJobs) -> &'a str {
+    if matches!(job.state, ProcedureState::Running) && paused_jobs.is_paused(job.id) {
+        "Paused"
+    } else {
+        job.state.as_str()
+    }
+}
+
+/// Returns true if the procedure is a job visible to users.
+fn is_job(procedure: &ProcedureInfo) -> bool {
+    procedure.parent_id.is_none() && procedure.type_name == CopyJobProcedure::TYPE_NAME
+}
@@ -0,0 +1,202 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! [StateStore] of copy jobs in distributed mode, which keeps the jobs in the kv
+//! backend of meta-srv.
+
+use async_stream::try_stream;
+use async_trait::async_trait;
+use common_error::ext::BoxedError;
+use common_meta::kv_backend::KvBackendRef;
+use common_meta::rpc::store::{BatchDeleteRequest, PutRequest, RangeRequest};
+use common_meta::util;
+use common_procedure::error::{
+    CorruptedDataSnafu, DeleteStatesSnafu, ListStateSnafu, PutStateSnafu,
+};
+use common_procedure::store::state_store::{KeyValueStream, StateStore};
+use common_procedure::Result;
+use snafu::ResultExt;
+
+const JOB_PREFIX: &str = "/__copy_job__";
+
+/// Keeps the states of the copy jobs run by a frontend under the prefix of its
+/// node id, so a restarted frontend only resumes its own jobs.
+pub(crate) struct KvJobStateStore {
+    kv_backend: KvBackendRef,
+    prefix: String,
+    max_size_per_range: i64,
+}
+
+impl KvJobStateStore {
+    pub(crate) fn new(kv_backend: KvBackendRef, node_id: u64) -> Self {
+        Self {
+            kv_backend,
+            prefix: format!("{JOB_PREFIX}/{node_id}/"),
+            max_size_per_range: -1,
+        }
+    }
+
+    fn with_prefix(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key.trim_start_matches('/'))
+    }
+}
+
+#[async_trait]
+impl StateStore for KvJobStateStore {
+    async fn put(&self, key: &str, value: Vec<u8>) -> Result<()> {
+        let _ = self
+            .kv_backend
+            .put(
+                PutRequest::new()
+                    .with_key(self.with_prefix(key))
+                    .with_value(value),
+            )
+            .await
+            .map_err(BoxedError::new)
+            .context(PutStateSnafu { key })?;
+        Ok(())
+    }
+
+    async fn walk_top_down(&self, path: &str) -> Result<KeyValueStream> {
+        // extend their lifetimes to be used in the stream
+        let path = path.to_string();
+        let prefix = self.prefix.clone();
+        let kv_backend = self.kv_backend.clone();
+        let limit = self.max_size_per_range;
+        let mut key = self.with_prefix(&path).into_bytes();
+
+        let stream = try_stream! {
+            let range_end = util::get_prefix_end_key(&key);
+            loop {
+                let req = RangeRequest::new()
+                    .with_range(key.clone(), range_end.clone())
+                    .with_limit(limit);
+                let resp = kv_backend.range(req).await.map_err(BoxedError::new).with_context(|_|
+                    ListStateSnafu { path: path.clone() }
+                )?;
+
+                let mut no_more_data = true;
+                if resp.more {
+                    if let Some(last) = resp.kvs.last() {
+                        key = util::get_prefix_end_key(&last.key);
+                        no_more_data = false;
+                    }
+                }
+
+                for kv in resp.kvs {
+                    let key = String::from_utf8(kv.key).context(CorruptedDataSnafu)?;
+                    let key = key.trim_start_matches(&prefix).to_string();
+                    yield (key, kv.value)
+                }
+
+                if no_more_data {
+                    break;
+                }
+            }
+        };
+        Ok(Box::pin(stream))
+    }
+
+    async fn batch_delete(&self, keys: &[String]) -> Result<()> {
+        let mut req = BatchDeleteRequest::new();
+        req.keys = keys
+            .iter()
+            .map(|key| self.with_prefix(key).into_bytes())
+            .collect();
+        let _ = self
+            .kv_backend
+            .batch_delete(req)
+            .await
+            .map_err(BoxedError::new)
+            .with_context(|_| DeleteStatesSnafu {
+                keys: format!("{keys:?}"),
+            })?;
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        self.batch_delete(&[key.to_string()]).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use common_meta::error::Error;
+    use common_meta::kv_backend::memory::MemoryKvBackend;
+    use common_procedure::store::state_store::KeyValue;
+    use futures::TryStreamExt;
+
+    use super::*;
+
+    async fn walk_top_down(store: &KvJobStateStore, path: &str) -> Vec<KeyValue> {
+        let mut data = store
+            .walk_top_down(path)
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        data.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        data
+    }
+
+    #[tokio::test]
+    async fn test_kv_job_state_store() {
+        let kv_backend: KvBackendRef = Arc::new(MemoryKvBackend::<Error>::default());
+        let store = KvJobStateStore {
+            max_size_per_range: 1, // for testing "more" in range
+            ..KvJobStateStore::new(kv_backend.clone(), 1)
+        };
+        // Jobs of another frontend.
+        let other = KvJobStateStore::new(kv_backend, 2);
+
+        assert!(walk_top_down(&store, "/").await.is_empty());
+
+        store.put("a/1", b"v1".to_vec()).await.unwrap();
+        store.put("a/2", b"v2".to_vec()).await.unwrap();
+        store.put("b/1", b"v3".to_vec()).await.unwrap();
+        other.put("a/3", b"v4".to_vec()).await.unwrap();
+
+        assert_eq!(
+            vec![
+                ("a/1".to_string(), b"v1".to_vec()),
+                ("a/2".to_string(), b"v2".to_vec()),
+                ("b/1".to_string(), b"v3".to_vec())
+            ],
+            walk_top_down(&store, "/").await
+        );
+        assert_eq!(
+            vec![
+                ("a/1".to_string(), b"v1".to_vec()),
+                ("a/2".to_string(), b"v2".to_vec()),
+            ],
+            walk_top_down(&store, "a/").await
+        );
+
+        store
+            .batch_delete(&["a/2".to_string(), "b/1".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(
+            vec![("a/1".to_string(), b"v1".to_vec())],
+            walk_top_down(&store, "a/").await
+        );
+        assert_eq!(
+            vec![("a/3".to_string(), b"v4".to_vec())],
+            walk_top_down(&other, "/").await
+        );
+    }
+}
@@ -0,0 +1,209 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::future::Future;
+use std::sync::Arc;
+
+use common_error::ext::BoxedError;
+use common_query::Output;
+use common_recordbatch::error::ExternalSnafu as RecordBatchExternalSnafu;
+use common_recordbatch::{
+    RecordBatch, RecordBatchStreamAdaptor, RecordBatches, SendableRecordBatchStream,
+};
+use datatypes::prelude::ConcreteDataType;
+use datatypes::schema::{ColumnSchema, Schema};
+use datatypes::vectors::{StringVector, TimestampMillisecondVector, UInt32Vector};
+use futures::StreamExt;
+use session::context::QueryContextRef;
+use session::process::{ProcessHandle, ProcessInfo, ProcessManager, ProcessStage};
+use snafu::{ensure, OptionExt, ResultExt};
+use sql::statements::kill::KillQuery;
+use sql::statements::show::ShowProcesslist;
+
+use crate::error::{
+    CreateRecordBatchSnafu, KillQueryDeniedSnafu, ProcessNotFoundSnafu, QueryCancelledSnafu, Result,
+};
+use crate::statement::StatementExecutor;
+
+/// Length of the query text shown by `SHOW PROCESSLIST` without `FULL`.
+const TRUNCATED_QUERY_LEN: usize = 100;
+
+impl StatementExecutor {
+    /// Lists running queries of current catalog.
+    pub(super) fn show_processlist(
+        &self,
+        stmt: ShowProcesslist,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output> {
+        let catalog = query_ctx.current_catalog();
+        let processes = ProcessManager::global()
+            .list()
+            .into_iter()
+            .filter(|process| process.catalog() == catalog)
+            .collect::<Vec<_>>();
+
+        let schema = Arc::new(Schema::new(vec![
+            ColumnSchema::new("Id", ConcreteDataType::uint32_datatype(), false),
+            ColumnSchema::new("User", ConcreteDataType::string_datatype(), true),
+            ColumnSchema::new("Host", ConcreteDataType::string_datatype(), true),
+            ColumnSchema::new("Schema", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("State", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new(
+                "Start Time",
+                ConcreteDataType::timestamp_millisecond_datatype(),
+                false,
+            ),
+            ColumnSchema::new("Info", ConcreteDataType::string_datatype(), false),
+        ]));
+        let columns = vec![
+            Arc::new(UInt32Vector::from_vec(
+                processes.iter().map(|process| process.id()).collect(),
+            )) as _,
+            Arc::new(StringVector::from(
+                processes
+                    .iter()
+                    .map(|process| process.user())
+                    .collect::<Vec<_>>(),
+            )) as _,
+            Arc::new(StringVector::from(
+                processes
+                    .iter()
+                    .map(|process| process.client())
+                    .collect::<Vec<_>>(),
+            )) as _,
+            Arc::new(StringVector::from(
+                processes
+                    .iter()
+                    .map(|process| process.schema())
+                    .collect::<Vec<_>>(),
+            )) as _,
+            Arc::new(StringVector::from(
+                processes
+                    .iter()
+                    .map(|process| process_state(process))
+                    .collect::<Vec<_>>(),
+            )) as _,
+            Arc::new(TimestampMillisecondVector::from_vec(
+                processes
+                    .iter()
+                    .map(|process| process.start_time_ms())
+                    .collect(),
+            )) as _,
+            Arc::new(StringVector::from(
+                processes
+                    .iter()
+                    .map(|process| {
+                        if stmt.full {
+                            process.query().to_string()
+                        } else {
+                            process.query().chars().take(TRUNCATED_QUERY_LEN).collect()
+                        }
+                    })
+                    .collect::<Vec<_>>(),
+            )) as _,
+        ];
+        let records =
+            RecordBatches::try_from_columns(schema, columns).context(CreateRecordBatchSnafu)?;
+
+        Ok(Output::RecordBatches(records))
+    }
+
+    /// Stops a running query of current catalog, which must be run by the current user.
+    /// The query fails with a cancelled error.
+    pub(super) fn kill_query(&self, stmt: KillQuery, query_ctx: QueryContextRef) -> Result<Output> {
+        let id = stmt.id();
+        let catalog = query_ctx.current_catalog();
+        let manager = ProcessManager::global();
+        let process = manager
+            .list()
+            .into_iter()
+            .find(|process| process.id() == id && process.catalog() == catalog)
+            .context(ProcessNotFoundSnafu { id })?;
+        ensure!(
+            process.user() == query_ctx.current_user().as_deref(),
+            KillQueryDeniedSnafu { id }
+        );
+        ensure!(manager.kill(id), ProcessNotFoundSnafu { id });
+
+        Ok(Output::AffectedRows(0))
+    }
+}
+
+fn process_state(process: &ProcessInfo) -> String {
+    if process.is_cancelled() {
+        "Killed".to_string()
+    } else {
+        process.stage().to_string()
+    }
+}
+
+/// Runs a query as a process in the [ProcessManager], so that it could be stopped by
+/// `KILL QUERY` either while it's executing or while its output stream is consumed.
+pub(crate) async fn track_query<F>(
+    query: &str,
+    query_ctx: &QueryContextRef,
+    execute: F,
+) -> Result<Output>
+where
+    F: Future<Output = Result<Output>>,
+{
+    let process = ProcessManager::global().register(query_ctx, query);
+    let output = tokio::select! {
+        output = execute => output?,
+        _ = process.cancelled() => return QueryCancelledSnafu { id: process.id() }.fail(),
+    };
+
+    match output {
+        Output::Stream(stream) => {
+            process.set_stage(ProcessStage::Streaming);
+            Ok(Output::Stream(cancellable_stream(stream, process)))
+        }
+        output => Ok(output),
+    }
+}
+
+/// Wraps the stream to stop polling it once the query is killed. The query is
+/// removed from the process list when the stream is dropped, and dropping the
+/// inner stream also stops the sub-queries on datanodes.
+fn cancellable_stream(
+    mut stream: SendableRecordBatchStream,
+    process: ProcessHandle,
+) -> SendableRecordBatchStream {
+    let schema = stream.schema();
+    let output_ordering = stream.output_ordering().map(|ordering| ordering.to_vec());
+    let stream = async_stream::stream! {
+        loop {
+            tokio::select! {
+                biased;
+                _ = process.cancelled() => {
+                    let cancelled: common_recordbatch::error::Result<RecordBatch> =
+                        Err(BoxedError::new(QueryCancelledSnafu { id: process.id() }.build()))
+                            .context(RecordBatchExternalSnafu);
+                    yield cancelled;
+                    break;
+                }
+                batch = stream.next() => match batch {
+                    Some(batch) => yield batch,
+                    None => break,
+                },
+            }
+        }
+    };
+    Box::pin(RecordBatchStreamAdaptor {
+        schema,
+        stream: Box::pin(stream),
+        output_ordering,
+        metrics: None,
+    })
+}
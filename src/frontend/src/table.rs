@@ -13,22 +13,23 @@
 // limitations under the License.
 
 use std::any::Any;
+use std::collections::HashSet;
+use std::future::Future;
 use std::iter;
 use std::pin::Pin;
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use client::Database;
+use common_catalog::consts::INFORMATION_SCHEMA_NAME;
 use common_error::ext::BoxedError;
 use common_meta::table_name::TableName;
 use common_query::error::Result as QueryResult;
 use common_query::logical_plan::Expr;
 use common_query::physical_plan::{PhysicalPlan, PhysicalPlanRef};
 use common_query::Output;
-use common_recordbatch::adapter::AsyncRecordBatchStreamAdapter;
-use common_recordbatch::error::{
-    InitRecordbatchStreamSnafu, PollStreamSnafu, Result as RecordBatchResult,
-};
+use common_recordbatch::adapter::{AsyncRecordBatchStreamAdapter, DfRecordBatchStreamAdapter};
+use common_recordbatch::error::{InitRecordbatchStreamSnafu, Result as RecordBatchResult};
 use common_recordbatch::{
     RecordBatch, RecordBatchStreamAdaptor, RecordBatches, SendableRecordBatchStream,
 };
@@ -38,6 +39,7 @@ use datafusion::physical_plan::{
 };
 use datafusion_common::DataFusionError;
 use datatypes::schema::{ColumnSchema, Schema, SchemaRef};
+use datatypes::vectors::UInt64Vector;
 use futures_util::{Stream, StreamExt};
 use partition::splitter::WriteSplitter;
 use snafu::prelude::*;
@@ -46,10 +48,11 @@ use table::error::TableOperationSnafu;
 use table::metadata::{FilterPushDownType, TableInfoRef};
 use table::requests::{DeleteRequest, InsertRequest};
 use table::Table;
-use tokio::sync::RwLock;
 
 use crate::catalog::FrontendCatalogManager;
-use crate::error::{FindDatanodeSnafu, FindTableRouteSnafu, Result};
+use crate::error::{
+    CollectRecordbatchSnafu, FindDatanodeSnafu, FindTableRouteSnafu, RequestDatanodeSnafu, Result,
+};
 use crate::instance::distributed::inserter::DistInserter;
 use crate::table::delete::to_grpc_delete_request;
 use crate::table::scan::{DatanodeInstance, TableScanPlan};
@@ -130,35 +133,15 @@ impl Table for DistTable {
                 projection: request.projection.clone(),
                 filters: request.filters.clone(),
                 limit: request.limit,
-                batches: Arc::new(RwLock::new(None)),
             }));
         }
 
         let schema = project_schema(self.schema(), request.projection.as_ref());
-        let schema_to_move = schema.clone();
-        let stream: Pin<Box<dyn Stream<Item = RecordBatchResult<RecordBatch>> + Send>> = Box::pin(
-            async_stream::try_stream! {
-                for partition_exec in partition_execs {
-                    partition_exec
-                        .maybe_init()
-                        .await
-                        .map_err(|e| DataFusionError::External(Box::new(e)))
-                        .context(InitRecordbatchStreamSnafu)?;
-                    let mut stream = partition_exec.as_stream().await.context(InitRecordbatchStreamSnafu)?;
-
-                    while let Some(batch) = stream.next().await{
-                        yield RecordBatch::try_from_df_record_batch(schema_to_move.clone(),batch.context(PollStreamSnafu)?)?
-                    }
-                }
-            },
-        );
-        let record_batch_stream = RecordBatchStreamAdaptor {
-            schema,
-            stream,
-            output_ordering: None,
-        };
-
-        Ok(Box::pin(record_batch_stream))
+        let scans = partition_execs
+            .into_iter()
+            .map(|partition_exec| move || async move { partition_exec.scan().await })
+            .collect();
+        Ok(scan_in_order(schema, scans, request.limit))
     }
 
     fn supports_filters_pushdown(
@@ -266,6 +249,105 @@ impl DistTable {
         }
         Ok(instances)
     }
+
+    /// Returns the committed sequences of the regions of the table by region ids,
+    /// read from `information_schema.region_statistics` of the datanodes.
+    pub(crate) async fn region_sequences(&self) -> Result<Vec<(u64, u64)>> {
+        let table_name = &self.table_name;
+        let peers = self
+            .catalog_manager
+            .partition_manager()
+            .find_table_region_leaders(table_name)
+            .await
+            .with_context(|_| FindTableRouteSnafu {
+                table_name: table_name.to_string(),
+            })?
+            .into_iter()
+            .collect::<HashSet<_>>();
+
+        let sql = format!(
+            "SELECT region_id, committed_sequence FROM {INFORMATION_SCHEMA_NAME}.region_statistics WHERE table_id = {}",
+            self.table_info.ident.table_id
+        );
+        let sql = &sql;
+        let datanode_clients = &self.catalog_manager.datanode_clients();
+        let outputs = futures::future::try_join_all(peers.iter().map(|peer| async move {
+            let client = datanode_clients.get_client(peer).await;
+            let db = Database::new(&table_name.catalog_name, &table_name.schema_name, client);
+            let output = db.sql(&sql).await.context(RequestDatanodeSnafu)?;
+            match output {
+                Output::Stream(stream) => RecordBatches::try_collect(stream)
+                    .await
+                    .context(CollectRecordbatchSnafu),
+                Output::RecordBatches(batches) => Ok(batches),
+                Output::AffectedRows(_) => Ok(RecordBatches::empty()),
+            }
+        }))
+        .await?;
+
+        let mut sequences = Vec::new();
+        for batch in outputs.iter().flat_map(|batches| batches.iter()) {
+            let (Some(region_ids), Some(committed_sequences)) = (
+                batch.column(0).as_any().downcast_ref::<UInt64Vector>(),
+                batch.column(1).as_any().downcast_ref::<UInt64Vector>(),
+            ) else {
+                continue;
+            };
+            sequences.extend(
+                region_ids
+                    .iter_data()
+                    .zip(committed_sequences.iter_data())
+                    .filter_map(|(region_id, sequence)| Some((region_id?, sequence?))),
+            );
+        }
+        sequences.sort_unstable();
+        Ok(sequences)
+    }
+}
+
+/// Chains the streams of `scans` into one. A scan only starts after the stream of
+/// the previous one is exhausted, so once `limit` rows are read the remaining scans
+/// are not started, and the current stream is dropped, which cancels its request.
+fn scan_in_order<F, Fut>(
+    schema: SchemaRef,
+    scans: Vec<F>,
+    limit: Option<usize>,
+) -> SendableRecordBatchStream
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<SendableRecordBatchStream>> + Send,
+{
+    let schema_to_move = schema.clone();
+    let stream: Pin<Box<dyn Stream<Item = RecordBatchResult<RecordBatch>> + Send>> = Box::pin(
+        async_stream::try_stream! {
+            let mut rows = 0;
+            for scan in scans {
+                if matches!(limit, Some(limit) if rows >= limit) {
+                    break;
+                }
+
+                let mut stream = scan()
+                    .await
+                    .map_err(|e| DataFusionError::External(Box::new(e)))
+                    .context(InitRecordbatchStreamSnafu)?;
+
+                while let Some(batch) = stream.next().await {
+                    let batch = batch?;
+                    rows += batch.num_rows();
+                    yield RecordBatch::try_from_df_record_batch(schema_to_move.clone(), batch.into_df_record_batch())?;
+                    if matches!(limit, Some(limit) if rows >= limit) {
+                        break;
+                    }
+                }
+            }
+        },
+    );
+    Box::pin(RecordBatchStreamAdaptor {
+        schema,
+        stream,
+        output_ordering: None,
+        metrics: None,
+    })
 }
 
 fn project_schema(table_schema: SchemaRef, projection: Option<&Vec<usize>>) -> SchemaRef {
@@ -315,10 +397,12 @@ impl PhysicalPlan for DistTableScan {
     ) -> QueryResult<SendableRecordBatchStream> {
         let exec = self.partition_execs[partition].clone();
         let stream = Box::pin(async move {
-            exec.maybe_init()
+            exec.scan()
                 .await
-                .map_err(|e| DataFusionError::External(Box::new(e)))?;
-            exec.as_stream().await
+                .map(|stream| {
+                    Box::pin(DfRecordBatchStreamAdapter::new(stream)) as DfSendableRecordBatchStream
+                })
+                .map_err(|e| DataFusionError::External(Box::new(e)))
         });
         let stream = AsyncRecordBatchStreamAdapter::new(self.schema(), stream);
         Ok(Box::pin(stream))
@@ -332,51 +416,33 @@ struct PartitionExec {
     projection: Option<Vec<usize>>,
     filters: Vec<Expr>,
     limit: Option<usize>,
-    batches: Arc<RwLock<Option<RecordBatches>>>,
 }
 
 impl PartitionExec {
-    async fn maybe_init(&self) -> Result<()> {
-        if self.batches.read().await.is_some() {
-            return Ok(());
-        }
-
-        let mut batches = self.batches.write().await;
-        if batches.is_some() {
-            return Ok(());
-        }
-
+    async fn scan(&self) -> Result<SendableRecordBatchStream> {
         let plan: TableScanPlan = TableScanPlan {
             table_name: self.table_name.clone(),
             projection: self.projection.clone(),
             filters: self.filters.clone(),
             limit: self.limit,
         };
-        let result = self.datanode_instance.grpc_table_scan(plan).await?;
-        let _ = batches.insert(result);
-        Ok(())
-    }
-
-    /// Notice: the record batch will be consumed.
-    async fn as_stream(&self) -> std::result::Result<DfSendableRecordBatchStream, DataFusionError> {
-        let mut batches = self.batches.write().await;
-        Ok(batches
-            .take()
-            .expect("should have been initialized in \"maybe_init\"")
-            .into_df_stream())
+        self.datanode_instance.grpc_table_scan(plan).await
     }
 }
 
 #[cfg(test)]
 pub(crate) mod test {
     use std::collections::HashMap;
-    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
 
     use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
     use common_meta::peer::Peer;
     use common_meta::rpc::router::{Region, RegionRoute, Table, TableRoute};
+    use common_recordbatch::util;
     use datafusion_expr::expr_fn::{and, binary_expr, col, or};
     use datafusion_expr::{lit, Operator};
+    use datatypes::prelude::ConcreteDataType;
+    use datatypes::vectors::{Int32Vector, VectorRef};
     use meta_client::client::MetaClient;
     use meter_core::collect::Collect;
     use meter_core::data::{ReadRecord, WriteRecord};
@@ -610,6 +676,96 @@ pub(crate) mod test {
         assert_eq!(range_columns_rule.regions(), &vec![1, 2, 3]);
     }
 
+    struct DropCounter(Arc<AtomicUsize>);
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            let _ = self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Counts of started scans, record batches read and dropped streams.
+    #[derive(Default)]
+    struct ScanCounters {
+        started: Arc<AtomicUsize>,
+        polled: Arc<AtomicUsize>,
+        dropped: Arc<AtomicUsize>,
+    }
+
+    impl ScanCounters {
+        fn get(&self) -> (usize, usize, usize) {
+            (
+                self.started.load(Ordering::Relaxed),
+                self.polled.load(Ordering::Relaxed),
+                self.dropped.load(Ordering::Relaxed),
+            )
+        }
+    }
+
+    /// Scans `partitions` partitions, each of them returns two batches of two rows.
+    async fn scan_partitions(
+        partitions: usize,
+        limit: Option<usize>,
+        counters: &ScanCounters,
+    ) -> usize {
+        let schema = Arc::new(Schema::new(vec![ColumnSchema::new(
+            "n",
+            ConcreteDataType::int32_datatype(),
+            false,
+        )]));
+        let scans = (0..partitions)
+            .map(|_| {
+                let schema = schema.clone();
+                let started = counters.started.clone();
+                let polled = counters.polled.clone();
+                let dropped = counters.dropped.clone();
+                move || async move {
+                    let _ = started.fetch_add(1, Ordering::Relaxed);
+                    let batch = RecordBatch::new(
+                        schema.clone(),
+                        vec![Arc::new(Int32Vector::from_slice([1, 2])) as VectorRef],
+                    )
+                    .unwrap();
+                    let guard = DropCounter(dropped);
+                    let stream =
+                        futures_util::stream::iter(vec![batch.clone(), batch]).map(move |batch| {
+                            let _ = &guard;
+                            let _ = polled.fetch_add(1, Ordering::Relaxed);
+                            Ok(batch)
+                        });
+                    Ok(Box::pin(RecordBatchStreamAdaptor {
+                        schema,
+                        stream: Box::pin(stream),
+                        output_ordering: None,
+                        metrics: None,
+                    }) as SendableRecordBatchStream)
+                }
+            })
+            .collect();
+
+        let batches = util::collect(scan_in_order(schema, scans, limit))
+            .await
+            .unwrap();
+        batches.iter().map(|batch| batch.num_rows()).sum()
+    }
+
+    #[tokio::test]
+    async fn test_scan_in_order() {
+        let counters = ScanCounters::default();
+        assert_eq!(12, scan_partitions(3, None, &counters).await);
+        assert_eq!((3, 6, 3), counters.get());
+
+        // The second partition is dropped after its first batch, and the third one
+        // is never scanned.
+        let counters = ScanCounters::default();
+        assert_eq!(6, scan_partitions(3, Some(5), &counters).await);
+        assert_eq!((2, 3, 2), counters.get());
+
+        let counters = ScanCounters::default();
+        assert_eq!(4, scan_partitions(3, Some(4), &counters).await);
+        assert_eq!((1, 2, 1), counters.get());
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_find_regions() {
         let partition_manager = Arc::new(PartitionRuleManager::new(Arc::new(TableRoutes::new(
@@ -17,7 +17,11 @@ use std::collections::HashMap;
 use api::helper::{push_vals, ColumnDataTypeWrapper};
 use api::v1::column::{SemanticType, Values};
 use api::v1::{Column, InsertRequest as GrpcInsertRequest};
+use catalog::CatalogManager;
+use common_catalog::format_full_table_name;
+use common_recordbatch::RecordBatch;
 use datatypes::prelude::*;
+use session::context::QueryContextRef;
 use snafu::{ensure, OptionExt, ResultExt};
 use store_api::storage::RegionNumber;
 use table::metadata::TableMeta;
@@ -70,6 +74,74 @@ pub(crate) fn to_grpc_insert_request(
     })
 }
 
+/// Inserts all columns of the `record_batch` into table `table_name` of the current schema.
+/// Every column of the `record_batch` must exist in the table with the same data type.
+///
+/// The vectors of the `record_batch` are written as they are. A distributed table splits
+/// them by its partition rule and writes each part to the datanode of the region.
+pub(crate) async fn insert_record_batch(
+    catalog_manager: &dyn CatalogManager,
+    table_name: &str,
+    record_batch: RecordBatch,
+    ctx: &QueryContextRef,
+) -> Result<usize> {
+    let catalog = ctx.current_catalog();
+    let schema = ctx.current_schema();
+    let full_table_name = format_full_table_name(&catalog, &schema, table_name);
+    let table = catalog_manager
+        .table(&catalog, &schema, table_name)
+        .await
+        .context(error::CatalogSnafu)?
+        .with_context(|| error::TableNotFoundSnafu {
+            table_name: &full_table_name,
+        })?;
+    let table_info = table.table_info();
+    let table_meta = &table_info.meta;
+
+    for column_schema in record_batch.schema.column_schemas() {
+        let table_column = table_meta
+            .schema
+            .column_schema_by_name(&column_schema.name)
+            .with_context(|| error::InvalidInsertRequestSnafu {
+                reason: format!(
+                    "column {} does not exist in table {}",
+                    column_schema.name, full_table_name
+                ),
+            })?;
+        ensure!(
+            table_column.data_type == column_schema.data_type,
+            error::InvalidInsertRequestSnafu {
+                reason: format!(
+                    "column {} of table {} is {}, but the record batch has {}",
+                    column_schema.name,
+                    full_table_name,
+                    table_column.data_type,
+                    column_schema.data_type
+                ),
+            }
+        );
+    }
+
+    let columns_values = record_batch
+        .schema
+        .column_schemas()
+        .iter()
+        .map(|column_schema| column_schema.name.clone())
+        .zip(record_batch.columns().iter().cloned())
+        .collect();
+    let request = InsertRequest {
+        catalog_name: catalog,
+        schema_name: schema,
+        table_name: table_name.to_string(),
+        columns_values,
+        region_number: 0,
+    };
+
+    table.insert(request).await.context(error::InsertSnafu {
+        table_name: full_table_name,
+    })
+}
+
 fn vector_to_grpc_column(
     table_meta: &TableMeta,
     column_name: &str,
@@ -20,7 +20,7 @@ use client::Database;
 use common_meta::table_name::TableName;
 use common_query::prelude::Expr;
 use common_query::Output;
-use common_recordbatch::RecordBatches;
+use common_recordbatch::SendableRecordBatchStream;
 use datafusion::datasource::DefaultTableSource;
 use datafusion_expr::{LogicalPlan, LogicalPlanBuilder};
 use snafu::ResultExt;
@@ -51,7 +51,12 @@ impl DatanodeInstance {
         self.db.delete(request).await
     }
 
-    pub(crate) async fn grpc_table_scan(&self, plan: TableScanPlan) -> Result<RecordBatches> {
+    /// Scans the table on the datanode. Record batches are streamed from the datanode
+    /// as the returned stream is polled.
+    pub(crate) async fn grpc_table_scan(
+        &self,
+        plan: TableScanPlan,
+    ) -> Result<SendableRecordBatchStream> {
         let logical_plan = self.build_logical_plan(&plan)?;
 
         let substrait_plan = DFLogicalSubstraitConvertor
@@ -63,8 +68,11 @@ impl DatanodeInstance {
             .logical_plan(substrait_plan.to_vec())
             .await
             .context(error::RequestDatanodeSnafu)?;
-        let Output::RecordBatches(record_batches) = result else { unreachable!() };
-        Ok(record_batches)
+        match result {
+            Output::Stream(stream) => Ok(stream),
+            Output::RecordBatches(record_batches) => Ok(record_batches.as_stream()),
+            Output::AffectedRows(_) => unreachable!(),
+        }
     }
 
     fn build_logical_plan(&self, table_scan: &TableScanPlan) -> Result<LogicalPlan> {
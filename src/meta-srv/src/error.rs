@@ -356,6 +356,20 @@ pub enum Error {
         source: common_procedure::Error,
     },
 
+    #[snafu(display("Failed to cancel procedure {procedure_id}, source: {source}"))]
+    CancelProcedure {
+        procedure_id: String,
+        location: Location,
+        source: common_procedure::Error,
+    },
+
+    #[snafu(display("Failed to retry procedure {procedure_id}, source: {source}"))]
+    RetryProcedure {
+        procedure_id: String,
+        location: Location,
+        source: common_procedure::Error,
+    },
+
     #[snafu(display("Schema already exists, name: {schema_name}"))]
     SchemaAlreadyExists {
         schema_name: String,
@@ -541,6 +555,8 @@ impl ErrorExt for Error {
             Error::InvalidCatalogValue { source, .. } => source.status_code(),
             Error::RecoverProcedure { source, .. }
             | Error::SubmitProcedure { source, .. }
+            | Error::CancelProcedure { source, .. }
+            | Error::RetryProcedure { source, .. }
             | Error::WaitProcedure { source, .. } => source.status_code(),
             Error::ShutdownServer { source, .. } | Error::StartHttp { source, .. } => {
                 source.status_code()
@@ -17,6 +17,7 @@ mod heartbeat;
 mod leader;
 mod meta;
 mod node_lease;
+mod procedure;
 mod route;
 
 use std::collections::HashMap;
@@ -89,6 +90,34 @@ pub fn make_admin_service(meta_srv: MetaSrv) -> Admin {
         },
     );
 
+    let router = router.route(
+        "/procedures",
+        procedure::ProceduresHandler {
+            procedure_manager: meta_srv.procedure_manager().clone(),
+        },
+    );
+
+    let router = router.route(
+        "/procedure",
+        procedure::ProcedureHandler {
+            procedure_manager: meta_srv.procedure_manager().clone(),
+        },
+    );
+
+    let router = router.route_post(
+        "/procedure/cancel",
+        procedure::CancelProcedureHandler {
+            procedure_manager: meta_srv.procedure_manager().clone(),
+        },
+    );
+
+    let router = router.route_post(
+        "/procedure/retry",
+        procedure::RetryProcedureHandler {
+            procedure_manager: meta_srv.procedure_manager().clone(),
+        },
+    );
+
     let router = Router::nest("/admin", router);
 
     Admin::new(router)
@@ -147,13 +176,20 @@ where
             })
             .unwrap_or_else(HashMap::new);
         let path = req.uri().path().to_owned();
-        Box::pin(async move { router.call(&path, query_params).await })
+        let method = req.method().clone();
+        Box::pin(async move { router.call(&method, &path, query_params).await })
     }
 }
 
 #[derive(Default)]
 pub struct Router {
-    handlers: HashMap<String, Box<dyn HttpHandler>>,
+    handlers: HashMap<String, Route>,
+}
+
+struct Route {
+    /// The only method accepted by the handler, or `None` to accept any method.
+    method: Option<http::Method>,
+    handler: Box<dyn HttpHandler>,
 }
 
 impl Router {
@@ -175,21 +211,40 @@ impl Router {
         Self { handlers }
     }
 
-    pub fn route(mut self, path: &str, handler: impl HttpHandler + 'static) -> Self {
+    pub fn route(self, path: &str, handler: impl HttpHandler + 'static) -> Self {
+        self.route_with_method(path, None, handler)
+    }
+
+    /// Routes POST requests of `path` to the handler, for handlers changing states.
+    pub fn route_post(self, path: &str, handler: impl HttpHandler + 'static) -> Self {
+        self.route_with_method(path, Some(http::Method::POST), handler)
+    }
+
+    fn route_with_method(
+        mut self,
+        path: &str,
+        method: Option<http::Method>,
+        handler: impl HttpHandler + 'static,
+    ) -> Self {
         check_path(path);
 
-        let _ = self.handlers.insert(path.to_owned(), Box::new(handler));
+        let route = Route {
+            method,
+            handler: Box::new(handler),
+        };
+        let _ = self.handlers.insert(path.to_owned(), route);
 
         self
     }
 
     pub async fn call(
         &self,
+        method: &http::Method,
         path: &str,
         params: HashMap<String, String>,
     ) -> Result<http::Response<BoxBody>, Infallible> {
-        let handler = match self.handlers.get(path) {
-            Some(handler) => handler,
+        let route = match self.handlers.get(path) {
+            Some(route) => route,
             None => {
                 return Ok(http::Response::builder()
                     .status(http::StatusCode::NOT_FOUND)
@@ -197,8 +252,18 @@ impl Router {
                     .unwrap())
             }
         };
+        if route
+            .method
+            .as_ref()
+            .map_or(false, |expected| expected != method)
+        {
+            return Ok(http::Response::builder()
+                .status(http::StatusCode::METHOD_NOT_ALLOWED)
+                .body(empty_body())
+                .unwrap());
+        }
 
-        let res = match handler.handle(path, &params).await {
+        let res = match route.handler.handle(path, &params).await {
             Ok(res) => res.map(boxed),
             Err(e) => http::Response::builder()
                 .status(http::StatusCode::INTERNAL_SERVER_ERROR)
@@ -284,7 +349,11 @@ mod tests {
         let router = Router::nest("/test_root", router);
 
         let res = router
-            .call("/test_root/test_node", HashMap::default())
+            .call(
+                &http::Method::GET,
+                "/test_root/test_node",
+                HashMap::default(),
+            )
             .await
             .unwrap();
 
@@ -296,13 +365,43 @@ mod tests {
         let router = Router::new();
 
         let res = router
-            .call("/test_root/test_node", HashMap::default())
+            .call(
+                &http::Method::GET,
+                "/test_root/test_node",
+                HashMap::default(),
+            )
             .await
             .unwrap();
 
         assert_eq!(http::StatusCode::NOT_FOUND, res.status());
     }
 
+    #[tokio::test]
+    async fn test_route_post() {
+        let router = Router::new().route_post("/test_node", MockOkHandler {});
+        let router = Router::nest("/test_root", router);
+
+        let res = router
+            .call(
+                &http::Method::GET,
+                "/test_root/test_node",
+                HashMap::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(http::StatusCode::METHOD_NOT_ALLOWED, res.status());
+
+        let res = router
+            .call(
+                &http::Method::POST,
+                "/test_root/test_node",
+                HashMap::default(),
+            )
+            .await
+            .unwrap();
+        assert!(res.status().is_success());
+    }
+
     #[tokio::test]
     async fn test_route_call_err() {
         let mock_handler = MockEmptyKeyErrorHandler {};
@@ -310,7 +409,11 @@ mod tests {
         let router = Router::nest("/test_root", router);
 
         let res = router
-            .call("/test_root/test_node", HashMap::default())
+            .call(
+                &http::Method::GET,
+                "/test_root/test_node",
+                HashMap::default(),
+            )
             .await
             .unwrap();
 
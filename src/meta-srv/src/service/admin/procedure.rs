@@ -0,0 +1,193 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_procedure::{ProcedureId, ProcedureManagerRef};
+use serde::Serialize;
+use snafu::{OptionExt, ResultExt};
+use tonic::codegen::http;
+
+use crate::error::{self, Result};
+use crate::service::admin::HttpHandler;
+
+/// Lists all procedures known by the procedure manager.
+pub struct ProceduresHandler {
+    pub procedure_manager: ProcedureManagerRef,
+}
+
+/// Inspects the procedure with specific `id`.
+pub struct ProcedureHandler {
+    pub procedure_manager: ProcedureManagerRef,
+}
+
+/// Requests to cancel the procedure with specific `id`.
+pub struct CancelProcedureHandler {
+    pub procedure_manager: ProcedureManagerRef,
+}
+
+/// Forces the failed procedure with specific `id` to retry.
+pub struct RetryProcedureHandler {
+    pub procedure_manager: ProcedureManagerRef,
+}
+
+#[async_trait::async_trait]
+impl HttpHandler for ProceduresHandler {
+    async fn handle(&self, _: &str, _: &HashMap<String, String>) -> Result<http::Response<String>> {
+        let mut procedures = self.procedure_manager.list_procedures();
+        procedures.sort_unstable_by_key(|procedure| procedure.start_time_ms);
+
+        to_json_response(&procedures)
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpHandler for ProcedureHandler {
+    async fn handle(
+        &self,
+        _: &str,
+        params: &HashMap<String, String>,
+    ) -> Result<http::Response<String>> {
+        let procedure_id = parse_procedure_id(params)?;
+
+        match self.procedure_manager.procedure_info(procedure_id) {
+            Some(info) => to_json_response(&info),
+            None => not_found(procedure_id),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpHandler for CancelProcedureHandler {
+    async fn handle(
+        &self,
+        _: &str,
+        params: &HashMap<String, String>,
+    ) -> Result<http::Response<String>> {
+        let procedure_id = parse_procedure_id(params)?;
+
+        let cancelled = self.procedure_manager.cancel(procedure_id).await.context(
+            error::CancelProcedureSnafu {
+                procedure_id: procedure_id.to_string(),
+            },
+        )?;
+
+        to_json_response(&HashMap::from([("cancelled", cancelled)]))
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpHandler for RetryProcedureHandler {
+    async fn handle(
+        &self,
+        _: &str,
+        params: &HashMap<String, String>,
+    ) -> Result<http::Response<String>> {
+        let procedure_id = parse_procedure_id(params)?;
+
+        let retried = self.procedure_manager.retry(procedure_id).await.context(
+            error::RetryProcedureSnafu {
+                procedure_id: procedure_id.to_string(),
+            },
+        )?;
+
+        to_json_response(&HashMap::from([("retried", retried)]))
+    }
+}
+
+fn parse_procedure_id(params: &HashMap<String, String>) -> Result<ProcedureId> {
+    let id = params
+        .get("id")
+        .context(error::MissingRequiredParameterSnafu { param: "id" })?;
+
+    ProcedureId::parse_str(id)
+        .ok()
+        .context(error::InvalidArgumentsSnafu {
+            err_msg: format!("invalid procedure id: {id}"),
+        })
+}
+
+fn to_json_response<T: Serialize>(value: &T) -> Result<http::Response<String>> {
+    let body = serde_json::to_string(value).context(error::SerializeToJsonSnafu {
+        input: "procedures".to_string(),
+    })?;
+
+    http::Response::builder()
+        .status(http::StatusCode::OK)
+        .body(body)
+        .context(error::InvalidHttpBodySnafu)
+}
+
+fn not_found(procedure_id: ProcedureId) -> Result<http::Response<String>> {
+    http::Response::builder()
+        .status(http::StatusCode::NOT_FOUND)
+        .body(format!("procedure {procedure_id} not found"))
+        .context(error::InvalidHttpBodySnafu)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use common_procedure::local::{LocalManager, ManagerConfig};
+
+    use super::*;
+    use crate::procedure::state_store::MetaStateStore;
+    use crate::service::store::memory::MemStore;
+
+    fn new_procedure_manager() -> ProcedureManagerRef {
+        let state_store = Arc::new(MetaStateStore::new(Arc::new(MemStore::new())));
+        Arc::new(LocalManager::new(ManagerConfig::default(), state_store))
+    }
+
+    #[tokio::test]
+    async fn test_list_procedures() {
+        let handler = ProceduresHandler {
+            procedure_manager: new_procedure_manager(),
+        };
+
+        let res = handler.handle("", &HashMap::new()).await.unwrap();
+        assert_eq!(http::StatusCode::OK, res.status());
+        assert_eq!("[]", res.body());
+    }
+
+    #[tokio::test]
+    async fn test_procedure_handlers_with_invalid_id() {
+        let procedure_manager = new_procedure_manager();
+        let handler = ProcedureHandler {
+            procedure_manager: procedure_manager.clone(),
+        };
+
+        let err = handler.handle("", &HashMap::new()).await.unwrap_err();
+        assert!(
+            matches!(err, error::Error::MissingRequiredParameter { .. }),
+            "{err}"
+        );
+
+        let params = HashMap::from([("id".to_string(), "not-an-id".to_string())]);
+        let err = handler.handle("", &params).await.unwrap_err();
+        assert!(
+            matches!(err, error::Error::InvalidArguments { .. }),
+            "{err}"
+        );
+
+        let params = HashMap::from([("id".to_string(), ProcedureId::random().to_string())]);
+        let res = handler.handle("", &params).await.unwrap();
+        assert_eq!(http::StatusCode::NOT_FOUND, res.status());
+
+        let handler = RetryProcedureHandler { procedure_manager };
+        let res = handler.handle("", &params).await.unwrap();
+        assert_eq!(r#"{"retried":false}"#, res.body());
+    }
+}
@@ -290,6 +290,7 @@ fn build_row_key_desc(
         )
         .default_constraint(column_schema.default_constraint().cloned())
         .is_nullable(column_schema.is_nullable())
+        .fulltext(column_schema.fulltext_analyzer())
         .build()
         .context(BuildColumnDescriptorSnafu {
             column_name: &column_schema.name,
@@ -333,6 +334,7 @@ fn build_column_family(
         )
         .default_constraint(column_schema.default_constraint().cloned())
         .is_nullable(column_schema.is_nullable())
+        .fulltext(column_schema.fulltext_analyzer())
         .build()
         .context(BuildColumnDescriptorSnafu {
             column_name: &column_schema.name,
@@ -25,7 +25,9 @@ use common_datasource::compression::CompressionType;
 use common_error::ext::BoxedError;
 use common_query::logical_plan::Expr;
 use common_recordbatch::error::ExternalSnafu;
-use common_recordbatch::{RecordBatch, RecordBatchStreamAdaptor, SendableRecordBatchStream};
+use common_recordbatch::{
+    RecordBatch, RecordBatchStreamAdaptor, SendableRecordBatchStream, SharedRecordBatchMetrics,
+};
 use common_telemetry::{info, logging};
 use datatypes::schema::Schema;
 use metrics::histogram;
@@ -150,7 +152,7 @@ impl<R: Region> Table for MitoTable<R> {
         // TODO(hl): Currently the API between frontend and datanode is under refactoring in
         // https://github.com/GreptimeTeam/greptimedb/issues/597 . Once it's finished, query plan
         // can carry filtered region info to avoid scanning all regions on datanode.
-        for region in regions.values() {
+        for (region_number, region) in regions.iter() {
             let snapshot = region
                 .snapshot(&read_ctx)
                 .map_err(BoxedError::new)
@@ -193,7 +195,7 @@ impl<R: Region> Table for MitoTable<R> {
             } else {
                 first_schema = Some(schema);
             }
-            readers.push(reader);
+            readers.push((*region_number, reader));
         }
 
         // TODO(hl): we assume table contains at least one region, but with region migration this
@@ -203,14 +205,34 @@ impl<R: Region> Table for MitoTable<R> {
         })?;
 
         let schema = stream_schema.clone();
-        let output_ordering = readers.get(0).and_then(|reader| reader.output_ordering());
+        let output_ordering = readers
+            .get(0)
+            .and_then(|(_, reader)| reader.output_ordering());
 
+        let metrics = SharedRecordBatchMetrics::default();
+        let stream_metrics = metrics.clone();
         let stream = Box::pin(async_stream::try_stream! {
-            for mut reader in readers {
+            for (region_number, mut reader) in readers {
+                let mut rows = 0;
                 while let Some(chunk) = reader.next_chunk().await.map_err(BoxedError::new).context(ExternalSnafu)? {
                     let chunk = reader.project_chunk(chunk);
-                    yield RecordBatch::new(stream_schema.clone(), chunk.columns)?
+                    let batch = RecordBatch::new(stream_schema.clone(), chunk.columns)?;
+                    rows += batch.num_rows();
+                    yield batch
                 }
+
+                let read_metrics = reader.read_metrics();
+                let counters = [
+                    ("rows_scanned", rows),
+                    ("memtable_rows", read_metrics.memtable_rows),
+                    ("sst_files", read_metrics.sst_files),
+                    ("pruned_sst_files", read_metrics.pruned_sst_files),
+                ];
+                stream_metrics.lock().unwrap().counters.extend(
+                    counters
+                        .into_iter()
+                        .map(|(name, value)| (format!("region_{region_number}_{name}"), value)),
+                );
             }
         });
 
@@ -218,6 +240,7 @@ impl<R: Region> Table for MitoTable<R> {
             schema,
             stream,
             output_ordering,
+            metrics: Some(metrics),
         }))
     }
 
@@ -365,10 +388,7 @@ impl<R: Region> Table for MitoTable<R> {
 
         Ok(regions
             .values()
-            .map(|region| RegionStat {
-                region_id: region.id().into(),
-                disk_usage_bytes: region.disk_usage_bytes(),
-            })
+            .map(|region| region.region_stat())
             .collect())
     }
 
@@ -28,8 +28,8 @@ use storage::write_batch::WriteBatch;
 use store_api::storage::{
     AlterRequest, Chunk, ChunkReader, CloseOptions, CompactContext, CreateOptions, EngineContext,
     FlushContext, GetRequest, GetResponse, OpenOptions, ReadContext, Region, RegionDescriptor,
-    RegionId, ScanRequest, ScanResponse, SchemaRef, Snapshot, StorageEngine, WriteContext,
-    WriteResponse,
+    RegionId, ScanRequest, ScanResponse, SchemaRef, SequenceNumber, Snapshot, StorageEngine,
+    WriteContext, WriteResponse,
 };
 
 pub type Result<T> = std::result::Result<T, MockError>;
@@ -202,6 +202,10 @@ impl Region for MockRegion {
         0
     }
 
+    fn committed_sequence(&self) -> SequenceNumber {
+        0
+    }
+
     async fn flush(&self, _ctx: &FlushContext) -> Result<()> {
         unimplemented!()
     }
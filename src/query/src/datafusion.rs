@@ -19,7 +19,9 @@ mod planner;
 
 use std::any::Any;
 use std::collections::HashMap;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::task::{Context, Poll};
 
 use async_trait::async_trait;
 use common_base::Plugins;
@@ -32,21 +34,24 @@ use common_query::prelude::ScalarUdf;
 use common_query::Output;
 use common_recordbatch::adapter::RecordBatchStreamAdapter;
 use common_recordbatch::{
-    EmptyRecordBatchStream, RecordBatch, RecordBatches, SendableRecordBatchStream,
+    EmptyRecordBatchStream, OrderOption, RecordBatch, RecordBatchMetrics, RecordBatchStream,
+    RecordBatches, SendableRecordBatchStream,
 };
 use common_telemetry::timer;
 use datafusion::common::Column;
 use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use datafusion::physical_plan::display::DisplayableExecutionPlan;
 use datafusion::physical_plan::ExecutionPlan;
 use datafusion::prelude::SessionContext;
 use datafusion_common::{ResolvedTableReference, ScalarValue};
 use datafusion_expr::{DmlStatement, Expr as DfExpr, LogicalPlan as DfLogicalPlan, WriteOp};
 use datatypes::prelude::VectorRef;
-use datatypes::schema::Schema;
-use futures_util::StreamExt;
+use datatypes::schema::{Schema, SchemaRef};
+use futures_util::{Stream, StreamExt};
 use session::context::QueryContextRef;
 use snafu::{ensure, OptionExt, ResultExt};
 use sql::ast::{BinaryOperator, Expr, Value};
+use sql::statements::function::CreateFunction;
 use table::requests::{DeleteRequest, InsertRequest};
 use table::TableRef;
 
@@ -65,6 +70,7 @@ use crate::physical_wrapper::PhysicalPlanWrapperRef;
 use crate::plan::LogicalPlan;
 use crate::planner::{DfLogicalPlanner, LogicalPlanner};
 use crate::query_engine::{DescribeResult, QueryEngineContext, QueryEngineState};
+use crate::sql_udf::create_sql_udf;
 use crate::{metrics, QueryEngine};
 
 pub struct DatafusionQueryEngine {
@@ -82,7 +88,7 @@ impl DatafusionQueryEngine {
         plan: LogicalPlan,
         query_ctx: QueryContextRef,
     ) -> Result<Output> {
-        let mut ctx = QueryEngineContext::new(self.state.session_state());
+        let mut ctx = self.state.query_engine_context();
 
         // `create_physical_plan` will optimize logical plan internally
         let physical_plan = self.create_physical_plan(&mut ctx, &plan).await?;
@@ -244,6 +250,23 @@ impl QueryEngine for DatafusionQueryEngine {
         self.state.register_udf(udf);
     }
 
+    fn register_schema_udf(&self, catalog: &str, schema: &str, udf: ScalarUdf) {
+        self.state.register_schema_udf(catalog, schema, udf);
+    }
+
+    fn deregister_schema_udf(&self, catalog: &str, schema: &str, name: &str) -> bool {
+        self.state.deregister_schema_udf(catalog, schema, name)
+    }
+
+    fn create_sql_udf(
+        &self,
+        catalog: &str,
+        schema: &str,
+        func: &CreateFunction,
+    ) -> Result<ScalarUdf> {
+        create_sql_udf(&self.state, catalog, schema, func)
+    }
+
     /// Note in SQL queries, aggregate names are looked up using
     /// lowercase unless the query uses quotes. For example,
     ///
@@ -363,13 +386,13 @@ impl QueryExecutor for DatafusionQueryEngine {
         plan: &Arc<dyn PhysicalPlan>,
     ) -> Result<SendableRecordBatchStream> {
         let _timer = timer!(metrics::METRIC_EXEC_PLAN_ELAPSED);
-        match plan.output_partitioning().partition_count() {
-            0 => Ok(Box::pin(EmptyRecordBatchStream::new(plan.schema()))),
-            1 => Ok(plan
-                .execute(0, ctx.state().task_ctx())
+        let stream: SendableRecordBatchStream = match plan.output_partitioning().partition_count() {
+            0 => return Ok(Box::pin(EmptyRecordBatchStream::new(plan.schema()))),
+            1 => plan
+                .execute(0, ctx.task_ctx())
                 .context(error::ExecutePhysicalPlanSnafu)
                 .map_err(BoxedError::new)
-                .context(QueryExecutionSnafu))?,
+                .context(QueryExecutionSnafu)?,
             _ => {
                 // merge into a single partition
                 let plan =
@@ -377,7 +400,7 @@ impl QueryExecutor for DatafusionQueryEngine {
                 // CoalescePartitionsExec must produce a single partition
                 assert_eq!(1, plan.output_partitioning().partition_count());
                 let df_stream = plan
-                    .execute(0, ctx.state().task_ctx())
+                    .execute(0, ctx.task_ctx())
                     .context(error::DatafusionSnafu {
                         msg: "Failed to execute DataFusion merge exec",
                     })
@@ -387,12 +410,55 @@ impl QueryExecutor for DatafusionQueryEngine {
                     .context(error::ConvertDfRecordBatchStreamSnafu)
                     .map_err(BoxedError::new)
                     .context(QueryExecutionSnafu)?;
-                Ok(Box::pin(stream))
+                Box::pin(stream)
             }
+        };
+
+        match plan.as_any().downcast_ref::<PhysicalPlanAdapter>() {
+            Some(plan) => Ok(Box::pin(PlanMetricsStream {
+                stream,
+                plan: plan.df_plan(),
+            })),
+            None => Ok(stream),
         }
     }
 }
 
+/// A stream that reports the plan producing it, annotated with the execution metrics,
+/// in its [RecordBatchMetrics].
+struct PlanMetricsStream {
+    stream: SendableRecordBatchStream,
+    plan: Arc<dyn ExecutionPlan>,
+}
+
+impl RecordBatchStream for PlanMetricsStream {
+    fn schema(&self) -> SchemaRef {
+        self.stream.schema()
+    }
+
+    fn output_ordering(&self) -> Option<&[OrderOption]> {
+        self.stream.output_ordering()
+    }
+
+    fn metrics(&self) -> Option<RecordBatchMetrics> {
+        let plan = DisplayableExecutionPlan::with_metrics(self.plan.as_ref())
+            .indent()
+            .to_string();
+        Some(RecordBatchMetrics {
+            counters: vec![],
+            plan: Some(plan),
+        })
+    }
+}
+
+impl Stream for PlanMetricsStream {
+    type Item = common_recordbatch::error::Result<RecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.stream.poll_next_unpin(cx)
+    }
+}
+
 fn convert_filter_to_df_filter(filter: Expr) -> Result<DfExpr> {
     match filter {
         Expr::BinaryOp { left, op, right } => {
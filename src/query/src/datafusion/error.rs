@@ -61,7 +61,10 @@ impl ErrorExt for InnerError {
 
         match self {
             // TODO(yingwen): Further categorize datafusion error.
-            Datafusion { .. } => StatusCode::EngineExecuteQuery,
+            Datafusion { source, .. } => match source {
+                DataFusionError::ResourcesExhausted(_) => StatusCode::ResourcesExhausted,
+                _ => StatusCode::EngineExecuteQuery,
+            },
             PhysicalPlanDowncast { .. } | ConvertSchema { .. } => StatusCode::Unexpected,
             ConvertDfRecordBatchStream { source, .. } => source.status_code(),
             ExecutePhysicalPlan { source, .. } => source.status_code(),
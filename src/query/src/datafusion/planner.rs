@@ -18,6 +18,9 @@ use std::sync::Arc;
 
 use arrow_schema::DataType;
 use catalog::table_source::DfTableSourceProvider;
+use common_function::scalars::postgres::CurrentSchemasFunction;
+use common_function::scalars::udf::create_udf;
+use common_function::scalars::Function;
 use common_query::logical_plan::create_aggregate_function;
 use datafusion::catalog::TableReference;
 use datafusion::error::Result as DfResult;
@@ -41,6 +44,7 @@ pub struct DfContextProviderAdapter {
     session_state: SessionState,
     tables: HashMap<String, Arc<dyn TableSource>>,
     table_provider: DfTableSourceProvider,
+    query_ctx: QueryContextRef,
 }
 
 impl DfContextProviderAdapter {
@@ -67,6 +71,7 @@ impl DfContextProviderAdapter {
             session_state,
             tables,
             table_provider,
+            query_ctx,
         })
     }
 }
@@ -104,7 +109,21 @@ impl ContextProvider for DfContextProviderAdapter {
     }
 
     fn get_function_meta(&self, name: &str) -> Option<Arc<ScalarUDF>> {
-        self.session_state.scalar_functions().get(name).cloned()
+        // functions depending on the session are bound to the query context here
+        let current_schemas = CurrentSchemasFunction::new(self.query_ctx.current_schema());
+        if name == current_schemas.name() {
+            return Some(Arc::new(
+                create_udf(Arc::new(current_schemas)).into_df_udf(),
+            ));
+        }
+
+        self.engine_state
+            .resolve_udf(
+                &self.query_ctx.current_catalog(),
+                &self.query_ctx.current_schema(),
+                name,
+            )
+            .map(|func| Arc::new(func.into_df_udf()))
     }
 
     fn get_aggregate_meta(&self, name: &str) -> Option<Arc<AggregateUDF>> {
@@ -12,11 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod aggregate;
+mod analyze;
 mod analyzer;
 mod commutativity;
 mod merge_scan;
 mod planner;
 mod utils;
 
+pub use analyze::DistAnalyzeExec;
 pub use analyzer::DistPlannerAnalyzer;
 pub use planner::DistExtensionPlanner;
@@ -0,0 +1,223 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Splits aggregations into a partial stage executed on datanodes and a
+//! final stage merging the partial results on the frontend.
+
+use arrow_schema::DataType;
+use datafusion_common::{DFSchema, DataFusionError, Result};
+use datafusion_expr::aggregate_function::AggregateFunction as AggregateFunctionType;
+use datafusion_expr::expr::AggregateFunction;
+use datafusion_expr::{
+    cast, count, max, min, sum, Aggregate, Expr, ExprSchemable, LogicalPlan, LogicalPlanBuilder,
+};
+
+use crate::dist_plan::merge_scan::MergeScanLogicalPlan;
+
+/// How the final stage combines the partial results of one aggregate expression.
+enum FinalAggregate {
+    /// Aggregate the partial column at the given index again with this function.
+    Merge(fn(Expr) -> Expr, usize),
+    /// `sum / count` from the partial columns at the given indexes, for `avg`.
+    Average { sum: usize, count: usize },
+}
+
+/// Output of one aggregate expression, built from the final aggregate columns.
+enum Output {
+    Column(usize),
+    Divide(usize, usize),
+}
+
+/// Check whether the aggregate expression can be split into partial and final stages.
+pub fn is_splittable(expr: &Expr, schema: &DFSchema) -> bool {
+    let Expr::AggregateFunction(AggregateFunction {
+        fun,
+        args,
+        distinct: false,
+        filter: None,
+        ..
+    }) = expr else {
+        return false;
+    };
+
+    match fun {
+        AggregateFunctionType::Count
+        | AggregateFunctionType::Sum
+        | AggregateFunctionType::Min
+        | AggregateFunctionType::Max => true,
+        // avg over decimals keeps its precision, which `sum / count` doesn't
+        AggregateFunctionType::Avg => {
+            args.len() == 1
+                && args[0]
+                    .get_type(schema)
+                    .map(|data_type| is_integer_or_float(&data_type))
+                    .unwrap_or(false)
+        }
+        _ => false,
+    }
+}
+
+fn is_integer_or_float(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+            | DataType::Float32
+            | DataType::Float64
+    )
+}
+
+/// Split the aggregate into `final (MergeScan (partial (input)))`.
+///
+/// The partial aggregate runs on each datanode and the final one merges its results.
+/// A projection is added on top to compute derived results like `avg` and to keep
+/// the output names of the original aggregate, so plans above it still resolve.
+/// All aggregate expressions must be [is_splittable].
+pub fn split_aggregate(aggregate: &Aggregate) -> Result<LogicalPlan> {
+    let group_len = aggregate.group_expr.len();
+
+    let mut partial_exprs = vec![];
+    let mut finals = Vec::with_capacity(aggregate.aggr_expr.len());
+    for expr in &aggregate.aggr_expr {
+        let Expr::AggregateFunction(AggregateFunction { fun, args, .. }) = expr else {
+            return Err(unsplittable(expr));
+        };
+        let final_aggregate = match fun {
+            // counts of each datanode are summed up
+            AggregateFunctionType::Count | AggregateFunctionType::Sum => {
+                FinalAggregate::Merge(sum, push_unique(&mut partial_exprs, expr.clone()))
+            }
+            AggregateFunctionType::Min => {
+                FinalAggregate::Merge(min, push_unique(&mut partial_exprs, expr.clone()))
+            }
+            AggregateFunctionType::Max => {
+                FinalAggregate::Merge(max, push_unique(&mut partial_exprs, expr.clone()))
+            }
+            AggregateFunctionType::Avg => FinalAggregate::Average {
+                sum: push_unique(&mut partial_exprs, sum(args[0].clone())),
+                count: push_unique(&mut partial_exprs, count(args[0].clone())),
+            },
+            _ => return Err(unsplittable(expr)),
+        };
+        finals.push(final_aggregate);
+    }
+
+    let partial = LogicalPlanBuilder::from(aggregate.input.as_ref().clone())
+        .aggregate(aggregate.group_expr.clone(), partial_exprs)?
+        .build()?;
+    let partial_columns = columns(partial.schema());
+    let (group_columns, aggr_columns) = partial_columns.split_at(group_len);
+    let merge_scan = MergeScanLogicalPlan::new(partial, false).into_logical_plan();
+
+    let mut final_exprs = vec![];
+    let outputs = finals
+        .into_iter()
+        .map(|final_aggregate| match final_aggregate {
+            FinalAggregate::Merge(fun, index) => Output::Column(push_unique(
+                &mut final_exprs,
+                fun(aggr_columns[index].clone()),
+            )),
+            FinalAggregate::Average { sum: s, count: c } => Output::Divide(
+                push_unique(&mut final_exprs, sum(aggr_columns[s].clone())),
+                push_unique(&mut final_exprs, sum(aggr_columns[c].clone())),
+            ),
+        })
+        .collect::<Vec<_>>();
+
+    let builder =
+        LogicalPlanBuilder::from(merge_scan).aggregate(group_columns.to_vec(), final_exprs)?;
+    if outputs.is_empty() {
+        return builder.build();
+    }
+
+    let final_columns = columns(builder.schema());
+    let mut projection = final_columns[..group_len].to_vec();
+    for (output, field) in outputs
+        .into_iter()
+        .zip(aggregate.schema.fields()[group_len..].iter())
+    {
+        let expr = match output {
+            Output::Column(index) => final_columns[group_len + index].clone(),
+            Output::Divide(dividend, divisor) => {
+                cast(
+                    final_columns[group_len + dividend].clone(),
+                    DataType::Float64,
+                ) / cast(
+                    final_columns[group_len + divisor].clone(),
+                    DataType::Float64,
+                )
+            }
+        };
+        projection.push(expr.alias(field.name()));
+    }
+    builder.project(projection)?.build()
+}
+
+/// Push the expression if there isn't an equal one, returns its index.
+fn push_unique(exprs: &mut Vec<Expr>, expr: Expr) -> usize {
+    match exprs.iter().position(|e| *e == expr) {
+        Some(index) => index,
+        None => {
+            exprs.push(expr);
+            exprs.len() - 1
+        }
+    }
+}
+
+/// Column expressions referring to each field of the schema.
+fn columns(schema: &DFSchema) -> Vec<Expr> {
+    schema
+        .fields()
+        .iter()
+        .map(|field| Expr::Column(field.qualified_column()))
+        .collect()
+}
+
+fn unsplittable(expr: &Expr) -> DataFusionError {
+    DataFusionError::Internal(format!("Aggregate expression {expr} can't be split"))
+}
+
+#[cfg(test)]
+mod test {
+    use datafusion_common::DFField;
+    use datafusion_expr::{avg, col, count_distinct, lit};
+
+    use super::*;
+
+    #[test]
+    fn check_splittable() {
+        let schema = DFSchema::new_with_metadata(
+            vec![
+                DFField::new_unqualified("i", DataType::Int64, true),
+                DFField::new_unqualified("d", DataType::Decimal128(10, 2), true),
+            ],
+            Default::default(),
+        )
+        .unwrap();
+
+        assert!(is_splittable(&count(lit(1u8)), &schema));
+        assert!(is_splittable(&max(col("d")), &schema));
+        assert!(is_splittable(&avg(col("i")), &schema));
+        assert!(!is_splittable(&avg(col("d")), &schema));
+        assert!(!is_splittable(&col("i"), &schema));
+
+        assert!(!is_splittable(&count_distinct(col("i")), &schema));
+    }
+}
@@ -0,0 +1,253 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Execution plan of "EXPLAIN ANALYZE" in distributed mode.
+
+use std::any::Any;
+use std::fmt::Write;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use arrow_schema::SchemaRef as ArrowSchemaRef;
+use common_meta::peer::Peer;
+use common_query::physical_plan::TaskContext;
+use common_recordbatch::{DfRecordBatch, DfSendableRecordBatchStream, RecordBatchMetrics};
+use datafusion::physical_plan::display::DisplayableExecutionPlan;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{DisplayFormatType, ExecutionPlan, Partitioning};
+use datafusion_common::{DataFusionError, Result, Statistics};
+use datafusion_physical_expr::PhysicalSortExpr;
+use datatypes::arrow::array::StringBuilder;
+use futures_util::StreamExt;
+
+use crate::dist_plan::merge_scan::MergeScanExec;
+
+/// Like DataFusion's `AnalyzeExec`, executes the input plan and returns it annotated with
+/// the execution metrics. The plans executed on the datanodes are rendered with their
+/// metrics under the [MergeScanExec]s that sent them.
+#[derive(Debug)]
+pub struct DistAnalyzeExec {
+    verbose: bool,
+    input: Arc<dyn ExecutionPlan>,
+    schema: ArrowSchemaRef,
+}
+
+impl DistAnalyzeExec {
+    pub fn new(verbose: bool, input: Arc<dyn ExecutionPlan>, schema: ArrowSchemaRef) -> Self {
+        Self {
+            verbose,
+            input,
+            schema,
+        }
+    }
+}
+
+impl ExecutionPlan for DistAnalyzeExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> ArrowSchemaRef {
+        self.schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        mut children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(Self::new(
+            self.verbose,
+            children.pop().unwrap(),
+            self.schema.clone(),
+        )))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<DfSendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Internal(format!(
+                "DistAnalyzeExec invalid partition. Expected 0, got {partition}"
+            )));
+        }
+
+        let verbose = self.verbose;
+        let input = self.input.clone();
+        let schema = self.schema.clone();
+        let output = async move {
+            let start = Instant::now();
+            let num_partitions = input.output_partitioning().partition_count();
+            let rows = futures::future::try_join_all((0..num_partitions).map(|partition| {
+                let stream = input.execute(partition, context.clone());
+                async move {
+                    let mut stream = stream?;
+                    let mut rows = 0;
+                    while let Some(batch) = stream.next().await {
+                        rows += batch?.num_rows();
+                    }
+                    Ok::<_, DataFusionError>(rows)
+                }
+            }))
+            .await?;
+
+            let total_rows = rows.into_iter().sum();
+            create_output_batch(verbose, input.as_ref(), total_rows, start.elapsed(), schema)
+        };
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema.clone(),
+            futures::stream::once(output),
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "DistAnalyzeExec verbose={}", self.verbose)
+    }
+}
+
+fn create_output_batch(
+    verbose: bool,
+    input: &dyn ExecutionPlan,
+    total_rows: usize,
+    duration: Duration,
+    schema: ArrowSchemaRef,
+) -> Result<DfRecordBatch> {
+    let mut plan_types = StringBuilder::new();
+    let mut plans = StringBuilder::new();
+
+    plan_types.append_value("Plan with Metrics");
+    plans.append_value(render_plan(input, false));
+    if verbose {
+        plan_types.append_value("Plan with Full Metrics");
+        plans.append_value(render_plan(input, true));
+
+        plan_types.append_value("Output Rows");
+        plans.append_value(total_rows.to_string());
+
+        plan_types.append_value("Duration");
+        plans.append_value(format!("{duration:?}"));
+    }
+
+    Ok(DfRecordBatch::try_new(
+        schema,
+        vec![Arc::new(plan_types.finish()), Arc::new(plans.finish())],
+    )?)
+}
+
+/// Renders the `plan` with its metrics. The plans executed on the datanodes are indented
+/// under their [MergeScanExec]s.
+fn render_plan(plan: &dyn ExecutionPlan, full: bool) -> String {
+    let displayable = if full {
+        DisplayableExecutionPlan::with_full_metrics(plan)
+    } else {
+        DisplayableExecutionPlan::with_metrics(plan)
+    };
+    let mut sub_stages = Vec::new();
+    collect_sub_stage_metrics(plan, &mut sub_stages);
+
+    splice_sub_stages(&displayable.indent().to_string(), sub_stages)
+}
+
+/// Collects the sub stage metrics of the [MergeScanExec]s in the `plan`, in the same
+/// (pre-)order as they are displayed.
+fn collect_sub_stage_metrics(
+    plan: &dyn ExecutionPlan,
+    sub_stages: &mut Vec<Vec<(Peer, RecordBatchMetrics)>>,
+) {
+    if let Some(merge_scan) = plan.as_any().downcast_ref::<MergeScanExec>() {
+        sub_stages.push(merge_scan.sub_stage_metrics());
+    }
+    for child in plan.children() {
+        collect_sub_stage_metrics(child.as_ref(), sub_stages);
+    }
+}
+
+/// Inserts the plans of the sub stages under the lines of their [MergeScanExec]s in the
+/// displayed `plan`.
+fn splice_sub_stages(plan: &str, sub_stages: Vec<Vec<(Peer, RecordBatchMetrics)>>) -> String {
+    let mut sub_stages = sub_stages.into_iter();
+    let mut output = String::with_capacity(plan.len());
+    for line in plan.lines() {
+        output.push_str(line);
+        output.push('\n');
+
+        let node = line.trim_start();
+        if !node.starts_with("MergeScanExec") {
+            continue;
+        }
+        let Some(metrics) = sub_stages.next() else {
+            continue;
+        };
+        let indent = line.len() - node.len() + 2;
+        for (peer, metrics) in metrics {
+            let _ = writeln!(output, "{:indent$}SubStage: peer={peer}", "");
+            for line in metrics.plan.iter().flat_map(|plan| plan.lines()) {
+                let _ = writeln!(output, "{:indent$}  {line}", "");
+            }
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_splice_sub_stages() {
+        let plan = "\
+CoalescePartitionsExec, metrics=[output_rows=3]
+  MergeScanExec: peers=[peer-1(127.0.0.1:4100), ], metrics=[output_rows=3]
+";
+        let metrics = RecordBatchMetrics {
+            counters: vec![],
+            plan: Some(
+                "\
+ProjectionExec: expr=[i@0 as i], metrics=[output_rows=3]
+  ExecutionPlan(PlaceHolder), metrics=[output_rows=3, region_0_memtable_rows=3]
+"
+                .to_string(),
+            ),
+        };
+        let peer = Peer::new(1, "127.0.0.1:4100");
+
+        let output = splice_sub_stages(plan, vec![vec![(peer, metrics)]]);
+        let expected = "\
+CoalescePartitionsExec, metrics=[output_rows=3]
+  MergeScanExec: peers=[peer-1(127.0.0.1:4100), ], metrics=[output_rows=3]
+    SubStage: peer=peer-1(127.0.0.1:4100)
+      ProjectionExec: expr=[i@0 as i], metrics=[output_rows=3]
+        ExecutionPlan(PlaceHolder), metrics=[output_rows=3, region_0_memtable_rows=3]
+";
+        assert_eq!(expected, output);
+    }
+}
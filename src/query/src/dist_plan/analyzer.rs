@@ -19,6 +19,7 @@ use datafusion_common::tree_node::{Transformed, TreeNode, TreeNodeVisitor, Visit
 use datafusion_expr::{Extension, LogicalPlan};
 use datafusion_optimizer::analyzer::AnalyzerRule;
 
+use crate::dist_plan::aggregate::split_aggregate;
 use crate::dist_plan::commutativity::{
     partial_commutative_transformer, Categorizer, Commutativity,
 };
@@ -108,7 +109,10 @@ impl DistPlannerAnalyzer {
             return Ok(Transformed::No(plan));
         }
 
-        if visitor.stop_node.is_some() {
+        if visitor.split_stop_node && let LogicalPlan::Aggregate(aggregate) = &plan {
+            // split the stop node into partial and final stages around the merge scan
+            plan = split_aggregate(aggregate)?;
+        } else if visitor.stop_node.is_some() {
             // insert merge scan between the stop node and its child
             let children = plan.inputs();
             let mut new_children = Vec::with_capacity(children.len());
@@ -161,6 +165,8 @@ struct CommutativeVisitor {
     next_stage: Vec<LogicalPlan>,
     // hash of the stop node
     stop_node: Option<u64>,
+    // if the stop node should be split into partial and final stages
+    split_stop_node: bool,
 }
 
 impl TreeNodeVisitor for CommutativeVisitor {
@@ -182,6 +188,13 @@ impl TreeNodeVisitor for CommutativeVisitor {
     }
 
     fn post_visit(&mut self, plan: &LogicalPlan) -> datafusion_common::Result<VisitRecursion> {
+        if !self.next_stage.is_empty() {
+            // nodes above a partial commutative one expect its merged result (e.g. a filter
+            // or an aggregation over a limit), so they can't be pushed down any more
+            self.stop_node = Some(utils::hash_plan(plan));
+            return Ok(VisitRecursion::Stop);
+        }
+
         match Categorizer::check_plan(plan) {
             Commutativity::Commutative => {}
             Commutativity::PartialCommutative => {
@@ -201,6 +214,11 @@ impl TreeNodeVisitor for CommutativeVisitor {
                     self.next_stage.push(plan)
                 }
             },
+            Commutativity::Splittable => {
+                self.stop_node = Some(utils::hash_plan(plan));
+                self.split_stop_node = true;
+                return Ok(VisitRecursion::Stop);
+            }
             Commutativity::NonCommutative
             | Commutativity::Unimplemented
             | Commutativity::Unsupported => {
@@ -218,6 +236,7 @@ impl CommutativeVisitor {
         Self {
             next_stage: vec![],
             stop_node: None,
+            split_stop_node: false,
         }
     }
 }
@@ -225,13 +244,12 @@ impl CommutativeVisitor {
 #[cfg(test)]
 mod test {
     use datafusion::datasource::DefaultTableSource;
-    use datafusion_expr::{avg, col, lit, Expr, LogicalPlanBuilder};
+    use datafusion_expr::{avg, col, lit, max, sum, Expr, LogicalPlanBuilder};
     use table::table::adapter::DfTableProviderAdapter;
     use table::table::numbers::NumbersTable;
 
     use super::*;
 
-    #[ignore = "Projection is disabled for https://github.com/apache/arrow-datafusion/issues/6489"]
     #[test]
     fn transform_simple_projection_filter() {
         let numbers_table = Arc::new(NumbersTable::new(0)) as _;
@@ -280,9 +298,74 @@ mod test {
         let config = ConfigOptions::default();
         let result = DistPlannerAnalyzer {}.analyze(plan, &config).unwrap();
         let expected = String::from(
-            "Aggregate: groupBy=[[]], aggr=[[AVG(t.number)]]\
-            \n  MergeScan [is_placeholder=false]\
-            \n    TableScan: t",
+            "Projection: CAST(SUM(SUM(t.number)) AS Float64) / CAST(SUM(COUNT(t.number)) AS Float64) AS AVG(t.number)\
+            \n  Aggregate: groupBy=[[]], aggr=[[SUM(SUM(t.number)), SUM(COUNT(t.number))]]\
+            \n    MergeScan [is_placeholder=false]\
+            \n      Aggregate: groupBy=[[]], aggr=[[SUM(t.number), COUNT(t.number)]]\
+            \n        TableScan: t",
+        );
+        assert_eq!(expected, format!("{:?}", result));
+    }
+
+    #[test]
+    fn transform_group_by_aggregator() {
+        let numbers_table = Arc::new(NumbersTable::new(0)) as _;
+        let table_source = Arc::new(DefaultTableSource::new(Arc::new(
+            DfTableProviderAdapter::new(numbers_table),
+        )));
+
+        let plan = LogicalPlanBuilder::scan_with_filters("t", table_source, None, vec![])
+            .unwrap()
+            .filter(col("number").lt(lit(10)))
+            .unwrap()
+            .aggregate(
+                vec![col("number")],
+                vec![sum(col("number")), avg(col("number")), max(col("number"))],
+            )
+            .unwrap()
+            .sort(vec![col("number").sort(true, false)])
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let config = ConfigOptions::default();
+        let result = DistPlannerAnalyzer {}.analyze(plan, &config).unwrap();
+        let expected = String::from(
+            "Sort: t.number ASC NULLS LAST\
+            \n  Projection: t.number, SUM(SUM(t.number)) AS SUM(t.number), CAST(SUM(SUM(t.number)) AS Float64) / CAST(SUM(COUNT(t.number)) AS Float64) AS AVG(t.number), MAX(MAX(t.number)) AS MAX(t.number)\
+            \n    Aggregate: groupBy=[[t.number]], aggr=[[SUM(SUM(t.number)), SUM(COUNT(t.number)), MAX(MAX(t.number))]]\
+            \n      MergeScan [is_placeholder=false]\
+            \n        Aggregate: groupBy=[[t.number]], aggr=[[SUM(t.number), COUNT(t.number), MAX(t.number)]]\
+            \n          Filter: t.number < Int32(10)\
+            \n            TableScan: t",
+        );
+        assert_eq!(expected, format!("{:?}", result));
+    }
+
+    #[test]
+    fn transform_aggregator_over_limit() {
+        let numbers_table = Arc::new(NumbersTable::new(0)) as _;
+        let table_source = Arc::new(DefaultTableSource::new(Arc::new(
+            DfTableProviderAdapter::new(numbers_table),
+        )));
+
+        let plan = LogicalPlanBuilder::scan_with_filters("t", table_source, None, vec![])
+            .unwrap()
+            .limit(0, Some(10))
+            .unwrap()
+            .aggregate(Vec::<Expr>::new(), vec![sum(col("number"))])
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let config = ConfigOptions::default();
+        let result = DistPlannerAnalyzer {}.analyze(plan, &config).unwrap();
+        let expected = String::from(
+            "Aggregate: groupBy=[[]], aggr=[[SUM(t.number)]]\
+            \n  Limit: skip=0, fetch=10\
+            \n    MergeScan [is_placeholder=false]\
+            \n      Limit: skip=0, fetch=10\
+            \n        TableScan: t",
         );
         assert_eq!(expected, format!("{:?}", result));
     }
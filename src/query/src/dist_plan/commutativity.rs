@@ -14,17 +14,23 @@
 
 use std::sync::Arc;
 
-use datafusion_expr::{Expr, LogicalPlan, UserDefinedLogicalNode};
+use datafusion_common::tree_node::{TreeNode, VisitRecursion};
+use datafusion_expr::{Aggregate, Expr, LogicalPlan, UserDefinedLogicalNode};
 use promql::extension_plan::{
     EmptyMetric, InstantManipulate, RangeManipulate, SeriesDivide, SeriesNormalize,
 };
 
+use crate::dist_plan::aggregate;
+
 #[allow(dead_code)]
 pub enum Commutativity {
     Commutative,
     PartialCommutative,
     ConditionalCommutative(Option<Transformer>),
     TransformedCommutative(Option<Transformer>),
+    /// Can be split into a partial stage executed before the merge and a final
+    /// stage executed after it, like aggregations.
+    Splittable,
     NonCommutative,
     Unimplemented,
     /// For unrelated plans like DDL
@@ -36,14 +42,13 @@ pub struct Categorizer {}
 impl Categorizer {
     pub fn check_plan(plan: &LogicalPlan) -> Commutativity {
         match plan {
-            LogicalPlan::Projection(_) => Commutativity::Unimplemented,
+            LogicalPlan::Projection(projection) => Self::check_exprs(&projection.expr),
             // TODO(ruihang): Change this to Commutative once Like is supported in substrait
-            LogicalPlan::Filter(filter) => Self::check_expr(&filter.predicate),
-            LogicalPlan::Window(_) => Commutativity::Unimplemented,
-            LogicalPlan::Aggregate(_) => {
-                // check all children exprs and uses the strictest level
-                Commutativity::Unimplemented
+            LogicalPlan::Filter(filter) => {
+                Self::check_exprs(std::slice::from_ref(&filter.predicate))
             }
+            LogicalPlan::Window(_) => Commutativity::Unimplemented,
+            LogicalPlan::Aggregate(aggregate) => Self::check_aggregate(aggregate),
             LogicalPlan::Sort(_) => Commutativity::NonCommutative,
             LogicalPlan::Join(_) => Commutativity::NonCommutative,
             LogicalPlan::CrossJoin(_) => Commutativity::NonCommutative,
@@ -73,6 +78,46 @@ impl Categorizer {
         }
     }
 
+    /// Check all expressions and their sub-expressions, uses the strictest level.
+    pub fn check_exprs(exprs: &[Expr]) -> Commutativity {
+        let mut commutative = true;
+        for expr in exprs {
+            let _ = expr.apply(&mut |expr| {
+                if matches!(Self::check_expr(expr), Commutativity::Commutative) {
+                    Ok(VisitRecursion::Continue)
+                } else {
+                    commutative = false;
+                    Ok(VisitRecursion::Stop)
+                }
+            });
+        }
+
+        if commutative {
+            Commutativity::Commutative
+        } else {
+            Commutativity::Unimplemented
+        }
+    }
+
+    fn check_aggregate(aggregate: &Aggregate) -> Commutativity {
+        let input_schema = aggregate.input.schema();
+        let is_commutative =
+            |exprs: &[Expr]| matches!(Self::check_exprs(exprs), Commutativity::Commutative);
+        let splittable = is_commutative(&aggregate.group_expr)
+            && aggregate.aggr_expr.iter().all(|expr| match expr {
+                Expr::AggregateFunction(function) => {
+                    aggregate::is_splittable(expr, input_schema) && is_commutative(&function.args)
+                }
+                _ => false,
+            });
+
+        if splittable {
+            Commutativity::Splittable
+        } else {
+            Commutativity::Unimplemented
+        }
+    }
+
     pub fn check_extension_plan(plan: &dyn UserDefinedLogicalNode) -> Commutativity {
         match plan.name() {
             name if name == EmptyMetric::name()
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use std::any::Any;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use arrow_schema::SchemaRef as ArrowSchemaRef;
 use async_stream::try_stream;
@@ -28,8 +28,10 @@ use common_query::Output;
 use common_recordbatch::adapter::DfRecordBatchStreamAdapter;
 use common_recordbatch::error::ExternalSnafu;
 use common_recordbatch::{
-    DfSendableRecordBatchStream, RecordBatchStreamAdaptor, SendableRecordBatchStream,
+    DfSendableRecordBatchStream, RecordBatchMetrics, RecordBatchStreamAdaptor,
+    SendableRecordBatchStream,
 };
+use datafusion::physical_plan::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
 use datafusion::physical_plan::{DisplayFormatType, ExecutionPlan, Partitioning};
 use datafusion_common::{DataFusionError, Result, Statistics};
 use datafusion_expr::{Extension, LogicalPlan, UserDefinedLogicalNodeCore};
@@ -113,6 +115,9 @@ pub struct MergeScanExec {
     substrait_plan: Bytes,
     arrow_schema: ArrowSchemaRef,
     clients: Arc<DatanodeClients>,
+    metric: ExecutionPlanMetricsSet,
+    /// Metrics of the sub stages executed on each peer, collected once their streams end.
+    sub_stage_metrics: Arc<Mutex<Vec<(Peer, RecordBatchMetrics)>>>,
 }
 
 impl MergeScanExec {
@@ -129,14 +134,18 @@ impl MergeScanExec {
             substrait_plan,
             arrow_schema,
             clients,
+            metric: ExecutionPlanMetricsSet::new(),
+            sub_stage_metrics: Arc::default(),
         }
     }
 
-    pub fn to_stream(&self) -> Result<SendableRecordBatchStream> {
+    pub fn to_stream(&self, partition: usize) -> Result<SendableRecordBatchStream> {
         let substrait_plan = self.substrait_plan.to_vec();
         let peers = self.peers.clone();
         let clients = self.clients.clone();
         let table = self.table.clone();
+        let metric = BaselineMetrics::new(&self.metric, partition);
+        let sub_stage_metrics = self.sub_stage_metrics.clone();
 
         let stream = try_stream! {
             for peer in peers {
@@ -162,16 +171,23 @@ impl MergeScanExec {
                     }
                     Output::RecordBatches(record_batches) => {
                         for batch in record_batches.into_iter() {
+                            metric.record_output(batch.num_rows());
                             yield batch;
                         }
                     }
                     Output::Stream(mut stream) => {
                         while let Some(batch) = stream.next().await {
-                            yield batch?;
+                            let batch = batch?;
+                            metric.record_output(batch.num_rows());
+                            yield batch;
+                        }
+                        if let Some(metrics) = stream.metrics() {
+                            sub_stage_metrics.lock().unwrap().push((peer, metrics));
                         }
                     }
                 }
             }
+            metric.done();
         };
 
         Ok(Box::pin(RecordBatchStreamAdaptor {
@@ -183,8 +199,14 @@ impl MergeScanExec {
             ),
             stream: Box::pin(stream),
             output_ordering: None,
+            metrics: None,
         }))
     }
+
+    /// Returns the metrics of the sub stages executed on the peers.
+    pub fn sub_stage_metrics(&self) -> Vec<(Peer, RecordBatchMetrics)> {
+        self.sub_stage_metrics.lock().unwrap().clone()
+    }
 }
 
 impl ExecutionPlan for MergeScanExec {
@@ -219,16 +241,22 @@ impl ExecutionPlan for MergeScanExec {
 
     fn execute(
         &self,
-        _partition: usize,
+        partition: usize,
         _context: Arc<TaskContext>,
     ) -> Result<DfSendableRecordBatchStream> {
-        Ok(Box::pin(DfRecordBatchStreamAdapter::new(self.to_stream()?)))
+        Ok(Box::pin(DfRecordBatchStreamAdapter::new(
+            self.to_stream(partition)?,
+        )))
     }
 
     fn statistics(&self) -> Statistics {
         Statistics::default()
     }
 
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metric.clone_inner())
+    }
+
     fn fmt_as(&self, _t: DisplayFormatType, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "MergeScanExec: peers=[")?;
         for peer in self.peers.iter() {
@@ -30,7 +30,9 @@ pub mod physical_wrapper;
 pub mod plan;
 pub mod planner;
 pub mod query_engine;
+pub mod range_select;
 pub mod sql;
+pub mod sql_udf;
 
 pub use crate::datafusion::DfContextProviderAdapter;
 pub use crate::query_engine::{
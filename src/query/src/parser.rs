@@ -275,7 +275,7 @@ mod test {
             having: None, \
             named_window: [], \
             qualify: None \
-            }), order_by: [], limit: None, offset: None, fetch: None, locks: [] } }))");
+            }), order_by: [], limit: None, offset: None, fetch: None, locks: [] }, align: None }))");
 
         assert_eq!(format!("{stmt:?}"), expected);
     }
@@ -29,6 +29,7 @@ use crate::error::{PlanSqlSnafu, QueryPlanSnafu, Result, SqlSnafu};
 use crate::parser::QueryStatement;
 use crate::plan::LogicalPlan;
 use crate::query_engine::QueryEngineState;
+use crate::range_select::planner::RangeSelectPlanner;
 use crate::DfContextProviderAdapter;
 
 #[async_trait]
@@ -69,6 +70,12 @@ impl DfLogicalPlanner {
 
         let sql_to_rel = SqlToRel::new_with_options(&context_provider, parser_options);
 
+        if let Statement::Query(query) = &stmt && let Some(align) = &query.align {
+            return RangeSelectPlanner::new(&sql_to_rel)
+                .plan(query, align)
+                .map(LogicalPlan::DfPlan);
+        }
+
         let result = sql_to_rel.statement_to_plan(df_stmt).with_context(|_| {
             let sql = if let Statement::Query(query) = stmt {
                 query.inner.to_string()
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod context;
+mod memory_pool;
 pub mod options;
 mod state;
 
@@ -30,6 +31,7 @@ use common_query::Output;
 use datatypes::schema::Schema;
 use partition::manager::PartitionRuleManager;
 use session::context::QueryContextRef;
+use sql::statements::function::CreateFunction;
 use sql::statements::statement::Statement;
 use table::TableRef;
 
@@ -39,6 +41,7 @@ use crate::error::Result;
 use crate::plan::LogicalPlan;
 use crate::planner::LogicalPlanner;
 pub use crate::query_engine::context::QueryEngineContext;
+use crate::query_engine::options::QueryConfig;
 pub use crate::query_engine::state::QueryEngineState;
 
 pub type SqlStatementExecutorRef = Arc<dyn SqlStatementExecutor>;
@@ -73,6 +76,22 @@ pub trait QueryEngine: Send + Sync {
 
     fn register_udf(&self, udf: ScalarUdf);
 
+    /// Registers the udf in the schema, it's only callable in queries whose current
+    /// schema is the schema.
+    fn register_schema_udf(&self, catalog: &str, schema: &str, udf: ScalarUdf);
+
+    /// Deregisters the udf of the schema, returns whether it's registered.
+    fn deregister_schema_udf(&self, catalog: &str, schema: &str, name: &str) -> bool;
+
+    /// Creates the udf defined by `CREATE FUNCTION` in the schema, which is not
+    /// registered yet.
+    fn create_sql_udf(
+        &self,
+        catalog: &str,
+        schema: &str,
+        func: &CreateFunction,
+    ) -> Result<ScalarUdf>;
+
     fn register_aggregate_function(&self, func: AggregateFunctionMetaRef);
 
     fn register_function(&self, func: FunctionRef);
@@ -93,6 +112,7 @@ impl QueryEngineFactory {
             None,
             None,
             Default::default(),
+            &QueryConfig::default(),
         )
     }
 
@@ -102,6 +122,7 @@ impl QueryEngineFactory {
         partition_manager: Option<Arc<PartitionRuleManager>>,
         clients: Option<Arc<DatanodeClients>>,
         plugins: Arc<Plugins>,
+        config: &QueryConfig,
     ) -> Self {
         let state = Arc::new(QueryEngineState::new(
             catalog_manager,
@@ -109,6 +130,7 @@ impl QueryEngineFactory {
             partition_manager,
             clients,
             plugins.clone(),
+            config,
         ));
         let query_engine = Arc::new(DatafusionQueryEngine::new(state, plugins));
         register_functions(&query_engine);
@@ -12,20 +12,45 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use datafusion::execution::context::SessionState;
+use std::sync::Arc;
+
+use datafusion::execution::context::{SessionState, TaskContext};
+use datafusion::execution::runtime_env::RuntimeEnv;
 
 #[derive(Debug)]
 pub struct QueryEngineContext {
     state: SessionState,
+    task_ctx: Arc<TaskContext>,
 }
 
 impl QueryEngineContext {
     pub fn new(state: SessionState) -> Self {
-        Self { state }
+        let task_ctx = state.task_ctx();
+        Self { state, task_ctx }
+    }
+
+    /// Creates a context that executes the query with given runtime instead of the
+    /// runtime of the session.
+    pub fn with_runtime_env(state: SessionState, runtime: Arc<RuntimeEnv>) -> Self {
+        let task_ctx = Arc::new(TaskContext::new(
+            None,
+            state.session_id().to_string(),
+            state.config().clone(),
+            state.scalar_functions().clone(),
+            state.aggregate_functions().clone(),
+            runtime,
+        ));
+        Self { state, task_ctx }
     }
 
     #[inline]
     pub fn state(&self) -> &SessionState {
         &self.state
     }
+
+    /// Returns the task context to execute physical plans.
+    #[inline]
+    pub fn task_ctx(&self) -> Arc<TaskContext> {
+        self.task_ctx.clone()
+    }
 }
@@ -0,0 +1,127 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use datafusion::error::{DataFusionError, Result as DfResult};
+use datafusion::execution::memory_pool::{MemoryConsumer, MemoryPool, MemoryReservation};
+
+/// Memory pool of a single query, which limits the memory used by the query and
+/// also reserves the memory from the memory pool shared by all queries.
+#[derive(Debug)]
+pub(crate) struct QueryMemoryPool {
+    global: Arc<dyn MemoryPool>,
+    limit: usize,
+    used: AtomicUsize,
+}
+
+impl QueryMemoryPool {
+    pub(crate) fn new(global: Arc<dyn MemoryPool>, limit: usize) -> Self {
+        Self {
+            global,
+            limit,
+            used: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl MemoryPool for QueryMemoryPool {
+    fn register(&self, consumer: &MemoryConsumer) {
+        self.global.register(consumer)
+    }
+
+    fn unregister(&self, consumer: &MemoryConsumer) {
+        self.global.unregister(consumer)
+    }
+
+    fn grow(&self, reservation: &MemoryReservation, additional: usize) {
+        let _ = self.used.fetch_add(additional, Ordering::Relaxed);
+        self.global.grow(reservation, additional)
+    }
+
+    fn shrink(&self, reservation: &MemoryReservation, shrink: usize) {
+        let _ = self.used.fetch_sub(shrink, Ordering::Relaxed);
+        self.global.shrink(reservation, shrink)
+    }
+
+    fn try_grow(&self, reservation: &MemoryReservation, additional: usize) -> DfResult<()> {
+        let _ = self
+            .used
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |used| {
+                used.checked_add(additional)
+                    .filter(|new_used| *new_used <= self.limit)
+            })
+            .map_err(|used| {
+                DataFusionError::ResourcesExhausted(format!(
+                    "Failed to allocate additional {} bytes for the query with {} bytes already allocated - maximum available is {}",
+                    additional,
+                    used,
+                    self.limit.saturating_sub(used)
+                ))
+            })?;
+
+        if let Err(e) = self.global.try_grow(reservation, additional) {
+            let _ = self.used.fetch_sub(additional, Ordering::Relaxed);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    fn reserved(&self) -> usize {
+        self.used.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::execution::memory_pool::GreedyMemoryPool;
+
+    use super::*;
+
+    #[test]
+    fn test_query_memory_pool() {
+        let global: Arc<dyn MemoryPool> = Arc::new(GreedyMemoryPool::new(100));
+        let query1: Arc<dyn MemoryPool> = Arc::new(QueryMemoryPool::new(global.clone(), 60));
+        let query2: Arc<dyn MemoryPool> = Arc::new(QueryMemoryPool::new(global.clone(), 60));
+
+        let mut r1 = MemoryConsumer::new("r1").register(&query1);
+        r1.try_grow(40).unwrap();
+        // Exceeds the limit of the query.
+        let err = r1.try_grow(30).unwrap_err();
+        assert!(
+            matches!(err, DataFusionError::ResourcesExhausted(_)),
+            "unexpected error: {err:?}"
+        );
+        assert_eq!(40, query1.reserved());
+        assert_eq!(40, global.reserved());
+
+        let mut r2 = MemoryConsumer::new("r2").register(&query2);
+        r2.try_grow(50).unwrap();
+        // Exceeds the global limit while the query is still under its own limit.
+        let err = r2.try_grow(20).unwrap_err();
+        assert!(
+            matches!(err, DataFusionError::ResourcesExhausted(_)),
+            "unexpected error: {err:?}"
+        );
+        assert_eq!(50, query2.reserved());
+        assert_eq!(90, global.reserved());
+
+        drop(r1);
+        assert_eq!(0, query1.reserved());
+        r2.try_grow(20).unwrap();
+        assert_eq!(70, query2.reserved());
+        assert_eq!(70, global.reserved());
+    }
+}
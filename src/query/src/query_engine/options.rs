@@ -12,7 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use common_catalog::consts::INFORMATION_SCHEMA_NAME;
+use std::path::Path;
+
+use common_base::readable_size::ReadableSize;
+use common_catalog::consts::{INFORMATION_SCHEMA_NAME, PG_CATALOG_NAME};
+use serde::{Deserialize, Serialize};
 use session::context::QueryContextRef;
 use snafu::ensure;
 
@@ -23,14 +27,44 @@ pub struct QueryOptions {
     pub disallow_cross_schema_query: bool,
 }
 
+/// Configuration of the query engine.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QueryConfig {
+    /// Memory shared by all running queries, unlimited if absent.
+    pub memory_limit: Option<ReadableSize>,
+    /// Memory a single query could use, unlimited if absent.
+    pub query_memory_limit: Option<ReadableSize>,
+    /// Directory to spill intermediate results of large sorts when the memory limit
+    /// is reached, uses the temp directory of the OS if absent. Startup fails if
+    /// the directory can't be used.
+    pub spill_dir: Option<String>,
+}
+
+impl QueryConfig {
+    /// Creates the spill directory and checks directories for spill files can
+    /// be created in it.
+    pub fn check_spill_dir(&self) -> std::io::Result<()> {
+        let Some(dir) = &self.spill_dir else {
+            return Ok(());
+        };
+        std::fs::create_dir_all(dir)?;
+        let probe = Path::new(dir).join(format!(".probe-{}", std::process::id()));
+        std::fs::create_dir(&probe)?;
+        std::fs::remove_dir(&probe)
+    }
+}
+
 // TODO(shuiyisong): remove one method after #559 is done
 pub fn validate_catalog_and_schema(
     catalog: &str,
     schema: &str,
     query_ctx: &QueryContextRef,
 ) -> Result<()> {
-    // information_schema is an exception
-    if schema.eq_ignore_ascii_case(INFORMATION_SCHEMA_NAME) {
+    // information_schema and pg_catalog are exceptions
+    if schema.eq_ignore_ascii_case(INFORMATION_SCHEMA_NAME)
+        || schema.eq_ignore_ascii_case(PG_CATALOG_NAME)
+    {
         return Ok(());
     }
 
@@ -49,6 +83,7 @@ pub fn validate_catalog_and_schema(
 mod tests {
     use std::sync::Arc;
 
+    use common_test_util::temp_dir::create_temp_dir;
     use session::context::QueryContext;
 
     use super::*;
@@ -67,4 +102,28 @@ mod tests {
 
         validate_catalog_and_schema("greptime", "information_schema", &context).unwrap();
     }
+
+    #[test]
+    fn test_check_spill_dir() {
+        QueryConfig::default().check_spill_dir().unwrap();
+
+        let dir = create_temp_dir("test_check_spill_dir");
+        let spill_dir = dir.path().join("spill");
+        let config = QueryConfig {
+            spill_dir: Some(spill_dir.to_str().unwrap().to_string()),
+            ..Default::default()
+        };
+        config.check_spill_dir().unwrap();
+        assert!(spill_dir.is_dir());
+        assert_eq!(0, std::fs::read_dir(&spill_dir).unwrap().count());
+
+        // The spill dir is a file.
+        let file = dir.path().join("file");
+        std::fs::write(&file, b"").unwrap();
+        let config = QueryConfig {
+            spill_dir: Some(file.to_str().unwrap().to_string()),
+            ..Default::default()
+        };
+        assert!(config.check_spill_dir().is_err());
+    }
 }
@@ -14,6 +14,7 @@
 
 use std::collections::HashMap;
 use std::fmt;
+use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
 
 use async_trait::async_trait;
@@ -27,7 +28,9 @@ use datafusion::catalog::catalog::MemoryCatalogList;
 use datafusion::dataframe::DataFrame;
 use datafusion::error::Result as DfResult;
 use datafusion::execution::context::{QueryPlanner, SessionConfig, SessionState};
-use datafusion::execution::runtime_env::RuntimeEnv;
+use datafusion::execution::disk_manager::DiskManagerConfig;
+use datafusion::execution::memory_pool::FairSpillPool;
+use datafusion::execution::runtime_env::{RuntimeConfig, RuntimeEnv};
 use datafusion::physical_optimizer::dist_enforcement::EnforceDistribution;
 use datafusion::physical_optimizer::repartition::Repartition;
 use datafusion::physical_optimizer::sort_enforcement::EnforceSorting;
@@ -42,11 +45,14 @@ use promql::extension_plan::PromExtensionPlanner;
 use table::table::adapter::DfTableProviderAdapter;
 use table::TableRef;
 
-use crate::dist_plan::{DistExtensionPlanner, DistPlannerAnalyzer};
+use crate::dist_plan::{DistAnalyzeExec, DistExtensionPlanner, DistPlannerAnalyzer};
 use crate::extension_serializer::ExtensionSerializer;
 use crate::optimizer::order_hint::OrderHintRule;
 use crate::optimizer::type_conversion::TypeConversionRule;
-use crate::query_engine::options::QueryOptions;
+use crate::query_engine::memory_pool::QueryMemoryPool;
+use crate::query_engine::options::{QueryConfig, QueryOptions};
+use crate::query_engine::QueryEngineContext;
+use crate::range_select::RangeSelectExtensionPlanner;
 
 /// Query engine global state
 // TODO(yingwen): This QueryEngineState still relies on datafusion, maybe we can define a trait for it,
@@ -56,8 +62,13 @@ use crate::query_engine::options::QueryOptions;
 pub struct QueryEngineState {
     df_context: SessionContext,
     catalog_manager: CatalogManagerRef,
+    scalar_functions: Arc<RwLock<HashMap<String, ScalarUdf>>>,
+    /// Functions created in schemas, by catalog, schema and function names.
+    schema_functions: Arc<RwLock<HashMap<(String, String, String), ScalarUdf>>>,
     aggregate_functions: Arc<RwLock<HashMap<String, AggregateFunctionMetaRef>>>,
     plugins: Arc<Plugins>,
+    /// Memory limit of a single query in bytes.
+    query_memory_limit: Option<usize>,
 }
 
 impl fmt::Debug for QueryEngineState {
@@ -75,8 +86,9 @@ impl QueryEngineState {
         partition_manager: Option<Arc<PartitionRuleManager>>,
         datanode_clients: Option<Arc<DatanodeClients>>,
         plugins: Arc<Plugins>,
+        config: &QueryConfig,
     ) -> Self {
-        let runtime_env = Arc::new(RuntimeEnv::default());
+        let runtime_env = create_runtime_env(config);
         let session_config = SessionConfig::new().with_create_default_catalog_and_schema(false);
         // Apply the type conversion rule first.
         let mut analyzer = Analyzer::new();
@@ -122,8 +134,13 @@ impl QueryEngineState {
         Self {
             df_context,
             catalog_manager: catalog_list,
+            scalar_functions: Arc::new(RwLock::new(HashMap::new())),
+            schema_functions: Arc::new(RwLock::new(HashMap::new())),
             aggregate_functions: Arc::new(RwLock::new(HashMap::new())),
             plugins,
+            query_memory_limit: config
+                .query_memory_limit
+                .map(|limit| limit.as_bytes() as usize),
         }
     }
 
@@ -143,10 +160,47 @@ impl QueryEngineState {
         }
     }
 
-    /// Register a udf function
-    // TODO(dennis): manage UDFs by ourself.
+    /// Register a udf function, an existing function with the same name is replaced.
     pub fn register_udf(&self, udf: ScalarUdf) {
-        self.df_context.register_udf(udf.into_df_udf());
+        // DataFusion still needs the function to decode plans.
+        self.df_context.register_udf(udf.clone().into_df_udf());
+        let _ = self
+            .scalar_functions
+            .write()
+            .unwrap()
+            .insert(udf.name.clone(), udf);
+    }
+
+    pub fn udf_function(&self, name: &str) -> Option<ScalarUdf> {
+        self.scalar_functions.read().unwrap().get(name).cloned()
+    }
+
+    /// Register a udf function in the schema, an existing function with the same
+    /// name is replaced. The function is only callable in the schema, so it isn't
+    /// registered into DataFusion, whose functions are global.
+    pub fn register_schema_udf(&self, catalog: &str, schema: &str, udf: ScalarUdf) {
+        let key = (catalog.to_string(), schema.to_string(), udf.name.clone());
+        let _ = self.schema_functions.write().unwrap().insert(key, udf);
+    }
+
+    /// Deregister the udf function of the schema, returns whether it's registered.
+    pub fn deregister_schema_udf(&self, catalog: &str, schema: &str, name: &str) -> bool {
+        let key = (catalog.to_string(), schema.to_string(), name.to_string());
+        self.schema_functions
+            .write()
+            .unwrap()
+            .remove(&key)
+            .is_some()
+    }
+
+    /// Resolves the udf function called in the schema, functions of the schema
+    /// take precedence over the global ones.
+    pub fn resolve_udf(&self, catalog: &str, schema: &str, name: &str) -> Option<ScalarUdf> {
+        let key = (catalog.to_string(), schema.to_string(), name.to_string());
+        if let Some(udf) = self.schema_functions.read().unwrap().get(&key) {
+            return Some(udf.clone());
+        }
+        self.udf_function(name)
     }
 
     pub fn aggregate_function(&self, function_name: &str) -> Option<AggregateFunctionMetaRef> {
@@ -193,6 +247,23 @@ impl QueryEngineState {
         self.df_context.state()
     }
 
+    /// Creates the context to execute a query. All queries share the memory pool of the
+    /// runtime, and a query fails or spills once it reaches the per query memory limit.
+    pub(crate) fn query_engine_context(&self) -> QueryEngineContext {
+        let state = self.session_state();
+        let Some(limit) = self.query_memory_limit else {
+            return QueryEngineContext::new(state);
+        };
+
+        let runtime_env = state.runtime_env();
+        let runtime_env = Arc::new(RuntimeEnv {
+            memory_pool: Arc::new(QueryMemoryPool::new(runtime_env.memory_pool.clone(), limit)),
+            disk_manager: runtime_env.disk_manager.clone(),
+            object_store_registry: runtime_env.object_store_registry.clone(),
+        });
+        QueryEngineContext::with_runtime_env(state, runtime_env)
+    }
+
     /// Create a DataFrame for a table
     pub fn read_table(&self, table: TableRef) -> DfResult<DataFrame> {
         self.df_context
@@ -200,8 +271,29 @@ impl QueryEngineState {
     }
 }
 
+/// Creates the runtime shared by all queries, whose memory pool is bounded by the memory
+/// limit and spills to the spill directory.
+fn create_runtime_env(config: &QueryConfig) -> Arc<RuntimeEnv> {
+    let mut runtime_config = RuntimeConfig::new();
+    if let Some(limit) = config.memory_limit {
+        runtime_config = runtime_config
+            .with_memory_pool(Arc::new(FairSpillPool::new(limit.as_bytes() as usize)));
+    }
+
+    if let Some(dir) = &config.spill_dir {
+        // The disk manager creates its temp dirs under the spill dir.
+        runtime_config = runtime_config
+            .with_disk_manager(DiskManagerConfig::NewSpecified(vec![PathBuf::from(dir)]));
+    }
+
+    // The spill dir is checked by `QueryConfig::check_spill_dir()` on startup.
+    Arc::new(RuntimeEnv::new(runtime_config).expect("Failed to create query runtime env"))
+}
+
 struct DfQueryPlanner {
     physical_planner: DefaultPhysicalPlanner,
+    /// Whether the plans are distributed to the datanodes.
+    distributed: bool,
 }
 
 #[async_trait]
@@ -211,6 +303,16 @@ impl QueryPlanner for DfQueryPlanner {
         logical_plan: &DfLogicalPlan,
         session_state: &SessionState,
     ) -> DfResult<Arc<dyn ExecutionPlan>> {
+        if self.distributed && let DfLogicalPlan::Analyze(analyze) = logical_plan {
+            // Collects the metrics of the datanodes as well.
+            let input = self
+                .physical_planner
+                .create_physical_plan(&analyze.input, session_state)
+                .await?;
+            let schema = Arc::new(analyze.schema.as_ref().into());
+            return Ok(Arc::new(DistAnalyzeExec::new(analyze.verbose, input, schema)));
+        }
+
         self.physical_planner
             .create_physical_plan(logical_plan, session_state)
             .await
@@ -222,14 +324,19 @@ impl DfQueryPlanner {
         partition_manager: Option<Arc<PartitionRuleManager>>,
         datanode_clients: Option<Arc<DatanodeClients>>,
     ) -> Self {
-        let mut planners: Vec<Arc<dyn ExtensionPlanner + Send + Sync>> =
-            vec![Arc::new(PromExtensionPlanner)];
+        let mut planners: Vec<Arc<dyn ExtensionPlanner + Send + Sync>> = vec![
+            Arc::new(PromExtensionPlanner),
+            Arc::new(RangeSelectExtensionPlanner),
+        ];
+        let mut distributed = false;
         if let Some(partition_manager) = partition_manager
          && let Some(datanode_clients) = datanode_clients {
             planners.push(Arc::new(DistExtensionPlanner::new(partition_manager, datanode_clients)));
+            distributed = true;
         }
         Self {
             physical_planner: DefaultPhysicalPlanner::with_extension_planners(planners),
+            distributed,
         }
     }
 }
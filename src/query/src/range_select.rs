@@ -0,0 +1,21 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! SQL range query, i.e. `SELECT avg(v) RANGE '5m' FILL PREV FROM t ALIGN '1m' BY (host)`.
+
+mod extension_planner;
+pub mod plan;
+pub mod planner;
+
+pub use extension_planner::RangeSelectExtensionPlanner;
@@ -0,0 +1,884 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use arrow_schema::{DataType, SchemaRef, TimeUnit};
+use datafusion::arrow::array::{Array, ArrayRef, Int64Array};
+use datafusion::arrow::compute;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::arrow::row::{OwnedRow, Row, RowConverter, SortField};
+use datafusion::execution::context::{SessionState, TaskContext};
+use datafusion::physical_plan::aggregates::create_aggregate_expr;
+use datafusion::physical_plan::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use datafusion::physical_plan::udaf::create_aggregate_expr as create_udaf_expr;
+use datafusion::physical_plan::{
+    AggregateExpr, DisplayFormatType, Distribution, ExecutionPlan, Partitioning, PhysicalExpr,
+    PhysicalPlanner, RecordBatchStream, SendableRecordBatchStream, Statistics,
+};
+use datafusion_common::{DFField, DFSchema, DFSchemaRef, DataFusionError, Result, ScalarValue};
+use datafusion_expr::{Expr, ExprSchemable, LogicalPlan, UserDefinedLogicalNodeCore};
+use datafusion_physical_expr::PhysicalSortExpr;
+use datatypes::schema::TIME_INDEX_KEY;
+use futures::{Stream, StreamExt};
+
+pub type Millisecond = i64;
+
+/// Max number of aligned timestamps of one series, which bounds the memory of aligning a
+/// series with a tiny `ALIGN` over a wide time range.
+const MAX_ALIGNED_STEPS: i64 = 1_000_000;
+
+/// How to fill the value of an aligned timestamp that has no data.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Fill {
+    /// Leave it as NULL.
+    Null,
+    /// Use the previous non-null value of the same series.
+    Prev,
+    /// Linear interpolate from the neighbouring non-null values of the same series.
+    Linear,
+    /// Use a constant value, which is casted to the data type of the range expression.
+    Const(String),
+}
+
+impl FromStr for Fill {
+    type Err = DataFusionError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_uppercase().as_str() {
+            "" | "NULL" => Ok(Fill::Null),
+            "PREV" => Ok(Fill::Prev),
+            "LINEAR" => Ok(Fill::Linear),
+            _ => {
+                if s.parse::<f64>().is_err() {
+                    return Err(DataFusionError::Plan(format!(
+                        "Unknown fill mode: {s}, expect NULL, PREV, LINEAR or a number"
+                    )));
+                }
+                Ok(Fill::Const(s.to_string()))
+            }
+        }
+    }
+}
+
+impl Display for Fill {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fill::Null => write!(f, "NULL"),
+            Fill::Prev => write!(f, "PREV"),
+            Fill::Linear => write!(f, "LINEAR"),
+            Fill::Const(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+impl Fill {
+    /// Fills the NULLs in `values` in place. `values` are the results of one series, ordered by
+    /// aligned timestamp without any gap.
+    fn apply(&self, values: &mut [ScalarValue], data_type: &DataType) -> Result<()> {
+        match self {
+            Fill::Null => {}
+            Fill::Prev => {
+                let mut prev: Option<ScalarValue> = None;
+                for value in values.iter_mut() {
+                    if value.is_null() {
+                        if let Some(prev) = &prev {
+                            *value = prev.clone();
+                        }
+                    } else {
+                        prev = Some(value.clone());
+                    }
+                }
+            }
+            Fill::Linear => {
+                let points = values
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, v)| !v.is_null())
+                    .map(|(i, v)| Ok((i, scalar_to_f64(v)?)))
+                    .collect::<Result<Vec<_>>>()?;
+                for window in points.windows(2) {
+                    let (start, start_value) = window[0];
+                    let (end, end_value) = window[1];
+                    let slope = (end_value - start_value) / (end - start) as f64;
+                    for (i, value) in values.iter_mut().enumerate().take(end).skip(start + 1) {
+                        let interpolated = start_value + slope * (i - start) as f64;
+                        *value = ScalarValue::Float64(Some(interpolated)).cast_to(data_type)?;
+                    }
+                }
+            }
+            Fill::Const(v) => {
+                let fill =
+                    ScalarValue::Float64(Some(v.parse::<f64>().map_err(|e| {
+                        DataFusionError::Plan(format!("Invalid fill value {v}: {e}"))
+                    })?))
+                    .cast_to(data_type)?;
+                for value in values.iter_mut().filter(|v| v.is_null()) {
+                    *value = fill.clone();
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn scalar_to_f64(value: &ScalarValue) -> Result<f64> {
+    match value.cast_to(&DataType::Float64)? {
+        ScalarValue::Float64(Some(v)) => Ok(v),
+        other => Err(DataFusionError::Execution(format!(
+            "Cannot linear fill value {other:?}"
+        ))),
+    }
+}
+
+/// A range expression like `avg(v) RANGE '5m' FILL PREV`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeFn {
+    /// Output column name of this range expression.
+    pub name: String,
+    /// The aggregate expression evaluated over each range.
+    pub expr: Expr,
+    pub range: Millisecond,
+    pub fill: Fill,
+}
+
+impl Display for RangeFn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} RANGE {}ms FILL {}", self.expr, self.range, self.fill)
+    }
+}
+
+/// Logical plan of SQL range query.
+///
+/// For every series grouped by `by`, this plan evaluates the aggregate of each [RangeFn] over
+/// the rows in `(t - range, t]` for every timestamp `t` aligned to `align`, the same as the
+/// range vector selector of PromQL. Timestamps without
+/// data between the first and the last aligned timestamp of a series are filled according to
+/// the [Fill] of the range expression.
+///
+/// The input must be sorted by `by` expressions and then the time index, so the series can be
+/// aligned one by one without buffering the whole input.
+///
+/// The output schema is range expressions, then time index, then `by` expressions.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct RangeSelect {
+    pub input: Arc<LogicalPlan>,
+    pub range_expr: Vec<RangeFn>,
+    pub align: Millisecond,
+    pub time_index: String,
+    pub by: Vec<Expr>,
+    pub schema: DFSchemaRef,
+}
+
+impl RangeSelect {
+    pub fn try_new(
+        input: Arc<LogicalPlan>,
+        range_expr: Vec<RangeFn>,
+        align: Millisecond,
+        time_index: String,
+        by: Vec<Expr>,
+    ) -> Result<Self> {
+        if align <= 0 {
+            return Err(DataFusionError::Plan(format!(
+                "ALIGN must be positive, got {align}ms"
+            )));
+        }
+        if let Some(range_fn) = range_expr.iter().find(|r| r.range <= 0) {
+            return Err(DataFusionError::Plan(format!(
+                "RANGE must be positive, got {}ms",
+                range_fn.range
+            )));
+        }
+
+        let input_schema = input.schema();
+        let mut fields = range_expr
+            .iter()
+            .map(|range_fn| {
+                let field = range_fn.expr.to_field(input_schema)?;
+                Ok(DFField::new_unqualified(
+                    &range_fn.name,
+                    field.data_type().clone(),
+                    true,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let ts_field = input_schema
+            .fields()
+            .iter()
+            .find(|f| f.name() == &time_index)
+            .cloned()
+            .ok_or_else(|| {
+                DataFusionError::Plan(format!("Time index column {time_index} not found"))
+            })?;
+        fields.push(ts_field);
+        for expr in &by {
+            fields.push(expr.to_field(input_schema)?);
+        }
+        let schema = Arc::new(DFSchema::new_with_metadata(fields, HashMap::new())?);
+
+        Ok(Self {
+            input,
+            range_expr,
+            align,
+            time_index,
+            by,
+            schema,
+        })
+    }
+
+    pub const fn name() -> &'static str {
+        "RangeSelect"
+    }
+
+    /// Finds the time index column of the given schema by its metadata.
+    pub fn find_time_index(schema: &DFSchema) -> Option<String> {
+        schema
+            .fields()
+            .iter()
+            .find(|f| f.field().metadata().contains_key(TIME_INDEX_KEY))
+            .map(|f| f.name().clone())
+    }
+
+    pub fn to_execution_plan(
+        &self,
+        exec_input: Arc<dyn ExecutionPlan>,
+        session_state: &SessionState,
+        physical_planner: &dyn PhysicalPlanner,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let input_dfschema = self.input.schema();
+        let input_schema = exec_input.schema();
+        let create_physical_expr = |expr: &Expr| {
+            physical_planner.create_physical_expr(
+                expr,
+                input_dfschema,
+                &input_schema,
+                session_state,
+            )
+        };
+
+        let range_exec = self
+            .range_expr
+            .iter()
+            .map(|range_fn| {
+                let expr = match &range_fn.expr {
+                    Expr::AggregateFunction(aggr) => {
+                        let args = aggr
+                            .args
+                            .iter()
+                            .map(create_physical_expr)
+                            .collect::<Result<Vec<_>>>()?;
+                        create_aggregate_expr(
+                            &aggr.fun,
+                            aggr.distinct,
+                            &args,
+                            &input_schema,
+                            &range_fn.name,
+                        )?
+                    }
+                    Expr::AggregateUDF(aggr) => {
+                        let args = aggr
+                            .args
+                            .iter()
+                            .map(create_physical_expr)
+                            .collect::<Result<Vec<_>>>()?;
+                        create_udaf_expr(&aggr.fun, &args, &input_schema, &range_fn.name)?
+                    }
+                    other => {
+                        return Err(DataFusionError::Plan(format!(
+                            "Range expression must be an aggregate function, got {other}"
+                        )))
+                    }
+                };
+                let data_type = expr.field()?.data_type().clone();
+                Ok(RangeFnExec {
+                    expr,
+                    range: range_fn.range,
+                    fill: range_fn.fill.clone(),
+                    data_type,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let by = self
+            .by
+            .iter()
+            .map(create_physical_expr)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Arc::new(RangeSelectExec {
+            input: exec_input,
+            range_exec,
+            align: self.align,
+            time_index: self.time_index.clone(),
+            by,
+            schema: Arc::new(self.schema.as_ref().into()),
+            metric: ExecutionPlanMetricsSet::new(),
+        }))
+    }
+}
+
+impl UserDefinedLogicalNodeCore for RangeSelect {
+    fn name(&self) -> &str {
+        Self::name()
+    }
+
+    fn inputs(&self) -> Vec<&LogicalPlan> {
+        vec![&self.input]
+    }
+
+    fn schema(&self) -> &DFSchemaRef {
+        &self.schema
+    }
+
+    fn expressions(&self) -> Vec<Expr> {
+        self.range_expr
+            .iter()
+            .map(|range_fn| range_fn.expr.clone())
+            .chain(self.by.iter().cloned())
+            .collect()
+    }
+
+    fn fmt_for_explain(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "RangeSelect: range_exprs=[{}], align={}ms, time_index={}, by=[{}]",
+            self.range_expr
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+            self.align,
+            self.time_index,
+            self.by
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+        )
+    }
+
+    fn from_template(&self, exprs: &[Expr], inputs: &[LogicalPlan]) -> Self {
+        assert!(!inputs.is_empty());
+        assert_eq!(exprs.len(), self.range_expr.len() + self.by.len());
+
+        let range_expr = self
+            .range_expr
+            .iter()
+            .zip(exprs)
+            .map(|(range_fn, expr)| RangeFn {
+                name: range_fn.name.clone(),
+                expr: expr.clone(),
+                range: range_fn.range,
+                fill: range_fn.fill.clone(),
+            })
+            .collect();
+        Self {
+            input: Arc::new(inputs[0].clone()),
+            range_expr,
+            align: self.align,
+            time_index: self.time_index.clone(),
+            by: exprs[self.range_expr.len()..].to_vec(),
+            schema: self.schema.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RangeFnExec {
+    expr: Arc<dyn AggregateExpr>,
+    range: Millisecond,
+    fill: Fill,
+    data_type: DataType,
+}
+
+#[derive(Debug)]
+pub struct RangeSelectExec {
+    input: Arc<dyn ExecutionPlan>,
+    range_exec: Vec<RangeFnExec>,
+    align: Millisecond,
+    time_index: String,
+    by: Vec<Arc<dyn PhysicalExpr>>,
+    schema: SchemaRef,
+    metric: ExecutionPlanMetricsSet,
+}
+
+impl ExecutionPlan for RangeSelectExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        vec![Distribution::SinglePartition]
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        assert!(!children.is_empty());
+        Ok(Arc::new(Self {
+            input: children[0].clone(),
+            range_exec: self
+                .range_exec
+                .iter()
+                .map(|range_fn| RangeFnExec {
+                    expr: range_fn.expr.clone(),
+                    range: range_fn.range,
+                    fill: range_fn.fill.clone(),
+                    data_type: range_fn.data_type.clone(),
+                })
+                .collect(),
+            align: self.align,
+            time_index: self.time_index.clone(),
+            by: self.by.clone(),
+            schema: self.schema.clone(),
+            metric: self.metric.clone(),
+        }))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let baseline_metric = BaselineMetrics::new(&self.metric, partition);
+        let input = self.input.execute(partition, context)?;
+        let input_schema = input.schema();
+        let time_index = input_schema.index_of(&self.time_index)?;
+        let converter = if self.by.is_empty() {
+            None
+        } else {
+            let fields = self
+                .by
+                .iter()
+                .map(|expr| Ok(SortField::new(expr.data_type(&input_schema)?)))
+                .collect::<Result<Vec<_>>>()?;
+            Some(RowConverter::new(fields)?)
+        };
+        let aligner = RangeAligner {
+            range_exec: self
+                .range_exec
+                .iter()
+                .map(|range_fn| RangeFnExec {
+                    expr: range_fn.expr.clone(),
+                    range: range_fn.range,
+                    fill: range_fn.fill.clone(),
+                    data_type: range_fn.data_type.clone(),
+                })
+                .collect(),
+            align: self.align,
+            time_index,
+            schema: self.schema.clone(),
+        };
+
+        Ok(Box::pin(RangeSelectStream {
+            aligner,
+            by: self.by.clone(),
+            converter,
+            input,
+            series_key: None,
+            series_batches: vec![],
+            done: false,
+            metric: baseline_metric,
+        }))
+    }
+
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default => {
+                write!(
+                    f,
+                    "RangeSelectExec: range_exprs=[{}], align={}ms, time_index={}",
+                    self.range_exec
+                        .iter()
+                        .map(|r| format!("{} RANGE {}ms FILL {}", r.expr.name(), r.range, r.fill))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    self.align,
+                    self.time_index
+                )
+            }
+        }
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metric.clone_inner())
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
+/// Aligns the input series by series.
+///
+/// The input is sorted by `by` expressions and then the time index, so only the rows of the
+/// series being read are buffered.
+struct RangeSelectStream {
+    aligner: RangeAligner,
+    by: Vec<Arc<dyn PhysicalExpr>>,
+    /// Encodes `by` columns into comparable series keys. `None` if there is no `by`, which
+    /// means the whole input is one series.
+    converter: Option<RowConverter>,
+    input: SendableRecordBatchStream,
+    /// Key of the series being buffered.
+    series_key: Option<OwnedRow>,
+    /// Rows of the series being buffered.
+    series_batches: Vec<RecordBatch>,
+    done: bool,
+    metric: BaselineMetrics,
+}
+
+impl RecordBatchStream for RangeSelectStream {
+    fn schema(&self) -> SchemaRef {
+        self.aligner.schema.clone()
+    }
+}
+
+impl Stream for RangeSelectStream {
+    type Item = Result<RecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let elapsed_compute = self.metric.elapsed_compute().clone();
+        let poll = loop {
+            if self.done {
+                break Poll::Ready(None);
+            }
+            match self.input.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(batch))) => {
+                    let _timer = elapsed_compute.timer();
+                    match self.push_batch(batch) {
+                        Ok(None) => continue,
+                        result => break Poll::Ready(result.transpose()),
+                    }
+                }
+                Poll::Ready(Some(Err(e))) => break Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => {
+                    self.done = true;
+                    let _timer = elapsed_compute.timer();
+                    break Poll::Ready(self.finish_series().transpose());
+                }
+                Poll::Pending => break Poll::Pending,
+            }
+        };
+        self.metric.record_poll(poll)
+    }
+}
+
+impl RangeSelectStream {
+    /// Buffers `batch` and aligns the series that end in it.
+    fn push_batch(&mut self, batch: RecordBatch) -> Result<Option<RecordBatch>> {
+        let Some(converter) = self.converter.as_mut() else {
+            self.series_batches.push(batch);
+            return Ok(None);
+        };
+        let by_columns = self
+            .by
+            .iter()
+            .map(|expr| Ok(expr.evaluate(&batch)?.into_array(batch.num_rows())))
+            .collect::<Result<Vec<_>>>()?;
+        let rows = converter.convert_columns(&by_columns)?;
+
+        let mut outputs = vec![];
+        let mut start = 0;
+        for (i, row) in rows.iter().enumerate() {
+            if self
+                .series_key
+                .as_ref()
+                .map_or(false, |key| key.row() == row)
+            {
+                continue;
+            }
+            // a new series starts at row i
+            if i > start {
+                self.series_batches.push(batch.slice(start, i - start));
+            }
+            if let Some(output) = self.finish_series()? {
+                outputs.push(output);
+            }
+            self.series_key = Some(row.owned());
+            start = i;
+        }
+        if start < batch.num_rows() {
+            self.series_batches
+                .push(batch.slice(start, batch.num_rows() - start));
+        }
+
+        if outputs.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(compute::concat_batches(
+                &self.aligner.schema,
+                &outputs,
+            )?))
+        }
+    }
+
+    /// Aligns the buffered series, if any.
+    fn finish_series(&mut self) -> Result<Option<RecordBatch>> {
+        if self.series_batches.is_empty() {
+            return Ok(None);
+        }
+        let batches = std::mem::take(&mut self.series_batches);
+        let batch = compute::concat_batches(&batches[0].schema(), &batches)?;
+        let key = self
+            .converter
+            .as_ref()
+            .zip(self.series_key.as_ref())
+            .map(|(converter, key)| (converter, key.row()));
+        self.aligner.align_series(&batch, key)
+    }
+}
+
+/// The computation part of [RangeSelectExec].
+struct RangeAligner {
+    range_exec: Vec<RangeFnExec>,
+    align: Millisecond,
+    time_index: usize,
+    schema: SchemaRef,
+}
+
+impl RangeAligner {
+    /// Aligns the rows of one series, which are sorted by time index. `key` is the
+    /// converter and the encoded `by` values of the series if there is `by`.
+    fn align_series(
+        &self,
+        batch: &RecordBatch,
+        key: Option<(&RowConverter, Row)>,
+    ) -> Result<Option<RecordBatch>> {
+        let timestamps = self.timestamps_in_millis(batch)?;
+        let (Some(first), Some(last)) = (timestamps.first(), timestamps.last()) else {
+            return Ok(None);
+        };
+        let max_range = self
+            .range_exec
+            .iter()
+            .map(|range_fn| range_fn.range)
+            .max()
+            .unwrap_or(1);
+        let aligned = aligned_timestamps(*first, *last, self.align, max_range)?;
+
+        let mut columns = Vec::with_capacity(self.schema.fields().len());
+        for range_fn in &self.range_exec {
+            let args = range_fn
+                .expr
+                .expressions()
+                .iter()
+                .map(|expr| Ok(expr.evaluate(batch)?.into_array(batch.num_rows())))
+                .collect::<Result<Vec<_>>>()?;
+            let null = ScalarValue::try_from(&range_fn.data_type)?;
+            let mut values = Vec::with_capacity(aligned.len());
+            for ts in &aligned {
+                // Rows in `(ts - range, ts]` are contiguous as the series is sorted.
+                let offset =
+                    timestamps.partition_point(|t| *t <= ts.saturating_sub(range_fn.range));
+                let len = timestamps[offset..].partition_point(|t| t <= ts);
+                if len == 0 {
+                    values.push(null.clone());
+                    continue;
+                }
+                let args = args
+                    .iter()
+                    .map(|arg| arg.slice(offset, len))
+                    .collect::<Vec<_>>();
+                let mut accumulator = range_fn.expr.create_accumulator()?;
+                accumulator.update_batch(&args)?;
+                values.push(accumulator.evaluate()?);
+            }
+            range_fn.fill.apply(&mut values, &range_fn.data_type)?;
+            let array = ScalarValue::iter_to_array(values)?;
+            columns.push(cast_if_needed(array, &range_fn.data_type)?);
+        }
+
+        let num_rows = aligned.len();
+        let ts_type = batch.schema().field(self.time_index).data_type().clone();
+        let timestamps: ArrayRef = Arc::new(Int64Array::from(aligned));
+        let timestamps = compute::cast(
+            &timestamps,
+            &DataType::Timestamp(TimeUnit::Millisecond, None),
+        )?;
+        columns.push(cast_if_needed(timestamps, &ts_type)?);
+        if let Some((converter, key)) = key {
+            columns.extend(converter.convert_rows(std::iter::repeat(key).take(num_rows))?);
+        }
+
+        Ok(Some(RecordBatch::try_new(self.schema.clone(), columns)?))
+    }
+
+    /// Returns the timestamps in millisecond. Rows with NULL timestamp are sorted to the end
+    /// and skipped, as they never fall into any range.
+    fn timestamps_in_millis(&self, batch: &RecordBatch) -> Result<Vec<Millisecond>> {
+        let ts = compute::cast(
+            batch.column(self.time_index),
+            &DataType::Timestamp(TimeUnit::Millisecond, None),
+        )?;
+        let ts = compute::cast(&ts, &DataType::Int64)?;
+        let ts = ts
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .ok_or_else(|| DataFusionError::Execution("Expect Int64Array".to_string()))?;
+        Ok(ts.iter().map_while(|ts| ts).collect())
+    }
+}
+
+/// Returns the timestamps aligned to `align` whose range of `max_range` covers any of the
+/// timestamps in `[first, last]`.
+fn aligned_timestamps(
+    first: Millisecond,
+    last: Millisecond,
+    align: Millisecond,
+    max_range: Millisecond,
+) -> Result<Vec<Millisecond>> {
+    // Computes in i128 to not overflow near the bounds of i64.
+    let (first, last, align) = (first as i128, last as i128, align as i128);
+    // The first aligned timestamp covering the first row, and the last aligned timestamp
+    // whose range covers the last row.
+    let start = (first + align - 1).div_euclid(align) * align;
+    let end = (last + max_range as i128 - 1).div_euclid(align) * align;
+    let steps = (end - start).div_euclid(align) + 1;
+    if steps > MAX_ALIGNED_STEPS as i128 {
+        return Err(DataFusionError::Execution(format!(
+            "Too many aligned timestamps in one series: {steps}, the limit is \
+             {MAX_ALIGNED_STEPS}, try a larger ALIGN or a narrower time range"
+        )));
+    }
+    Ok((0..steps.max(0))
+        .filter_map(|i| Millisecond::try_from(start + i * align).ok())
+        .collect())
+}
+
+fn cast_if_needed(array: ArrayRef, data_type: &DataType) -> Result<ArrayRef> {
+    if array.data_type() == data_type {
+        Ok(array)
+    } else {
+        Ok(compute::cast(&array, data_type)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fill_prev_and_const() {
+        let mut values = vec![
+            ScalarValue::Float64(None),
+            ScalarValue::Float64(Some(1.0)),
+            ScalarValue::Float64(None),
+            ScalarValue::Float64(Some(3.0)),
+            ScalarValue::Float64(None),
+        ];
+        let mut prev = values.clone();
+        Fill::Prev.apply(&mut prev, &DataType::Float64).unwrap();
+        assert_eq!(
+            vec![
+                ScalarValue::Float64(None),
+                ScalarValue::Float64(Some(1.0)),
+                ScalarValue::Float64(Some(1.0)),
+                ScalarValue::Float64(Some(3.0)),
+                ScalarValue::Float64(Some(3.0)),
+            ],
+            prev
+        );
+
+        Fill::Const("-1".to_string())
+            .apply(&mut values, &DataType::Float64)
+            .unwrap();
+        assert_eq!(
+            vec![
+                ScalarValue::Float64(Some(-1.0)),
+                ScalarValue::Float64(Some(1.0)),
+                ScalarValue::Float64(Some(-1.0)),
+                ScalarValue::Float64(Some(3.0)),
+                ScalarValue::Float64(Some(-1.0)),
+            ],
+            values
+        );
+    }
+
+    #[test]
+    fn fill_linear() {
+        let mut values = vec![
+            ScalarValue::Int64(Some(0)),
+            ScalarValue::Int64(None),
+            ScalarValue::Int64(None),
+            ScalarValue::Int64(Some(30)),
+            ScalarValue::Int64(None),
+        ];
+        Fill::Linear.apply(&mut values, &DataType::Int64).unwrap();
+        assert_eq!(
+            vec![
+                ScalarValue::Int64(Some(0)),
+                ScalarValue::Int64(Some(10)),
+                ScalarValue::Int64(Some(20)),
+                ScalarValue::Int64(Some(30)),
+                ScalarValue::Int64(None),
+            ],
+            values
+        );
+    }
+
+    #[test]
+    fn align_timestamps() {
+        // Ranges end at the aligned timestamps.
+        assert_eq!(
+            vec![0, 5000, 10000, 15000, 20000, 25000],
+            aligned_timestamps(0, 20000, 5000, 10000).unwrap()
+        );
+        assert_eq!(
+            vec![5000, 10000],
+            aligned_timestamps(1, 9000, 5000, 5000).unwrap()
+        );
+        assert_eq!(
+            vec![-5000, 0],
+            aligned_timestamps(-6000, -1, 5000, 1000).unwrap()
+        );
+        // No aligned timestamp covers the row.
+        assert!(aligned_timestamps(3, 3, 5, 1).unwrap().is_empty());
+
+        assert!(aligned_timestamps(0, i64::MAX, 1, 1).is_err());
+        assert!(aligned_timestamps(i64::MIN, i64::MAX, 1000, 1000).is_err());
+    }
+
+    #[test]
+    fn parse_fill() {
+        assert_eq!(Fill::Null, "".parse().unwrap());
+        assert_eq!(Fill::Null, "null".parse().unwrap());
+        assert_eq!(Fill::Prev, "PREV".parse().unwrap());
+        assert_eq!(Fill::Linear, "Linear".parse().unwrap());
+        assert_eq!(Fill::Const("1.5".to_string()), "1.5".parse().unwrap());
+        assert!("next".parse::<Fill>().is_err());
+    }
+}
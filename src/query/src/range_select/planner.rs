@@ -0,0 +1,329 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use datafusion_common::{Column, DFSchema};
+use datafusion_expr::expr::Sort;
+use datafusion_expr::{Expr, Extension, LogicalPlan, LogicalPlanBuilder};
+use datafusion_sql::planner::{ContextProvider, PlannerContext, SqlToRel};
+use snafu::{ensure, OptionExt, ResultExt};
+use sql::ast::{
+    Expr as SqlExpr, Function, FunctionArg, FunctionArgExpr, Query as SpQuery, SelectItem,
+    SetExpr, Statement as SpStatement, Value, WildcardAdditionalOptions,
+};
+use sql::statements::query::{Align, Query, RANGE_FN};
+
+use crate::error::{DataFusionSnafu, RangeQuerySnafu, Result};
+use crate::range_select::plan::{Fill, Millisecond, RangeFn, RangeSelect};
+
+/// Plans a SQL range query into [RangeSelect], like
+/// `SELECT ts, host, avg(v) RANGE '5m' FILL PREV FROM t ALIGN '1m' BY (host)`.
+///
+/// The rows selected by `FROM` and `WHERE` are sorted by `BY` expressions and time index, then
+/// fed into [RangeSelect]. The select list, `ORDER BY` and `LIMIT` are planned on top of it.
+pub struct RangeSelectPlanner<'a, S: ContextProvider> {
+    sql_to_rel: &'a SqlToRel<'a, S>,
+}
+
+/// One planned item of the select list.
+enum ProjectionItem {
+    /// Output of range expression with this name.
+    Range(String),
+    /// Other expression evaluated on [RangeSelect]'s output.
+    Expr(SqlExpr, Option<String>),
+}
+
+impl<'a, S: ContextProvider> RangeSelectPlanner<'a, S> {
+    pub fn new(sql_to_rel: &'a SqlToRel<'a, S>) -> Self {
+        Self { sql_to_rel }
+    }
+
+    pub fn plan(&self, query: &Query, align: &Align) -> Result<LogicalPlan> {
+        let SetExpr::Select(select) = query.inner.body.as_ref() else {
+            return RangeQuerySnafu {
+                msg: "range query must be a plain SELECT",
+            }
+            .fail();
+        };
+        ensure!(
+            select.group_by.is_empty() && select.having.is_none() && select.distinct.is_none(),
+            RangeQuerySnafu {
+                msg: "GROUP BY, HAVING and DISTINCT are not allowed in range query",
+            }
+        );
+
+        // Plan the source rows: `SELECT * FROM ... WHERE ...`
+        let mut source_select = select.as_ref().clone();
+        source_select.projection =
+            vec![SelectItem::Wildcard(WildcardAdditionalOptions::default())];
+        let source_query = SpQuery {
+            with: query.inner.with.clone(),
+            body: Box::new(SetExpr::Select(Box::new(source_select))),
+            order_by: vec![],
+            limit: None,
+            offset: None,
+            fetch: None,
+            locks: vec![],
+        };
+        let input = self
+            .sql_to_rel
+            .sql_statement_to_plan(SpStatement::Query(Box::new(source_query)))
+            .context(DataFusionSnafu)?;
+        let input_schema = input.schema().clone();
+        let time_index = RangeSelect::find_time_index(&input_schema).context(RangeQuerySnafu {
+            msg: "range query requires a table with time index",
+        })?;
+
+        let default_fill = align
+            .fill
+            .as_deref()
+            .unwrap_or_default()
+            .parse::<Fill>()
+            .context(DataFusionSnafu)?;
+        let mut range_exprs = vec![];
+        let mut items = vec![];
+        for item in &select.projection {
+            let (expr, alias) = match item {
+                SelectItem::UnnamedExpr(expr) => (expr, None),
+                SelectItem::ExprWithAlias { expr, alias } => (expr, Some(alias.value.clone())),
+                other => {
+                    return RangeQuerySnafu {
+                        msg: format!("unsupported select item in range query: {other}"),
+                    }
+                    .fail()
+                }
+            };
+            match expr {
+                SqlExpr::Function(func) if is_range_fn(func) => {
+                    let name = alias.unwrap_or_else(|| range_fn_display_name(func));
+                    range_exprs.push(self.plan_range_fn(
+                        func,
+                        name.clone(),
+                        &input_schema,
+                        &default_fill,
+                    )?);
+                    items.push(ProjectionItem::Range(name));
+                }
+                _ => items.push(ProjectionItem::Expr(expr.clone(), alias)),
+            }
+        }
+
+        let by = align
+            .by
+            .iter()
+            .map(|expr| self.sql_to_expr(expr.clone(), &input_schema))
+            .collect::<Result<Vec<_>>>()?;
+        // Sort the rows series by series, so RangeSelect only buffers one series at a time.
+        let sort_exprs = by
+            .iter()
+            .cloned()
+            .chain(std::iter::once(Expr::Column(Column::from_name(&time_index))))
+            .map(|expr| expr.sort(true, false))
+            .collect::<Vec<_>>();
+        let input = LogicalPlanBuilder::from(input)
+            .sort(sort_exprs)
+            .context(DataFusionSnafu)?
+            .build()
+            .context(DataFusionSnafu)?;
+        let range_select = RangeSelect::try_new(
+            Arc::new(input),
+            range_exprs,
+            parse_duration(&align.interval)?,
+            time_index,
+            by,
+        )
+        .context(DataFusionSnafu)?;
+        let range_schema = range_select.schema.clone();
+        let plan = LogicalPlan::Extension(Extension {
+            node: Arc::new(range_select),
+        });
+
+        let projection = items
+            .into_iter()
+            .map(|item| match item {
+                ProjectionItem::Range(name) => Ok(Expr::Column(Column::from_name(name))),
+                ProjectionItem::Expr(expr, alias) => {
+                    let expr = self.sql_to_expr(expr, &range_schema)?;
+                    Ok(match alias {
+                        Some(alias) => expr.alias(alias),
+                        None => expr,
+                    })
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mut builder = LogicalPlanBuilder::from(plan)
+            .project(projection)
+            .context(DataFusionSnafu)?;
+
+        if !query.inner.order_by.is_empty() {
+            let schema = builder.schema().clone();
+            let sort_exprs = query
+                .inner
+                .order_by
+                .iter()
+                .map(|order_by| {
+                    let asc = order_by.asc.unwrap_or(true);
+                    Ok(Expr::Sort(Sort::new(
+                        Box::new(self.sql_to_expr(order_by.expr.clone(), &schema)?),
+                        asc,
+                        // NULLS FIRST is the default of descending order.
+                        order_by.nulls_first.unwrap_or(!asc),
+                    )))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            builder = builder.sort(sort_exprs).context(DataFusionSnafu)?;
+        }
+
+        let skip = match &query.inner.offset {
+            Some(offset) => to_usize(&offset.value)?,
+            None => 0,
+        };
+        let fetch = query.inner.limit.as_ref().map(to_usize).transpose()?;
+        if skip > 0 || fetch.is_some() {
+            builder = builder.limit(skip, fetch).context(DataFusionSnafu)?;
+        }
+
+        builder.build().context(DataFusionSnafu)
+    }
+
+    /// Plans `range_fn(<aggregate>, '<range>', '<fill>')`.
+    fn plan_range_fn(
+        &self,
+        func: &Function,
+        name: String,
+        input_schema: &DFSchema,
+        default_fill: &Fill,
+    ) -> Result<RangeFn> {
+        let args = func
+            .args
+            .iter()
+            .map(|arg| match arg {
+                FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => Ok(expr),
+                other => RangeQuerySnafu {
+                    msg: format!("unexpected range function argument: {other}"),
+                }
+                .fail(),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        ensure!(
+            args.len() == 3,
+            RangeQuerySnafu {
+                msg: format!("{RANGE_FN} expects 3 arguments, got {}", args.len()),
+            }
+        );
+
+        let expr = self.sql_to_expr(args[0].clone(), input_schema)?;
+        ensure!(
+            matches!(expr, Expr::AggregateFunction(_) | Expr::AggregateUDF(_)),
+            RangeQuerySnafu {
+                msg: format!("expect an aggregate function before RANGE, got {expr}"),
+            }
+        );
+        let range = parse_duration(&string_arg(args[1])?)?;
+        let fill = string_arg(args[2])?;
+        let fill = if fill.is_empty() {
+            default_fill.clone()
+        } else {
+            fill.parse::<Fill>().context(DataFusionSnafu)?
+        };
+
+        Ok(RangeFn {
+            name,
+            expr,
+            range,
+            fill,
+        })
+    }
+
+    fn sql_to_expr(&self, expr: SqlExpr, schema: &DFSchema) -> Result<Expr> {
+        self.sql_to_rel
+            .sql_to_expr(expr, schema, &mut PlannerContext::new())
+            .context(DataFusionSnafu)
+    }
+}
+
+fn is_range_fn(func: &Function) -> bool {
+    func.name.0.len() == 1 && func.name.0[0].value.eq_ignore_ascii_case(RANGE_FN)
+}
+
+/// Display name of range expression without alias, like `avg(v) RANGE 5m FILL PREV`.
+fn range_fn_display_name(func: &Function) -> String {
+    let args = func.args.iter().map(ToString::to_string).collect::<Vec<_>>();
+    let mut name = format!(
+        "{} RANGE {}",
+        args.first().cloned().unwrap_or_default(),
+        args.get(1).map(|s| s.trim_matches('\'')).unwrap_or_default()
+    );
+    if let Some(fill) = args.get(2).map(|s| s.trim_matches('\'')) && !fill.is_empty() {
+        name.push_str(&format!(" FILL {fill}"));
+    }
+    name
+}
+
+fn string_arg(expr: &SqlExpr) -> Result<String> {
+    match expr {
+        SqlExpr::Value(Value::SingleQuotedString(s)) => Ok(s.clone()),
+        other => RangeQuerySnafu {
+            msg: format!("expect a string literal, got {other}"),
+        }
+        .fail(),
+    }
+}
+
+fn parse_duration(s: &str) -> Result<Millisecond> {
+    let duration = promql_parser::util::parse_duration(s).map_err(|msg| {
+        RangeQuerySnafu {
+            msg: format!("invalid duration '{s}': {msg}"),
+        }
+        .build()
+    })?;
+    let millis = duration.as_millis() as Millisecond;
+    ensure!(
+        millis > 0,
+        RangeQuerySnafu {
+            msg: format!("duration '{s}' must be at least 1ms"),
+        }
+    );
+    Ok(millis)
+}
+
+fn to_usize(expr: &SqlExpr) -> Result<usize> {
+    match expr {
+        SqlExpr::Value(Value::Number(n, _)) => n.parse::<usize>().map_err(|e| {
+            RangeQuerySnafu {
+                msg: format!("invalid number {n}: {e}"),
+            }
+            .build()
+        }),
+        other => RangeQuerySnafu {
+            msg: format!("expect a number in LIMIT/OFFSET, got {other}"),
+        }
+        .fail(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(300_000, parse_duration("5m").unwrap());
+        assert_eq!(5_400_000, parse_duration("1h30m").unwrap());
+        assert_eq!(1_500, parse_duration("1500ms").unwrap());
+        assert!(parse_duration("5 apples").is_err());
+        assert!(parse_duration("0s").is_err());
+    }
+}
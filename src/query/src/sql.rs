@@ -24,9 +24,11 @@ use common_catalog::consts::{
 use common_datasource::file_format::{infer_schemas, FileFormat, Format};
 use common_datasource::lister::{Lister, Source};
 use common_datasource::object_store::build_backend;
+use common_datasource::partition::{infer_partition_columns, partition_base_dir, partition_field};
 use common_datasource::util::find_dir_and_filename;
 use common_query::Output;
 use common_recordbatch::{RecordBatch, RecordBatches};
+use datatypes::arrow::datatypes::Schema as ArrowSchema;
 use datatypes::prelude::*;
 use datatypes::schema::{ColumnSchema, RawSchema, Schema};
 use datatypes::vectors::{Helper, StringVector};
@@ -264,23 +266,40 @@ fn describe_column_semantic_types(
     ))
 }
 
+/// Lists files of an external table and returns them with the table's partition columns
+/// and schema. Partition columns are inferred from Hive-style directories of the files.
 pub async fn prepare_immutable_file_table_files_and_schema(
     options: &HashMap<String, String>,
     columns: &Vec<ColumnDef>,
-) -> Result<(Vec<String>, RawSchema)> {
+) -> Result<(Vec<String>, Vec<String>, RawSchema)> {
     let (object_store, files) = prepare_immutable_file_table(options).await?;
-    let schema = if !columns.is_empty() {
+    let location =
+        options
+            .get(IMMUTABLE_TABLE_LOCATION_KEY)
+            .context(error::MissingRequiredFieldSnafu {
+                name: IMMUTABLE_TABLE_LOCATION_KEY,
+            })?;
+    let base_dir = partition_base_dir(location).context(error::InferSchemaSnafu)?;
+    let partition_columns =
+        infer_partition_columns(&base_dir, &files).context(error::InferSchemaSnafu)?;
+    let (partition_columns, schema) = if !columns.is_empty() {
         let columns_schemas: Vec<_> = columns
             .iter()
             .map(|column| column_def_to_schema(column, false).context(error::ParseSqlSnafu))
             .collect::<Result<Vec<_>>>()?;
-        RawSchema::new(columns_schemas)
+        // Only partition columns declared by the user are exposed.
+        let partition_columns = partition_columns
+            .into_iter()
+            .filter(|c| columns_schemas.iter().any(|column| &column.name == c))
+            .collect();
+        (partition_columns, RawSchema::new(columns_schemas))
     } else {
         let format = parse_immutable_file_table_format(options)?;
-        infer_immutable_file_table_schema(&object_store, &*format, &files).await?
+        infer_immutable_file_table_schema(&object_store, &*format, &files, partition_columns)
+            .await?
     };
 
-    Ok((files, schema))
+    Ok((files, partition_columns, schema))
 }
 
 // lists files in the frontend to reduce unnecessary scan requests repeated in each datanode.
@@ -306,7 +325,7 @@ async fn prepare_immutable_file_table(
         .transpose()
         .context(error::BuildRegexSnafu)?;
     let object_store = build_backend(url, options).context(error::BuildBackendSnafu)?;
-    let lister = Lister::new(object_store.clone(), source, dir, regex);
+    let lister = Lister::new(object_store.clone(), source, dir, regex).with_partition_dirs(true);
     // If we scan files in a directory every time the database restarts,
     // then it might lead to a potential undefined behavior:
     // If a user adds a file with an incompatible schema to that directory,
@@ -336,20 +355,41 @@ fn parse_immutable_file_table_format(
             Format::Json(format) => Box::new(format),
             Format::Parquet(format) => Box::new(format),
             Format::Orc(format) => Box::new(format),
+            Format::Avro(format) => Box::new(format),
+            Format::ArrowIpc(format) => Box::new(format),
         },
     )
 }
 
+/// Infers the schema from files, partition columns also in files are read from files instead.
 async fn infer_immutable_file_table_schema(
     object_store: &ObjectStore,
     file_format: &dyn FileFormat,
     files: &[String],
-) -> Result<RawSchema> {
+    partition_columns: Vec<String>,
+) -> Result<(Vec<String>, RawSchema)> {
     let merged = infer_schemas(object_store, files, file_format)
         .await
         .context(error::InferSchemaSnafu)?;
-    Ok(RawSchema::from(
-        &Schema::try_from(merged).context(error::ConvertSchemaSnafu)?,
+    let partition_columns = partition_columns
+        .into_iter()
+        .filter(|c| merged.field_with_name(c).is_err())
+        .collect::<Vec<_>>();
+    let fields = merged
+        .fields()
+        .iter()
+        .cloned()
+        .chain(
+            partition_columns
+                .iter()
+                .map(|c| Arc::new(partition_field(c))),
+        )
+        .collect::<Vec<_>>();
+    let merged = ArrowSchema::new_with_metadata(fields, merged.metadata().clone());
+
+    Ok((
+        partition_columns,
+        RawSchema::from(&Schema::try_from(merged).context(error::ConvertSchemaSnafu)?),
     ))
 }
 
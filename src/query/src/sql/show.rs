@@ -115,6 +115,12 @@ fn create_column_def(column_schema: &ColumnSchema) -> Result<ColumnDef> {
         options.push(column_option_def(ColumnOption::Comment(c.to_string())));
     }
 
+    if let Some(analyzer) = column_schema.fulltext_analyzer() {
+        options.push(column_option_def(statements::fulltext_column_option(
+            analyzer,
+        )));
+    }
+
     Ok(ColumnDef {
         name: name[..].into(),
         data_type: statements::concrete_data_type_to_sql_data_type(&column_schema.data_type)
@@ -0,0 +1,272 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Scalar functions defined by SQL expressions through `CREATE FUNCTION`.
+
+use std::sync::Arc;
+
+use arrow_schema::DataType;
+use common_query::error::{
+    ArrowComputeSnafu, ExecuteFunctionSnafu, GeneralDataFusionSnafu, Result as QueryResult,
+};
+use common_query::prelude::{
+    ColumnarValue, ReturnTypeFunction, ScalarFunctionImplementation, ScalarUdf, Signature,
+    Volatility,
+};
+use datafusion::catalog::TableReference;
+use datafusion::execution::context::SessionState;
+use datafusion::physical_plan::udaf::AggregateUDF;
+use datafusion::physical_plan::udf::ScalarUDF as DfScalarUdf;
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::tree_node::{TreeNode, VisitRecursion};
+use datafusion_common::{DFSchema, DataFusionError, ScalarValue};
+use datafusion_expr::expr::{ScalarFunction, ScalarUDF};
+use datafusion_expr::{cast, EmptyRelation, Expr, LogicalPlan, LogicalPlanBuilder, TableSource};
+use datafusion_optimizer::analyzer::Analyzer;
+use datafusion_physical_expr::execution_props::ExecutionProps;
+use datafusion_physical_expr::{create_physical_expr, PhysicalExpr};
+use datafusion_sql::planner::{ContextProvider, PlannerContext, SqlToRel};
+use datatypes::arrow::datatypes::{Field, Schema, SchemaRef};
+use datatypes::arrow::record_batch::{RecordBatch, RecordBatchOptions};
+use datatypes::prelude::ConcreteDataType;
+use snafu::ResultExt;
+use sql::ast::Ident;
+use sql::statements::function::CreateFunction;
+use sql::statements::sql_data_type_to_concrete_data_type;
+
+use crate::error::{DataFusionSnafu, Result, SqlSnafu};
+use crate::query_engine::QueryEngineState;
+
+/// Creates the udf of the schema evaluating the body of the function over its
+/// arguments. The body is only allowed to call functions callable in the schema.
+pub(crate) fn create_sql_udf(
+    state: &QueryEngineState,
+    catalog: &str,
+    schema: &str,
+    func: &CreateFunction,
+) -> Result<ScalarUdf> {
+    let mut arg_types = Vec::with_capacity(func.args.len());
+    let mut fields = Vec::with_capacity(func.args.len());
+    for arg in &func.args {
+        let data_type = sql_data_type_to_concrete_data_type(&arg.data_type).context(SqlSnafu)?;
+        fields.push(Field::new(
+            normalize_ident(&arg.name),
+            data_type.as_arrow_type(),
+            true,
+        ));
+        arg_types.push(data_type);
+    }
+    let return_type = sql_data_type_to_concrete_data_type(&func.return_type).context(SqlSnafu)?;
+
+    let arrow_schema = Arc::new(Schema::new(fields));
+    let df_schema =
+        Arc::new(DFSchema::try_from(arrow_schema.as_ref().clone()).context(DataFusionSnafu)?);
+    let context_provider = SqlFunctionContextProvider {
+        state,
+        session_state: &state.session_state(),
+        catalog,
+        schema,
+    };
+    let expr =
+        plan_body(&context_provider, func, &df_schema, &return_type).context(DataFusionSnafu)?;
+    // Also makes sure the body is executable.
+    let physical_expr =
+        create_physical_expr(&expr, &df_schema, &arrow_schema, &ExecutionProps::new())
+            .context(DataFusionSnafu)?;
+    let volatility = body_volatility(&expr);
+
+    let evaluator = BodyEvaluator {
+        physical_expr: (volatility == Volatility::Immutable).then_some(physical_expr),
+        expr,
+        df_schema,
+        schema: arrow_schema,
+    };
+    let return_type = Arc::new(return_type);
+    let return_type: ReturnTypeFunction =
+        Arc::new(move |_: &[ConcreteDataType]| Ok(return_type.clone()));
+    let fun: ScalarFunctionImplementation =
+        Arc::new(move |args: &[ColumnarValue]| evaluator.evaluate(args));
+
+    Ok(ScalarUdf::new(
+        &normalize_ident(&func.name),
+        &Signature::exact(arg_types, volatility),
+        &return_type,
+        &fun,
+    ))
+}
+
+/// Returns the volatility of the body, which is the volatility of the most
+/// volatile function it calls.
+fn body_volatility(expr: &Expr) -> Volatility {
+    let mut volatility = Volatility::Immutable;
+    let _ = expr.apply(&mut |expr| {
+        let called = match expr {
+            Expr::ScalarFunction(ScalarFunction { fun, .. }) => fun.volatility(),
+            Expr::ScalarUDF(ScalarUDF { fun, .. }) => fun.signature.volatility,
+            _ => return Ok(VisitRecursion::Continue),
+        };
+        match called {
+            Volatility::Volatile => {
+                volatility = Volatility::Volatile;
+                return Ok(VisitRecursion::Stop);
+            }
+            Volatility::Stable => volatility = Volatility::Stable,
+            Volatility::Immutable => {}
+        }
+        Ok(VisitRecursion::Continue)
+    });
+    volatility
+}
+
+/// Plans the body to an expression of the return type.
+fn plan_body(
+    context_provider: &SqlFunctionContextProvider<'_>,
+    func: &CreateFunction,
+    df_schema: &Arc<DFSchema>,
+    return_type: &ConcreteDataType,
+) -> datafusion_common::Result<Expr> {
+    let expr = SqlToRel::new(context_provider).sql_to_expr(
+        func.body.clone(),
+        df_schema,
+        &mut PlannerContext::new(),
+    )?;
+    let expr = cast(expr, return_type.as_arrow_type());
+
+    // The analyzer works on plans, so the expression is wrapped by a projection
+    // to get its arguments coerced.
+    let plan = LogicalPlanBuilder::from(LogicalPlan::EmptyRelation(EmptyRelation {
+        produce_one_row: false,
+        schema: df_schema.clone(),
+    }))
+    .project(vec![expr])?
+    .build()?;
+    let plan = Analyzer::new().execute_and_check(&plan, context_provider.options())?;
+    match plan {
+        LogicalPlan::Projection(projection) if projection.expr.len() == 1 => {
+            Ok(projection.expr[0].clone().unalias())
+        }
+        plan => Err(DataFusionError::Internal(format!(
+            "Unexpected plan of function body: {plan:?}"
+        ))),
+    }
+}
+
+/// Evaluates the planned body over the arguments.
+struct BodyEvaluator {
+    /// The physical expression of the body, it's absent if the body isn't
+    /// immutable. For example, the body calling `now()` is created for each
+    /// evaluation so it's not bound to the time the function is created.
+    physical_expr: Option<Arc<dyn PhysicalExpr>>,
+    expr: Expr,
+    df_schema: Arc<DFSchema>,
+    schema: SchemaRef,
+}
+
+impl BodyEvaluator {
+    fn evaluate(&self, args: &[ColumnarValue]) -> QueryResult<ColumnarValue> {
+        // Zero-argument functions may be passed an array indicating the row count.
+        let args = &args[..self.schema.fields().len().min(args.len())];
+        let num_rows = args.iter().find_map(|arg| match arg {
+            ColumnarValue::Vector(v) => Some(v.len()),
+            ColumnarValue::Scalar(_) => None,
+        });
+
+        let columns = args
+            .iter()
+            .map(|arg| {
+                arg.clone()
+                    .try_into_vector(num_rows.unwrap_or(1))
+                    .map(|v| v.to_arrow_array())
+            })
+            .collect::<QueryResult<Vec<_>>>()?;
+        let batch = RecordBatch::try_new_with_options(
+            self.schema.clone(),
+            columns,
+            &RecordBatchOptions::new().with_row_count(Some(num_rows.unwrap_or(1))),
+        )
+        .context(ArrowComputeSnafu)?;
+
+        let physical_expr = match &self.physical_expr {
+            Some(physical_expr) => physical_expr.clone(),
+            None => create_physical_expr(
+                &self.expr,
+                &self.df_schema,
+                &self.schema,
+                &ExecutionProps::new(),
+            )
+            .context(ExecuteFunctionSnafu)?,
+        };
+        let value = physical_expr
+            .evaluate(&batch)
+            .context(ExecuteFunctionSnafu)?;
+
+        match (num_rows, value) {
+            // All arguments are scalars, so is the result.
+            (None, datafusion_expr::ColumnarValue::Array(array)) => {
+                let scalar =
+                    ScalarValue::try_from_array(&array, 0).context(GeneralDataFusionSnafu)?;
+                Ok(ColumnarValue::Scalar(scalar))
+            }
+            (_, value) => ColumnarValue::try_from(&value),
+        }
+    }
+}
+
+/// Resolves functions called in the body of SQL functions of the schema, tables
+/// are not accessible.
+struct SqlFunctionContextProvider<'a> {
+    state: &'a QueryEngineState,
+    session_state: &'a SessionState,
+    catalog: &'a str,
+    schema: &'a str,
+}
+
+impl ContextProvider for SqlFunctionContextProvider<'_> {
+    fn get_table_provider(
+        &self,
+        name: TableReference,
+    ) -> datafusion_common::Result<Arc<dyn TableSource>> {
+        Err(DataFusionError::Plan(format!(
+            "Table {name} can't be accessed in functions"
+        )))
+    }
+
+    fn get_function_meta(&self, name: &str) -> Option<Arc<DfScalarUdf>> {
+        self.state
+            .resolve_udf(self.catalog, self.schema, name)
+            .map(|func| Arc::new(func.into_df_udf()))
+    }
+
+    fn get_aggregate_meta(&self, _name: &str) -> Option<Arc<AggregateUDF>> {
+        None
+    }
+
+    fn get_variable_type(&self, _variable_names: &[String]) -> Option<DataType> {
+        None
+    }
+
+    fn options(&self) -> &ConfigOptions {
+        self.session_state.config_options()
+    }
+}
+
+/// Normalizes names of functions and arguments the same way as identifiers in
+/// SQL, unquoted names are case insensitive.
+pub fn normalize_ident(ident: &Ident) -> String {
+    if ident.quote_style.is_some() {
+        ident.value.clone()
+    } else {
+        ident.value.to_lowercase()
+    }
+}
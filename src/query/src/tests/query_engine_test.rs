@@ -26,9 +26,13 @@ use datafusion::datasource::DefaultTableSource;
 use datafusion_expr::logical_plan::builder::LogicalPlanBuilder;
 use datatypes::prelude::*;
 use datatypes::schema::{ColumnSchema, Schema};
-use datatypes::vectors::UInt32Vector;
+use datatypes::vectors::{Int64Vector, UInt32Vector};
 use session::context::QueryContext;
 use snafu::ResultExt;
+use sql::dialect::GreptimeDbDialect;
+use sql::parser::ParserContext;
+use sql::statements::function::CreateFunction;
+use sql::statements::statement::Statement;
 use table::table::adapter::DfTableProviderAdapter;
 use table::table::numbers::{NumbersTable, NUMBERS_TABLE_NAME};
 use table::test_util::MemTable;
@@ -36,7 +40,7 @@ use table::test_util::MemTable;
 use crate::error::{QueryExecutionSnafu, Result};
 use crate::parser::QueryLanguageParser;
 use crate::plan::LogicalPlan;
-use crate::query_engine::options::QueryOptions;
+use crate::query_engine::options::{QueryConfig, QueryOptions};
 use crate::query_engine::QueryEngineFactory;
 use crate::tests::exec_selection;
 use crate::tests::pow::pow;
@@ -129,7 +133,14 @@ async fn test_query_validate() -> Result<()> {
     });
     let plugins = Arc::new(plugins);
 
-    let factory = QueryEngineFactory::new_with_plugins(catalog_list, false, None, None, plugins);
+    let factory = QueryEngineFactory::new_with_plugins(
+        catalog_list,
+        false,
+        None,
+        None,
+        plugins,
+        &QueryConfig::default(),
+    );
     let engine = factory.query_engine();
 
     let stmt = QueryLanguageParser::parse_sql("select number from public.numbers").unwrap();
@@ -190,3 +201,92 @@ async fn test_udf() -> Result<()> {
 
     Ok(())
 }
+
+fn parse_create_function(sql: &str) -> CreateFunction {
+    let mut stmts = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+    let Statement::CreateFunction(func) = stmts.remove(0) else { unreachable!() };
+    func
+}
+
+#[tokio::test]
+async fn test_sql_udf() -> Result<()> {
+    common_telemetry::init_default_ut_logging();
+    let catalog_list = catalog_manager()?;
+
+    let factory = QueryEngineFactory::new(catalog_list, false);
+    let engine = factory.query_engine();
+
+    let create_sql_udf = |sql| {
+        engine.create_sql_udf(
+            DEFAULT_CATALOG_NAME,
+            DEFAULT_SCHEMA_NAME,
+            &parse_create_function(sql),
+        )
+    };
+
+    let udf = create_sql_udf("CREATE FUNCTION Double_It(n BIGINT) RETURNS BIGINT AS 'n * 2'")?;
+    assert_eq!("double_it", udf.name);
+    assert_eq!(Volatility::Immutable, udf.signature.volatility);
+    engine.register_schema_udf(DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, udf);
+    // Functions can call each other.
+    let udf = create_sql_udf(
+        "CREATE FUNCTION quadruple(n BIGINT) RETURNS BIGINT AS 'double_it(double_it(n))'",
+    )?;
+    engine.register_schema_udf(DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, udf);
+
+    let udf = create_sql_udf("CREATE FUNCTION jitter(n DOUBLE) RETURNS DOUBLE AS 'n + random()'")?;
+    assert_eq!(Volatility::Volatile, udf.signature.volatility);
+
+    let sql = "select quadruple(number) as q from numbers limit 3";
+    let batches = exec_selection(engine.clone(), sql).await;
+    assert_eq!(1, batches.len());
+    assert_eq!("q", batches[0].schema.column_schemas()[0].name);
+    assert_eq!(
+        *batches[0].column(0),
+        Arc::new(Int64Vector::from_slice([0, 4, 8])) as VectorRef
+    );
+
+    // Functions are only visible in their own schema.
+    let stmt = QueryLanguageParser::parse_sql("select double_it(1)").unwrap();
+    assert!(engine
+        .planner()
+        .plan(
+            stmt,
+            Arc::new(QueryContext::with(DEFAULT_CATALOG_NAME, "other"))
+        )
+        .await
+        .is_err());
+    assert!(engine
+        .create_sql_udf(
+            DEFAULT_CATALOG_NAME,
+            "other",
+            &parse_create_function("CREATE FUNCTION f(n BIGINT) RETURNS BIGINT AS 'double_it(n)'"),
+        )
+        .is_err());
+
+    // Columns other than the arguments and tables are not accessible.
+    for sql in [
+        "CREATE FUNCTION f(n BIGINT) RETURNS BIGINT AS 'm + 1'",
+        "CREATE FUNCTION f(n BIGINT) RETURNS BIGINT AS 'n + (SELECT max(number) FROM numbers)'",
+        "CREATE FUNCTION f(n BIGINT) RETURNS BIGINT AS 'no_such_function(n)'",
+    ] {
+        assert!(create_sql_udf(sql).is_err());
+    }
+
+    assert!(engine.deregister_schema_udf(DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, "double_it"));
+    assert!(!engine.deregister_schema_udf(DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, "double_it"));
+    let stmt = QueryLanguageParser::parse_sql("select double_it(number) from numbers").unwrap();
+    assert!(engine
+        .planner()
+        .plan(stmt, QueryContext::arc())
+        .await
+        .is_err());
+    // Functions created before still work.
+    let batches = exec_selection(engine, sql).await;
+    assert_eq!(
+        *batches[0].column(0),
+        Arc::new(Int64Vector::from_slice([0, 4, 8])) as VectorRef
+    );
+
+    Ok(())
+}
@@ -82,6 +82,59 @@ pub enum Error {
 
     #[snafu(display("Failed to cast type, msg: {}", msg))]
     CastType { msg: String, location: Location },
+
+    #[snafu(display("Failed to scan scripts table, source: {}", source))]
+    ScanScriptsTable {
+        location: Location,
+        source: table::error::Error,
+    },
+
+    #[snafu(display("Invalid schedule of script {}, reason: {}", name, reason))]
+    InvalidSchedule {
+        name: String,
+        reason: String,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to find table {}, source: {}", table, source))]
+    FindTable {
+        table: String,
+        location: Location,
+        source: catalog::error::Error,
+    },
+
+    #[snafu(display("Table not found: {}", table))]
+    TableNotFound { table: String, location: Location },
+
+    #[snafu(display(
+        "Failed to write script output into table {}, source: {}",
+        table,
+        source
+    ))]
+    WriteTargetTable {
+        table: String,
+        location: Location,
+        source: table::error::Error,
+    },
+
+    #[snafu(display("Failed to query source table {}, source: {}", table, source))]
+    QuerySourceTable {
+        table: String,
+        location: Location,
+        source: query::error::Error,
+    },
+
+    #[snafu(display("Failed to start script scheduler, source: {}", source))]
+    StartScheduler {
+        location: Location,
+        source: common_runtime::error::Error,
+    },
+
+    #[snafu(display("Failed to stop script scheduler, source: {}", source))]
+    StopScheduler {
+        location: Location,
+        source: common_runtime::error::Error,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -91,11 +144,16 @@ impl ErrorExt for Error {
         use Error::*;
         match self {
             FindColumnInScriptsTable { .. } | CastType { .. } => StatusCode::Unexpected,
-            ScriptsTableNotFound { .. } => StatusCode::TableNotFound,
-            RegisterScriptsTable { source, .. } | FindScriptsTable { source, .. } => {
-                source.status_code()
-            }
-            InsertScript { source, .. } => source.status_code(),
+            ScriptsTableNotFound { .. } | TableNotFound { .. } => StatusCode::TableNotFound,
+            RegisterScriptsTable { source, .. }
+            | FindScriptsTable { source, .. }
+            | FindTable { source, .. } => source.status_code(),
+            InsertScript { source, .. }
+            | ScanScriptsTable { source, .. }
+            | WriteTargetTable { source, .. } => source.status_code(),
+            QuerySourceTable { source, .. } => source.status_code(),
+            StartScheduler { source, .. } | StopScheduler { source, .. } => source.status_code(),
+            InvalidSchedule { .. } => StatusCode::InvalidArguments,
             CompilePython { source, .. } | ExecutePython { source, .. } => source.status_code(),
             FindScript { source, .. } => source.status_code(),
             CollectRecords { source, .. } => source.status_code(),
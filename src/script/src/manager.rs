@@ -17,7 +17,7 @@ use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
 use catalog::CatalogManagerRef;
-use common_query::Output;
+use common_query::{Output, ScriptSchedule};
 use common_telemetry::logging;
 use query::QueryEngineRef;
 use snafu::{OptionExt, ResultExt};
@@ -25,12 +25,18 @@ use snafu::{OptionExt, ResultExt};
 use crate::engine::{CompileContext, EvalContext, Script, ScriptEngine};
 use crate::error::{CompilePythonSnafu, ExecutePythonSnafu, Result, ScriptNotFoundSnafu};
 use crate::python::{PyEngine, PyScript};
+use crate::scheduler::validate_schedule;
 use crate::table::ScriptsTable;
 
+/// Schedules of scripts, keyed by schema and name.
+pub type ScriptSchedules = HashMap<(String, String), ScriptSchedule>;
+
 pub struct ScriptManager {
     compiled: RwLock<HashMap<String, Arc<PyScript>>>,
     py_engine: PyEngine,
     table: ScriptsTable,
+    /// Scripts to run by the scheduler, `None` until loaded from the scripts table.
+    schedules: RwLock<Option<ScriptSchedules>>,
 }
 
 impl ScriptManager {
@@ -42,6 +48,7 @@ impl ScriptManager {
             compiled: RwLock::new(HashMap::default()),
             py_engine: PyEngine::new(query_engine.clone()),
             table: ScriptsTable::new(catalog_manager, query_engine).await?,
+            schedules: RwLock::new(None),
         })
     }
 
@@ -74,17 +81,55 @@ impl ScriptManager {
             .context(CompilePythonSnafu { name })
     }
 
+    /// Compiles and stores the script, the script is run by the scheduler if
+    /// the schedule is set.
     pub async fn insert_and_compile(
         &self,
         schema: &str,
         name: &str,
         script: &str,
+        schedule: ScriptSchedule,
     ) -> Result<Arc<PyScript>> {
+        validate_schedule(&schedule, name)?;
         let compiled_script = self.compile(name, script).await?;
-        self.table.insert(schema, name, script).await?;
+        self.table.insert(schema, name, script, &schedule).await?;
+
+        if let Some(schedules) = self.schedules.write().unwrap().as_mut() {
+            let key = (schema.to_string(), name.to_string());
+            if schedule.is_scheduled() {
+                let _ = schedules.insert(key, schedule);
+            } else {
+                let _ = schedules.remove(&key);
+            }
+        }
         Ok(compiled_script)
     }
 
+    /// Returns the schedules of scripts, loads them from the scripts table on
+    /// the first call.
+    pub async fn schedules(&self) -> Result<ScriptSchedules> {
+        if let Some(schedules) = self.schedules.read().unwrap().as_ref() {
+            return Ok(schedules.clone());
+        }
+
+        let loaded = self.table.find_schedules().await?;
+        logging::info!("Loaded {} scheduled scripts", loaded.len());
+        let mut schedules = self.schedules.write().unwrap();
+        let schedules = schedules.get_or_insert_with(|| {
+            loaded
+                .into_iter()
+                .map(|(schema, name, schedule)| ((schema, name), schedule))
+                .collect()
+        });
+        Ok(schedules.clone())
+    }
+
+    /// Drops the loaded schedules, so the next call of [Self::schedules]
+    /// reloads them with scripts inserted through other frontends.
+    pub fn invalidate_schedules(&self) {
+        *self.schedules.write().unwrap() = None;
+    }
+
     pub async fn execute(
         &self,
         schema: &str,
@@ -189,6 +234,7 @@ mod tests {
 def test(n):
     return n + 1;
 "#,
+                &ScriptSchedule::default(),
             )
             .await
             .unwrap();
@@ -206,5 +252,40 @@ def test(n):
             let cached = mgr.compiled.read().unwrap();
             let _ = cached.get(name).unwrap();
         }
+
+        // insert a scheduled script
+        let schedule = ScriptSchedule {
+            cron: Some("*/5 * * * *".to_string()),
+            source_table: None,
+            target_table: Some("target".to_string()),
+        };
+        let _ = mgr
+            .insert_and_compile(
+                schema,
+                "scheduled",
+                r#"
+@copr(sql='select number from numbers limit 10', args=['number'], returns=['n'])
+def scheduled(n):
+    return n + 1;
+"#,
+                schedule.clone(),
+            )
+            .await
+            .unwrap();
+        let schedules = mgr.schedules().await.unwrap();
+        assert_eq!(1, schedules.len());
+        assert_eq!(
+            Some(&schedule),
+            schedules.get(&(schema.to_string(), "scheduled".to_string()))
+        );
+
+        let invalid = ScriptSchedule {
+            cron: Some("every minute".to_string()),
+            ..Default::default()
+        };
+        assert!(mgr
+            .insert_and_compile(schema, "invalid", "", invalid)
+            .await
+            .is_err());
     }
 }
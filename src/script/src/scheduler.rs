@@ -0,0 +1,680 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Scheduler to run scripts by themselves.
+//!
+//! A script runs periodically by a cron expression, or when new data arrives in
+//! its source table, and its output is written into the target table. Each run
+//! is passed to a [ScriptRunRecorder]. After a failed run, the script is
+//! retried with an exponential backoff.
+//!
+//! New data in the source table is detected by the time index growing, late
+//! data with older timestamps doesn't trigger the script. When several
+//! schedulers share the same scripts table, a [RunnerElection] picks the one
+//! running the scripts, otherwise scripts run more than once.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use catalog::CatalogManagerRef;
+use chrono::{DateTime, Utc};
+use common_catalog::consts::DEFAULT_CATALOG_NAME;
+use common_error::ext::ErrorExt;
+use common_query::{Output, ScriptSchedule};
+use common_recordbatch::{util as record_util, RecordBatch};
+use common_runtime::{RepeatedTask, TaskFunction};
+use common_telemetry::logging;
+use common_time::timestamp::TimeUnit;
+use common_time::{util, Timestamp};
+use datatypes::value::Value;
+use query::parser::QueryLanguageParser;
+use query::QueryEngineRef;
+use session::context::QueryContext;
+use snafu::{ensure, OptionExt, ResultExt};
+use table::requests::InsertRequest;
+use table::TableRef;
+
+use crate::error::{
+    CollectRecordsSnafu, Error, FindTableSnafu, InvalidScheduleSnafu, QuerySourceTableSnafu,
+    Result, StartSchedulerSnafu, StopSchedulerSnafu, TableNotFoundSnafu, WriteTargetTableSnafu,
+};
+use crate::manager::ScriptManager;
+
+/// Checks the cron expression and that the target table comes with a trigger.
+pub(crate) fn validate_schedule(schedule: &ScriptSchedule, name: &str) -> Result<()> {
+    if let Some(cron) = &schedule.cron {
+        let _ = parse_cron(cron).map_err(|reason| {
+            InvalidScheduleSnafu {
+                name,
+                reason: format!("invalid cron expression {cron}: {reason}"),
+            }
+            .build()
+        })?;
+    }
+    ensure!(
+        schedule.target_table.is_none() || schedule.is_scheduled(),
+        InvalidScheduleSnafu {
+            name,
+            reason: "target table requires a cron expression or a source table",
+        }
+    );
+    Ok(())
+}
+
+/// Parses the cron expression of 5 fields (minute, hour, day of month, month
+/// and day of week), or 6 fields with seconds in front.
+fn parse_cron(expr: &str) -> std::result::Result<cron::Schedule, String> {
+    let expr = expr.trim();
+    let expr = if expr.split_whitespace().count() == 5 {
+        format!("0 {expr}")
+    } else {
+        expr.to_string()
+    };
+    cron::Schedule::from_str(&expr).map_err(|e| e.to_string())
+}
+
+#[derive(Debug, Clone)]
+pub struct SchedulerConfig {
+    /// Interval to check which scripts are due.
+    pub tick_interval: Duration,
+    /// Interval to check the source tables for new data.
+    pub trigger_interval: Duration,
+    /// Backoff after the first failed run, doubled on each consecutive failure.
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            tick_interval: Duration::from_secs(1),
+            trigger_interval: Duration::from_secs(10),
+            initial_backoff: Duration::from_secs(10),
+            max_backoff: Duration::from_secs(600),
+        }
+    }
+}
+
+/// A run of a script by the scheduler.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptRun {
+    pub schema: String,
+    pub name: String,
+    /// What triggered the run, `schedule` or `data`.
+    pub trigger: String,
+    /// Start time of the run in milliseconds.
+    pub start_time: i64,
+    pub elapsed_ms: i64,
+    /// Rows the script outputs, `None` if the run failed.
+    pub rows: Option<i64>,
+    pub error: Option<String>,
+}
+
+/// Keeps the history of script runs by the scheduler.
+#[async_trait]
+pub trait ScriptRunRecorder: Send + Sync {
+    async fn record(&self, run: ScriptRun);
+}
+
+pub type ScriptRunRecorderRef = Arc<dyn ScriptRunRecorder>;
+
+/// Elects the scheduler running the scripts among schedulers sharing the
+/// scripts table.
+pub trait RunnerElection: Send + Sync {
+    /// Returns whether this scheduler may run scripts now.
+    fn is_runner(&self) -> bool;
+}
+
+pub type RunnerElectionRef = Arc<dyn RunnerElection>;
+
+/// Returns the backoff after `failures` consecutive failed runs.
+fn backoff(failures: u32, config: &SchedulerConfig) -> Duration {
+    let factor = 2u32.saturating_pow(failures.saturating_sub(1));
+    config
+        .initial_backoff
+        .saturating_mul(factor)
+        .min(config.max_backoff)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum RunTrigger {
+    /// The cron expression is due.
+    Schedule,
+    /// Rows with timestamps in `(since, until]` arrived in the source table.
+    Data {
+        since: Option<Timestamp>,
+        until: Timestamp,
+    },
+}
+
+impl RunTrigger {
+    fn name(&self) -> &'static str {
+        match self {
+            RunTrigger::Schedule => "schedule",
+            RunTrigger::Data { .. } => "data",
+        }
+    }
+
+    /// Params passed to the script, data triggered runs get the time range of
+    /// new data in milliseconds as `since` (exclusive) and `until` (inclusive).
+    fn params(&self) -> HashMap<String, String> {
+        let millis = |ts: &Timestamp| {
+            ts.convert_to(TimeUnit::Millisecond)
+                .map(|ts| ts.value())
+                .unwrap_or_else(|| ts.value())
+                .to_string()
+        };
+        let mut params = HashMap::new();
+        if let RunTrigger::Data { since, until } = self {
+            if let Some(since) = since {
+                let _ = params.insert("since".to_string(), millis(since));
+            }
+            let _ = params.insert("until".to_string(), millis(until));
+        }
+        params
+    }
+}
+
+/// Run state of a scheduled script.
+struct Job {
+    schedule: ScriptSchedule,
+    cron: Option<cron::Schedule>,
+    /// Next time to run by the cron expression.
+    next_run: Option<DateTime<Utc>>,
+    /// Whether the source table has been checked, data before the first check
+    /// doesn't trigger the script.
+    source_checked: bool,
+    /// Max timestamp of the source table covered by successful runs.
+    watermark: Option<Timestamp>,
+    last_source_check: Option<Instant>,
+    /// Number of consecutive failed runs.
+    failures: u32,
+    /// The script is not run until this time after failed runs.
+    retry_at: Option<Instant>,
+}
+
+impl Job {
+    fn try_new(schedule: ScriptSchedule, now: DateTime<Utc>) -> std::result::Result<Self, String> {
+        let cron = schedule.cron.as_deref().map(parse_cron).transpose()?;
+        let next_run = cron.as_ref().and_then(|cron| cron.after(&now).next());
+        Ok(Self {
+            schedule,
+            cron,
+            next_run,
+            source_checked: false,
+            watermark: None,
+            last_source_check: None,
+            failures: 0,
+            retry_at: None,
+        })
+    }
+
+    fn in_backoff(&self, now: Instant) -> bool {
+        self.retry_at.map(|t| now < t).unwrap_or(false)
+    }
+
+    fn cron_due(&self, now: DateTime<Utc>) -> bool {
+        self.next_run.map(|t| t <= now).unwrap_or(false)
+    }
+
+    fn source_check_due(&self, now: Instant, config: &SchedulerConfig) -> bool {
+        self.schedule.source_table.is_some()
+            && self
+                .last_source_check
+                .map(|t| now.duration_since(t) >= config.trigger_interval)
+                .unwrap_or(true)
+    }
+
+    fn on_success(&mut self, trigger: &RunTrigger, now: DateTime<Utc>) {
+        self.failures = 0;
+        self.retry_at = None;
+        match trigger {
+            RunTrigger::Schedule => {
+                self.next_run = self.cron.as_ref().and_then(|cron| cron.after(&now).next());
+            }
+            RunTrigger::Data { until, .. } => self.watermark = Some(*until),
+        }
+    }
+
+    /// The trigger is kept so the run is retried after the backoff.
+    fn on_failure(&mut self, now: Instant, config: &SchedulerConfig) {
+        self.failures += 1;
+        self.retry_at = Some(now + backoff(self.failures, config));
+    }
+}
+
+/// Runs scheduled scripts in the background.
+pub struct ScriptScheduler {
+    task: RepeatedTask<Error>,
+}
+
+impl ScriptScheduler {
+    pub fn new(
+        manager: Arc<ScriptManager>,
+        catalog_manager: CatalogManagerRef,
+        query_engine: QueryEngineRef,
+        recorder: ScriptRunRecorderRef,
+        election: Option<RunnerElectionRef>,
+        config: SchedulerConfig,
+    ) -> Self {
+        let task = SchedulerTask {
+            manager,
+            catalog_manager,
+            query_engine,
+            recorder,
+            election,
+            config: config.clone(),
+            jobs: HashMap::new(),
+            last_reload: None,
+        };
+        Self {
+            task: RepeatedTask::new(config.tick_interval, Box::new(task)),
+        }
+    }
+
+    pub fn start(&self) -> Result<()> {
+        self.task
+            .start(common_runtime::bg_runtime())
+            .context(StartSchedulerSnafu)
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        self.task.stop().await.context(StopSchedulerSnafu)
+    }
+}
+
+struct SchedulerTask {
+    manager: Arc<ScriptManager>,
+    catalog_manager: CatalogManagerRef,
+    query_engine: QueryEngineRef,
+    recorder: ScriptRunRecorderRef,
+    /// Runs scripts only while elected, always runs them if absent.
+    election: Option<RunnerElectionRef>,
+    config: SchedulerConfig,
+    /// Jobs keyed by schema and name of the script.
+    jobs: HashMap<(String, String), Job>,
+    /// When the schedules were reloaded, only tracked if elected.
+    last_reload: Option<Instant>,
+}
+
+#[async_trait]
+impl TaskFunction<Error> for SchedulerTask {
+    async fn call(&mut self) -> Result<()> {
+        if !self.is_runner() {
+            // Another scheduler runs the scripts, jobs are recreated once
+            // elected again so missed cron runs are not caught up.
+            if !self.jobs.is_empty() {
+                logging::info!("Script scheduler is not the runner, stop running scripts");
+                self.jobs.clear();
+            }
+            self.last_reload = None;
+            return Ok(());
+        }
+        self.maybe_reload_schedules();
+
+        let schedules = match self.manager.schedules().await {
+            Ok(schedules) => schedules,
+            // The scripts table is not opened until the catalog manager starts.
+            Err(Error::ScriptsTableNotFound { .. }) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        // Jobs of removed or changed schedules are dropped and recreated.
+        self.jobs
+            .retain(|key, job| schedules.get(key) == Some(&job.schedule));
+
+        let now = Utc::now();
+        for (key, schedule) in schedules {
+            // Stops if the election is lost during previous runs.
+            if !self.is_runner() {
+                break;
+            }
+            let job = match self.jobs.remove(&key) {
+                Some(job) => job,
+                None => match Job::try_new(schedule, now) {
+                    Ok(job) => job,
+                    Err(e) => {
+                        logging::warn!("Invalid schedule of script {}.{}: {}", key.0, key.1, e);
+                        continue;
+                    }
+                },
+            };
+            let job = self.poll(&key.0, &key.1, job).await;
+            let _ = self.jobs.insert(key, job);
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "ScriptScheduler"
+    }
+}
+
+impl SchedulerTask {
+    fn is_runner(&self) -> bool {
+        self.election
+            .as_ref()
+            .map_or(true, |election| election.is_runner())
+    }
+
+    /// Scripts may be inserted through other frontends, so the elected
+    /// scheduler reloads the schedules periodically.
+    fn maybe_reload_schedules(&mut self) {
+        if self.election.is_none() {
+            return;
+        }
+        let now = Instant::now();
+        let due = self.last_reload.map_or(true, |last| {
+            now.duration_since(last) >= self.config.trigger_interval
+        });
+        if due {
+            self.manager.invalidate_schedules();
+            self.last_reload = Some(now);
+        }
+    }
+
+    /// Runs the script of the job if it's due.
+    async fn poll(&self, schema: &str, name: &str, mut job: Job) -> Job {
+        let now = Instant::now();
+        if job.in_backoff(now) {
+            return job;
+        }
+
+        let trigger = if job.cron_due(Utc::now()) {
+            Some(RunTrigger::Schedule)
+        } else if job.source_check_due(now, &self.config) {
+            job.last_source_check = Some(now);
+            self.check_source(schema, &mut job).await
+        } else {
+            None
+        };
+        let Some(trigger) = trigger else { return job };
+
+        let start_time = util::current_time_millis();
+        let result = self
+            .run(schema, name, &job.schedule, trigger.params())
+            .await;
+        let elapsed_ms = (util::current_time_millis() - start_time).max(0);
+        let (rows, error) = match result {
+            Ok(rows) => {
+                job.on_success(&trigger, Utc::now());
+                (Some(rows as i64), None)
+            }
+            Err(e) => {
+                logging::error!(e; "Failed to run scheduled script {}.{}", schema, name);
+                job.on_failure(Instant::now(), &self.config);
+                (None, Some(e.to_string()))
+            }
+        };
+
+        self.recorder
+            .record(ScriptRun {
+                schema: schema.to_string(),
+                name: name.to_string(),
+                trigger: trigger.name().to_string(),
+                start_time,
+                elapsed_ms,
+                rows,
+                error,
+            })
+            .await;
+        job
+    }
+
+    /// Checks the source table for new data, returns the trigger if any.
+    async fn check_source(&self, schema: &str, job: &mut Job) -> Option<RunTrigger> {
+        let source = job.schedule.source_table.as_deref()?;
+        let max_timestamp = match self.max_timestamp(schema, source, job.watermark).await {
+            Ok(max_timestamp) => max_timestamp,
+            Err(e) => {
+                logging::warn!("Failed to check source table {}.{}: {}", schema, source, e);
+                return None;
+            }
+        };
+        if !job.source_checked {
+            job.source_checked = true;
+            job.watermark = max_timestamp;
+            return None;
+        }
+        max_timestamp.map(|until| RunTrigger::Data {
+            since: job.watermark,
+            until,
+        })
+    }
+
+    async fn table(&self, schema: &str, table: &str) -> Result<TableRef> {
+        self.catalog_manager
+            .table(DEFAULT_CATALOG_NAME, schema, table)
+            .await
+            .context(FindTableSnafu { table })?
+            .context(TableNotFoundSnafu { table })
+    }
+
+    /// Returns the max timestamp of the table after `after`.
+    async fn max_timestamp(
+        &self,
+        schema: &str,
+        table_name: &str,
+        after: Option<Timestamp>,
+    ) -> Result<Option<Timestamp>> {
+        let table = self.table(schema, table_name).await?;
+        let Some(ts) = table.schema().timestamp_column().map(|c| c.name.clone()) else {
+            return Ok(None);
+        };
+
+        let mut sql = format!("SELECT max(\"{ts}\") FROM \"{schema}\".\"{table_name}\"");
+        if let Some(after) = after {
+            sql.push_str(&format!(
+                " WHERE \"{ts}\" > '{}'",
+                after.to_iso8601_string()
+            ));
+        }
+        let stmt = QueryLanguageParser::parse_sql(&sql)
+            .context(QuerySourceTableSnafu { table: table_name })?;
+        let plan = self
+            .query_engine
+            .planner()
+            .plan(stmt, QueryContext::arc())
+            .await
+            .context(QuerySourceTableSnafu { table: table_name })?;
+        let output = self
+            .query_engine
+            .execute(plan, QueryContext::arc())
+            .await
+            .context(QuerySourceTableSnafu { table: table_name })?;
+
+        let max_timestamp = collect_batches(output)
+            .await?
+            .iter()
+            .filter(|batch| batch.num_rows() > 0)
+            .find_map(|batch| match batch.column(0).get(0) {
+                Value::Timestamp(ts) => Some(ts),
+                _ => None,
+            });
+        Ok(max_timestamp)
+    }
+
+    /// Runs the script and writes the output into the target table, returns
+    /// the number of output rows.
+    async fn run(
+        &self,
+        schema: &str,
+        name: &str,
+        schedule: &ScriptSchedule,
+        params: HashMap<String, String>,
+    ) -> Result<usize> {
+        let output = self.manager.execute(schema, name, params).await?;
+        let batches = collect_batches(output).await?;
+        let Some(target) = &schedule.target_table else {
+            return Ok(batches.iter().map(|batch| batch.num_rows()).sum());
+        };
+
+        let table = self.table(schema, target).await?;
+        let mut rows = 0;
+        for batch in batches {
+            let columns_values = batch
+                .schema
+                .column_schemas()
+                .iter()
+                .zip(batch.columns())
+                .map(|(column_schema, vector)| (column_schema.name.clone(), vector.clone()))
+                .collect();
+            rows += table
+                .insert(InsertRequest {
+                    catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+                    schema_name: schema.to_string(),
+                    table_name: target.to_string(),
+                    columns_values,
+                    region_number: 0,
+                })
+                .await
+                .context(WriteTargetTableSnafu { table: target })?;
+        }
+        Ok(rows)
+    }
+}
+
+async fn collect_batches(output: Output) -> Result<Vec<RecordBatch>> {
+    match output {
+        Output::Stream(stream) => record_util::collect(stream)
+            .await
+            .context(CollectRecordsSnafu),
+        Output::RecordBatches(batches) => Ok(batches.take()),
+        Output::AffectedRows(_) => Ok(vec![]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_validate_schedule() {
+        let schedule = ScriptSchedule::default();
+        assert!(!schedule.is_scheduled());
+        validate_schedule(&schedule, "test").unwrap();
+
+        let schedule = ScriptSchedule {
+            cron: Some("*/5 * * * *".to_string()),
+            ..Default::default()
+        };
+        assert!(schedule.is_scheduled());
+        validate_schedule(&schedule, "test").unwrap();
+
+        let schedule = ScriptSchedule {
+            cron: Some("0 0 * * * *".to_string()),
+            source_table: Some("source".to_string()),
+            target_table: Some("target".to_string()),
+        };
+        validate_schedule(&schedule, "test").unwrap();
+
+        let schedule = ScriptSchedule {
+            cron: Some("* * *".to_string()),
+            ..Default::default()
+        };
+        assert!(validate_schedule(&schedule, "test").is_err());
+
+        let schedule = ScriptSchedule {
+            target_table: Some("target".to_string()),
+            ..Default::default()
+        };
+        assert!(validate_schedule(&schedule, "test").is_err());
+    }
+
+    #[test]
+    fn test_backoff() {
+        let config = SchedulerConfig::default();
+        assert_eq!(Duration::from_secs(10), backoff(1, &config));
+        assert_eq!(Duration::from_secs(20), backoff(2, &config));
+        assert_eq!(Duration::from_secs(80), backoff(4, &config));
+        assert_eq!(Duration::from_secs(600), backoff(10, &config));
+        assert_eq!(Duration::from_secs(600), backoff(100, &config));
+    }
+
+    #[test]
+    fn test_run_trigger_params() {
+        assert!(RunTrigger::Schedule.params().is_empty());
+
+        let trigger = RunTrigger::Data {
+            since: Some(Timestamp::new(1000, TimeUnit::Second)),
+            until: Timestamp::new(2_000_000_000, TimeUnit::Nanosecond),
+        };
+        assert_eq!("data", trigger.name());
+        assert_eq!(
+            HashMap::from([
+                ("since".to_string(), "1000000".to_string()),
+                ("until".to_string(), "2000".to_string()),
+            ]),
+            trigger.params()
+        );
+    }
+
+    #[test]
+    fn test_cron_job() {
+        let config = SchedulerConfig::default();
+        let now = Utc.with_ymd_and_hms(2023, 6, 1, 0, 1, 0).unwrap();
+        let schedule = ScriptSchedule {
+            cron: Some("*/5 * * * *".to_string()),
+            ..Default::default()
+        };
+        let mut job = Job::try_new(schedule, now).unwrap();
+        let next_run = Utc.with_ymd_and_hms(2023, 6, 1, 0, 5, 0).unwrap();
+        assert_eq!(Some(next_run), job.next_run);
+        assert!(!job.cron_due(now));
+        assert!(job.cron_due(next_run));
+        assert!(!job.source_check_due(Instant::now(), &config));
+
+        // Failed runs are retried after the backoff.
+        let instant = Instant::now();
+        job.on_failure(instant, &config);
+        job.on_failure(instant, &config);
+        assert_eq!(2, job.failures);
+        assert!(job.in_backoff(instant + Duration::from_secs(19)));
+        assert!(!job.in_backoff(instant + Duration::from_secs(20)));
+        assert!(job.cron_due(next_run));
+
+        job.on_success(&RunTrigger::Schedule, next_run);
+        assert_eq!(0, job.failures);
+        assert!(!job.in_backoff(instant));
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2023, 6, 1, 0, 10, 0).unwrap()),
+            job.next_run
+        );
+    }
+
+    #[test]
+    fn test_data_triggered_job() {
+        let config = SchedulerConfig::default();
+        let schedule = ScriptSchedule {
+            source_table: Some("source".to_string()),
+            ..Default::default()
+        };
+        let mut job = Job::try_new(schedule, Utc::now()).unwrap();
+        assert_eq!(None, job.next_run);
+
+        let instant = Instant::now();
+        assert!(job.source_check_due(instant, &config));
+        job.last_source_check = Some(instant);
+        assert!(!job.source_check_due(instant + Duration::from_secs(1), &config));
+        assert!(job.source_check_due(instant + Duration::from_secs(10), &config));
+
+        let until = Timestamp::new_millisecond(1000);
+        job.on_success(&RunTrigger::Data { since: None, until }, Utc::now());
+        assert_eq!(Some(until), job.watermark);
+    }
+}
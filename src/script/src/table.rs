@@ -23,7 +23,7 @@ use common_catalog::consts::{
 };
 use common_catalog::format_full_table_name;
 use common_error::ext::BoxedError;
-use common_query::Output;
+use common_query::{Output, ScriptSchedule};
 use common_recordbatch::{util as record_util, RecordBatch};
 use common_telemetry::logging;
 use common_time::util;
@@ -40,14 +40,21 @@ use table::TableRef;
 
 use crate::error::{
     CastTypeSnafu, CollectRecordsSnafu, FindColumnInScriptsTableSnafu, FindScriptSnafu,
-    FindScriptsTableSnafu, InsertScriptSnafu, RegisterScriptsTableSnafu, Result,
-    ScriptNotFoundSnafu, ScriptsTableNotFoundSnafu,
+    FindScriptsTableSnafu, InsertScriptSnafu, InvalidScheduleSnafu, RegisterScriptsTableSnafu,
+    Result, ScanScriptsTableSnafu, ScriptNotFoundSnafu, ScriptsTableNotFoundSnafu,
 };
 use crate::python::utils::block_on_async;
 use crate::python::PyScript;
 
 pub const SCRIPTS_TABLE_NAME: &str = "scripts";
 
+/// Column of the cron expression to run the script by.
+const SCHEDULE_COLUMN_NAME: &str = "schedule";
+/// Column of the table whose new data triggers the script.
+const SOURCE_TABLE_COLUMN_NAME: &str = "source_table";
+/// Column of the table the output of scheduled runs is written into.
+const TARGET_TABLE_COLUMN_NAME: &str = "target_table";
+
 pub struct ScriptsTable {
     catalog_manager: CatalogManagerRef,
     query_engine: QueryEngineRef,
@@ -177,9 +184,15 @@ impl ScriptsTable {
         })
     }
 
-    pub async fn insert(&self, schema: &str, name: &str, script: &str) -> Result<()> {
+    pub async fn insert(
+        &self,
+        schema: &str,
+        name: &str,
+        script: &str,
+        schedule: &ScriptSchedule,
+    ) -> Result<()> {
         let now = util::current_time_millis();
-        let columns_values: HashMap<String, VectorRef> = HashMap::from([
+        let mut columns_values: HashMap<String, VectorRef> = HashMap::from([
             (
                 "schema".to_string(),
                 Arc::new(StringVector::from(vec![schema])) as VectorRef,
@@ -208,16 +221,36 @@ impl ScriptsTable {
                 Arc::new(TimestampMillisecondVector::from_slice([now])) as VectorRef,
             ),
         ]);
-        let table = self
-            .catalog_manager
-            .table(
-                DEFAULT_CATALOG_NAME,
-                DEFAULT_SCHEMA_NAME,
-                SCRIPTS_TABLE_NAME,
-            )
-            .await
-            .context(FindScriptsTableSnafu)?
-            .context(ScriptsTableNotFoundSnafu)?;
+        let table = self.table().await?;
+
+        let schedule_values = [
+            (SCHEDULE_COLUMN_NAME, &schedule.cron),
+            (SOURCE_TABLE_COLUMN_NAME, &schedule.source_table),
+            (TARGET_TABLE_COLUMN_NAME, &schedule.target_table),
+        ];
+        // Scripts tables created by older versions don't have the schedule columns,
+        // scheduled scripts are rejected instead of losing their schedules.
+        if table
+            .schema()
+            .column_schema_by_name(SCHEDULE_COLUMN_NAME)
+            .is_some()
+        {
+            for (column, value) in schedule_values {
+                let _ = columns_values.insert(
+                    column.to_string(),
+                    Arc::new(StringVector::from(vec![value.clone()])) as VectorRef,
+                );
+            }
+        } else {
+            ensure!(
+                !schedule.is_scheduled(),
+                InvalidScheduleSnafu {
+                    name,
+                    reason:
+                        "the scripts table is created by an older version without schedule columns",
+                }
+            );
+        }
 
         let _ = table
             .insert(InsertRequest {
@@ -287,6 +320,58 @@ impl ScriptsTable {
         Ok(script_column.get_data(0).unwrap().to_string())
     }
 
+    /// Finds scripts to run by the scheduler, returns their schemas, names and
+    /// schedules.
+    pub async fn find_schedules(&self) -> Result<Vec<(String, String, ScriptSchedule)>> {
+        let table = self.table().await?;
+        let stream = table
+            .scan_to_stream(ScanRequest::default())
+            .await
+            .context(ScanScriptsTableSnafu)?;
+        let records = record_util::collect(stream)
+            .await
+            .context(CollectRecordsSnafu)?;
+
+        let mut schedules = Vec::new();
+        for record in records {
+            // Scripts tables created by older versions don't have the schedule columns.
+            if record.column_by_name(SCHEDULE_COLUMN_NAME).is_none() {
+                continue;
+            }
+            let schemas = Self::get_str_col_by_name(&record, "schema")?;
+            let names = Self::get_str_col_by_name(&record, "name")?;
+            let crons = Self::get_str_col_by_name(&record, SCHEDULE_COLUMN_NAME)?;
+            let source_tables = Self::get_str_col_by_name(&record, SOURCE_TABLE_COLUMN_NAME)?;
+            let target_tables = Self::get_str_col_by_name(&record, TARGET_TABLE_COLUMN_NAME)?;
+            for i in 0..record.num_rows() {
+                let (Some(schema), Some(name)) = (schemas.get_data(i), names.get_data(i)) else {
+                    continue;
+                };
+                let schedule = ScriptSchedule {
+                    cron: crons.get_data(i).map(|s| s.to_string()),
+                    source_table: source_tables.get_data(i).map(|s| s.to_string()),
+                    target_table: target_tables.get_data(i).map(|s| s.to_string()),
+                };
+                if schedule.is_scheduled() {
+                    schedules.push((schema.to_string(), name.to_string(), schedule));
+                }
+            }
+        }
+        Ok(schedules)
+    }
+
+    async fn table(&self) -> Result<TableRef> {
+        self.catalog_manager
+            .table(
+                DEFAULT_CATALOG_NAME,
+                DEFAULT_SCHEMA_NAME,
+                SCRIPTS_TABLE_NAME,
+            )
+            .await
+            .context(FindScriptsTableSnafu)?
+            .context(ScriptsTableNotFoundSnafu)
+    }
+
     #[inline]
     pub fn name(&self) -> &str {
         &self.name
@@ -332,6 +417,21 @@ pub fn build_scripts_schema() -> RawSchema {
             ConcreteDataType::timestamp_millisecond_datatype(),
             false,
         ),
+        ColumnSchema::new(
+            SCHEDULE_COLUMN_NAME.to_string(),
+            ConcreteDataType::string_datatype(),
+            true,
+        ),
+        ColumnSchema::new(
+            SOURCE_TABLE_COLUMN_NAME.to_string(),
+            ConcreteDataType::string_datatype(),
+            true,
+        ),
+        ColumnSchema::new(
+            TARGET_TABLE_COLUMN_NAME.to_string(),
+            ConcreteDataType::string_datatype(),
+            true,
+        ),
     ];
 
     RawSchema::new(cols)
@@ -0,0 +1,336 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A minimal Elasticsearch `_bulk` API for shipping logs.
+//!
+//! Only the `index` and `create` actions are supported. Each document is
+//! written to the table named after its index: the `@timestamp` field becomes
+//! the time index in nanoseconds, the `message` field the
+//! [LOG_MESSAGE_COLUMN_NAME] column, other string fields become tags, numeric
+//! and boolean fields become fields.
+//! Nested objects are flattened with `_` joined names, and arrays are stored
+//! as JSON strings.
+
+use std::collections::{BTreeMap, HashMap};
+
+use api::v1::{InsertRequest as GrpcInsertRequest, InsertRequests};
+use common_grpc::writer::LinesWriter;
+use common_time::util::current_time_millis;
+use serde::Serialize;
+use serde_json::{Map, Value as JsonValue};
+use snafu::{ensure, OptionExt, ResultExt};
+
+use crate::error::{self, Result};
+use crate::loki::{LOG_MESSAGE_COLUMN_NAME, LOG_TIMESTAMP_COLUMN_NAME};
+use crate::otlp::normalize_name;
+
+/// The field holding the timestamp of a document.
+const TIMESTAMP_FIELD: &str = "@timestamp";
+/// The field holding the log message of a document.
+const MESSAGE_FIELD: &str = "message";
+
+/// A document to index.
+#[derive(Debug, PartialEq)]
+pub struct BulkDocument {
+    /// The action, either `index` or `create`.
+    pub action: String,
+    pub index: String,
+    pub source: Map<String, JsonValue>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BulkRequest {
+    pub documents: Vec<BulkDocument>,
+}
+
+impl BulkRequest {
+    /// Parses the newline delimited JSON body, documents without `_index` in
+    /// their action go to `default_index`.
+    pub fn parse(default_index: Option<&str>, body: &str) -> Result<Self> {
+        let mut documents = Vec::new();
+        let mut lines = body.lines().filter(|line| !line.trim().is_empty());
+        while let Some(line) = lines.next() {
+            let action: Map<String, JsonValue> =
+                serde_json::from_str(line).map_err(|e| invalid_request(e.to_string()))?;
+            ensure!(
+                action.len() == 1,
+                error::InvalidElasticsearchBulkRequestSnafu {
+                    reason: format!("invalid action: {line}"),
+                }
+            );
+            let (name, meta) = action.into_iter().next().unwrap();
+            ensure!(
+                name == "index" || name == "create",
+                error::InvalidElasticsearchBulkRequestSnafu {
+                    reason: format!("unsupported action: {name}"),
+                }
+            );
+            let index = meta
+                .get("_index")
+                .and_then(JsonValue::as_str)
+                .or(default_index)
+                .with_context(|| error::InvalidElasticsearchBulkRequestSnafu {
+                    reason: format!("index is missing in action: {line}"),
+                })?
+                .to_string();
+
+            let source =
+                lines
+                    .next()
+                    .with_context(|| error::InvalidElasticsearchBulkRequestSnafu {
+                        reason: format!("document is missing for action: {line}"),
+                    })?;
+            let source: Map<String, JsonValue> =
+                serde_json::from_str(source).map_err(|e| invalid_request(e.to_string()))?;
+
+            documents.push(BulkDocument {
+                action: name,
+                index,
+                source,
+            });
+        }
+        Ok(Self { documents })
+    }
+}
+
+fn invalid_request(reason: String) -> error::Error {
+    error::InvalidElasticsearchBulkRequestSnafu { reason }.build()
+}
+
+/// The response of the `_bulk` API.
+#[derive(Debug, Serialize)]
+pub struct BulkResponse {
+    pub took: u64,
+    pub errors: bool,
+    pub items: Vec<BTreeMap<String, BulkItem>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkItem {
+    #[serde(rename = "_index")]
+    pub index: String,
+    pub status: u16,
+    pub result: String,
+}
+
+impl BulkResponse {
+    /// Creates a response reporting all documents of the request as created.
+    pub fn created(request: &BulkRequest, took: u64) -> Self {
+        let items = request
+            .documents
+            .iter()
+            .map(|doc| {
+                BTreeMap::from([(
+                    doc.action.clone(),
+                    BulkItem {
+                        index: doc.index.clone(),
+                        status: 201,
+                        result: "created".to_string(),
+                    },
+                )])
+            })
+            .collect();
+        Self {
+            took,
+            errors: false,
+            items,
+        }
+    }
+}
+
+/// Parses the `@timestamp` field, in RFC3339 or epoch milliseconds, into epoch
+/// nanoseconds.
+fn parse_timestamp(value: &JsonValue) -> Result<i64> {
+    match value {
+        JsonValue::String(s) => chrono::DateTime::parse_from_rfc3339(s)
+            .ok()
+            .map(|t| t.timestamp_nanos())
+            .with_context(|| error::InvalidElasticsearchBulkRequestSnafu {
+                reason: format!("invalid timestamp: {s}"),
+            }),
+        JsonValue::Number(n) => n
+            .as_i64()
+            .and_then(|ms| ms.checked_mul(1_000_000))
+            .with_context(|| error::InvalidElasticsearchBulkRequestSnafu {
+                reason: format!("invalid timestamp: {n}"),
+            }),
+        _ => Err(invalid_request(format!("invalid timestamp: {value}"))),
+    }
+}
+
+fn write_value(writer: &mut LinesWriter, name: &str, value: &JsonValue) -> Result<()> {
+    match value {
+        JsonValue::Null => {}
+        JsonValue::Bool(v) => writer.write_bool(name, *v).context(error::LogsWriteSnafu)?,
+        JsonValue::Number(v) => writer
+            .write_f64(name, v.as_f64().unwrap_or(f64::NAN))
+            .context(error::LogsWriteSnafu)?,
+        JsonValue::String(v) => writer.write_tag(name, v).context(error::LogsWriteSnafu)?,
+        JsonValue::Array(_) => writer
+            .write_string(name, &value.to_string())
+            .context(error::LogsWriteSnafu)?,
+        JsonValue::Object(fields) => {
+            for (key, value) in fields {
+                write_value(writer, &format!("{name}_{}", normalize_name(key)), value)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Converts a bulk request into insert requests, returns the requests and the
+/// number of rows.
+pub fn to_grpc_insert_requests(request: &BulkRequest) -> Result<(InsertRequests, usize)> {
+    let now = current_time_millis() * 1_000_000;
+    let mut writers: HashMap<&str, LinesWriter> = HashMap::new();
+    for doc in &request.documents {
+        let writer = writers
+            .entry(doc.index.as_str())
+            .or_insert_with(|| LinesWriter::with_lines(16));
+        let mut timestamp = now;
+        for (key, value) in &doc.source {
+            match key.as_str() {
+                TIMESTAMP_FIELD => timestamp = parse_timestamp(value)?,
+                MESSAGE_FIELD => {
+                    let message = match value {
+                        JsonValue::String(s) => s.clone(),
+                        value => value.to_string(),
+                    };
+                    writer
+                        .write_string(LOG_MESSAGE_COLUMN_NAME, &message)
+                        .context(error::LogsWriteSnafu)?;
+                }
+                key => write_value(writer, &normalize_name(key), value)?,
+            }
+        }
+        writer
+            .write_ts_nanosecond(LOG_TIMESTAMP_COLUMN_NAME, timestamp)
+            .context(error::LogsWriteSnafu)?;
+        writer.commit();
+    }
+
+    let mut rows = 0;
+    let inserts = writers
+        .into_iter()
+        .map(|(index, writer)| {
+            let (columns, row_count) = writer.finish();
+            rows += row_count as usize;
+            GrpcInsertRequest {
+                table_name: normalize_name(index),
+                region_number: 0,
+                columns,
+                row_count,
+            }
+        })
+        .collect();
+    Ok((InsertRequests { inserts }, rows))
+}
+
+#[cfg(test)]
+mod tests {
+    use api::v1::column::Values;
+    use api::v1::ColumnDataType;
+
+    use super::*;
+
+    const BODY: &str = r#"
+{"index":{"_index":"app-logs"}}
+{"@timestamp":"2023-06-01T00:00:00Z","message":"hello","host":{"name":"h1"},"level":"info","latency":1.5}
+{"create":{}}
+{"@timestamp":1685577600000,"message":"world","tags":["a","b"],"ok":true}
+"#;
+
+    #[test]
+    fn test_parse_bulk_request() {
+        let request = BulkRequest::parse(Some("default"), BODY).unwrap();
+        assert_eq!(2, request.documents.len());
+        assert_eq!("index", request.documents[0].action);
+        assert_eq!("app-logs", request.documents[0].index);
+        assert_eq!("create", request.documents[1].action);
+        assert_eq!("default", request.documents[1].index);
+
+        assert!(BulkRequest::parse(None, BODY).is_err());
+        assert!(BulkRequest::parse(None, r#"{"delete":{"_index":"a","_id":"1"}}"#).is_err());
+        assert!(BulkRequest::parse(None, r#"{"index":{"_index":"a"}}"#).is_err());
+    }
+
+    #[test]
+    fn test_to_grpc_insert_requests() {
+        let request = BulkRequest::parse(Some("default"), BODY).unwrap();
+        let (requests, rows) = to_grpc_insert_requests(&request).unwrap();
+        assert_eq!(2, rows);
+        assert_eq!(2, requests.inserts.len());
+
+        let insert = requests
+            .inserts
+            .iter()
+            .find(|insert| insert.table_name == "app_logs")
+            .unwrap();
+        let mut names = insert
+            .columns
+            .iter()
+            .map(|c| c.column_name.as_str())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(
+            vec![
+                LOG_TIMESTAMP_COLUMN_NAME,
+                "host_name",
+                "latency",
+                "level",
+                LOG_MESSAGE_COLUMN_NAME
+            ],
+            names
+        );
+        let ts = insert
+            .columns
+            .iter()
+            .find(|c| c.column_name == LOG_TIMESTAMP_COLUMN_NAME)
+            .unwrap();
+        assert_eq!(ColumnDataType::TimestampNanosecond as i32, ts.datatype);
+        let Some(Values {
+            ts_nanosecond_values,
+            ..
+        }) = ts.values.as_ref()
+        else {
+            unreachable!()
+        };
+        assert_eq!(&vec![1685577600000000000], ts_nanosecond_values);
+
+        let insert = requests
+            .inserts
+            .iter()
+            .find(|insert| insert.table_name == "default")
+            .unwrap();
+        let tags = insert
+            .columns
+            .iter()
+            .find(|c| c.column_name == "tags")
+            .unwrap();
+        let Some(Values { string_values, .. }) = tags.values.as_ref() else {
+            unreachable!()
+        };
+        assert_eq!(&vec![r#"["a","b"]"#.to_string()], string_values);
+    }
+
+    #[test]
+    fn test_bulk_response() {
+        let request = BulkRequest::parse(Some("default"), BODY).unwrap();
+        let response = BulkResponse::created(&request, 3);
+        assert_eq!(
+            r#"{"took":3,"errors":false,"items":[{"index":{"_index":"app-logs","status":201,"result":"created"}},{"create":{"_index":"default","status":201,"result":"created"}}]}"#,
+            serde_json::to_string(&response).unwrap()
+        );
+    }
+}
@@ -136,6 +136,18 @@ pub enum Error {
         source: common_grpc::error::Error,
     },
 
+    #[snafu(display("Failed to write OTLP metrics, source: {}", source))]
+    OtlpMetricsWrite {
+        location: Location,
+        source: common_grpc::error::Error,
+    },
+
+    #[snafu(display("Failed to write logs, source: {}", source))]
+    LogsWrite {
+        location: Location,
+        source: common_grpc::error::Error,
+    },
+
     #[snafu(display("Failed to convert time precision, name: {}", name))]
     TimePrecision { name: String, location: Location },
 
@@ -157,12 +169,36 @@ pub enum Error {
         location: Location,
     },
 
+    #[snafu(display("Invalid OpenTSDB query: {}", reason))]
+    InvalidOpentsdbQuery { reason: String, location: Location },
+
+    #[snafu(display("Invalid InfluxQL query: {}", reason))]
+    InvalidInfluxql { reason: String, location: Location },
+
     #[snafu(display("Failed to decode prometheus remote request, source: {}", source))]
     DecodePromRemoteRequest {
         location: Location,
         source: prost::DecodeError,
     },
 
+    #[snafu(display("Failed to decode OTLP request, source: {}", source))]
+    DecodeOtlpRequest {
+        location: Location,
+        source: prost::DecodeError,
+    },
+
+    #[snafu(display("Failed to decode Loki push request, source: {}", source))]
+    DecodeLokiRequest {
+        location: Location,
+        source: prost::DecodeError,
+    },
+
+    #[snafu(display("Invalid Loki push request: {}", reason))]
+    InvalidLokiRequest { reason: String, location: Location },
+
+    #[snafu(display("Invalid Elasticsearch bulk request: {}", reason))]
+    InvalidElasticsearchBulkRequest { reason: String, location: Location },
+
     #[snafu(display("Failed to decompress prometheus remote request, source: {}", source))]
     DecompressPromRemoteRequest {
         location: Location,
@@ -181,6 +217,27 @@ pub enum Error {
         location: Location,
     },
 
+    #[snafu(display("Invalid FlightDescriptor: {}", reason))]
+    InvalidFlightDescriptor { reason: String, location: Location },
+
+    #[snafu(display("Failed to decode FlightData, source: {}", source))]
+    DecodeFlightData {
+        location: Location,
+        source: common_grpc::error::Error,
+    },
+
+    #[snafu(display("Invalid Flight SQL command: {}", reason))]
+    InvalidFlightSqlCommand { reason: String, location: Location },
+
+    #[snafu(display("Unsupported Flight SQL command: {}", name))]
+    UnsupportedFlightSqlCommand { name: String, location: Location },
+
+    #[snafu(display("Failed to encode Flight schema, source: {}", source))]
+    EncodeFlightSchema {
+        location: Location,
+        source: datatypes::arrow::error::ArrowError,
+    },
+
     #[snafu(display("Tls is required for {}, plain connection is rejected", server))]
     TlsRequired { server: String },
 
@@ -239,6 +296,17 @@ pub enum Error {
     #[snafu(display("Invalid flush argument: {}", err_msg))]
     InvalidFlushArgument { err_msg: String },
 
+    #[snafu(display("Invalid procedure id: {}", id))]
+    InvalidProcedureId { id: String, location: Location },
+
+    #[snafu(display("Failed to {} procedure {}, source: {}", action, id, source))]
+    ManageProcedure {
+        action: String,
+        id: String,
+        location: Location,
+        source: common_procedure::Error,
+    },
+
     #[snafu(display("Failed to build gRPC reflection service, source: {}", source))]
     GrpcReflectionService {
         source: tonic_reflection::server::Error,
@@ -321,6 +389,19 @@ pub enum Error {
         actual: opensrv_mysql::ColumnType,
         location: Location,
     },
+
+    #[snafu(display(
+        "Failed to parse prepared statement parameter, source: {source}, location: {location}"
+    ))]
+    ParsePreparedStmtParam {
+        source: sql::error::Error,
+        location: Location,
+    },
+
+    #[snafu(display(
+        "Too many prepared statements in the connection, max: {max}, location: {location}"
+    ))]
+    TooManyPreparedStmts { max: usize, location: Location },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -340,6 +421,7 @@ impl ErrorExt for Error {
             | TcpBind { .. }
             | CatalogError { .. }
             | GrpcReflectionService { .. }
+            | EncodeFlightSchema { .. }
             | BuildHttpResponse { .. } => StatusCode::Internal,
 
             InsertScript { source, .. }
@@ -355,18 +437,33 @@ impl ErrorExt for Error {
             | ConnResetByPeer { .. }
             | InvalidOpentsdbLine { .. }
             | InvalidOpentsdbJsonRequest { .. }
+            | InvalidOpentsdbQuery { .. }
+            | InvalidInfluxql { .. }
             | DecodePromRemoteRequest { .. }
+            | DecodeOtlpRequest { .. }
+            | DecodeLokiRequest { .. }
+            | InvalidLokiRequest { .. }
+            | InvalidElasticsearchBulkRequest { .. }
             | DecompressPromRemoteRequest { .. }
             | InvalidPromRemoteRequest { .. }
             | InvalidFlightTicket { .. }
+            | InvalidFlightDescriptor { .. }
+            | InvalidFlightSqlCommand { .. }
             | InvalidPrepareStatement { .. }
             | DataFrame { .. }
             | PreparedStmtTypeMismatch { .. }
+            | TooManyPreparedStmts { .. }
             | TimePrecision { .. } => StatusCode::InvalidArguments,
 
-            InfluxdbLinesWrite { source, .. } | PromSeriesWrite { source, .. } => {
-                source.status_code()
-            }
+            ParsePreparedStmtParam { source, .. } => source.status_code(),
+
+            InfluxdbLinesWrite { source, .. }
+            | PromSeriesWrite { source, .. }
+            | OtlpMetricsWrite { source, .. }
+            | LogsWrite { source, .. }
+            | DecodeFlightData { source, .. } => source.status_code(),
+
+            UnsupportedFlightSqlCommand { .. } => StatusCode::Unsupported,
 
             Hyper { .. } => StatusCode::Unknown,
             TlsRequired { .. } => StatusCode::Unknown,
@@ -383,7 +480,8 @@ impl ErrorExt for Error {
             DatabaseNotFound { .. } => StatusCode::DatabaseNotFound,
             #[cfg(feature = "mem-prof")]
             DumpProfileData { source, .. } => source.status_code(),
-            InvalidFlushArgument { .. } => StatusCode::InvalidArguments,
+            InvalidFlushArgument { .. } | InvalidProcedureId { .. } => StatusCode::InvalidArguments,
+            ManageProcedure { source, .. } => source.status_code(),
 
             ReplacePreparedStmtParams { source, .. }
             | GetPreparedStmtParams { source, .. }
@@ -434,7 +532,9 @@ fn status_to_tonic_code(status_code: StatusCode) -> Code {
         | StatusCode::DatabaseNotFound
         | StatusCode::UserNotFound => Code::NotFound,
         StatusCode::StorageUnavailable => Code::Unavailable,
-        StatusCode::RuntimeResourcesExhausted | StatusCode::RateLimited => Code::ResourceExhausted,
+        StatusCode::RuntimeResourcesExhausted
+        | StatusCode::RateLimited
+        | StatusCode::ResourcesExhausted => Code::ResourceExhausted,
         StatusCode::UnsupportedPasswordType
         | StatusCode::UserPasswordMismatch
         | StatusCode::AuthHeaderNotFound
@@ -477,10 +577,18 @@ impl IntoResponse for Error {
             | Error::PromSeriesWrite { .. }
             | Error::InvalidOpentsdbLine { .. }
             | Error::InvalidOpentsdbJsonRequest { .. }
+            | Error::InvalidOpentsdbQuery { .. }
+            | Error::InvalidInfluxql { .. }
             | Error::DecodePromRemoteRequest { .. }
+            | Error::DecodeOtlpRequest { .. }
+            | Error::DecodeLokiRequest { .. }
+            | Error::InvalidLokiRequest { .. }
+            | Error::InvalidElasticsearchBulkRequest { .. }
+            | Error::LogsWrite { .. }
             | Error::DecompressPromRemoteRequest { .. }
             | Error::InvalidPromRemoteRequest { .. }
             | Error::InvalidQuery { .. }
+            | Error::InvalidProcedureId { .. }
             | Error::TimePrecision { .. } => (HttpStatusCode::BAD_REQUEST, self.to_string()),
             _ => {
                 logging::error!(self; "Failed to handle HTTP request");
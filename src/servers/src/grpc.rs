@@ -15,6 +15,7 @@
 mod database;
 pub mod flight;
 pub mod handler;
+pub mod otlp;
 pub mod prom_query_gateway;
 
 use std::net::SocketAddr;
@@ -30,6 +31,9 @@ use common_runtime::Runtime;
 use common_telemetry::logging::info;
 use common_telemetry::{error, warn};
 use futures::FutureExt;
+use opentelemetry_proto::tonic::collector::metrics::v1::metrics_service_server::{
+    MetricsService, MetricsServiceServer,
+};
 use snafu::{ensure, OptionExt, ResultExt};
 use tokio::net::TcpListener;
 use tokio::sync::oneshot::{self, Receiver, Sender};
@@ -46,8 +50,10 @@ use crate::error::{
 use crate::grpc::database::DatabaseService;
 use crate::grpc::flight::FlightHandler;
 use crate::grpc::handler::GreptimeRequestHandler;
+use crate::grpc::otlp::OtlpService;
 use crate::prometheus::PrometheusHandlerRef;
 use crate::query_handler::grpc::ServerGrpcQueryHandlerRef;
+use crate::query_handler::OpenTelemetryProtocolHandlerRef;
 use crate::server::Server;
 
 type TonicResult<T> = std::result::Result<T, Status>;
@@ -57,6 +63,9 @@ pub struct GrpcServer {
     request_handler: Arc<GreptimeRequestHandler>,
     /// Handler for Prometheus-compatible PromQL queries. Only present for frontend server.
     prometheus_handler: Option<PrometheusHandlerRef>,
+    /// Handler for OTLP metrics. Only present for frontend server.
+    otlp_handler: Option<OpenTelemetryProtocolHandlerRef>,
+    user_provider: Option<UserProviderRef>,
 
     /// gRPC serving state receiver. Only present if the gRPC server is started.
     /// Used to wait for the server to stop, performing the old blocking fashion.
@@ -67,18 +76,21 @@ impl GrpcServer {
     pub fn new(
         query_handler: ServerGrpcQueryHandlerRef,
         prometheus_handler: Option<PrometheusHandlerRef>,
+        otlp_handler: Option<OpenTelemetryProtocolHandlerRef>,
         user_provider: Option<UserProviderRef>,
         runtime: Arc<Runtime>,
     ) -> Self {
         let request_handler = Arc::new(GreptimeRequestHandler::new(
             query_handler,
-            user_provider,
+            user_provider.clone(),
             runtime,
         ));
         Self {
             shutdown_tx: Mutex::new(None),
             request_handler,
             prometheus_handler,
+            otlp_handler,
+            user_provider,
             serve_state: Mutex::new(None),
         }
     }
@@ -102,6 +114,13 @@ impl GrpcServer {
         PrometheusGatewayServer::new(PrometheusGatewayService::new(handler))
     }
 
+    pub fn create_otlp_metrics_service(
+        &self,
+        handler: OpenTelemetryProtocolHandlerRef,
+    ) -> MetricsServiceServer<impl MetricsService> {
+        MetricsServiceServer::new(OtlpService::new(handler, self.user_provider.clone()))
+    }
+
     pub async fn wait_for_serve(&self) -> Result<()> {
         let mut serve_state = self.serve_state.lock().await;
         let rx = serve_state.take().context(InternalSnafu {
@@ -182,6 +201,9 @@ impl Server for GrpcServer {
             builder = builder
                 .add_service(self.create_prom_query_gateway_service(prometheus_handler.clone()))
         }
+        if let Some(otlp_handler) = &self.otlp_handler {
+            builder = builder.add_service(self.create_otlp_metrics_service(otlp_handler.clone()))
+        }
         let builder = builder.add_service(reflection_service);
 
         let (serve_state_tx, serve_state_rx) = oneshot::channel();
@@ -12,32 +12,50 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod sql;
 mod stream;
 
 use std::pin::Pin;
 use std::sync::Arc;
 
-use api::v1::GreptimeRequest;
+use api::v1::auth_header::AuthScheme as GrpcAuthScheme;
+use api::v1::greptime_request::Request as GreptimeRequestKind;
+use api::v1::query_request::Query;
+use api::v1::{
+    AffectedRows, AuthHeader, Basic, FlightMetadata, GreptimeRequest, QueryRequest, RequestHeader,
+};
+use arrow_flight::flight_descriptor::DescriptorType;
 use arrow_flight::flight_service_server::FlightService;
+use arrow_flight::sql::{ActionCreatePreparedStatementResult, DoPutUpdateResult, ProstMessageExt};
 use arrow_flight::{
-    Action, ActionType, Criteria, Empty, FlightData, FlightDescriptor, FlightInfo,
+    Action, ActionType, Criteria, Empty, FlightData, FlightDescriptor, FlightEndpoint, FlightInfo,
     HandshakeRequest, HandshakeResponse, PutResult, SchemaResult, Ticket,
 };
 use async_trait::async_trait;
-use common_grpc::flight::{FlightEncoder, FlightMessage};
+use common_grpc::flight::{FlightDecoder, FlightEncoder, FlightMessage};
 use common_query::Output;
+use datatypes::schema::{Schema, SchemaRef};
 use futures::Stream;
 use prost::Message;
-use snafu::ResultExt;
+use secrecy::ExposeSecret;
+use session::context::QueryContextRef;
+use snafu::{OptionExt, ResultExt};
+use tonic::metadata::MetadataMap;
 use tonic::{Request, Response, Status, Streaming};
 
 use crate::error;
+use crate::grpc::flight::sql::{FlightSqlCommand, FlightSqlPlan};
 use crate::grpc::flight::stream::FlightRecordBatchStream;
-use crate::grpc::handler::GreptimeRequestHandler;
+use crate::grpc::handler::{create_query_context, GreptimeRequestHandler};
 use crate::grpc::TonicResult;
+use crate::http::authorize::AuthScheme;
 
 type TonicStream<T> = Pin<Box<dyn Stream<Item = TonicResult<T>> + Send + Sync + 'static>>;
 
+/// gRPC metadata key of the database name, for Flight clients that can't
+/// put a [RequestHeader] into their requests.
+pub const DBNAME_METADATA_KEY: &str = "x-greptime-db-name";
+
 pub struct FlightHandler {
     handler: Arc<GreptimeRequestHandler>,
 }
@@ -46,6 +64,124 @@ impl FlightHandler {
     pub fn new(handler: Arc<GreptimeRequestHandler>) -> Self {
         Self { handler }
     }
+
+    async fn execute_sql(&self, sql: String, header: RequestHeader) -> TonicResult<Output> {
+        let request = GreptimeRequest {
+            header: Some(header),
+            request: Some(GreptimeRequestKind::Query(QueryRequest {
+                query: Some(Query::Sql(sql)),
+            })),
+        };
+        self.handler.handle_request(request).await
+    }
+
+    async fn execute_flight_sql(
+        &self,
+        command: &FlightSqlCommand,
+        header: RequestHeader,
+    ) -> TonicResult<Output> {
+        match command.plan()? {
+            FlightSqlPlan::Sql(sql) => self.execute_sql(sql, header).await,
+            FlightSqlPlan::RecordBatches(batches) => {
+                let query_ctx = create_query_context(Some(&header));
+                self.handler.auth(Some(&header), &query_ctx).await?;
+                Ok(Output::RecordBatches(batches))
+            }
+        }
+    }
+
+    /// Returns the schema of the results of the `command`. Commands with side effects
+    /// are not executed, their schemas are empty.
+    async fn flight_sql_schema(
+        &self,
+        command: &FlightSqlCommand,
+        header: RequestHeader,
+    ) -> TonicResult<SchemaRef> {
+        if !command.is_query() {
+            let query_ctx = create_query_context(Some(&header));
+            self.handler.auth(Some(&header), &query_ctx).await?;
+            return Ok(Arc::new(Schema::new(vec![])));
+        }
+
+        // Queries are executed lazily, so we only plan the query here.
+        let output = self.execute_flight_sql(command, header).await?;
+        let schema = match output {
+            Output::Stream(stream) => stream.schema(),
+            Output::RecordBatches(batches) => batches.schema(),
+            Output::AffectedRows(_) => Arc::new(Schema::new(vec![])),
+        };
+        Ok(schema)
+    }
+
+    /// Executes the Flight SQL update command in `descriptor`.
+    async fn put_flight_sql_update(
+        &self,
+        descriptor: FlightDescriptor,
+        header: RequestHeader,
+    ) -> TonicResult<Vec<TonicResult<PutResult>>> {
+        let any =
+            sql::decode_any(&descriptor.cmd).context(error::InvalidFlightDescriptorSnafu {
+                reason: "expecting a Flight SQL command",
+            })?;
+        let statement = sql::update_statement(&any)?;
+
+        let record_count = match self.execute_sql(statement, header).await? {
+            Output::AffectedRows(rows) => rows as i64,
+            // The number of updated records is unknown.
+            Output::Stream(_) | Output::RecordBatches(_) => -1,
+        };
+        let result = PutResult {
+            app_metadata: DoPutUpdateResult { record_count }.encode_to_vec().into(),
+        };
+        Ok(vec![Ok(result)])
+    }
+
+    /// Inserts the record batches in the `stream` into the table in `descriptor.path`, which
+    /// is `[table]`, `[schema, table]` or `[catalog, schema, table]`.
+    async fn put_record_batches(
+        &self,
+        descriptor: FlightDescriptor,
+        header: RequestHeader,
+        first: FlightData,
+        mut stream: Streaming<FlightData>,
+    ) -> TonicResult<Vec<TonicResult<PutResult>>> {
+        let query_ctx = create_query_context(Some(&header));
+        let table_name = resolve_table_path(&descriptor.path, &query_ctx)?;
+        self.handler.auth(Some(&header), &query_ctx).await?;
+
+        let mut decoder = FlightDecoder::default();
+        let mut results = Vec::new();
+        let mut next = Some(first);
+        while let Some(flight_data) = next {
+            let message = decoder
+                .try_decode(flight_data)
+                .context(error::DecodeFlightDataSnafu)?;
+            match message {
+                FlightMessage::Schema(_) => {}
+                FlightMessage::Recordbatch(record_batch) => {
+                    let rows = self
+                        .handler
+                        .put_record_batch(table_name.clone(), record_batch, query_ctx.clone())
+                        .await?;
+                    let metadata = FlightMetadata {
+                        affected_rows: Some(AffectedRows { value: rows as _ }),
+                    };
+                    results.push(Ok(PutResult {
+                        app_metadata: metadata.encode_to_vec().into(),
+                    }));
+                }
+                FlightMessage::AffectedRows(_) | FlightMessage::Metrics(_) => {
+                    return Err(error::InvalidFlightDescriptorSnafu {
+                        reason: "expecting schema or record batches in DoPut",
+                    }
+                    .build()
+                    .into())
+                }
+            }
+            next = stream.message().await?;
+        }
+        Ok(results)
+    }
 }
 
 #[async_trait]
@@ -54,9 +190,16 @@ impl FlightService for FlightHandler {
 
     async fn handshake(
         &self,
-        _: Request<Streaming<HandshakeRequest>>,
+        request: Request<Streaming<HandshakeRequest>>,
     ) -> TonicResult<Response<Self::HandshakeStream>> {
-        Err(Status::unimplemented("Not yet implemented"))
+        // Every call is authenticated by its gRPC metadata, so no token is returned here.
+        let header = request_header(request.metadata())?;
+        let query_ctx = create_query_context(Some(&header));
+        self.handler.auth(Some(&header), &query_ctx).await?;
+
+        let stream: Self::HandshakeStream =
+            Box::pin(tokio_stream::once(Ok(HandshakeResponse::default())));
+        Ok(Response::new(stream))
     }
 
     type ListFlightsStream = TonicStream<FlightInfo>;
@@ -70,28 +213,79 @@ impl FlightService for FlightHandler {
 
     async fn get_flight_info(
         &self,
-        _: Request<FlightDescriptor>,
+        request: Request<FlightDescriptor>,
     ) -> TonicResult<Response<FlightInfo>> {
-        Err(Status::unimplemented("Not yet implemented"))
+        let header = request_header(request.metadata())?;
+        let descriptor = request.into_inner();
+        let any =
+            sql::decode_any(&descriptor.cmd).context(error::InvalidFlightDescriptorSnafu {
+                reason: "expecting a Flight SQL command",
+            })?;
+        let command = FlightSqlCommand::try_from_any(&any)?;
+        let schema = self.flight_sql_schema(&command, header).await?;
+
+        let endpoint = FlightEndpoint {
+            ticket: Some(Ticket {
+                ticket: command.ticket(&any),
+            }),
+            location: vec![],
+        };
+        let info = FlightInfo {
+            schema: sql::encode_schema(&schema)?,
+            flight_descriptor: Some(descriptor),
+            endpoint: vec![endpoint],
+            total_records: -1,
+            total_bytes: -1,
+        };
+        Ok(Response::new(info))
     }
 
     async fn get_schema(
         &self,
-        _: Request<FlightDescriptor>,
+        request: Request<FlightDescriptor>,
     ) -> TonicResult<Response<SchemaResult>> {
-        Err(Status::unimplemented("Not yet implemented"))
+        let header = request_header(request.metadata())?;
+        let descriptor = request.into_inner();
+        let any =
+            sql::decode_any(&descriptor.cmd).context(error::InvalidFlightDescriptorSnafu {
+                reason: "expecting a Flight SQL command",
+            })?;
+        let command = FlightSqlCommand::try_from_any(&any)?;
+        let schema = self.flight_sql_schema(&command, header).await?;
+
+        Ok(Response::new(SchemaResult {
+            schema: sql::encode_schema(&schema)?,
+        }))
     }
 
     type DoGetStream = TonicStream<FlightData>;
 
     async fn do_get(&self, request: Request<Ticket>) -> TonicResult<Response<Self::DoGetStream>> {
-        let ticket = request.into_inner().ticket;
+        let (metadata, _, ticket) = request.into_parts();
+        let ticket = ticket.ticket;
+
+        if let Some(any) = sql::decode_any(&ticket) {
+            let command = FlightSqlCommand::try_from_any(&any)?;
+            let output = self
+                .execute_flight_sql(&command, request_header(&metadata)?)
+                .await?;
+            return Ok(Response::new(to_flight_data_stream(output, false)));
+        }
+
         let request =
             GreptimeRequest::decode(ticket.as_ref()).context(error::InvalidFlightTicketSnafu)?;
+        // Logical plans are sent by the frontend, which merges the metrics of the
+        // datanodes' streams for "EXPLAIN ANALYZE".
+        let with_metrics = matches!(
+            &request.request,
+            Some(GreptimeRequestKind::Query(QueryRequest {
+                query: Some(Query::LogicalPlan(_))
+            }))
+        );
 
         let output = self.handler.handle_request(request).await?;
 
-        let stream = to_flight_data_stream(output);
+        let stream = to_flight_data_stream(output, with_metrics);
         Ok(Response::new(stream))
     }
 
@@ -99,9 +293,42 @@ impl FlightService for FlightHandler {
 
     async fn do_put(
         &self,
-        _: Request<Streaming<FlightData>>,
+        request: Request<Streaming<FlightData>>,
     ) -> TonicResult<Response<Self::DoPutStream>> {
-        Err(Status::unimplemented("Not yet implemented"))
+        let (metadata, _, mut stream) = request.into_parts();
+        let header = request_header(&metadata)?;
+
+        let first = stream
+            .message()
+            .await?
+            .context(error::InvalidFlightDescriptorSnafu {
+                reason: "empty DoPut request",
+            })?;
+        let descriptor =
+            first
+                .flight_descriptor
+                .clone()
+                .context(error::InvalidFlightDescriptorSnafu {
+                    reason: "missing FlightDescriptor in the first FlightData",
+                })?;
+
+        let results = match descriptor.r#type() {
+            DescriptorType::Cmd => self.put_flight_sql_update(descriptor, header).await?,
+            DescriptorType::Path => {
+                self.put_record_batches(descriptor, header, first, stream)
+                    .await?
+            }
+            DescriptorType::Unknown => {
+                return Err(error::InvalidFlightDescriptorSnafu {
+                    reason: "unknown descriptor type",
+                }
+                .build()
+                .into())
+            }
+        };
+
+        let stream: Self::DoPutStream = Box::pin(futures::stream::iter(results));
+        Ok(Response::new(stream))
     }
 
     type DoExchangeStream = TonicStream<FlightData>;
@@ -115,8 +342,40 @@ impl FlightService for FlightHandler {
 
     type DoActionStream = TonicStream<arrow_flight::Result>;
 
-    async fn do_action(&self, _: Request<Action>) -> TonicResult<Response<Self::DoActionStream>> {
-        Err(Status::unimplemented("Not yet implemented"))
+    async fn do_action(
+        &self,
+        request: Request<Action>,
+    ) -> TonicResult<Response<Self::DoActionStream>> {
+        let (metadata, _, action) = request.into_parts();
+        let header = request_header(&metadata)?;
+
+        match action.r#type.as_str() {
+            sql::CREATE_PREPARED_STATEMENT => {
+                let (statement, handle) = sql::prepare_statement(&action.body)?;
+                let command = FlightSqlCommand::Statement(statement);
+                let schema = self.flight_sql_schema(&command, header).await?;
+
+                let result = ActionCreatePreparedStatementResult {
+                    prepared_statement_handle: handle,
+                    dataset_schema: sql::encode_schema(&schema)?,
+                    // Statements with parameters are rejected when they are prepared.
+                    parameter_schema: Default::default(),
+                };
+                let result = arrow_flight::Result {
+                    body: result.as_any().encode_to_vec().into(),
+                };
+                let stream: Self::DoActionStream = Box::pin(tokio_stream::once(Ok(result)));
+                Ok(Response::new(stream))
+            }
+            sql::CLOSE_PREPARED_STATEMENT => {
+                // Prepared statements are stateless, nothing to release.
+                let stream: Self::DoActionStream = Box::pin(futures::stream::empty());
+                Ok(Response::new(stream))
+            }
+            other => Err(Status::unimplemented(format!(
+                "Unsupported action: {other}"
+            ))),
+        }
     }
 
     type ListActionsStream = TonicStream<ActionType>;
@@ -125,18 +384,92 @@ impl FlightService for FlightHandler {
         &self,
         _: Request<Empty>,
     ) -> TonicResult<Response<Self::ListActionsStream>> {
-        Err(Status::unimplemented("Not yet implemented"))
+        let actions = [
+            (
+                sql::CREATE_PREPARED_STATEMENT,
+                "Creates a reusable prepared statement resource on the server.",
+            ),
+            (
+                sql::CLOSE_PREPARED_STATEMENT,
+                "Closes a reusable prepared statement resource on the server.",
+            ),
+        ]
+        .into_iter()
+        .map(|(r#type, description)| {
+            Ok(ActionType {
+                r#type: r#type.to_string(),
+                description: description.to_string(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+        let stream: Self::ListActionsStream = Box::pin(futures::stream::iter(actions));
+        Ok(Response::new(stream))
+    }
+}
+
+/// Builds the [RequestHeader] from the gRPC metadata.
+fn request_header(metadata: &MetadataMap) -> TonicResult<RequestHeader> {
+    let mut header = RequestHeader::default();
+
+    if let Some(dbname) = metadata.get(DBNAME_METADATA_KEY) {
+        header.dbname = dbname
+            .to_str()
+            .ok()
+            .context(error::InvalidQuerySnafu {
+                reason: format!("invalid {DBNAME_METADATA_KEY} metadata"),
+            })?
+            .to_string();
+    }
+
+    if let Some(authorization) = metadata.get("authorization") {
+        let authorization = authorization
+            .to_str()
+            .ok()
+            .context(error::InvalidAuthorizationHeaderSnafu)?;
+        let AuthScheme::Basic(username, password) = AuthScheme::try_from(authorization)?;
+        header.authorization = Some(AuthHeader {
+            auth_scheme: Some(GrpcAuthScheme::Basic(Basic {
+                username,
+                password: password.expose_secret().to_string(),
+            })),
+        });
+    }
+
+    Ok(header)
+}
+
+/// Sets the catalog and schema in the `path` to the `query_ctx` and returns the table name.
+fn resolve_table_path(path: &[String], query_ctx: &QueryContextRef) -> TonicResult<String> {
+    match path {
+        [table] => Ok(table.clone()),
+        [schema, table] => {
+            query_ctx.set_current_schema(schema);
+            Ok(table.clone())
+        }
+        [catalog, schema, table] => {
+            query_ctx.set_current_catalog(catalog);
+            query_ctx.set_current_schema(schema);
+            Ok(table.clone())
+        }
+        _ => Err(error::InvalidFlightDescriptorSnafu {
+            reason: format!("invalid table path: {path:?}"),
+        }
+        .build()
+        .into()),
     }
 }
 
-fn to_flight_data_stream(output: Output) -> TonicStream<FlightData> {
+/// Converts the `output` to a Flight data stream. If `with_metrics` is true, the metrics of
+/// the output stream are sent after its record batches.
+fn to_flight_data_stream(output: Output, with_metrics: bool) -> TonicStream<FlightData> {
     match output {
         Output::Stream(stream) => {
-            let stream = FlightRecordBatchStream::new(stream);
+            let stream = FlightRecordBatchStream::new(stream, with_metrics);
             Box::pin(stream) as _
         }
         Output::RecordBatches(x) => {
-            let stream = FlightRecordBatchStream::new(x.as_stream());
+            let stream = FlightRecordBatchStream::new(x.as_stream(), with_metrics);
             Box::pin(stream) as _
         }
         Output::AffectedRows(rows) => {
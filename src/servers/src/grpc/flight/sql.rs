@@ -0,0 +1,469 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Commands of the [Arrow Flight SQL](https://arrow.apache.org/docs/format/FlightSql.html)
+//! protocol.
+//!
+//! Statements are stateless: the handle of a statement ticket or a prepared statement is
+//! the SQL itself, so any frontend can serve the following requests of a client. Catalog
+//! metadata are queried from `information_schema`.
+
+use std::ops::ControlFlow;
+use std::sync::Arc;
+
+use arrow_flight::sql::{
+    ActionCreatePreparedStatementRequest, Any, CommandGetCatalogs, CommandGetDbSchemas,
+    CommandGetTableTypes, CommandGetTables, CommandPreparedStatementQuery,
+    CommandPreparedStatementUpdate, CommandStatementQuery, CommandStatementUpdate, ProstMessageExt,
+    TicketStatementQuery,
+};
+use arrow_flight::{IpcMessage, SchemaAsIpc};
+use bytes::Bytes;
+use common_recordbatch::RecordBatches;
+use datatypes::arrow::ipc::writer::IpcWriteOptions;
+use datatypes::prelude::ConcreteDataType;
+use datatypes::schema::{ColumnSchema, Schema, SchemaRef};
+use datatypes::vectors::StringVector;
+use prost::Message;
+use snafu::{ensure, OptionExt, ResultExt};
+use sql::ast::{visit_expressions, Expr, Value, Visit};
+use sql::dialect::GreptimeDbDialect;
+use sql::parser::ParserContext;
+use sql::statements::statement::Statement;
+
+use crate::error::{self, Result};
+
+/// Type url prefix of all Flight SQL messages packed in an [Any].
+const FLIGHT_SQL_TYPE_URL_PREFIX: &str = "type.googleapis.com/arrow.flight.protocol.sql.";
+
+pub(crate) const CREATE_PREPARED_STATEMENT: &str = "CreatePreparedStatement";
+pub(crate) const CLOSE_PREPARED_STATEMENT: &str = "ClosePreparedStatement";
+
+/// Table types listed by `information_schema.tables`.
+const TABLE_TYPES: [&str; 3] = ["BASE TABLE", "LOCAL TEMPORARY", "VIEW"];
+
+/// A Flight SQL command to fetch results.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum FlightSqlCommand {
+    /// Executes a SQL statement.
+    Statement(String),
+    GetCatalogs,
+    GetDbSchemas {
+        catalog: Option<String>,
+        db_schema_filter_pattern: Option<String>,
+    },
+    GetTables {
+        catalog: Option<String>,
+        db_schema_filter_pattern: Option<String>,
+        table_name_filter_pattern: Option<String>,
+        table_types: Vec<String>,
+    },
+    GetTableTypes,
+}
+
+/// How to get the results of a [FlightSqlCommand].
+pub(crate) enum FlightSqlPlan {
+    /// Results of the SQL.
+    Sql(String),
+    /// Results known without querying the database.
+    RecordBatches(RecordBatches),
+}
+
+impl FlightSqlCommand {
+    /// Decodes the command from a [Any] message.
+    pub(crate) fn try_from_any(any: &Any) -> Result<Self> {
+        if any.is::<CommandStatementQuery>() {
+            let command: CommandStatementQuery = unpack(any)?;
+            Ok(FlightSqlCommand::Statement(command.query))
+        } else if any.is::<TicketStatementQuery>() {
+            let ticket: TicketStatementQuery = unpack(any)?;
+            Ok(FlightSqlCommand::Statement(handle_to_sql(
+                &ticket.statement_handle,
+            )?))
+        } else if any.is::<CommandPreparedStatementQuery>() {
+            let command: CommandPreparedStatementQuery = unpack(any)?;
+            Ok(FlightSqlCommand::Statement(handle_to_sql(
+                &command.prepared_statement_handle,
+            )?))
+        } else if any.is::<CommandGetCatalogs>() {
+            Ok(FlightSqlCommand::GetCatalogs)
+        } else if any.is::<CommandGetDbSchemas>() {
+            let command: CommandGetDbSchemas = unpack(any)?;
+            Ok(FlightSqlCommand::GetDbSchemas {
+                catalog: command.catalog,
+                db_schema_filter_pattern: command.db_schema_filter_pattern,
+            })
+        } else if any.is::<CommandGetTables>() {
+            let command: CommandGetTables = unpack(any)?;
+            if command.include_schema {
+                return error::UnsupportedFlightSqlCommandSnafu {
+                    name: "CommandGetTables with include_schema",
+                }
+                .fail();
+            }
+            Ok(FlightSqlCommand::GetTables {
+                catalog: command.catalog,
+                db_schema_filter_pattern: command.db_schema_filter_pattern,
+                table_name_filter_pattern: command.table_name_filter_pattern,
+                table_types: command.table_types,
+            })
+        } else if any.is::<CommandGetTableTypes>() {
+            Ok(FlightSqlCommand::GetTableTypes)
+        } else {
+            error::UnsupportedFlightSqlCommandSnafu {
+                name: &any.type_url,
+            }
+            .fail()
+        }
+    }
+
+    /// Returns the ticket to fetch results of the command decoded from `any`.
+    pub(crate) fn ticket(&self, any: &Any) -> Bytes {
+        match self {
+            // Returns a statement ticket instead of the command, as suggested by the protocol.
+            FlightSqlCommand::Statement(sql) if any.is::<CommandStatementQuery>() => {
+                TicketStatementQuery {
+                    statement_handle: sql.clone().into_bytes().into(),
+                }
+                .as_any()
+                .encode_to_vec()
+                .into()
+            }
+            _ => any.encode_to_vec().into(),
+        }
+    }
+
+    /// Returns true if the results of the command can be computed without side effects,
+    /// so it's fine to run it just to get the schema of the results.
+    pub(crate) fn is_query(&self) -> bool {
+        match self {
+            FlightSqlCommand::Statement(sql) => is_query_sql(sql),
+            _ => true,
+        }
+    }
+
+    pub(crate) fn plan(&self) -> Result<FlightSqlPlan> {
+        let plan = match self {
+            FlightSqlCommand::Statement(sql) => FlightSqlPlan::Sql(sql.clone()),
+            FlightSqlCommand::GetCatalogs => FlightSqlPlan::Sql(
+                "SELECT DISTINCT table_catalog AS catalog_name \
+                 FROM information_schema.tables ORDER BY catalog_name"
+                    .to_string(),
+            ),
+            FlightSqlCommand::GetDbSchemas {
+                catalog,
+                db_schema_filter_pattern,
+            } => {
+                let filters = [
+                    catalog.as_deref().map(|c| equal_filter("table_catalog", c)),
+                    db_schema_filter_pattern
+                        .as_deref()
+                        .map(|p| like_filter("table_schema", p)),
+                ];
+                FlightSqlPlan::Sql(format!(
+                    "SELECT DISTINCT table_catalog AS catalog_name, table_schema AS db_schema_name \
+                     FROM information_schema.tables{} ORDER BY catalog_name, db_schema_name",
+                    where_clause(filters)
+                ))
+            }
+            FlightSqlCommand::GetTables {
+                catalog,
+                db_schema_filter_pattern,
+                table_name_filter_pattern,
+                table_types,
+            } => {
+                let filters = [
+                    catalog.as_deref().map(|c| equal_filter("table_catalog", c)),
+                    db_schema_filter_pattern
+                        .as_deref()
+                        .map(|p| like_filter("table_schema", p)),
+                    table_name_filter_pattern
+                        .as_deref()
+                        .map(|p| like_filter("table_name", p)),
+                    (!table_types.is_empty()).then(|| in_filter("table_type", table_types)),
+                ];
+                FlightSqlPlan::Sql(format!(
+                    "SELECT table_catalog AS catalog_name, table_schema AS db_schema_name, \
+                     table_name, table_type FROM information_schema.tables{} \
+                     ORDER BY catalog_name, db_schema_name, table_name",
+                    where_clause(filters)
+                ))
+            }
+            FlightSqlCommand::GetTableTypes => {
+                let schema = Arc::new(Schema::new(vec![ColumnSchema::new(
+                    "table_type",
+                    ConcreteDataType::string_datatype(),
+                    false,
+                )]));
+                let table_types = Arc::new(StringVector::from(TABLE_TYPES.to_vec()));
+                let batches = RecordBatches::try_from_columns(schema, vec![table_types])
+                    .context(error::CollectRecordbatchSnafu)?;
+                FlightSqlPlan::RecordBatches(batches)
+            }
+        };
+        Ok(plan)
+    }
+}
+
+/// Decodes `bytes` into a Flight SQL message, returns `None` if they are not.
+pub(crate) fn decode_any(bytes: &[u8]) -> Option<Any> {
+    Any::decode(bytes)
+        .ok()
+        .filter(|any| any.type_url.starts_with(FLIGHT_SQL_TYPE_URL_PREFIX))
+}
+
+/// Returns the SQL of a [CREATE_PREPARED_STATEMENT] action and the handle of the prepared
+/// statement.
+pub(crate) fn prepare_statement(body: &[u8]) -> Result<(String, Bytes)> {
+    let any = decode_any(body).context(error::InvalidFlightSqlCommandSnafu {
+        reason: "expecting ActionCreatePreparedStatementRequest",
+    })?;
+    let request: ActionCreatePreparedStatementRequest = unpack(&any)?;
+    // Statements are stateless, so there is nowhere to keep the parameters bound by
+    // `DoPut` with `CommandPreparedStatementQuery`.
+    ensure!(
+        !has_placeholders(&request.query),
+        error::UnsupportedFlightSqlCommandSnafu {
+            name: "prepared statement with parameters",
+        }
+    );
+    let handle = request.query.clone().into_bytes().into();
+    Ok((request.query, handle))
+}
+
+/// Returns the SQL of a `CommandStatementUpdate` or `CommandPreparedStatementUpdate`.
+pub(crate) fn update_statement(any: &Any) -> Result<String> {
+    if any.is::<CommandStatementUpdate>() {
+        let command: CommandStatementUpdate = unpack(any)?;
+        Ok(command.query)
+    } else if any.is::<CommandPreparedStatementUpdate>() {
+        let command: CommandPreparedStatementUpdate = unpack(any)?;
+        handle_to_sql(&command.prepared_statement_handle)
+    } else {
+        error::UnsupportedFlightSqlCommandSnafu {
+            name: &any.type_url,
+        }
+        .fail()
+    }
+}
+
+/// Encodes the `schema` in the IPC format used by `FlightInfo` and prepared statements.
+pub(crate) fn encode_schema(schema: &SchemaRef) -> Result<Bytes> {
+    let message = IpcMessage::try_from(SchemaAsIpc::new(
+        schema.arrow_schema(),
+        &IpcWriteOptions::default(),
+    ))
+    .context(error::EncodeFlightSchemaSnafu)?;
+    Ok(message.0)
+}
+
+fn unpack<M: ProstMessageExt>(any: &Any) -> Result<M> {
+    any.unpack::<M>()
+        .map_err(|e| {
+            error::InvalidFlightSqlCommandSnafu {
+                reason: e.to_string(),
+            }
+            .build()
+        })?
+        .with_context(|| error::InvalidFlightSqlCommandSnafu {
+            reason: format!("expecting {}", M::type_url()),
+        })
+}
+
+fn handle_to_sql(handle: &[u8]) -> Result<String> {
+    String::from_utf8(handle.to_vec())
+        .ok()
+        .context(error::InvalidFlightSqlCommandSnafu {
+            reason: "invalid statement handle",
+        })
+}
+
+fn is_query_sql(sql: &str) -> bool {
+    let Ok(statements) = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}) else {
+        return false;
+    };
+    matches!(
+        statements.as_slice(),
+        [Statement::Query(_)
+            | Statement::Tql(_)
+            | Statement::Explain(_)
+            | Statement::DescribeTable(_)
+            | Statement::ShowDatabases(_)
+            | Statement::ShowTables(_)
+            | Statement::ShowCreateTable(_)
+            | Statement::ShowJobs(_)
+            | Statement::ShowProcesslist(_)
+            | Statement::ShowFunctions(_)]
+    )
+}
+
+/// Returns true if the `sql` has parameter placeholders, like `?` or `$1`.
+fn has_placeholders(sql: &str) -> bool {
+    let Ok(statements) = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}) else {
+        return false;
+    };
+    statements.iter().any(|statement| {
+        let visited = match statement {
+            Statement::Query(query) => visit_placeholders(&query.inner),
+            Statement::Insert(insert) => visit_placeholders(&insert.inner),
+            Statement::Delete(delete) => visit_placeholders(&delete.inner),
+            _ => ControlFlow::Continue(()),
+        };
+        visited.is_break()
+    })
+}
+
+fn visit_placeholders<V: Visit>(v: &V) -> ControlFlow<()> {
+    visit_expressions(v, |expr| {
+        if let Expr::Value(Value::Placeholder(_)) = expr {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    })
+}
+
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+fn equal_filter(column: &str, value: &str) -> String {
+    format!("{column} = {}", quote(value))
+}
+
+fn like_filter(column: &str, pattern: &str) -> String {
+    format!("{column} LIKE {}", quote(pattern))
+}
+
+fn in_filter(column: &str, values: &[String]) -> String {
+    let values = values
+        .iter()
+        .map(|v| quote(v))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("{column} IN ({values})")
+}
+
+fn where_clause<const N: usize>(filters: [Option<String>; N]) -> String {
+    let filters = filters.into_iter().flatten().collect::<Vec<_>>();
+    if filters.is_empty() {
+        String::new()
+    } else {
+        format!(" WHERE {}", filters.join(" AND "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_command() {
+        // Encoded `CommandStatementQuery { query: "SELECT 1" }`.
+        let mut value = vec![0x0a, 8];
+        value.extend_from_slice(b"SELECT 1");
+        let any = Any {
+            type_url: CommandStatementQuery::type_url().to_string(),
+            value: value.into(),
+        };
+        let bytes = any.encode_to_vec();
+        let any = decode_any(&bytes).unwrap();
+        let command = FlightSqlCommand::try_from_any(&any).unwrap();
+        assert_eq!(FlightSqlCommand::Statement("SELECT 1".to_string()), command);
+        assert!(command.is_query());
+
+        // The ticket of a statement query is a TicketStatementQuery.
+        let ticket = command.ticket(&any);
+        let any = decode_any(&ticket).unwrap();
+        assert!(any.is::<TicketStatementQuery>());
+        assert_eq!(command, FlightSqlCommand::try_from_any(&any).unwrap());
+
+        let any = CommandGetCatalogs::default().as_any();
+        let command = FlightSqlCommand::try_from_any(&any).unwrap();
+        assert_eq!(FlightSqlCommand::GetCatalogs, command);
+        assert_eq!(any.encode_to_vec(), command.ticket(&any).to_vec());
+
+        // Not a Flight SQL message.
+        assert!(decode_any(b"not a message").is_none());
+    }
+
+    #[test]
+    fn test_is_query_sql() {
+        assert!(is_query_sql("SELECT * FROM t"));
+        assert!(is_query_sql("SHOW TABLES"));
+        assert!(!is_query_sql("INSERT INTO t VALUES (1, 2)"));
+        assert!(!is_query_sql("SELECT 1; SELECT 2"));
+        assert!(!is_query_sql("NOT SQL"));
+    }
+
+    #[test]
+    fn test_prepare_statement() {
+        let request = ActionCreatePreparedStatementRequest {
+            query: "SELECT * FROM t WHERE a = 1".to_string(),
+        };
+        let (sql, handle) = prepare_statement(&request.as_any().encode_to_vec()).unwrap();
+        assert_eq!("SELECT * FROM t WHERE a = 1", sql);
+        assert_eq!(sql.as_bytes(), handle.as_ref());
+
+        for query in [
+            "SELECT * FROM t WHERE a = ?",
+            "SELECT * FROM t WHERE a = $1",
+            "INSERT INTO t VALUES ($1, $2)",
+        ] {
+            let request = ActionCreatePreparedStatementRequest {
+                query: query.to_string(),
+            };
+            let err = prepare_statement(&request.as_any().encode_to_vec()).unwrap_err();
+            assert!(
+                matches!(err, error::Error::UnsupportedFlightSqlCommand { .. }),
+                "{err}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_get_tables_with_schema() {
+        let any = CommandGetTables {
+            include_schema: true,
+            ..Default::default()
+        }
+        .as_any();
+        let err = FlightSqlCommand::try_from_any(&any).unwrap_err();
+        assert!(
+            matches!(err, error::Error::UnsupportedFlightSqlCommand { .. }),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_get_tables_sql() {
+        let command = FlightSqlCommand::GetTables {
+            catalog: Some("greptime".to_string()),
+            db_schema_filter_pattern: Some("pub%".to_string()),
+            table_name_filter_pattern: Some("it's".to_string()),
+            table_types: vec!["BASE TABLE".to_string()],
+        };
+        let FlightSqlPlan::Sql(sql) = command.plan().unwrap() else {
+            unreachable!()
+        };
+        assert_eq!(
+            "SELECT table_catalog AS catalog_name, table_schema AS db_schema_name, \
+             table_name, table_type FROM information_schema.tables \
+             WHERE table_catalog = 'greptime' AND table_schema LIKE 'pub%' \
+             AND table_name LIKE 'it''s' AND table_type IN ('BASE TABLE') \
+             ORDER BY catalog_name, db_schema_name, table_name",
+            sql
+        );
+    }
+}
@@ -39,12 +39,11 @@ pub(super) struct FlightRecordBatchStream {
 }
 
 impl FlightRecordBatchStream {
-    pub(super) fn new(recordbatches: SendableRecordBatchStream) -> Self {
+    pub(super) fn new(recordbatches: SendableRecordBatchStream, with_metrics: bool) -> Self {
         let (tx, rx) = mpsc::channel::<TonicResult<FlightMessage>>(1);
-        let join_handle =
-            common_runtime::spawn_read(
-                async move { Self::flight_data_stream(recordbatches, tx).await },
-            );
+        let join_handle = common_runtime::spawn_read(async move {
+            Self::flight_data_stream(recordbatches, tx, with_metrics).await
+        });
         Self {
             rx,
             join_handle,
@@ -56,6 +55,7 @@ impl FlightRecordBatchStream {
     async fn flight_data_stream(
         mut recordbatches: SendableRecordBatchStream,
         mut tx: Sender<TonicResult<FlightMessage>>,
+        with_metrics: bool,
     ) {
         let schema = recordbatches.schema();
         if let Err(e) = tx.send(Ok(FlightMessage::Schema(schema))).await {
@@ -80,6 +80,20 @@ impl FlightRecordBatchStream {
                 }
             }
         }
+
+        if !with_metrics {
+            return;
+        }
+        if let Some(metrics) = recordbatches.metrics() {
+            match serde_json::to_string(&metrics) {
+                Ok(metrics) => {
+                    if let Err(e) = tx.send(Ok(FlightMessage::Metrics(metrics))).await {
+                        warn!("stop sending Flight data, err: {e}");
+                    }
+                }
+                Err(e) => warn!("failed to serialize metrics of the stream, err: {e}"),
+            }
+        }
     }
 }
 
@@ -124,7 +138,11 @@ mod test {
     use std::sync::Arc;
 
     use common_grpc::flight::{FlightDecoder, FlightMessage};
-    use common_recordbatch::{RecordBatch, RecordBatches};
+    use common_recordbatch::error::Result as RecordBatchResult;
+    use common_recordbatch::{
+        RecordBatch, RecordBatchMetrics, RecordBatchStreamAdaptor, RecordBatches,
+        SharedRecordBatchMetrics,
+    };
     use datatypes::prelude::*;
     use datatypes::schema::{ColumnSchema, Schema};
     use datatypes::vectors::Int32Vector;
@@ -146,7 +164,7 @@ mod test {
         let recordbatches = RecordBatches::try_new(schema.clone(), vec![recordbatch.clone()])
             .unwrap()
             .as_stream();
-        let mut stream = FlightRecordBatchStream::new(recordbatches);
+        let mut stream = FlightRecordBatchStream::new(recordbatches, false);
 
         let mut raw_data = Vec::with_capacity(2);
         raw_data.push(stream.next().await.unwrap().unwrap());
@@ -175,4 +193,38 @@ mod test {
             _ => unreachable!(),
         }
     }
+
+    #[tokio::test]
+    async fn test_flight_record_batch_stream_with_metrics() {
+        let schema = Arc::new(Schema::new(vec![ColumnSchema::new(
+            "a",
+            ConcreteDataType::int32_datatype(),
+            false,
+        )]));
+        let metrics = SharedRecordBatchMetrics::default();
+        metrics
+            .lock()
+            .unwrap()
+            .counters
+            .push(("rows_scanned".to_string(), 0));
+        let recordbatches = Box::pin(RecordBatchStreamAdaptor {
+            schema,
+            stream: Box::pin(futures::stream::empty::<RecordBatchResult<RecordBatch>>()),
+            output_ordering: None,
+            metrics: Some(metrics.clone()),
+        });
+        let stream = FlightRecordBatchStream::new(recordbatches, true);
+
+        let raw_data = stream.map(|x| x.unwrap()).collect::<Vec<_>>().await;
+        assert_eq!(raw_data.len(), 2);
+
+        let decoder = &mut FlightDecoder::default();
+        let mut flight_messages = raw_data
+            .into_iter()
+            .map(|x| decoder.try_decode(x).unwrap())
+            .collect::<Vec<FlightMessage>>();
+        let FlightMessage::Metrics(actual) = flight_messages.remove(1) else { unreachable!() };
+        let actual: RecordBatchMetrics = serde_json::from_str(&actual).unwrap();
+        assert_eq!(actual, *metrics.lock().unwrap());
+    }
 }
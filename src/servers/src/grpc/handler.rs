@@ -21,10 +21,11 @@ use api::v1::{Basic, GreptimeRequest, RequestHeader};
 use common_error::ext::ErrorExt;
 use common_error::status_code::StatusCode;
 use common_query::Output;
+use common_recordbatch::RecordBatch;
 use common_runtime::Runtime;
 use common_telemetry::logging;
 use metrics::{histogram, increment_counter};
-use session::context::{QueryContext, QueryContextRef};
+use session::context::{Channel, QueryContext, QueryContextRef};
 use snafu::{OptionExt, ResultExt};
 use tonic::Status;
 
@@ -98,7 +99,38 @@ impl GreptimeRequestHandler {
         Ok(output)
     }
 
-    async fn auth(
+    /// Inserts the `record_batch` into table `table_name` of the current schema. Callers
+    /// should authenticate the request via [GreptimeRequestHandler::auth] first.
+    pub(crate) async fn put_record_batch(
+        &self,
+        table_name: String,
+        record_batch: RecordBatch,
+        query_ctx: QueryContextRef,
+    ) -> TonicResult<usize> {
+        let handler = self.handler.clone();
+        let timer = RequestTimer::new(query_ctx.get_db_string(), "put_record_batch");
+
+        // Executes in another runtime for the same reasons as `handle_request`.
+        let handle = self.runtime.spawn(async move {
+            handler
+                .put_record_batch(&table_name, record_batch, query_ctx)
+                .await
+                .map_err(|e| {
+                    if e.status_code().should_log_error() {
+                        logging::error!(e; "Failed to put record batch");
+                    }
+                    e
+                })
+        });
+
+        let rows = handle.await.context(JoinTaskSnafu).map_err(|e| {
+            timer.record(e.status_code());
+            e
+        })??;
+        Ok(rows)
+    }
+
+    pub(crate) async fn auth(
         &self,
         header: Option<&RequestHeader>,
         query_ctx: &QueryContextRef,
@@ -140,7 +172,7 @@ impl GreptimeRequestHandler {
 }
 
 pub(crate) fn create_query_context(header: Option<&RequestHeader>) -> QueryContextRef {
-    let ctx = QueryContext::arc();
+    let ctx = Arc::new(QueryContext::new().with_channel(Channel::Grpc));
     if let Some(header) = header {
         // We provide dbname field in newer versions of protos/sdks
         // parse dbname from header in priority
@@ -0,0 +1,118 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use common_error::ext::ErrorExt;
+use common_telemetry::timer;
+use metrics::increment_counter;
+use opentelemetry_proto::tonic::collector::metrics::v1::metrics_service_server::MetricsService;
+use opentelemetry_proto::tonic::collector::metrics::v1::{
+    ExportMetricsServiceRequest, ExportMetricsServiceResponse,
+};
+use session::context::{QueryContext, QueryContextRef};
+use snafu::{OptionExt, ResultExt};
+use tonic::metadata::MetadataMap;
+use tonic::{Request, Response, Status};
+
+use crate::auth::{Identity, Password, UserProviderRef};
+use crate::error::{self, AuthSnafu, Result};
+use crate::grpc::TonicResult;
+use crate::http::authorize::AuthScheme;
+use crate::metrics::{METRIC_AUTH_FAILURE, METRIC_CODE_LABEL};
+use crate::query_handler::OpenTelemetryProtocolHandlerRef;
+
+/// The gRPC metadata of the database to write to.
+pub const GREPTIME_DB_NAME_METADATA: &str = "x-greptime-db-name";
+
+/// The OTLP/gRPC metrics service.
+pub struct OtlpService {
+    handler: OpenTelemetryProtocolHandlerRef,
+    user_provider: Option<UserProviderRef>,
+}
+
+impl OtlpService {
+    pub fn new(
+        handler: OpenTelemetryProtocolHandlerRef,
+        user_provider: Option<UserProviderRef>,
+    ) -> Self {
+        Self {
+            handler,
+            user_provider,
+        }
+    }
+
+    /// Authenticates the request by the `authorization` metadata in the form of
+    /// HTTP basic authentication.
+    async fn auth(&self, metadata: &MetadataMap, ctx: &QueryContextRef) -> Result<()> {
+        let Some(user_provider) = self.user_provider.as_ref() else { return Ok(()) };
+
+        let authorization = metadata
+            .get("authorization")
+            .context(error::NotFoundAuthHeaderSnafu)?
+            .to_str()
+            .ok()
+            .context(error::InvalidAuthorizationHeaderSnafu)?;
+        let AuthScheme::Basic(username, password) = AuthScheme::try_from(authorization)?;
+
+        let _ = user_provider
+            .auth(
+                Identity::UserId(&username, None),
+                Password::PlainText(password),
+                &ctx.current_catalog(),
+                &ctx.current_schema(),
+            )
+            .await
+            .context(AuthSnafu)?;
+        Ok(())
+    }
+}
+
+fn create_query_context(metadata: &MetadataMap) -> QueryContextRef {
+    match metadata
+        .get(GREPTIME_DB_NAME_METADATA)
+        .and_then(|db| db.to_str().ok())
+    {
+        Some(db) => {
+            let (catalog, schema) = crate::parse_catalog_and_schema_from_client_database_name(db);
+            Arc::new(QueryContext::with(catalog, schema))
+        }
+        None => QueryContext::arc(),
+    }
+}
+
+#[async_trait]
+impl MetricsService for OtlpService {
+    async fn export(
+        &self,
+        request: Request<ExportMetricsServiceRequest>,
+    ) -> TonicResult<Response<ExportMetricsServiceResponse>> {
+        let ctx = create_query_context(request.metadata());
+        self.auth(request.metadata(), &ctx).await.map_err(|e| {
+            increment_counter!(
+                METRIC_AUTH_FAILURE,
+                &[(METRIC_CODE_LABEL, format!("{}", e.status_code()))]
+            );
+            Status::unauthenticated(e.to_string())
+        })?;
+
+        let _timer = timer!(
+            crate::metrics::METRIC_GRPC_OTLP_METRICS_ELAPSED,
+            &[(crate::metrics::METRIC_DB_LABEL, ctx.get_db_string())]
+        );
+        let response = self.handler.metrics(request.into_inner(), ctx).await?;
+        Ok(Response::new(response))
+    }
+}
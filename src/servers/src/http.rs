@@ -14,11 +14,15 @@
 
 mod admin;
 pub mod authorize;
+pub mod elasticsearch;
 pub mod handler;
 pub mod influxdb;
+pub mod loki;
 pub mod mem_prof;
 pub mod opentsdb;
+pub mod otlp;
 mod pprof;
+mod procedure;
 pub mod prom_store;
 pub mod script;
 
@@ -42,6 +46,7 @@ use axum::{routing, BoxError, Extension, Router};
 use common_base::readable_size::ReadableSize;
 use common_error::ext::ErrorExt;
 use common_error::status_code::StatusCode;
+use common_procedure::ProcedureManagerRef;
 use common_query::Output;
 use common_recordbatch::{util, RecordBatch};
 use common_telemetry::logging::{self, info};
@@ -50,7 +55,7 @@ use futures::FutureExt;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use session::context::QueryContext;
+use session::context::{Channel, QueryContext};
 use snafu::{ensure, ResultExt};
 use tokio::sync::oneshot::{self, Sender};
 use tokio::sync::Mutex;
@@ -60,7 +65,9 @@ use tower_http::auth::AsyncRequireAuthorizationLayer;
 use tower_http::trace::TraceLayer;
 
 use self::authorize::HttpAuth;
-use self::influxdb::{influxdb_health, influxdb_ping, influxdb_write_v1, influxdb_write_v2};
+use self::influxdb::{
+    influxdb_health, influxdb_ping, influxdb_query, influxdb_write_v1, influxdb_write_v2,
+};
 use crate::auth::UserProviderRef;
 use crate::configurator::ConfiguratorRef;
 use crate::error::{AlreadyStartedSnafu, Result, StartHttpSnafu};
@@ -73,7 +80,8 @@ use crate::metrics_handler::MetricsHandler;
 use crate::query_handler::grpc::ServerGrpcQueryHandlerRef;
 use crate::query_handler::sql::ServerSqlQueryHandlerRef;
 use crate::query_handler::{
-    InfluxdbLineProtocolHandlerRef, OpentsdbProtocolHandlerRef, PromStoreProtocolHandlerRef,
+    ElasticsearchProtocolHandlerRef, InfluxdbLineProtocolHandlerRef, LokiProtocolHandlerRef,
+    OpenTelemetryProtocolHandlerRef, OpentsdbProtocolHandlerRef, PromStoreProtocolHandlerRef,
     ScriptHandlerRef,
 };
 use crate::server::Server;
@@ -88,7 +96,9 @@ pub(crate) async fn query_context_from_db(
         let (catalog, schema) = super::parse_catalog_and_schema_from_client_database_name(db);
 
         match query_handler.is_valid_schema(catalog, schema).await {
-            Ok(true) => Ok(Arc::new(QueryContext::with(catalog, schema))),
+            Ok(true) => Ok(Arc::new(
+                QueryContext::with(catalog, schema).with_channel(Channel::Http),
+            )),
             Ok(false) => Err(JsonResponse::with_error(
                 format!("Database not found: {db}"),
                 StatusCode::DatabaseNotFound,
@@ -99,7 +109,7 @@ pub(crate) async fn query_context_from_db(
             )),
         }
     } else {
-        Ok(QueryContext::arc())
+        Ok(Arc::new(QueryContext::new().with_channel(Channel::Http)))
     }
 }
 
@@ -119,7 +129,11 @@ pub struct HttpServer {
     influxdb_handler: Option<InfluxdbLineProtocolHandlerRef>,
     opentsdb_handler: Option<OpentsdbProtocolHandlerRef>,
     prom_handler: Option<PromStoreProtocolHandlerRef>,
+    otlp_handler: Option<OpenTelemetryProtocolHandlerRef>,
+    loki_handler: Option<LokiProtocolHandlerRef>,
+    elasticsearch_handler: Option<ElasticsearchProtocolHandlerRef>,
     script_handler: Option<ScriptHandlerRef>,
+    procedure_manager: Option<ProcedureManagerRef>,
     shutdown_tx: Mutex<Option<Sender<()>>>,
     user_provider: Option<UserProviderRef>,
     metrics_handler: Option<MetricsHandler>,
@@ -399,8 +413,12 @@ impl HttpServerBuilder {
                 opentsdb_handler: None,
                 influxdb_handler: None,
                 prom_handler: None,
+                otlp_handler: None,
+                loki_handler: None,
+                elasticsearch_handler: None,
                 user_provider: None,
                 script_handler: None,
+                procedure_manager: None,
                 metrics_handler: None,
                 shutdown_tx: Mutex::new(None),
                 configurator: None,
@@ -439,6 +457,32 @@ impl HttpServerBuilder {
         self
     }
 
+    pub fn with_otlp_handler(&mut self, handler: OpenTelemetryProtocolHandlerRef) -> &mut Self {
+        let _ = self.inner.otlp_handler.get_or_insert(handler);
+        self
+    }
+
+    pub fn with_loki_handler(&mut self, handler: LokiProtocolHandlerRef) -> &mut Self {
+        let _ = self.inner.loki_handler.get_or_insert(handler);
+        self
+    }
+
+    pub fn with_elasticsearch_handler(
+        &mut self,
+        handler: ElasticsearchProtocolHandlerRef,
+    ) -> &mut Self {
+        let _ = self.inner.elasticsearch_handler.get_or_insert(handler);
+        self
+    }
+
+    pub fn with_procedure_manager(&mut self, procedure_manager: ProcedureManagerRef) -> &mut Self {
+        let _ = self
+            .inner
+            .procedure_manager
+            .get_or_insert(procedure_manager);
+        self
+    }
+
     pub fn with_user_provider(&mut self, user_provider: UserProviderRef) -> &mut Self {
         let _ = self.inner.user_provider.get_or_insert(user_provider);
         self
@@ -493,11 +537,15 @@ impl HttpServer {
             router = router.nest(&format!("/{HTTP_API_VERSION}"), sql_router);
         }
 
-        if let Some(grpc_handler) = self.grpc_handler.clone() {
-            router = router.nest(
-                &format!("/{HTTP_API_VERSION}/admin"),
-                self.route_admin(grpc_handler.clone()),
-            );
+        if self.grpc_handler.is_some() || self.procedure_manager.is_some() {
+            let mut admin_router = Router::new();
+            if let Some(grpc_handler) = self.grpc_handler.clone() {
+                admin_router = admin_router.merge(self.route_admin(grpc_handler));
+            }
+            if let Some(procedure_manager) = self.procedure_manager.clone() {
+                admin_router = admin_router.merge(self.route_procedure(procedure_manager));
+            }
+            router = router.nest(&format!("/{HTTP_API_VERSION}/admin"), admin_router);
         }
 
         if let Some(opentsdb_handler) = self.opentsdb_handler.clone() {
@@ -521,6 +569,27 @@ impl HttpServer {
             );
         }
 
+        if let Some(otlp_handler) = self.otlp_handler.clone() {
+            router = router.nest(
+                &format!("/{HTTP_API_VERSION}/otlp"),
+                self.route_otlp(otlp_handler),
+            );
+        }
+
+        if let Some(loki_handler) = self.loki_handler.clone() {
+            router = router.nest(
+                &format!("/{HTTP_API_VERSION}/loki"),
+                self.route_loki(loki_handler),
+            );
+        }
+
+        if let Some(elasticsearch_handler) = self.elasticsearch_handler.clone() {
+            router = router.nest(
+                &format!("/{HTTP_API_VERSION}/elasticsearch"),
+                self.route_elasticsearch(elasticsearch_handler),
+            );
+        }
+
         if let Some(metrics_handler) = self.metrics_handler {
             router = router.nest("", self.route_metrics(metrics_handler));
         }
@@ -632,10 +701,36 @@ impl HttpServer {
             .with_state(prom_handler)
     }
 
+    fn route_otlp<S>(&self, otlp_handler: OpenTelemetryProtocolHandlerRef) -> Router<S> {
+        Router::new()
+            .route("/v1/metrics", routing::post(otlp::metrics))
+            .with_state(otlp_handler)
+    }
+
+    fn route_loki<S>(&self, loki_handler: LokiProtocolHandlerRef) -> Router<S> {
+        Router::new()
+            .route("/api/v1/push", routing::post(loki::push))
+            .with_state(loki_handler)
+    }
+
+    fn route_elasticsearch<S>(
+        &self,
+        elasticsearch_handler: ElasticsearchProtocolHandlerRef,
+    ) -> Router<S> {
+        Router::new()
+            .route("/_bulk", routing::post(elasticsearch::bulk))
+            .route(
+                "/:index/_bulk",
+                routing::post(elasticsearch::bulk_with_index),
+            )
+            .with_state(elasticsearch_handler)
+    }
+
     fn route_influxdb<S>(&self, influxdb_handler: InfluxdbLineProtocolHandlerRef) -> Router<S> {
         Router::new()
             .route("/write", routing::post(influxdb_write_v1))
             .route("/api/v2/write", routing::post(influxdb_write_v2))
+            .route("/query", routing::get(influxdb_query).post(influxdb_query))
             .route("/ping", routing::get(influxdb_ping))
             .route("/health", routing::get(influxdb_health))
             .with_state(influxdb_handler)
@@ -644,6 +739,12 @@ impl HttpServer {
     fn route_opentsdb<S>(&self, opentsdb_handler: OpentsdbProtocolHandlerRef) -> Router<S> {
         Router::new()
             .route("/api/put", routing::post(opentsdb::put))
+            .route(
+                "/api/query",
+                routing::get(opentsdb::query_by_params).post(opentsdb::query),
+            )
+            .route("/api/suggest", routing::get(opentsdb::suggest))
+            .route("/api/search/lookup", routing::get(opentsdb::lookup))
             .with_state(opentsdb_handler)
     }
 
@@ -654,6 +755,21 @@ impl HttpServer {
             .with_state(grpc_handler)
     }
 
+    fn route_procedure<S>(&self, procedure_manager: ProcedureManagerRef) -> Router<S> {
+        Router::new()
+            .route("/procedures", routing::get(procedure::list_procedures))
+            .route("/procedures/:id", routing::get(procedure::procedure_info))
+            .route(
+                "/procedures/:id/cancel",
+                routing::post(procedure::cancel_procedure),
+            )
+            .route(
+                "/procedures/:id/retry",
+                routing::post(procedure::retry_procedure),
+            )
+            .with_state(procedure_manager)
+    }
+
     fn route_config<S>(&self, state: GreptimeOptionsConfigState) -> ApiRouter<S> {
         ApiRouter::new()
             .route("/config", apirouting::get(handler::config))
@@ -790,6 +906,15 @@ mod test {
         ) -> std::result::Result<Output, Self::Error> {
             unimplemented!()
         }
+
+        async fn put_record_batch(
+            &self,
+            _table_name: &str,
+            _record_batch: RecordBatch,
+            _ctx: QueryContextRef,
+        ) -> std::result::Result<usize, Self::Error> {
+            unimplemented!()
+        }
     }
 
     #[async_trait]
@@ -0,0 +1,78 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use axum::extract::{Path, Query, State};
+use axum::Json;
+use common_telemetry::timer;
+use session::context::QueryContext;
+
+use crate::elasticsearch::{BulkRequest, BulkResponse};
+use crate::error::Result;
+use crate::http::prom_store::DatabaseQuery;
+use crate::parse_catalog_and_schema_from_client_database_name;
+use crate::query_handler::ElasticsearchProtocolHandlerRef;
+
+/// Handles `POST /_bulk`.
+#[axum_macros::debug_handler]
+pub async fn bulk(
+    State(handler): State<ElasticsearchProtocolHandlerRef>,
+    Query(params): Query<DatabaseQuery>,
+    body: String,
+) -> Result<Json<BulkResponse>> {
+    handle_bulk(handler, params, None, body).await
+}
+
+/// Handles `POST /{index}/_bulk`, documents without an index go to `index`.
+#[axum_macros::debug_handler]
+pub async fn bulk_with_index(
+    State(handler): State<ElasticsearchProtocolHandlerRef>,
+    Query(params): Query<DatabaseQuery>,
+    Path(index): Path<String>,
+    body: String,
+) -> Result<Json<BulkResponse>> {
+    handle_bulk(handler, params, Some(index), body).await
+}
+
+async fn handle_bulk(
+    handler: ElasticsearchProtocolHandlerRef,
+    params: DatabaseQuery,
+    index: Option<String>,
+    body: String,
+) -> Result<Json<BulkResponse>> {
+    let _timer = timer!(
+        crate::metrics::METRIC_HTTP_ELASTICSEARCH_BULK_ELAPSED,
+        &[(
+            crate::metrics::METRIC_DB_LABEL,
+            params.db.clone().unwrap_or_default()
+        )]
+    );
+    let start = Instant::now();
+    let ctx = if let Some(db) = params.db {
+        let (catalog, schema) = parse_catalog_and_schema_from_client_database_name(&db);
+        Arc::new(QueryContext::with(catalog, schema))
+    } else {
+        QueryContext::arc()
+    };
+
+    let request = BulkRequest::parse(index.as_deref(), &body)?;
+    handler.bulk(&request, ctx).await?;
+
+    Ok(Json(BulkResponse::created(
+        &request,
+        start.elapsed().as_millis() as u64,
+    )))
+}
@@ -18,13 +18,17 @@ use std::sync::Arc;
 use axum::extract::{Query, State};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
+use axum::{Form, Json};
 use common_catalog::consts::DEFAULT_SCHEMA_NAME;
 use common_grpc::writer::Precision;
 use common_telemetry::timer;
+use serde::{Deserialize, Serialize};
 use session::context::QueryContext;
+use snafu::OptionExt;
 
-use crate::error::{Result, TimePrecisionSnafu};
-use crate::influxdb::InfluxdbRequest;
+use crate::error::{InvalidInfluxqlSnafu, Result, TimePrecisionSnafu};
+use crate::influxdb::query::{Epoch, Series};
+use crate::influxdb::{influxql, InfluxdbRequest};
 use crate::parse_catalog_and_schema_from_client_database_name;
 use crate::query_handler::InfluxdbLineProtocolHandlerRef;
 
@@ -97,6 +101,75 @@ pub async fn influxdb_write(
     Ok((StatusCode::NO_CONTENT, ()))
 }
 
+/// The response of `/query`, with a result for each statement.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InfluxdbQueryResponse {
+    pub results: Vec<InfluxdbStatementResult>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InfluxdbStatementResult {
+    pub statement_id: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub series: Vec<Series>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// https://docs.influxdata.com/influxdb/v1.8/tools/api/#query-http-endpoint
+// Parameters could also be given in the form body of `POST` requests.
+#[axum_macros::debug_handler]
+pub async fn influxdb_query(
+    State(handler): State<InfluxdbLineProtocolHandlerRef>,
+    Query(mut params): Query<HashMap<String, String>>,
+    form: Option<Form<HashMap<String, String>>>,
+) -> Result<Json<InfluxdbQueryResponse>> {
+    if let Some(Form(form)) = form {
+        params.extend(form);
+    }
+    let query = params.get("q").context(InvalidInfluxqlSnafu {
+        reason: "missing required parameter \"q\"",
+    })?;
+    let epoch = params
+        .get("epoch")
+        .map(|val| Epoch::parse(val))
+        .transpose()?;
+    let db = params
+        .get("db")
+        .map(|db| db.as_str())
+        .unwrap_or(DEFAULT_SCHEMA_NAME);
+
+    let _timer = timer!(
+        crate::metrics::METRIC_HTTP_INFLUXDB_QUERY_ELAPSED,
+        &[(crate::metrics::METRIC_DB_LABEL, db.to_string())]
+    );
+
+    let statements = influxql::parse(query)?;
+    let (catalog, schema) = parse_catalog_and_schema_from_client_database_name(db);
+    let ctx = Arc::new(QueryContext::with(catalog, schema));
+
+    // Like InfluxDB, a failed statement reports its error in the result, and
+    // doesn't fail the other statements.
+    let mut results = Vec::with_capacity(statements.len());
+    for (statement_id, statement) in statements.into_iter().enumerate() {
+        let result = match handler.query(statement, epoch, ctx.clone()).await {
+            Ok(series) => InfluxdbStatementResult {
+                statement_id,
+                series,
+                error: None,
+            },
+            Err(e) => InfluxdbStatementResult {
+                statement_id,
+                series: vec![],
+                error: Some(e.to_string()),
+            },
+        };
+        results.push(result);
+    }
+
+    Ok(Json(InfluxdbQueryResponse { results }))
+}
+
 fn parse_time_precision(value: &str) -> Result<Precision> {
     // Precision conversion needs to be compatible with influxdb v1 v2 api.
     // For details, see the Influxdb documents.
@@ -0,0 +1,78 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use axum::extract::{Query, RawBody, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::IntoResponse;
+use common_telemetry::timer;
+use serde::{Deserialize, Serialize};
+use session::context::QueryContext;
+use snafu::prelude::*;
+
+use crate::error::{self, Result};
+use crate::loki::{LokiRequest, LOKI_TABLE_NAME};
+use crate::parse_catalog_and_schema_from_client_database_name;
+use crate::query_handler::LokiProtocolHandlerRef;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct LokiQuery {
+    pub db: Option<String>,
+    /// The table to write logs to, defaults to [LOKI_TABLE_NAME].
+    pub table: Option<String>,
+}
+
+/// Handles Loki push requests, the payload is either the snappy compressed
+/// protobuf, or JSON if the content type is `application/json`.
+#[axum_macros::debug_handler]
+pub async fn push(
+    State(handler): State<LokiProtocolHandlerRef>,
+    Query(params): Query<LokiQuery>,
+    headers: HeaderMap,
+    RawBody(body): RawBody,
+) -> Result<impl IntoResponse> {
+    let _timer = timer!(
+        crate::metrics::METRIC_HTTP_LOKI_PUSH_ELAPSED,
+        &[(
+            crate::metrics::METRIC_DB_LABEL,
+            params.db.clone().unwrap_or_default()
+        )]
+    );
+    let ctx = if let Some(db) = params.db {
+        let (catalog, schema) = parse_catalog_and_schema_from_client_database_name(&db);
+        Arc::new(QueryContext::with(catalog, schema))
+    } else {
+        QueryContext::arc()
+    };
+
+    let body = hyper::body::to_bytes(body)
+        .await
+        .context(error::HyperSnafu)?;
+    let table_name = params.table.unwrap_or_else(|| LOKI_TABLE_NAME.to_string());
+    let is_json = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.starts_with("application/json"))
+        .unwrap_or(false);
+    let request = if is_json {
+        LokiRequest::decode_json(table_name, &body)?
+    } else {
+        LokiRequest::decode_protobuf(table_name, &body)?
+    };
+
+    handler.push(request, ctx).await?;
+
+    Ok((StatusCode::NO_CONTENT, ()))
+}
@@ -14,6 +14,7 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Instant;
 
 use axum::extract::{Query, RawBody, State};
 use axum::http::StatusCode as HttpStatusCode;
@@ -21,11 +22,14 @@ use axum::Json;
 use common_catalog::consts::DEFAULT_SCHEMA_NAME;
 use hyper::Body;
 use serde::{Deserialize, Serialize};
-use session::context::QueryContext;
-use snafu::ResultExt;
+use session::context::{QueryContext, QueryContextRef};
+use snafu::{OptionExt, ResultExt};
 
 use crate::error::{self, Error, Result};
 use crate::opentsdb::codec::DataPoint;
+use crate::opentsdb::query::{
+    LookupRequest, LookupResult, QueryRequest, QueryResponse, SuggestRequest,
+};
 use crate::parse_catalog_and_schema_from_client_database_name;
 use crate::query_handler::OpentsdbProtocolHandlerRef;
 
@@ -85,13 +89,7 @@ pub async fn put(
     let summary = params.contains_key("summary");
     let details = params.contains_key("details");
 
-    let db = params
-        .get("db")
-        .map(|v| v.as_str())
-        .unwrap_or(DEFAULT_SCHEMA_NAME);
-
-    let (catalog, schema) = parse_catalog_and_schema_from_client_database_name(db);
-    let ctx = Arc::new(QueryContext::with(catalog, schema));
+    let ctx = query_context(params.get("db").map(|v| v.as_str()));
 
     let data_points = parse_data_points(body).await?;
 
@@ -136,6 +134,133 @@ pub async fn put(
     Ok(response)
 }
 
+fn query_context(db: Option<&str>) -> QueryContextRef {
+    let db = db.unwrap_or(DEFAULT_SCHEMA_NAME);
+    let (catalog, schema) = parse_catalog_and_schema_from_client_database_name(db);
+    Arc::new(QueryContext::with(catalog, schema))
+}
+
+// Please refer to the OpenTSDB documents of ["api/query"](http://opentsdb.net/docs/build/html/api_http/query/index.html)
+// for more details.
+#[axum_macros::debug_handler]
+pub async fn query(
+    State(opentsdb_handler): State<OpentsdbProtocolHandlerRef>,
+    Query(params): Query<HashMap<String, String>>,
+    RawBody(body): RawBody,
+) -> Result<Json<Vec<QueryResponse>>> {
+    let ctx = query_context(params.get("db").map(|v| v.as_str()));
+
+    let body = hyper::body::to_bytes(body)
+        .await
+        .context(error::HyperSnafu)?;
+    let request = serde_json::from_slice::<QueryRequest>(&body[..])
+        .context(error::InvalidOpentsdbJsonRequestSnafu)?;
+
+    Ok(Json(opentsdb_handler.query(request, ctx).await?))
+}
+
+/// Handles `/api/query` in the form of `GET`, where sub queries are given by the
+/// `m` parameters.
+#[axum_macros::debug_handler]
+pub async fn query_by_params(
+    State(opentsdb_handler): State<OpentsdbProtocolHandlerRef>,
+    Query(params): Query<Vec<(String, String)>>,
+) -> Result<Json<Vec<QueryResponse>>> {
+    let db = params
+        .iter()
+        .find(|(k, _)| k == "db")
+        .map(|(_, v)| v.as_str());
+    let ctx = query_context(db);
+
+    let request = QueryRequest::try_from_params(&params)?;
+    Ok(Json(opentsdb_handler.query(request, ctx).await?))
+}
+
+// Please refer to the OpenTSDB documents of ["api/suggest"](http://opentsdb.net/docs/build/html/api_http/suggest.html)
+// for more details.
+#[axum_macros::debug_handler]
+pub async fn suggest(
+    State(opentsdb_handler): State<OpentsdbProtocolHandlerRef>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<Vec<String>>> {
+    let ctx = query_context(params.get("db").map(|v| v.as_str()));
+
+    let request = SuggestRequest::try_from_params(&params)?;
+    Ok(Json(opentsdb_handler.suggest(request, ctx).await?))
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LookupResponse {
+    #[serde(rename = "type")]
+    lookup_type: String,
+    metric: String,
+    tags: Vec<LookupTag>,
+    limit: usize,
+    time: i64,
+    results: Vec<LookupResult>,
+    start_index: usize,
+    total_results: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct LookupTag {
+    key: String,
+    value: String,
+}
+
+// Please refer to the OpenTSDB documents of ["api/search/lookup"](http://opentsdb.net/docs/build/html/api_http/search/lookup.html)
+// for more details.
+#[axum_macros::debug_handler]
+pub async fn lookup(
+    State(opentsdb_handler): State<OpentsdbProtocolHandlerRef>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<LookupResponse>> {
+    let start = Instant::now();
+    let ctx = query_context(params.get("db").map(|v| v.as_str()));
+
+    let m = params.get("m").context(error::InvalidOpentsdbQuerySnafu {
+        reason: "missing parameter 'm'",
+    })?;
+    let limit = params
+        .get("limit")
+        .map(|limit| {
+            limit
+                .parse::<usize>()
+                .ok()
+                .context(error::InvalidOpentsdbQuerySnafu {
+                    reason: format!("invalid parameter 'limit': {limit}"),
+                })
+        })
+        .transpose()?;
+    let request = LookupRequest::parse(m, limit)?;
+
+    let metric = request.metric.clone();
+    let tags = request
+        .tags
+        .iter()
+        .map(|(key, value)| LookupTag {
+            key: key.clone(),
+            value: value.clone(),
+        })
+        .collect();
+    let limit = request.limit;
+    let mut results = opentsdb_handler.lookup(request, ctx).await?;
+    let total_results = results.len();
+    results.truncate(limit);
+
+    Ok(Json(LookupResponse {
+        lookup_type: "LOOKUP".to_string(),
+        metric,
+        tags,
+        limit,
+        time: start.elapsed().as_millis() as i64,
+        results,
+        start_index: 0,
+        total_results,
+    }))
+}
+
 async fn parse_data_points(body: Body) -> Result<Vec<DataPointRequest>> {
     let body = hyper::body::to_bytes(body)
         .await
@@ -0,0 +1,81 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use axum::extract::{Query, RawBody, State};
+use axum::http::header;
+use axum::response::IntoResponse;
+use common_telemetry::timer;
+use hyper::Body;
+use opentelemetry_proto::tonic::collector::metrics::v1::{
+    ExportMetricsServiceRequest, ExportMetricsServiceResponse,
+};
+use prost::Message;
+use session::context::QueryContext;
+use snafu::prelude::*;
+
+use crate::error::{self, Result};
+use crate::http::prom_store::DatabaseQuery;
+use crate::parse_catalog_and_schema_from_client_database_name;
+use crate::query_handler::OpenTelemetryProtocolHandlerRef;
+
+/// Handles OTLP/HTTP metrics export requests encoded in protobuf.
+#[axum_macros::debug_handler]
+pub async fn metrics(
+    State(handler): State<OpenTelemetryProtocolHandlerRef>,
+    Query(params): Query<DatabaseQuery>,
+    RawBody(body): RawBody,
+) -> Result<OtlpMetricsResponse> {
+    let _timer = timer!(
+        crate::metrics::METRIC_HTTP_OTLP_METRICS_ELAPSED,
+        &[(
+            crate::metrics::METRIC_DB_LABEL,
+            params.db.clone().unwrap_or_default()
+        )]
+    );
+    let ctx = if let Some(db) = params.db {
+        let (catalog, schema) = parse_catalog_and_schema_from_client_database_name(&db);
+        Arc::new(QueryContext::with(catalog, schema))
+    } else {
+        QueryContext::arc()
+    };
+
+    let request = decode_metrics_request(body).await?;
+    handler
+        .metrics(request, ctx)
+        .await
+        .map(|resp| OtlpMetricsResponse { resp })
+}
+
+async fn decode_metrics_request(body: Body) -> Result<ExportMetricsServiceRequest> {
+    let body = hyper::body::to_bytes(body)
+        .await
+        .context(error::HyperSnafu)?;
+    ExportMetricsServiceRequest::decode(&body[..]).context(error::DecodeOtlpRequestSnafu)
+}
+
+pub struct OtlpMetricsResponse {
+    resp: ExportMetricsServiceResponse,
+}
+
+impl IntoResponse for OtlpMetricsResponse {
+    fn into_response(self) -> axum::response::Response {
+        (
+            [(header::CONTENT_TYPE, "application/x-protobuf")],
+            self.resp.encode_to_vec(),
+        )
+            .into_response()
+    }
+}
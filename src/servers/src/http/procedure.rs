@@ -0,0 +1,101 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use common_procedure::{ProcedureId, ProcedureManagerRef};
+use serde_json::json;
+use snafu::{OptionExt, ResultExt};
+
+use crate::error::{self, Result};
+
+/// Lists all procedures of the node, ordered by their start time.
+#[axum_macros::debug_handler]
+pub async fn list_procedures(
+    State(procedure_manager): State<ProcedureManagerRef>,
+) -> Result<Response> {
+    let mut procedures = procedure_manager.list_procedures();
+    procedures.sort_unstable_by_key(|procedure| procedure.start_time_ms);
+
+    Ok(Json(procedures).into_response())
+}
+
+/// Returns the type, lock keys, state history and error of the procedure.
+#[axum_macros::debug_handler]
+pub async fn procedure_info(
+    State(procedure_manager): State<ProcedureManagerRef>,
+    Path(id): Path<String>,
+) -> Result<Response> {
+    let procedure_id = parse_procedure_id(&id)?;
+
+    match procedure_manager.procedure_info(procedure_id) {
+        Some(info) => Ok(Json(info).into_response()),
+        None => Ok(not_found(&id)),
+    }
+}
+
+/// Requests to cancel a running procedure.
+#[axum_macros::debug_handler]
+pub async fn cancel_procedure(
+    State(procedure_manager): State<ProcedureManagerRef>,
+    Path(id): Path<String>,
+) -> Result<Response> {
+    let procedure_id = parse_procedure_id(&id)?;
+
+    let cancelled =
+        procedure_manager
+            .cancel(procedure_id)
+            .await
+            .context(error::ManageProcedureSnafu {
+                action: "cancel",
+                id: &id,
+            })?;
+
+    Ok(Json(json!({ "cancelled": cancelled })).into_response())
+}
+
+/// Forces a failed procedure to run again from its last persisted step.
+#[axum_macros::debug_handler]
+pub async fn retry_procedure(
+    State(procedure_manager): State<ProcedureManagerRef>,
+    Path(id): Path<String>,
+) -> Result<Response> {
+    let procedure_id = parse_procedure_id(&id)?;
+
+    let retried =
+        procedure_manager
+            .retry(procedure_id)
+            .await
+            .context(error::ManageProcedureSnafu {
+                action: "retry",
+                id: &id,
+            })?;
+
+    Ok(Json(json!({ "retried": retried })).into_response())
+}
+
+fn parse_procedure_id(id: &str) -> Result<ProcedureId> {
+    ProcedureId::parse_str(id)
+        .ok()
+        .context(error::InvalidProcedureIdSnafu { id })
+}
+
+fn not_found(id: &str) -> Response {
+    let body = Json(json!({
+        "error": format!("Procedure {id} not found"),
+    }));
+    (StatusCode::NOT_FOUND, body).into_response()
+}
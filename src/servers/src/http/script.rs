@@ -16,6 +16,7 @@ use std::time::Instant;
 
 use axum::extract::{Json, Query, RawBody, State};
 use common_error::ext::ErrorExt;
+use common_query::ScriptSchedule;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -67,8 +68,14 @@ pub async fn scripts(
 
         let script = unwrap_or_json_err!(String::from_utf8(bytes.to_vec()));
 
+        let schedule = ScriptSchedule {
+            cron: params.schedule.clone(),
+            source_table: params.source_table.clone(),
+            target_table: params.target_table.clone(),
+        };
+
         let body = match script_handler
-            .insert_script(schema.unwrap(), name.unwrap(), &script)
+            .insert_script(schema.unwrap(), name.unwrap(), &script, schedule)
             .await
         {
             Ok(()) => JsonResponse::with_output(None),
@@ -85,6 +92,12 @@ pub async fn scripts(
 pub struct ScriptQuery {
     pub db: Option<String>,
     pub name: Option<String>,
+    /// Cron expression to run the script periodically.
+    pub schedule: Option<String>,
+    /// Table whose new data triggers the script.
+    pub source_table: Option<String>,
+    /// Table the output of scheduled runs is written into.
+    pub target_table: Option<String>,
     #[serde(flatten)]
     pub params: HashMap<String, String>,
 }
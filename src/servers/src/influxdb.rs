@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod influxql;
+pub mod query;
+
 use std::collections::HashMap;
 
 use api::v1::{InsertRequest as GrpcInsertRequest, InsertRequests};
@@ -0,0 +1,843 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A parser of the InfluxQL subset served by the `/query` endpoint.
+//!
+//! Supported statements are:
+//! - `SELECT` with raw fields or the `mean`, `sum`, `count` and `last` functions,
+//!   `WHERE` conditions on time, tags and fields, `GROUP BY time()` and tags,
+//!   `fill()`, `ORDER BY time` and `LIMIT`
+//! - `SHOW MEASUREMENTS`
+//! - `SHOW TAG KEYS` and `SHOW TAG VALUES ... WITH KEY`
+//! - `SHOW FIELD KEYS`
+
+use snafu::{ensure, OptionExt};
+
+use crate::error::{self, Result};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Statement {
+    Select(SelectStatement),
+    ShowMeasurements(ShowStatement),
+    ShowTagKeys(ShowStatement),
+    ShowTagValues {
+        show: ShowStatement,
+        keys: Vec<String>,
+    },
+    ShowFieldKeys(ShowStatement),
+}
+
+/// The common clauses of `SHOW` statements.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ShowStatement {
+    pub from: Option<String>,
+    /// The regex of `WITH MEASUREMENT =~ /regex/`, only for `SHOW MEASUREMENTS`.
+    pub measurement_regex: Option<String>,
+    pub condition: Option<Condition>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectStatement {
+    pub fields: Vec<SelectField>,
+    pub from: String,
+    pub condition: Option<Condition>,
+    pub group_by: GroupBy,
+    pub fill: Fill,
+    pub order_desc: bool,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SelectField {
+    /// `*`, all fields and tags.
+    Wildcard,
+    Column {
+        name: String,
+        alias: Option<String>,
+    },
+    /// A function call on a field, or on all fields if the argument is `None`.
+    Call {
+        function: Function,
+        arg: Option<String>,
+        alias: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Function {
+    Mean,
+    Sum,
+    Count,
+    Last,
+}
+
+impl Function {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Function::Mean => "mean",
+            Function::Sum => "sum",
+            Function::Count => "count",
+            Function::Last => "last",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "mean" => Some(Function::Mean),
+            "sum" => Some(Function::Sum),
+            "count" => Some(Function::Count),
+            "last" => Some(Function::Last),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GroupBy {
+    /// The interval of `time()` in milliseconds.
+    pub interval: Option<i64>,
+    pub tags: Vec<String>,
+    /// `GROUP BY *`, grouping by all tags.
+    pub all_tags: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Fill {
+    #[default]
+    Null,
+    None,
+    Previous,
+    Integer(i64),
+    Float(f64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+    Compare {
+        lhs: Operand,
+        op: CompareOp,
+        rhs: Operand,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+    RegexMatch,
+    RegexNotMatch,
+}
+
+impl CompareOp {
+    /// Returns the operator with operands swapped, e.g. `a < b` to `b > a`.
+    pub fn swap(self) -> Self {
+        match self {
+            CompareOp::Lt => CompareOp::Gt,
+            CompareOp::LtEq => CompareOp::GtEq,
+            CompareOp::Gt => CompareOp::Lt,
+            CompareOp::GtEq => CompareOp::LtEq,
+            op => op,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operand {
+    Identifier(String),
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    /// A duration in milliseconds.
+    Duration(i64),
+    Regex(String),
+    Now,
+    Add(Box<Operand>, Box<Operand>),
+    Sub(Box<Operand>, Box<Operand>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// A bare word, either a keyword or an identifier.
+    Word(String),
+    QuotedIdentifier(String),
+    String(String),
+    Integer(i64),
+    Float(f64),
+    /// A duration in milliseconds.
+    Duration(i64),
+    Regex(String),
+    Symbol(&'static str),
+}
+
+const SYMBOLS: [&str; 17] = [
+    "=~", "!~", "!=", "<>", "<=", ">=", "=", "<", ">", "(", ")", ",", ";", "*", ".", "+", "-",
+];
+
+fn invalid(reason: impl Into<String>) -> error::Error {
+    error::InvalidInfluxqlSnafu {
+        reason: reason.into(),
+    }
+    .build()
+}
+
+fn tokenize(query: &str) -> Result<Vec<Token>> {
+    let chars = query.chars().collect::<Vec<_>>();
+    let mut tokens = vec![];
+    let mut pos = 0;
+
+    // Reads until the unescaped `end`, returns the content and the position after `end`.
+    let read_quoted = |start: usize, end: char| -> Result<(String, usize)> {
+        let mut content = String::new();
+        let mut pos = start;
+        while pos < chars.len() {
+            match chars[pos] {
+                '\\' if pos + 1 < chars.len() => {
+                    // Keeps the escapes in regexes except the delimiter.
+                    if end == '/' && chars[pos + 1] != '/' {
+                        content.push('\\');
+                    }
+                    content.push(chars[pos + 1]);
+                    pos += 2;
+                }
+                c if c == end => return Ok((content, pos + 1)),
+                c => {
+                    content.push(c);
+                    pos += 1;
+                }
+            }
+        }
+        Err(invalid(format!("unterminated {end} in '{query}'")))
+    };
+
+    while pos < chars.len() {
+        let c = chars[pos];
+        if c.is_whitespace() {
+            pos += 1;
+        } else if c == '\'' || c == '"' || c == '/' {
+            let (content, next) = read_quoted(pos + 1, c)?;
+            tokens.push(match c {
+                '\'' => Token::String(content),
+                '"' => Token::QuotedIdentifier(content),
+                _ => Token::Regex(content),
+            });
+            pos = next;
+        } else if c.is_ascii_digit() {
+            let start = pos;
+            while pos < chars.len() && (chars[pos].is_ascii_digit() || chars[pos] == '.') {
+                pos += 1;
+            }
+            let number = chars[start..pos].iter().collect::<String>();
+            let unit_start = pos;
+            while pos < chars.len() && chars[pos].is_alphabetic() {
+                pos += 1;
+            }
+            let unit = chars[unit_start..pos].iter().collect::<String>();
+            tokens.push(number_token(&number, &unit)?);
+        } else if c.is_alphabetic() || c == '_' {
+            let start = pos;
+            while pos < chars.len() && (chars[pos].is_alphanumeric() || chars[pos] == '_') {
+                pos += 1;
+            }
+            tokens.push(Token::Word(chars[start..pos].iter().collect()));
+        } else {
+            let rest = chars[pos..chars.len().min(pos + 2)]
+                .iter()
+                .collect::<String>();
+            let symbol = SYMBOLS
+                .iter()
+                .find(|s| rest.starts_with(**s))
+                .with_context(|| error::InvalidInfluxqlSnafu {
+                    reason: format!("unexpected character '{c}' in '{query}'"),
+                })?;
+            tokens.push(Token::Symbol(symbol));
+            pos += symbol.len();
+        }
+    }
+    Ok(tokens)
+}
+
+fn number_token(number: &str, unit: &str) -> Result<Token> {
+    let invalid_number = || invalid(format!("invalid number '{number}{unit}'"));
+    if unit.is_empty() {
+        return if number.contains('.') {
+            number
+                .parse::<f64>()
+                .map(Token::Float)
+                .map_err(|_| invalid_number())
+        } else {
+            number
+                .parse::<i64>()
+                .map(Token::Integer)
+                .map_err(|_| invalid_number())
+        };
+    }
+
+    let value = number.parse::<i64>().map_err(|_| invalid_number())?;
+    let millis = match unit {
+        "ns" => value / 1_000_000,
+        "u" | "µ" => value / 1_000,
+        "ms" => value,
+        "s" => value.saturating_mul(1_000),
+        "m" => value.saturating_mul(60_000),
+        "h" => value.saturating_mul(3_600_000),
+        "d" => value.saturating_mul(86_400_000),
+        "w" => value.saturating_mul(7 * 86_400_000),
+        _ => return Err(invalid_number()),
+    };
+    Ok(Token::Duration(millis))
+}
+
+/// Parses a query into statements, which are separated by `;`.
+pub fn parse(query: &str) -> Result<Vec<Statement>> {
+    let tokens = tokenize(query)?;
+    let mut parser = Parser { tokens, pos: 0 };
+
+    let mut statements = vec![];
+    loop {
+        while parser.consume_symbol(";") {}
+        if parser.peek().is_none() {
+            break;
+        }
+        statements.push(parser.parse_statement()?);
+        ensure!(
+            parser.peek().is_none() || parser.consume_symbol(";"),
+            error::InvalidInfluxqlSnafu {
+                reason: format!("unexpected {:?}", parser.peek()),
+            }
+        );
+    }
+    Ok(statements)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word(w)) if w.eq_ignore_ascii_case(keyword))
+    }
+
+    fn consume_keyword(&mut self, keyword: &str) -> bool {
+        let matched = self.peek_keyword(keyword);
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<()> {
+        ensure!(
+            self.consume_keyword(keyword),
+            error::InvalidInfluxqlSnafu {
+                reason: format!("expect {keyword}, found {:?}", self.peek()),
+            }
+        );
+        Ok(())
+    }
+
+    fn consume_symbol(&mut self, symbol: &str) -> bool {
+        let matched = matches!(self.peek(), Some(Token::Symbol(s)) if *s == symbol);
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> Result<()> {
+        ensure!(
+            self.consume_symbol(symbol),
+            error::InvalidInfluxqlSnafu {
+                reason: format!("expect '{symbol}', found {:?}", self.peek()),
+            }
+        );
+        Ok(())
+    }
+
+    fn parse_identifier(&mut self) -> Result<String> {
+        match self.next() {
+            Some(Token::Word(w)) | Some(Token::QuotedIdentifier(w)) => Ok(w),
+            other => Err(invalid(format!("expect identifier, found {other:?}"))),
+        }
+    }
+
+    /// Parses a measurement, which could be qualified by the database and the
+    /// retention policy like `"db"."rp"."measurement"` or `db..measurement`.
+    fn parse_measurement(&mut self) -> Result<String> {
+        let mut measurement = self.parse_identifier()?;
+        while self.consume_symbol(".") {
+            // The retention policy is omitted in `db..measurement`.
+            let _ = self.consume_symbol(".");
+            measurement = self.parse_identifier()?;
+        }
+        Ok(measurement)
+    }
+
+    fn parse_usize(&mut self) -> Result<usize> {
+        match self.next() {
+            Some(Token::Integer(n)) if n >= 0 => Ok(n as usize),
+            other => Err(invalid(format!(
+                "expect a positive integer, found {other:?}"
+            ))),
+        }
+    }
+
+    fn parse_statement(&mut self) -> Result<Statement> {
+        if self.consume_keyword("SELECT") {
+            return self.parse_select().map(Statement::Select);
+        }
+        if !self.consume_keyword("SHOW") {
+            return Err(invalid(format!(
+                "expect SELECT or SHOW, found {:?}",
+                self.peek()
+            )));
+        }
+
+        if self.consume_keyword("MEASUREMENTS") {
+            let mut show = ShowStatement::default();
+            if self.consume_keyword("WITH") {
+                self.expect_keyword("MEASUREMENT")?;
+                if self.consume_symbol("=~") {
+                    match self.next() {
+                        Some(Token::Regex(regex)) => show.measurement_regex = Some(regex),
+                        other => return Err(invalid(format!("expect regex, found {other:?}"))),
+                    }
+                } else {
+                    self.expect_symbol("=")?;
+                    let measurement = self.parse_measurement()?;
+                    show.measurement_regex = Some(format!("^{}$", regex::escape(&measurement)));
+                }
+            }
+            self.parse_show_clauses(&mut show)?;
+            Ok(Statement::ShowMeasurements(show))
+        } else if self.consume_keyword("TAG") {
+            if self.consume_keyword("KEYS") {
+                let show = self.parse_show()?;
+                return Ok(Statement::ShowTagKeys(show));
+            }
+            self.expect_keyword("VALUES")?;
+            let mut show = ShowStatement::default();
+            if self.consume_keyword("FROM") {
+                show.from = Some(self.parse_measurement()?);
+            }
+            self.expect_keyword("WITH")?;
+            self.expect_keyword("KEY")?;
+            let keys = if self.consume_keyword("IN") {
+                self.expect_symbol("(")?;
+                let mut keys = vec![self.parse_identifier()?];
+                while self.consume_symbol(",") {
+                    keys.push(self.parse_identifier()?);
+                }
+                self.expect_symbol(")")?;
+                keys
+            } else {
+                self.expect_symbol("=")?;
+                vec![self.parse_identifier()?]
+            };
+            self.parse_show_clauses(&mut show)?;
+            Ok(Statement::ShowTagValues { show, keys })
+        } else if self.consume_keyword("FIELD") {
+            self.expect_keyword("KEYS")?;
+            Ok(Statement::ShowFieldKeys(self.parse_show()?))
+        } else {
+            Err(invalid(format!(
+                "unsupported SHOW statement at {:?}",
+                self.peek()
+            )))
+        }
+    }
+
+    fn parse_show(&mut self) -> Result<ShowStatement> {
+        let mut show = ShowStatement::default();
+        if self.consume_keyword("FROM") {
+            show.from = Some(self.parse_measurement()?);
+        }
+        self.parse_show_clauses(&mut show)?;
+        Ok(show)
+    }
+
+    fn parse_show_clauses(&mut self, show: &mut ShowStatement) -> Result<()> {
+        if self.consume_keyword("WHERE") {
+            show.condition = Some(self.parse_condition()?);
+        }
+        if self.consume_keyword("LIMIT") {
+            show.limit = Some(self.parse_usize()?);
+        }
+        Ok(())
+    }
+
+    fn parse_select(&mut self) -> Result<SelectStatement> {
+        let mut fields = vec![self.parse_select_field()?];
+        while self.consume_symbol(",") {
+            fields.push(self.parse_select_field()?);
+        }
+
+        self.expect_keyword("FROM")?;
+        let from = self.parse_measurement()?;
+
+        let condition = if self.consume_keyword("WHERE") {
+            Some(self.parse_condition()?)
+        } else {
+            None
+        };
+
+        let mut group_by = GroupBy::default();
+        if self.consume_keyword("GROUP") {
+            self.expect_keyword("BY")?;
+            loop {
+                if self.consume_symbol("*") {
+                    group_by.all_tags = true;
+                } else if self.peek_keyword("time")
+                    && matches!(self.tokens.get(self.pos + 1), Some(Token::Symbol("(")))
+                {
+                    self.pos += 2;
+                    match self.next() {
+                        Some(Token::Duration(interval)) if interval > 0 => {
+                            group_by.interval = Some(interval)
+                        }
+                        other => {
+                            return Err(invalid(format!(
+                                "expect a positive duration in time(), found {other:?}"
+                            )))
+                        }
+                    }
+                    self.expect_symbol(")")?;
+                } else {
+                    group_by.tags.push(self.parse_identifier()?);
+                }
+                if !self.consume_symbol(",") {
+                    break;
+                }
+            }
+        }
+
+        let mut fill = Fill::default();
+        if self.consume_keyword("fill") {
+            self.expect_symbol("(")?;
+            let negative = self.consume_symbol("-");
+            fill = match self.next() {
+                Some(Token::Word(w)) if w.eq_ignore_ascii_case("null") => Fill::Null,
+                Some(Token::Word(w)) if w.eq_ignore_ascii_case("none") => Fill::None,
+                Some(Token::Word(w)) if w.eq_ignore_ascii_case("previous") => Fill::Previous,
+                Some(Token::Integer(n)) => Fill::Integer(if negative { -n } else { n }),
+                Some(Token::Float(n)) => Fill::Float(if negative { -n } else { n }),
+                other => return Err(invalid(format!("unsupported fill option {other:?}"))),
+            };
+            self.expect_symbol(")")?;
+        }
+
+        let mut order_desc = false;
+        if self.consume_keyword("ORDER") {
+            self.expect_keyword("BY")?;
+            self.expect_keyword("time")?;
+            if self.consume_keyword("DESC") {
+                order_desc = true;
+            } else {
+                let _ = self.consume_keyword("ASC");
+            }
+        }
+
+        let limit = if self.consume_keyword("LIMIT") {
+            Some(self.parse_usize()?)
+        } else {
+            None
+        };
+
+        Ok(SelectStatement {
+            fields,
+            from,
+            condition,
+            group_by,
+            fill,
+            order_desc,
+            limit,
+        })
+    }
+
+    fn parse_select_field(&mut self) -> Result<SelectField> {
+        if self.consume_symbol("*") {
+            return Ok(SelectField::Wildcard);
+        }
+
+        let name = self.parse_identifier()?;
+        let field = if self.consume_symbol("(") {
+            let function = Function::parse(&name).with_context(|| error::InvalidInfluxqlSnafu {
+                reason: format!("unsupported function {name}"),
+            })?;
+            let arg = if self.consume_symbol("*") {
+                None
+            } else {
+                Some(self.parse_identifier()?)
+            };
+            self.expect_symbol(")")?;
+            SelectField::Call {
+                function,
+                arg,
+                alias: self.parse_alias()?,
+            }
+        } else {
+            SelectField::Column {
+                name,
+                alias: self.parse_alias()?,
+            }
+        };
+        Ok(field)
+    }
+
+    fn parse_alias(&mut self) -> Result<Option<String>> {
+        if self.consume_keyword("AS") {
+            self.parse_identifier().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn parse_condition(&mut self) -> Result<Condition> {
+        let mut condition = self.parse_and()?;
+        while self.consume_keyword("OR") {
+            condition = Condition::Or(Box::new(condition), Box::new(self.parse_and()?));
+        }
+        Ok(condition)
+    }
+
+    fn parse_and(&mut self) -> Result<Condition> {
+        let mut condition = self.parse_primary_condition()?;
+        while self.consume_keyword("AND") {
+            condition = Condition::And(
+                Box::new(condition),
+                Box::new(self.parse_primary_condition()?),
+            );
+        }
+        Ok(condition)
+    }
+
+    fn parse_primary_condition(&mut self) -> Result<Condition> {
+        if self.consume_symbol("(") {
+            let condition = self.parse_condition()?;
+            self.expect_symbol(")")?;
+            return Ok(condition);
+        }
+
+        let lhs = self.parse_operand()?;
+        let op = match self.next() {
+            Some(Token::Symbol("=")) => CompareOp::Eq,
+            Some(Token::Symbol("!=")) | Some(Token::Symbol("<>")) => CompareOp::NotEq,
+            Some(Token::Symbol("<")) => CompareOp::Lt,
+            Some(Token::Symbol("<=")) => CompareOp::LtEq,
+            Some(Token::Symbol(">")) => CompareOp::Gt,
+            Some(Token::Symbol(">=")) => CompareOp::GtEq,
+            Some(Token::Symbol("=~")) => CompareOp::RegexMatch,
+            Some(Token::Symbol("!~")) => CompareOp::RegexNotMatch,
+            other => {
+                return Err(invalid(format!(
+                    "expect comparison operator, found {other:?}"
+                )))
+            }
+        };
+        let rhs = self.parse_operand()?;
+        Ok(Condition::Compare { lhs, op, rhs })
+    }
+
+    fn parse_operand(&mut self) -> Result<Operand> {
+        let mut operand = self.parse_term()?;
+        loop {
+            if self.consume_symbol("+") {
+                operand = Operand::Add(Box::new(operand), Box::new(self.parse_term()?));
+            } else if self.consume_symbol("-") {
+                operand = Operand::Sub(Box::new(operand), Box::new(self.parse_term()?));
+            } else {
+                return Ok(operand);
+            }
+        }
+    }
+
+    fn parse_term(&mut self) -> Result<Operand> {
+        if self.consume_symbol("-") {
+            return match self.next() {
+                Some(Token::Integer(n)) => Ok(Operand::Integer(-n)),
+                Some(Token::Float(n)) => Ok(Operand::Float(-n)),
+                Some(Token::Duration(d)) => Ok(Operand::Duration(-d)),
+                other => Err(invalid(format!("expect number after '-', found {other:?}"))),
+            };
+        }
+
+        let operand = match self.next() {
+            Some(Token::Word(w))
+                if w.eq_ignore_ascii_case("now")
+                    && matches!(self.peek(), Some(Token::Symbol("("))) =>
+            {
+                self.expect_symbol("(")?;
+                self.expect_symbol(")")?;
+                Operand::Now
+            }
+            Some(Token::Word(w)) if w.eq_ignore_ascii_case("true") => Operand::Boolean(true),
+            Some(Token::Word(w)) if w.eq_ignore_ascii_case("false") => Operand::Boolean(false),
+            Some(Token::Word(w)) | Some(Token::QuotedIdentifier(w)) => Operand::Identifier(w),
+            Some(Token::String(s)) => Operand::String(s),
+            Some(Token::Integer(n)) => Operand::Integer(n),
+            Some(Token::Float(n)) => Operand::Float(n),
+            Some(Token::Duration(d)) => Operand::Duration(d),
+            Some(Token::Regex(r)) => Operand::Regex(r),
+            other => return Err(invalid(format!("expect operand, found {other:?}"))),
+        };
+        Ok(operand)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_select() {
+        let statements = parse(
+            r#"SELECT mean("usage") AS u, count(*) FROM "db"."autogen"."cpu" WHERE host =~ /^a/ AND time > now() - 1h GROUP BY time(5m), "host" fill(none) ORDER BY time DESC LIMIT 10"#,
+        )
+        .unwrap();
+        assert_eq!(
+            vec![Statement::Select(SelectStatement {
+                fields: vec![
+                    SelectField::Call {
+                        function: Function::Mean,
+                        arg: Some("usage".to_string()),
+                        alias: Some("u".to_string()),
+                    },
+                    SelectField::Call {
+                        function: Function::Count,
+                        arg: None,
+                        alias: None,
+                    },
+                ],
+                from: "cpu".to_string(),
+                condition: Some(Condition::And(
+                    Box::new(Condition::Compare {
+                        lhs: Operand::Identifier("host".to_string()),
+                        op: CompareOp::RegexMatch,
+                        rhs: Operand::Regex("^a".to_string()),
+                    }),
+                    Box::new(Condition::Compare {
+                        lhs: Operand::Identifier("time".to_string()),
+                        op: CompareOp::Gt,
+                        rhs: Operand::Sub(
+                            Box::new(Operand::Now),
+                            Box::new(Operand::Duration(3_600_000))
+                        ),
+                    }),
+                )),
+                group_by: GroupBy {
+                    interval: Some(300_000),
+                    tags: vec!["host".to_string()],
+                    all_tags: false,
+                },
+                fill: Fill::None,
+                order_desc: true,
+                limit: Some(10),
+            })],
+            statements
+        );
+
+        let statements = parse("select * from cpu; select last(v) from mem group by *").unwrap();
+        assert_eq!(2, statements.len());
+        let Statement::Select(select) = &statements[1] else {
+            unreachable!()
+        };
+        assert!(select.group_by.all_tags);
+        assert_eq!(Fill::Null, select.fill);
+    }
+
+    #[test]
+    fn test_parse_condition() {
+        let statements =
+            parse("SELECT v FROM t WHERE a = 'x' OR b != 1.5 AND time >= '2023-01-01T00:00:00Z'")
+                .unwrap();
+        let Statement::Select(select) = &statements[0] else {
+            unreachable!()
+        };
+        // AND binds tighter than OR.
+        let Some(Condition::Or(lhs, rhs)) = &select.condition else {
+            unreachable!()
+        };
+        assert_eq!(
+            Condition::Compare {
+                lhs: Operand::Identifier("a".to_string()),
+                op: CompareOp::Eq,
+                rhs: Operand::String("x".to_string()),
+            },
+            **lhs
+        );
+        assert!(matches!(**rhs, Condition::And(_, _)));
+    }
+
+    #[test]
+    fn test_parse_show() {
+        assert_eq!(
+            vec![Statement::ShowMeasurements(ShowStatement {
+                measurement_regex: Some("cpu.*".to_string()),
+                limit: Some(100),
+                ..Default::default()
+            })],
+            parse("SHOW MEASUREMENTS WITH MEASUREMENT =~ /cpu.*/ LIMIT 100").unwrap()
+        );
+        assert_eq!(
+            vec![Statement::ShowTagKeys(ShowStatement {
+                from: Some("cpu".to_string()),
+                ..Default::default()
+            })],
+            parse("SHOW TAG KEYS FROM cpu").unwrap()
+        );
+        assert_eq!(
+            vec![Statement::ShowTagValues {
+                show: ShowStatement {
+                    from: Some("cpu".to_string()),
+                    ..Default::default()
+                },
+                keys: vec!["host".to_string(), "region".to_string()],
+            }],
+            parse(r#"SHOW TAG VALUES FROM "cpu" WITH KEY IN ("host", region)"#).unwrap()
+        );
+        assert_eq!(
+            vec![Statement::ShowFieldKeys(ShowStatement::default())],
+            parse("show field keys").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert!(parse("DELETE FROM cpu").is_err());
+        assert!(parse("SELECT median(v) FROM cpu").is_err());
+        assert!(parse("SELECT v FROM cpu GROUP BY time(0s)").is_err());
+        assert!(parse("SELECT v FROM cpu WHERE a = -'x'").is_err());
+        assert!(parse("SELECT v FROM cpu extra").is_err());
+        assert!(parse("SHOW DATABASES").is_err());
+    }
+}
@@ -0,0 +1,1455 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Plans InfluxQL statements onto the tables written by the line protocol, and
+//! converts the query results into the series of InfluxDB `/query` responses.
+//!
+//! In those tables, the tags are the primary key columns, the fields are the
+//! other columns besides the time index. `mean`, `sum` and `count` are
+//! aggregated by the query engine, while `last` is aggregated on the results of
+//! a raw query ordered by time.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone, Utc};
+use common_recordbatch::RecordBatches;
+use common_time::timestamp::TimeUnit;
+use datafusion::arrow::datatypes::{DataType, TimeUnit as ArrowTimeUnit};
+use datafusion::common::Column;
+use datafusion::prelude::{avg, cast, count, lit, regexp_match, sum, Expr};
+use datatypes::prelude::{ConcreteDataType, Value};
+use query::dataframe::DataFrame;
+use query::plan::LogicalPlan;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use snafu::{ensure, OptionExt, ResultExt};
+use table::TableRef;
+
+use crate::error::{self, Result};
+use crate::influxdb::influxql::{
+    CompareOp, Condition, Fill, Function, Operand, SelectField, SelectStatement, ShowStatement,
+};
+
+/// Upper limit of points a series can be filled to by `GROUP BY time()`.
+const MAX_FILL_POINTS: i64 = 100_000;
+
+/// The time column name in the query results, regardless of the time index name.
+const TIME_COLUMN: &str = "time";
+
+/// A series of the `/query` response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Series {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: BTreeMap<String, String>,
+    pub columns: Vec<String>,
+    pub values: Vec<Vec<JsonValue>>,
+}
+
+/// The format of the time in the results, set by the `epoch` parameter. Times are
+/// formatted as RFC3339 strings if it's absent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Epoch {
+    Nanosecond,
+    Microsecond,
+    Millisecond,
+    Second,
+    Minute,
+    Hour,
+}
+
+impl Epoch {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "n" | "ns" => Ok(Epoch::Nanosecond),
+            "u" | "us" | "µ" => Ok(Epoch::Microsecond),
+            "ms" => Ok(Epoch::Millisecond),
+            "s" => Ok(Epoch::Second),
+            "m" => Ok(Epoch::Minute),
+            "h" => Ok(Epoch::Hour),
+            unknown => error::InvalidInfluxqlSnafu {
+                reason: format!("invalid epoch '{unknown}'"),
+            }
+            .fail(),
+        }
+    }
+
+    fn format(&self, millis: i64) -> JsonValue {
+        let value = match self {
+            Epoch::Nanosecond => millis.saturating_mul(1_000_000),
+            Epoch::Microsecond => millis.saturating_mul(1_000),
+            Epoch::Millisecond => millis,
+            Epoch::Second => millis.div_euclid(1_000),
+            Epoch::Minute => millis.div_euclid(60_000),
+            Epoch::Hour => millis.div_euclid(3_600_000),
+        };
+        JsonValue::from(value)
+    }
+}
+
+fn format_time(millis: i64, epoch: Option<Epoch>) -> JsonValue {
+    match epoch {
+        Some(epoch) => epoch.format(millis),
+        None => match Utc.timestamp_millis_opt(millis).single() {
+            Some(time) => JsonValue::String(time.to_rfc3339_opts(SecondsFormat::AutoSi, true)),
+            None => JsonValue::from(millis),
+        },
+    }
+}
+
+/// The measurement of a table written by the line protocol.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InfluxdbTable {
+    pub measurement: String,
+    pub time_index: String,
+    pub tags: Vec<String>,
+    /// Field names and their data types, in the order of the columns.
+    pub fields: Vec<(String, ConcreteDataType)>,
+}
+
+impl InfluxdbTable {
+    /// Returns `None` if the table has no time index.
+    pub fn try_new(table: &TableRef) -> Option<Self> {
+        let table_info = table.table_info();
+        let schema = &table_info.meta.schema;
+        let time_index = schema.timestamp_column()?.name.clone();
+        let tags = table_info
+            .meta
+            .primary_key_indices
+            .iter()
+            .map(|i| schema.column_schemas()[*i].name.clone())
+            .collect::<Vec<_>>();
+        let fields = schema
+            .column_schemas()
+            .iter()
+            .filter(|c| c.name != time_index && !tags.contains(&c.name))
+            .map(|c| (c.name.clone(), c.data_type.clone()))
+            .collect();
+
+        Some(Self {
+            measurement: table_info.name.clone(),
+            time_index,
+            tags,
+            fields,
+        })
+    }
+
+    fn is_tag(&self, name: &str) -> bool {
+        self.tags.iter().any(|tag| tag == name)
+    }
+
+    fn field_type(&self, name: &str) -> Option<&ConcreteDataType> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, data_type)| data_type)
+    }
+}
+
+/// Returns the InfluxDB type name of a field, one of `float`, `integer`, `string`
+/// and `boolean`.
+fn field_type_name(data_type: &ConcreteDataType) -> &'static str {
+    match data_type {
+        ConcreteDataType::Float32(_) | ConcreteDataType::Float64(_) => "float",
+        ConcreteDataType::Int8(_)
+        | ConcreteDataType::Int16(_)
+        | ConcreteDataType::Int32(_)
+        | ConcreteDataType::Int64(_)
+        | ConcreteDataType::UInt8(_)
+        | ConcreteDataType::UInt16(_)
+        | ConcreteDataType::UInt32(_)
+        | ConcreteDataType::UInt64(_) => "integer",
+        ConcreteDataType::Boolean(_) => "boolean",
+        _ => "string",
+    }
+}
+
+fn is_numeric(data_type: &ConcreteDataType) -> bool {
+    matches!(field_type_name(data_type), "float" | "integer")
+}
+
+fn column(name: &str) -> Expr {
+    Expr::Column(Column::from_name(name))
+}
+
+fn invalid(reason: impl Into<String>) -> error::Error {
+    error::InvalidInfluxqlSnafu {
+        reason: reason.into(),
+    }
+    .build()
+}
+
+/// A column of the `SELECT` results.
+#[derive(Debug, Clone, PartialEq)]
+enum Projection {
+    Column {
+        name: String,
+        output: String,
+    },
+    Aggregate {
+        function: Function,
+        field: String,
+        output: String,
+    },
+}
+
+impl Projection {
+    fn output(&self) -> &str {
+        match self {
+            Projection::Column { output, .. } | Projection::Aggregate { output, .. } => output,
+        }
+    }
+
+    fn output_mut(&mut self) -> &mut String {
+        match self {
+            Projection::Column { output, .. } | Projection::Aggregate { output, .. } => output,
+        }
+    }
+
+    /// The column read from the table.
+    fn source(&self) -> &str {
+        match self {
+            Projection::Column { name, .. } => name,
+            Projection::Aggregate { field, .. } => field,
+        }
+    }
+}
+
+/// A `SELECT` statement resolved against the schema of the measurement.
+#[derive(Debug, Clone)]
+pub struct SelectQuery {
+    measurement: String,
+    time_index: String,
+    projections: Vec<Projection>,
+    group_by_tags: Vec<String>,
+    interval: Option<i64>,
+    /// The time range of the `WHERE` clause, both inclusive.
+    start: Option<i64>,
+    end: Option<i64>,
+    now: i64,
+    fill: Fill,
+    order_desc: bool,
+    limit: Option<usize>,
+    condition: Option<Expr>,
+}
+
+impl SelectQuery {
+    pub fn try_new(select: &SelectStatement, table: &InfluxdbTable, now: i64) -> Result<Self> {
+        // Tags absent in the measurement have empty values in every series, so
+        // they don't split the results.
+        let group_by_tags = if select.group_by.all_tags {
+            table.tags.clone()
+        } else {
+            select
+                .group_by
+                .tags
+                .iter()
+                .filter(|tag| table.is_tag(tag))
+                .cloned()
+                .collect()
+        };
+
+        let mut projections = vec![];
+        for field in &select.fields {
+            match field {
+                SelectField::Wildcard => {
+                    let names = table
+                        .fields
+                        .iter()
+                        .map(|(name, _)| name)
+                        .chain(table.tags.iter().filter(|tag| !group_by_tags.contains(tag)))
+                        .collect::<BTreeSet<_>>();
+                    projections.extend(names.into_iter().map(|name| Projection::Column {
+                        name: name.clone(),
+                        output: name.clone(),
+                    }));
+                }
+                SelectField::Column { name, alias } => {
+                    ensure!(
+                        table.is_tag(name) || table.field_type(name).is_some(),
+                        error::InvalidInfluxqlSnafu {
+                            reason: format!(
+                                "unknown field or tag '{name}' in measurement '{}'",
+                                table.measurement
+                            ),
+                        }
+                    );
+                    projections.push(Projection::Column {
+                        name: name.clone(),
+                        output: alias.clone().unwrap_or_else(|| name.clone()),
+                    });
+                }
+                SelectField::Call {
+                    function,
+                    arg: Some(field),
+                    alias,
+                } => {
+                    let data_type =
+                        table
+                            .field_type(field)
+                            .with_context(|| error::InvalidInfluxqlSnafu {
+                                reason: format!(
+                                    "unknown field '{field}' in measurement '{}'",
+                                    table.measurement
+                                ),
+                            })?;
+                    ensure!(
+                        !matches!(function, Function::Mean | Function::Sum)
+                            || is_numeric(data_type),
+                        error::InvalidInfluxqlSnafu {
+                            reason: format!(
+                                "unsupported {}() on {} field '{field}'",
+                                function.name(),
+                                field_type_name(data_type)
+                            ),
+                        }
+                    );
+                    projections.push(Projection::Aggregate {
+                        function: *function,
+                        field: field.clone(),
+                        output: alias.clone().unwrap_or_else(|| function.name().to_string()),
+                    });
+                }
+                SelectField::Call {
+                    function,
+                    arg: None,
+                    alias,
+                } => {
+                    // `mean(*)` and `sum(*)` skip fields that are not numbers.
+                    let prefix = alias.as_deref().unwrap_or(function.name());
+                    projections.extend(
+                        table
+                            .fields
+                            .iter()
+                            .filter(|(_, data_type)| {
+                                !matches!(function, Function::Mean | Function::Sum)
+                                    || is_numeric(data_type)
+                            })
+                            .map(|(field, _)| Projection::Aggregate {
+                                function: *function,
+                                field: field.clone(),
+                                output: format!("{prefix}_{field}"),
+                            }),
+                    );
+                }
+            }
+        }
+
+        let is_aggregate = projections
+            .iter()
+            .any(|p| matches!(p, Projection::Aggregate { .. }));
+        ensure!(
+            !is_aggregate
+                || projections
+                    .iter()
+                    .all(|p| matches!(p, Projection::Aggregate { .. })),
+            error::InvalidInfluxqlSnafu {
+                reason: "mixing aggregate and non-aggregate queries is not supported",
+            }
+        );
+        ensure!(
+            is_aggregate || select.group_by.interval.is_none(),
+            error::InvalidInfluxqlSnafu {
+                reason: "GROUP BY time() requires at least one aggregate function",
+            }
+        );
+        ensure!(
+            !projections.is_empty(),
+            error::InvalidInfluxqlSnafu {
+                reason: format!("no field to select in measurement '{}'", table.measurement),
+            }
+        );
+        dedup_outputs(&mut projections);
+
+        let (start, end) = match &select.condition {
+            Some(condition) => time_range(condition, now)?,
+            None => (None, None),
+        };
+        let condition = select
+            .condition
+            .as_ref()
+            .map(|condition| condition_to_expr(condition, table, now))
+            .transpose()?;
+
+        Ok(Self {
+            measurement: table.measurement.clone(),
+            time_index: table.time_index.clone(),
+            projections,
+            group_by_tags,
+            interval: select.group_by.interval,
+            start,
+            end,
+            now,
+            fill: select.fill.clone(),
+            order_desc: select.order_desc,
+            limit: select.limit,
+            condition,
+        })
+    }
+
+    fn is_aggregate(&self) -> bool {
+        matches!(self.projections.first(), Some(Projection::Aggregate { .. }))
+    }
+
+    /// Whether the aggregation is done by the query engine, otherwise it's done on
+    /// the raw rows of the results.
+    fn aggregate_in_plan(&self) -> bool {
+        self.is_aggregate()
+            && self.projections.iter().all(|p| {
+                !matches!(
+                    p,
+                    Projection::Aggregate {
+                        function: Function::Last,
+                        ..
+                    }
+                )
+            })
+    }
+
+    /// Translates the query into a logical plan, which returns the rows of each
+    /// group ordered by the time.
+    pub fn to_plan(&self, dataframe: DataFrame) -> Result<LogicalPlan> {
+        let DataFrame::DataFusion(mut dataframe) = dataframe;
+        if let Some(condition) = &self.condition {
+            dataframe = dataframe
+                .filter(condition.clone())
+                .context(error::DataFrameSnafu)?;
+        }
+
+        let mut sort_columns = self.group_by_tags.clone();
+        if self.aggregate_in_plan() {
+            let mut group_exprs = self
+                .group_by_tags
+                .iter()
+                .map(|tag| column(tag))
+                .collect::<Vec<_>>();
+            if let Some(interval) = self.interval {
+                let bucket = (cast(column(&self.time_index), DataType::Int64) / lit(interval))
+                    * lit(interval);
+                group_exprs.push(
+                    cast(
+                        bucket,
+                        DataType::Timestamp(ArrowTimeUnit::Millisecond, None),
+                    )
+                    .alias(&self.time_index),
+                );
+                sort_columns.push(self.time_index.clone());
+            }
+            let aggr_exprs = self
+                .projections
+                .iter()
+                .map(|p| match p {
+                    Projection::Aggregate {
+                        function,
+                        field,
+                        output,
+                    } => {
+                        let expr = match function {
+                            Function::Mean => avg(column(field)),
+                            Function::Sum => sum(column(field)),
+                            Function::Count => count(column(field)),
+                            Function::Last => unreachable!(),
+                        };
+                        expr.alias(output)
+                    }
+                    Projection::Column { .. } => unreachable!(),
+                })
+                .collect();
+            dataframe = dataframe
+                .aggregate(group_exprs, aggr_exprs)
+                .context(error::DataFrameSnafu)?;
+        } else {
+            let mut columns = self.group_by_tags.clone();
+            columns.push(self.time_index.clone());
+            for projection in &self.projections {
+                if !columns.iter().any(|c| c == projection.source()) {
+                    columns.push(projection.source().to_string());
+                }
+            }
+            dataframe = dataframe
+                .select(columns.iter().map(|c| column(c)).collect())
+                .context(error::DataFrameSnafu)?;
+            sort_columns.push(self.time_index.clone());
+        }
+
+        if !sort_columns.is_empty() {
+            dataframe = dataframe
+                .sort(
+                    sort_columns
+                        .iter()
+                        .map(|c| column(c).sort(true, true))
+                        .collect(),
+                )
+                .context(error::DataFrameSnafu)?;
+        }
+
+        Ok(LogicalPlan::DfPlan(dataframe.into_parts().1))
+    }
+
+    /// Converts the results of the plan into series, one for each group of the
+    /// `GROUP BY` tags.
+    pub fn recordbatches_to_series(
+        &self,
+        recordbatches: RecordBatches,
+        epoch: Option<Epoch>,
+    ) -> Result<Vec<Series>> {
+        let value_columns = self
+            .projections
+            .iter()
+            .map(|p| {
+                if self.aggregate_in_plan() {
+                    p.output()
+                } else {
+                    p.source()
+                }
+            })
+            .collect::<Vec<_>>();
+        // Aggregations without `GROUP BY time()` are reported at the start time.
+        let default_time = self.start.unwrap_or(0);
+
+        let mut groups: BTreeMap<Vec<String>, Vec<(i64, Vec<Value>)>> = BTreeMap::new();
+        for recordbatch in recordbatches.iter() {
+            let column = |name: &str| {
+                recordbatch
+                    .column_by_name(name)
+                    .context(error::InvalidInfluxqlSnafu {
+                        reason: format!("missing column {name} in query result"),
+                    })
+            };
+            let tag_columns = self
+                .group_by_tags
+                .iter()
+                .map(|tag| column(tag))
+                .collect::<Result<Vec<_>>>()?;
+            let time_column = recordbatch.column_by_name(&self.time_index);
+            let value_columns = value_columns
+                .iter()
+                .map(|name| column(name))
+                .collect::<Result<Vec<_>>>()?;
+
+            for row in 0..recordbatch.num_rows() {
+                let key = tag_columns
+                    .iter()
+                    .map(|c| match c.get(row) {
+                        Value::String(v) => v.as_utf8().to_string(),
+                        _ => String::new(),
+                    })
+                    .collect();
+                let time = time_column
+                    .and_then(|c| match c.get(row) {
+                        Value::Timestamp(t) => {
+                            t.convert_to(TimeUnit::Millisecond).map(|t| t.value())
+                        }
+                        _ => None,
+                    })
+                    .unwrap_or(default_time);
+                let values = value_columns.iter().map(|c| c.get(row)).collect();
+                groups.entry(key).or_default().push((time, values));
+            }
+        }
+
+        let mut columns = vec![TIME_COLUMN.to_string()];
+        columns.extend(self.projections.iter().map(|p| p.output().to_string()));
+
+        let mut series = Vec::with_capacity(groups.len());
+        for (key, mut rows) in groups {
+            if self.is_aggregate() {
+                if !self.aggregate_in_plan() {
+                    rows = self.aggregate_rows(rows);
+                }
+                if self.interval.is_some() {
+                    rows = self.fill_rows(rows)?;
+                }
+            }
+            if self.order_desc {
+                rows.reverse();
+            }
+            if let Some(limit) = self.limit {
+                rows.truncate(limit);
+            }
+            if rows.is_empty() {
+                continue;
+            }
+
+            series.push(Series {
+                name: self.measurement.clone(),
+                tags: self.group_by_tags.iter().cloned().zip(key).collect(),
+                columns: columns.clone(),
+                values: rows
+                    .into_iter()
+                    .map(|(time, values)| {
+                        std::iter::once(format_time(time, epoch))
+                            .chain(values.into_iter().map(value_to_json))
+                            .collect()
+                    })
+                    .collect(),
+            });
+        }
+        Ok(series)
+    }
+
+    /// Aggregates raw rows ordered by time into buckets of the interval.
+    fn aggregate_rows(&self, rows: Vec<(i64, Vec<Value>)>) -> Vec<(i64, Vec<Value>)> {
+        let mut buckets: BTreeMap<i64, Vec<Vec<Value>>> = BTreeMap::new();
+        for (time, values) in rows {
+            let bucket = match self.interval {
+                Some(interval) => time - time.rem_euclid(interval),
+                None => self.start.unwrap_or(0),
+            };
+            buckets.entry(bucket).or_default().push(values);
+        }
+
+        buckets
+            .into_iter()
+            .map(|(bucket, rows)| {
+                let values = self
+                    .projections
+                    .iter()
+                    .enumerate()
+                    .map(|(i, p)| match p {
+                        Projection::Aggregate { function, .. } => {
+                            aggregate(*function, rows.iter().map(|row| &row[i]))
+                        }
+                        Projection::Column { .. } => Value::Null,
+                    })
+                    .collect();
+                (bucket, values)
+            })
+            .collect()
+    }
+
+    /// Fills the missing buckets in the time range according to the `fill()` option.
+    fn fill_rows(&self, rows: Vec<(i64, Vec<Value>)>) -> Result<Vec<(i64, Vec<Value>)>> {
+        let Some(interval) = self.interval else {
+            return Ok(rows);
+        };
+        if self.fill == Fill::None {
+            return Ok(rows);
+        }
+        let align = |time: i64| time - time.rem_euclid(interval);
+        let Some(first) = self.start.map(align).or_else(|| rows.first().map(|r| r.0)) else {
+            return Ok(rows);
+        };
+        let last =
+            align(self.end.unwrap_or(self.now)).max(rows.last().map(|r| r.0).unwrap_or(i64::MIN));
+        ensure!(
+            (last - first) / interval < MAX_FILL_POINTS,
+            error::InvalidInfluxqlSnafu {
+                reason: format!("too many points in GROUP BY time({interval}ms)"),
+            }
+        );
+
+        let mut rows = rows.into_iter().peekable();
+        let mut filled = vec![];
+        let mut previous: Option<Vec<Value>> = None;
+        let mut time = first;
+        while time <= last {
+            while let Some(row) = rows.next_if(|row| row.0 < time) {
+                filled.push(row);
+            }
+            match rows.next_if(|row| row.0 == time) {
+                Some(row) => {
+                    previous = Some(row.1.clone());
+                    filled.push(row);
+                }
+                None => {
+                    let values = self
+                        .projections
+                        .iter()
+                        .enumerate()
+                        .map(|(i, p)| match &self.fill {
+                            Fill::Null => match p {
+                                Projection::Aggregate {
+                                    function: Function::Count,
+                                    ..
+                                } => Value::from(0i64),
+                                _ => Value::Null,
+                            },
+                            Fill::Previous => previous
+                                .as_ref()
+                                .map(|values| values[i].clone())
+                                .unwrap_or(Value::Null),
+                            Fill::Integer(n) => Value::from(*n),
+                            Fill::Float(n) => Value::from(*n),
+                            Fill::None => unreachable!(),
+                        })
+                        .collect();
+                    filled.push((time, values));
+                }
+            }
+            time += interval;
+        }
+        filled.extend(rows);
+        Ok(filled)
+    }
+}
+
+/// Renames duplicate output names like InfluxDB does, e.g. `mean`, `mean_1`.
+fn dedup_outputs(projections: &mut [Projection]) {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for projection in projections {
+        let output = projection.output_mut();
+        let count = seen.entry(output.clone()).or_insert(0);
+        if *count > 0 {
+            *output = format!("{output}_{count}");
+        }
+        *count += 1;
+    }
+}
+
+fn value_to_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Int8(v) => Some(*v as f64),
+        Value::Int16(v) => Some(*v as f64),
+        Value::Int32(v) => Some(*v as f64),
+        Value::Int64(v) => Some(*v as f64),
+        Value::UInt8(v) => Some(*v as f64),
+        Value::UInt16(v) => Some(*v as f64),
+        Value::UInt32(v) => Some(*v as f64),
+        Value::UInt64(v) => Some(*v as f64),
+        Value::Float32(v) => Some(v.0 as f64),
+        Value::Float64(v) => Some(v.0),
+        _ => None,
+    }
+}
+
+fn aggregate<'a>(function: Function, values: impl Iterator<Item = &'a Value>) -> Value {
+    let values = values.filter(|v| !v.is_null()).collect::<Vec<_>>();
+    match function {
+        Function::Count => Value::from(values.len() as i64),
+        Function::Last => values.last().map(|v| (*v).clone()).unwrap_or(Value::Null),
+        Function::Mean | Function::Sum => {
+            let numbers = values
+                .iter()
+                .filter_map(|v| value_to_f64(v))
+                .collect::<Vec<_>>();
+            if numbers.is_empty() {
+                return Value::Null;
+            }
+            let sum = numbers.iter().sum::<f64>();
+            if function == Function::Mean {
+                Value::from(sum / numbers.len() as f64)
+            } else if values
+                .iter()
+                .all(|v| !matches!(v, Value::Float32(_) | Value::Float64(_)))
+            {
+                Value::from(sum as i64)
+            } else {
+                Value::from(sum)
+            }
+        }
+    }
+}
+
+fn value_to_json(value: Value) -> JsonValue {
+    JsonValue::try_from(value).unwrap_or(JsonValue::Null)
+}
+
+/// Splits a comparison into the identifier, the operator and the other operand,
+/// with the identifier on the left. Returns `None` if neither side is an identifier.
+fn split_compare<'a>(
+    lhs: &'a Operand,
+    op: CompareOp,
+    rhs: &'a Operand,
+) -> Option<(&'a str, CompareOp, &'a Operand)> {
+    match (lhs, rhs) {
+        (Operand::Identifier(name), rhs) => Some((name, op, rhs)),
+        (lhs, Operand::Identifier(name)) => Some((name, op.swap(), lhs)),
+        _ => None,
+    }
+}
+
+fn is_time(name: &str) -> bool {
+    name.eq_ignore_ascii_case(TIME_COLUMN)
+}
+
+/// Evaluates a time operand into milliseconds. Integers are nanoseconds as in
+/// InfluxDB, and strings are RFC3339 times or dates like `2023-07-01 12:00:00`.
+fn eval_time(operand: &Operand, now: i64) -> Result<i64> {
+    match operand {
+        Operand::Now => Ok(now),
+        Operand::Duration(millis) => Ok(*millis),
+        Operand::Integer(nanos) => Ok(nanos.div_euclid(1_000_000)),
+        Operand::Float(nanos) => Ok((nanos / 1_000_000.0) as i64),
+        Operand::String(time) => parse_time(time),
+        Operand::Add(lhs, rhs) => Ok(eval_time(lhs, now)? + eval_time(rhs, now)?),
+        Operand::Sub(lhs, rhs) => Ok(eval_time(lhs, now)? - eval_time(rhs, now)?),
+        other => Err(invalid(format!("invalid time {other:?}"))),
+    }
+}
+
+fn parse_time(time: &str) -> Result<i64> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(time) {
+        return Ok(time.timestamp_millis());
+    }
+    if let Ok(time) = NaiveDateTime::parse_from_str(time, "%Y-%m-%d %H:%M:%S%.f") {
+        return Ok(time.timestamp_millis());
+    }
+    if let Some(time) = NaiveDate::parse_from_str(time, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+    {
+        return Ok(time.timestamp_millis());
+    }
+    Err(invalid(format!("invalid time '{time}'")))
+}
+
+/// Extracts the time range, both inclusive, from the time comparisons joined by
+/// `AND` in the condition.
+fn time_range(condition: &Condition, now: i64) -> Result<(Option<i64>, Option<i64>)> {
+    let mut range = (None, None);
+    collect_time_range(condition, now, &mut range)?;
+    Ok(range)
+}
+
+fn collect_time_range(
+    condition: &Condition,
+    now: i64,
+    range: &mut (Option<i64>, Option<i64>),
+) -> Result<()> {
+    match condition {
+        Condition::And(lhs, rhs) => {
+            collect_time_range(lhs, now, range)?;
+            collect_time_range(rhs, now, range)
+        }
+        Condition::Or(_, _) => Ok(()),
+        Condition::Compare { lhs, op, rhs } => {
+            let Some((name, op, operand)) = split_compare(lhs, *op, rhs) else {
+                return Ok(());
+            };
+            if !is_time(name) {
+                return Ok(());
+            }
+            let time = eval_time(operand, now)?;
+            let (start, end) = match op {
+                CompareOp::Gt => (Some(time + 1), None),
+                CompareOp::GtEq => (Some(time), None),
+                CompareOp::Lt => (None, Some(time - 1)),
+                CompareOp::LtEq => (None, Some(time)),
+                CompareOp::Eq => (Some(time), Some(time)),
+                _ => (None, None),
+            };
+            if let Some(start) = start {
+                range.0 = Some(range.0.map_or(start, |s| s.max(start)));
+            }
+            if let Some(end) = end {
+                range.1 = Some(range.1.map_or(end, |e| e.min(end)));
+            }
+            Ok(())
+        }
+    }
+}
+
+fn compare(lhs: Expr, op: CompareOp, rhs: Expr) -> Result<Expr> {
+    match op {
+        CompareOp::Eq => Ok(lhs.eq(rhs)),
+        CompareOp::NotEq => Ok(lhs.not_eq(rhs)),
+        CompareOp::Lt => Ok(lhs.lt(rhs)),
+        CompareOp::LtEq => Ok(lhs.lt_eq(rhs)),
+        CompareOp::Gt => Ok(lhs.gt(rhs)),
+        CompareOp::GtEq => Ok(lhs.gt_eq(rhs)),
+        CompareOp::RegexMatch | CompareOp::RegexNotMatch => {
+            Err(invalid("regex comparison requires a regex operand"))
+        }
+    }
+}
+
+/// Translates a `WHERE` condition into a filter expression on the table.
+fn condition_to_expr(condition: &Condition, table: &InfluxdbTable, now: i64) -> Result<Expr> {
+    let (lhs, op, rhs) = match condition {
+        Condition::And(lhs, rhs) => {
+            return Ok(condition_to_expr(lhs, table, now)?.and(condition_to_expr(rhs, table, now)?))
+        }
+        Condition::Or(lhs, rhs) => {
+            return Ok(condition_to_expr(lhs, table, now)?.or(condition_to_expr(rhs, table, now)?))
+        }
+        Condition::Compare { lhs, op, rhs } => (lhs, *op, rhs),
+    };
+
+    let (name, op, operand) =
+        split_compare(lhs, op, rhs).with_context(|| error::InvalidInfluxqlSnafu {
+            reason: format!("invalid condition {condition:?}"),
+        })?;
+    if is_time(name) {
+        let time = eval_time(operand, now)?;
+        return compare(column(&table.time_index), op, lit(time));
+    }
+    if !table.is_tag(name) && table.field_type(name).is_none() {
+        // Unknown tags or fields are null, which only match the negative conditions.
+        return Ok(lit(matches!(
+            op,
+            CompareOp::NotEq | CompareOp::RegexNotMatch
+        )));
+    }
+
+    let lhs = column(name);
+    match operand {
+        Operand::Regex(regex) => {
+            Regex::new(regex).map_err(|e| invalid(format!("invalid regex /{regex}/: {e}")))?;
+            let matched = regexp_match(vec![lhs, lit(regex.as_str())]);
+            match op {
+                CompareOp::RegexMatch => Ok(matched.is_not_null()),
+                CompareOp::RegexNotMatch => Ok(matched.is_null()),
+                _ => Err(invalid(format!("invalid operator {op:?} on regex"))),
+            }
+        }
+        // Tags with empty values are absent, and stored as null.
+        Operand::String(value) if value.is_empty() && table.is_tag(name) => match op {
+            CompareOp::Eq => Ok(lhs.clone().is_null().or(lhs.eq(lit("")))),
+            CompareOp::NotEq => Ok(lhs.clone().is_not_null().and(lhs.not_eq(lit("")))),
+            _ => compare(lhs, op, lit("")),
+        },
+        Operand::String(value) => compare(lhs, op, lit(value.as_str())),
+        Operand::Integer(value) => compare(lhs, op, lit(*value)),
+        Operand::Float(value) => compare(lhs, op, lit(*value)),
+        Operand::Boolean(value) => compare(lhs, op, lit(*value)),
+        Operand::Identifier(other) => compare(lhs, op, column(other)),
+        other => Err(invalid(format!("invalid operand {other:?} of '{name}'"))),
+    }
+}
+
+/// Returns the measurements matching the `FROM` and `WITH MEASUREMENT` clauses,
+/// ordered by their names.
+pub fn filter_measurements<'a>(
+    show: &ShowStatement,
+    tables: &'a [InfluxdbTable],
+) -> Result<Vec<&'a InfluxdbTable>> {
+    let regex = show
+        .measurement_regex
+        .as_deref()
+        .map(Regex::new)
+        .transpose()
+        .map_err(|e| invalid(format!("invalid measurement regex: {e}")))?;
+    let mut tables = tables
+        .iter()
+        .filter(|table| match &show.from {
+            Some(from) => &table.measurement == from,
+            None => true,
+        })
+        .filter(|table| match &regex {
+            Some(regex) => regex.is_match(&table.measurement),
+            None => true,
+        })
+        .collect::<Vec<_>>();
+    tables.sort_by(|a, b| a.measurement.cmp(&b.measurement));
+    Ok(tables)
+}
+
+fn ensure_no_condition(show: &ShowStatement, statement: &str) -> Result<()> {
+    ensure!(
+        show.condition.is_none(),
+        error::InvalidInfluxqlSnafu {
+            reason: format!("WHERE is not supported in {statement}"),
+        }
+    );
+    Ok(())
+}
+
+/// Results of `SHOW MEASUREMENTS`.
+pub fn show_measurements(show: &ShowStatement, tables: &[InfluxdbTable]) -> Result<Vec<Series>> {
+    ensure_no_condition(show, "SHOW MEASUREMENTS")?;
+    let mut names = filter_measurements(show, tables)?
+        .into_iter()
+        .map(|table| table.measurement.clone())
+        .collect::<Vec<_>>();
+    if let Some(limit) = show.limit {
+        names.truncate(limit);
+    }
+    if names.is_empty() {
+        return Ok(vec![]);
+    }
+
+    Ok(vec![Series {
+        name: "measurements".to_string(),
+        tags: BTreeMap::new(),
+        columns: vec!["name".to_string()],
+        values: names
+            .into_iter()
+            .map(|name| vec![JsonValue::String(name)])
+            .collect(),
+    }])
+}
+
+/// Results of `SHOW TAG KEYS`, a series for each measurement.
+pub fn show_tag_keys(show: &ShowStatement, tables: &[InfluxdbTable]) -> Result<Vec<Series>> {
+    ensure_no_condition(show, "SHOW TAG KEYS")?;
+    show_keys(show, tables, &["tagKey"], |table| {
+        table
+            .tags
+            .iter()
+            .map(|tag| vec![JsonValue::String(tag.clone())])
+            .collect()
+    })
+}
+
+/// Results of `SHOW FIELD KEYS`, a series for each measurement.
+pub fn show_field_keys(show: &ShowStatement, tables: &[InfluxdbTable]) -> Result<Vec<Series>> {
+    ensure_no_condition(show, "SHOW FIELD KEYS")?;
+    show_keys(show, tables, &["fieldKey", "fieldType"], |table| {
+        table
+            .fields
+            .iter()
+            .map(|(field, data_type)| {
+                vec![
+                    JsonValue::String(field.clone()),
+                    JsonValue::String(field_type_name(data_type).to_string()),
+                ]
+            })
+            .collect()
+    })
+}
+
+fn show_keys(
+    show: &ShowStatement,
+    tables: &[InfluxdbTable],
+    columns: &[&str],
+    keys: impl Fn(&InfluxdbTable) -> Vec<Vec<JsonValue>>,
+) -> Result<Vec<Series>> {
+    Ok(filter_measurements(show, tables)?
+        .into_iter()
+        .filter_map(|table| {
+            let mut values = keys(table);
+            values.sort_by(|a, b| a[0].as_str().cmp(&b[0].as_str()));
+            if let Some(limit) = show.limit {
+                values.truncate(limit);
+            }
+            if values.is_empty() {
+                return None;
+            }
+            Some(Series {
+                name: table.measurement.clone(),
+                tags: BTreeMap::new(),
+                columns: columns.iter().map(|c| c.to_string()).collect(),
+                values,
+            })
+        })
+        .collect())
+}
+
+/// Translates `SHOW TAG VALUES` on a measurement into a logical plan returning
+/// the distinct values of the keys. Returns `None` if none of the keys is a tag
+/// of the measurement.
+pub fn tag_values_to_plan(
+    dataframe: DataFrame,
+    table: &InfluxdbTable,
+    show: &ShowStatement,
+    keys: &[String],
+    now: i64,
+) -> Result<Option<LogicalPlan>> {
+    let keys = keys
+        .iter()
+        .filter(|key| table.is_tag(key))
+        .collect::<BTreeSet<_>>();
+    if keys.is_empty() {
+        return Ok(None);
+    }
+
+    let DataFrame::DataFusion(mut dataframe) = dataframe;
+    if let Some(condition) = &show.condition {
+        dataframe = dataframe
+            .filter(condition_to_expr(condition, table, now)?)
+            .context(error::DataFrameSnafu)?;
+    }
+    let dataframe = dataframe
+        .select(keys.into_iter().map(|key| column(key)).collect())
+        .context(error::DataFrameSnafu)?
+        .distinct()
+        .context(error::DataFrameSnafu)?;
+
+    Ok(Some(LogicalPlan::DfPlan(dataframe.into_parts().1)))
+}
+
+/// Converts the results of [tag_values_to_plan] into a series of key and value pairs.
+pub fn recordbatches_to_tag_values(
+    table: &InfluxdbTable,
+    show: &ShowStatement,
+    recordbatches: RecordBatches,
+) -> Option<Series> {
+    let mut pairs = BTreeSet::new();
+    for recordbatch in recordbatches.iter() {
+        for (column_schema, column) in recordbatch
+            .schema
+            .column_schemas()
+            .iter()
+            .zip(recordbatch.columns())
+        {
+            for row in 0..column.len() {
+                if let Value::String(value) = column.get(row) {
+                    let _ = pairs.insert((column_schema.name.clone(), value.as_utf8().to_string()));
+                }
+            }
+        }
+    }
+
+    let mut values = pairs
+        .into_iter()
+        .map(|(key, value)| vec![JsonValue::String(key), JsonValue::String(value)])
+        .collect::<Vec<_>>();
+    if let Some(limit) = show.limit {
+        values.truncate(limit);
+    }
+    if values.is_empty() {
+        return None;
+    }
+
+    Some(Series {
+        name: table.measurement.clone(),
+        tags: BTreeMap::new(),
+        columns: vec!["key".to_string(), "value".to_string()],
+        values,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use common_recordbatch::RecordBatch;
+    use datatypes::schema::{ColumnSchema, Schema};
+    use datatypes::vectors::{Float64Vector, StringVector, TimestampMillisecondVector};
+    use serde_json::json;
+
+    use super::*;
+    use crate::influxdb::influxql::{self, Statement};
+
+    fn mock_table() -> InfluxdbTable {
+        InfluxdbTable {
+            measurement: "cpu".to_string(),
+            time_index: "ts".to_string(),
+            tags: vec!["host".to_string(), "region".to_string()],
+            fields: vec![
+                ("usage".to_string(), ConcreteDataType::float64_datatype()),
+                ("status".to_string(), ConcreteDataType::string_datatype()),
+            ],
+        }
+    }
+
+    fn select_query(sql: &str) -> Result<SelectQuery> {
+        let Statement::Select(select) = influxql::parse(sql)?.remove(0) else {
+            unreachable!()
+        };
+        SelectQuery::try_new(&select, &mock_table(), 600_000)
+    }
+
+    fn mock_recordbatches(
+        columns: Vec<(&str, ConcreteDataType)>,
+        vectors: Vec<VectorRef>,
+    ) -> RecordBatches {
+        let schema = Arc::new(Schema::new(
+            columns
+                .into_iter()
+                .map(|(name, data_type)| ColumnSchema::new(name, data_type, true))
+                .collect(),
+        ));
+        let recordbatch = RecordBatch::new(schema.clone(), vectors).unwrap();
+        RecordBatches::try_new(schema, vec![recordbatch]).unwrap()
+    }
+
+    #[test]
+    fn test_format_time() {
+        assert_eq!(json!("1970-01-01T00:00:00Z"), format_time(0, None));
+        assert_eq!(
+            json!("2023-07-01T00:00:00.123Z"),
+            format_time(1_688_169_600_123, None)
+        );
+        assert_eq!(
+            json!(1_500_000_000),
+            format_time(1_500, Some(Epoch::Nanosecond))
+        );
+        assert_eq!(json!(1), format_time(1_500, Some(Epoch::Second)));
+        assert_eq!(Epoch::Microsecond, Epoch::parse("u").unwrap());
+        assert!(Epoch::parse("d").is_err());
+    }
+
+    #[test]
+    fn test_resolve_select() {
+        let query = select_query("SELECT * FROM cpu GROUP BY host").unwrap();
+        assert_eq!(
+            vec!["region", "status", "usage"],
+            query
+                .projections
+                .iter()
+                .map(|p| p.output())
+                .collect::<Vec<_>>()
+        );
+        assert!(!query.is_aggregate());
+
+        let query =
+            select_query("SELECT mean(*), count(*), last(usage), last(status) FROM cpu").unwrap();
+        assert_eq!(
+            vec![
+                "mean_usage",
+                "count_usage",
+                "count_status",
+                "last",
+                "last_1"
+            ],
+            query
+                .projections
+                .iter()
+                .map(|p| p.output())
+                .collect::<Vec<_>>()
+        );
+        assert!(query.is_aggregate());
+        assert!(!query.aggregate_in_plan());
+
+        let query = select_query(
+            "SELECT sum(usage) FROM cpu WHERE time > now() - 5m AND time <= now() GROUP BY *",
+        )
+        .unwrap();
+        assert!(query.aggregate_in_plan());
+        assert_eq!(vec!["host", "region"], query.group_by_tags);
+        assert_eq!((Some(300_001), Some(600_000)), (query.start, query.end));
+
+        assert!(select_query("SELECT usage, mean(usage) FROM cpu").is_err());
+        assert!(select_query("SELECT usage FROM cpu GROUP BY time(1m)").is_err());
+        assert!(select_query("SELECT unknown FROM cpu").is_err());
+        assert!(select_query("SELECT mean(status) FROM cpu").is_err());
+    }
+
+    #[test]
+    fn test_time_range() {
+        let range = |sql: &str| {
+            let Statement::Select(select) = influxql::parse(sql).unwrap().remove(0) else {
+                unreachable!()
+            };
+            time_range(&select.condition.unwrap(), 600_000).unwrap()
+        };
+
+        assert_eq!(
+            (Some(1_688_169_600_000), Some(1_688_169_660_000)),
+            range("SELECT v FROM t WHERE time >= '2023-07-01T00:00:00Z' AND time <= '2023-07-01 00:01:00'")
+        );
+        assert_eq!(
+            (Some(1_000), Some(599_999)),
+            range("SELECT v FROM t WHERE host = 'a' AND 1000000000 <= time AND time < now()")
+        );
+        assert_eq!(
+            (None, None),
+            range("SELECT v FROM t WHERE time > 0 OR time < 10")
+        );
+    }
+
+    #[test]
+    fn test_condition_to_expr() {
+        let expr = |sql: &str| {
+            let Statement::Select(select) = influxql::parse(sql).unwrap().remove(0) else {
+                unreachable!()
+            };
+            condition_to_expr(&select.condition.unwrap(), &mock_table(), 600_000)
+        };
+
+        assert_eq!(
+            column("host")
+                .eq(lit("a"))
+                .and(column("ts").gt(lit(540_000i64))),
+            expr("SELECT usage FROM cpu WHERE host = 'a' AND time > now() - 1m").unwrap()
+        );
+        assert_eq!(
+            column("host").is_null().or(column("host").eq(lit(""))),
+            expr("SELECT usage FROM cpu WHERE host = ''").unwrap()
+        );
+        assert_eq!(
+            regexp_match(vec![column("region"), lit("^us")]).is_null(),
+            expr("SELECT usage FROM cpu WHERE region !~ /^us/").unwrap()
+        );
+        assert_eq!(
+            column("usage").lt(lit(0.5)),
+            expr("SELECT usage FROM cpu WHERE 0.5 > usage").unwrap()
+        );
+        assert_eq!(
+            lit(false),
+            expr("SELECT usage FROM cpu WHERE unknown = 'a'").unwrap()
+        );
+        assert!(expr("SELECT usage FROM cpu WHERE host =~ 'a'").is_err());
+        assert!(expr("SELECT usage FROM cpu WHERE host = now()").is_err());
+    }
+
+    #[test]
+    fn test_aggregated_series() {
+        let query = select_query(
+            "SELECT mean(usage) FROM cpu WHERE time >= 0 AND time < 180000000000 GROUP BY time(1m), host",
+        )
+        .unwrap();
+        let recordbatches = mock_recordbatches(
+            vec![
+                ("host", ConcreteDataType::string_datatype()),
+                ("ts", ConcreteDataType::timestamp_millisecond_datatype()),
+                ("mean", ConcreteDataType::float64_datatype()),
+            ],
+            vec![
+                Arc::new(StringVector::from(vec!["a", "a", "b"])) as _,
+                Arc::new(TimestampMillisecondVector::from_vec(vec![
+                    0, 120_000, 60_000,
+                ])) as _,
+                Arc::new(Float64Vector::from_vec(vec![1.0, 3.0, 2.0])) as _,
+            ],
+        );
+
+        let series = query.recordbatches_to_series(recordbatches, None).unwrap();
+        assert_eq!(
+            vec![
+                Series {
+                    name: "cpu".to_string(),
+                    tags: BTreeMap::from([("host".to_string(), "a".to_string())]),
+                    columns: vec!["time".to_string(), "mean".to_string()],
+                    values: vec![
+                        vec![json!("1970-01-01T00:00:00Z"), json!(1.0)],
+                        vec![json!("1970-01-01T00:01:00Z"), JsonValue::Null],
+                        vec![json!("1970-01-01T00:02:00Z"), json!(3.0)],
+                    ],
+                },
+                Series {
+                    name: "cpu".to_string(),
+                    tags: BTreeMap::from([("host".to_string(), "b".to_string())]),
+                    columns: vec!["time".to_string(), "mean".to_string()],
+                    values: vec![
+                        vec![json!("1970-01-01T00:00:00Z"), JsonValue::Null],
+                        vec![json!("1970-01-01T00:01:00Z"), json!(2.0)],
+                        vec![json!("1970-01-01T00:02:00Z"), JsonValue::Null],
+                    ],
+                },
+            ],
+            series
+        );
+    }
+
+    fn raw_recordbatches() -> RecordBatches {
+        mock_recordbatches(
+            vec![
+                ("ts", ConcreteDataType::timestamp_millisecond_datatype()),
+                ("usage", ConcreteDataType::float64_datatype()),
+            ],
+            vec![
+                Arc::new(TimestampMillisecondVector::from_vec(vec![
+                    0, 30_000, 60_000,
+                ])) as _,
+                Arc::new(Float64Vector::from_vec(vec![1.0, 2.0, 3.0])) as _,
+            ],
+        )
+    }
+
+    #[test]
+    fn test_last_series() {
+        let query =
+            select_query("SELECT last(usage), count(usage) FROM cpu GROUP BY time(1m) fill(none)")
+                .unwrap();
+        let series = query
+            .recordbatches_to_series(raw_recordbatches(), Some(Epoch::Millisecond))
+            .unwrap();
+        assert_eq!(1, series.len());
+        assert!(series[0].tags.is_empty());
+        assert_eq!(vec!["time", "last", "count"], series[0].columns);
+        assert_eq!(
+            vec![
+                vec![json!(0), json!(2.0), json!(2)],
+                vec![json!(60_000), json!(3.0), json!(1)],
+            ],
+            series[0].values
+        );
+    }
+
+    #[test]
+    fn test_raw_series() {
+        let query = select_query("SELECT usage AS u FROM cpu ORDER BY time DESC LIMIT 2").unwrap();
+        let series = query
+            .recordbatches_to_series(raw_recordbatches(), Some(Epoch::Second))
+            .unwrap();
+        assert_eq!(vec!["time", "u"], series[0].columns);
+        assert_eq!(
+            vec![vec![json!(60), json!(3.0)], vec![json!(30), json!(2.0)]],
+            series[0].values
+        );
+    }
+
+    #[test]
+    fn test_show() {
+        let mut mem = mock_table();
+        mem.measurement = "mem".to_string();
+        mem.tags = vec!["host".to_string()];
+        let tables = vec![mock_table(), mem];
+
+        let show = |sql: &str| influxql::parse(sql).unwrap().remove(0);
+
+        let Statement::ShowMeasurements(statement) = show("SHOW MEASUREMENTS") else {
+            unreachable!()
+        };
+        let series = show_measurements(&statement, &tables).unwrap();
+        assert_eq!(
+            vec![vec![json!("cpu")], vec![json!("mem")]],
+            series[0].values
+        );
+
+        let Statement::ShowMeasurements(statement) =
+            show("SHOW MEASUREMENTS WITH MEASUREMENT =~ /^m/")
+        else {
+            unreachable!()
+        };
+        let series = show_measurements(&statement, &tables).unwrap();
+        assert_eq!(vec![vec![json!("mem")]], series[0].values);
+
+        let Statement::ShowTagKeys(statement) = show("SHOW TAG KEYS FROM cpu") else {
+            unreachable!()
+        };
+        let series = show_tag_keys(&statement, &tables).unwrap();
+        assert_eq!(1, series.len());
+        assert_eq!(
+            vec![vec![json!("host")], vec![json!("region")]],
+            series[0].values
+        );
+
+        let Statement::ShowFieldKeys(statement) = show("SHOW FIELD KEYS LIMIT 1") else {
+            unreachable!()
+        };
+        let series = show_field_keys(&statement, &tables).unwrap();
+        assert_eq!(2, series.len());
+        assert_eq!(vec!["fieldKey", "fieldType"], series[0].columns);
+        assert_eq!(
+            vec![vec![json!("status"), json!("string")]],
+            series[0].values
+        );
+
+        let Statement::ShowTagKeys(statement) = show("SHOW TAG KEYS WHERE host = 'a'") else {
+            unreachable!()
+        };
+        assert!(show_tag_keys(&statement, &tables).is_err());
+    }
+
+    #[test]
+    fn test_recordbatches_to_tag_values() {
+        let recordbatches = mock_recordbatches(
+            vec![
+                ("host", ConcreteDataType::string_datatype()),
+                ("region", ConcreteDataType::string_datatype()),
+            ],
+            vec![
+                Arc::new(StringVector::from(vec![Some("b"), Some("a")])) as _,
+                Arc::new(StringVector::from(vec![Some("us"), None])) as _,
+            ],
+        );
+        let series =
+            recordbatches_to_tag_values(&mock_table(), &ShowStatement::default(), recordbatches)
+                .unwrap();
+        assert_eq!(vec!["key", "value"], series.columns);
+        assert_eq!(
+            vec![
+                vec![json!("host"), json!("a")],
+                vec![json!("host"), json!("b")],
+                vec![json!("region"), json!("us")],
+            ],
+            series.values
+        );
+    }
+}
@@ -18,6 +18,7 @@ use std::sync::Arc;
 use api::v1::greptime_request::Request;
 use common_error::ext::ErrorExt;
 use common_query::Output;
+use common_recordbatch::RecordBatch;
 use query::parser::PromQuery;
 use query::plan::LogicalPlan;
 use session::context::QueryContextRef;
@@ -143,6 +144,17 @@ pub trait GrpcQueryInterceptor {
         Ok(())
     }
 
+    /// Called before the record batch put by Flight `do_put` is written into table
+    /// `table_name`.
+    fn pre_put_record_batch(
+        &self,
+        _table_name: &str,
+        _record_batch: &RecordBatch,
+        _query_ctx: QueryContextRef,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     /// Called after execution finished. The implementation can modify the
     /// output if needed.
     fn post_execute(
@@ -175,6 +187,19 @@ where
         }
     }
 
+    fn pre_put_record_batch(
+        &self,
+        table_name: &str,
+        record_batch: &RecordBatch,
+        query_ctx: QueryContextRef,
+    ) -> Result<(), Self::Error> {
+        if let Some(this) = self {
+            this.pre_put_record_batch(table_name, record_batch, query_ctx)
+        } else {
+            Ok(())
+        }
+    }
+
     fn post_execute(
         &self,
         output: Output,
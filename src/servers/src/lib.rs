@@ -22,6 +22,7 @@ use serde::{Deserialize, Serialize};
 
 pub mod auth;
 pub mod configurator;
+pub mod elasticsearch;
 pub mod error;
 pub mod grpc;
 pub mod heartbeat_options;
@@ -29,10 +30,12 @@ pub mod http;
 pub mod influxdb;
 pub mod interceptor;
 pub mod line_writer;
+pub mod loki;
 mod metrics;
 pub mod metrics_handler;
 pub mod mysql;
 pub mod opentsdb;
+pub mod otlp;
 pub mod postgres;
 pub mod prom_store;
 pub mod prometheus;
@@ -0,0 +1,443 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Grafana Loki push API supports.
+//!
+//! Log entries of all streams are written to one table, with the stream labels
+//! as tags, the entry timestamp as the time index and the log line in the
+//! [LOG_MESSAGE_COLUMN_NAME] column. The time index keeps the nanosecond precision
+//! of Loki, so entries of the same stream inside one millisecond are all kept.
+
+use std::collections::BTreeMap;
+
+use api::v1::{InsertRequest as GrpcInsertRequest, InsertRequests};
+use common_grpc::writer::LinesWriter;
+use prost::Message;
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use snafu::{ensure, OptionExt, ResultExt};
+
+use crate::error::{self, Result};
+use crate::prom_store::snappy_decompress;
+
+/// The default table to write Loki logs to.
+pub const LOKI_TABLE_NAME: &str = "loki_logs";
+/// The time index column of log tables.
+pub const LOG_TIMESTAMP_COLUMN_NAME: &str = "greptime_timestamp";
+/// The column holding the log message of log tables.
+pub const LOG_MESSAGE_COLUMN_NAME: &str = "message";
+
+/// The `logproto.PushRequest` message of Loki.
+#[derive(Clone, PartialEq, Message)]
+pub struct PushRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub streams: Vec<StreamAdapter>,
+}
+
+/// The `logproto.StreamAdapter` message of Loki.
+#[derive(Clone, PartialEq, Message)]
+pub struct StreamAdapter {
+    /// Labels in the Prometheus selector format, e.g. `{job="app"}`.
+    #[prost(string, tag = "1")]
+    pub labels: String,
+    #[prost(message, repeated, tag = "2")]
+    pub entries: Vec<EntryAdapter>,
+    #[prost(uint64, tag = "3")]
+    pub hash: u64,
+}
+
+/// The `logproto.EntryAdapter` message of Loki.
+#[derive(Clone, PartialEq, Message)]
+pub struct EntryAdapter {
+    #[prost(message, optional, tag = "1")]
+    pub timestamp: Option<Timestamp>,
+    #[prost(string, tag = "2")]
+    pub line: String,
+}
+
+/// The `google.protobuf.Timestamp` message.
+#[derive(Clone, PartialEq, Message)]
+pub struct Timestamp {
+    #[prost(int64, tag = "1")]
+    pub seconds: i64,
+    #[prost(int32, tag = "2")]
+    pub nanos: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonPushRequest {
+    streams: Vec<JsonStream>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonStream {
+    #[serde(default)]
+    stream: BTreeMap<String, String>,
+    /// Entries in `[<unix epoch in nanoseconds>, <log line>]`, newer clients may
+    /// append structured metadata, which is ignored.
+    values: Vec<Vec<JsonValue>>,
+}
+
+/// A log stream, i.e. log entries sharing the same labels.
+#[derive(Debug, Default, PartialEq)]
+pub struct LokiStream {
+    pub labels: BTreeMap<String, String>,
+    pub entries: Vec<LokiEntry>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct LokiEntry {
+    /// Unix epoch in nanoseconds.
+    pub timestamp: i64,
+    pub line: String,
+}
+
+/// A Loki push request, decoded from either the snappy compressed protobuf or
+/// the JSON payload.
+#[derive(Debug, PartialEq)]
+pub struct LokiRequest {
+    pub table_name: String,
+    pub streams: Vec<LokiStream>,
+}
+
+impl LokiRequest {
+    /// Decodes the snappy compressed protobuf payload.
+    pub fn decode_protobuf(table_name: String, body: &[u8]) -> Result<Self> {
+        let buf = snappy_decompress(body)?;
+        let request = PushRequest::decode(&buf[..]).context(error::DecodeLokiRequestSnafu)?;
+
+        let streams = request
+            .streams
+            .into_iter()
+            .map(|stream| {
+                let labels = parse_labels(&stream.labels)?;
+                let entries = stream
+                    .entries
+                    .into_iter()
+                    .map(|entry| {
+                        let timestamp = entry
+                            .timestamp
+                            .map(|ts| ts.seconds * 1_000_000_000 + ts.nanos as i64)
+                            .unwrap_or_default();
+                        LokiEntry {
+                            timestamp,
+                            line: entry.line,
+                        }
+                    })
+                    .collect();
+                Ok(LokiStream { labels, entries })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            table_name,
+            streams,
+        })
+    }
+
+    /// Decodes the JSON payload.
+    pub fn decode_json(table_name: String, body: &[u8]) -> Result<Self> {
+        let request: JsonPushRequest =
+            serde_json::from_slice(body).map_err(|e| invalid_request(e.to_string()))?;
+
+        let streams = request
+            .streams
+            .into_iter()
+            .map(|stream| {
+                let entries = stream
+                    .values
+                    .into_iter()
+                    .map(|value| {
+                        let (Some(JsonValue::String(timestamp)), Some(JsonValue::String(line))) =
+                            (value.first(), value.get(1))
+                        else {
+                            return Err(invalid_request(format!("invalid entry: {value:?}")));
+                        };
+                        let timestamp = timestamp.parse::<i64>().map_err(|_| {
+                            invalid_request(format!("invalid timestamp: {timestamp}"))
+                        })?;
+                        Ok(LokiEntry {
+                            timestamp,
+                            line: line.clone(),
+                        })
+                    })
+                    .collect::<Result<_>>()?;
+                Ok(LokiStream {
+                    labels: stream.stream,
+                    entries,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            table_name,
+            streams,
+        })
+    }
+}
+
+fn invalid_request(reason: String) -> error::Error {
+    error::InvalidLokiRequestSnafu { reason }.build()
+}
+
+/// Parses labels in the Prometheus selector format, e.g. `{job="app", env="prod"}`.
+pub fn parse_labels(input: &str) -> Result<BTreeMap<String, String>> {
+    let input = input.trim();
+    let inner = input
+        .strip_prefix('{')
+        .and_then(|s| s.strip_suffix('}'))
+        .with_context(|| error::InvalidLokiRequestSnafu {
+            reason: format!("invalid labels: {input}"),
+        })?;
+
+    let mut labels = BTreeMap::new();
+    let mut chars = inner.chars().peekable();
+    loop {
+        while matches!(chars.peek(), Some(c) if c.is_whitespace() || *c == ',') {
+            let _ = chars.next();
+        }
+        if chars.peek().is_none() {
+            break;
+        }
+
+        let mut name = String::new();
+        while let Some(c) = chars.next_if(|c| *c != '=') {
+            name.push(c);
+        }
+        let name = name.trim().to_string();
+        ensure!(
+            !name.is_empty() && chars.next() == Some('='),
+            error::InvalidLokiRequestSnafu {
+                reason: format!("invalid labels: {input}"),
+            }
+        );
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        ensure!(
+            chars.next() == Some('"'),
+            error::InvalidLokiRequestSnafu {
+                reason: format!("label value of {name} is not quoted"),
+            }
+        );
+
+        let mut value = String::new();
+        let mut closed = false;
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => {
+                    closed = true;
+                    break;
+                }
+                '\\' => match chars.next() {
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some(c) => value.push(c),
+                    None => break,
+                },
+                c => value.push(c),
+            }
+        }
+        ensure!(
+            closed,
+            error::InvalidLokiRequestSnafu {
+                reason: format!("label value of {name} is not closed"),
+            }
+        );
+        let _ = labels.insert(name, value);
+    }
+    Ok(labels)
+}
+
+/// Converts a Loki push request into insert requests, returns the requests and
+/// the number of rows.
+pub fn to_grpc_insert_requests(request: LokiRequest) -> Result<(InsertRequests, usize)> {
+    let mut writer = LinesWriter::with_lines(
+        request
+            .streams
+            .iter()
+            .map(|stream| stream.entries.len())
+            .sum(),
+    );
+    for stream in &request.streams {
+        for entry in &stream.entries {
+            for (name, value) in &stream.labels {
+                writer
+                    .write_tag(name, value)
+                    .context(error::LogsWriteSnafu)?;
+            }
+            writer
+                .write_ts_nanosecond(LOG_TIMESTAMP_COLUMN_NAME, entry.timestamp)
+                .context(error::LogsWriteSnafu)?;
+            writer
+                .write_string(LOG_MESSAGE_COLUMN_NAME, &entry.line)
+                .context(error::LogsWriteSnafu)?;
+            writer.commit();
+        }
+    }
+
+    let (columns, row_count) = writer.finish();
+    let inserts = if row_count == 0 {
+        vec![]
+    } else {
+        vec![GrpcInsertRequest {
+            table_name: request.table_name,
+            region_number: 0,
+            columns,
+            row_count,
+        }]
+    };
+    Ok((InsertRequests { inserts }, row_count as usize))
+}
+
+#[cfg(test)]
+mod tests {
+    use api::v1::column::Values;
+    use api::v1::ColumnDataType;
+
+    use super::*;
+    use crate::prom_store::snappy_compress;
+
+    #[test]
+    fn test_parse_labels() {
+        let labels = parse_labels(r#"{job="app", env="prod", path="C:\\logs \"a\""}"#).unwrap();
+        assert_eq!(3, labels.len());
+        assert_eq!("app", labels["job"]);
+        assert_eq!("prod", labels["env"]);
+        assert_eq!(r#"C:\logs "a""#, labels["path"]);
+
+        assert!(parse_labels("{}").unwrap().is_empty());
+        assert!(parse_labels(r#"job="app""#).is_err());
+        assert!(parse_labels(r#"{job=app}"#).is_err());
+        assert!(parse_labels(r#"{job="app}"#).is_err());
+    }
+
+    #[test]
+    fn test_decode_protobuf() {
+        let request = PushRequest {
+            streams: vec![StreamAdapter {
+                labels: r#"{job="app"}"#.to_string(),
+                entries: vec![EntryAdapter {
+                    timestamp: Some(Timestamp {
+                        seconds: 1,
+                        nanos: 2,
+                    }),
+                    line: "hello".to_string(),
+                }],
+                hash: 0,
+            }],
+        };
+        let body = snappy_compress(&request.encode_to_vec()).unwrap();
+
+        let request = LokiRequest::decode_protobuf("logs".to_string(), &body).unwrap();
+        assert_eq!(
+            LokiRequest {
+                table_name: "logs".to_string(),
+                streams: vec![LokiStream {
+                    labels: BTreeMap::from([("job".to_string(), "app".to_string())]),
+                    entries: vec![LokiEntry {
+                        timestamp: 1_000_000_002,
+                        line: "hello".to_string(),
+                    }],
+                }],
+            },
+            request
+        );
+    }
+
+    #[test]
+    fn test_decode_json() {
+        let body = r#"{"streams":[{"stream":{"job":"app"},"values":[["1000000002","hello"],["2000000000","world",{"trace_id":"1"}]]}]}"#;
+        let request = LokiRequest::decode_json("logs".to_string(), body.as_bytes()).unwrap();
+        assert_eq!(1, request.streams.len());
+        assert_eq!(
+            vec![
+                LokiEntry {
+                    timestamp: 1_000_000_002,
+                    line: "hello".to_string(),
+                },
+                LokiEntry {
+                    timestamp: 2_000_000_000,
+                    line: "world".to_string(),
+                }
+            ],
+            request.streams[0].entries
+        );
+
+        let body = r#"{"streams":[{"stream":{"job":"app"},"values":[[1000000002,"hello"]]}]}"#;
+        assert!(LokiRequest::decode_json("logs".to_string(), body.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_to_grpc_insert_requests() {
+        let request = LokiRequest {
+            table_name: "logs".to_string(),
+            streams: vec![
+                LokiStream {
+                    labels: BTreeMap::from([("job".to_string(), "app".to_string())]),
+                    entries: vec![LokiEntry {
+                        timestamp: 1_000_000_000,
+                        line: "hello".to_string(),
+                    }],
+                },
+                LokiStream {
+                    labels: BTreeMap::from([("env".to_string(), "prod".to_string())]),
+                    entries: vec![LokiEntry {
+                        timestamp: 2_000_000_000,
+                        line: "world".to_string(),
+                    }],
+                },
+            ],
+        };
+
+        let (requests, rows) = to_grpc_insert_requests(request).unwrap();
+        assert_eq!(2, rows);
+        assert_eq!(1, requests.inserts.len());
+        let insert = &requests.inserts[0];
+        assert_eq!("logs", insert.table_name);
+        let mut names = insert
+            .columns
+            .iter()
+            .map(|c| c.column_name.as_str())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(
+            vec![
+                "env",
+                LOG_TIMESTAMP_COLUMN_NAME,
+                "job",
+                LOG_MESSAGE_COLUMN_NAME
+            ],
+            names
+        );
+        let message = insert
+            .columns
+            .iter()
+            .find(|c| c.column_name == LOG_MESSAGE_COLUMN_NAME)
+            .unwrap();
+        let Some(Values { string_values, .. }) = message.values.as_ref() else {
+            unreachable!()
+        };
+        assert_eq!(
+            &vec!["hello".to_string(), "world".to_string()],
+            string_values
+        );
+        let ts = insert
+            .columns
+            .iter()
+            .find(|c| c.column_name == LOG_TIMESTAMP_COLUMN_NAME)
+            .unwrap();
+        assert_eq!(ColumnDataType::TimestampNanosecond as i32, ts.datatype);
+        assert_eq!(
+            vec![1_000_000_000, 2_000_000_000],
+            ts.values.as_ref().unwrap().ts_nanosecond_values
+        );
+    }
+}
@@ -40,6 +40,12 @@ pub(crate) const METRIC_HTTP_SQL_ELAPSED: &str = "servers.http_sql_elapsed";
 pub(crate) const METRIC_HTTP_PROMQL_ELAPSED: &str = "servers.http_promql_elapsed";
 pub(crate) const METRIC_AUTH_FAILURE: &str = "servers.auth_failure_count";
 pub(crate) const METRIC_HTTP_INFLUXDB_WRITE_ELAPSED: &str = "servers.http_influxdb_write_elapsed";
+pub(crate) const METRIC_HTTP_INFLUXDB_QUERY_ELAPSED: &str = "servers.http_influxdb_query_elapsed";
+pub(crate) const METRIC_HTTP_OTLP_METRICS_ELAPSED: &str = "servers.http_otlp_metrics_elapsed";
+pub(crate) const METRIC_HTTP_LOKI_PUSH_ELAPSED: &str = "servers.http_loki_push_elapsed";
+pub(crate) const METRIC_HTTP_ELASTICSEARCH_BULK_ELAPSED: &str =
+    "servers.http_elasticsearch_bulk_elapsed";
+pub(crate) const METRIC_GRPC_OTLP_METRICS_ELAPSED: &str = "servers.grpc_otlp_metrics_elapsed";
 pub(crate) const METRIC_HTTP_PROM_STORE_WRITE_ELAPSED: &str =
     "servers.http_prometheus_write_elapsed";
 pub(crate) const METRIC_HTTP_PROM_STORE_READ_ELAPSED: &str = "servers.http_prometheus_read_elapsed";
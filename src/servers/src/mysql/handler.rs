@@ -46,10 +46,15 @@ use crate::mysql::helper::{
     self, format_placeholder, replace_placeholders, transform_placeholders,
 };
 use crate::mysql::writer;
-use crate::mysql::writer::create_mysql_column;
+use crate::mysql::writer::{create_mysql_column, RowFormat};
 use crate::query_handler::sql::ServerSqlQueryHandlerRef;
 use crate::SqlPlan;
 
+/// Max number of prepared statements a connection can hold, same as the default
+/// `max_prepared_stmt_count` of MySQL. Clients should close the statements they
+/// no longer use.
+const MAX_PREPARED_STMTS: usize = 16382;
+
 // An intermediate shim for executing MySQL queries.
 pub struct MysqlInstanceShim {
     query_handler: ServerSqlQueryHandlerRef,
@@ -151,6 +156,12 @@ impl<W: AsyncWrite + Send + Sync + Unpin> AsyncMysqlShim<W> for MysqlInstanceShi
         self.salt
     }
 
+    /// The connection id is also the id of its queries in the process list, so that
+    /// clients can stop the running query by `KILL QUERY <connection id>`.
+    fn connect_id(&self) -> u32 {
+        self.session.context().connection_id().unwrap_or_default()
+    }
+
     async fn authenticate(
         &self,
         auth_plugin: &str,
@@ -200,6 +211,20 @@ impl<W: AsyncWrite + Send + Sync + Unpin> AsyncMysqlShim<W> for MysqlInstanceShi
         raw_query: &'a str,
         w: StatementMetaWriter<'a, W>,
     ) -> Result<()> {
+        let prepared_stmts_num = self.prepared_stmts.read().len();
+        if prepared_stmts_num >= MAX_PREPARED_STMTS {
+            let err = error::TooManyPreparedStmtsSnafu {
+                max: MAX_PREPARED_STMTS,
+            }
+            .build();
+            w.error(
+                ErrorKind::ER_MAX_PREPARED_STMT_COUNT_REACHED,
+                err.to_string().as_bytes(),
+            )
+            .await?;
+            return Ok(());
+        }
+
         let (query, param_num) = replace_placeholders(raw_query);
 
         let statement = validate_query(raw_query).await?;
@@ -307,7 +332,14 @@ impl<W: AsyncWrite + Send + Sync + Unpin> AsyncMysqlShim<W> for MysqlInstanceShi
             }
         };
 
-        writer::write_output(w, &query, self.session.context(), outputs).await?;
+        writer::write_output(
+            w,
+            &query,
+            self.session.context(),
+            outputs,
+            RowFormat::Binary,
+        )
+        .await?;
 
         Ok(())
     }
@@ -317,7 +349,9 @@ impl<W: AsyncWrite + Send + Sync + Unpin> AsyncMysqlShim<W> for MysqlInstanceShi
         W: 'async_trait,
     {
         let mut guard = self.prepared_stmts.write();
-        let _ = guard.remove(&stmt_id);
+        if guard.remove(&stmt_id).is_none() {
+            trace!("Closing unknown prepared statement: {}", stmt_id);
+        }
     }
 
     async fn on_query<'a>(
@@ -339,7 +373,14 @@ impl<W: AsyncWrite + Send + Sync + Unpin> AsyncMysqlShim<W> for MysqlInstanceShi
             ]
         );
         let outputs = self.do_query(query).await;
-        writer::write_output(writer, query, self.session.context(), outputs).await?;
+        writer::write_output(
+            writer,
+            query,
+            self.session.context(),
+            outputs,
+            RowFormat::Text,
+        )
+        .await?;
         Ok(())
     }
 
@@ -422,11 +463,12 @@ fn replace_params_with_values(
     let mut values = Vec::with_capacity(params.len());
 
     for (i, param) in params.iter().enumerate() {
-        if let Some(Some(t)) = param_types.get(&format_placeholder(i + 1)) {
-            let value = helper::convert_value(param, t)?;
-
-            values.push(value);
-        }
+        let value = match param_types.get(&format_placeholder(i + 1)) {
+            Some(Some(t)) => helper::convert_value(param, t)?,
+            // keep the parameter's own type if it can't be inferred from the plan
+            _ => helper::convert_value(param, &helper::param_value_type(param))?,
+        };
+        values.push(value);
     }
 
     plan.replace_params_with_values(&values)
@@ -16,12 +16,14 @@ use std::time::Duration;
 
 use chrono::{NaiveDate, NaiveDateTime};
 use common_query::prelude::ScalarValue;
+use common_time::Timestamp;
 use datatypes::prelude::ConcreteDataType;
 use datatypes::value::{self, Value};
 use itertools::Itertools;
 use opensrv_mysql::{ParamValue, ValueInner};
 use snafu::ResultExt;
 use sql::ast::{visit_expressions_mut, Expr, Value as ValueExpr, VisitMut};
+use sql::statements::sql_value_to_value;
 use sql::statements::statement::Statement;
 
 use crate::error::{self, Result};
@@ -159,26 +161,74 @@ pub fn convert_value(param: &ParamValue, t: &ConcreteDataType) -> Result<ScalarV
                 String::from_utf8_lossy(b).to_string(),
             ))),
             ConcreteDataType::Binary(_) => Ok(ScalarValue::LargeBinary(Some(b.to_vec()))),
-
-            _ => error::PreparedStmtTypeMismatchSnafu {
-                expected: t,
-                actual: param.coltype,
+            // Drivers may send parameters of any type as strings, e.g. JDBC's `setString`
+            // or timestamps formatted by the client.
+            _ => {
+                let s = String::from_utf8_lossy(b).trim().to_string();
+                let sql_value = if t.is_boolean() {
+                    ValueExpr::Boolean(matches!(s.to_ascii_lowercase().as_str(), "1" | "true"))
+                } else if t.is_signed() || t.is_unsigned() || t.is_float() {
+                    ValueExpr::Number(s, false)
+                } else {
+                    ValueExpr::SingleQuotedString(s)
+                };
+                sql_value_to_value("", t, &sql_value)
+                    .context(error::ParsePreparedStmtParamSnafu)?
+                    .try_to_scalar_value(t)
+                    .context(error::ConvertScalarValueSnafu)
             }
-            .fail(),
         },
         ValueInner::Date(_) => {
-            let date: common_time::Date = NaiveDate::from(param.value).into();
-            Ok(ScalarValue::Date32(Some(date.val())))
+            let date = NaiveDate::from(param.value);
+            match t {
+                ConcreteDataType::Timestamp(_) | ConcreteDataType::DateTime(_) => {
+                    datetime_to_scalar_value(date.and_hms_opt(0, 0, 0).unwrap(), t)
+                }
+                _ => {
+                    let date: common_time::Date = date.into();
+                    Ok(ScalarValue::Date32(Some(date.val())))
+                }
+            }
         }
-        ValueInner::Datetime(_) => Ok(ScalarValue::Date64(Some(
-            NaiveDateTime::from(param.value).timestamp_millis(),
-        ))),
+        ValueInner::Datetime(_) => datetime_to_scalar_value(NaiveDateTime::from(param.value), t),
         ValueInner::Time(_) => Ok(ScalarValue::Time64Nanosecond(Some(
             Duration::from(param.value).as_millis() as i64,
         ))),
     }
 }
 
+/// Converts a datetime parameter, which is taken as UTC, to the param type.
+fn datetime_to_scalar_value(datetime: NaiveDateTime, t: &ConcreteDataType) -> Result<ScalarValue> {
+    match t {
+        ConcreteDataType::Timestamp(ts_type) => {
+            let ts = Timestamp::new_microsecond(datetime.timestamp_micros());
+            let ts = ts.convert_to(ts_type.unit()).unwrap_or(ts);
+            Value::Timestamp(ts)
+                .try_to_scalar_value(t)
+                .context(error::ConvertScalarValueSnafu)
+        }
+        ConcreteDataType::String(_) => Ok(ScalarValue::Utf8(Some(
+            datetime.format("%Y-%m-%d %H:%M:%S%.f").to_string(),
+        ))),
+        _ => Ok(ScalarValue::Date64(Some(datetime.timestamp_millis()))),
+    }
+}
+
+/// Returns the datatype a parameter is converted to when its type can't be
+/// inferred from the plan, e.g. `SELECT ?`.
+pub fn param_value_type(param: &ParamValue) -> ConcreteDataType {
+    match param.value.into_inner() {
+        ValueInner::Int(_) => ConcreteDataType::int64_datatype(),
+        ValueInner::UInt(_) => ConcreteDataType::uint64_datatype(),
+        ValueInner::Double(_) => ConcreteDataType::float64_datatype(),
+        ValueInner::NULL => ConcreteDataType::null_datatype(),
+        ValueInner::Bytes(_) => ConcreteDataType::string_datatype(),
+        ValueInner::Date(_) => ConcreteDataType::date_datatype(),
+        ValueInner::Datetime(_) => ConcreteDataType::datetime_datatype(),
+        ValueInner::Time(_) => ConcreteDataType::int64_datatype(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use sql::dialect::MySqlDialect;
@@ -235,4 +285,35 @@ mod tests {
         let Statement::Query(select) = transform_placeholders(select) else { unreachable!()};
         assert_eq!("SELECT from AS demo WHERE host = $1 AND idc IN (SELECT idc FROM idcs WHERE name = $2) AND cpu > $3", select.inner.to_string());
     }
+
+    #[test]
+    fn test_datetime_to_scalar_value() {
+        let datetime = NaiveDate::from_ymd_opt(2023, 6, 1)
+            .unwrap()
+            .and_hms_milli_opt(10, 20, 30, 123)
+            .unwrap();
+        let millis = datetime.timestamp_millis();
+
+        assert_eq!(
+            ScalarValue::TimestampMillisecond(Some(millis), None),
+            datetime_to_scalar_value(
+                datetime,
+                &ConcreteDataType::timestamp_millisecond_datatype()
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            ScalarValue::TimestampNanosecond(Some(millis * 1_000_000), None),
+            datetime_to_scalar_value(datetime, &ConcreteDataType::timestamp_nanosecond_datatype())
+                .unwrap()
+        );
+        assert_eq!(
+            ScalarValue::Date64(Some(millis)),
+            datetime_to_scalar_value(datetime, &ConcreteDataType::datetime_datatype()).unwrap()
+        );
+        assert_eq!(
+            ScalarValue::Utf8(Some("2023-06-01 10:20:30.123".to_string())),
+            datetime_to_scalar_value(datetime, &ConcreteDataType::string_datatype()).unwrap()
+        );
+    }
 }
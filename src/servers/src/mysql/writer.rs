@@ -30,14 +30,24 @@ use tokio::io::AsyncWrite;
 use crate::error::{self, Error, Result};
 use crate::metrics::*;
 
+/// Encoding of rows in a result set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowFormat {
+    /// Text protocol, used by `COM_QUERY`.
+    Text,
+    /// Binary protocol, used by `COM_STMT_EXECUTE`.
+    Binary,
+}
+
 /// Try to write multiple output to the writer if possible.
 pub async fn write_output<'a, W: AsyncWrite + Send + Sync + Unpin>(
     w: QueryResultWriter<'a, W>,
     query: &str,
     query_context: QueryContextRef,
     outputs: Vec<Result<Output>>,
+    row_format: RowFormat,
 ) -> Result<()> {
-    let mut writer = Some(MysqlResultWriter::new(w, query_context.clone()));
+    let mut writer = Some(MysqlResultWriter::new(w, query_context.clone(), row_format));
     for output in outputs {
         let result_writer = writer.take().context(error::InternalSnafu {
             err_msg: "Sending multiple result set is unsupported",
@@ -59,16 +69,19 @@ struct QueryResult {
 pub struct MysqlResultWriter<'a, W: AsyncWrite + Unpin> {
     writer: QueryResultWriter<'a, W>,
     query_context: QueryContextRef,
+    row_format: RowFormat,
 }
 
 impl<'a, W: AsyncWrite + Unpin> MysqlResultWriter<'a, W> {
     pub fn new(
         writer: QueryResultWriter<'a, W>,
         query_context: QueryContextRef,
+        row_format: RowFormat,
     ) -> MysqlResultWriter<'a, W> {
         MysqlResultWriter::<'a, W> {
             writer,
             query_context,
+            row_format,
         }
     }
 
@@ -91,22 +104,35 @@ impl<'a, W: AsyncWrite + Unpin> MysqlResultWriter<'a, W> {
                         recordbatches,
                         schema,
                     };
-                    Self::write_query_result(query, query_result, self.writer, self.query_context)
-                        .await?;
+                    Self::write_query_result(
+                        query,
+                        query_result,
+                        self.writer,
+                        self.query_context,
+                        self.row_format,
+                    )
+                    .await?;
                 }
                 Output::RecordBatches(recordbatches) => {
                     let query_result = QueryResult {
                         schema: recordbatches.schema(),
                         recordbatches: recordbatches.take(),
                     };
-                    Self::write_query_result(query, query_result, self.writer, self.query_context)
-                        .await?;
+                    Self::write_query_result(
+                        query,
+                        query_result,
+                        self.writer,
+                        self.query_context,
+                        self.row_format,
+                    )
+                    .await?;
                 }
                 Output::AffectedRows(rows) => {
                     let next_writer = Self::write_affected_rows(self.writer, rows).await?;
                     return Ok(Some(MysqlResultWriter::new(
                         next_writer,
                         self.query_context,
+                        self.row_format,
                     )));
                 }
             },
@@ -139,6 +165,7 @@ impl<'a, W: AsyncWrite + Unpin> MysqlResultWriter<'a, W> {
         query_result: QueryResult,
         writer: QueryResultWriter<'a, W>,
         query_context: QueryContextRef,
+        row_format: RowFormat,
     ) -> Result<()> {
         match create_mysql_column_def(&query_result.schema) {
             Ok(column_def) => {
@@ -146,8 +173,13 @@ impl<'a, W: AsyncWrite + Unpin> MysqlResultWriter<'a, W> {
                 // to return a new QueryResultWriter.
                 let mut row_writer = writer.start(&column_def).await?;
                 for recordbatch in &query_result.recordbatches {
-                    Self::write_recordbatch(&mut row_writer, recordbatch, query_context.clone())
-                        .await?;
+                    Self::write_recordbatch(
+                        &mut row_writer,
+                        recordbatch,
+                        query_context.clone(),
+                        row_format,
+                    )
+                    .await?;
                 }
                 row_writer.finish().await?;
                 Ok(())
@@ -160,6 +192,7 @@ impl<'a, W: AsyncWrite + Unpin> MysqlResultWriter<'a, W> {
         row_writer: &mut RowWriter<'_, W>,
         recordbatch: &RecordBatch,
         query_context: QueryContextRef,
+        row_format: RowFormat,
     ) -> Result<()> {
         for row in recordbatch.rows() {
             for value in row.into_iter() {
@@ -180,16 +213,15 @@ impl<'a, W: AsyncWrite + Unpin> MysqlResultWriter<'a, W> {
                     Value::Binary(v) => row_writer.write_col(v.deref())?,
                     Value::Date(v) => row_writer.write_col(v.to_chrono_date())?,
                     Value::DateTime(v) => row_writer.write_col(v.to_chrono_datetime())?,
-                    Value::Timestamp(v) => row_writer
-                        .write_col(v.to_timezone_aware_string(query_context.time_zone()))?,
-                    Value::List(_) => {
-                        return Err(Error::Internal {
-                            err_msg: format!(
-                                "cannot write value {:?} in mysql protocol: unimplemented",
-                                &value
-                            ),
-                        })
-                    }
+                    Value::Timestamp(v) => match row_format {
+                        RowFormat::Text => row_writer
+                            .write_col(v.to_timezone_aware_string(query_context.time_zone()))?,
+                        // binary protocol encodes timestamps as datetime structs
+                        RowFormat::Binary => row_writer.write_col(
+                            v.to_timezone_aware_chrono_datetime(query_context.time_zone()),
+                        )?,
+                    },
+                    Value::List(_) => row_writer.write_col(value.to_string())?,
                 }
             }
             row_writer.end_row().await?;
@@ -219,29 +251,23 @@ pub(crate) fn create_mysql_column(
     column_name: &str,
 ) -> Result<Column> {
     let column_type = match data_type {
-        ConcreteDataType::Null(_) => Ok(ColumnType::MYSQL_TYPE_NULL),
+        ConcreteDataType::Null(_) => ColumnType::MYSQL_TYPE_NULL,
         ConcreteDataType::Boolean(_) | ConcreteDataType::Int8(_) | ConcreteDataType::UInt8(_) => {
-            Ok(ColumnType::MYSQL_TYPE_TINY)
-        }
-        ConcreteDataType::Int16(_) | ConcreteDataType::UInt16(_) => {
-            Ok(ColumnType::MYSQL_TYPE_SHORT)
-        }
-        ConcreteDataType::Int32(_) | ConcreteDataType::UInt32(_) => Ok(ColumnType::MYSQL_TYPE_LONG),
-        ConcreteDataType::Int64(_) | ConcreteDataType::UInt64(_) => {
-            Ok(ColumnType::MYSQL_TYPE_LONGLONG)
-        }
-        ConcreteDataType::Float32(_) => Ok(ColumnType::MYSQL_TYPE_FLOAT),
-        ConcreteDataType::Float64(_) => Ok(ColumnType::MYSQL_TYPE_DOUBLE),
-        ConcreteDataType::Binary(_) | ConcreteDataType::String(_) => {
-            Ok(ColumnType::MYSQL_TYPE_VARCHAR)
+            ColumnType::MYSQL_TYPE_TINY
         }
-        ConcreteDataType::Timestamp(_) => Ok(ColumnType::MYSQL_TYPE_TIMESTAMP),
-        ConcreteDataType::Date(_) => Ok(ColumnType::MYSQL_TYPE_DATE),
-        ConcreteDataType::DateTime(_) => Ok(ColumnType::MYSQL_TYPE_DATETIME),
-        _ => error::InternalSnafu {
-            err_msg: format!("not implemented for column datatype {:?}", data_type),
+        ConcreteDataType::Int16(_) | ConcreteDataType::UInt16(_) => ColumnType::MYSQL_TYPE_SHORT,
+        ConcreteDataType::Int32(_) | ConcreteDataType::UInt32(_) => ColumnType::MYSQL_TYPE_LONG,
+        ConcreteDataType::Int64(_) | ConcreteDataType::UInt64(_) => ColumnType::MYSQL_TYPE_LONGLONG,
+        ConcreteDataType::Float32(_) => ColumnType::MYSQL_TYPE_FLOAT,
+        ConcreteDataType::Float64(_) => ColumnType::MYSQL_TYPE_DOUBLE,
+        ConcreteDataType::Binary(_) | ConcreteDataType::String(_) => ColumnType::MYSQL_TYPE_VARCHAR,
+        ConcreteDataType::Timestamp(_) => ColumnType::MYSQL_TYPE_TIMESTAMP,
+        ConcreteDataType::Date(_) => ColumnType::MYSQL_TYPE_DATE,
+        ConcreteDataType::DateTime(_) => ColumnType::MYSQL_TYPE_DATETIME,
+        // lists and dictionaries are written in their string form
+        ConcreteDataType::List(_) | ConcreteDataType::Dictionary(_) => {
+            ColumnType::MYSQL_TYPE_VARCHAR
         }
-        .fail(),
     };
     let mut colflags = ColumnFlags::empty();
     match data_type {
@@ -251,7 +277,7 @@ pub(crate) fn create_mysql_column(
         | ConcreteDataType::UInt64(_) => colflags |= ColumnFlags::UNSIGNED_FLAG,
         _ => {}
     };
-    column_type.map(|column_type| Column {
+    Ok(Column {
         column: column_name.to_string(),
         coltype: column_type,
 
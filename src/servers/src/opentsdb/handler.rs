@@ -119,6 +119,9 @@ mod tests {
 
     use super::*;
     use crate::error;
+    use crate::opentsdb::query::{
+        LookupRequest, LookupResult, QueryRequest, QueryResponse, SuggestRequest,
+    };
     use crate::query_handler::OpentsdbProtocolHandler;
 
     struct DummyQueryHandler {
@@ -138,6 +141,30 @@ mod tests {
             self.tx.send(metric.to_string()).await.unwrap();
             Ok(())
         }
+
+        async fn query(
+            &self,
+            _request: QueryRequest,
+            _ctx: QueryContextRef,
+        ) -> Result<Vec<QueryResponse>> {
+            unimplemented!()
+        }
+
+        async fn suggest(
+            &self,
+            _request: SuggestRequest,
+            _ctx: QueryContextRef,
+        ) -> Result<Vec<String>> {
+            unimplemented!()
+        }
+
+        async fn lookup(
+            &self,
+            _request: LookupRequest,
+            _ctx: QueryContextRef,
+        ) -> Result<Vec<LookupResult>> {
+            unimplemented!()
+        }
     }
 
     #[tokio::test]
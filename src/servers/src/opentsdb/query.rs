@@ -0,0 +1,1543 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The read path of OpenTSDB HTTP APIs: `/api/query`, `/api/suggest` and
+//! `/api/search/lookup`.
+//!
+//! Sub queries are translated into logical plans over the tables created by the
+//! write path, where each metric is a table, each tag is a string column, and the
+//! values are stored in [OPENTSDB_FIELD_COLUMN_NAME]. The filtering and the
+//! downsampling are done by the query engine, while the rate and the aggregation
+//! across series are done on the query results.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use common_recordbatch::RecordBatches;
+use common_time::timestamp::TimeUnit;
+use datafusion::arrow::datatypes::{DataType, TimeUnit as ArrowTimeUnit};
+use datafusion::common::Column;
+use datafusion::prelude::{
+    avg, cast, count, lit, lower, max, min, regexp_match, starts_with, sum,
+    DataFrame as DfDataFrame, Expr,
+};
+use datatypes::prelude::Value;
+use query::dataframe::DataFrame;
+use query::plan::LogicalPlan;
+use serde::{Deserialize, Serialize};
+use snafu::{ensure, OptionExt, ResultExt};
+
+use crate::error::{self, Result};
+use crate::opentsdb::codec::{
+    DataPoint, OPENTSDB_FIELD_COLUMN_NAME, OPENTSDB_TIMESTAMP_COLUMN_NAME,
+};
+
+/// Upper limit of data points a series can be filled to by downsampling.
+const MAX_FILL_POINTS: i64 = 100_000;
+
+/// A time in a query, could be an absolute timestamp in seconds or milliseconds,
+/// a relative time like `1h-ago`, or a date like `2023/07/01-12:00:00`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TimeValue {
+    Number(i64),
+    String(String),
+}
+
+/// The body of `/api/query`.
+///
+/// Please refer to the OpenTSDB documents of ["api/query"](http://opentsdb.net/docs/build/html/api_http/query/index.html)
+/// for more details.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryRequest {
+    pub start: TimeValue,
+    #[serde(default)]
+    pub end: Option<TimeValue>,
+    pub queries: Vec<SubQuery>,
+    #[serde(default)]
+    pub ms_resolution: bool,
+}
+
+impl QueryRequest {
+    /// Parses the query from the parameters of a `GET` request, where each `m`
+    /// parameter is a sub query.
+    pub fn try_from_params(params: &[(String, String)]) -> Result<Self> {
+        let mut start = None;
+        let mut end = None;
+        let mut queries = vec![];
+        let mut ms_resolution = false;
+        for (key, value) in params {
+            match key.as_str() {
+                "start" => start = Some(TimeValue::String(value.clone())),
+                "end" => end = Some(TimeValue::String(value.clone())),
+                "m" => queries.push(SubQuery::parse(value)?),
+                "ms" | "msResolution" => ms_resolution = value.is_empty() || value == "true",
+                _ => {}
+            }
+        }
+
+        let start = start.context(error::InvalidOpentsdbQuerySnafu {
+            reason: "missing parameter 'start'",
+        })?;
+        Ok(Self {
+            start,
+            end,
+            queries,
+            ms_resolution,
+        })
+    }
+
+    /// Returns the time range of the query in milliseconds, both ends inclusive.
+    pub fn time_range(&self, now_millis: i64) -> Result<(i64, i64)> {
+        let start = parse_time(&self.start, now_millis)?;
+        let end = match &self.end {
+            Some(end) => parse_time(end, now_millis)?,
+            None => now_millis,
+        };
+        ensure!(
+            start <= end,
+            error::InvalidOpentsdbQuerySnafu {
+                reason: format!("start time {start} is after end time {end}"),
+            }
+        );
+        Ok((start, end))
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubQuery {
+    pub aggregator: String,
+    pub metric: String,
+    #[serde(default)]
+    pub downsample: Option<String>,
+    #[serde(default)]
+    pub rate: bool,
+    #[serde(default)]
+    pub rate_options: RateOptions,
+    /// Tags in the form of OpenTSDB 2.1 and before, they are converted to group by
+    /// filters.
+    #[serde(default)]
+    pub tags: HashMap<String, String>,
+    #[serde(default)]
+    pub filters: Vec<Filter>,
+}
+
+impl SubQuery {
+    /// Parses a sub query in the form of
+    /// `aggregator:[rate[{counter[,max[,reset]]}]:][downsample:]metric[{group by filters}][{filters}]`.
+    pub fn parse(m: &str) -> Result<Self> {
+        let (head, groups) = split_brace_groups(m)?;
+        ensure!(
+            groups.len() <= 2,
+            error::InvalidOpentsdbQuerySnafu {
+                reason: format!("too many filter groups in '{m}'"),
+            }
+        );
+
+        let mut parts = head.split(':').collect::<Vec<_>>();
+        ensure!(
+            parts.len() >= 2,
+            error::InvalidOpentsdbQuerySnafu {
+                reason: format!("expect at least an aggregator and a metric in '{m}'"),
+            }
+        );
+        let metric = parts.pop().unwrap().to_string();
+        let aggregator = parts.remove(0).to_string();
+
+        let mut query = SubQuery {
+            aggregator,
+            metric,
+            ..Default::default()
+        };
+        for part in parts {
+            if let Some(options) = part.strip_prefix("rate") {
+                query.rate = true;
+                query.rate_options = RateOptions::parse(options)?;
+            } else {
+                query.downsample = Some(part.to_string());
+            }
+        }
+
+        for (idx, group) in groups.iter().enumerate() {
+            for filter in group.split(',').filter(|s| !s.is_empty()) {
+                let (tagk, tagv) =
+                    filter
+                        .split_once('=')
+                        .context(error::InvalidOpentsdbQuerySnafu {
+                            reason: format!("invalid filter '{filter}'"),
+                        })?;
+                query
+                    .filters
+                    .push(Filter::parse(tagk.trim(), tagv.trim(), idx == 0));
+            }
+        }
+
+        Ok(query)
+    }
+
+    /// Returns the filters, including the ones converted from the legacy tags.
+    fn all_filters(&self) -> Vec<Filter> {
+        let mut filters = self.filters.clone();
+        filters.extend(
+            self.tags
+                .iter()
+                .map(|(tagk, tagv)| Filter::parse(tagk, tagv, true)),
+        );
+        filters
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateOptions {
+    #[serde(default)]
+    pub counter: bool,
+    #[serde(default)]
+    pub counter_max: Option<f64>,
+    #[serde(default)]
+    pub reset_value: Option<f64>,
+    #[serde(default)]
+    pub drop_resets: bool,
+}
+
+impl RateOptions {
+    /// Parses the options in `rate{counter[,max[,reset]]}`, the input is the part
+    /// after `rate`.
+    fn parse(options: &str) -> Result<Self> {
+        if options.is_empty() {
+            return Ok(Self::default());
+        }
+        let options = options
+            .strip_prefix('{')
+            .and_then(|s| s.strip_suffix('}'))
+            .context(error::InvalidOpentsdbQuerySnafu {
+                reason: format!("invalid rate options 'rate{options}'"),
+            })?;
+
+        let parse_number = |s: &str| -> Result<Option<f64>> {
+            if s.is_empty() {
+                return Ok(None);
+            }
+            s.parse::<f64>()
+                .ok()
+                .map(Some)
+                .context(error::InvalidOpentsdbQuerySnafu {
+                    reason: format!("invalid number '{s}' in rate options"),
+                })
+        };
+
+        let mut parts = options.split(',').map(str::trim);
+        Ok(Self {
+            counter: parts.next() == Some("counter"),
+            counter_max: parse_number(parts.next().unwrap_or_default())?,
+            reset_value: parse_number(parts.next().unwrap_or_default())?,
+            drop_resets: false,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Filter {
+    #[serde(rename = "type")]
+    pub filter_type: String,
+    pub tagk: String,
+    pub filter: String,
+    #[serde(default)]
+    pub group_by: bool,
+}
+
+impl Filter {
+    /// Parses a filter value, either in the form of `type(expression)`, or a plain
+    /// value which is a wildcard if it contains `*`, or else a `|` separated list
+    /// of literals.
+    fn parse(tagk: &str, tagv: &str, group_by: bool) -> Self {
+        let (filter_type, filter) = match tagv.split_once('(') {
+            Some((filter_type, rest)) if rest.ends_with(')') => {
+                (filter_type, &rest[..rest.len() - 1])
+            }
+            _ if tagv.contains('*') => ("wildcard", tagv),
+            _ => ("literal_or", tagv),
+        };
+        Self {
+            filter_type: filter_type.to_string(),
+            tagk: tagk.to_string(),
+            filter: filter.to_string(),
+            group_by,
+        }
+    }
+
+    fn to_expr(&self) -> Result<Expr> {
+        let column = column(&self.tagk);
+        let literals = |lowercase: bool| -> Vec<Expr> {
+            self.filter
+                .split('|')
+                .map(|v| {
+                    if lowercase {
+                        lit(v.to_lowercase())
+                    } else {
+                        lit(v)
+                    }
+                })
+                .collect()
+        };
+
+        let expr = match self.filter_type.as_str() {
+            "literal_or" => column.in_list(literals(false), false),
+            "iliteral_or" => lower(column).in_list(literals(true), false),
+            "not_literal_or" => column.in_list(literals(false), true),
+            "not_iliteral_or" => lower(column).in_list(literals(true), true),
+            "wildcard" => wildcard_to_expr(column, &self.filter, false),
+            "iwildcard" => wildcard_to_expr(column, &self.filter, true),
+            "regexp" => regexp_match(vec![column, lit(self.filter.as_str())]).is_not_null(),
+            other => {
+                return error::InvalidOpentsdbQuerySnafu {
+                    reason: format!("unsupported filter type '{other}'"),
+                }
+                .fail()
+            }
+        };
+        Ok(expr)
+    }
+}
+
+fn wildcard_to_expr(column: Expr, wildcard: &str, case_insensitive: bool) -> Expr {
+    if wildcard.chars().all(|c| c == '*') {
+        return column.is_not_null();
+    }
+
+    let mut pattern = String::with_capacity(wildcard.len());
+    for c in wildcard.chars() {
+        match c {
+            '*' => pattern.push('%'),
+            '%' | '_' | '\\' => {
+                pattern.push('\\');
+                pattern.push(c);
+            }
+            _ => pattern.push(c),
+        }
+    }
+    if case_insensitive {
+        column.ilike(lit(pattern))
+    } else {
+        column.like(lit(pattern))
+    }
+}
+
+/// Splits `head{a}{b}` into `head` and the contents of the groups in braces.
+fn split_brace_groups(s: &str) -> Result<(&str, Vec<&str>)> {
+    let Some(brace) = s.find('{') else { return Ok((s, vec![])) };
+    let (head, mut rest) = s.split_at(brace);
+    // The braces of the rate options are part of the head.
+    if head.ends_with("rate") {
+        let close = rest.find('}').context(error::InvalidOpentsdbQuerySnafu {
+            reason: format!("unclosed brace in '{s}'"),
+        })?;
+        let rate_end = brace + close + 1;
+        let (head, groups) = split_brace_groups(&s[rate_end..])?;
+        return Ok((&s[..rate_end + head.len()], groups));
+    }
+
+    let mut groups = vec![];
+    while !rest.is_empty() {
+        let group = rest
+            .strip_prefix('{')
+            .and_then(|rest| rest.find('}').map(|close| &rest[..close]))
+            .context(error::InvalidOpentsdbQuerySnafu {
+                reason: format!("invalid braces in '{s}'"),
+            })?;
+        groups.push(group);
+        rest = &rest[group.len() + 2..];
+    }
+    Ok((head, groups))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Aggregator {
+    Sum,
+    /// Sum without interpolating missing points.
+    ZimSum,
+    Min,
+    /// Min without interpolating missing points.
+    MimMin,
+    Max,
+    /// Max without interpolating missing points.
+    MimMax,
+    Avg,
+    Count,
+    /// Returns each series without aggregation.
+    None,
+}
+
+impl Aggregator {
+    fn parse(name: &str) -> Result<Self> {
+        let aggregator = match name {
+            "sum" => Self::Sum,
+            "zimsum" => Self::ZimSum,
+            "min" => Self::Min,
+            "mimmin" => Self::MimMin,
+            "max" => Self::Max,
+            "mimmax" => Self::MimMax,
+            "avg" => Self::Avg,
+            "count" => Self::Count,
+            "none" => Self::None,
+            _ => {
+                return error::InvalidOpentsdbQuerySnafu {
+                    reason: format!("unsupported aggregator '{name}'"),
+                }
+                .fail()
+            }
+        };
+        Ok(aggregator)
+    }
+
+    /// Whether a series missing a point aggregated with other series contributes
+    /// the value linearly interpolated from its neighbouring points.
+    fn interpolates(&self) -> bool {
+        matches!(self, Self::Sum | Self::Min | Self::Max | Self::Avg)
+    }
+
+    /// Aggregates the values, ignoring `NaN`s produced by the fill policies.
+    fn aggregate(&self, values: &[f64]) -> f64 {
+        let values = values
+            .iter()
+            .copied()
+            .filter(|v| !v.is_nan())
+            .collect::<Vec<_>>();
+        if values.is_empty() {
+            return if *self == Self::Count { 0.0 } else { f64::NAN };
+        }
+        match self {
+            Self::Sum | Self::ZimSum => values.iter().sum(),
+            Self::Min | Self::MimMin => values.iter().copied().fold(f64::INFINITY, f64::min),
+            Self::Max | Self::MimMax => values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            Self::Avg => values.iter().sum::<f64>() / values.len() as f64,
+            // Points are turned into counts before aggregation, see [count_points].
+            Self::Count => values.iter().sum(),
+            // Series are not merged with the "none" aggregator.
+            Self::None => values[0],
+        }
+    }
+
+    fn to_expr(self, value: Expr) -> Result<Expr> {
+        let expr = match self {
+            Self::Sum | Self::ZimSum => sum(value),
+            Self::Min | Self::MimMin => min(value),
+            Self::Max | Self::MimMax => max(value),
+            Self::Avg => avg(value),
+            Self::Count => cast(count(value), DataType::Float64),
+            Self::None => {
+                return error::InvalidOpentsdbQuerySnafu {
+                    reason: "aggregator 'none' is not allowed in downsampling",
+                }
+                .fail()
+            }
+        };
+        Ok(expr)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FillPolicy {
+    None,
+    Nan,
+    Null,
+    Zero,
+}
+
+/// A downsample specifier like `1m-avg-zero`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Downsample {
+    /// The interval in milliseconds, or `None` to downsample all points into one.
+    interval: Option<i64>,
+    aggregator: Aggregator,
+    fill: FillPolicy,
+}
+
+impl Downsample {
+    fn parse(spec: &str) -> Result<Self> {
+        let parts = spec.split('-').collect::<Vec<_>>();
+        ensure!(
+            parts.len() == 2 || parts.len() == 3,
+            error::InvalidOpentsdbQuerySnafu {
+                reason: format!("invalid downsample specifier '{spec}'"),
+            }
+        );
+
+        let interval = if parts[0].ends_with("all") {
+            None
+        } else {
+            let interval = parse_duration(parts[0])?;
+            ensure!(
+                interval > 0,
+                error::InvalidOpentsdbQuerySnafu {
+                    reason: format!("invalid downsample interval '{}'", parts[0]),
+                }
+            );
+            Some(interval)
+        };
+        let fill = match parts.get(2).copied().unwrap_or("none") {
+            "none" => FillPolicy::None,
+            "nan" => FillPolicy::Nan,
+            "null" => FillPolicy::Null,
+            "zero" => FillPolicy::Zero,
+            other => {
+                return error::InvalidOpentsdbQuerySnafu {
+                    reason: format!("unsupported fill policy '{other}'"),
+                }
+                .fail()
+            }
+        };
+
+        Ok(Self {
+            interval,
+            aggregator: Aggregator::parse(parts[1])?,
+            fill,
+        })
+    }
+}
+
+/// Parses a duration like `10s` into milliseconds.
+fn parse_duration(s: &str) -> Result<i64> {
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let number = number
+        .parse::<i64>()
+        .ok()
+        .context(error::InvalidOpentsdbQuerySnafu {
+            reason: format!("invalid duration '{s}'"),
+        })?;
+    let unit_millis = match unit {
+        "ms" => 1,
+        "s" => 1_000,
+        "m" => 60_000,
+        "h" => 3_600_000,
+        "d" => 86_400_000,
+        "w" => 7 * 86_400_000,
+        "n" => 30 * 86_400_000,
+        "y" => 365 * 86_400_000,
+        _ => {
+            return error::InvalidOpentsdbQuerySnafu {
+                reason: format!("invalid duration unit in '{s}'"),
+            }
+            .fail()
+        }
+    };
+    Ok(number.saturating_mul(unit_millis))
+}
+
+/// Parses a time in a query into a timestamp in milliseconds.
+pub fn parse_time(value: &TimeValue, now_millis: i64) -> Result<i64> {
+    let s = match value {
+        TimeValue::Number(t) => return Ok(DataPoint::timestamp_to_millis(*t)),
+        TimeValue::String(s) => s.trim(),
+    };
+
+    if s == "now" {
+        return Ok(now_millis);
+    }
+    if let Some(duration) = s.strip_suffix("-ago") {
+        return Ok(now_millis - parse_duration(duration)?);
+    }
+    if let Ok(t) = s.parse::<i64>() {
+        return Ok(DataPoint::timestamp_to_millis(t));
+    }
+
+    const FORMATS: [&str; 4] = [
+        "%Y/%m/%d-%H:%M:%S",
+        "%Y/%m/%d %H:%M:%S",
+        "%Y/%m/%d-%H:%M",
+        "%Y/%m/%d %H:%M",
+    ];
+    if let Some(datetime) = FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(s, format).ok())
+    {
+        return Ok(datetime.timestamp_millis());
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(s, "%Y/%m/%d") {
+        return Ok(date.and_time(NaiveTime::MIN).timestamp_millis());
+    }
+
+    error::InvalidOpentsdbQuerySnafu {
+        reason: format!("invalid time '{s}'"),
+    }
+    .fail()
+}
+
+fn column(name: &str) -> Expr {
+    Expr::Column(Column::from_name(name))
+}
+
+/// Returns the tag columns of an OpenTSDB table, which are all the columns except
+/// the timestamp and the value.
+fn tag_columns(dataframe: &DfDataFrame) -> Vec<String> {
+    dataframe
+        .schema()
+        .fields()
+        .iter()
+        .map(|field| field.name())
+        .filter(|name| {
+            name.as_str() != OPENTSDB_TIMESTAMP_COLUMN_NAME
+                && name.as_str() != OPENTSDB_FIELD_COLUMN_NAME
+        })
+        .cloned()
+        .collect()
+}
+
+fn ensure_tag_column(tag_columns: &[String], tagk: &str) -> Result<()> {
+    ensure!(
+        tag_columns.iter().any(|c| c == tagk),
+        error::InvalidOpentsdbQuerySnafu {
+            reason: format!("No such name for 'tagk': '{tagk}'"),
+        }
+    );
+    Ok(())
+}
+
+/// Translates a sub query into a logical plan, which returns the filtered, and
+/// downsampled if required, data points of each series ordered by the tags and
+/// the timestamp.
+pub fn query_to_plan(
+    dataframe: DataFrame,
+    query: &SubQuery,
+    start: i64,
+    end: i64,
+) -> Result<LogicalPlan> {
+    let DataFrame::DataFusion(dataframe) = dataframe;
+    let tag_columns = tag_columns(&dataframe);
+
+    let timestamp = column(OPENTSDB_TIMESTAMP_COLUMN_NAME);
+    let mut conditions = vec![
+        timestamp.clone().gt_eq(lit(start)),
+        timestamp.clone().lt_eq(lit(end)),
+    ];
+    for filter in query.all_filters() {
+        ensure_tag_column(&tag_columns, &filter.tagk)?;
+        conditions.push(filter.to_expr()?);
+    }
+    // Safety: conditions MUST not be empty, reduce always return Some(expr).
+    let conditions = conditions.into_iter().reduce(Expr::and).unwrap();
+    let mut dataframe = dataframe
+        .filter(conditions)
+        .context(error::DataFrameSnafu)?;
+
+    if let Some(downsample) = &query.downsample {
+        let downsample = Downsample::parse(downsample)?;
+        let bucket = match downsample.interval {
+            Some(interval) => (cast(timestamp, DataType::Int64) / lit(interval)) * lit(interval),
+            None => lit(start),
+        };
+        let bucket = cast(
+            bucket,
+            DataType::Timestamp(ArrowTimeUnit::Millisecond, None),
+        )
+        .alias(OPENTSDB_TIMESTAMP_COLUMN_NAME);
+
+        let mut group_exprs = tag_columns.iter().map(|c| column(c)).collect::<Vec<_>>();
+        group_exprs.push(bucket);
+        let aggr_expr = downsample
+            .aggregator
+            .to_expr(column(OPENTSDB_FIELD_COLUMN_NAME))?
+            .alias(OPENTSDB_FIELD_COLUMN_NAME);
+        dataframe = dataframe
+            .aggregate(group_exprs, vec![aggr_expr])
+            .context(error::DataFrameSnafu)?;
+    }
+
+    let mut columns = tag_columns.clone();
+    columns.push(OPENTSDB_TIMESTAMP_COLUMN_NAME.to_string());
+    columns.push(OPENTSDB_FIELD_COLUMN_NAME.to_string());
+    let dataframe = dataframe
+        .select(columns.iter().map(|c| column(c)).collect())
+        .context(error::DataFrameSnafu)?
+        .sort(
+            columns[..columns.len() - 1]
+                .iter()
+                .map(|c| column(c).sort(true, true))
+                .collect(),
+        )
+        .context(error::DataFrameSnafu)?;
+
+    Ok(LogicalPlan::DfPlan(dataframe.into_parts().1))
+}
+
+/// The result of a sub query, which may be aggregated from several series.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryResponse {
+    pub metric: String,
+    /// Tags with the same value in all aggregated series.
+    pub tags: BTreeMap<String, String>,
+    /// Tags with different values among the aggregated series.
+    pub aggregate_tags: Vec<String>,
+    /// Data points keyed by timestamps, in seconds unless `msResolution` is set.
+    pub dps: BTreeMap<i64, f64>,
+}
+
+type SeriesKey = Vec<(String, String)>;
+
+/// Collects the rows of the query results into series, sorted by their tags.
+fn collect_series(
+    recordbatches: &RecordBatches,
+) -> Result<BTreeMap<SeriesKey, BTreeMap<i64, f64>>> {
+    let mut series: BTreeMap<SeriesKey, BTreeMap<i64, f64>> = BTreeMap::new();
+    for recordbatch in recordbatches.iter() {
+        let column = |name: &str| {
+            recordbatch
+                .column_by_name(name)
+                .context(error::InvalidOpentsdbQuerySnafu {
+                    reason: format!("missing column {name} in query result"),
+                })
+        };
+        let ts_column = column(OPENTSDB_TIMESTAMP_COLUMN_NAME)?;
+        let field_column = column(OPENTSDB_FIELD_COLUMN_NAME)?;
+        let mut tag_columns = recordbatch
+            .schema
+            .column_schemas()
+            .iter()
+            .filter(|c| {
+                c.name != OPENTSDB_TIMESTAMP_COLUMN_NAME && c.name != OPENTSDB_FIELD_COLUMN_NAME
+            })
+            .map(|c| Ok((c.name.clone(), column(&c.name)?)))
+            .collect::<Result<Vec<_>>>()?;
+        tag_columns.sort_by(|a, b| a.0.cmp(&b.0));
+
+        for row in 0..recordbatch.num_rows() {
+            let ts = match ts_column.get(row) {
+                Value::Timestamp(t) => t.convert_to(TimeUnit::Millisecond).map(|t| t.value()),
+                _ => None,
+            };
+            let value = match field_column.get(row) {
+                Value::Float64(v) => Some(v.into_inner()),
+                Value::Int64(v) => Some(v as f64),
+                _ => None,
+            };
+            let (Some(ts), Some(value)) = (ts, value) else { continue };
+
+            let key = tag_columns
+                .iter()
+                .filter_map(|(name, column)| match column.get(row) {
+                    Value::String(v) => Some((name.clone(), v.as_utf8().to_string())),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            let _ = series.entry(key).or_default().insert(ts, value);
+        }
+    }
+    Ok(series)
+}
+
+/// Fills the missing buckets of a downsampled series in the time range.
+fn fill_series(
+    dps: &mut BTreeMap<i64, f64>,
+    interval: i64,
+    fill: FillPolicy,
+    start: i64,
+    end: i64,
+) -> Result<()> {
+    let value = match fill {
+        FillPolicy::None => return Ok(()),
+        FillPolicy::Nan | FillPolicy::Null => f64::NAN,
+        FillPolicy::Zero => 0.0,
+    };
+
+    let first = start - start.rem_euclid(interval);
+    ensure!(
+        (end - first) / interval < MAX_FILL_POINTS,
+        error::InvalidOpentsdbQuerySnafu {
+            reason: format!("too many data points to fill, the limit is {MAX_FILL_POINTS}"),
+        }
+    );
+    let mut ts = first;
+    while ts <= end {
+        let _ = dps.entry(ts).or_insert(value);
+        ts += interval;
+    }
+    Ok(())
+}
+
+/// Computes the per-second rate of change of a series.
+fn rate(dps: &BTreeMap<i64, f64>, options: &RateOptions) -> BTreeMap<i64, f64> {
+    let mut rates = BTreeMap::new();
+    let mut prev: Option<(i64, f64)> = None;
+    for (&ts, &value) in dps.iter().filter(|(_, v)| !v.is_nan()) {
+        if let Some((prev_ts, prev_value)) = prev {
+            let mut delta = value - prev_value;
+            if options.counter && delta < 0.0 {
+                if options.drop_resets {
+                    prev = Some((ts, value));
+                    continue;
+                }
+                delta += options.counter_max.unwrap_or(i64::MAX as f64);
+            }
+
+            let rate = delta * 1000.0 / (ts - prev_ts) as f64;
+            let rate = match options.reset_value {
+                Some(reset_value) if reset_value > 0.0 && rate > reset_value => 0.0,
+                _ => rate,
+            };
+            let _ = rates.insert(ts, rate);
+        }
+        prev = Some((ts, value));
+    }
+    rates
+}
+
+/// Turns the points of a series into counts, so they can be summed by [Aggregator::Count].
+fn count_points(dps: &mut BTreeMap<i64, f64>) {
+    for value in dps.values_mut().filter(|v| !v.is_nan()) {
+        *value = 1.0;
+    }
+}
+
+/// Merges the points of a series in the same second with the aggregator, so they are
+/// not collided when timestamps are truncated to seconds.
+fn merge_in_seconds(dps: &BTreeMap<i64, f64>, aggregator: Aggregator) -> BTreeMap<i64, f64> {
+    let aggregator = match aggregator {
+        Aggregator::None => Aggregator::Avg,
+        aggregator => aggregator,
+    };
+    let mut seconds: BTreeMap<i64, Vec<f64>> = BTreeMap::new();
+    for (ts, value) in dps {
+        seconds
+            .entry(ts - ts.rem_euclid(1000))
+            .or_default()
+            .push(*value);
+    }
+    seconds
+        .into_iter()
+        .map(|(ts, values)| (ts, aggregator.aggregate(&values)))
+        .collect()
+}
+
+/// Returns the value of the series at `ts`. If the series has no point at `ts` but has
+/// points before and after it, the value is linearly interpolated from them when
+/// `interpolate` is set.
+fn value_at(dps: &BTreeMap<i64, f64>, ts: i64, interpolate: bool) -> Option<f64> {
+    if let Some(value) = dps.get(&ts) {
+        return Some(*value);
+    }
+    if !interpolate {
+        return None;
+    }
+    let (prev_ts, prev) = dps.range(..ts).rev().find(|(_, v)| !v.is_nan())?;
+    let (next_ts, next) = dps.range(ts..).find(|(_, v)| !v.is_nan())?;
+    Some(prev + (next - prev) * (ts - prev_ts) as f64 / (next_ts - prev_ts) as f64)
+}
+
+/// Converts the results of [query_to_plan] into the responses of a sub query,
+/// applying the fill policy, the rate and the aggregator.
+pub fn recordbatches_to_responses(
+    query: &SubQuery,
+    recordbatches: RecordBatches,
+    start: i64,
+    end: i64,
+    ms_resolution: bool,
+) -> Result<Vec<QueryResponse>> {
+    let aggregator = Aggregator::parse(&query.aggregator)?;
+    let downsample = query
+        .downsample
+        .as_deref()
+        .map(Downsample::parse)
+        .transpose()?;
+
+    let mut series = collect_series(&recordbatches)?;
+    for dps in series.values_mut() {
+        if let Some(Downsample {
+            interval: Some(interval),
+            fill,
+            ..
+        }) = downsample
+        {
+            fill_series(dps, interval, fill, start, end)?;
+        }
+        if query.rate {
+            *dps = rate(dps, &query.rate_options);
+        }
+        if aggregator == Aggregator::Count {
+            count_points(dps);
+        }
+        if !ms_resolution {
+            *dps = merge_in_seconds(dps, aggregator);
+        }
+    }
+
+    // Groups the series by the values of the group by tags.
+    let group_by = query
+        .all_filters()
+        .into_iter()
+        .filter(|f| f.group_by)
+        .map(|f| f.tagk)
+        .collect::<BTreeSet<_>>();
+    let mut groups: BTreeMap<SeriesKey, Vec<(SeriesKey, BTreeMap<i64, f64>)>> = BTreeMap::new();
+    for (key, dps) in series {
+        let group_key = if aggregator == Aggregator::None {
+            key.clone()
+        } else {
+            key.iter()
+                .filter(|(tagk, _)| group_by.contains(tagk))
+                .cloned()
+                .collect()
+        };
+        groups.entry(group_key).or_default().push((key, dps));
+    }
+
+    let to_resolution = |ts: i64| {
+        if ms_resolution {
+            ts
+        } else {
+            ts.div_euclid(1000)
+        }
+    };
+    let interpolate = aggregator.interpolates();
+    let responses = groups
+        .into_values()
+        .map(|series| {
+            let mut tags: BTreeMap<String, String> = series[0].0.iter().cloned().collect();
+            let mut all_tagks = BTreeSet::new();
+            let mut timestamps = BTreeSet::new();
+            for (key, dps) in &series {
+                tags.retain(|tagk, tagv| key.iter().any(|(k, v)| k == tagk && v == tagv));
+                all_tagks.extend(key.iter().map(|(tagk, _)| tagk.clone()));
+                timestamps.extend(dps.keys().copied());
+            }
+
+            QueryResponse {
+                metric: query.metric.clone(),
+                aggregate_tags: all_tagks
+                    .into_iter()
+                    .filter(|tagk| !tags.contains_key(tagk))
+                    .collect(),
+                tags,
+                dps: timestamps
+                    .into_iter()
+                    .map(|ts| {
+                        let values = series
+                            .iter()
+                            .filter_map(|(_, dps)| value_at(dps, ts, interpolate))
+                            .collect::<Vec<_>>();
+                        (to_resolution(ts), aggregator.aggregate(&values))
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+    Ok(responses)
+}
+
+/// The kinds of names `/api/suggest` returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SuggestType {
+    Metrics,
+    Tagk,
+    Tagv,
+}
+
+/// The parameters of `/api/suggest`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SuggestRequest {
+    #[serde(rename = "type")]
+    pub suggest_type: SuggestType,
+    /// The prefix of the names.
+    #[serde(default)]
+    pub q: String,
+    #[serde(default = "default_max_results")]
+    pub max: usize,
+}
+
+impl SuggestRequest {
+    pub fn try_from_params(params: &HashMap<String, String>) -> Result<Self> {
+        let suggest_type = match params.get("type").map(|s| s.as_str()) {
+            Some("metrics") => SuggestType::Metrics,
+            Some("tagk") => SuggestType::Tagk,
+            Some("tagv") => SuggestType::Tagv,
+            other => {
+                return error::InvalidOpentsdbQuerySnafu {
+                    reason: format!("invalid suggest type {other:?}"),
+                }
+                .fail()
+            }
+        };
+        let max = params
+            .get("max")
+            .map(|max| {
+                max.parse::<usize>()
+                    .ok()
+                    .context(error::InvalidOpentsdbQuerySnafu {
+                        reason: format!("invalid parameter 'max': {max}"),
+                    })
+            })
+            .transpose()?
+            .unwrap_or_else(default_max_results);
+
+        Ok(Self {
+            suggest_type,
+            q: params.get("q").cloned().unwrap_or_default(),
+            max,
+        })
+    }
+}
+
+fn default_max_results() -> usize {
+    25
+}
+
+/// Translates a query of tag values with the prefix into a logical plan.
+pub fn tag_values_to_plan(dataframe: DataFrame, tagk: &str, prefix: &str) -> Result<LogicalPlan> {
+    let DataFrame::DataFusion(dataframe) = dataframe;
+    let condition = if prefix.is_empty() {
+        column(tagk).is_not_null()
+    } else {
+        starts_with(column(tagk), lit(prefix))
+    };
+    let dataframe = dataframe
+        .filter(condition)
+        .context(error::DataFrameSnafu)?
+        .select(vec![column(tagk)])
+        .context(error::DataFrameSnafu)?
+        .distinct()
+        .context(error::DataFrameSnafu)?;
+    Ok(LogicalPlan::DfPlan(dataframe.into_parts().1))
+}
+
+/// Collects the strings in the first column of the query results.
+pub fn recordbatches_to_strings(recordbatches: RecordBatches) -> Vec<String> {
+    recordbatches
+        .iter()
+        .flat_map(|recordbatch| {
+            let column = recordbatch.column(0);
+            (0..column.len())
+                .filter_map(|row| match column.get(row) {
+                    Value::String(v) => Some(v.as_utf8().to_string()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// The parameters of `/api/search/lookup`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LookupRequest {
+    pub metric: String,
+    /// Pairs of tag key and value, either could be `*` to match any.
+    pub tags: Vec<(String, String)>,
+    pub limit: usize,
+}
+
+impl LookupRequest {
+    /// Parses the `m` parameter in the form of `metric{tagk=tagv,...}`.
+    pub fn parse(m: &str, limit: Option<usize>) -> Result<Self> {
+        let (metric, groups) = split_brace_groups(m)?;
+        ensure!(
+            !metric.is_empty() && groups.len() <= 1,
+            error::InvalidOpentsdbQuerySnafu {
+                reason: format!("invalid lookup query '{m}'"),
+            }
+        );
+
+        let tags = groups
+            .first()
+            .map(|group| {
+                group
+                    .split(',')
+                    .filter(|s| !s.is_empty())
+                    .map(|tag| {
+                        tag.split_once('=')
+                            .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+                            .context(error::InvalidOpentsdbQuerySnafu {
+                                reason: format!("invalid tag '{tag}'"),
+                            })
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(Self {
+            metric: metric.to_string(),
+            tags,
+            limit: limit.unwrap_or_else(default_max_results),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LookupResult {
+    pub metric: String,
+    pub tags: BTreeMap<String, String>,
+}
+
+/// Translates a lookup into a logical plan returning the distinct tags of series.
+pub fn lookup_to_plan(dataframe: DataFrame, request: &LookupRequest) -> Result<LogicalPlan> {
+    let DataFrame::DataFusion(dataframe) = dataframe;
+    let tag_columns = tag_columns(&dataframe);
+
+    let mut conditions = vec![];
+    for (tagk, tagv) in &request.tags {
+        let condition = match (tagk.as_str(), tagv.as_str()) {
+            ("*", "*") => continue,
+            ("*", tagv) => tag_columns
+                .iter()
+                .map(|c| column(c).eq(lit(tagv)))
+                .reduce(Expr::or)
+                .unwrap_or_else(|| lit(false)),
+            (tagk, "*") => {
+                ensure_tag_column(&tag_columns, tagk)?;
+                column(tagk).is_not_null()
+            }
+            (tagk, tagv) => {
+                ensure_tag_column(&tag_columns, tagk)?;
+                column(tagk).eq(lit(tagv))
+            }
+        };
+        conditions.push(condition);
+    }
+
+    let mut dataframe = dataframe;
+    if let Some(conditions) = conditions.into_iter().reduce(Expr::and) {
+        dataframe = dataframe
+            .filter(conditions)
+            .context(error::DataFrameSnafu)?;
+    }
+    let dataframe = dataframe
+        .select(tag_columns.iter().map(|c| column(c)).collect())
+        .context(error::DataFrameSnafu)?
+        .distinct()
+        .context(error::DataFrameSnafu)?;
+    Ok(LogicalPlan::DfPlan(dataframe.into_parts().1))
+}
+
+/// Converts the results of [lookup_to_plan] into lookup results, sorted by tags.
+pub fn recordbatches_to_lookup_results(
+    metric: &str,
+    recordbatches: RecordBatches,
+) -> Vec<LookupResult> {
+    let mut results = recordbatches
+        .iter()
+        .flat_map(|recordbatch| {
+            let column_schemas = recordbatch.schema.column_schemas();
+            (0..recordbatch.num_rows())
+                .map(|row| LookupResult {
+                    metric: metric.to_string(),
+                    tags: column_schemas
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(idx, c)| match recordbatch.column(idx).get(row) {
+                            Value::String(v) => Some((c.name.clone(), v.as_utf8().to_string())),
+                            _ => None,
+                        })
+                        .collect(),
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    results.sort_by(|a, b| a.tags.cmp(&b.tags));
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use common_recordbatch::RecordBatch;
+    use datatypes::prelude::ConcreteDataType;
+    use datatypes::schema::{ColumnSchema, Schema};
+    use datatypes::vectors::{Float64Vector, StringVector, TimestampMillisecondVector};
+
+    use super::*;
+
+    #[test]
+    fn test_parse_time() {
+        let now = 1_688_000_000_000;
+        let parse = |s: &str| parse_time(&TimeValue::String(s.to_string()), now);
+
+        assert_eq!(now, parse("now").unwrap());
+        assert_eq!(now - 3_600_000, parse("1h-ago").unwrap());
+        assert_eq!(now - 90_000, parse("90s-ago").unwrap());
+        assert_eq!(1_688_000_000_000, parse("1688000000").unwrap());
+        assert_eq!(1_688_000_000_123, parse("1688000000123").unwrap());
+        assert_eq!(
+            1_688_000_000_000,
+            parse_time(&TimeValue::Number(1_688_000_000), now).unwrap()
+        );
+        assert_eq!(1_688_169_600_000, parse("2023/07/01").unwrap());
+        assert_eq!(1_688_213_045_000, parse("2023/07/01-12:04:05").unwrap());
+        assert_eq!(1_688_213_040_000, parse("2023/07/01 12:04").unwrap());
+
+        assert!(parse("1x-ago").is_err());
+        assert!(parse("yesterday").is_err());
+    }
+
+    #[test]
+    fn test_parse_sub_query() {
+        let query = SubQuery::parse("sum:rate{counter,100,10}:1m-avg-zero:sys.cpu{host=web*,dc=lga|sjc}{env=literal_or(prod)}").unwrap();
+        assert_eq!("sum", query.aggregator);
+        assert_eq!("sys.cpu", query.metric);
+        assert_eq!(Some("1m-avg-zero".to_string()), query.downsample);
+        assert!(query.rate);
+        assert_eq!(
+            RateOptions {
+                counter: true,
+                counter_max: Some(100.0),
+                reset_value: Some(10.0),
+                drop_resets: false,
+            },
+            query.rate_options
+        );
+        assert_eq!(
+            vec![
+                Filter {
+                    filter_type: "wildcard".to_string(),
+                    tagk: "host".to_string(),
+                    filter: "web*".to_string(),
+                    group_by: true,
+                },
+                Filter {
+                    filter_type: "literal_or".to_string(),
+                    tagk: "dc".to_string(),
+                    filter: "lga|sjc".to_string(),
+                    group_by: true,
+                },
+                Filter {
+                    filter_type: "literal_or".to_string(),
+                    tagk: "env".to_string(),
+                    filter: "prod".to_string(),
+                    group_by: false,
+                },
+            ],
+            query.filters
+        );
+
+        let query = SubQuery::parse("avg:sys.cpu").unwrap();
+        assert_eq!("avg", query.aggregator);
+        assert!(!query.rate);
+        assert!(query.downsample.is_none());
+        assert!(query.filters.is_empty());
+
+        assert!(SubQuery::parse("sys.cpu").is_err());
+        assert!(SubQuery::parse("sum:sys.cpu{host=a").is_err());
+    }
+
+    #[test]
+    fn test_parse_query_request() {
+        let body = r#"{
+            "start": "1h-ago",
+            "queries": [{
+                "aggregator": "sum",
+                "metric": "sys.cpu.nice",
+                "rate": true,
+                "rateOptions": {"counter": true, "dropResets": true},
+                "filters": [{"type": "wildcard", "tagk": "host", "filter": "*", "groupBy": true}]
+            }],
+            "msResolution": true
+        }"#;
+        let request = serde_json::from_str::<QueryRequest>(body).unwrap();
+        assert!(request.ms_resolution);
+        assert_eq!(TimeValue::String("1h-ago".to_string()), request.start);
+        assert!(request.queries[0].rate_options.drop_resets);
+        assert_eq!((1000, 3_601_000), request.time_range(3_601_000).unwrap());
+
+        let params = vec![
+            ("start".to_string(), "1688000000".to_string()),
+            ("end".to_string(), "1688000600".to_string()),
+            ("m".to_string(), "sum:sys.cpu".to_string()),
+            ("m".to_string(), "max:sys.mem".to_string()),
+        ];
+        let request = QueryRequest::try_from_params(&params).unwrap();
+        assert_eq!(2, request.queries.len());
+        assert_eq!(
+            (1_688_000_000_000, 1_688_000_600_000),
+            request.time_range(0).unwrap()
+        );
+
+        assert!(QueryRequest::try_from_params(&params[2..]).is_err());
+    }
+
+    #[test]
+    fn test_parse_downsample() {
+        assert_eq!(
+            Downsample {
+                interval: Some(60_000),
+                aggregator: Aggregator::Avg,
+                fill: FillPolicy::None,
+            },
+            Downsample::parse("1m-avg").unwrap()
+        );
+        assert_eq!(
+            Downsample {
+                interval: None,
+                aggregator: Aggregator::ZimSum,
+                fill: FillPolicy::Zero,
+            },
+            Downsample::parse("0all-zimsum-zero").unwrap()
+        );
+        assert!(Downsample::parse("1m").is_err());
+        assert!(Downsample::parse("0s-avg").is_err());
+        assert!(Downsample::parse("1m-none").is_ok());
+        assert!(Downsample::parse("1m-p99").is_err());
+    }
+
+    #[test]
+    fn test_wildcard_to_expr() {
+        assert_eq!(
+            column("host").is_not_null(),
+            wildcard_to_expr(column("host"), "*", false)
+        );
+        assert_eq!(
+            column("host").like(lit("web\\_%")),
+            wildcard_to_expr(column("host"), "web_*", false)
+        );
+        assert_eq!(
+            column("host").ilike(lit("%.lga")),
+            wildcard_to_expr(column("host"), "*.lga", true)
+        );
+    }
+
+    #[test]
+    fn test_rate() {
+        let dps = BTreeMap::from([(1000, 10.0), (2000, 30.0), (4000, 40.0), (5000, 5.0)]);
+        let rates = rate(&dps, &RateOptions::default());
+        assert_eq!(
+            BTreeMap::from([(2000, 20.0), (4000, 5.0), (5000, -35.0)]),
+            rates
+        );
+
+        let options = RateOptions {
+            counter: true,
+            counter_max: Some(50.0),
+            ..Default::default()
+        };
+        assert_eq!(Some(&15.0), rate(&dps, &options).get(&5000));
+
+        let options = RateOptions {
+            counter: true,
+            drop_resets: true,
+            ..Default::default()
+        };
+        assert_eq!(None, rate(&dps, &options).get(&5000));
+
+        let options = RateOptions {
+            reset_value: Some(10.0),
+            ..Default::default()
+        };
+        assert_eq!(Some(&0.0), rate(&dps, &options).get(&2000));
+    }
+
+    fn mock_recordbatches() -> RecordBatches {
+        let schema = Arc::new(Schema::new(vec![
+            ColumnSchema::new("dc", ConcreteDataType::string_datatype(), true),
+            ColumnSchema::new("host", ConcreteDataType::string_datatype(), true),
+            ColumnSchema::new(
+                OPENTSDB_TIMESTAMP_COLUMN_NAME,
+                ConcreteDataType::timestamp_millisecond_datatype(),
+                false,
+            ),
+            ColumnSchema::new(
+                OPENTSDB_FIELD_COLUMN_NAME,
+                ConcreteDataType::float64_datatype(),
+                true,
+            ),
+        ]));
+        let recordbatch = RecordBatch::new(
+            schema.clone(),
+            vec![
+                Arc::new(StringVector::from(vec!["lga", "lga", "lga", "sjc"])) as _,
+                Arc::new(StringVector::from(vec!["web01", "web01", "web02", "web03"])) as _,
+                Arc::new(TimestampMillisecondVector::from_vec(vec![
+                    0, 60_000, 0, 60_000,
+                ])) as _,
+                Arc::new(Float64Vector::from_vec(vec![1.0, 2.0, 3.0, 4.0])) as _,
+            ],
+        )
+        .unwrap();
+        RecordBatches::try_new(schema, vec![recordbatch]).unwrap()
+    }
+
+    #[test]
+    fn test_recordbatches_to_responses() {
+        let mut query = SubQuery::parse("sum:1m-avg:sys.cpu{dc=*}").unwrap();
+        let responses =
+            recordbatches_to_responses(&query, mock_recordbatches(), 0, 60_000, false).unwrap();
+        assert_eq!(
+            vec![
+                QueryResponse {
+                    metric: "sys.cpu".to_string(),
+                    tags: BTreeMap::from([("dc".to_string(), "lga".to_string())]),
+                    aggregate_tags: vec!["host".to_string()],
+                    dps: BTreeMap::from([(0, 4.0), (60, 2.0)]),
+                },
+                QueryResponse {
+                    metric: "sys.cpu".to_string(),
+                    tags: BTreeMap::from([
+                        ("dc".to_string(), "sjc".to_string()),
+                        ("host".to_string(), "web03".to_string()),
+                    ]),
+                    aggregate_tags: vec![],
+                    dps: BTreeMap::from([(60, 4.0)]),
+                },
+            ],
+            responses
+        );
+
+        query.aggregator = "none".to_string();
+        let responses =
+            recordbatches_to_responses(&query, mock_recordbatches(), 0, 60_000, true).unwrap();
+        assert_eq!(3, responses.len());
+        assert_eq!(BTreeMap::from([(0, 1.0), (60_000, 2.0)]), responses[0].dps);
+
+        query.aggregator = "max".to_string();
+        query.downsample = Some("1m-avg-zero".to_string());
+        query.filters.clear();
+        let responses =
+            recordbatches_to_responses(&query, mock_recordbatches(), 0, 60_000, true).unwrap();
+        assert_eq!(
+            vec![QueryResponse {
+                metric: "sys.cpu".to_string(),
+                tags: BTreeMap::new(),
+                aggregate_tags: vec!["dc".to_string(), "host".to_string()],
+                dps: BTreeMap::from([(0, 3.0), (60_000, 4.0)]),
+            }],
+            responses
+        );
+    }
+
+    fn host_recordbatches(rows: Vec<(&str, i64, f64)>) -> RecordBatches {
+        let schema = Arc::new(Schema::new(vec![
+            ColumnSchema::new("host", ConcreteDataType::string_datatype(), true),
+            ColumnSchema::new(
+                OPENTSDB_TIMESTAMP_COLUMN_NAME,
+                ConcreteDataType::timestamp_millisecond_datatype(),
+                false,
+            ),
+            ColumnSchema::new(
+                OPENTSDB_FIELD_COLUMN_NAME,
+                ConcreteDataType::float64_datatype(),
+                true,
+            ),
+        ]));
+        let recordbatch = RecordBatch::new(
+            schema.clone(),
+            vec![
+                Arc::new(StringVector::from(
+                    rows.iter().map(|r| r.0).collect::<Vec<_>>(),
+                )) as _,
+                Arc::new(TimestampMillisecondVector::from_vec(
+                    rows.iter().map(|r| r.1).collect(),
+                )) as _,
+                Arc::new(Float64Vector::from_vec(rows.iter().map(|r| r.2).collect())) as _,
+            ],
+        )
+        .unwrap();
+        RecordBatches::try_new(schema, vec![recordbatch]).unwrap()
+    }
+
+    #[test]
+    fn test_aggregate_with_interpolation() {
+        let rows = || {
+            host_recordbatches(vec![
+                ("web01", 0, 1.0),
+                ("web01", 2000, 3.0),
+                ("web02", 1000, 10.0),
+            ])
+        };
+        let dps = |aggregator: &str| {
+            let query = SubQuery::parse(&format!("{aggregator}:sys.cpu")).unwrap();
+            let mut responses = recordbatches_to_responses(&query, rows(), 0, 2000, true).unwrap();
+            assert_eq!(1, responses.len());
+            responses.remove(0).dps
+        };
+
+        // web01 is interpolated at 1000, web02 has no points around 0 and 2000.
+        assert_eq!(
+            BTreeMap::from([(0, 1.0), (1000, 12.0), (2000, 3.0)]),
+            dps("sum")
+        );
+        assert_eq!(
+            BTreeMap::from([(0, 1.0), (1000, 6.0), (2000, 3.0)]),
+            dps("avg")
+        );
+        assert_eq!(
+            BTreeMap::from([(0, 1.0), (1000, 10.0), (2000, 3.0)]),
+            dps("zimsum")
+        );
+        assert_eq!(
+            BTreeMap::from([(0, 1.0), (1000, 2.0), (2000, 3.0)]),
+            dps("min")
+        );
+        assert_eq!(
+            BTreeMap::from([(0, 1.0), (1000, 10.0), (2000, 3.0)]),
+            dps("mimmin")
+        );
+        assert_eq!(
+            BTreeMap::from([(0, 1.0), (1000, 1.0), (2000, 1.0)]),
+            dps("count")
+        );
+    }
+
+    #[test]
+    fn test_aggregate_in_seconds() {
+        let rows = || {
+            host_recordbatches(vec![
+                ("web01", 1000, 1.0),
+                ("web01", 1500, 3.0),
+                ("web01", 2000, 5.0),
+                ("web02", 1200, 10.0),
+            ])
+        };
+        let dps = |aggregator: &str, ms_resolution: bool| {
+            let query = SubQuery::parse(&format!("{aggregator}:sys.cpu")).unwrap();
+            let mut responses =
+                recordbatches_to_responses(&query, rows(), 0, 2000, ms_resolution).unwrap();
+            assert_eq!(1, responses.len());
+            responses.remove(0).dps
+        };
+
+        // Points in the same second are aggregated before truncating timestamps.
+        assert_eq!(BTreeMap::from([(1, 14.0), (2, 5.0)]), dps("sum", false));
+        assert_eq!(BTreeMap::from([(1, 6.0), (2, 5.0)]), dps("avg", false));
+        assert_eq!(BTreeMap::from([(1, 3.0), (2, 1.0)]), dps("count", false));
+
+        // web01 is interpolated at 1200.
+        assert_eq!(
+            BTreeMap::from([(1000, 1.0), (1200, 11.8), (1500, 3.0), (2000, 5.0)]),
+            dps("sum", true)
+        );
+    }
+
+    #[test]
+    fn test_parse_lookup_request() {
+        let request = LookupRequest::parse("sys.cpu{host=*,*=lga}", None).unwrap();
+        assert_eq!("sys.cpu", request.metric);
+        assert_eq!(
+            vec![
+                ("host".to_string(), "*".to_string()),
+                ("*".to_string(), "lga".to_string()),
+            ],
+            request.tags
+        );
+        assert_eq!(25, request.limit);
+
+        let request = LookupRequest::parse("sys.cpu", Some(5)).unwrap();
+        assert!(request.tags.is_empty());
+        assert_eq!(5, request.limit);
+
+        assert!(LookupRequest::parse("{host=web01}", None).is_err());
+        assert!(LookupRequest::parse("sys.cpu{host}", None).is_err());
+    }
+}
@@ -0,0 +1,492 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! OpenTelemetry protocol (OTLP) supports, converts OTLP metrics into insert
+//! requests.
+//!
+//! Each metric is written to a table named after the metric, with the resource,
+//! scope and data point attributes as tags. Like Prometheus, histograms are
+//! written to the `<metric>_bucket`, `<metric>_sum` and `<metric>_count` tables,
+//! and summaries to the `<metric>`, `<metric>_sum` and `<metric>_count` tables.
+
+use std::collections::{BTreeMap, HashMap};
+
+use api::v1::{InsertRequest as GrpcInsertRequest, InsertRequests};
+use common_grpc::writer::{LinesWriter, Precision};
+use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
+use opentelemetry_proto::tonic::common::v1::{any_value, AnyValue, KeyValue};
+use opentelemetry_proto::tonic::metrics::v1::{
+    metric, number_data_point, HistogramDataPoint, NumberDataPoint, SummaryDataPoint,
+};
+use snafu::ResultExt;
+
+use crate::error::{self, Result};
+use crate::prom_store::{FIELD_COLUMN_NAME, TIMESTAMP_COLUMN_NAME};
+
+/// The tag of the upper bounds of histogram buckets.
+const BUCKET_BOUND_TAG: &str = "le";
+/// The tag of the quantiles of summaries.
+const QUANTILE_TAG: &str = "quantile";
+
+type Tags = BTreeMap<String, String>;
+
+/// Normalizes a metric or attribute name into a valid column or table name, by
+/// replacing characters other than letters, digits and `_` with `_`.
+pub fn normalize_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn any_value_to_string(value: &AnyValue) -> String {
+    match &value.value {
+        Some(any_value::Value::StringValue(v)) => v.clone(),
+        Some(any_value::Value::BoolValue(v)) => v.to_string(),
+        Some(any_value::Value::IntValue(v)) => v.to_string(),
+        Some(any_value::Value::DoubleValue(v)) => v.to_string(),
+        Some(any_value::Value::BytesValue(v)) => hex::encode(v),
+        Some(any_value::Value::ArrayValue(v)) => format!(
+            "[{}]",
+            v.values
+                .iter()
+                .map(any_value_to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        ),
+        Some(any_value::Value::KvlistValue(v)) => format!(
+            "{{{}}}",
+            v.values
+                .iter()
+                .map(|kv| format!(
+                    "{}={}",
+                    kv.key,
+                    kv.value
+                        .as_ref()
+                        .map(any_value_to_string)
+                        .unwrap_or_default()
+                ))
+                .collect::<Vec<_>>()
+                .join(",")
+        ),
+        None => String::new(),
+    }
+}
+
+/// Adds attributes to the tags, overriding the existing tags with the same names.
+fn extend_tags(tags: &mut Tags, attributes: &[KeyValue]) {
+    for attribute in attributes {
+        let value = attribute
+            .value
+            .as_ref()
+            .map(any_value_to_string)
+            .unwrap_or_default();
+        let _ = tags.insert(normalize_name(&attribute.key), value);
+    }
+}
+
+/// Writers of the tables, keyed by table names.
+#[derive(Default)]
+struct TableWriters {
+    writers: HashMap<String, LinesWriter>,
+}
+
+impl TableWriters {
+    fn write(&mut self, table_name: &str, tags: &Tags, timestamp: u64, value: f64) -> Result<()> {
+        let writer = self
+            .writers
+            .entry(table_name.to_string())
+            .or_insert_with(|| LinesWriter::with_lines(16));
+        for (name, value) in tags {
+            writer
+                .write_tag(name, value)
+                .context(error::OtlpMetricsWriteSnafu)?;
+        }
+        writer
+            .write_ts(
+                TIMESTAMP_COLUMN_NAME,
+                (timestamp as i64, Precision::Nanosecond),
+            )
+            .context(error::OtlpMetricsWriteSnafu)?;
+        writer
+            .write_f64(FIELD_COLUMN_NAME, value)
+            .context(error::OtlpMetricsWriteSnafu)?;
+        writer.commit();
+        Ok(())
+    }
+
+    fn finish(self) -> (InsertRequests, usize) {
+        let mut rows = 0;
+        let inserts = self
+            .writers
+            .into_iter()
+            .map(|(table_name, writer)| {
+                let (columns, row_count) = writer.finish();
+                rows += row_count as usize;
+                GrpcInsertRequest {
+                    table_name,
+                    region_number: 0,
+                    columns,
+                    row_count,
+                }
+            })
+            .collect();
+        (InsertRequests { inserts }, rows)
+    }
+}
+
+/// Converts an OTLP metrics request into insert requests, returns the requests
+/// and the number of rows.
+pub fn to_grpc_insert_requests(
+    request: ExportMetricsServiceRequest,
+) -> Result<(InsertRequests, usize)> {
+    let mut writers = TableWriters::default();
+    for resource_metrics in &request.resource_metrics {
+        let mut resource_tags = Tags::new();
+        if let Some(resource) = &resource_metrics.resource {
+            extend_tags(&mut resource_tags, &resource.attributes);
+        }
+
+        for scope_metrics in &resource_metrics.scope_metrics {
+            let mut scope_tags = resource_tags.clone();
+            if let Some(scope) = &scope_metrics.scope {
+                extend_tags(&mut scope_tags, &scope.attributes);
+            }
+
+            for metric in &scope_metrics.metrics {
+                let table_name = normalize_name(&metric.name);
+                match &metric.data {
+                    Some(metric::Data::Gauge(gauge)) => {
+                        write_numbers(&mut writers, &table_name, &scope_tags, &gauge.data_points)?
+                    }
+                    Some(metric::Data::Sum(sum)) => {
+                        write_numbers(&mut writers, &table_name, &scope_tags, &sum.data_points)?
+                    }
+                    Some(metric::Data::Histogram(histogram)) => write_histograms(
+                        &mut writers,
+                        &table_name,
+                        &scope_tags,
+                        &histogram.data_points,
+                    )?,
+                    Some(metric::Data::Summary(summary)) => write_summaries(
+                        &mut writers,
+                        &table_name,
+                        &scope_tags,
+                        &summary.data_points,
+                    )?,
+                    // Exponential histograms have no equivalent in Prometheus, they
+                    // are not supported yet.
+                    Some(metric::Data::ExponentialHistogram(_)) | None => {}
+                }
+            }
+        }
+    }
+    Ok(writers.finish())
+}
+
+fn point_tags(tags: &Tags, attributes: &[KeyValue]) -> Tags {
+    let mut tags = tags.clone();
+    extend_tags(&mut tags, attributes);
+    tags
+}
+
+fn write_numbers(
+    writers: &mut TableWriters,
+    table_name: &str,
+    tags: &Tags,
+    data_points: &[NumberDataPoint],
+) -> Result<()> {
+    for data_point in data_points {
+        let value = match data_point.value {
+            Some(number_data_point::Value::AsDouble(v)) => v,
+            Some(number_data_point::Value::AsInt(v)) => v as f64,
+            None => continue,
+        };
+        let tags = point_tags(tags, &data_point.attributes);
+        writers.write(table_name, &tags, data_point.time_unix_nano, value)?;
+    }
+    Ok(())
+}
+
+fn write_histograms(
+    writers: &mut TableWriters,
+    table_name: &str,
+    tags: &Tags,
+    data_points: &[HistogramDataPoint],
+) -> Result<()> {
+    let bucket_table = format!("{table_name}_bucket");
+    let sum_table = format!("{table_name}_sum");
+    let count_table = format!("{table_name}_count");
+
+    for data_point in data_points {
+        let tags = point_tags(tags, &data_point.attributes);
+        let timestamp = data_point.time_unix_nano;
+
+        // Bucket counts are not cumulative in OTLP, and the last bucket has no
+        // explicit bound.
+        let mut cumulative_count = 0;
+        for (i, count) in data_point.bucket_counts.iter().enumerate() {
+            cumulative_count += count;
+            let bound = data_point
+                .explicit_bounds
+                .get(i)
+                .map(|bound| bound.to_string())
+                .unwrap_or_else(|| "+Inf".to_string());
+            let mut bucket_tags = tags.clone();
+            let _ = bucket_tags.insert(BUCKET_BOUND_TAG.to_string(), bound);
+            writers.write(
+                &bucket_table,
+                &bucket_tags,
+                timestamp,
+                cumulative_count as f64,
+            )?;
+        }
+
+        if let Some(sum) = data_point.sum {
+            writers.write(&sum_table, &tags, timestamp, sum)?;
+        }
+        writers.write(&count_table, &tags, timestamp, data_point.count as f64)?;
+    }
+    Ok(())
+}
+
+fn write_summaries(
+    writers: &mut TableWriters,
+    table_name: &str,
+    tags: &Tags,
+    data_points: &[SummaryDataPoint],
+) -> Result<()> {
+    let sum_table = format!("{table_name}_sum");
+    let count_table = format!("{table_name}_count");
+
+    for data_point in data_points {
+        let tags = point_tags(tags, &data_point.attributes);
+        let timestamp = data_point.time_unix_nano;
+
+        for quantile in &data_point.quantile_values {
+            let mut quantile_tags = tags.clone();
+            let _ = quantile_tags.insert(QUANTILE_TAG.to_string(), quantile.quantile.to_string());
+            writers.write(table_name, &quantile_tags, timestamp, quantile.value)?;
+        }
+
+        writers.write(&sum_table, &tags, timestamp, data_point.sum)?;
+        writers.write(&count_table, &tags, timestamp, data_point.count as f64)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use api::v1::column::Values;
+    use opentelemetry_proto::tonic::common::v1::InstrumentationScope;
+    use opentelemetry_proto::tonic::metrics::v1::{
+        summary_data_point, Gauge, Histogram, Metric, ResourceMetrics, ScopeMetrics, Summary,
+    };
+    use opentelemetry_proto::tonic::resource::v1::Resource;
+
+    use super::*;
+
+    fn string_attribute(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            key: key.to_string(),
+            value: Some(AnyValue {
+                value: Some(any_value::Value::StringValue(value.to_string())),
+            }),
+        }
+    }
+
+    fn mock_request(metrics: Vec<Metric>) -> ExportMetricsServiceRequest {
+        ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                resource: Some(Resource {
+                    attributes: vec![string_attribute("service.name", "app")],
+                    dropped_attributes_count: 0,
+                }),
+                scope_metrics: vec![ScopeMetrics {
+                    scope: Some(InstrumentationScope {
+                        name: "meter".to_string(),
+                        version: String::new(),
+                        attributes: vec![string_attribute("scope", "s")],
+                        dropped_attributes_count: 0,
+                    }),
+                    metrics,
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        }
+    }
+
+    fn column_names(insert: &GrpcInsertRequest) -> Vec<&str> {
+        let mut names = insert
+            .columns
+            .iter()
+            .map(|c| c.column_name.as_str())
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    fn find_insert<'a>(requests: &'a InsertRequests, table_name: &str) -> &'a GrpcInsertRequest {
+        requests
+            .inserts
+            .iter()
+            .find(|insert| insert.table_name == table_name)
+            .unwrap()
+    }
+
+    fn f64_values<'a>(insert: &'a GrpcInsertRequest, column_name: &str) -> &'a [f64] {
+        let column = insert
+            .columns
+            .iter()
+            .find(|c| c.column_name == column_name)
+            .unwrap();
+        let Some(Values { f64_values, .. }) = column.values.as_ref() else {
+            unreachable!()
+        };
+        f64_values
+    }
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(
+            "http_server_duration",
+            normalize_name("http.server.duration")
+        );
+        assert_eq!("service_name", normalize_name("service.name"));
+        assert_eq!("a_b_c", normalize_name("a-b/c"));
+    }
+
+    #[test]
+    fn test_gauge_to_insert_requests() {
+        let request = mock_request(vec![Metric {
+            name: "system.memory.usage".to_string(),
+            description: String::new(),
+            unit: String::new(),
+            data: Some(metric::Data::Gauge(Gauge {
+                data_points: vec![
+                    NumberDataPoint {
+                        attributes: vec![string_attribute("state", "used")],
+                        time_unix_nano: 1_000_000_000,
+                        value: Some(number_data_point::Value::AsInt(10)),
+                        ..Default::default()
+                    },
+                    NumberDataPoint {
+                        attributes: vec![string_attribute("state", "free")],
+                        time_unix_nano: 1_000_000_000,
+                        value: Some(number_data_point::Value::AsDouble(2.5)),
+                        ..Default::default()
+                    },
+                ],
+            })),
+        }]);
+
+        let (requests, rows) = to_grpc_insert_requests(request).unwrap();
+        assert_eq!(2, rows);
+        assert_eq!(1, requests.inserts.len());
+        let insert = &requests.inserts[0];
+        assert_eq!("system_memory_usage", insert.table_name);
+        assert_eq!(
+            vec![
+                FIELD_COLUMN_NAME,
+                TIMESTAMP_COLUMN_NAME,
+                "scope",
+                "service_name",
+                "state"
+            ],
+            column_names(insert)
+        );
+        assert_eq!(&[10.0, 2.5], f64_values(insert, FIELD_COLUMN_NAME));
+    }
+
+    #[test]
+    fn test_histogram_to_insert_requests() {
+        let request = mock_request(vec![Metric {
+            name: "latency".to_string(),
+            description: String::new(),
+            unit: String::new(),
+            data: Some(metric::Data::Histogram(Histogram {
+                data_points: vec![HistogramDataPoint {
+                    time_unix_nano: 1_000_000_000,
+                    count: 6,
+                    sum: Some(12.5),
+                    bucket_counts: vec![1, 2, 3],
+                    explicit_bounds: vec![0.5, 1.0],
+                    ..Default::default()
+                }],
+                aggregation_temporality: 2,
+            })),
+        }]);
+
+        let (requests, rows) = to_grpc_insert_requests(request).unwrap();
+        assert_eq!(5, rows);
+        let bucket = find_insert(&requests, "latency_bucket");
+        assert_eq!(3, bucket.row_count);
+        assert!(column_names(bucket).contains(&BUCKET_BOUND_TAG));
+        assert_eq!(&[1.0, 3.0, 6.0], f64_values(bucket, FIELD_COLUMN_NAME));
+        assert_eq!(
+            &[12.5],
+            f64_values(find_insert(&requests, "latency_sum"), FIELD_COLUMN_NAME)
+        );
+        assert_eq!(
+            &[6.0],
+            f64_values(find_insert(&requests, "latency_count"), FIELD_COLUMN_NAME)
+        );
+    }
+
+    #[test]
+    fn test_summary_to_insert_requests() {
+        let request = mock_request(vec![Metric {
+            name: "rpc.duration".to_string(),
+            description: String::new(),
+            unit: String::new(),
+            data: Some(metric::Data::Summary(Summary {
+                data_points: vec![SummaryDataPoint {
+                    time_unix_nano: 1_000_000_000,
+                    count: 4,
+                    sum: 10.0,
+                    quantile_values: vec![
+                        summary_data_point::ValueAtQuantile {
+                            quantile: 0.5,
+                            value: 2.0,
+                        },
+                        summary_data_point::ValueAtQuantile {
+                            quantile: 0.99,
+                            value: 4.0,
+                        },
+                    ],
+                    ..Default::default()
+                }],
+            })),
+        }]);
+
+        let (requests, rows) = to_grpc_insert_requests(request).unwrap();
+        assert_eq!(4, rows);
+        let quantiles = find_insert(&requests, "rpc_duration");
+        assert!(column_names(quantiles).contains(&QUANTILE_TAG));
+        assert_eq!(&[2.0, 4.0], f64_values(quantiles, FIELD_COLUMN_NAME));
+        assert_eq!(
+            &[10.0],
+            f64_values(
+                find_insert(&requests, "rpc_duration_sum"),
+                FIELD_COLUMN_NAME
+            )
+        );
+    }
+}
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod auth_handler;
+mod cancel;
 mod handler;
 mod server;
 mod types;
@@ -37,6 +38,7 @@ use session::context::Channel;
 use session::Session;
 
 use self::auth_handler::PgLoginVerifier;
+use self::cancel::{CancelKey, CancelKeys};
 use self::handler::DefaultQueryParser;
 use crate::auth::UserProviderRef;
 use crate::query_handler::sql::ServerSqlQueryHandlerRef;
@@ -74,6 +76,7 @@ pub struct PostgresServerHandler {
     login_verifier: PgLoginVerifier,
     force_tls: bool,
     param_provider: Arc<GreptimeDBStartupParameters>,
+    cancel_key: CancelKey,
 
     session: Arc<Session>,
     portal_store: Arc<MemPortalStore<SqlPlan>>,
@@ -87,16 +90,20 @@ pub(crate) struct MakePostgresServerHandler {
     #[builder(default = "Arc::new(GreptimeDBStartupParameters::new())")]
     param_provider: Arc<GreptimeDBStartupParameters>,
     force_tls: bool,
+    #[builder(default)]
+    cancel_keys: Arc<CancelKeys>,
 }
 
 impl MakePostgresServerHandler {
     fn make(&self, addr: Option<SocketAddr>) -> PostgresServerHandler {
         let session = Arc::new(Session::new(addr, Channel::Postgres));
+        let connection_id = session.context().connection_id().unwrap_or_default();
         PostgresServerHandler {
             query_handler: self.query_handler.clone(),
             login_verifier: PgLoginVerifier::new(self.user_provider.clone()),
             force_tls: self.force_tls,
             param_provider: self.param_provider.clone(),
+            cancel_key: self.cancel_keys.register(connection_id),
 
             session: session.clone(),
             portal_store: Arc::new(MemPortalStore::new()),
@@ -18,11 +18,11 @@ use async_trait::async_trait;
 use common_error::ext::ErrorExt;
 use futures::{Sink, SinkExt};
 use metrics::increment_counter;
-use pgwire::api::auth::StartupHandler;
+use pgwire::api::auth::{ServerParameterProvider, StartupHandler};
 use pgwire::api::{auth, ClientInfo, PgWireConnectionState};
 use pgwire::error::{ErrorInfo, PgWireError, PgWireResult};
-use pgwire::messages::response::ErrorResponse;
-use pgwire::messages::startup::Authentication;
+use pgwire::messages::response::{ErrorResponse, ReadyForQuery, READY_STATUS_IDLE};
+use pgwire::messages::startup::{Authentication, BackendKeyData, ParameterStatus};
 use pgwire::messages::{PgWireBackendMessage, PgWireFrontendMessage};
 use session::context::UserInfo;
 use session::Session;
@@ -174,7 +174,7 @@ impl StartupHandler for PostgresServerHandler {
                         .await?;
                 } else {
                     set_client_info(client, &self.session);
-                    auth::finish_authentication(client, self.param_provider.as_ref()).await;
+                    self.finish_authentication(client).await?;
                 }
             }
             PgWireFrontendMessage::PasswordMessageFamily(pwd) => {
@@ -197,7 +197,7 @@ impl StartupHandler for PostgresServerHandler {
                     .await;
                 }
                 set_client_info(client, &self.session);
-                auth::finish_authentication(client, self.param_provider.as_ref()).await;
+                self.finish_authentication(client).await?;
             }
             _ => {}
         }
@@ -205,6 +205,45 @@ impl StartupHandler for PostgresServerHandler {
     }
 }
 
+impl PostgresServerHandler {
+    /// Like [auth::finish_authentication], but the backend key data carries the
+    /// connection id and its secret key, which the client sends back in cancel
+    /// requests.
+    async fn finish_authentication<C>(&self, client: &mut C) -> PgWireResult<()>
+    where
+        C: ClientInfo + Sink<PgWireBackendMessage> + Unpin + Send,
+        C::Error: Debug,
+        PgWireError: From<<C as Sink<PgWireBackendMessage>>::Error>,
+    {
+        client
+            .feed(PgWireBackendMessage::Authentication(Authentication::Ok))
+            .await?;
+        if let Some(parameters) = self.param_provider.server_parameters(client) {
+            for (name, value) in parameters {
+                client
+                    .feed(PgWireBackendMessage::ParameterStatus(ParameterStatus::new(
+                        name, value,
+                    )))
+                    .await?;
+            }
+        }
+        client
+            .feed(PgWireBackendMessage::BackendKeyData(BackendKeyData::new(
+                self.cancel_key.connection_id() as i32,
+                self.cancel_key.secret(),
+            )))
+            .await?;
+        client
+            .feed(PgWireBackendMessage::ReadyForQuery(ReadyForQuery::new(
+                READY_STATUS_IDLE,
+            )))
+            .await?;
+        client.flush().await?;
+        client.set_state(PgWireConnectionState::ReadyForQuery);
+        Ok(())
+    }
+}
+
 async fn send_error<C>(client: &mut C, level: &str, code: &str, message: String) -> PgWireResult<()>
 where
     C: ClientInfo + Sink<PgWireBackendMessage> + Unpin + Send,
@@ -0,0 +1,265 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cancel requests of PostgreSQL clients.
+//!
+//! A client receives the id and a secret key of its connection when it starts. To
+//! cancel the running query, it opens another connection and sends them in a
+//! cancel request, which is handled before the connection reaches pgwire.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use common_telemetry::debug;
+use session::process::ProcessManager;
+use tokio::net::TcpStream;
+
+/// Code in place of the protocol version of a startup message.
+const CANCEL_REQUEST_CODE: i32 = 80877102;
+/// Length of a cancel request, which also carries the connection id and the secret key.
+const CANCEL_REQUEST_LEN: usize = 16;
+/// Time to wait for the whole cancel request, a request split across packets
+/// may arrive in pieces.
+const CANCEL_REQUEST_TIMEOUT: Duration = Duration::from_millis(500);
+/// Interval to peek again while the request is incomplete.
+const PEEK_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Secret keys of open connections, by connection id.
+#[derive(Debug, Default)]
+pub(crate) struct CancelKeys {
+    keys: RwLock<HashMap<u32, i32>>,
+}
+
+impl CancelKeys {
+    /// Generates the secret key of the connection, which is valid until the
+    /// returned key is dropped.
+    pub(crate) fn register(self: &Arc<Self>, connection_id: u32) -> CancelKey {
+        let secret = rand::random::<i32>();
+        let _ = self.keys.write().unwrap().insert(connection_id, secret);
+        CancelKey {
+            keys: self.clone(),
+            connection_id,
+            secret,
+        }
+    }
+
+    /// Kills the running query of the connection if the secret key matches,
+    /// returns whether a query is killed.
+    pub(crate) fn cancel(&self, connection_id: u32, secret: i32) -> bool {
+        let matched = self.keys.read().unwrap().get(&connection_id) == Some(&secret);
+        matched && ProcessManager::global().kill(connection_id)
+    }
+}
+
+/// Secret key of a connection, removed from [CancelKeys] once dropped.
+#[derive(Debug)]
+pub(crate) struct CancelKey {
+    keys: Arc<CancelKeys>,
+    connection_id: u32,
+    secret: i32,
+}
+
+impl CancelKey {
+    pub(crate) fn connection_id(&self) -> u32 {
+        self.connection_id
+    }
+
+    pub(crate) fn secret(&self) -> i32 {
+        self.secret
+    }
+}
+
+impl Drop for CancelKey {
+    fn drop(&mut self) {
+        let mut keys = self.keys.keys.write().unwrap();
+        if keys.get(&self.connection_id) == Some(&self.secret) {
+            let _ = keys.remove(&self.connection_id);
+        }
+    }
+}
+
+/// Handles the cancel request if the connection starts with one, returns false
+/// if it doesn't. The server never responds to a cancel request.
+pub(crate) async fn try_handle_cancel_request(
+    stream: &TcpStream,
+    cancel_keys: &CancelKeys,
+) -> bool {
+    let mut buf = [0u8; CANCEL_REQUEST_LEN];
+    let peeked = tokio::time::timeout(
+        CANCEL_REQUEST_TIMEOUT,
+        peek_cancel_request(stream, &mut buf),
+    )
+    .await
+    .unwrap_or(false);
+    if !peeked {
+        return false;
+    }
+    let Some((connection_id, secret)) = parse_cancel_request(&buf) else {
+        return false;
+    };
+
+    let cancelled = cancel_keys.cancel(connection_id, secret);
+    debug!("PostgreSQL cancel request for connection {connection_id}, cancelled: {cancelled}");
+    true
+}
+
+/// Peeks until the whole cancel request is in `buf`, returns false on EOF,
+/// error or once the received bytes rule out a cancel request.
+async fn peek_cancel_request(stream: &TcpStream, buf: &mut [u8; CANCEL_REQUEST_LEN]) -> bool {
+    let mut header = [0u8; 8];
+    header[0..4].copy_from_slice(&(CANCEL_REQUEST_LEN as i32).to_be_bytes());
+    header[4..8].copy_from_slice(&CANCEL_REQUEST_CODE.to_be_bytes());
+
+    loop {
+        let n = match stream.peek(buf).await {
+            Ok(0) | Err(_) => return false,
+            Ok(n) => n,
+        };
+        let len = n.min(header.len());
+        if buf[..len] != header[..len] {
+            return false;
+        }
+        if n == CANCEL_REQUEST_LEN {
+            return true;
+        }
+        // Peek returns the buffered bytes at once, so waits for more.
+        tokio::time::sleep(PEEK_INTERVAL).await;
+    }
+}
+
+/// Parses the connection id and the secret key of a cancel request.
+fn parse_cancel_request(buf: &[u8; CANCEL_REQUEST_LEN]) -> Option<(u32, i32)> {
+    let read_i32 = |offset: usize| {
+        i32::from_be_bytes([
+            buf[offset],
+            buf[offset + 1],
+            buf[offset + 2],
+            buf[offset + 3],
+        ])
+    };
+    if read_i32(0) != CANCEL_REQUEST_LEN as i32 || read_i32(4) != CANCEL_REQUEST_CODE {
+        return None;
+    }
+    Some((read_i32(8) as u32, read_i32(12)))
+}
+
+#[cfg(test)]
+mod tests {
+    use session::context::QueryContext;
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    fn cancel_request(connection_id: u32, secret: i32) -> [u8; CANCEL_REQUEST_LEN] {
+        let mut buf = [0u8; CANCEL_REQUEST_LEN];
+        buf[0..4].copy_from_slice(&(CANCEL_REQUEST_LEN as i32).to_be_bytes());
+        buf[4..8].copy_from_slice(&CANCEL_REQUEST_CODE.to_be_bytes());
+        buf[8..12].copy_from_slice(&connection_id.to_be_bytes());
+        buf[12..16].copy_from_slice(&secret.to_be_bytes());
+        buf
+    }
+
+    #[test]
+    fn test_parse_cancel_request() {
+        let buf = cancel_request(42, -7);
+        assert_eq!(Some((42, -7)), parse_cancel_request(&buf));
+
+        // A startup message of protocol 3.0.
+        let mut buf = cancel_request(42, -7);
+        buf[4..8].copy_from_slice(&196608i32.to_be_bytes());
+        assert_eq!(None, parse_cancel_request(&buf));
+    }
+
+    #[test]
+    fn test_cancel_with_secret_key() {
+        let manager = ProcessManager::global();
+        let cancel_keys = Arc::new(CancelKeys::default());
+        let connection_id = manager.next_id();
+        let ctx = QueryContext::with("greptime", "public").with_connection(connection_id, None);
+        let process = manager.register(&ctx, "SELECT 1");
+
+        let key = cancel_keys.register(connection_id);
+        assert_eq!(connection_id, key.connection_id());
+        assert!(!cancel_keys.cancel(connection_id, key.secret().wrapping_add(1)));
+        assert!(!process.is_cancelled());
+        assert!(cancel_keys.cancel(connection_id, key.secret()));
+        assert!(process.is_cancelled());
+
+        // The key is gone with the connection.
+        let secret = key.secret();
+        drop(key);
+        assert!(!cancel_keys.cancel(connection_id, secret));
+    }
+
+    /// Connects to a local server, returns the client and the server side.
+    async fn connect() -> (TcpStream, TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (server, _) = listener.accept().await.unwrap();
+        (client, server)
+    }
+
+    #[tokio::test]
+    async fn test_cancel_request_in_pieces() {
+        let cancel_keys = Arc::new(CancelKeys::default());
+        let (mut client, server) = connect().await;
+
+        let buf = cancel_request(42, -7);
+        client.write_all(&buf[..6]).await.unwrap();
+        let handle = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            client.write_all(&buf[6..]).await.unwrap();
+            client
+        });
+        assert!(try_handle_cancel_request(&server, &cancel_keys).await);
+        let _client = handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_not_cancel_request() {
+        let cancel_keys = Arc::new(CancelKeys::default());
+
+        // A startup message is ruled out by its first bytes, without waiting
+        // for the rest.
+        let (mut client, server) = connect().await;
+        let mut buf = cancel_request(42, -7);
+        buf[4..8].copy_from_slice(&196608i32.to_be_bytes());
+        client.write_all(&buf[..8]).await.unwrap();
+        let start = std::time::Instant::now();
+        assert!(!try_handle_cancel_request(&server, &cancel_keys).await);
+        assert!(start.elapsed() < CANCEL_REQUEST_TIMEOUT);
+
+        // The client closes the connection before a whole request.
+        let (mut client, server) = connect().await;
+        client
+            .write_all(&cancel_request(42, -7)[..4])
+            .await
+            .unwrap();
+        drop(client);
+        assert!(!try_handle_cancel_request(&server, &cancel_keys).await);
+
+        // An incomplete request times out.
+        let (mut client, server) = connect().await;
+        client
+            .write_all(&cancel_request(42, -7)[..10])
+            .await
+            .unwrap();
+        assert!(!try_handle_cancel_request(&server, &cancel_keys).await);
+        drop(client);
+    }
+}
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -22,6 +23,7 @@ use common_telemetry::timer;
 use datatypes::schema::SchemaRef;
 use futures::{future, stream, Stream, StreamExt};
 use metrics::increment_counter;
+use parking_lot::Mutex;
 use pgwire::api::portal::{Format, Portal};
 use pgwire::api::query::{ExtendedQueryHandler, SimpleQueryHandler, StatementOrPortal};
 use pgwire::api::results::{DataRowEncoder, DescribeResponse, QueryResponse, Response, Tag};
@@ -140,9 +142,16 @@ where
     )))
 }
 
+/// Max number of parsed statements cached in a session.
+const STATEMENT_CACHE_CAPACITY: usize = 256;
+
 pub struct DefaultQueryParser {
     query_handler: ServerSqlQueryHandlerRef,
     session: Arc<Session>,
+    /// Planned statements of this session, keyed by database and SQL, with the
+    /// schema version they are planned at. Drivers like pgx prepare the same
+    /// statements over and over on a connection.
+    statements: Mutex<HashMap<(String, String), (u64, SqlPlan)>>,
 }
 
 impl DefaultQueryParser {
@@ -150,8 +159,35 @@ impl DefaultQueryParser {
         DefaultQueryParser {
             query_handler,
             session,
+            statements: Mutex::new(HashMap::new()),
         }
     }
+
+    /// Returns the cached plan of `key` if the schema of tables hasn't changed since
+    /// it's planned.
+    fn cached_statement(&self, key: &(String, String), version: u64) -> Option<SqlPlan> {
+        self.statements
+            .lock()
+            .get(key)
+            .filter(|(planned_version, _)| *planned_version == version)
+            .map(|(_, sql_plan)| sql_plan.clone())
+    }
+
+    fn cache_statement(&self, key: (String, String), version: u64, sql_plan: &SqlPlan) {
+        // only statements with logical plan are worth to cache
+        if sql_plan.plan.is_none() {
+            return;
+        }
+        let mut statements = self.statements.lock();
+        if statements.len() >= STATEMENT_CACHE_CAPACITY {
+            // Plans of older versions will never be used again.
+            statements.retain(|_, (planned_version, _)| *planned_version == version);
+            if statements.len() >= STATEMENT_CACHE_CAPACITY {
+                statements.clear();
+            }
+        }
+        let _ = statements.insert(key, (version, sql_plan.clone()));
+    }
 }
 
 #[async_trait]
@@ -160,6 +196,14 @@ impl QueryParser for DefaultQueryParser {
 
     async fn parse_sql(&self, sql: &str, _types: &[Type]) -> PgWireResult<Self::Statement> {
         increment_counter!(crate::metrics::METRIC_POSTGRES_PREPARED_COUNT);
+        let cache_key = (self.session.context().get_db_string(), sql.to_owned());
+        // The version must be read before planning, so the plan is dropped if the
+        // schema changes during planning.
+        let version = self.query_handler.schema_version();
+        if let Some(sql_plan) = version.and_then(|v| self.cached_statement(&cache_key, v)) {
+            return Ok(sql_plan);
+        }
+
         let mut stmts = ParserContext::create_with_dialect(sql, &PostgreSqlDialect {})
             .map_err(|e| PgWireError::ApiError(Box::new(e)))?;
         if stmts.len() != 1 {
@@ -186,11 +230,15 @@ impl QueryParser for DefaultQueryParser {
                 (None, None)
             };
 
-            Ok(SqlPlan {
+            let sql_plan = SqlPlan {
                 query: sql.to_owned(),
                 plan,
                 schema,
-            })
+            };
+            if let Some(version) = version {
+                self.cache_statement(cache_key, version, &sql_plan);
+            }
+            Ok(sql_plan)
         }
     }
 }
@@ -268,10 +316,17 @@ impl ExtendedQueryHandler for PostgresServerHandler {
     {
         let (param_types, sql_plan, format) = match target {
             StatementOrPortal::Statement(stmt) => {
-                let param_types = Some(stmt.parameter_types().clone());
-                // TODO(sunng87): return server inferenced param_types if client
-                // not specified
-                (param_types, stmt.statement(), &Format::UnifiedBinary)
+                let sql_plan = stmt.statement();
+                let server_param_types = if let Some(plan) = &sql_plan.plan {
+                    server_param_types(plan)?
+                } else {
+                    vec![]
+                };
+                let param_types = Some(merge_param_types(
+                    stmt.parameter_types(),
+                    &server_param_types,
+                ));
+                (param_types, sql_plan, &Format::UnifiedBinary)
             }
             StatementOrPortal::Portal(portal) => (
                 None,
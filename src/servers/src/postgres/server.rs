@@ -26,6 +26,7 @@ use pgwire::tokio::process_socket;
 use tokio;
 use tokio_rustls::TlsAcceptor;
 
+use super::cancel::try_handle_cancel_request;
 use super::{MakePostgresServerHandler, MakePostgresServerHandlerBuilder};
 use crate::auth::UserProviderRef;
 use crate::error::Result;
@@ -90,6 +91,12 @@ impl PostgresServer {
                         };
 
                         let _handle = io_runtime.spawn(async move {
+                            if try_handle_cancel_request(&io_stream, &handler_maker.cancel_keys)
+                                .await
+                            {
+                                return Ok(());
+                            }
+
                             increment_gauge!(crate::metrics::METRIC_POSTGRES_CONNECTIONS, 1.0);
                             let pg_handler = Arc::new(handler_maker.make(addr));
                             let r = process_socket(
@@ -14,15 +14,16 @@
 
 use std::ops::Deref;
 
-use chrono::{NaiveDate, NaiveDateTime};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Utc};
 use datafusion_common::ScalarValue;
-use datatypes::prelude::{ConcreteDataType, Value};
+use datatypes::arrow::compute::cast;
+use datatypes::prelude::{ConcreteDataType, DataType, Value};
 use datatypes::schema::Schema;
-use datatypes::types::TimestampType;
 use pgwire::api::portal::{Format, Portal};
-use pgwire::api::results::{DataRowEncoder, FieldInfo};
+use pgwire::api::results::{DataRowEncoder, FieldFormat, FieldInfo};
 use pgwire::api::Type;
 use pgwire::error::{ErrorInfo, PgWireError, PgWireResult};
+use postgres_types::FromSql;
 use query::plan::LogicalPlan;
 
 use crate::error::{self, Error, Result};
@@ -141,345 +142,289 @@ pub(super) fn type_pg_to_gt(origin: &Type) -> Result<ConcreteDataType> {
     }
 }
 
-pub(super) fn parameter_to_string(portal: &Portal<SqlPlan>, idx: usize) -> PgWireResult<String> {
-    // the index is managed from portal's parameters count so it's safe to
-    // unwrap here.
-    let param_type = portal.statement().parameter_types().get(idx).unwrap();
-    match param_type {
-        &Type::VARCHAR | &Type::TEXT => Ok(format!(
-            "'{}'",
-            portal.parameter::<String>(idx)?.as_deref().unwrap_or("")
-        )),
-        &Type::BOOL => Ok(portal
-            .parameter::<bool>(idx)?
-            .map(|v| v.to_string())
-            .unwrap_or_else(|| "".to_owned())),
-        &Type::INT4 => Ok(portal
-            .parameter::<i32>(idx)?
-            .map(|v| v.to_string())
-            .unwrap_or_else(|| "".to_owned())),
-        &Type::INT8 => Ok(portal
-            .parameter::<i64>(idx)?
-            .map(|v| v.to_string())
-            .unwrap_or_else(|| "".to_owned())),
-        &Type::FLOAT4 => Ok(portal
-            .parameter::<f32>(idx)?
-            .map(|v| v.to_string())
-            .unwrap_or_else(|| "".to_owned())),
-        &Type::FLOAT8 => Ok(portal
-            .parameter::<f64>(idx)?
-            .map(|v| v.to_string())
-            .unwrap_or_else(|| "".to_owned())),
-        &Type::DATE => Ok(portal
-            .parameter::<NaiveDate>(idx)?
-            .map(|v| v.format("%Y-%m-%d").to_string())
-            .unwrap_or_else(|| "".to_owned())),
-        &Type::TIMESTAMP => Ok(portal
-            .parameter::<NaiveDateTime>(idx)?
-            .map(|v| v.format("%Y-%m-%d %H:%M:%S%.6f").to_string())
-            .unwrap_or_else(|| "".to_owned())),
-        _ => Err(invalid_parameter_error(
-            "unsupported_parameter_type",
-            Some(&param_type.to_string()),
-        )),
+/// Maps an inferred parameter type to the pg type described to clients.
+/// `CHAR` is a single byte in pg, so small integers are described as `INT2`.
+fn param_type_gt_to_pg(origin: &ConcreteDataType) -> Option<Type> {
+    match origin {
+        ConcreteDataType::Int8(_) | ConcreteDataType::UInt8(_) => Some(Type::INT2),
+        _ => type_gt_to_pg(origin).ok(),
     }
 }
 
-pub(super) fn invalid_parameter_error(msg: &str, detail: Option<&str>) -> PgWireError {
-    let mut error_info = ErrorInfo::new("ERROR".to_owned(), "22023".to_owned(), msg.to_owned());
-    error_info.set_detail(detail.map(|s| s.to_owned()));
-    PgWireError::UserError(Box::new(error_info))
+/// Returns parameter types inferred from the logical plan, ordered by their
+/// placeholder index (`$1`, `$2`, ...).
+pub(super) fn server_param_types(
+    plan: &LogicalPlan,
+) -> PgWireResult<Vec<Option<ConcreteDataType>>> {
+    let param_types = plan
+        .get_param_types()
+        .map_err(|e| PgWireError::ApiError(Box::new(e)))?;
+
+    Ok((1..=param_types.len())
+        .map(|idx| param_types.get(&format!("${idx}")).cloned().flatten())
+        .collect())
+}
+
+/// Merges parameter types specified by client with the ones inferred on server
+/// side. Types the client left unspecified (or `UNKNOWN`) are filled with the
+/// server inferred ones.
+pub(super) fn merge_param_types(
+    client_types: &[Type],
+    server_types: &[Option<ConcreteDataType>],
+) -> Vec<Type> {
+    (0..client_types.len().max(server_types.len()))
+        .map(|idx| match client_types.get(idx) {
+            Some(client_type) if *client_type != Type::UNKNOWN => client_type.clone(),
+            _ => server_types
+                .get(idx)
+                .and_then(|t| t.as_ref())
+                .and_then(param_type_gt_to_pg)
+                .unwrap_or(Type::UNKNOWN),
+        })
+        .collect()
+}
+
+/// Parses parameter sent in text format.
+trait FromTextParameter: Sized {
+    fn from_text(text: &str) -> Option<Self>;
+}
+
+macro_rules! impl_from_text_parameter {
+    ($($t: ty),*) => {
+        $(
+            impl FromTextParameter for $t {
+                fn from_text(text: &str) -> Option<Self> {
+                    text.trim().parse().ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_from_text_parameter!(i8, i16, i32, i64, f32, f64);
+
+impl FromTextParameter for bool {
+    fn from_text(text: &str) -> Option<Self> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "t" | "true" | "y" | "yes" | "on" | "1" => Some(true),
+            "f" | "false" | "n" | "no" | "off" | "0" => Some(false),
+            _ => None,
+        }
+    }
+}
+
+impl FromTextParameter for String {
+    fn from_text(text: &str) -> Option<Self> {
+        Some(text.to_owned())
+    }
+}
+
+impl FromTextParameter for Vec<u8> {
+    fn from_text(text: &str) -> Option<Self> {
+        // bytea in text format is hex encoded with a `\x` prefix
+        match text.strip_prefix("\\x") {
+            Some(hex) => hex::decode(hex).ok(),
+            None => Some(text.as_bytes().to_vec()),
+        }
+    }
+}
+
+impl FromTextParameter for NaiveDate {
+    fn from_text(text: &str) -> Option<Self> {
+        NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d").ok()
+    }
+}
+
+impl FromTextParameter for NaiveDateTime {
+    fn from_text(text: &str) -> Option<Self> {
+        let text = text.trim();
+        NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f")
+            .or_else(|_| NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M:%S%.f"))
+            .ok()
+    }
 }
 
-fn to_timestamp_scalar_value<T>(
-    data: Option<T>,
-    unit: &TimestampType,
-    ctype: &ConcreteDataType,
-) -> PgWireResult<ScalarValue>
+impl FromTextParameter for DateTime<Utc> {
+    fn from_text(text: &str) -> Option<Self> {
+        let text = text.trim();
+        DateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f%#z")
+            .or_else(|_| DateTime::parse_from_rfc3339(text))
+            .map(|ts| ts.with_timezone(&Utc))
+            .ok()
+            .or_else(|| NaiveDateTime::from_text(text).map(|ts| DateTime::from_utc(ts, Utc)))
+    }
+}
+
+/// Decodes the parameter at `idx` as `pg_type`, in the format the client
+/// sent it. `pg_type` may differ from the statement's parameter type when the
+/// type is inferred on server side.
+fn decode_parameter<T>(
+    portal: &Portal<SqlPlan>,
+    idx: usize,
+    pg_type: &Type,
+) -> PgWireResult<Option<T>>
 where
-    T: Into<i64>,
+    T: for<'a> FromSql<'a> + FromTextParameter,
 {
-    if let Some(n) = data {
-        Value::Timestamp(unit.create_timestamp(n.into()))
-            .try_to_scalar_value(ctype)
-            .map_err(|e| PgWireError::ApiError(Box::new(e)))
-    } else {
-        Ok(ScalarValue::Null)
+    let Some(Some(bytes)) = portal.parameters().get(idx) else { return Ok(None) };
+
+    let value = match portal.parameter_format().format_for(idx) {
+        FieldFormat::Binary => T::from_sql(pg_type, bytes).ok(),
+        FieldFormat::Text => std::str::from_utf8(bytes).ok().and_then(T::from_text),
+    };
+    value.map(Some).ok_or_else(|| {
+        invalid_parameter_error(
+            "invalid_parameter_value",
+            Some(&format!(
+                "Failed to decode parameter ${} as {}",
+                idx + 1,
+                pg_type
+            )),
+        )
+    })
+}
+
+/// Decodes the parameter at `idx` into a scalar value of the datatype that
+/// naturally matches `pg_type`.
+fn parameter_to_scalar_value(
+    portal: &Portal<SqlPlan>,
+    idx: usize,
+    pg_type: &Type,
+) -> PgWireResult<ScalarValue> {
+    let value = match pg_type {
+        &Type::BOOL => ScalarValue::Boolean(decode_parameter(portal, idx, pg_type)?),
+        &Type::CHAR => ScalarValue::Int8(decode_parameter(portal, idx, pg_type)?),
+        &Type::INT2 => ScalarValue::Int16(decode_parameter(portal, idx, pg_type)?),
+        &Type::INT4 => ScalarValue::Int32(decode_parameter(portal, idx, pg_type)?),
+        &Type::INT8 => ScalarValue::Int64(decode_parameter(portal, idx, pg_type)?),
+        &Type::FLOAT4 => ScalarValue::Float32(decode_parameter(portal, idx, pg_type)?),
+        &Type::FLOAT8 => ScalarValue::Float64(decode_parameter(portal, idx, pg_type)?),
+        // parameters of unknown type are sent as text by clients
+        &Type::VARCHAR | &Type::TEXT | &Type::UNKNOWN => {
+            ScalarValue::Utf8(decode_parameter(portal, idx, pg_type)?)
+        }
+        &Type::BYTEA => ScalarValue::Binary(decode_parameter(portal, idx, pg_type)?),
+        &Type::DATE => ScalarValue::Date32(
+            decode_parameter::<NaiveDate>(portal, idx, pg_type)?
+                .map(|d| (d - NaiveDate::from_ymd_opt(1970, 1, 1).unwrap()).num_days() as i32),
+        ),
+        &Type::TIMESTAMP => ScalarValue::TimestampMicrosecond(
+            decode_parameter::<NaiveDateTime>(portal, idx, pg_type)?
+                .map(|ts| ts.timestamp_micros()),
+            None,
+        ),
+        &Type::TIMESTAMPTZ => ScalarValue::TimestampMicrosecond(
+            decode_parameter::<DateTime<Utc>>(portal, idx, pg_type)?
+                .map(|ts| ts.timestamp_micros()),
+            None,
+        ),
+        _ => {
+            return Err(invalid_parameter_error(
+                "unsupported_parameter_type",
+                Some(&format!("Found type: {}", pg_type)),
+            ))
+        }
+    };
+    Ok(value)
+}
+
+/// Casts a decoded parameter value to the parameter type inferred on server side.
+fn cast_scalar_value(
+    value: ScalarValue,
+    pg_type: &Type,
+    server_type: &ConcreteDataType,
+) -> PgWireResult<ScalarValue> {
+    let target_type = server_type.as_arrow_type();
+    if value.get_datatype() == target_type {
+        return Ok(value);
+    }
+
+    let invalid_type = || {
+        invalid_parameter_error(
+            "invalid_parameter_type",
+            Some(&format!("Expected: {}, found: {}", server_type, pg_type)),
+        )
+    };
+    let casted = cast(&value.to_array(), &target_type).map_err(|_| invalid_type())?;
+    let casted = ScalarValue::try_from_array(&casted, 0).map_err(|_| invalid_type())?;
+    // cast turns values it can't convert into null
+    if casted.is_null() && !value.is_null() {
+        return Err(invalid_type());
+    }
+    Ok(casted)
+}
+
+fn scalar_value_to_sql_literal(value: &ScalarValue) -> String {
+    let quote = |s: &str| format!("'{}'", s.replace('\'', "''"));
+    if value.is_null() {
+        return "NULL".to_owned();
+    }
+    match value {
+        ScalarValue::Utf8(Some(s)) => quote(s),
+        ScalarValue::Binary(Some(b)) => quote(&String::from_utf8_lossy(b)),
+        ScalarValue::Date32(Some(days)) => quote(
+            &(NaiveDate::from_ymd_opt(1970, 1, 1).unwrap() + Duration::days(*days as i64))
+                .format("%Y-%m-%d")
+                .to_string(),
+        ),
+        ScalarValue::TimestampMicrosecond(Some(us), _) => NaiveDateTime::from_timestamp_micros(*us)
+            .map(|ts| quote(&ts.format("%Y-%m-%d %H:%M:%S%.6f").to_string()))
+            .unwrap_or_else(|| us.to_string()),
+        _ => value.to_string(),
     }
 }
 
+pub(super) fn parameter_to_string(portal: &Portal<SqlPlan>, idx: usize) -> PgWireResult<String> {
+    let param_type = portal
+        .statement()
+        .parameter_types()
+        .get(idx)
+        .cloned()
+        .unwrap_or(Type::UNKNOWN);
+    let value = parameter_to_scalar_value(portal, idx, &param_type)?;
+    Ok(scalar_value_to_sql_literal(&value))
+}
+
+pub(super) fn invalid_parameter_error(msg: &str, detail: Option<&str>) -> PgWireError {
+    let mut error_info = ErrorInfo::new("ERROR".to_owned(), "22023".to_owned(), msg.to_owned());
+    error_info.set_detail(detail.map(|s| s.to_owned()));
+    PgWireError::UserError(Box::new(error_info))
+}
+
 pub(super) fn parameters_to_scalar_values(
     plan: &LogicalPlan,
     portal: &Portal<SqlPlan>,
 ) -> PgWireResult<Vec<ScalarValue>> {
     let param_count = portal.parameter_len();
-    let mut results = Vec::with_capacity(param_count);
-
-    let client_param_types = portal.statement().parameter_types();
-    let param_types = plan
-        .get_param_types()
-        .map_err(|e| PgWireError::ApiError(Box::new(e)))?;
+    let server_param_types = server_param_types(plan)?;
 
-    // ensure parameter count consistent for: client parameter types, server
-    // parameter types and parameter count
-    if param_types.len() != param_count {
-        return Err(invalid_parameter_error(
-            "invalid_parameter_count",
-            Some(&format!(
-                "Expected: {}, found: {}",
-                param_types.len(),
-                param_count
-            )),
-        ));
-    }
-    if client_param_types.len() != param_count {
+    // ensure parameter count consistent for server parameter types and
+    // parameter count. Client parameter types are optional, the missing ones
+    // are inferred on server side.
+    if server_param_types.len() != param_count {
         return Err(invalid_parameter_error(
             "invalid_parameter_count",
             Some(&format!(
                 "Expected: {}, found: {}",
-                client_param_types.len(),
+                server_param_types.len(),
                 param_count
             )),
         ));
     }
+    let param_types = merge_param_types(portal.statement().parameter_types(), &server_param_types);
 
-    for (idx, client_type) in client_param_types.iter().enumerate() {
-        let Some(Some(server_type)) = param_types.get(&format!("${}", idx + 1)) else { continue };
-        let value = match client_type {
-            &Type::VARCHAR | &Type::TEXT => {
-                let data = portal.parameter::<String>(idx)?;
-                match server_type {
-                    ConcreteDataType::String(_) => ScalarValue::Utf8(data),
-                    _ => {
-                        return Err(invalid_parameter_error(
-                            "invalid_parameter_type",
-                            Some(&format!(
-                                "Expected: {}, found: {}",
-                                server_type, client_type
-                            )),
-                        ))
-                    }
-                }
-            }
-            &Type::BOOL => {
-                let data = portal.parameter::<bool>(idx)?;
-                match server_type {
-                    ConcreteDataType::Boolean(_) => ScalarValue::Boolean(data),
-                    _ => {
-                        return Err(invalid_parameter_error(
-                            "invalid_parameter_type",
-                            Some(&format!(
-                                "Expected: {}, found: {}",
-                                server_type, client_type
-                            )),
-                        ))
-                    }
-                }
-            }
-            &Type::INT2 => {
-                let data = portal.parameter::<i16>(idx)?;
-                match server_type {
-                    ConcreteDataType::Int8(_) => ScalarValue::Int8(data.map(|n| n as i8)),
-                    ConcreteDataType::Int16(_) => ScalarValue::Int16(data),
-                    ConcreteDataType::Int32(_) => ScalarValue::Int32(data.map(|n| n as i32)),
-                    ConcreteDataType::Int64(_) => ScalarValue::Int64(data.map(|n| n as i64)),
-                    ConcreteDataType::UInt8(_) => ScalarValue::UInt8(data.map(|n| n as u8)),
-                    ConcreteDataType::UInt16(_) => ScalarValue::UInt16(data.map(|n| n as u16)),
-                    ConcreteDataType::UInt32(_) => ScalarValue::UInt32(data.map(|n| n as u32)),
-                    ConcreteDataType::UInt64(_) => ScalarValue::UInt64(data.map(|n| n as u64)),
-                    ConcreteDataType::Timestamp(unit) => {
-                        to_timestamp_scalar_value(data, unit, server_type)?
-                    }
-                    ConcreteDataType::DateTime(_) => ScalarValue::Date64(data.map(|d| d as i64)),
-                    _ => {
-                        return Err(invalid_parameter_error(
-                            "invalid_parameter_type",
-                            Some(&format!(
-                                "Expected: {}, found: {}",
-                                server_type, client_type
-                            )),
-                        ))
-                    }
-                }
-            }
-            &Type::INT4 => {
-                let data = portal.parameter::<i32>(idx)?;
-                match server_type {
-                    ConcreteDataType::Int8(_) => ScalarValue::Int8(data.map(|n| n as i8)),
-                    ConcreteDataType::Int16(_) => ScalarValue::Int16(data.map(|n| n as i16)),
-                    ConcreteDataType::Int32(_) => ScalarValue::Int32(data),
-                    ConcreteDataType::Int64(_) => ScalarValue::Int64(data.map(|n| n as i64)),
-                    ConcreteDataType::UInt8(_) => ScalarValue::UInt8(data.map(|n| n as u8)),
-                    ConcreteDataType::UInt16(_) => ScalarValue::UInt16(data.map(|n| n as u16)),
-                    ConcreteDataType::UInt32(_) => ScalarValue::UInt32(data.map(|n| n as u32)),
-                    ConcreteDataType::UInt64(_) => ScalarValue::UInt64(data.map(|n| n as u64)),
-                    ConcreteDataType::Timestamp(unit) => {
-                        to_timestamp_scalar_value(data, unit, server_type)?
-                    }
-                    ConcreteDataType::DateTime(_) => ScalarValue::Date64(data.map(|d| d as i64)),
-                    _ => {
-                        return Err(invalid_parameter_error(
-                            "invalid_parameter_type",
-                            Some(&format!(
-                                "Expected: {}, found: {}",
-                                server_type, client_type
-                            )),
-                        ))
-                    }
-                }
-            }
-            &Type::INT8 => {
-                let data = portal.parameter::<i64>(idx)?;
-                match server_type {
-                    ConcreteDataType::Int8(_) => ScalarValue::Int8(data.map(|n| n as i8)),
-                    ConcreteDataType::Int16(_) => ScalarValue::Int16(data.map(|n| n as i16)),
-                    ConcreteDataType::Int32(_) => ScalarValue::Int32(data.map(|n| n as i32)),
-                    ConcreteDataType::Int64(_) => ScalarValue::Int64(data),
-                    ConcreteDataType::UInt8(_) => ScalarValue::UInt8(data.map(|n| n as u8)),
-                    ConcreteDataType::UInt16(_) => ScalarValue::UInt16(data.map(|n| n as u16)),
-                    ConcreteDataType::UInt32(_) => ScalarValue::UInt32(data.map(|n| n as u32)),
-                    ConcreteDataType::UInt64(_) => ScalarValue::UInt64(data.map(|n| n as u64)),
-                    ConcreteDataType::Timestamp(unit) => {
-                        to_timestamp_scalar_value(data, unit, server_type)?
-                    }
-                    ConcreteDataType::DateTime(_) => ScalarValue::Date64(data),
-                    _ => {
-                        return Err(invalid_parameter_error(
-                            "invalid_parameter_type",
-                            Some(&format!(
-                                "Expected: {}, found: {}",
-                                server_type, client_type
-                            )),
-                        ))
-                    }
-                }
-            }
-            &Type::FLOAT4 => {
-                let data = portal.parameter::<f32>(idx)?;
-                match server_type {
-                    ConcreteDataType::Int8(_) => ScalarValue::Int8(data.map(|n| n as i8)),
-                    ConcreteDataType::Int16(_) => ScalarValue::Int16(data.map(|n| n as i16)),
-                    ConcreteDataType::Int32(_) => ScalarValue::Int32(data.map(|n| n as i32)),
-                    ConcreteDataType::Int64(_) => ScalarValue::Int64(data.map(|n| n as i64)),
-                    ConcreteDataType::UInt8(_) => ScalarValue::UInt8(data.map(|n| n as u8)),
-                    ConcreteDataType::UInt16(_) => ScalarValue::UInt16(data.map(|n| n as u16)),
-                    ConcreteDataType::UInt32(_) => ScalarValue::UInt32(data.map(|n| n as u32)),
-                    ConcreteDataType::UInt64(_) => ScalarValue::UInt64(data.map(|n| n as u64)),
-                    ConcreteDataType::Float32(_) => ScalarValue::Float32(data),
-                    ConcreteDataType::Float64(_) => ScalarValue::Float64(data.map(|n| n as f64)),
-                    _ => {
-                        return Err(invalid_parameter_error(
-                            "invalid_parameter_type",
-                            Some(&format!(
-                                "Expected: {}, found: {}",
-                                server_type, client_type
-                            )),
-                        ))
-                    }
-                }
-            }
-            &Type::FLOAT8 => {
-                let data = portal.parameter::<f64>(idx)?;
-                match server_type {
-                    ConcreteDataType::Int8(_) => ScalarValue::Int8(data.map(|n| n as i8)),
-                    ConcreteDataType::Int16(_) => ScalarValue::Int16(data.map(|n| n as i16)),
-                    ConcreteDataType::Int32(_) => ScalarValue::Int32(data.map(|n| n as i32)),
-                    ConcreteDataType::Int64(_) => ScalarValue::Int64(data.map(|n| n as i64)),
-                    ConcreteDataType::UInt8(_) => ScalarValue::UInt8(data.map(|n| n as u8)),
-                    ConcreteDataType::UInt16(_) => ScalarValue::UInt16(data.map(|n| n as u16)),
-                    ConcreteDataType::UInt32(_) => ScalarValue::UInt32(data.map(|n| n as u32)),
-                    ConcreteDataType::UInt64(_) => ScalarValue::UInt64(data.map(|n| n as u64)),
-                    ConcreteDataType::Float32(_) => ScalarValue::Float32(data.map(|n| n as f32)),
-                    ConcreteDataType::Float64(_) => ScalarValue::Float64(data),
-                    _ => {
-                        return Err(invalid_parameter_error(
-                            "invalid_parameter_type",
-                            Some(&format!(
-                                "Expected: {}, found: {}",
-                                server_type, client_type
-                            )),
-                        ))
-                    }
-                }
-            }
-            &Type::TIMESTAMP => {
-                let data = portal.parameter::<NaiveDateTime>(idx)?;
-                match server_type {
-                    ConcreteDataType::Timestamp(unit) => match *unit {
-                        TimestampType::Second(_) => {
-                            ScalarValue::TimestampSecond(data.map(|ts| ts.timestamp()), None)
-                        }
-                        TimestampType::Millisecond(_) => ScalarValue::TimestampMillisecond(
-                            data.map(|ts| ts.timestamp_millis()),
-                            None,
-                        ),
-                        TimestampType::Microsecond(_) => ScalarValue::TimestampMicrosecond(
-                            data.map(|ts| ts.timestamp_micros()),
-                            None,
-                        ),
-                        TimestampType::Nanosecond(_) => ScalarValue::TimestampNanosecond(
-                            data.map(|ts| ts.timestamp_micros()),
-                            None,
-                        ),
-                    },
-                    ConcreteDataType::DateTime(_) => {
-                        ScalarValue::Date64(data.map(|d| d.timestamp_millis()))
-                    }
-                    _ => {
-                        return Err(invalid_parameter_error(
-                            "invalid_parameter_type",
-                            Some(&format!(
-                                "Expected: {}, found: {}",
-                                server_type, client_type
-                            )),
-                        ))
-                    }
-                }
-            }
-            &Type::DATE => {
-                let data = portal.parameter::<NaiveDate>(idx)?;
-                match server_type {
-                    ConcreteDataType::Date(_) => ScalarValue::Date32(data.map(|d| {
-                        (d - NaiveDate::from_ymd_opt(1970, 1, 1).unwrap()).num_days() as i32
-                    })),
-                    _ => {
-                        return Err(invalid_parameter_error(
-                            "invalid_parameter_type",
-                            Some(&format!(
-                                "Expected: {}, found: {}",
-                                server_type, client_type
-                            )),
-                        ));
-                    }
-                }
-            }
-            &Type::BYTEA => {
-                let data = portal.parameter::<Vec<u8>>(idx)?;
-                match server_type {
-                    ConcreteDataType::String(_) => {
-                        ScalarValue::Utf8(data.map(|d| String::from_utf8_lossy(&d).to_string()))
-                    }
-                    ConcreteDataType::Binary(_) => ScalarValue::Binary(data),
-                    _ => {
-                        return Err(invalid_parameter_error(
-                            "invalid_parameter_type",
-                            Some(&format!(
-                                "Expected: {}, found: {}",
-                                server_type, client_type
-                            )),
-                        ));
-                    }
-                }
+    server_param_types
+        .iter()
+        .zip(param_types.iter())
+        .enumerate()
+        .map(|(idx, (server_type, pg_type))| {
+            let value = parameter_to_scalar_value(portal, idx, pg_type)?;
+            match server_type {
+                Some(server_type) => cast_scalar_value(value, pg_type, server_type),
+                None => Ok(value),
             }
-            _ => Err(invalid_parameter_error(
-                "unsupported_parameter_value",
-                Some(&format!("Found type: {}", client_type)),
-            ))?,
-        };
-        results.push(value);
-    }
-
-    Ok(results)
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -709,4 +654,125 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_merge_param_types() {
+        let server_types = vec![
+            Some(ConcreteDataType::int8_datatype()),
+            Some(ConcreteDataType::timestamp_millisecond_datatype()),
+            Some(ConcreteDataType::string_datatype()),
+            None,
+        ];
+
+        assert_eq!(
+            vec![Type::INT2, Type::TIMESTAMP, Type::VARCHAR, Type::UNKNOWN],
+            merge_param_types(&[], &server_types)
+        );
+        assert_eq!(
+            vec![Type::INT8, Type::TIMESTAMP, Type::TEXT, Type::UNKNOWN],
+            merge_param_types(&[Type::INT8, Type::UNKNOWN, Type::TEXT], &server_types)
+        );
+    }
+
+    #[test]
+    fn test_text_parameter() {
+        assert_eq!(Some(42i32), i32::from_text("42"));
+        assert_eq!(None, i64::from_text("greptime"));
+        assert_eq!(Some(true), bool::from_text("t"));
+        assert_eq!(Some(false), bool::from_text("false"));
+        assert_eq!(Some(vec![0xde, 0xad]), Vec::<u8>::from_text("\\xdead"));
+        assert_eq!(
+            NaiveDate::from_ymd_opt(2023, 6, 1),
+            NaiveDate::from_text("2023-06-01")
+        );
+
+        let ts = NaiveDate::from_ymd_opt(2023, 6, 1)
+            .unwrap()
+            .and_hms_micro_opt(10, 20, 30, 123456)
+            .unwrap();
+        assert_eq!(
+            Some(ts),
+            NaiveDateTime::from_text("2023-06-01 10:20:30.123456")
+        );
+        assert_eq!(
+            Some(DateTime::<Utc>::from_utc(ts, Utc)),
+            DateTime::<Utc>::from_text("2023-06-01 18:20:30.123456+08")
+        );
+    }
+
+    #[test]
+    fn test_cast_scalar_value() {
+        let micros = 1_685_614_830_123_456i64;
+        let value = ScalarValue::TimestampMicrosecond(Some(micros), None);
+
+        assert_eq!(
+            ScalarValue::TimestampNanosecond(Some(micros * 1000), None),
+            cast_scalar_value(
+                value.clone(),
+                &Type::TIMESTAMP,
+                &ConcreteDataType::timestamp_nanosecond_datatype()
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            ScalarValue::TimestampMillisecond(Some(micros / 1000), None),
+            cast_scalar_value(
+                value,
+                &Type::TIMESTAMP,
+                &ConcreteDataType::timestamp_millisecond_datatype()
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            ScalarValue::UInt32(Some(42)),
+            cast_scalar_value(
+                ScalarValue::Int64(Some(42)),
+                &Type::INT8,
+                &ConcreteDataType::uint32_datatype()
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            ScalarValue::Int32(None),
+            cast_scalar_value(
+                ScalarValue::Utf8(None),
+                &Type::UNKNOWN,
+                &ConcreteDataType::int32_datatype()
+            )
+            .unwrap()
+        );
+        assert!(cast_scalar_value(
+            ScalarValue::Utf8(Some("greptime".to_string())),
+            &Type::VARCHAR,
+            &ConcreteDataType::int32_datatype()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_scalar_value_to_sql_literal() {
+        assert_eq!(
+            "NULL",
+            scalar_value_to_sql_literal(&ScalarValue::Int32(None))
+        );
+        assert_eq!(
+            "42",
+            scalar_value_to_sql_literal(&ScalarValue::Int32(Some(42)))
+        );
+        assert_eq!(
+            "'it''s'",
+            scalar_value_to_sql_literal(&ScalarValue::Utf8(Some("it's".to_string())))
+        );
+        assert_eq!(
+            "'2023-06-01'",
+            scalar_value_to_sql_literal(&ScalarValue::Date32(Some(19509)))
+        );
+        assert_eq!(
+            "'2023-06-01 10:20:30.123456'",
+            scalar_value_to_sql_literal(&ScalarValue::TimestampMicrosecond(
+                Some(1_685_614_830_123_456),
+                None
+            ))
+        );
+    }
 }
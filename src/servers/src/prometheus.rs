@@ -41,7 +41,7 @@ use query::parser::{PromQuery, DEFAULT_LOOKBACK_STRING};
 use schemars::JsonSchema;
 use serde::de::{self, MapAccess, Visitor};
 use serde::{Deserialize, Serialize};
-use session::context::{QueryContext, QueryContextRef};
+use session::context::{Channel, QueryContext, QueryContextRef};
 use snafu::{ensure, Location, OptionExt, ResultExt};
 use tokio::sync::oneshot::Sender;
 use tokio::sync::{oneshot, Mutex};
@@ -443,7 +443,7 @@ pub async fn instant_query(
     let db = &params.db.unwrap_or(DEFAULT_SCHEMA_NAME.to_string());
     let (catalog, schema) = crate::parse_catalog_and_schema_from_client_database_name(db);
 
-    let query_ctx = QueryContext::with(catalog, schema);
+    let query_ctx = QueryContext::with(catalog, schema).with_channel(Channel::Prometheus);
 
     let result = handler.do_query(&prom_query, Arc::new(query_ctx)).await;
     let (metric_name, result_type) = match retrieve_metric_name_and_result_type(&prom_query.query) {
@@ -481,7 +481,7 @@ pub async fn range_query(
     let db = &params.db.unwrap_or(DEFAULT_SCHEMA_NAME.to_string());
     let (catalog, schema) = crate::parse_catalog_and_schema_from_client_database_name(db);
 
-    let query_ctx = QueryContext::with(catalog, schema);
+    let query_ctx = QueryContext::with(catalog, schema).with_channel(Channel::Prometheus);
 
     let result = handler.do_query(&prom_query, Arc::new(query_ctx)).await;
     let metric_name = match retrieve_metric_name_and_result_type(&prom_query.query) {
@@ -562,7 +562,7 @@ pub async fn labels_query(
 
     let db = &params.db.unwrap_or(DEFAULT_SCHEMA_NAME.to_string());
     let (catalog, schema) = crate::parse_catalog_and_schema_from_client_database_name(db);
-    let query_ctx = Arc::new(QueryContext::with(catalog, schema));
+    let query_ctx = Arc::new(QueryContext::with(catalog, schema).with_channel(Channel::Prometheus));
 
     let mut labels = HashSet::new();
     let _ = labels.insert(METRIC_NAME.to_string());
@@ -788,7 +788,7 @@ pub async fn label_values_query(
     let end = params.end.unwrap_or_else(current_time_rfc3339);
     let db = &params.db.unwrap_or(DEFAULT_SCHEMA_NAME.to_string());
     let (catalog, schema) = crate::parse_catalog_and_schema_from_client_database_name(db);
-    let query_ctx = Arc::new(QueryContext::with(catalog, schema));
+    let query_ctx = Arc::new(QueryContext::with(catalog, schema).with_channel(Channel::Prometheus));
 
     let mut label_values = HashSet::new();
 
@@ -909,7 +909,7 @@ pub async fn series_query(
 
     let db = &params.db.unwrap_or(DEFAULT_SCHEMA_NAME.to_string());
     let (catalog, schema) = super::parse_catalog_and_schema_from_client_database_name(db);
-    let query_ctx = Arc::new(QueryContext::with(catalog, schema));
+    let query_ctx = Arc::new(QueryContext::with(catalog, schema).with_channel(Channel::Prometheus));
 
     let mut series = Vec::new();
     for query in queries {
@@ -30,22 +30,41 @@ use std::sync::Arc;
 
 use api::prom_store::remote::{ReadRequest, WriteRequest};
 use async_trait::async_trait;
-use common_query::Output;
+use common_query::{Output, ScriptSchedule};
+use opentelemetry_proto::tonic::collector::metrics::v1::{
+    ExportMetricsServiceRequest, ExportMetricsServiceResponse,
+};
 use session::context::QueryContextRef;
 
+use crate::elasticsearch::BulkRequest;
 use crate::error::Result;
+use crate::influxdb::influxql::Statement;
+use crate::influxdb::query::{Epoch, Series};
 use crate::influxdb::InfluxdbRequest;
+use crate::loki::LokiRequest;
 use crate::opentsdb::codec::DataPoint;
+use crate::opentsdb::query::{
+    LookupRequest, LookupResult, QueryRequest, QueryResponse, SuggestRequest,
+};
 use crate::prom_store::Metrics;
 
 pub type OpentsdbProtocolHandlerRef = Arc<dyn OpentsdbProtocolHandler + Send + Sync>;
 pub type InfluxdbLineProtocolHandlerRef = Arc<dyn InfluxdbLineProtocolHandler + Send + Sync>;
 pub type PromStoreProtocolHandlerRef = Arc<dyn PromStoreProtocolHandler + Send + Sync>;
+pub type OpenTelemetryProtocolHandlerRef = Arc<dyn OpenTelemetryProtocolHandler + Send + Sync>;
+pub type LokiProtocolHandlerRef = Arc<dyn LokiProtocolHandler + Send + Sync>;
+pub type ElasticsearchProtocolHandlerRef = Arc<dyn ElasticsearchProtocolHandler + Send + Sync>;
 pub type ScriptHandlerRef = Arc<dyn ScriptHandler + Send + Sync>;
 
 #[async_trait]
 pub trait ScriptHandler {
-    async fn insert_script(&self, schema: &str, name: &str, script: &str) -> Result<()>;
+    async fn insert_script(
+        &self,
+        schema: &str,
+        name: &str,
+        script: &str,
+        schedule: ScriptSchedule,
+    ) -> Result<()>;
     async fn execute_script(
         &self,
         schema: &str,
@@ -59,6 +78,13 @@ pub trait InfluxdbLineProtocolHandler {
     /// A successful request will not return a response.
     /// Only on error will the socket return a line of data.
     async fn exec(&self, request: &InfluxdbRequest, ctx: QueryContextRef) -> Result<()>;
+    /// Handling an InfluxQL statement of `/query` requests, returns the result series.
+    async fn query(
+        &self,
+        statement: Statement,
+        epoch: Option<Epoch>,
+        ctx: QueryContextRef,
+    ) -> Result<Vec<Series>>;
 }
 
 #[async_trait]
@@ -66,6 +92,20 @@ pub trait OpentsdbProtocolHandler {
     /// A successful request will not return a response.
     /// Only on error will the socket return a line of data.
     async fn exec(&self, data_point: &DataPoint, ctx: QueryContextRef) -> Result<()>;
+    /// Handling `/api/query` requests, returns the results of all sub queries.
+    async fn query(
+        &self,
+        request: QueryRequest,
+        ctx: QueryContextRef,
+    ) -> Result<Vec<QueryResponse>>;
+    /// Handling `/api/suggest` requests, returns the sorted names with the prefix.
+    async fn suggest(&self, request: SuggestRequest, ctx: QueryContextRef) -> Result<Vec<String>>;
+    /// Handling `/api/search/lookup` requests, returns all matched series.
+    async fn lookup(
+        &self,
+        request: LookupRequest,
+        ctx: QueryContextRef,
+    ) -> Result<Vec<LookupResult>>;
 }
 
 pub struct PromStoreResponse {
@@ -83,3 +123,25 @@ pub trait PromStoreProtocolHandler {
     /// Handling push gateway requests
     async fn ingest_metrics(&self, metrics: Metrics) -> Result<()>;
 }
+
+#[async_trait]
+pub trait OpenTelemetryProtocolHandler {
+    /// Handling OTLP metrics export requests
+    async fn metrics(
+        &self,
+        request: ExportMetricsServiceRequest,
+        ctx: QueryContextRef,
+    ) -> Result<ExportMetricsServiceResponse>;
+}
+
+#[async_trait]
+pub trait LokiProtocolHandler {
+    /// Handling Loki push requests
+    async fn push(&self, request: LokiRequest, ctx: QueryContextRef) -> Result<()>;
+}
+
+#[async_trait]
+pub trait ElasticsearchProtocolHandler {
+    /// Handling Elasticsearch bulk requests
+    async fn bulk(&self, request: &BulkRequest, ctx: QueryContextRef) -> Result<()>;
+}
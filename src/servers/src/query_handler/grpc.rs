@@ -18,6 +18,7 @@ use api::v1::greptime_request::Request;
 use async_trait::async_trait;
 use common_error::ext::{BoxedError, ErrorExt};
 use common_query::Output;
+use common_recordbatch::RecordBatch;
 use session::context::QueryContextRef;
 use snafu::ResultExt;
 
@@ -35,6 +36,15 @@ pub trait GrpcQueryHandler {
         query: Request,
         ctx: QueryContextRef,
     ) -> std::result::Result<Output, Self::Error>;
+
+    /// Inserts the `record_batch` into table `table_name` of the current schema directly,
+    /// without converting it into rows. Returns the number of inserted rows.
+    async fn put_record_batch(
+        &self,
+        table_name: &str,
+        record_batch: RecordBatch,
+        ctx: QueryContextRef,
+    ) -> std::result::Result<usize, Self::Error>;
 }
 
 pub struct ServerGrpcQueryHandlerAdaptor<E>(GrpcQueryHandlerRef<E>);
@@ -59,4 +69,17 @@ where
             .map_err(BoxedError::new)
             .context(error::ExecuteGrpcQuerySnafu)
     }
+
+    async fn put_record_batch(
+        &self,
+        table_name: &str,
+        record_batch: RecordBatch,
+        ctx: QueryContextRef,
+    ) -> Result<usize> {
+        self.0
+            .put_record_batch(table_name, record_batch, ctx)
+            .await
+            .map_err(BoxedError::new)
+            .context(error::ExecuteGrpcQuerySnafu)
+    }
 }
@@ -62,6 +62,12 @@ pub trait SqlQueryHandler {
         catalog: &str,
         schema: &str,
     ) -> std::result::Result<bool, Self::Error>;
+
+    /// Returns the version of table schemas, which changes whenever a table schema may
+    /// have changed. Plans must not be cached if the handler doesn't track it.
+    fn schema_version(&self) -> Option<u64> {
+        None
+    }
 }
 
 pub struct ServerSqlQueryHandlerAdaptor<E>(SqlQueryHandlerRef<E>);
@@ -138,4 +144,8 @@ where
             .map_err(BoxedError::new)
             .context(error::CheckDatabaseValiditySnafu)
     }
+
+    fn schema_version(&self) -> Option<u64> {
+        self.0.schema_version()
+    }
 }
@@ -13,20 +13,30 @@
 // limitations under the License.
 
 use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 use api::v1::auth_header::AuthScheme;
+use api::v1::greptime_request::Request;
 use api::v1::Basic;
 use arrow_flight::flight_service_server::{FlightService, FlightServiceServer};
 use async_trait::async_trait;
 use client::{Client, Database, DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
+use common_query::Output;
+use common_recordbatch::{RecordBatch, RecordBatchStreamAdaptor};
 use common_runtime::{Builder as RuntimeBuilder, Runtime};
+use datatypes::prelude::ConcreteDataType;
+use datatypes::schema::{ColumnSchema, Schema};
+use datatypes::vectors::{Int32Vector, VectorRef};
+use futures::StreamExt;
 use servers::auth::UserProviderRef;
-use servers::error::{Result, StartGrpcSnafu, TcpBindSnafu};
+use servers::error::{Error, Result, StartGrpcSnafu, TcpBindSnafu};
 use servers::grpc::flight::FlightHandler;
 use servers::grpc::handler::GreptimeRequestHandler;
-use servers::query_handler::grpc::ServerGrpcQueryHandlerRef;
+use servers::query_handler::grpc::{GrpcQueryHandler, ServerGrpcQueryHandlerRef};
 use servers::server::Server;
+use session::context::QueryContextRef;
 use snafu::ResultExt;
 use table::test_util::MemTable;
 use tokio::net::TcpListener;
@@ -100,6 +110,12 @@ impl Server for MockGrpcServer {
 
 fn create_grpc_server(table: MemTable) -> Result<Arc<dyn Server>> {
     let query_handler = create_testing_grpc_query_handler(table);
+    create_grpc_server_with_handler(query_handler)
+}
+
+fn create_grpc_server_with_handler(
+    query_handler: ServerGrpcQueryHandlerRef,
+) -> Result<Arc<dyn Server>> {
     let io_runtime = Arc::new(
         RuntimeBuilder::default()
             .worker_threads(4)
@@ -145,3 +161,99 @@ async fn test_grpc_query() {
     let re = db.sql("select * from numbers").await;
     let _ = re.unwrap();
 }
+
+struct DropFlag(Arc<AtomicBool>);
+
+impl Drop for DropFlag {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Answers every query with an endless stream of record batches, and tracks how
+/// many batches are produced and whether the stream is dropped.
+#[derive(Default)]
+struct EndlessQueryHandler {
+    produced: Arc<AtomicUsize>,
+    dropped: Arc<AtomicBool>,
+}
+
+#[async_trait]
+impl GrpcQueryHandler for EndlessQueryHandler {
+    type Error = Error;
+
+    async fn do_query(&self, _query: Request, _ctx: QueryContextRef) -> Result<Output> {
+        let schema = Arc::new(Schema::new(vec![ColumnSchema::new(
+            "n",
+            ConcreteDataType::int32_datatype(),
+            false,
+        )]));
+        let batch = RecordBatch::new(
+            schema.clone(),
+            vec![Arc::new(Int32Vector::from_slice([1, 2, 3])) as VectorRef],
+        )
+        .unwrap();
+        let produced = self.produced.clone();
+        let guard = DropFlag(self.dropped.clone());
+        let stream = futures::stream::repeat(batch).map(move |batch| {
+            let _ = &guard;
+            let _ = produced.fetch_add(1, Ordering::Relaxed);
+            Ok(batch)
+        });
+        Ok(Output::Stream(Box::pin(RecordBatchStreamAdaptor {
+            schema,
+            stream: Box::pin(stream),
+            output_ordering: None,
+            metrics: None,
+        })))
+    }
+
+    async fn put_record_batch(
+        &self,
+        _table_name: &str,
+        _record_batch: RecordBatch,
+        _ctx: QueryContextRef,
+    ) -> Result<usize> {
+        unimplemented!()
+    }
+}
+
+#[tokio::test]
+async fn test_grpc_stream_cancelled_on_drop() {
+    let handler = Arc::new(EndlessQueryHandler::default());
+    let server = create_grpc_server_with_handler(handler.clone()).unwrap();
+    let addr = server
+        .start(LOCALHOST_WITH_0.parse().unwrap())
+        .await
+        .unwrap();
+    let grpc_client = Client::with_urls(vec![addr.to_string()]);
+    let mut db = Database::new(DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, grpc_client);
+    let greptime = "greptime".to_string();
+    db.set_auth(AuthScheme::Basic(Basic {
+        username: greptime.clone(),
+        password: greptime,
+    }));
+
+    // Plans are not decoded by the handler, any bytes are fine.
+    let Output::Stream(mut stream) = db.logical_plan(vec![]).await.unwrap() else {
+        unreachable!()
+    };
+    for _ in 0..3 {
+        let batch = stream.next().await.unwrap().unwrap();
+        assert_eq!(3, batch.num_rows());
+    }
+    assert!(!handler.dropped.load(Ordering::Relaxed));
+
+    // The server stops producing once the client drops the stream.
+    drop(stream);
+    for _ in 0..100 {
+        if handler.dropped.load(Ordering::Relaxed) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    assert!(handler.dropped.load(Ordering::Relaxed));
+    let produced = handler.produced.load(Ordering::Relaxed);
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(produced, handler.produced.load(Ordering::Relaxed));
+}
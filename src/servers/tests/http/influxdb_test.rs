@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use api::v1::greptime_request::Request;
@@ -20,12 +21,16 @@ use async_trait::async_trait;
 use axum::{http, Router};
 use axum_test_helper::TestClient;
 use common_query::Output;
+use common_recordbatch::RecordBatch;
 use common_test_util::ports;
 use query::parser::PromQuery;
 use query::plan::LogicalPlan;
 use query::query_engine::DescribeResult;
-use servers::error::{Error, Result};
+use servers::error::{self, Error, Result};
+use servers::http::influxdb::InfluxdbQueryResponse;
 use servers::http::{HttpOptions, HttpServerBuilder};
+use servers::influxdb::influxql::Statement;
+use servers::influxdb::query::{Epoch, Series};
 use servers::influxdb::InfluxdbRequest;
 use servers::query_handler::grpc::GrpcQueryHandler;
 use servers::query_handler::sql::SqlQueryHandler;
@@ -50,6 +55,15 @@ impl GrpcQueryHandler for DummyInstance {
     ) -> std::result::Result<Output, Self::Error> {
         unimplemented!()
     }
+
+    async fn put_record_batch(
+        &self,
+        _table_name: &str,
+        _record_batch: RecordBatch,
+        _ctx: QueryContextRef,
+    ) -> std::result::Result<usize, Self::Error> {
+        unimplemented!()
+    }
 }
 
 #[async_trait]
@@ -62,6 +76,30 @@ impl InfluxdbLineProtocolHandler for DummyInstance {
 
         Ok(())
     }
+
+    async fn query(
+        &self,
+        statement: Statement,
+        epoch: Option<Epoch>,
+        _ctx: QueryContextRef,
+    ) -> Result<Vec<Series>> {
+        let Statement::Select(select) = statement else {
+            return error::InvalidInfluxqlSnafu {
+                reason: "unsupported statement",
+            }
+            .fail();
+        };
+        let time = match epoch {
+            Some(_) => serde_json::json!(0),
+            None => serde_json::json!("1970-01-01T00:00:00Z"),
+        };
+        Ok(vec![Series {
+            name: select.from,
+            tags: BTreeMap::new(),
+            columns: vec!["time".to_string(), "value".to_string()],
+            values: vec![vec![time, serde_json::json!(1.0)]],
+        }])
+    }
 }
 
 #[async_trait]
@@ -212,3 +250,59 @@ async fn test_influxdb_write() {
         ]
     );
 }
+
+#[tokio::test]
+async fn test_influxdb_query() {
+    let (tx, _rx) = mpsc::channel(100);
+    let app = make_test_app(Arc::new(tx), None);
+    let client = TestClient::new(app);
+
+    let result = client
+        .get("/v1/influxdb/query?db=public&u=greptime&p=greptime&q=SELECT%20value%20FROM%20monitor%3B%20SHOW%20MEASUREMENTS")
+        .send()
+        .await;
+    assert_eq!(result.status(), 200);
+    let response: InfluxdbQueryResponse = serde_json::from_str(&result.text().await).unwrap();
+    assert_eq!(2, response.results.len());
+    assert_eq!(0, response.results[0].statement_id);
+    assert_eq!("monitor", response.results[0].series[0].name);
+    assert_eq!(
+        vec![vec![
+            serde_json::json!("1970-01-01T00:00:00Z"),
+            serde_json::json!(1.0)
+        ]],
+        response.results[0].series[0].values
+    );
+    assert!(response.results[0].error.is_none());
+    assert!(response.results[1].series.is_empty());
+    assert!(response.results[1].error.is_some());
+
+    // parameters in the form body
+    let result = client
+        .post("/v1/influxdb/query?u=greptime&p=greptime")
+        .header(
+            http::header::CONTENT_TYPE,
+            "application/x-www-form-urlencoded",
+        )
+        .body("db=public&epoch=ms&q=SELECT+value+FROM+monitor")
+        .send()
+        .await;
+    assert_eq!(result.status(), 200);
+    assert_eq!(
+        r#"{"results":[{"statement_id":0,"series":[{"name":"monitor","columns":["time","value"],"values":[[0,1.0]]}]}]}"#,
+        result.text().await
+    );
+
+    // invalid queries
+    let result = client
+        .get("/v1/influxdb/query?u=greptime&p=greptime&q=DROP%20MEASUREMENT%20monitor")
+        .send()
+        .await;
+    assert_eq!(result.status(), 400);
+
+    let result = client
+        .get("/v1/influxdb/query?u=greptime&p=greptime")
+        .send()
+        .await;
+    assert_eq!(result.status(), 400);
+}
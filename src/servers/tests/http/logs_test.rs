@@ -0,0 +1,242 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use api::v1::greptime_request::Request;
+use async_trait::async_trait;
+use axum::Router;
+use axum_test_helper::TestClient;
+use common_query::Output;
+use common_recordbatch::RecordBatch;
+use common_test_util::ports;
+use prost::Message;
+use query::parser::PromQuery;
+use query::plan::LogicalPlan;
+use query::query_engine::DescribeResult;
+use servers::elasticsearch::BulkRequest;
+use servers::error::{Error, Result};
+use servers::http::{HttpOptions, HttpServerBuilder};
+use servers::loki::{EntryAdapter, LokiRequest, PushRequest, StreamAdapter, Timestamp};
+use servers::prom_store::snappy_compress;
+use servers::query_handler::grpc::GrpcQueryHandler;
+use servers::query_handler::sql::SqlQueryHandler;
+use servers::query_handler::{ElasticsearchProtocolHandler, LokiProtocolHandler};
+use session::context::QueryContextRef;
+use tokio::sync::mpsc;
+
+/// Sends `(schema, table, rows)` of the received requests.
+struct DummyInstance {
+    tx: mpsc::Sender<(String, String, usize)>,
+}
+
+#[async_trait]
+impl GrpcQueryHandler for DummyInstance {
+    type Error = Error;
+
+    async fn do_query(
+        &self,
+        _query: Request,
+        _ctx: QueryContextRef,
+    ) -> std::result::Result<Output, Self::Error> {
+        unimplemented!()
+    }
+
+    async fn put_record_batch(
+        &self,
+        _table_name: &str,
+        _record_batch: RecordBatch,
+        _ctx: QueryContextRef,
+    ) -> std::result::Result<usize, Self::Error> {
+        unimplemented!()
+    }
+}
+
+#[async_trait]
+impl LokiProtocolHandler for DummyInstance {
+    async fn push(&self, request: LokiRequest, ctx: QueryContextRef) -> Result<()> {
+        let rows = request.streams.iter().map(|s| s.entries.len()).sum();
+        let _ = self
+            .tx
+            .send((ctx.current_schema(), request.table_name, rows))
+            .await;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ElasticsearchProtocolHandler for DummyInstance {
+    async fn bulk(&self, request: &BulkRequest, ctx: QueryContextRef) -> Result<()> {
+        for doc in &request.documents {
+            let _ = self
+                .tx
+                .send((ctx.current_schema(), doc.index.clone(), 1))
+                .await;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SqlQueryHandler for DummyInstance {
+    type Error = Error;
+
+    async fn do_query(&self, _: &str, _: QueryContextRef) -> Vec<Result<Output>> {
+        unimplemented!()
+    }
+
+    async fn do_exec_plan(
+        &self,
+        _plan: LogicalPlan,
+        _query_ctx: QueryContextRef,
+    ) -> std::result::Result<Output, Self::Error> {
+        unimplemented!()
+    }
+
+    async fn do_promql_query(
+        &self,
+        _: &PromQuery,
+        _: QueryContextRef,
+    ) -> Vec<std::result::Result<Output, Self::Error>> {
+        unimplemented!()
+    }
+
+    async fn do_describe(
+        &self,
+        _stmt: sql::statements::statement::Statement,
+        _query_ctx: QueryContextRef,
+    ) -> Result<Option<DescribeResult>> {
+        unimplemented!()
+    }
+
+    async fn is_valid_schema(&self, _catalog: &str, _schema: &str) -> Result<bool> {
+        Ok(true)
+    }
+}
+
+fn make_test_app(tx: mpsc::Sender<(String, String, usize)>) -> Router {
+    let http_opts = HttpOptions {
+        addr: format!("127.0.0.1:{}", ports::get_port()),
+        ..Default::default()
+    };
+
+    let instance = Arc::new(DummyInstance { tx });
+    let server = HttpServerBuilder::new(http_opts)
+        .with_grpc_handler(instance.clone())
+        .with_sql_handler(instance.clone())
+        .with_loki_handler(instance.clone())
+        .with_elasticsearch_handler(instance)
+        .build();
+    server.build(server.make_app())
+}
+
+#[tokio::test]
+async fn test_loki_push() {
+    let (tx, mut rx) = mpsc::channel(100);
+    let app = make_test_app(tx);
+    let client = TestClient::new(app);
+
+    let request = PushRequest {
+        streams: vec![StreamAdapter {
+            labels: r#"{job="app"}"#.to_string(),
+            entries: vec![EntryAdapter {
+                timestamp: Some(Timestamp {
+                    seconds: 1,
+                    nanos: 0,
+                }),
+                line: "hello".to_string(),
+            }],
+            hash: 0,
+        }],
+    };
+    let result = client
+        .post("/v1/loki/api/v1/push")
+        .header("Content-Type", "application/x-protobuf")
+        .body(snappy_compress(&request.encode_to_vec()).unwrap())
+        .send()
+        .await;
+    assert_eq!(result.status(), 204);
+
+    let result = client
+        .post("/v1/loki/api/v1/push?db=logs&table=app_logs")
+        .header("Content-Type", "application/json")
+        .body(r#"{"streams":[{"stream":{"job":"app"},"values":[["1000000000","a"],["2000000000","b"]]}]}"#)
+        .send()
+        .await;
+    assert_eq!(result.status(), 204);
+
+    let result = client
+        .post("/v1/loki/api/v1/push")
+        .header("Content-Type", "application/json")
+        .body(r#"{"streams":[{"values":[["bad","a"]]}]}"#)
+        .send()
+        .await;
+    assert_eq!(result.status(), 400);
+
+    let mut requests = vec![];
+    while let Ok(s) = rx.try_recv() {
+        requests.push(s);
+    }
+    assert_eq!(
+        vec![
+            ("public".to_string(), "loki_logs".to_string(), 1),
+            ("logs".to_string(), "app_logs".to_string(), 2),
+        ],
+        requests
+    );
+}
+
+#[tokio::test]
+async fn test_elasticsearch_bulk() {
+    let (tx, mut rx) = mpsc::channel(100);
+    let app = make_test_app(tx);
+    let client = TestClient::new(app);
+
+    let body = r#"{"index":{"_index":"nginx"}}
+{"@timestamp":"2023-06-01T00:00:00Z","message":"GET /"}
+{"create":{}}
+{"message":"POST /"}
+"#;
+    let result = client
+        .post("/v1/elasticsearch/app/_bulk?db=logs")
+        .header("Content-Type", "application/x-ndjson")
+        .body(body)
+        .send()
+        .await;
+    assert_eq!(result.status(), 200);
+    let response: serde_json::Value = serde_json::from_str(&result.text().await).unwrap();
+    assert_eq!(Some(false), response["errors"].as_bool());
+    assert_eq!(201, response["items"][0]["index"]["status"]);
+    assert_eq!("app", response["items"][1]["create"]["_index"]);
+
+    let result = client
+        .post("/v1/elasticsearch/_bulk")
+        .header("Content-Type", "application/x-ndjson")
+        .body(body)
+        .send()
+        .await;
+    assert_eq!(result.status(), 400);
+
+    let mut requests = vec![];
+    while let Ok(s) = rx.try_recv() {
+        requests.push(s);
+    }
+    assert_eq!(
+        vec![
+            ("logs".to_string(), "nginx".to_string(), 1),
+            ("logs".to_string(), "app".to_string(), 1),
+        ],
+        requests
+    );
+}
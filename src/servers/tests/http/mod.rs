@@ -16,5 +16,7 @@ mod authorize;
 mod http_handler_test;
 mod http_test;
 mod influxdb_test;
+mod logs_test;
 mod opentsdb_test;
+mod procedure_test;
 mod prom_store_test;
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use api::v1::greptime_request::Request;
@@ -19,6 +20,7 @@ use async_trait::async_trait;
 use axum::Router;
 use axum_test_helper::TestClient;
 use common_query::Output;
+use common_recordbatch::RecordBatch;
 use common_test_util::ports;
 use query::parser::PromQuery;
 use query::plan::LogicalPlan;
@@ -26,6 +28,9 @@ use query::query_engine::DescribeResult;
 use servers::error::{self, Result};
 use servers::http::{HttpOptions, HttpServerBuilder};
 use servers::opentsdb::codec::DataPoint;
+use servers::opentsdb::query::{
+    LookupRequest, LookupResult, QueryRequest, QueryResponse, SuggestRequest,
+};
 use servers::query_handler::grpc::GrpcQueryHandler;
 use servers::query_handler::sql::SqlQueryHandler;
 use servers::query_handler::OpentsdbProtocolHandler;
@@ -47,6 +52,15 @@ impl GrpcQueryHandler for DummyInstance {
     ) -> std::result::Result<Output, Self::Error> {
         unimplemented!()
     }
+
+    async fn put_record_batch(
+        &self,
+        _table_name: &str,
+        _record_batch: RecordBatch,
+        _ctx: QueryContextRef,
+    ) -> std::result::Result<usize, Self::Error> {
+        unimplemented!()
+    }
 }
 
 #[async_trait]
@@ -61,6 +75,46 @@ impl OpentsdbProtocolHandler for DummyInstance {
         let _ = self.tx.send(data_point.metric().to_string()).await;
         Ok(())
     }
+
+    async fn query(
+        &self,
+        request: QueryRequest,
+        _ctx: QueryContextRef,
+    ) -> Result<Vec<QueryResponse>> {
+        Ok(request
+            .queries
+            .into_iter()
+            .map(|query| QueryResponse {
+                metric: query.metric,
+                tags: BTreeMap::from([("host".to_string(), "web01".to_string())]),
+                aggregate_tags: vec![],
+                dps: BTreeMap::from([(1000, 1.0)]),
+            })
+            .collect())
+    }
+
+    async fn suggest(&self, request: SuggestRequest, _ctx: QueryContextRef) -> Result<Vec<String>> {
+        Ok(["m1", "m2", "n1"]
+            .into_iter()
+            .filter(|name| name.starts_with(&request.q))
+            .take(request.max)
+            .map(String::from)
+            .collect())
+    }
+
+    async fn lookup(
+        &self,
+        request: LookupRequest,
+        _ctx: QueryContextRef,
+    ) -> Result<Vec<LookupResult>> {
+        Ok(["web01", "web02"]
+            .into_iter()
+            .map(|host| LookupResult {
+                metric: request.metric.clone(),
+                tags: BTreeMap::from([("host".to_string(), host.to_string())]),
+            })
+            .collect())
+    }
 }
 
 #[async_trait]
@@ -245,6 +299,62 @@ async fn test_opentsdb_debug_put() {
     );
 }
 
+#[tokio::test]
+async fn test_opentsdb_query() {
+    let (tx, _rx) = mpsc::channel(100);
+
+    let app = make_test_app(tx);
+    let client = TestClient::new(app);
+
+    let result = client
+        .post("/v1/opentsdb/api/query")
+        .body(r#"{"start":"1h-ago","queries":[{"aggregator":"sum","metric":"m1"}]}"#)
+        .send()
+        .await;
+    assert_eq!(result.status(), 200);
+    assert_eq!(
+        result.text().await,
+        r#"[{"metric":"m1","tags":{"host":"web01"},"aggregateTags":[],"dps":{"1000":1.0}}]"#
+    );
+
+    let result = client
+        .get("/v1/opentsdb/api/query?start=1h-ago&m=sum:m1&m=avg:1m-avg:m2%7Bhost=*%7D")
+        .send()
+        .await;
+    assert_eq!(result.status(), 200);
+    let responses = serde_json::from_str::<Vec<QueryResponse>>(&result.text().await).unwrap();
+    assert_eq!(2, responses.len());
+    assert_eq!("m2", responses[1].metric);
+
+    let result = client.get("/v1/opentsdb/api/query?m=sum:m1").send().await;
+    assert_eq!(result.status(), 400);
+    assert_eq!(
+        result.text().await,
+        "{\"error\":\"Invalid OpenTSDB query: missing parameter 'start'\"}"
+    );
+
+    let result = client
+        .get("/v1/opentsdb/api/suggest?type=metrics&q=m&max=1")
+        .send()
+        .await;
+    assert_eq!(result.status(), 200);
+    assert_eq!(result.text().await, r#"["m1"]"#);
+
+    let result = client.get("/v1/opentsdb/api/suggest?type=foo").send().await;
+    assert_eq!(result.status(), 400);
+
+    let result = client
+        .get("/v1/opentsdb/api/search/lookup?m=m1%7Bhost=*%7D&limit=1")
+        .send()
+        .await;
+    assert_eq!(result.status(), 200);
+    let response = serde_json::from_str::<serde_json::Value>(&result.text().await).unwrap();
+    assert_eq!("LOOKUP", response["type"]);
+    assert_eq!(2, response["totalResults"]);
+    assert_eq!(1, response["results"].as_array().unwrap().len());
+    assert_eq!("web01", response["results"][0]["tags"]["host"]);
+}
+
 fn create_data_point(metric: &str) -> String {
     format!(
         r#"{{
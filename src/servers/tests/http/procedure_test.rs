@@ -0,0 +1,165 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::Router;
+use axum_test_helper::TestClient;
+use common_procedure::{
+    BoxedProcedure, LockKey, ProcedureId, ProcedureInfo, ProcedureManager, ProcedureState,
+    ProcedureWithId, Result, Watcher,
+};
+use common_test_util::ports;
+use servers::http::{HttpOptions, HttpServerBuilder};
+
+/// A procedure manager that only knows one procedure, which can be retried.
+struct MockProcedureManager {
+    failed_id: ProcedureId,
+}
+
+impl MockProcedureManager {
+    fn failed_info(&self) -> ProcedureInfo {
+        ProcedureInfo {
+            id: self.failed_id,
+            type_name: "MockProcedure".to_string(),
+            parent_id: None,
+            lock_key: LockKey::single("catalog.schema.table"),
+            state: ProcedureState::Done,
+            state_history: Vec::new(),
+            start_time_ms: 1,
+        }
+    }
+}
+
+#[async_trait]
+impl ProcedureManager for MockProcedureManager {
+    fn register_loader(
+        &self,
+        _name: &str,
+        _loader: Box<dyn Fn(&str) -> Result<BoxedProcedure> + Send>,
+    ) -> Result<()> {
+        unimplemented!()
+    }
+
+    fn start(&self) -> Result<()> {
+        unimplemented!()
+    }
+
+    async fn stop(&self) -> Result<()> {
+        unimplemented!()
+    }
+
+    async fn submit(&self, _procedure: ProcedureWithId) -> Result<Watcher> {
+        unimplemented!()
+    }
+
+    async fn recover(&self) -> Result<()> {
+        unimplemented!()
+    }
+
+    async fn procedure_state(&self, _procedure_id: ProcedureId) -> Result<Option<ProcedureState>> {
+        unimplemented!()
+    }
+
+    fn procedure_watcher(&self, _procedure_id: ProcedureId) -> Option<Watcher> {
+        unimplemented!()
+    }
+
+    fn list_procedures(&self) -> Vec<ProcedureInfo> {
+        vec![self.failed_info()]
+    }
+
+    async fn cancel(&self, _procedure_id: ProcedureId) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn procedure_info(&self, procedure_id: ProcedureId) -> Option<ProcedureInfo> {
+        (procedure_id == self.failed_id).then(|| self.failed_info())
+    }
+
+    async fn retry(&self, procedure_id: ProcedureId) -> Result<bool> {
+        Ok(procedure_id == self.failed_id)
+    }
+}
+
+fn make_test_app(failed_id: ProcedureId) -> Router {
+    let http_opts = HttpOptions {
+        addr: format!("127.0.0.1:{}", ports::get_port()),
+        ..Default::default()
+    };
+
+    let mut builder = HttpServerBuilder::new(http_opts);
+    let _ = builder.with_procedure_manager(Arc::new(MockProcedureManager { failed_id }));
+    let server = builder.build();
+    server.build(server.make_app())
+}
+
+#[tokio::test]
+async fn test_procedure_routes() {
+    let failed_id = ProcedureId::random();
+    let client = TestClient::new(make_test_app(failed_id));
+
+    let result = client.get("/v1/admin/procedures").send().await;
+    assert_eq!(result.status(), 200);
+    let body: serde_json::Value = serde_json::from_str(&result.text().await).unwrap();
+    assert_eq!(body[0]["id"], failed_id.to_string());
+    assert_eq!(body[0]["lock_key"][0], "catalog.schema.table");
+
+    let result = client
+        .get(&format!("/v1/admin/procedures/{failed_id}"))
+        .send()
+        .await;
+    assert_eq!(result.status(), 200);
+    let body: serde_json::Value = serde_json::from_str(&result.text().await).unwrap();
+    assert_eq!(body["type_name"], "MockProcedure");
+    assert_eq!(body["state"]["status"], "Done");
+
+    let unknown_id = ProcedureId::random();
+    let result = client
+        .get(&format!("/v1/admin/procedures/{unknown_id}"))
+        .send()
+        .await;
+    assert_eq!(result.status(), 404);
+
+    let result = client.get("/v1/admin/procedures/not-an-id").send().await;
+    assert_eq!(result.status(), 400);
+
+    let result = client
+        .post(&format!("/v1/admin/procedures/{failed_id}/retry"))
+        .send()
+        .await;
+    assert_eq!(result.status(), 200);
+    assert_eq!(result.text().await, r#"{"retried":true}"#);
+
+    let result = client
+        .post(&format!("/v1/admin/procedures/{unknown_id}/retry"))
+        .send()
+        .await;
+    assert_eq!(result.text().await, r#"{"retried":false}"#);
+
+    let result = client
+        .post(&format!("/v1/admin/procedures/{failed_id}/cancel"))
+        .send()
+        .await;
+    assert_eq!(result.status(), 200);
+    assert_eq!(result.text().await, r#"{"cancelled":false}"#);
+
+    // Procedures can only be cancelled or retried by POST.
+    let result = client
+        .get(&format!("/v1/admin/procedures/{failed_id}/retry"))
+        .send()
+        .await;
+    assert_eq!(result.status(), 405);
+}
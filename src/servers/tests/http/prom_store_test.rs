@@ -22,6 +22,7 @@ use async_trait::async_trait;
 use axum::Router;
 use axum_test_helper::TestClient;
 use common_query::Output;
+use common_recordbatch::RecordBatch;
 use common_test_util::ports;
 use prost::Message;
 use query::parser::PromQuery;
@@ -52,6 +53,15 @@ impl GrpcQueryHandler for DummyInstance {
     ) -> std::result::Result<Output, Self::Error> {
         unimplemented!()
     }
+
+    async fn put_record_batch(
+        &self,
+        _table_name: &str,
+        _record_batch: RecordBatch,
+        _ctx: QueryContextRef,
+    ) -> std::result::Result<usize, Self::Error> {
+        unimplemented!()
+    }
 }
 
 #[async_trait]
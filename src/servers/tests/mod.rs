@@ -20,7 +20,8 @@ use api::v1::query_request::Query;
 use async_trait::async_trait;
 use catalog::local::MemoryCatalogManager;
 use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
-use common_query::Output;
+use common_query::{Output, ScriptSchedule};
+use common_recordbatch::RecordBatch;
 use query::parser::{PromQuery, QueryLanguageParser, QueryStatement};
 use query::plan::LogicalPlan;
 use query::query_engine::DescribeResult;
@@ -117,7 +118,13 @@ impl SqlQueryHandler for DummyInstance {
 
 #[async_trait]
 impl ScriptHandler for DummyInstance {
-    async fn insert_script(&self, schema: &str, name: &str, script: &str) -> Result<()> {
+    async fn insert_script(
+        &self,
+        schema: &str,
+        name: &str,
+        script: &str,
+        _schedule: ScriptSchedule,
+    ) -> Result<()> {
         let script = self
             .py_engine
             .compile(script, CompileContext::default())
@@ -198,6 +205,15 @@ impl GrpcQueryHandler for DummyInstance {
         };
         Ok(output)
     }
+
+    async fn put_record_batch(
+        &self,
+        _table_name: &str,
+        _record_batch: RecordBatch,
+        _ctx: QueryContextRef,
+    ) -> std::result::Result<usize, Self::Error> {
+        unimplemented!()
+    }
 }
 
 fn create_testing_instance(table: MemTable) -> DummyInstance {
@@ -16,6 +16,7 @@ use std::sync::Arc;
 
 use datatypes::prelude::*;
 use datatypes::schema::{ColumnSchema, Schema};
+use opensrv_mysql::ColumnType;
 use servers::mysql::writer::create_mysql_column_def;
 
 use crate::mysql::{all_datatype_testing_data, TestingData};
@@ -44,5 +45,6 @@ fn test_create_mysql_column_def() {
         true,
     )];
     let schema = Arc::new(Schema::new(column_schemas));
-    assert!(create_mysql_column_def(&schema).is_err());
+    let columns_def = create_mysql_column_def(&schema).unwrap();
+    assert_eq!(ColumnType::MYSQL_TYPE_VARCHAR, columns_def[0].coltype);
 }
@@ -24,6 +24,9 @@ use rand::Rng;
 use servers::error::{self as server_error, Error, Result};
 use servers::opentsdb::codec::DataPoint;
 use servers::opentsdb::connection::Connection;
+use servers::opentsdb::query::{
+    LookupRequest, LookupResult, QueryRequest, QueryResponse, SuggestRequest,
+};
 use servers::opentsdb::OpentsdbServer;
 use servers::query_handler::OpentsdbProtocolHandler;
 use servers::server::Server;
@@ -49,6 +52,30 @@ impl OpentsdbProtocolHandler for DummyOpentsdbInstance {
         let _ = self.tx.send(i * i).await;
         Ok(())
     }
+
+    async fn query(
+        &self,
+        _request: QueryRequest,
+        _ctx: QueryContextRef,
+    ) -> Result<Vec<QueryResponse>> {
+        unimplemented!()
+    }
+
+    async fn suggest(
+        &self,
+        _request: SuggestRequest,
+        _ctx: QueryContextRef,
+    ) -> Result<Vec<String>> {
+        unimplemented!()
+    }
+
+    async fn lookup(
+        &self,
+        _request: LookupRequest,
+        _ctx: QueryContextRef,
+    ) -> Result<Vec<LookupResult>> {
+        unimplemented!()
+    }
 }
 
 fn create_opentsdb_server(tx: mpsc::Sender<i32>) -> Result<Box<dyn Server>> {
@@ -34,7 +34,7 @@ def double_that(col) -> vector[u32]:
     return col*2
     "#;
     instance
-        .insert_script("schema_test", "double_that", src)
+        .insert_script("schema_test", "double_that", src, Default::default())
         .await?;
     let res = instance
         .do_query("select double_that(uint32s) from numbers", query_ctx)
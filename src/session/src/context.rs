@@ -32,6 +32,13 @@ pub struct QueryContext {
     current_schema: ArcSwap<String>,
     time_zone: ArcSwap<Option<TimeZone>>,
     sql_dialect: Box<dyn Dialect + Send + Sync>,
+    /// Id of the connection this query comes from, which is also the id of
+    /// its queries in the process list.
+    connection_id: Option<u32>,
+    client: Option<String>,
+    current_user: ArcSwap<Option<String>>,
+    /// Protocol channel this query comes from.
+    channel: Channel,
 }
 
 impl Default for QueryContext {
@@ -62,6 +69,10 @@ impl QueryContext {
             current_schema: ArcSwap::new(Arc::new(DEFAULT_SCHEMA_NAME.to_string())),
             time_zone: ArcSwap::new(Arc::new(None)),
             sql_dialect: Box::new(GreptimeDbDialect {}),
+            connection_id: None,
+            client: None,
+            current_user: ArcSwap::new(Arc::new(None)),
+            channel: Channel::Unknown,
         }
     }
 
@@ -79,9 +90,26 @@ impl QueryContext {
             current_schema: ArcSwap::new(Arc::new(schema.to_string())),
             time_zone: ArcSwap::new(Arc::new(None)),
             sql_dialect,
+            connection_id: None,
+            client: None,
+            current_user: ArcSwap::new(Arc::new(None)),
+            channel: Channel::Unknown,
         }
     }
 
+    /// Binds the context to a connection with given id and client address.
+    pub fn with_connection(mut self, connection_id: u32, client: Option<String>) -> Self {
+        self.connection_id = Some(connection_id);
+        self.client = client;
+        self
+    }
+
+    /// Marks the protocol channel the query comes from.
+    pub fn with_channel(mut self, channel: Channel) -> Self {
+        self.channel = channel;
+        self
+    }
+
     #[inline]
     pub fn current_schema(&self) -> String {
         self.current_schema.load().as_ref().clone()
@@ -132,6 +160,31 @@ impl QueryContext {
     pub fn set_time_zone(&self, tz: Option<TimeZone>) {
         let _ = self.time_zone.swap(Arc::new(tz));
     }
+
+    #[inline]
+    pub fn connection_id(&self) -> Option<u32> {
+        self.connection_id
+    }
+
+    #[inline]
+    pub fn client(&self) -> Option<&str> {
+        self.client.as_deref()
+    }
+
+    #[inline]
+    pub fn channel(&self) -> Channel {
+        self.channel
+    }
+
+    #[inline]
+    pub fn current_user(&self) -> Option<String> {
+        self.current_user.load().as_ref().clone()
+    }
+
+    #[inline]
+    pub fn set_current_user(&self, user: &str) {
+        let _ = self.current_user.swap(Arc::new(Some(user.to_string())));
+    }
 }
 
 pub const DEFAULT_USERNAME: &str = "greptime";
@@ -190,10 +243,15 @@ impl ConnInfo {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum Channel {
+    #[default]
+    Unknown,
     Mysql,
     Postgres,
+    Http,
+    Grpc,
+    Prometheus,
 }
 
 impl Channel {
@@ -201,6 +259,7 @@ impl Channel {
         match self {
             Channel::Mysql => Box::new(MySqlDialect {}),
             Channel::Postgres => Box::new(PostgreSqlDialect {}),
+            _ => Box::new(GreptimeDbDialect {}),
         }
     }
 }
@@ -208,8 +267,12 @@ impl Channel {
 impl std::fmt::Display for Channel {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
+            Channel::Unknown => write!(f, "unknown"),
             Channel::Mysql => write!(f, "mysql"),
             Channel::Postgres => write!(f, "postgres"),
+            Channel::Http => write!(f, "http"),
+            Channel::Grpc => write!(f, "grpc"),
+            Channel::Prometheus => write!(f, "prometheus"),
         }
     }
 }
@@ -227,9 +290,15 @@ mod test {
         assert_eq!(session.user_info().username(), "greptime");
         session.set_user_info(UserInfo::new("root"));
         assert_eq!(session.user_info().username(), "root");
+        assert_eq!(session.context().current_user().as_deref(), Some("root"));
+
+        // test connection
+        assert!(session.context().connection_id().is_some());
+        assert_eq!(session.context().client(), Some("127.0.0.1:9000"));
 
         // test channel
         assert_eq!(session.conn_info().channel, Channel::Mysql);
+        assert_eq!(session.context().channel(), Channel::Mysql);
         let client_addr = session.conn_info().client_addr.as_ref().unwrap();
         assert_eq!(client_addr.ip().to_string(), "127.0.0.1");
         assert_eq!(client_addr.port(), 9000);
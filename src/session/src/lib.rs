@@ -13,6 +13,7 @@
 // limitations under the License.
 
 pub mod context;
+pub mod process;
 
 use std::net::SocketAddr;
 use std::sync::Arc;
@@ -21,6 +22,7 @@ use arc_swap::ArcSwap;
 use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
 
 use crate::context::{Channel, ConnInfo, QueryContext, QueryContextRef, UserInfo};
+use crate::process::ProcessManager;
 
 /// Session for persistent connection such as MySQL, PostgreSQL etc.
 #[derive(Debug)]
@@ -35,11 +37,18 @@ pub type SessionRef = Arc<Session>;
 impl Session {
     pub fn new(addr: Option<SocketAddr>, channel: Channel) -> Self {
         Session {
-            query_ctx: Arc::new(QueryContext::with_sql_dialect(
-                DEFAULT_CATALOG_NAME,
-                DEFAULT_SCHEMA_NAME,
-                channel.dialect(),
-            )),
+            query_ctx: Arc::new(
+                QueryContext::with_sql_dialect(
+                    DEFAULT_CATALOG_NAME,
+                    DEFAULT_SCHEMA_NAME,
+                    channel.dialect(),
+                )
+                .with_connection(
+                    ProcessManager::global().next_id(),
+                    addr.map(|addr| addr.to_string()),
+                )
+                .with_channel(channel),
+            ),
             user_info: ArcSwap::new(Arc::new(UserInfo::default())),
             conn_info: ConnInfo::new(addr, channel),
         }
@@ -67,6 +76,7 @@ impl Session {
 
     #[inline]
     pub fn set_user_info(&self, user_info: UserInfo) {
+        self.query_ctx.set_current_user(user_info.username());
         self.user_info.store(Arc::new(user_info));
     }
 }
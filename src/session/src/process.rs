@@ -0,0 +1,272 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Registry of the queries running in this process, listed by `SHOW PROCESSLIST`
+//! and stopped by `KILL QUERY`.
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicU32, AtomicU8, Ordering};
+use std::sync::{Arc, RwLock};
+
+use common_time::util::current_time_millis;
+use lazy_static::lazy_static;
+use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
+
+use crate::context::QueryContext;
+
+lazy_static! {
+    static ref PROCESS_MANAGER: ProcessManager = ProcessManager::default();
+}
+
+/// Stage of a running query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ProcessStage {
+    /// The query is being planned or executed.
+    Executing = 0,
+    /// The query has produced a stream and its results are being sent to the client.
+    Streaming = 1,
+}
+
+impl Display for ProcessStage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProcessStage::Executing => write!(f, "Executing"),
+            ProcessStage::Streaming => write!(f, "Streaming"),
+        }
+    }
+}
+
+/// A query registered in the [ProcessManager].
+#[derive(Debug)]
+pub struct ProcessInfo {
+    id: u32,
+    user: Option<String>,
+    client: Option<String>,
+    catalog: String,
+    schema: String,
+    query: String,
+    start_time_ms: i64,
+    stage: AtomicU8,
+    cancellation: CancellationToken,
+}
+
+impl ProcessInfo {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn user(&self) -> Option<&str> {
+        self.user.as_deref()
+    }
+
+    pub fn client(&self) -> Option<&str> {
+        self.client.as_deref()
+    }
+
+    pub fn catalog(&self) -> &str {
+        &self.catalog
+    }
+
+    pub fn schema(&self) -> &str {
+        &self.schema
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn start_time_ms(&self) -> i64 {
+        self.start_time_ms
+    }
+
+    pub fn stage(&self) -> ProcessStage {
+        match self.stage.load(Ordering::Relaxed) {
+            0 => ProcessStage::Executing,
+            _ => ProcessStage::Streaming,
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+}
+
+/// Keeps a query in the process list until it's dropped.
+#[derive(Debug)]
+pub struct ProcessHandle {
+    info: Arc<ProcessInfo>,
+    manager: &'static ProcessManager,
+}
+
+impl ProcessHandle {
+    pub fn id(&self) -> u32 {
+        self.info.id
+    }
+
+    pub fn set_stage(&self, stage: ProcessStage) {
+        self.info.stage.store(stage as u8, Ordering::Relaxed);
+    }
+
+    /// Returns a future that completes once the query is killed.
+    pub fn cancelled(&self) -> WaitForCancellationFuture<'_> {
+        self.info.cancellation.cancelled()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.info.is_cancelled()
+    }
+}
+
+impl Drop for ProcessHandle {
+    fn drop(&mut self) {
+        let mut processes = self.manager.processes.write().unwrap();
+        // A connection reuses its id for every statement, only remove the entry
+        // registered by this handle.
+        if processes
+            .get(&self.info.id)
+            .map(|info| Arc::ptr_eq(info, &self.info))
+            .unwrap_or(false)
+        {
+            let _ = processes.remove(&self.info.id);
+        }
+    }
+}
+
+/// Tracks the running queries of this process.
+///
+/// Queries from a connection (MySQL, PostgreSQL) are identified by the connection id,
+/// other queries get a fresh id when they are registered.
+#[derive(Debug, Default)]
+pub struct ProcessManager {
+    next_id: AtomicU32,
+    processes: RwLock<HashMap<u32, Arc<ProcessInfo>>>,
+}
+
+impl ProcessManager {
+    pub fn global() -> &'static ProcessManager {
+        &PROCESS_MANAGER
+    }
+
+    /// Allocates a new process id. Ids start from 1.
+    pub fn next_id(&self) -> u32 {
+        self.next_id.fetch_add(1, Ordering::Relaxed).wrapping_add(1)
+    }
+
+    /// Registers a query, which stays in the process list until the returned
+    /// handle is dropped.
+    pub fn register(&'static self, query_ctx: &QueryContext, query: &str) -> ProcessHandle {
+        let id = query_ctx.connection_id().unwrap_or_else(|| self.next_id());
+        let info = Arc::new(ProcessInfo {
+            id,
+            user: query_ctx.current_user(),
+            client: query_ctx.client().map(ToString::to_string),
+            catalog: query_ctx.current_catalog(),
+            schema: query_ctx.current_schema(),
+            query: query.to_string(),
+            start_time_ms: current_time_millis(),
+            stage: AtomicU8::new(ProcessStage::Executing as u8),
+            cancellation: CancellationToken::new(),
+        });
+        let _ = self.processes.write().unwrap().insert(id, info.clone());
+        ProcessHandle {
+            info,
+            manager: self,
+        }
+    }
+
+    /// Lists running queries ordered by id.
+    pub fn list(&self) -> Vec<Arc<ProcessInfo>> {
+        let mut processes = self
+            .processes
+            .read()
+            .unwrap()
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        processes.sort_unstable_by_key(|info| info.id);
+        processes
+    }
+
+    /// Cancels the query with given id, returns false if there is no such query.
+    pub fn kill(&self, id: u32) -> bool {
+        match self.processes.read().unwrap().get(&id) {
+            Some(info) => {
+                info.cancellation.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_register_and_kill() {
+        let manager = ProcessManager::global();
+        let ctx = QueryContext::with("greptime", "public");
+        let handle = manager.register(&ctx, "SELECT 1");
+        let id = handle.id();
+
+        let info = manager
+            .list()
+            .into_iter()
+            .find(|info| info.id() == id)
+            .unwrap();
+        assert_eq!("SELECT 1", info.query());
+        assert_eq!("public", info.schema());
+        assert_eq!(ProcessStage::Executing, info.stage());
+
+        handle.set_stage(ProcessStage::Streaming);
+        assert_eq!(ProcessStage::Streaming, info.stage());
+
+        assert!(manager.kill(id));
+        handle.cancelled().await;
+        assert!(info.is_cancelled());
+
+        drop(handle);
+        assert!(!manager.list().iter().any(|info| info.id() == id));
+        assert!(!manager.kill(id));
+    }
+
+    #[test]
+    fn test_connection_reuses_id() {
+        let manager = ProcessManager::global();
+        let ctx = QueryContext::with("greptime", "public")
+            .with_connection(manager.next_id(), Some("127.0.0.1:4000".to_string()));
+        let id = ctx.connection_id().unwrap();
+
+        let first = manager.register(&ctx, "SELECT 1");
+        let second = manager.register(&ctx, "SELECT 2");
+        assert_eq!(id, first.id());
+        assert_eq!(id, second.id());
+
+        // Dropping a stale handle keeps the entry of the latest query.
+        drop(first);
+        let info = manager
+            .list()
+            .into_iter()
+            .find(|info| info.id() == id)
+            .unwrap();
+        assert_eq!("SELECT 2", info.query());
+        assert_eq!(Some("127.0.0.1:4000"), info.client());
+
+        drop(second);
+        assert!(!manager.list().iter().any(|info| info.id() == id));
+    }
+}
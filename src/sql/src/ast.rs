@@ -13,7 +13,8 @@
 // limitations under the License.
 
 pub use sqlparser::ast::{
-    visit_expressions_mut, BinaryOperator, ColumnDef, ColumnOption, ColumnOptionDef, DataType,
-    Expr, Function, FunctionArg, FunctionArgExpr, Ident, ObjectName, SqlOption, TableConstraint,
-    TimezoneInfo, Value, VisitMut, Visitor,
+    visit_expressions, visit_expressions_mut, BinaryOperator, ColumnDef, ColumnOption,
+    ColumnOptionDef, DataType, Expr, Function, FunctionArg, FunctionArgExpr, Ident, ObjectName,
+    Query, SelectItem, SetExpr, SqlOption, Statement, TableConstraint, TimezoneInfo, Value, Visit,
+    VisitMut, Visitor, WildcardAdditionalOptions,
 };
@@ -12,7 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use sqlparser::ast::{Expr, FunctionArg, FunctionArgExpr};
 pub use sqlparser::dialect::{Dialect, MySqlDialect, PostgreSqlDialect};
+use sqlparser::parser::{Parser, ParserError};
+use sqlparser::tokenizer::Token;
+
+/// The full-text match operator, `text @@ query` is the same as `matches(text, query)`.
+const MATCH_OPERATOR: &str = "@@";
+/// Precedence of [MATCH_OPERATOR], the same as comparison operators.
+const MATCH_PRECEDENCE: u8 = 20;
 
 /// GreptimeDb dialect
 #[derive(Debug, Clone)]
@@ -40,4 +48,69 @@ impl Dialect for GreptimeDbDialect {
     fn supports_filter_during_aggregation(&self) -> bool {
         true
     }
+
+    fn get_next_precedence(&self, parser: &Parser) -> Option<Result<u8, ParserError>> {
+        is_match_operator(&parser.peek_token().token).then_some(Ok(MATCH_PRECEDENCE))
+    }
+
+    fn parse_infix(
+        &self,
+        parser: &mut Parser,
+        expr: &Expr,
+        precedence: u8,
+    ) -> Option<Result<Expr, ParserError>> {
+        if !is_match_operator(&parser.peek_token().token) {
+            return None;
+        }
+        let _ = parser.next_token();
+        Some(
+            parser
+                .parse_subexpr(precedence)
+                .and_then(|query| self.matches_function(expr.clone(), query)),
+        )
+    }
+}
+
+impl GreptimeDbDialect {
+    /// Builds the `matches(text, query)` function call.
+    fn matches_function(&self, text: Expr, query: Expr) -> Result<Expr, ParserError> {
+        let mut function = Parser::new(self)
+            .try_with_sql("matches(NULL, NULL)")?
+            .parse_expr()?;
+        if let Expr::Function(f) = &mut function {
+            f.args = [text, query]
+                .into_iter()
+                .map(|arg| FunctionArg::Unnamed(FunctionArgExpr::Expr(arg)))
+                .collect();
+        }
+        Ok(function)
+    }
+}
+
+fn is_match_operator(token: &Token) -> bool {
+    matches!(token, Token::Word(w) if w.quote_style.is_none() && w.value == MATCH_OPERATOR)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::ParserContext;
+    use crate::statements::statement::Statement;
+
+    #[test]
+    fn test_parse_match_operator() {
+        let sql = "SELECT * FROM logs WHERE message @@ 'connection refused' AND host = 'h1'";
+        let mut statements =
+            ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        let Statement::Query(query) = statements.remove(0) else {
+            unreachable!()
+        };
+        assert_eq!(
+            "SELECT * FROM logs WHERE matches(message, 'connection refused') AND host = 'h1'",
+            query.to_string()
+        );
+
+        let sql = "SELECT * FROM logs WHERE message @@";
+        assert!(ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).is_err());
+    }
 }
@@ -20,28 +20,36 @@ use sqlparser::tokenizer::{Token, TokenWithLocation};
 
 use crate::ast::{Expr, ObjectName};
 use crate::error::{self, InvalidDatabaseNameSnafu, InvalidTableNameSnafu, Result, SyntaxSnafu};
-use crate::parsers::tql_parser;
+use crate::parsers::{cancel_parser, kill_parser, pause_parser, tql_parser};
 use crate::statements::describe::DescribeTable;
 use crate::statements::drop::DropTable;
 use crate::statements::explain::Explain;
-use crate::statements::show::{ShowCreateTable, ShowDatabases, ShowKind, ShowTables};
+use crate::statements::function::{DropFunction, ShowFunctions};
+use crate::statements::show::{
+    ShowCreateTable, ShowDatabases, ShowJobs, ShowKind, ShowProcesslist, ShowTables,
+};
 use crate::statements::statement::Statement;
 
 /// GrepTime SQL parser context, a simple wrapper for Datafusion SQL parser.
 pub struct ParserContext<'a> {
     pub(crate) parser: Parser<'a>,
     pub(crate) sql: &'a str,
+    pub(crate) dialect: &'a dyn Dialect,
 }
 
 impl<'a> ParserContext<'a> {
     /// Parses SQL with given dialect
-    pub fn create_with_dialect(sql: &'a str, dialect: &dyn Dialect) -> Result<Vec<Statement>> {
+    pub fn create_with_dialect(sql: &'a str, dialect: &'a dyn Dialect) -> Result<Vec<Statement>> {
         let mut stmts: Vec<Statement> = Vec::new();
 
         let parser = Parser::new(dialect)
             .try_with_sql(sql)
             .context(SyntaxSnafu { sql })?;
-        let mut parser_ctx = ParserContext { sql, parser };
+        let mut parser_ctx = ParserContext {
+            sql,
+            parser,
+            dialect,
+        };
 
         let mut expecting_statement_delimiter = false;
         loop {
@@ -135,6 +143,23 @@ impl<'a> ParserContext<'a> {
                         self.parse_tql()
                     }
 
+                    _ if w.value.to_uppercase() == cancel_parser::CANCEL
+                        && w.quote_style.is_none() =>
+                    {
+                        self.parse_cancel()
+                    }
+
+                    _ if w.value.to_uppercase() == kill_parser::KILL && w.quote_style.is_none() => {
+                        self.parse_kill()
+                    }
+
+                    _ if (w.value.to_uppercase() == pause_parser::PAUSE
+                        || w.value.to_uppercase() == pause_parser::RESUME)
+                        && w.quote_style.is_none() =>
+                    {
+                        self.parse_pause_or_resume()
+                    }
+
                     // todo(hl) support more statements.
                     _ => self.unsupported(self.peek_token_as_string()),
                 }
@@ -161,6 +186,18 @@ impl<'a> ParserContext<'a> {
         } else if self.matches_keyword(Keyword::TABLES) {
             let _ = self.parser.next_token();
             self.parse_show_tables()
+        } else if self.consume_token("FUNCTIONS") {
+            Ok(Statement::ShowFunctions(ShowFunctions::default()))
+        } else if self.consume_token("JOBS") {
+            Ok(Statement::ShowJobs(ShowJobs::default()))
+        } else if self.consume_token("PROCESSLIST") {
+            Ok(Statement::ShowProcesslist(ShowProcesslist { full: false }))
+        } else if self.consume_token("FULL") {
+            if self.consume_token("PROCESSLIST") {
+                Ok(Statement::ShowProcesslist(ShowProcesslist { full: true }))
+            } else {
+                self.unsupported(self.peek_token_as_string())
+            }
         } else if self.consume_token("CREATE") {
             if self.consume_token("TABLE") {
                 self.parse_show_create_table()
@@ -300,6 +337,9 @@ impl<'a> ParserContext<'a> {
 
     fn parse_drop(&mut self) -> Result<Statement> {
         let _ = self.parser.next_token();
+        if self.matches_keyword(Keyword::FUNCTION) {
+            return self.parse_drop_function();
+        }
         if !self.matches_keyword(Keyword::TABLE) {
             return self.unsupported(self.peek_token_as_string());
         }
@@ -323,6 +363,21 @@ impl<'a> ParserContext<'a> {
         Ok(Statement::DropTable(DropTable::new(table_ident)))
     }
 
+    fn parse_drop_function(&mut self) -> Result<Statement> {
+        let _ = self.parser.next_token();
+        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+        let name = self
+            .parser
+            .parse_identifier()
+            .context(error::UnexpectedSnafu {
+                sql: self.sql,
+                expected: "a function name",
+                actual: self.peek_token_as_string(),
+            })?;
+
+        Ok(Statement::DropFunction(DropFunction { name, if_exists }))
+    }
+
     // Report unexpected token
     pub(crate) fn expected<T>(&self, expected: &str, found: TokenWithLocation) -> Result<T> {
         Err(ParserError::ParserError(format!(
@@ -621,6 +676,43 @@ mod tests {
         )
     }
 
+    #[test]
+    pub fn test_drop_function() {
+        let sql = "DROP FUNCTION c_to_f";
+        let mut stmts = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(
+            stmts.pop().unwrap(),
+            Statement::DropFunction(DropFunction {
+                name: Ident::new("c_to_f"),
+                if_exists: false,
+            })
+        );
+
+        let sql = "drop function if exists c_to_f";
+        let mut stmts = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(
+            stmts.pop().unwrap(),
+            Statement::DropFunction(DropFunction {
+                name: Ident::new("c_to_f"),
+                if_exists: true,
+            })
+        );
+
+        let sql = "DROP FUNCTION";
+        let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {});
+        assert!(result.is_err(), "result is: {result:?}");
+    }
+
+    #[test]
+    pub fn test_show_functions() {
+        let sql = "SHOW FUNCTIONS";
+        let mut stmts = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(
+            stmts.pop().unwrap(),
+            Statement::ShowFunctions(ShowFunctions::default())
+        );
+    }
+
     fn test_timestamp_precision(sql: &str, expected_type: ConcreteDataType) {
         match ParserContext::create_with_dialect(sql, &GreptimeDbDialect {})
             .unwrap()
@@ -13,10 +13,13 @@
 // limitations under the License.
 
 mod alter_parser;
+pub(crate) mod cancel_parser;
 pub(crate) mod copy_parser;
 pub(crate) mod create_parser;
 pub(crate) mod delete_parser;
 pub(crate) mod insert_parser;
+pub(crate) mod kill_parser;
+pub(crate) mod pause_parser;
 pub(crate) mod query_parser;
 pub(crate) mod tql_parser;
 pub(crate) mod truncate_parser;
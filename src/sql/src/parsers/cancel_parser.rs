@@ -0,0 +1,80 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snafu::ResultExt;
+
+use crate::error::{self, Result};
+use crate::parser::ParserContext;
+use crate::statements::cancel::CancelJob;
+use crate::statements::statement::Statement;
+
+pub const CANCEL: &str = "CANCEL";
+const JOB: &str = "JOB";
+
+/// CANCEL JOB '<job_id>';
+impl<'a> ParserContext<'a> {
+    pub(crate) fn parse_cancel(&mut self) -> Result<Statement> {
+        let _ = self.parser.next_token();
+        if !self.consume_token(JOB) {
+            return self.unsupported(self.peek_token_as_string());
+        }
+
+        let job_id =
+            self.parser
+                .parse_literal_string()
+                .with_context(|_| error::UnexpectedSnafu {
+                    sql: self.sql,
+                    expected: "a quoted job id",
+                    actual: self.peek_token_as_string(),
+                })?;
+
+        Ok(Statement::CancelJob(CancelJob::new(job_id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::GreptimeDbDialect;
+
+    #[test]
+    pub fn test_parse_cancel_job() {
+        let sql = "CANCEL JOB '9f805a1f-05f7-490c-9f91-bd56e3cc54c1'";
+        let mut stmts = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(
+            stmts.pop().unwrap(),
+            Statement::CancelJob(CancelJob::new(
+                "9f805a1f-05f7-490c-9f91-bd56e3cc54c1".to_string()
+            ))
+        );
+
+        let sql = "cancel job 'abc';";
+        let mut stmts = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(
+            stmts.pop().unwrap(),
+            Statement::CancelJob(CancelJob::new("abc".to_string()))
+        );
+    }
+
+    #[test]
+    pub fn test_parse_invalid_cancel_job() {
+        let sql = "CANCEL JOB";
+        let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {});
+        assert!(result.is_err(), "result is: {result:?}");
+
+        let sql = "CANCEL QUERY 'abc'";
+        let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {});
+        assert!(result.is_err(), "result is: {result:?}");
+    }
+}
@@ -13,7 +13,9 @@
 // limitations under the License.
 
 use std::cmp::Ordering;
+use std::collections::HashSet;
 
+use datatypes::prelude::ConcreteDataType;
 use itertools::Itertools;
 use once_cell::sync::Lazy;
 use snafu::{ensure, OptionExt, ResultExt};
@@ -24,17 +26,22 @@ use sqlparser::parser::IsOptional::Mandatory;
 use sqlparser::parser::{Parser, ParserError};
 use sqlparser::tokenizer::{Token, TokenWithLocation, Word};
 
-use crate::ast::{ColumnDef, Ident, TableConstraint, Value as SqlValue};
+use crate::ast::{ColumnDef, Expr, Ident, TableConstraint, Value as SqlValue};
 use crate::error::{
     self, InvalidColumnOptionSnafu, InvalidTimeIndexSnafu, MissingTimeIndexSnafu, Result,
     SyntaxSnafu,
 };
 use crate::parser::ParserContext;
 use crate::statements::create::{
-    CreateDatabase, CreateExternalTable, CreateTable, PartitionEntry, Partitions, TIME_INDEX,
+    CreateDatabase, CreateExternalTable, CreateTable, PartitionEntry, Partitions, FULLTEXT,
+    TIME_INDEX,
 };
+use crate::statements::function::{CreateFunction, FunctionArgDef};
 use crate::statements::statement::Statement;
-use crate::statements::{sql_data_type_to_concrete_data_type, sql_value_to_value};
+use crate::statements::{
+    fulltext_analyzer, fulltext_column_option_with, sql_data_type_to_concrete_data_type,
+    sql_value_to_value,
+};
 use crate::util::parse_option_string;
 
 const ENGINE: &str = "ENGINE";
@@ -54,6 +61,8 @@ impl<'a> ParserContext<'a> {
 
                 Keyword::EXTERNAL => self.parse_create_external_table(),
 
+                Keyword::FUNCTION => self.parse_create_function(),
+
                 _ => self.unsupported(w.to_string()),
             },
             unexpected => self.unsupported(unexpected.to_string()),
@@ -124,6 +133,88 @@ impl<'a> ParserContext<'a> {
         }))
     }
 
+    fn parse_create_function(&mut self) -> Result<Statement> {
+        let _ = self.parser.next_token();
+
+        let name = self
+            .parser
+            .parse_identifier()
+            .context(error::UnexpectedSnafu {
+                sql: self.sql,
+                expected: "a function name",
+                actual: self.peek_token_as_string(),
+            })?;
+
+        let args = self.parse_comma_separated(Self::parse_function_arg)?;
+        let mut names = HashSet::with_capacity(args.len());
+        for arg in &args {
+            ensure!(
+                names.insert(arg.name.value.to_lowercase()),
+                error::InvalidSqlSnafu {
+                    msg: format!("duplicate argument name: {}", arg.name),
+                }
+            );
+        }
+
+        self.parser
+            .expect_keyword(Keyword::RETURNS)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        let return_type = self
+            .parser
+            .parse_data_type()
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+
+        self.parser
+            .expect_keyword(Keyword::AS)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        let body = self
+            .parser
+            .parse_literal_string()
+            .context(error::UnexpectedSnafu {
+                sql: self.sql,
+                expected: "a quoted SQL expression",
+                actual: self.peek_token_as_string(),
+            })?;
+        let body = self.parse_function_body(&body)?;
+
+        Ok(Statement::CreateFunction(CreateFunction {
+            name,
+            args,
+            return_type,
+            body,
+        }))
+    }
+
+    fn parse_function_arg(&mut self) -> Result<FunctionArgDef> {
+        let name = self
+            .parser
+            .parse_identifier()
+            .context(error::UnexpectedSnafu {
+                sql: self.sql,
+                expected: "an argument name",
+                actual: self.peek_token_as_string(),
+            })?;
+        let data_type = self
+            .parser
+            .parse_data_type()
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        Ok(FunctionArgDef { name, data_type })
+    }
+
+    /// Parses the body of a function, which must be exactly one expression.
+    fn parse_function_body(&self, body: &str) -> Result<Expr> {
+        let mut parser = Parser::new(self.dialect)
+            .try_with_sql(body)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        let expr = parser
+            .parse_expr()
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        parser
+            .expect_token(&Token::EOF)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        Ok(expr)
+    }
+
     fn parse_create_table(&mut self) -> Result<Statement> {
         let _ = self.parser.next_token();
         let if_not_exists =
@@ -380,6 +471,19 @@ impl<'a> ParserContext<'a> {
             let _ = column.options.remove(index);
         }
 
+        if fulltext_analyzer(&column)?.is_some() {
+            ensure!(
+                matches!(
+                    sql_data_type_to_concrete_data_type(&column.data_type)?,
+                    ConcreteDataType::String(_)
+                ),
+                InvalidColumnOptionSnafu {
+                    name: column.name.to_string(),
+                    msg: "fulltext column data type should be string",
+                }
+            );
+        }
+
         columns.push(column);
 
         Ok(())
@@ -470,6 +574,21 @@ impl<'a> ParserContext<'a> {
                     keyword: Keyword::INDEX,
                 }),
             ])))
+        } else if matches!(&parser.peek_token().token, Token::Word(w) if w.value.eq_ignore_ascii_case(FULLTEXT))
+        {
+            let _ = parser.next_token();
+            let options = parser
+                .parse_options(Keyword::WITH)?
+                .into_iter()
+                .map(|option| {
+                    let value = match option.value {
+                        Value::SingleQuotedString(v) | Value::DoubleQuotedString(v) => v,
+                        v => v.to_string(),
+                    };
+                    (option.name.value, value)
+                })
+                .collect();
+            Ok(Some(fulltext_column_option_with(options)))
         } else {
             Ok(None)
         }
@@ -783,6 +902,7 @@ mod tests {
     use std::collections::HashMap;
 
     use common_catalog::consts::IMMUTABLE_FILE_ENGINE;
+    use datatypes::schema::FulltextAnalyzer;
     use sqlparser::ast::ColumnOption::NotNull;
 
     use super::*;
@@ -920,6 +1040,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_create_function() {
+        let sql = "CREATE FUNCTION c_to_f(c DOUBLE) RETURNS DOUBLE AS 'c * 9 / 5 + 32'";
+        let stmts = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(1, stmts.len());
+        let Statement::CreateFunction(c) = &stmts[0] else { unreachable!() };
+        assert_eq!("c_to_f", c.name.value);
+        assert_eq!(1, c.args.len());
+        assert_eq!("c", c.args[0].name.value);
+        assert_eq!(DataType::Double, c.args[0].data_type);
+        assert_eq!(DataType::Double, c.return_type);
+        assert_eq!("c * 9 / 5 + 32", c.body.to_string());
+        assert_eq!(
+            "CREATE FUNCTION c_to_f(c DOUBLE) RETURNS DOUBLE AS 'c * 9 / 5 + 32'",
+            c.to_string()
+        );
+
+        // Quotes in the body are escaped.
+        let sql = "create function greet(name STRING) returns STRING as 'concat(''hi '', name)'";
+        let stmts = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        let Statement::CreateFunction(c) = &stmts[0] else { unreachable!() };
+        assert_eq!("concat('hi ', name)", c.body.to_string());
+        let reparsed =
+            ParserContext::create_with_dialect(&c.to_string(), &GreptimeDbDialect {}).unwrap();
+        assert_eq!(Statement::CreateFunction(c.clone()), reparsed[0]);
+
+        let sql = "CREATE FUNCTION pi() RETURNS DOUBLE AS '3.14159'";
+        let stmts = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        let Statement::CreateFunction(c) = &stmts[0] else { unreachable!() };
+        assert!(c.args.is_empty());
+    }
+
+    #[test]
+    fn test_parse_invalid_create_function() {
+        let sqls = [
+            // Missing return type.
+            "CREATE FUNCTION f(a DOUBLE) AS 'a + 1'",
+            // Body isn't quoted.
+            "CREATE FUNCTION f(a DOUBLE) RETURNS DOUBLE AS a + 1",
+            // Body isn't a single expression.
+            "CREATE FUNCTION f(a DOUBLE) RETURNS DOUBLE AS 'a + 1, a'",
+            "CREATE FUNCTION f(a DOUBLE) RETURNS DOUBLE AS 'SELECT a'",
+            // Duplicate arguments.
+            "CREATE FUNCTION f(a DOUBLE, A DOUBLE) RETURNS DOUBLE AS 'a + 1'",
+        ];
+        for sql in sqls {
+            let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {});
+            assert!(result.is_err(), "sql: {sql}, result: {result:?}");
+        }
+    }
+
     #[test]
     fn test_validate_create() {
         let sql = r"
@@ -1475,6 +1646,47 @@ ENGINE=mito";
         assert_matches!(result, Err(crate::error::Error::InvalidTimeIndex { .. }));
     }
 
+    #[test]
+    fn test_parse_fulltext_column() {
+        let sql = r"create table logs(
+                             ts timestamp time index,
+                             message string fulltext,
+                             path string FULLTEXT WITH (analyzer = 'Whitespace'))";
+        let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        let Statement::CreateTable(c) = &result[0] else {
+            unreachable!()
+        };
+        assert_eq!(
+            Some(FulltextAnalyzer::English),
+            fulltext_analyzer(&c.columns[1]).unwrap()
+        );
+        assert_eq!(
+            Some(FulltextAnalyzer::Whitespace),
+            fulltext_analyzer(&c.columns[2]).unwrap()
+        );
+        assert_eq!(None, fulltext_analyzer(&c.columns[0]).unwrap());
+
+        // The displayed statement can be parsed again.
+        let result =
+            ParserContext::create_with_dialect(&c.to_string(), &GreptimeDbDialect {}).unwrap();
+        let Statement::CreateTable(c) = &result[0] else {
+            unreachable!()
+        };
+        assert_eq!(
+            Some(FulltextAnalyzer::Whitespace),
+            fulltext_analyzer(&c.columns[2]).unwrap()
+        );
+
+        for sql in [
+            "create table logs(ts timestamp time index, n int fulltext)",
+            "create table logs(ts timestamp time index, s string fulltext with (analyzer = 'chinese'))",
+            "create table logs(ts timestamp time index, s string fulltext with (tokenizer = 'english'))",
+        ] {
+            let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {});
+            assert_matches!(result, Err(crate::error::Error::InvalidColumnOption { .. }));
+        }
+    }
+
     #[test]
     fn test_invalid_column_name() {
         let sql = "create table foo(user string, i bigint time index)";
@@ -0,0 +1,90 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snafu::OptionExt;
+use sqlparser::tokenizer::Token;
+
+use crate::error::{self, Result};
+use crate::parser::ParserContext;
+use crate::statements::kill::KillQuery;
+use crate::statements::statement::Statement;
+
+pub const KILL: &str = "KILL";
+const QUERY: &str = "QUERY";
+
+/// KILL QUERY <id>;
+impl<'a> ParserContext<'a> {
+    pub(crate) fn parse_kill(&mut self) -> Result<Statement> {
+        let _ = self.parser.next_token();
+        // Killing a connection is not supported, only the running query could be stopped.
+        if !self.consume_token(QUERY) {
+            return self.unsupported(self.peek_token_as_string());
+        }
+
+        let token = self.parser.next_token();
+        let id = match &token.token {
+            Token::Number(id, _) => id.parse::<u32>().ok(),
+            _ => None,
+        };
+        let id = id.with_context(|| error::UnexpectedSnafu {
+            sql: self.sql,
+            expected: "a process id",
+            actual: token.to_string(),
+        })?;
+
+        Ok(Statement::KillQuery(KillQuery::new(id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::GreptimeDbDialect;
+
+    #[test]
+    pub fn test_parse_kill_query() {
+        let sql = "KILL QUERY 42";
+        let mut stmts = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(
+            stmts.pop().unwrap(),
+            Statement::KillQuery(KillQuery::new(42))
+        );
+
+        let sql = "kill query 7;";
+        let mut stmts = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(
+            stmts.pop().unwrap(),
+            Statement::KillQuery(KillQuery::new(7))
+        );
+    }
+
+    #[test]
+    pub fn test_parse_invalid_kill_query() {
+        let sql = "KILL QUERY";
+        let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {});
+        assert!(result.is_err(), "result is: {result:?}");
+
+        let sql = "KILL QUERY 'abc'";
+        let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {});
+        assert!(result.is_err(), "result is: {result:?}");
+
+        let sql = "KILL QUERY -1";
+        let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {});
+        assert!(result.is_err(), "result is: {result:?}");
+
+        let sql = "KILL 42";
+        let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {});
+        assert!(result.is_err(), "result is: {result:?}");
+    }
+}
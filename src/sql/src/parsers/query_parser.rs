@@ -13,15 +13,32 @@
 // limitations under the License.
 
 use snafu::prelude::*;
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::{Parser, ParserError};
+use sqlparser::tokenizer::{Token, TokenWithLocation};
 
 use crate::error::{self, Result};
 use crate::parser::ParserContext;
-use crate::statements::query::Query;
+use crate::statements::query::{Align, Query, RANGE_FN};
 use crate::statements::statement::Statement;
 
+const ALIGN: &str = "ALIGN";
+const FILL: &str = "FILL";
+
 impl<'a> ParserContext<'a> {
     /// Parses select and it's variants.
     pub(crate) fn parse_query(&mut self) -> Result<Statement> {
+        let tokens = self.collect_statement_tokens();
+        if Self::is_range_query(&tokens) {
+            return self
+                .parse_range_query(tokens)
+                .context(error::SyntaxSnafu { sql: self.sql });
+        }
+        // Not a range query, rewinds and lets sqlparser do the work.
+        for _ in 0..tokens.len() {
+            self.parser.prev_token();
+        }
+
         let spquery = self
             .parser
             .parse_query()
@@ -29,12 +46,267 @@ impl<'a> ParserContext<'a> {
 
         Ok(Statement::Query(Box::new(Query::try_from(spquery)?)))
     }
+
+    /// Consumes the tokens of current statement, until the statement delimiter or EOF.
+    fn collect_statement_tokens(&mut self) -> Vec<TokenWithLocation> {
+        let mut tokens = vec![];
+        let mut depth = 0usize;
+        loop {
+            let token = self.parser.peek_token();
+            match token.token {
+                Token::EOF => break,
+                Token::SemiColon if depth == 0 => break,
+                Token::LParen => depth += 1,
+                Token::RParen => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            tokens.push(self.parser.next_token());
+        }
+        tokens
+    }
+
+    /// A range query has either `RANGE '<duration>'` or `ALIGN '<duration>'` at the top level.
+    fn is_range_query(tokens: &[TokenWithLocation]) -> bool {
+        top_level_positions(tokens)
+            .into_iter()
+            .any(|i| is_range_keyword(tokens, i) || is_align_keyword(tokens, i))
+    }
+
+    /// Parses range query, like
+    /// `SELECT avg(v) RANGE '5m' FILL PREV FROM t ALIGN '1m' BY (host) FILL NULL`.
+    ///
+    /// Each `<expr> RANGE '<duration>' [FILL <fill>]` select item is rewritten into
+    /// `range_fn(<expr>, '<duration>', '<fill>')`, and the `ALIGN` clause is taken out of
+    /// the statement into [Align] before handing the rest to sqlparser.
+    fn parse_range_query(
+        &self,
+        mut tokens: Vec<TokenWithLocation>,
+    ) -> std::result::Result<Statement, ParserError> {
+        let align = self.take_align_clause(&mut tokens)?;
+        let (tokens, has_range) = rewrite_range_items(tokens)?;
+
+        let Some(align) = align else {
+            return Err(ParserError::ParserError(
+                "RANGE query requires an ALIGN clause".to_string(),
+            ));
+        };
+        if !has_range {
+            return Err(ParserError::ParserError(
+                "ALIGN clause requires at least one RANGE expression".to_string(),
+            ));
+        }
+
+        let mut parser = Parser::new(self.dialect).with_tokens_with_locations(tokens);
+        let inner = parser.parse_query()?;
+        let next = parser.next_token();
+        if next.token != Token::EOF {
+            return parser.expected("end of statement", next);
+        }
+
+        Ok(Statement::Query(Box::new(Query {
+            inner,
+            align: Some(align),
+        })))
+    }
+
+    /// Removes the `ALIGN` clause from `tokens` and parses it.
+    fn take_align_clause(
+        &self,
+        tokens: &mut Vec<TokenWithLocation>,
+    ) -> std::result::Result<Option<Align>, ParserError> {
+        let Some(start) = top_level_positions(tokens)
+            .into_iter()
+            .find(|i| is_align_keyword(tokens, *i)) else {
+            return Ok(None);
+        };
+        // The clause ends at ORDER BY, LIMIT, OFFSET, FETCH or the end of statement.
+        let end = top_level_positions(tokens)
+            .into_iter()
+            .filter(|i| *i > start)
+            .find(|i| {
+                matches!(
+                    &tokens[*i].token,
+                    Token::Word(w) if matches!(
+                        w.keyword,
+                        Keyword::ORDER | Keyword::LIMIT | Keyword::OFFSET | Keyword::FETCH
+                    )
+                )
+            })
+            .unwrap_or(tokens.len());
+        let clause = tokens.drain(start..end).collect::<Vec<_>>();
+
+        let mut parser = Parser::new(self.dialect).with_tokens_with_locations(clause);
+        let _ = parser.next_token(); // ALIGN
+        let interval = parser.parse_literal_string()?;
+
+        let mut by = vec![];
+        if parser.parse_keyword(Keyword::BY) {
+            parser.expect_token(&Token::LParen)?;
+            if !parser.consume_token(&Token::RParen) {
+                by = parser.parse_comma_separated(Parser::parse_expr)?;
+                parser.expect_token(&Token::RParen)?;
+            }
+        }
+
+        let fill = if consume_word(&mut parser, FILL) {
+            Some(parse_fill(&mut parser)?)
+        } else {
+            None
+        };
+
+        let next = parser.next_token();
+        if next.token != Token::EOF {
+            return parser.expected("end of ALIGN clause", next);
+        }
+
+        Ok(Some(Align { interval, by, fill }))
+    }
+}
+
+/// Indexes of the tokens that are not enclosed in any parentheses.
+fn top_level_positions(tokens: &[TokenWithLocation]) -> Vec<usize> {
+    let mut depth = 0usize;
+    let mut positions = Vec::with_capacity(tokens.len());
+    for (i, token) in tokens.iter().enumerate() {
+        match token.token {
+            Token::LParen => depth += 1,
+            Token::RParen => depth = depth.saturating_sub(1),
+            _ if depth == 0 => positions.push(i),
+            _ => {}
+        }
+    }
+    positions
+}
+
+fn is_range_keyword(tokens: &[TokenWithLocation], i: usize) -> bool {
+    matches!(&tokens[i].token, Token::Word(w) if w.keyword == Keyword::RANGE && w.quote_style.is_none())
+        && matches!(
+            tokens.get(i + 1).map(|t| &t.token),
+            Some(Token::SingleQuotedString(_))
+        )
+}
+
+fn is_align_keyword(tokens: &[TokenWithLocation], i: usize) -> bool {
+    matches!(&tokens[i].token, Token::Word(w) if w.value.eq_ignore_ascii_case(ALIGN) && w.quote_style.is_none())
+        && matches!(
+            tokens.get(i + 1).map(|t| &t.token),
+            Some(Token::SingleQuotedString(_))
+        )
+}
+
+fn consume_word(parser: &mut Parser, word: &str) -> bool {
+    match parser.peek_token().token {
+        Token::Word(w) if w.value.eq_ignore_ascii_case(word) && w.quote_style.is_none() => {
+            let _ = parser.next_token();
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Parses the fill mode after `FILL`, which is `NULL`, `PREV`, `LINEAR` or a constant number.
+fn parse_fill(parser: &mut Parser) -> std::result::Result<String, ParserError> {
+    let next = parser.next_token();
+    match next.token {
+        Token::Word(w) if w.quote_style.is_none() => Ok(w.value.to_uppercase()),
+        Token::Number(n, _) => Ok(n),
+        Token::Minus => match parser.next_token().token {
+            Token::Number(n, _) => Ok(format!("-{n}")),
+            other => Err(ParserError::ParserError(format!(
+                "Expected a number after '-' in FILL, found: {other}"
+            ))),
+        },
+        Token::SingleQuotedString(s) => Ok(s),
+        _ => parser.expected("NULL, PREV, LINEAR or a constant after FILL", next),
+    }
+}
+
+/// Rewrites every top level `<expr> RANGE '<duration>' [FILL <fill>]` in the select list into
+/// `range_fn(<expr>, '<duration>', '<fill>')`. Returns the new tokens and whether any select
+/// item is rewritten.
+fn rewrite_range_items(
+    tokens: Vec<TokenWithLocation>,
+) -> std::result::Result<(Vec<TokenWithLocation>, bool), ParserError> {
+    let positions = top_level_positions(&tokens);
+    let Some(select) = positions
+        .iter()
+        .copied()
+        .find(|i| matches!(&tokens[*i].token, Token::Word(w) if w.keyword == Keyword::SELECT)) else {
+        return Ok((tokens, false));
+    };
+    let from = positions
+        .iter()
+        .copied()
+        .find(|i| *i > select && matches!(&tokens[*i].token, Token::Word(w) if w.keyword == Keyword::FROM))
+        .unwrap_or(tokens.len());
+
+    let mut items = vec![];
+    let mut item_start = select + 1;
+    for i in positions.iter().copied().filter(|i| *i > select && *i < from) {
+        if tokens[i].token == Token::Comma {
+            items.push(item_start..i);
+            item_start = i + 1;
+        }
+    }
+    items.push(item_start..from);
+
+    let mut rewritten = tokens[..=select].to_vec();
+    let mut has_range = false;
+    for (n, item) in items.into_iter().enumerate() {
+        if n > 0 {
+            rewritten.push(TokenWithLocation::wrap(Token::Comma));
+        }
+        let item_tokens = &tokens[item];
+        let Some(range_pos) = top_level_positions(item_tokens)
+            .into_iter()
+            .find(|i| is_range_keyword(item_tokens, *i)) else {
+            rewritten.extend_from_slice(item_tokens);
+            continue;
+        };
+        if range_pos == 0 {
+            return Err(ParserError::ParserError(
+                "Expected an expression before RANGE".to_string(),
+            ));
+        }
+        has_range = true;
+
+        // `range_pos + 1` is the range duration, which is checked by `is_range_keyword`.
+        let mut rest = range_pos + 2;
+        let fill = match item_tokens.get(rest).map(|t| &t.token) {
+            Some(Token::Word(w)) if w.value.eq_ignore_ascii_case(FILL) && w.quote_style.is_none() => {
+                let mut parser = Parser::new(&sqlparser::dialect::GenericDialect {})
+                    .with_tokens_with_locations(item_tokens[rest + 1..].to_vec());
+                let fill = parse_fill(&mut parser)?;
+                // FILL keyword, an optional minus sign and the fill value.
+                rest += match item_tokens.get(rest + 1).map(|t| &t.token) {
+                    Some(Token::Minus) => 3,
+                    _ => 2,
+                };
+                fill
+            }
+            _ => String::new(),
+        };
+
+        rewritten.push(TokenWithLocation::wrap(Token::make_word(RANGE_FN, None)));
+        rewritten.push(TokenWithLocation::wrap(Token::LParen));
+        rewritten.extend_from_slice(&item_tokens[..range_pos]);
+        rewritten.push(TokenWithLocation::wrap(Token::Comma));
+        rewritten.push(item_tokens[range_pos + 1].clone());
+        rewritten.push(TokenWithLocation::wrap(Token::Comma));
+        rewritten.push(TokenWithLocation::wrap(Token::SingleQuotedString(fill)));
+        rewritten.push(TokenWithLocation::wrap(Token::RParen));
+        rewritten.extend_from_slice(&item_tokens[rest.min(item_tokens.len())..]);
+    }
+    rewritten.extend_from_slice(&tokens[from..]);
+
+    Ok((rewritten, has_range))
 }
 
 #[cfg(test)]
 mod tests {
     use crate::dialect::GreptimeDbDialect;
     use crate::parser::ParserContext;
+    use crate::statements::statement::Statement;
 
     #[test]
     pub fn test_parse_query() {
@@ -56,4 +328,49 @@ mod tests {
             .to_string()
             .contains("Expected an expression"));
     }
+
+    #[test]
+    pub fn test_parse_range_query() {
+        let sql = "SELECT ts, host, avg(cpu) RANGE '5m' FILL PREV AS a, max(cpu) RANGE '10m' \
+           FROM host_cpu \
+           WHERE host != 'h1' \
+           ALIGN '1m' BY (host) FILL -1 \
+           ORDER BY ts LIMIT 10";
+
+        let mut result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(1, result.len());
+        let Statement::Query(query) = result.remove(0) else {
+            unreachable!()
+        };
+        let align = query.align.as_ref().unwrap();
+        assert_eq!("1m", align.interval);
+        assert_eq!(1, align.by.len());
+        assert_eq!(Some("-1".to_string()), align.fill);
+        assert_eq!(
+            "SELECT ts, host, range_fn(avg(cpu), '5m', 'PREV') AS a, range_fn(max(cpu), '10m', '') \
+            FROM host_cpu WHERE host <> 'h1' ORDER BY ts LIMIT 10 ALIGN '1m' BY (host) FILL -1",
+            query.to_string()
+        );
+    }
+
+    #[test]
+    pub fn test_parse_invalid_range_query() {
+        let sql = "SELECT avg(cpu) RANGE '5m' FROM host_cpu";
+        let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {});
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("RANGE query requires an ALIGN clause"));
+
+        let sql = "SELECT avg(cpu) FROM host_cpu ALIGN '5m'";
+        let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {});
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("ALIGN clause requires at least one RANGE expression"));
+
+        let sql = "SELECT avg(cpu) RANGE '5m' FROM host_cpu ALIGN '5m' BY host";
+        let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {});
+        assert!(result.is_err());
+    }
 }
@@ -13,13 +13,17 @@
 // limitations under the License.
 
 pub mod alter;
+pub mod cancel;
 pub mod copy;
 pub mod create;
 pub mod delete;
 pub mod describe;
 pub mod drop;
 pub mod explain;
+pub mod function;
 pub mod insert;
+pub mod kill;
+pub mod pause;
 pub mod query;
 pub mod show;
 pub mod statement;
@@ -35,10 +39,11 @@ use common_base::bytes::Bytes;
 use common_query::AddColumnLocation;
 use common_time::Timestamp;
 use datatypes::prelude::ConcreteDataType;
-use datatypes::schema::{ColumnDefaultConstraint, ColumnSchema, COMMENT_KEY};
+use datatypes::schema::{ColumnDefaultConstraint, ColumnSchema, FulltextAnalyzer, COMMENT_KEY};
 use datatypes::types::TimestampType;
 use datatypes::value::Value;
 use snafu::{ensure, OptionExt, ResultExt};
+use sqlparser::tokenizer::Token;
 
 use crate::ast::{
     ColumnDef, ColumnOption, ColumnOptionDef, DataType as SqlDataType, Expr, TimezoneInfo,
@@ -46,7 +51,7 @@ use crate::ast::{
 };
 use crate::error::{
     self, ColumnTypeMismatchSnafu, ConvertSqlValueSnafu, ConvertToGrpcDataTypeSnafu,
-    ConvertValueSnafu, InvalidSqlValueSnafu, ParseSqlValueSnafu, Result,
+    ConvertValueSnafu, InvalidColumnOptionSnafu, InvalidSqlValueSnafu, ParseSqlValueSnafu, Result,
     SerializeColumnDefaultConstraintSnafu, TimestampOverflowSnafu, UnsupportedDefaultValueSnafu,
 };
 
@@ -304,7 +309,71 @@ pub fn column_def_to_schema(column_def: &ColumnDef, is_time_index: bool) -> Resu
             .insert(COMMENT_KEY.to_string(), c.to_string());
     }
 
-    Ok(column_schema)
+    let analyzer = fulltext_analyzer(column_def)?;
+    Ok(column_schema.with_fulltext(analyzer))
+}
+
+/// Builds the `FULLTEXT WITH (analyzer = '...')` column option.
+pub fn fulltext_column_option(analyzer: FulltextAnalyzer) -> ColumnOption {
+    fulltext_column_option_with(vec![(
+        create::FULLTEXT_ANALYZER.to_string(),
+        analyzer.to_string(),
+    )])
+}
+
+/// Builds the `FULLTEXT` column option with given options, which are validated
+/// by [fulltext_analyzer].
+pub(crate) fn fulltext_column_option_with(options: Vec<(String, String)>) -> ColumnOption {
+    let mut tokens = vec![Token::make_keyword(create::FULLTEXT)];
+    if !options.is_empty() {
+        tokens.push(Token::make_keyword("WITH"));
+        tokens.push(Token::LParen);
+        for (i, (name, value)) in options.into_iter().enumerate() {
+            if i > 0 {
+                tokens.push(Token::Comma);
+            }
+            tokens.push(Token::make_word(&name, None));
+            tokens.push(Token::Eq);
+            tokens.push(Token::SingleQuotedString(value));
+        }
+        tokens.push(Token::RParen);
+    }
+    ColumnOption::DialectSpecific(tokens)
+}
+
+/// Returns the analyzer if the column has the `FULLTEXT` option.
+pub fn fulltext_analyzer(column_def: &ColumnDef) -> Result<Option<FulltextAnalyzer>> {
+    for option in &column_def.options {
+        let ColumnOption::DialectSpecific(tokens) = &option.option else {
+            continue;
+        };
+        if !matches!(tokens.first(), Some(Token::Word(w)) if w.value.eq_ignore_ascii_case(create::FULLTEXT))
+        {
+            continue;
+        }
+
+        let mut analyzer = FulltextAnalyzer::default();
+        for window in tokens.windows(3) {
+            if let [Token::Word(name), Token::Eq, Token::SingleQuotedString(value)] = window {
+                ensure!(
+                    name.value.eq_ignore_ascii_case(create::FULLTEXT_ANALYZER),
+                    InvalidColumnOptionSnafu {
+                        name: column_def.name.to_string(),
+                        msg: format!("unknown fulltext option: {}", name.value),
+                    }
+                );
+                analyzer = value.parse().map_err(|msg| {
+                    InvalidColumnOptionSnafu {
+                        name: column_def.name.to_string(),
+                        msg,
+                    }
+                    .build()
+                })?;
+            }
+        }
+        return Ok(Some(analyzer));
+    }
+    Ok(None)
 }
 
 /// Convert `ColumnDef` in sqlparser to `ColumnDef` in gRPC proto.
@@ -795,6 +864,29 @@ mod tests {
         );
     }
 
+    #[test]
+    pub fn test_column_def_to_schema_with_fulltext() {
+        let column_def = ColumnDef {
+            name: "message".into(),
+            data_type: SqlDataType::String,
+            collation: None,
+            options: vec![ColumnOptionDef {
+                name: None,
+                option: fulltext_column_option(FulltextAnalyzer::Whitespace),
+            }],
+        };
+
+        let column_schema = column_def_to_schema(&column_def, false).unwrap();
+        assert_eq!(
+            Some(FulltextAnalyzer::Whitespace),
+            column_schema.fulltext_analyzer()
+        );
+        assert_eq!(
+            "message STRING FULLTEXT WITH ( analyzer = 'Whitespace' )",
+            column_def.to_string()
+        );
+    }
+
     #[test]
     pub fn test_parse_placeholder_value() {
         assert!(sql_value_to_value(
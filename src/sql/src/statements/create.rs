@@ -47,6 +47,10 @@ macro_rules! format_list_comma {
 
 /// Time index name, used in table constraints.
 pub const TIME_INDEX: &str = "__time_index";
+/// Column option to build a full-text index on the column.
+pub const FULLTEXT: &str = "FULLTEXT";
+/// Option of the `FULLTEXT` column option to choose the analyzer.
+pub const FULLTEXT_ANALYZER: &str = "analyzer";
 
 #[inline]
 pub fn is_time_index(constraint: &TableConstraint) -> bool {
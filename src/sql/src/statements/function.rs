@@ -0,0 +1,68 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::{Display, Formatter};
+
+use itertools::Itertools;
+
+use crate::ast::{DataType, Expr, Ident};
+
+/// An argument of the function in `CREATE FUNCTION`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionArgDef {
+    pub name: Ident,
+    pub data_type: DataType,
+}
+
+impl Display for FunctionArgDef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.name, self.data_type)
+    }
+}
+
+/// SQL structure for `CREATE FUNCTION <name>(<args>) RETURNS <type> AS '<expr>'`.
+///
+/// The function evaluates the SQL expression `body` over its arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateFunction {
+    pub name: Ident,
+    pub args: Vec<FunctionArgDef>,
+    pub return_type: DataType,
+    pub body: Expr,
+}
+
+impl Display for CreateFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "CREATE FUNCTION {}({}) RETURNS {} AS '{}'",
+            self.name,
+            self.args.iter().join(", "),
+            self.return_type,
+            self.body.to_string().replace('\'', "''"),
+        )
+    }
+}
+
+/// SQL structure for `DROP FUNCTION [IF EXISTS] <name>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropFunction {
+    pub name: Ident,
+    /// Drop if exists
+    pub if_exists: bool,
+}
+
+/// SQL structure for `SHOW FUNCTIONS`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShowFunctions {}
@@ -0,0 +1,31 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// SQL structure for `KILL QUERY <id>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KillQuery {
+    id: u32,
+}
+
+impl KillQuery {
+    /// Creates a statement for `KILL QUERY`.
+    pub fn new(id: u32) -> Self {
+        Self { id }
+    }
+
+    /// Id of the query in the process list.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+}
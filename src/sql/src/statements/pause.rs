@@ -0,0 +1,47 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// SQL structure for `PAUSE JOB '<job_id>'`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PauseJob {
+    job_id: String,
+}
+
+impl PauseJob {
+    /// Creates a statement for `PAUSE JOB`.
+    pub fn new(job_id: String) -> Self {
+        Self { job_id }
+    }
+
+    pub fn job_id(&self) -> &str {
+        &self.job_id
+    }
+}
+
+/// SQL structure for `RESUME JOB '<job_id>'`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResumeJob {
+    job_id: String,
+}
+
+impl ResumeJob {
+    /// Creates a statement for `RESUME JOB`.
+    pub fn new(job_id: String) -> Self {
+        Self { job_id }
+    }
+
+    pub fn job_id(&self) -> &str {
+        &self.job_id
+    }
+}
@@ -14,14 +14,46 @@
 
 use std::fmt;
 
-use sqlparser::ast::Query as SpQuery;
+use itertools::Itertools;
+use snafu::ensure;
+use sqlparser::ast::{Expr, Query as SpQuery};
 
-use crate::error::Error;
+use crate::error::{Error, UnsupportedSnafu};
+
+/// Name of the placeholder function that a `<expr> RANGE '<duration>' [FILL <fill>]`
+/// select item is rewritten into, i.e. `range_fn(<expr>, '<duration>', '<fill>')`.
+pub const RANGE_FN: &str = "range_fn";
 
 /// Query statement instance.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Query {
     pub inner: SpQuery,
+    /// The `ALIGN` clause of a range query, `None` for plain queries.
+    pub align: Option<Align>,
+}
+
+/// `ALIGN '<duration>' [BY (<expr>, ...)] [FILL <fill>]` clause of a range query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Align {
+    /// Step between two output timestamps, like `1m`.
+    pub interval: String,
+    /// Expressions to group series by.
+    pub by: Vec<Expr>,
+    /// Default fill mode of range expressions without their own `FILL`.
+    pub fill: Option<String>,
+}
+
+impl fmt::Display for Align {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ALIGN '{}'", self.interval)?;
+        if !self.by.is_empty() {
+            write!(f, " BY ({})", self.by.iter().join(", "))?;
+        }
+        if let Some(fill) = &self.fill {
+            write!(f, " FILL {fill}")?;
+        }
+        Ok(())
+    }
 }
 
 /// Automatically converts from sqlparser Query instance to SqlQuery.
@@ -29,14 +61,25 @@ impl TryFrom<SpQuery> for Query {
     type Error = Error;
 
     fn try_from(q: SpQuery) -> Result<Self, Self::Error> {
-        Ok(Query { inner: q })
+        Ok(Query {
+            inner: q,
+            align: None,
+        })
     }
 }
 
+/// Fails on range queries, as sqlparser Query has no `ALIGN` clause.
 impl TryFrom<Query> for SpQuery {
     type Error = Error;
 
     fn try_from(value: Query) -> Result<Self, Self::Error> {
+        ensure!(
+            value.align.is_none(),
+            UnsupportedSnafu {
+                sql: value.to_string(),
+                keyword: "ALIGN",
+            }
+        );
         Ok(value.inner)
     }
 }
@@ -44,6 +87,9 @@ impl TryFrom<Query> for SpQuery {
 impl fmt::Display for Query {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.inner)?;
+        if let Some(align) = &self.align {
+            write!(f, " {align}")?;
+        }
         Ok(())
     }
 }
@@ -51,6 +97,8 @@ impl fmt::Display for Query {
 #[cfg(test)]
 mod test {
 
+    use sqlparser::ast::Query as SpQuery;
+
     use super::Query;
     use crate::dialect::GreptimeDbDialect;
     use crate::parser::ParserContext;
@@ -83,4 +131,14 @@ mod test {
             "SELECT * FROM abc LEFT JOIN bcd WHERE abc.a = 1 AND bcd.d = 7 AND abc.id = bcd.id"
         );
     }
+
+    #[test]
+    fn test_range_query_to_sp_query() {
+        let query = create_query("select * from abc").unwrap();
+        assert!(SpQuery::try_from(*query).is_ok());
+
+        let query = create_query("SELECT ts, min(v) RANGE '5s' FROM abc ALIGN '5s'").unwrap();
+        assert!(query.align.is_some());
+        assert!(SpQuery::try_from(*query).is_err());
+    }
 }
@@ -60,6 +60,17 @@ pub struct ShowCreateTable {
     pub table_name: ObjectName,
 }
 
+/// SQL structure for `SHOW JOBS`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShowJobs {}
+
+/// SQL structure for `SHOW [FULL] PROCESSLIST`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShowProcesslist {
+    /// Shows the whole query text instead of its first 100 characters.
+    pub full: bool,
+}
+
 #[cfg(test)]
 mod tests {
     use std::assert_matches::assert_matches;
@@ -137,4 +148,37 @@ mod tests {
         let sql = "SHOW CREATE TABLE";
         assert!(ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).is_err());
     }
+
+    #[test]
+    pub fn test_show_jobs() {
+        let sql = "SHOW JOBS";
+        let stmts: Vec<Statement> =
+            ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(1, stmts.len());
+        assert_eq!(Statement::ShowJobs(ShowJobs::default()), stmts[0]);
+    }
+
+    #[test]
+    pub fn test_show_processlist() {
+        let sql = "SHOW PROCESSLIST";
+        let stmts: Vec<Statement> =
+            ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(1, stmts.len());
+        assert_eq!(
+            Statement::ShowProcesslist(ShowProcesslist { full: false }),
+            stmts[0]
+        );
+
+        let sql = "show full processlist";
+        let stmts: Vec<Statement> =
+            ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(1, stmts.len());
+        assert_eq!(
+            Statement::ShowProcesslist(ShowProcesslist { full: true }),
+            stmts[0]
+        );
+
+        let sql = "SHOW FULL JOBS";
+        assert!(ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).is_err());
+    }
 }
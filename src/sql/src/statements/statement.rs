@@ -17,14 +17,20 @@ use sqlparser::ast::Statement as SpStatement;
 
 use crate::error::{ConvertToDfStatementSnafu, Error};
 use crate::statements::alter::AlterTable;
+use crate::statements::cancel::CancelJob;
 use crate::statements::create::{CreateDatabase, CreateExternalTable, CreateTable};
 use crate::statements::delete::Delete;
 use crate::statements::describe::DescribeTable;
 use crate::statements::drop::DropTable;
 use crate::statements::explain::Explain;
+use crate::statements::function::{CreateFunction, DropFunction, ShowFunctions};
 use crate::statements::insert::Insert;
+use crate::statements::kill::KillQuery;
+use crate::statements::pause::{PauseJob, ResumeJob};
 use crate::statements::query::Query;
-use crate::statements::show::{ShowCreateTable, ShowDatabases, ShowTables};
+use crate::statements::show::{
+    ShowCreateTable, ShowDatabases, ShowJobs, ShowProcesslist, ShowTables,
+};
 use crate::statements::tql::Tql;
 use crate::statements::truncate::TruncateTable;
 
@@ -64,6 +70,24 @@ pub enum Statement {
     Tql(Tql),
     // TRUNCATE TABLE
     TruncateTable(TruncateTable),
+    // SHOW JOBS
+    ShowJobs(ShowJobs),
+    // CANCEL JOB
+    CancelJob(CancelJob),
+    // PAUSE JOB
+    PauseJob(PauseJob),
+    // RESUME JOB
+    ResumeJob(ResumeJob),
+    // SHOW PROCESSLIST
+    ShowProcesslist(ShowProcesslist),
+    // KILL QUERY
+    KillQuery(KillQuery),
+    // CREATE FUNCTION
+    CreateFunction(CreateFunction),
+    // DROP FUNCTION
+    DropFunction(DropFunction),
+    // SHOW FUNCTIONS
+    ShowFunctions(ShowFunctions),
 }
 
 /// Comment hints from SQL.
@@ -20,7 +20,7 @@ use common_recordbatch::OrderOption;
 use common_telemetry::logging;
 use common_time::range::TimestampRange;
 use snafu::ResultExt;
-use store_api::storage::{Chunk, ChunkReader, RegionId, SchemaRef, SequenceNumber};
+use store_api::storage::{Chunk, ChunkReader, ReadMetrics, RegionId, SchemaRef, SequenceNumber};
 use table::predicate::{Predicate, TimeRangePredicateBuilder};
 
 use crate::error::{self, Error, Result};
@@ -40,6 +40,7 @@ pub struct ChunkReaderImpl {
     schema: ProjectedSchemaRef,
     batch_reader: BoxedBatchReader,
     output_ordering: Option<Vec<OrderOption>>,
+    read_metrics: ReadMetrics,
 }
 
 #[async_trait]
@@ -68,6 +69,10 @@ impl ChunkReader for ChunkReaderImpl {
     fn output_ordering(&self) -> Option<Vec<OrderOption>> {
         self.output_ordering.clone()
     }
+
+    fn read_metrics(&self) -> ReadMetrics {
+        self.read_metrics
+    }
 }
 
 impl ChunkReaderImpl {
@@ -80,9 +85,16 @@ impl ChunkReaderImpl {
             schema,
             batch_reader,
             output_ordering,
+            read_metrics: ReadMetrics::default(),
         }
     }
 
+    /// Sets the metrics about the sources of this reader.
+    pub fn with_read_metrics(mut self, read_metrics: ReadMetrics) -> Self {
+        self.read_metrics = read_metrics;
+        self
+    }
+
     #[inline]
     pub fn projected_schema(&self) -> &ProjectedSchemaRef {
         &self.schema
@@ -295,6 +307,7 @@ impl ChunkReaderBuilder {
                 .context(error::InvalidProjectionSnafu)?,
         );
         self.iter_ctx.projected_schema = Some(schema.clone());
+        let read_metrics = self.read_metrics(&time_range_predicate);
 
         let mut output_ordering = None;
         let reader = if let Some(ordering) = self.output_ordering.take() &&
@@ -308,7 +321,22 @@ impl ChunkReaderBuilder {
             self.build_reader(&schema, &time_range_predicate).await?
         };
 
-        Ok(ChunkReaderImpl::new(schema, reader, output_ordering))
+        Ok(ChunkReaderImpl::new(schema, reader, output_ordering).with_read_metrics(read_metrics))
+    }
+
+    /// Collects the metrics about the memtables and files to read in `time_range`.
+    fn read_metrics(&self, time_range: &TimestampRange) -> ReadMetrics {
+        let pruned_sst_files = self
+            .files_to_read
+            .iter()
+            .filter(|file| !Self::file_in_range(file, time_range))
+            .count();
+        ReadMetrics {
+            memtables: self.memtables.len(),
+            memtable_rows: self.memtables.iter().map(|m| m.num_rows()).sum(),
+            sst_files: self.files_to_read.len() - pruned_sst_files,
+            pruned_sst_files,
+        }
     }
 
     async fn build_chained(
@@ -134,6 +134,9 @@ pub enum Error {
         source: JsonError,
     },
 
+    #[snafu(display("Corrupted full-text index file: {}", path))]
+    DecodeFulltextIndex { path: String, location: Location },
+
     #[snafu(display("Invalid scan index, start: {}, end: {}", start, end))]
     InvalidScanIndex {
         start: ManifestVersion,
@@ -576,6 +579,7 @@ impl ErrorExt for Error {
             Utf8 { .. }
             | EncodeJson { .. }
             | DecodeJson { .. }
+            | DecodeFulltextIndex { .. }
             | WaitFlush { .. }
             | DecodeMetaActionList { .. }
             | Readline { .. }
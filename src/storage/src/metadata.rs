@@ -20,7 +20,7 @@ use std::sync::Arc;
 use common_error::ext::ErrorExt;
 use common_error::status_code::StatusCode;
 use datatypes::data_type::ConcreteDataType;
-use datatypes::schema::{ColumnSchema, Metadata, COMMENT_KEY};
+use datatypes::schema::{ColumnSchema, Metadata, COMMENT_KEY, FULLTEXT_KEY};
 use serde::{Deserialize, Serialize};
 use snafu::{ensure, Location, OptionExt, ResultExt, Snafu};
 use store_api::storage::consts::{self, ReservedColumnId};
@@ -407,6 +407,7 @@ impl ColumnMetadata {
         let cf_id = try_parse_int(metadata, METADATA_CF_ID_KEY, Some(consts::DEFAULT_CF_ID))?;
         let column_id = try_parse_int(metadata, METADATA_COLUMN_ID_KEY, None)?;
         let comment = metadata.get(COMMENT_KEY).cloned().unwrap_or_default();
+        let fulltext = column_schema.fulltext_analyzer();
 
         let desc = ColumnDescriptorBuilder::new(
             column_id,
@@ -417,6 +418,7 @@ impl ColumnMetadata {
         .is_time_index(column_schema.is_time_index())
         .default_constraint(column_schema.default_constraint().cloned())
         .comment(comment)
+        .fulltext(fulltext)
         .build()
         .context(BuildColumnDescriptorSnafu)?;
 
@@ -432,6 +434,9 @@ impl ColumnMetadata {
         if !self.desc.comment.is_empty() {
             let _ = metadata.insert(COMMENT_KEY.to_string(), self.desc.comment.clone());
         }
+        if let Some(analyzer) = self.desc.fulltext {
+            let _ = metadata.insert(FULLTEXT_KEY.to_string(), analyzer.to_string());
+        }
 
         metadata
     }
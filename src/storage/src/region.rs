@@ -147,6 +147,10 @@ impl<S: LogStore> Region for RegionImpl<S> {
             .sum()
     }
 
+    fn committed_sequence(&self) -> SequenceNumber {
+        self.inner.version_control().committed_sequence()
+    }
+
     async fn flush(&self, ctx: &FlushContext) -> Result<()> {
         self.inner.flush(ctx).await
     }
@@ -562,11 +566,6 @@ impl<S: LogStore> RegionImpl<S> {
 // Private methods for tests.
 #[cfg(test)]
 impl<S: LogStore> RegionImpl<S> {
-    #[inline]
-    fn committed_sequence(&self) -> store_api::storage::SequenceNumber {
-        self.inner.version_control().committed_sequence()
-    }
-
     fn current_manifest_version(&self) -> ManifestVersion {
         self.inner.version_control().current_manifest_version()
     }
@@ -23,9 +23,13 @@ use common_recordbatch::OrderOption;
 use common_test_util::temp_dir::create_temp_dir;
 use common_time::timestamp::TimeUnit;
 use datafusion_common::Column;
+use datafusion_expr::Expr as DfExpr;
 use datatypes::value::timestamp_to_scalar_value;
 use log_store::raft_engine::log_store::RaftEngineLogStore;
-use store_api::storage::{FlushContext, FlushReason, OpenOptions, Region, ScanRequest};
+use store_api::storage::{
+    ChunkReader, FlushContext, FlushReason, OpenOptions, ReadContext, ReadMetrics, Region,
+    ScanRequest, Snapshot,
+};
 
 use crate::config::EngineConfig;
 use crate::engine::{self, RegionMap};
@@ -437,7 +441,6 @@ async fn test_flush_and_query_empty() {
         .await;
     tester.flush(Some(true)).await;
 
-    use datafusion_expr::Expr as DfExpr;
     let req = ScanRequest {
         sequence: None,
         projection: None,
@@ -460,3 +463,49 @@ async fn test_flush_and_query_empty() {
     };
     let _ = tester.scan(req).await;
 }
+
+#[tokio::test]
+async fn test_read_metrics_after_flush() {
+    common_telemetry::init_default_ut_logging();
+
+    let dir = create_temp_dir("read-metrics-flush");
+    let store_dir = dir.path().to_str().unwrap();
+
+    let flush_switch = Arc::new(FlushSwitch::default());
+    let tester = FlushTester::new(store_dir, flush_switch.clone()).await;
+
+    tester.put(&[(1000, Some(100)), (2000, Some(200))]).await;
+    tester.flush(None).await;
+    tester.put(&[(30000, Some(300))]).await;
+
+    let reader = tester.base().full_scan_reader().await;
+    let metrics = reader.read_metrics();
+    assert_eq!(1, metrics.memtable_rows);
+    assert_eq!(1, metrics.sst_files);
+    assert_eq!(0, metrics.pruned_sst_files);
+
+    // The flushed file is out of the time range of the request.
+    let req = ScanRequest {
+        filters: vec![Expr::from(datafusion_expr::binary_expr(
+            DfExpr::Column(Column::from("timestamp")),
+            datafusion_expr::Operator::GtEq,
+            datafusion_expr::lit(timestamp_to_scalar_value(
+                TimeUnit::Millisecond,
+                Some(20000),
+            )),
+        ))],
+        ..Default::default()
+    };
+    let read_ctx = ReadContext::default();
+    let snapshot = tester.base().region.snapshot(&read_ctx).unwrap();
+    let reader = snapshot.scan(&read_ctx, req).await.unwrap().reader;
+    assert_eq!(
+        ReadMetrics {
+            memtables: metrics.memtables,
+            memtable_rows: 1,
+            sst_files: 0,
+            pruned_sst_files: 1,
+        },
+        reader.read_metrics()
+    );
+}
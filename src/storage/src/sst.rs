@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod fulltext;
 pub(crate) mod parquet;
 mod pruning;
 mod stream_writer;
@@ -36,6 +37,7 @@ use serde::{Deserialize, Deserializer, Serialize};
 use snafu::{ResultExt, Snafu};
 use store_api::storage::{ChunkReader, RegionId};
 use table::predicate::Predicate;
+use tokio::sync::OnceCell;
 use uuid::Uuid;
 
 use crate::chunk::ChunkReaderImpl;
@@ -46,6 +48,7 @@ use crate::memtable::BoxedBatchIterator;
 use crate::read::{Batch, BatchReader, BoxedBatchReader};
 use crate::scheduler::Scheduler;
 use crate::schema::ProjectedSchemaRef;
+use crate::sst::fulltext::FulltextIndex;
 use crate::sst::parquet::{ChunkStream, ParquetReader, ParquetWriter};
 
 /// Maximum level of SSTs.
@@ -316,6 +319,23 @@ impl FileHandle {
     pub fn file_size(&self) -> u64 {
         self.inner.meta.file_size
     }
+
+    /// Returns the full-text index of the file, which is read from `object_store`
+    /// only once and then cached in the handle.
+    pub(crate) async fn fulltext_index(
+        &self,
+        object_store: &ObjectStore,
+    ) -> Result<Option<Arc<FulltextIndex>>> {
+        self.inner
+            .fulltext_index
+            .get_or_try_init(|| async {
+                FulltextIndex::read(object_store, &self.file_path())
+                    .await
+                    .map(|index| index.map(Arc::new))
+            })
+            .await
+            .cloned()
+    }
 }
 
 /// Actually data of [FileHandle].
@@ -327,6 +347,8 @@ struct FileHandleInner {
     deleted: AtomicBool,
     sst_layer: AccessLayerRef,
     file_purger: FilePurgerRef,
+    /// Full-text index of the file, read on the first scan that needs it.
+    fulltext_index: OnceCell<Option<Arc<FulltextIndex>>>,
 }
 
 impl fmt::Debug for FileHandleInner {
@@ -377,6 +399,7 @@ impl FileHandleInner {
             deleted: AtomicBool::new(false),
             sst_layer,
             file_purger,
+            fulltext_index: OnceCell::new(),
         }
     }
 }
@@ -607,12 +630,17 @@ impl AccessLayer for FsAccessLayer {
         Ok(Box::new(LazyParquetBatchReader::new(reader)))
     }
 
-    /// Deletes a SST file with given file id.
+    /// Deletes a SST file with given file id, along with its full-text index.
     async fn delete_sst(&self, file_id: FileId) -> Result<()> {
         let path = self.sst_file_path(&file_id.as_parquet());
         self.object_store
             .delete(&path)
             .await
+            .context(DeleteSstSnafu)?;
+        // Deleting a nonexistent object is not an error.
+        self.object_store
+            .delete(&fulltext::index_file_path(&path))
+            .await
             .context(DeleteSstSnafu)
     }
 }
@@ -0,0 +1,723 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Full-text index of SST files.
+//!
+//! For each column with a full-text analyzer, the index maps every term of the
+//! column to the ids of the rows containing it. The index is stored next to the
+//! SST file and used to skip row groups and rows that can't match a `matches`
+//! predicate.
+//!
+//! The index file starts with [MAGIC] and [VERSION], followed by varints of the
+//! number of rows and columns. Each column has its name, analyzer and terms in
+//! order. A term shares a prefix with the previous term of the column, so only
+//! the length of the shared prefix and the remaining suffix are stored, then the
+//! number of its rows and the gaps between the sorted row ids.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use datafusion_common::ScalarValue;
+use datafusion_expr::Operator;
+use datafusion_physical_expr::expressions::{BinaryExpr, Column, Literal};
+use datafusion_physical_expr::{PhysicalExpr, ScalarFunctionExpr};
+use datatypes::prelude::ConcreteDataType;
+use datatypes::schema::{ColumnSchema, FulltextAnalyzer, SchemaRef};
+use datatypes::value::ValueRef;
+use object_store::{ErrorKind, ObjectStore};
+use parquet::arrow::arrow_reader::{RowSelection, RowSelector};
+use parquet::file::metadata::RowGroupMetaData;
+use snafu::{OptionExt, ResultExt};
+
+use crate::error::{self, Result};
+use crate::read::Batch;
+
+/// Name of the function to match a full-text indexed column against a query.
+const MATCHES_FUNCTION_NAME: &str = "matches";
+
+/// Magic bytes at the start of an index file.
+const MAGIC: &[u8; 4] = b"GTFT";
+/// Version of the index file format.
+const VERSION: u8 = 1;
+
+/// Returns the path of the index file of the SST file in `sst_path`.
+pub(crate) fn index_file_path(sst_path: &str) -> String {
+    let stem = sst_path.strip_suffix(".parquet").unwrap_or(sst_path);
+    format!("{stem}.fulltext")
+}
+
+/// Term index of a column.
+#[derive(Debug, Default, PartialEq)]
+struct ColumnIndex {
+    analyzer: FulltextAnalyzer,
+    /// Sorted ids of rows containing the term.
+    postings: BTreeMap<String, Vec<usize>>,
+}
+
+/// Full-text index of a SST file.
+#[derive(Debug, PartialEq)]
+pub(crate) struct FulltextIndex {
+    num_rows: usize,
+    columns: HashMap<String, ColumnIndex>,
+}
+
+impl FulltextIndex {
+    /// Reads the index of the SST file in `sst_path`, returns `None` if the
+    /// file has no index.
+    pub(crate) async fn read(object_store: &ObjectStore, sst_path: &str) -> Result<Option<Self>> {
+        let path = index_file_path(sst_path);
+        match object_store.read(&path).await {
+            Ok(bytes) => Self::decode(&bytes)
+                .map(Some)
+                .context(error::DecodeFulltextIndexSnafu { path }),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e).context(error::ReadObjectSnafu { path }),
+        }
+    }
+
+    /// Writes the index of the SST file in `sst_path`.
+    pub(crate) async fn write(&self, object_store: &ObjectStore, sst_path: &str) -> Result<()> {
+        let path = index_file_path(sst_path);
+        object_store
+            .write(&path, self.encode())
+            .await
+            .context(error::WriteObjectSnafu { path })
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut buf = MAGIC.to_vec();
+        buf.push(VERSION);
+        put_varint(&mut buf, self.num_rows as u64);
+        // Sorts columns so the same index is always encoded into the same bytes.
+        let mut columns = self.columns.iter().collect::<Vec<_>>();
+        columns.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        put_varint(&mut buf, columns.len() as u64);
+        for (name, column) in columns {
+            put_bytes(&mut buf, name.as_bytes());
+            put_bytes(&mut buf, column.analyzer.to_string().as_bytes());
+            put_varint(&mut buf, column.postings.len() as u64);
+            let mut prev_term: &[u8] = &[];
+            for (term, rows) in &column.postings {
+                let term = term.as_bytes();
+                let shared = prev_term
+                    .iter()
+                    .zip(term)
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                put_varint(&mut buf, shared as u64);
+                put_bytes(&mut buf, &term[shared..]);
+                prev_term = term;
+
+                put_varint(&mut buf, rows.len() as u64);
+                let mut prev_row = 0;
+                for &row in rows {
+                    put_varint(&mut buf, (row - prev_row) as u64);
+                    prev_row = row;
+                }
+            }
+        }
+        buf
+    }
+
+    /// Decodes the index from `bytes`, returns `None` if they are corrupted.
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        let mut decoder = Decoder {
+            buf: bytes.strip_prefix(MAGIC)?,
+        };
+        if decoder.byte()? != VERSION {
+            return None;
+        }
+        let num_rows = decoder.usize()?;
+        let num_columns = decoder.usize()?;
+        let mut columns = HashMap::new();
+        for _ in 0..num_columns {
+            let name = String::from_utf8(decoder.bytes()?.to_vec()).ok()?;
+            let analyzer = std::str::from_utf8(decoder.bytes()?).ok()?.parse().ok()?;
+            let num_terms = decoder.usize()?;
+            let mut postings = BTreeMap::new();
+            let mut term = Vec::new();
+            for _ in 0..num_terms {
+                let shared = decoder.usize()?;
+                if shared > term.len() {
+                    return None;
+                }
+                term.truncate(shared);
+                term.extend_from_slice(decoder.bytes()?);
+
+                let num_term_rows = decoder.usize()?;
+                let mut rows: Vec<usize> = Vec::new();
+                for _ in 0..num_term_rows {
+                    let gap = decoder.usize()?;
+                    let row = match rows.last() {
+                        // Row ids are strictly increasing.
+                        Some(_) if gap == 0 => return None,
+                        Some(prev) => prev.checked_add(gap)?,
+                        None => gap,
+                    };
+                    if row >= num_rows {
+                        return None;
+                    }
+                    rows.push(row);
+                }
+                let term = String::from_utf8(term.clone()).ok()?;
+                let _ = postings.insert(term, rows);
+            }
+            let _ = columns.insert(name, ColumnIndex { analyzer, postings });
+        }
+        if !decoder.buf.is_empty() {
+            return None;
+        }
+
+        Some(FulltextIndex { num_rows, columns })
+    }
+
+    /// Evaluates `matches` functions in the predicate exprs against the index.
+    /// Returns the rows that may match, or `None` if the index can't prune any
+    /// row of the SST file with these `row_groups`.
+    pub(crate) fn matched_rows(
+        &self,
+        exprs: &[Arc<dyn PhysicalExpr>],
+        row_groups: &[RowGroupMetaData],
+    ) -> Option<MatchedRows> {
+        let row_group_rows = row_groups
+            .iter()
+            .map(|rg| rg.num_rows() as usize)
+            .collect::<Vec<_>>();
+        if row_group_rows.iter().sum::<usize>() != self.num_rows {
+            return None;
+        }
+        self.match_rows(exprs).map(|rows| MatchedRows {
+            row_group_rows,
+            rows,
+        })
+    }
+
+    /// Returns sorted ids of rows that may match the `matches` functions in
+    /// `exprs`, or `None` if no function can be evaluated by the index.
+    fn match_rows(&self, exprs: &[Arc<dyn PhysicalExpr>]) -> Option<Vec<usize>> {
+        let mut matches = Vec::new();
+        for expr in exprs {
+            collect_matches(expr, &mut matches);
+        }
+        let mut res: Option<Vec<usize>> = None;
+        for (column, query, analyzer) in matches {
+            let Some(index) = self.columns.get(&column) else { continue };
+            // Terms of the query are only comparable to the index if they are
+            // produced by the same analyzer.
+            if index.analyzer != analyzer {
+                continue;
+            }
+            let terms = analyzer.tokenize(&query);
+            if terms.is_empty() {
+                // Nothing matches an empty query.
+                return Some(Vec::new());
+            }
+            for term in terms {
+                let postings = index.postings.get(&term).map(Vec::as_slice).unwrap_or(&[]);
+                res = Some(match res {
+                    Some(rows) => rows
+                        .into_iter()
+                        .filter(|row| postings.binary_search(row).is_ok())
+                        .collect(),
+                    None => postings.to_vec(),
+                });
+            }
+        }
+        res
+    }
+}
+
+/// Appends `value` as a LEB128 varint.
+fn put_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+/// Appends the length of `bytes` and `bytes` themselves.
+fn put_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+    put_varint(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+/// Reads values written by [put_varint] and [put_bytes], all methods return
+/// `None` if the remaining bytes are too short.
+struct Decoder<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    fn byte(&mut self) -> Option<u8> {
+        let (&byte, rest) = self.buf.split_first()?;
+        self.buf = rest;
+        Some(byte)
+    }
+
+    fn varint(&mut self) -> Option<u64> {
+        let mut value = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    fn usize(&mut self) -> Option<usize> {
+        self.varint()?.try_into().ok()
+    }
+
+    fn bytes(&mut self) -> Option<&'a [u8]> {
+        let len = self.usize()?;
+        if len > self.buf.len() {
+            return None;
+        }
+        let (bytes, rest) = self.buf.split_at(len);
+        self.buf = rest;
+        Some(bytes)
+    }
+}
+
+/// Rows of a SST file that may match the `matches` functions of a predicate.
+#[derive(Debug)]
+pub(crate) struct MatchedRows {
+    /// Number of rows of each row group.
+    row_group_rows: Vec<usize>,
+    /// Sorted ids of the matched rows.
+    rows: Vec<usize>,
+}
+
+impl MatchedRows {
+    /// Returns a vector of boolean values, among which `false` means the row
+    /// group has no matched row and can be skipped.
+    pub(crate) fn row_group_mask(&self) -> Vec<bool> {
+        self.row_group_ranges()
+            .map(|(start, end)| self.rows_in(start, end).next().is_some())
+            .collect()
+    }
+
+    /// Builds the selection of the matched rows in `row_groups`, which are the
+    /// sorted ids of the row groups to read.
+    pub(crate) fn row_selection(&self, row_groups: &[usize]) -> RowSelection {
+        let ranges = self.row_group_ranges().collect::<Vec<_>>();
+        let mut selectors = Vec::new();
+        for &row_group in row_groups {
+            let (start, end) = ranges[row_group];
+            let mut pos = start;
+            for &row in self.rows_in(start, end) {
+                push_selector(&mut selectors, RowSelector::skip(row - pos));
+                push_selector(&mut selectors, RowSelector::select(1));
+                pos = row + 1;
+            }
+            push_selector(&mut selectors, RowSelector::skip(end - pos));
+        }
+        selectors.into()
+    }
+
+    /// Returns `(start, end)` row ids of each row group.
+    fn row_group_ranges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.row_group_rows.iter().scan(0, |start, num_rows| {
+            let range = (*start, *start + num_rows);
+            *start += num_rows;
+            Some(range)
+        })
+    }
+
+    fn rows_in(&self, start: usize, end: usize) -> impl Iterator<Item = &usize> {
+        let lo = self.rows.partition_point(|row| *row < start);
+        self.rows[lo..].iter().take_while(move |row| **row < end)
+    }
+}
+
+/// Pushes the selector, merging it into the last one of the same kind.
+fn push_selector(selectors: &mut Vec<RowSelector>, selector: RowSelector) {
+    if selector.row_count == 0 {
+        return;
+    }
+    match selectors.last_mut() {
+        Some(last) if last.skip == selector.skip => last.row_count += selector.row_count,
+        _ => selectors.push(selector),
+    }
+}
+
+/// Returns true if the conjunction of `exprs` has `matches` functions, which
+/// the index might evaluate.
+pub(crate) fn has_matches(exprs: &[Arc<dyn PhysicalExpr>]) -> bool {
+    let mut matches = Vec::new();
+    for expr in exprs {
+        collect_matches(expr, &mut matches);
+    }
+    !matches.is_empty()
+}
+
+/// Returns true if `schema` has full-text indexed string columns.
+pub(crate) fn has_fulltext_columns(schema: &SchemaRef) -> bool {
+    fulltext_columns(schema).next().is_some()
+}
+
+/// Returns the index and the analyzer of full-text indexed string columns in `schema`.
+fn fulltext_columns(
+    schema: &SchemaRef,
+) -> impl Iterator<Item = (usize, &ColumnSchema, FulltextAnalyzer)> {
+    schema
+        .column_schemas()
+        .iter()
+        .enumerate()
+        .filter(|(_, column_schema)| matches!(column_schema.data_type, ConcreteDataType::String(_)))
+        .filter_map(|(index, column_schema)| {
+            column_schema
+                .fulltext_analyzer()
+                .map(|analyzer| (index, column_schema, analyzer))
+        })
+}
+
+/// Collects `(column, query, analyzer)` of `matches` functions in the conjunction
+/// of `expr`.
+fn collect_matches(
+    expr: &Arc<dyn PhysicalExpr>,
+    matches: &mut Vec<(String, String, FulltextAnalyzer)>,
+) {
+    let any = expr.as_any();
+    if let Some(binary) = any.downcast_ref::<BinaryExpr>() {
+        if *binary.op() == Operator::And {
+            collect_matches(binary.left(), matches);
+            collect_matches(binary.right(), matches);
+        }
+        return;
+    }
+    let Some(func) = any.downcast_ref::<ScalarFunctionExpr>() else { return };
+    if !func.name().eq_ignore_ascii_case(MATCHES_FUNCTION_NAME) {
+        return;
+    }
+    let args = func.args();
+    let Some(column) = args.first().and_then(|arg| arg.as_any().downcast_ref::<Column>()) else { return };
+    let Some(query) = args.get(1).and_then(string_literal) else { return };
+    let analyzer = match args.get(2) {
+        Some(arg) => match string_literal(arg).and_then(|s| s.parse().ok()) {
+            Some(analyzer) => analyzer,
+            None => return,
+        },
+        None => FulltextAnalyzer::default(),
+    };
+    matches.push((column.name().to_string(), query, analyzer));
+}
+
+fn string_literal(expr: &Arc<dyn PhysicalExpr>) -> Option<String> {
+    match expr.as_any().downcast_ref::<Literal>()?.value() {
+        ScalarValue::Utf8(Some(s)) | ScalarValue::LargeUtf8(Some(s)) => Some(s.clone()),
+        _ => None,
+    }
+}
+
+struct ColumnIndexBuilder {
+    /// Index of the column in the batch.
+    index: usize,
+    name: String,
+    column: ColumnIndex,
+}
+
+/// Builds the full-text index while rows are written to the SST file.
+pub(crate) struct FulltextIndexBuilder {
+    num_rows: usize,
+    columns: Vec<ColumnIndexBuilder>,
+}
+
+impl FulltextIndexBuilder {
+    /// Creates a builder for the full-text indexed string columns of `schema`,
+    /// returns `None` if there is no such column.
+    pub(crate) fn new(schema: &SchemaRef) -> Option<Self> {
+        let columns = fulltext_columns(schema)
+            .map(|(index, column_schema, analyzer)| ColumnIndexBuilder {
+                index,
+                name: column_schema.name.clone(),
+                column: ColumnIndex {
+                    analyzer,
+                    postings: BTreeMap::new(),
+                },
+            })
+            .collect::<Vec<_>>();
+        if columns.is_empty() {
+            return None;
+        }
+        Some(Self {
+            num_rows: 0,
+            columns,
+        })
+    }
+
+    /// Indexes the rows of the batch, the batch must be written to the SST file
+    /// after all previous batches.
+    pub(crate) fn update(&mut self, batch: &Batch) {
+        for builder in &mut self.columns {
+            let vector = batch.column(builder.index);
+            for row in 0..vector.len() {
+                let ValueRef::String(text) = vector.get_ref(row) else { continue };
+                let row_id = self.num_rows + row;
+                for term in builder.column.analyzer.tokenize(text) {
+                    let rows = builder.column.postings.entry(term).or_default();
+                    if rows.last() != Some(&row_id) {
+                        rows.push(row_id);
+                    }
+                }
+            }
+        }
+        self.num_rows += batch.num_rows();
+    }
+
+    pub(crate) fn finish(self) -> FulltextIndex {
+        FulltextIndex {
+            num_rows: self.num_rows,
+            columns: self
+                .columns
+                .into_iter()
+                .map(|builder| (builder.name, builder.column))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion_common::arrow::datatypes::DataType;
+    use datafusion_expr::ColumnarValue;
+    use datatypes::schema::{ColumnSchema, Schema};
+    use datatypes::vectors::{StringVector, VectorRef};
+
+    use super::*;
+
+    fn new_matches(column: &str, args: &[&str]) -> Arc<dyn PhysicalExpr> {
+        let mut exprs: Vec<Arc<dyn PhysicalExpr>> = vec![Arc::new(Column::new(column, 0))];
+        exprs.extend(
+            args.iter()
+                .map(|arg| Arc::new(Literal::new(ScalarValue::Utf8(Some(arg.to_string())))) as _),
+        );
+        Arc::new(ScalarFunctionExpr::new(
+            MATCHES_FUNCTION_NAME,
+            Arc::new(|_: &[ColumnarValue]| unreachable!()),
+            exprs,
+            &DataType::Boolean,
+        ))
+    }
+
+    fn new_index() -> FulltextIndex {
+        let schema = Arc::new(Schema::new(vec![
+            ColumnSchema::new("host", ConcreteDataType::string_datatype(), true),
+            ColumnSchema::new("message", ConcreteDataType::string_datatype(), true)
+                .with_fulltext(Some(FulltextAnalyzer::English)),
+        ]));
+        let mut builder = FulltextIndexBuilder::new(&schema).unwrap();
+        let batch = |hosts: Vec<&str>, messages: Vec<Option<&str>>| {
+            Batch::new(vec![
+                Arc::new(StringVector::from(hosts)) as VectorRef,
+                Arc::new(StringVector::from(messages)) as VectorRef,
+            ])
+        };
+        builder.update(&batch(
+            vec!["h1", "h2", "h3"],
+            vec![Some("Connection refused"), None, Some("disk is full")],
+        ));
+        builder.update(&batch(vec!["h4"], vec![Some("connection reset by peer")]));
+        builder.finish()
+    }
+
+    #[test]
+    fn test_index_file_path() {
+        assert_eq!(
+            "region/1a2b.fulltext",
+            index_file_path("region/1a2b.parquet")
+        );
+    }
+
+    #[test]
+    fn test_build_index() {
+        let index = new_index();
+        assert_eq!(4, index.num_rows);
+        assert_eq!(1, index.columns.len());
+        let column = &index.columns["message"];
+        assert_eq!(FulltextAnalyzer::English, column.analyzer);
+        assert_eq!(Some(&vec![0, 3]), column.postings.get("connection"));
+        assert_eq!(Some(&vec![2]), column.postings.get("full"));
+        assert_eq!(Some(&vec![3]), column.postings.get("peer"));
+        assert!(!column.postings.contains_key("is"));
+
+        assert!(
+            FulltextIndexBuilder::new(&Arc::new(Schema::new(vec![ColumnSchema::new(
+                "message",
+                ConcreteDataType::string_datatype(),
+                true
+            )])))
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        let index = new_index();
+        let bytes = index.encode();
+        assert_eq!(index, FulltextIndex::decode(&bytes).unwrap());
+
+        let empty = FulltextIndex {
+            num_rows: 0,
+            columns: HashMap::new(),
+        };
+        assert_eq!(empty, FulltextIndex::decode(&empty.encode()).unwrap());
+
+        let mut buf = Vec::new();
+        for value in [0, 1, 127, 128, 300, u64::MAX] {
+            put_varint(&mut buf, value);
+        }
+        let mut decoder = Decoder { buf: &buf };
+        for value in [0, 1, 127, 128, 300, u64::MAX] {
+            assert_eq!(Some(value), decoder.varint());
+        }
+        assert_eq!(None, decoder.varint());
+    }
+
+    #[test]
+    fn test_decode_corrupted() {
+        let bytes = new_index().encode();
+        // Truncated.
+        for len in 0..bytes.len() {
+            assert!(FulltextIndex::decode(&bytes[..len]).is_none(), "{len}");
+        }
+        // Trailing bytes.
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(FulltextIndex::decode(&trailing).is_none());
+        // Unknown version.
+        let mut version = bytes.clone();
+        version[MAGIC.len()] = VERSION + 1;
+        assert!(FulltextIndex::decode(&version).is_none());
+
+        // Row ids out of range or not increasing.
+        let encode_rows = |rows: &[usize]| {
+            let mut postings = BTreeMap::new();
+            let _ = postings.insert("term".to_string(), vec![0]);
+            let mut bytes = FulltextIndex {
+                num_rows: 4,
+                columns: HashMap::from([(
+                    "message".to_string(),
+                    ColumnIndex {
+                        analyzer: FulltextAnalyzer::English,
+                        postings,
+                    },
+                )]),
+            }
+            .encode();
+            // Replaces postings of the only term at the end.
+            let _ = bytes.split_off(bytes.len() - 2);
+            put_varint(&mut bytes, rows.len() as u64);
+            let mut prev = 0;
+            for &row in rows {
+                put_varint(&mut bytes, row.wrapping_sub(prev) as u64);
+                prev = row;
+            }
+            bytes
+        };
+        assert!(FulltextIndex::decode(&encode_rows(&[1, 3])).is_some());
+        assert!(FulltextIndex::decode(&encode_rows(&[1, 4])).is_none());
+        assert!(FulltextIndex::decode(&encode_rows(&[1, 1])).is_none());
+    }
+
+    #[test]
+    fn test_has_matches() {
+        let schema = Arc::new(Schema::new(vec![ColumnSchema::new(
+            "message",
+            ConcreteDataType::string_datatype(),
+            true,
+        )
+        .with_fulltext(Some(FulltextAnalyzer::English))]));
+        assert!(has_fulltext_columns(&schema));
+        let schema = Arc::new(Schema::new(vec![ColumnSchema::new(
+            "message",
+            ConcreteDataType::string_datatype(),
+            true,
+        )]));
+        assert!(!has_fulltext_columns(&schema));
+
+        assert!(has_matches(&[new_matches("message", &["connection"])]));
+        let literal = Arc::new(Literal::new(ScalarValue::Boolean(Some(true)))) as _;
+        assert!(!has_matches(&[literal]));
+        assert!(!has_matches(&[]));
+    }
+
+    #[test]
+    fn test_match_rows() {
+        let index = new_index();
+        assert_eq!(
+            Some(vec![0, 3]),
+            index.match_rows(&[new_matches("message", &["connection"])])
+        );
+        assert_eq!(
+            Some(vec![2]),
+            index.match_rows(&[new_matches("message", &["Disk full"])])
+        );
+        assert_eq!(
+            Some(vec![]),
+            index.match_rows(&[new_matches("message", &["timeout"])])
+        );
+        // Analyzer of the query differs from the index.
+        assert_eq!(
+            None,
+            index.match_rows(&[new_matches("message", &["timeout", "whitespace"])])
+        );
+        // Column without index.
+        assert_eq!(None, index.match_rows(&[new_matches("host", &["timeout"])]));
+
+        let and = Arc::new(BinaryExpr::new(
+            new_matches("message", &["connection"]),
+            Operator::And,
+            new_matches("message", &["peer"]),
+        )) as _;
+        assert_eq!(Some(vec![3]), index.match_rows(&[and]));
+        let or = Arc::new(BinaryExpr::new(
+            new_matches("message", &["connection"]),
+            Operator::Or,
+            new_matches("message", &["timeout"]),
+        )) as _;
+        assert_eq!(None, index.match_rows(&[or]));
+    }
+
+    #[test]
+    fn test_matched_rows() {
+        let matched = MatchedRows {
+            row_group_rows: vec![3, 3, 2],
+            rows: vec![1, 2, 7],
+        };
+        assert_eq!(vec![true, false, true], matched.row_group_mask());
+        assert_eq!(
+            RowSelection::from(vec![
+                RowSelector::skip(1),
+                RowSelector::select(2),
+                RowSelector::skip(1),
+                RowSelector::select(1),
+            ]),
+            matched.row_selection(&[0, 2])
+        );
+        assert_eq!(
+            RowSelection::from(vec![RowSelector::skip(3)]),
+            matched.row_selection(&[1])
+        );
+
+        let matched = MatchedRows {
+            row_group_rows: vec![2, 2],
+            rows: vec![],
+        };
+        assert_eq!(vec![false, false], matched.row_group_mask());
+    }
+}
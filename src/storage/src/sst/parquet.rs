@@ -45,6 +45,7 @@ use crate::read::{Batch, BatchReader};
 use crate::schema::compat::ReadAdapter;
 use crate::schema::{ProjectedSchemaRef, StoreSchema};
 use crate::sst;
+use crate::sst::fulltext::{self, FulltextIndexBuilder};
 use crate::sst::pruning::build_row_filter;
 use crate::sst::stream_writer::BufferedWriter;
 use crate::sst::{FileHandle, Source, SstInfo};
@@ -117,9 +118,13 @@ impl<'a> ParquetWriter<'a> {
         )
         .await?;
         let mut rows_written = 0;
+        let mut index_builder = FulltextIndexBuilder::new(&schema);
 
         while let Some(batch) = self.source.next_batch().await? {
             buffered_writer.write(&batch).await?;
+            if let Some(builder) = &mut index_builder {
+                builder.update(&batch);
+            }
             rows_written += batch.num_rows();
         }
 
@@ -130,6 +135,12 @@ impl<'a> ParquetWriter<'a> {
         }
 
         let (file_meta, file_size) = buffered_writer.close().await?;
+        if let Some(builder) = index_builder {
+            builder
+                .finish()
+                .write(&self.object_store, self.file_path)
+                .await?;
+        }
         let time_range = decode_timestamp_range(&file_meta, &schema).ok().flatten();
 
         // object_store.write will make sure all bytes are written or an error is raised.
@@ -267,13 +278,29 @@ impl ParquetReader {
 
         let adapter = ReadAdapter::new(store_schema.clone(), self.projected_schema.clone())?;
 
-        let pruned_row_groups = self
-            .predicate
-            .prune_row_groups(builder.metadata().row_groups())
+        let row_groups = builder.metadata().row_groups();
+        let mut row_group_mask = self.predicate.prune_row_groups(row_groups);
+        let mut matched_rows = None;
+        // Only reads the index if the predicate might be evaluated by it.
+        if fulltext::has_fulltext_columns(store_schema.schema())
+            && fulltext::has_matches(self.predicate.exprs())
+        {
+            if let Some(index) = self.file_handle.fulltext_index(&operator).await? {
+                matched_rows = index.matched_rows(self.predicate.exprs(), row_groups);
+            }
+        }
+        if let Some(matched_rows) = &matched_rows {
+            for (valid, index_valid) in row_group_mask.iter_mut().zip(matched_rows.row_group_mask())
+            {
+                *valid &= index_valid;
+            }
+        }
+        let pruned_row_groups = row_group_mask
             .into_iter()
             .enumerate()
             .filter_map(|(idx, valid)| if valid { Some(idx) } else { None })
             .collect::<Vec<_>>();
+        let row_selection = matched_rows.map(|rows| rows.row_selection(&pruned_row_groups));
 
         let parquet_schema_desc = builder.metadata().file_metadata().schema_descr_ptr();
 
@@ -281,6 +308,9 @@ impl ParquetReader {
         let mut builder = builder
             .with_projection(projection_mask.clone())
             .with_row_groups(pruned_row_groups);
+        if let Some(row_selection) = row_selection {
+            builder = builder.with_row_selection(row_selection);
+        }
 
         if let Some(row_filter) = build_row_filter(
             self.time_range,
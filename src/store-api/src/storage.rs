@@ -30,7 +30,7 @@ pub use datatypes::schema::{
     ColumnDefaultConstraint, ColumnSchema, Schema, SchemaBuilder, SchemaRef,
 };
 
-pub use self::chunk::{Chunk, ChunkReader};
+pub use self::chunk::{Chunk, ChunkReader, ReadMetrics};
 pub use self::descriptors::*;
 pub use self::engine::{
     CloseOptions, CompactionStrategy, CreateOptions, EngineContext, OpenOptions, StorageEngine,
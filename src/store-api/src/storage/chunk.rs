@@ -32,6 +32,19 @@ impl Chunk {
     }
 }
 
+/// Metrics about the sources a [ChunkReader] reads from.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ReadMetrics {
+    /// Number of memtables to read.
+    pub memtables: usize,
+    /// Number of rows in the memtables to read.
+    pub memtable_rows: usize,
+    /// Number of SST files to read.
+    pub sst_files: usize,
+    /// Number of SST files skipped by the time range of the request.
+    pub pruned_sst_files: usize,
+}
+
 /// `ChunkReader` is similar to async iterator of [Chunk].
 #[async_trait]
 pub trait ChunkReader: Send {
@@ -50,4 +63,9 @@ pub trait ChunkReader: Send {
     fn output_ordering(&self) -> Option<Vec<OrderOption>> {
         None
     }
+
+    /// Returns the metrics about the sources of this reader.
+    fn read_metrics(&self) -> ReadMetrics {
+        ReadMetrics::default()
+    }
 }
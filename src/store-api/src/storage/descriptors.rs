@@ -14,6 +14,7 @@
 
 use std::fmt;
 
+use datatypes::schema::FulltextAnalyzer;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
@@ -121,6 +122,11 @@ pub struct ColumnDescriptor {
     default_constraint: Option<ColumnDefaultConstraint>,
     #[builder(default, setter(into))]
     pub comment: String,
+    /// Analyzer of the full-text index, default is None, which means the column
+    /// is not full-text indexed.
+    #[builder(default)]
+    #[serde(default)]
+    pub fulltext: Option<FulltextAnalyzer>,
 }
 
 impl ColumnDescriptor {
@@ -145,6 +151,7 @@ impl ColumnDescriptor {
             .with_time_index(self.is_time_index)
             .with_default_constraint(self.default_constraint.clone())
             .expect("ColumnDescriptor should validate default constraint")
+            .with_fulltext(self.fulltext)
     }
 }
 
@@ -294,6 +301,12 @@ mod tests {
             .unwrap();
         assert_eq!("A test column", desc.comment);
 
+        let desc = new_column_desc_builder()
+            .fulltext(Some(FulltextAnalyzer::English))
+            .build()
+            .unwrap();
+        assert_eq!(Some(FulltextAnalyzer::English), desc.fulltext);
+
         assert!(new_column_desc_builder()
             .is_nullable(false)
             .default_constraint(Some(ColumnDefaultConstraint::Value(Value::Null)))
@@ -40,7 +40,7 @@ use crate::storage::metadata::RegionMeta;
 use crate::storage::requests::{AlterRequest, WriteRequest};
 use crate::storage::responses::WriteResponse;
 use crate::storage::snapshot::{ReadContext, Snapshot};
-use crate::storage::RegionId;
+use crate::storage::{RegionId, SequenceNumber};
 
 /// Chunks of rows in storage engine.
 #[async_trait]
@@ -77,10 +77,15 @@ pub trait Region: Send + Sync + Clone + std::fmt::Debug + 'static {
 
     fn disk_usage_bytes(&self) -> u64;
 
+    /// Returns the sequence of the last committed write, it increases after
+    /// each write.
+    fn committed_sequence(&self) -> SequenceNumber;
+
     fn region_stat(&self) -> RegionStat {
         RegionStat {
             region_id: self.id().into(),
             disk_usage_bytes: self.disk_usage_bytes(),
+            committed_sequence: self.committed_sequence(),
         }
     }
 
@@ -94,6 +99,7 @@ pub trait Region: Send + Sync + Clone + std::fmt::Debug + 'static {
 pub struct RegionStat {
     pub region_id: u64,
     pub disk_usage_bytes: u64,
+    pub committed_sequence: SequenceNumber,
 }
 
 /// Context for write operations.
@@ -211,6 +211,7 @@ impl TableMeta {
         )
         .is_nullable(new_column.is_nullable())
         .default_constraint(new_column.default_constraint().cloned())
+        .fulltext(new_column.fulltext_analyzer())
         .build()
         .context(error::BuildColumnDescriptorSnafu {
             table_name,
@@ -257,14 +257,14 @@ pub struct DeleteRequest {
     pub key_column_values: HashMap<String, VectorRef>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CopyDirection {
     Export,
     Import,
 }
 
 /// Copy table request
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CopyTableRequest {
     pub catalog_name: String,
     pub schema_name: String,
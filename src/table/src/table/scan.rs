@@ -22,9 +22,13 @@ use common_query::error as query_error;
 use common_query::error::Result as QueryResult;
 use common_query::physical_plan::{Partitioning, PhysicalPlan, PhysicalPlanRef};
 use common_recordbatch::error::Result as RecordBatchResult;
-use common_recordbatch::{RecordBatch, RecordBatchStream, SendableRecordBatchStream};
+use common_recordbatch::{
+    RecordBatch, RecordBatchMetrics, RecordBatchStream, SendableRecordBatchStream,
+};
 use datafusion::execution::context::TaskContext;
-use datafusion::physical_plan::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use datafusion::physical_plan::metrics::{
+    BaselineMetrics, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet,
+};
 use datafusion_physical_expr::PhysicalSortExpr;
 use datatypes::schema::SchemaRef;
 use futures::{Stream, StreamExt};
@@ -101,6 +105,8 @@ impl PhysicalPlan for StreamScanAdapter {
         Ok(Box::pin(StreamWithMetricWrapper {
             stream,
             metric: baseline_metric,
+            metric_set: self.metric.clone(),
+            partition,
         }))
     }
 
@@ -112,6 +118,20 @@ impl PhysicalPlan for StreamScanAdapter {
 pub struct StreamWithMetricWrapper {
     stream: SendableRecordBatchStream,
     metric: BaselineMetrics,
+    metric_set: ExecutionPlanMetricsSet,
+    partition: usize,
+}
+
+impl StreamWithMetricWrapper {
+    /// Records the counters reported by the inner stream, once it's exhausted.
+    fn record_stream_metrics(&self) {
+        let Some(metrics) = self.stream.metrics() else { return; };
+        for (name, value) in metrics.counters {
+            MetricBuilder::new(&self.metric_set)
+                .counter(name, self.partition)
+                .add(value);
+        }
+    }
 }
 
 impl Stream for StreamWithMetricWrapper {
@@ -121,8 +141,12 @@ impl Stream for StreamWithMetricWrapper {
         let this = self.get_mut();
         let _timer = this.metric.elapsed_compute().timer();
         let poll = this.stream.poll_next_unpin(cx);
-        if let Poll::Ready(Option::Some(Result::Ok(record_batch))) = &poll {
-            this.metric.record_output(record_batch.num_rows());
+        match &poll {
+            Poll::Ready(Some(Ok(record_batch))) => {
+                this.metric.record_output(record_batch.num_rows());
+            }
+            Poll::Ready(None) => this.record_stream_metrics(),
+            _ => {}
         }
 
         poll
@@ -133,11 +157,17 @@ impl RecordBatchStream for StreamWithMetricWrapper {
     fn schema(&self) -> SchemaRef {
         self.stream.schema()
     }
+
+    fn metrics(&self) -> Option<RecordBatchMetrics> {
+        self.stream.metrics()
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use common_recordbatch::{util, RecordBatch, RecordBatches};
+    use common_recordbatch::{
+        util, RecordBatch, RecordBatchStreamAdaptor, RecordBatches, SharedRecordBatchMetrics,
+    };
     use datafusion::prelude::SessionContext;
     use datatypes::data_type::ConcreteDataType;
     use datatypes::schema::{ColumnSchema, Schema};
@@ -187,4 +217,43 @@ mod test {
             _ => unreachable!(),
         }
     }
+
+    #[tokio::test]
+    async fn test_record_stream_metrics() {
+        let ctx = SessionContext::new();
+        let schema = Arc::new(Schema::new(vec![ColumnSchema::new(
+            "a",
+            ConcreteDataType::int32_datatype(),
+            false,
+        )]));
+        let batch = RecordBatch::new(
+            schema.clone(),
+            vec![Arc::new(Int32Vector::from_slice([1, 2])) as _],
+        )
+        .unwrap();
+
+        let metrics = SharedRecordBatchMetrics::default();
+        metrics
+            .lock()
+            .unwrap()
+            .counters
+            .push(("rows_scanned".to_string(), 2));
+        let stream = RecordBatchStreamAdaptor {
+            schema: schema.clone(),
+            stream: Box::pin(futures::stream::iter(vec![RecordBatchResult::Ok(batch)])),
+            output_ordering: None,
+            metrics: Some(metrics),
+        };
+        let scan = StreamScanAdapter::new(Box::pin(stream));
+
+        let stream = scan.execute(0, ctx.task_ctx()).unwrap();
+        let _ = util::collect(stream).await.unwrap();
+
+        let metrics = scan.metrics().unwrap();
+        assert_eq!(Some(2), metrics.output_rows());
+        let counter = metrics
+            .sum_by_name("rows_scanned")
+            .map(|value| value.as_usize());
+        assert_eq!(Some(2), counter);
+    }
 }
@@ -282,6 +282,7 @@ async fn create_datanode_client(datanode_instance: Arc<DatanodeInstance>) -> (St
         ServerGrpcQueryHandlerAdaptor::arc(datanode_instance),
         None,
         None,
+        None,
         runtime,
     );
     let _handle = tokio::spawn(async move {
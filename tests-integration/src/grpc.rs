@@ -15,6 +15,8 @@
 #[cfg(test)]
 mod test {
     use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
 
     use api::v1::column::{SemanticType, Values};
     use api::v1::ddl_request::Expr as DdlExpr;
@@ -25,15 +27,23 @@ mod test {
         CreateDatabaseExpr, CreateTableExpr, DdlRequest, DeleteRequest, DropTableExpr,
         FlushTableExpr, InsertRequest, InsertRequests, QueryRequest,
     };
+    use common_base::Plugins;
     use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, MITO_ENGINE};
     use common_meta::table_name::TableName;
     use common_query::Output;
-    use common_recordbatch::RecordBatches;
+    use common_recordbatch::{RecordBatch, RecordBatches};
+    use datatypes::prelude::{ConcreteDataType, Vector, VectorRef};
+    use datatypes::schema::{ColumnSchema, Schema};
+    use datatypes::vectors::{
+        Float64Vector, Int32Vector, Int64Vector, StringVector, TimestampMillisecondVector,
+    };
+    use frontend::error::{Error, Result};
     use frontend::instance::Instance;
     use frontend::table::DistTable;
     use query::parser::QueryLanguageParser;
+    use servers::interceptor::{GrpcQueryInterceptor, GrpcQueryInterceptorRef};
     use servers::query_handler::grpc::GrpcQueryHandler;
-    use session::context::QueryContext;
+    use session::context::{QueryContext, QueryContextRef};
     use store_api::storage::RegionNumber;
     use table::Table;
     use tests::{has_parquet_file, test_region_dir};
@@ -869,4 +879,175 @@ CREATE TABLE {table_name} (
 +---+------+---------------------+";
         assert_eq!(recordbatches.pretty_print().unwrap(), expected);
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_distributed_put_record_batch() {
+        let instance =
+            tests::create_distributed_instance("test_distributed_put_record_batch").await;
+        let frontend = instance.frontend();
+        let frontend = frontend.as_ref();
+
+        let table_name = "my_dist_table";
+        let sql = format!(
+            r"
+CREATE TABLE {table_name} (
+    a INT,
+    b STRING PRIMARY KEY,
+    ts TIMESTAMP,
+    TIME INDEX (ts)
+) PARTITION BY RANGE COLUMNS(a) (
+    PARTITION r0 VALUES LESS THAN (10),
+    PARTITION r1 VALUES LESS THAN (MAXVALUE),
+)"
+        );
+        create_table(frontend, sql).await;
+
+        let schema = Arc::new(Schema::new(vec![
+            ColumnSchema::new("a", ConcreteDataType::int32_datatype(), true),
+            ColumnSchema::new("b", ConcreteDataType::string_datatype(), true),
+            ColumnSchema::new(
+                "ts",
+                ConcreteDataType::timestamp_millisecond_datatype(),
+                false,
+            ),
+        ]));
+        let record_batch = RecordBatch::new(
+            schema,
+            vec![
+                Arc::new(Int32Vector::from_vec(vec![1, 11, 2])) as _,
+                Arc::new(StringVector::from(vec!["x", "y", "z"])) as _,
+                Arc::new(TimestampMillisecondVector::from_vec(vec![1000, 2000, 3000])) as _,
+            ],
+        )
+        .unwrap();
+        let rows = frontend
+            .put_record_batch(table_name, record_batch, QueryContext::arc())
+            .await
+            .unwrap();
+        assert_eq!(rows, 3);
+
+        // Rows are written to the datanodes of their regions.
+        verify_data_distribution(
+            &instance,
+            table_name,
+            HashMap::from([
+                (
+                    0u32,
+                    "\
++---------------------+---+---+
+| ts                  | a | b |
++---------------------+---+---+
+| 1970-01-01T00:00:01 | 1 | x |
+| 1970-01-01T00:00:03 | 2 | z |
++---------------------+---+---+",
+                ),
+                (
+                    1u32,
+                    "\
++---------------------+----+---+
+| ts                  | a  | b |
++---------------------+----+---+
+| 1970-01-01T00:00:02 | 11 | y |
++---------------------+----+---+",
+                ),
+            ]),
+        )
+        .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_put_record_batch() {
+        #[derive(Default)]
+        struct CountPutsHook {
+            puts: AtomicU32,
+        }
+
+        impl GrpcQueryInterceptor for CountPutsHook {
+            type Error = Error;
+
+            fn pre_put_record_batch(
+                &self,
+                _table_name: &str,
+                _record_batch: &RecordBatch,
+                _query_ctx: QueryContextRef,
+            ) -> Result<()> {
+                let _ = self.puts.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+        }
+
+        let standalone = tests::create_standalone_instance("test_put_record_batch").await;
+        let mut instance = standalone.instance;
+
+        let plugins = Plugins::new();
+        let hook = Arc::new(CountPutsHook::default());
+        plugins.insert::<GrpcQueryInterceptorRef<Error>>(hook.clone());
+        Arc::make_mut(&mut instance).set_plugins(Arc::new(plugins));
+
+        let table_name = "demo";
+        let sql = format!("CREATE TABLE {table_name} (host STRING, cpu DOUBLE, ts TIMESTAMP, TIME INDEX (ts), PRIMARY KEY (host))");
+        create_table(&instance, sql).await;
+
+        let record_batch = |cpu: VectorRef| {
+            let schema = Arc::new(Schema::new(vec![
+                ColumnSchema::new("host", ConcreteDataType::string_datatype(), true),
+                ColumnSchema::new("cpu", cpu.data_type(), true),
+                ColumnSchema::new(
+                    "ts",
+                    ConcreteDataType::timestamp_millisecond_datatype(),
+                    false,
+                ),
+            ]));
+            RecordBatch::new(
+                schema,
+                vec![
+                    Arc::new(StringVector::from(vec!["host1", "host2"])) as _,
+                    cpu,
+                    Arc::new(TimestampMillisecondVector::from_vec(vec![1000, 2000])) as _,
+                ],
+            )
+            .unwrap()
+        };
+
+        let rows = instance
+            .put_record_batch(
+                table_name,
+                record_batch(Arc::new(Float64Vector::from_vec(vec![1.0, 2.0]))),
+                QueryContext::arc(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(rows, 2);
+        assert_eq!(hook.puts.load(Ordering::Relaxed), 1);
+
+        let err = instance
+            .put_record_batch(
+                table_name,
+                record_batch(Arc::new(Int64Vector::from_vec(vec![1, 2]))),
+                QueryContext::arc(),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidInsertRequest { .. }));
+        assert!(err.to_string().contains(
+            "column cpu of table greptime.public.demo is Float64, but the record batch has Int64"
+        ));
+        // The interceptor sees the record batch before its schema is checked.
+        assert_eq!(hook.puts.load(Ordering::Relaxed), 2);
+
+        let request = Request::Query(QueryRequest {
+            query: Some(Query::Sql(format!("SELECT * FROM {table_name}"))),
+        });
+        let output = query(&instance, request).await;
+        let Output::Stream(stream) = output else { unreachable!() };
+        let recordbatches = RecordBatches::try_collect(stream).await.unwrap();
+        let expected = "\
++-------+-----+---------------------+
+| host  | cpu | ts                  |
++-------+-----+---------------------+
+| host1 | 1.0 | 1970-01-01T00:00:01 |
+| host2 | 2.0 | 1970-01-01T00:00:02 |
++-------+-----+---------------------+";
+        assert_eq!(recordbatches.pretty_print().unwrap(), expected);
+    }
 }
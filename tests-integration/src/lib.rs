@@ -17,6 +17,7 @@ pub mod cluster;
 mod grpc;
 mod influxdb;
 mod instance;
+mod logs;
 mod opentsdb;
 mod prom_store;
 pub mod test_util;
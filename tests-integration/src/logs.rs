@@ -0,0 +1,108 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+
+    use common_query::Output;
+    use common_recordbatch::RecordBatches;
+    use frontend::instance::Instance;
+    use itertools::Itertools;
+    use servers::elasticsearch::BulkRequest;
+    use servers::loki::{LokiEntry, LokiRequest, LokiStream};
+    use servers::query_handler::sql::SqlQueryHandler;
+    use servers::query_handler::{ElasticsearchProtocolHandler, LokiProtocolHandler};
+    use session::context::QueryContext;
+
+    use crate::tests;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_standalone_logs_in_same_millisecond() {
+        let standalone =
+            tests::create_standalone_instance("test_standalone_logs_in_same_millisecond").await;
+        let instance = &standalone.instance;
+
+        test_loki_push(instance).await;
+        test_elasticsearch_bulk(instance).await;
+    }
+
+    async fn test_loki_push(instance: &Arc<Instance>) {
+        let request = LokiRequest {
+            table_name: "loki_logs".to_string(),
+            streams: vec![LokiStream {
+                labels: BTreeMap::from([("job".to_string(), "app".to_string())]),
+                entries: vec![
+                    LokiEntry {
+                        timestamp: 1_000_000_001,
+                        line: "first".to_string(),
+                    },
+                    LokiEntry {
+                        timestamp: 1_000_000_002,
+                        line: "second".to_string(),
+                    },
+                ],
+            }],
+        };
+        instance.push(request, QueryContext::arc()).await.unwrap();
+
+        assert_messages(
+            instance,
+            "select message from loki_logs order by greptime_timestamp",
+            &["first", "second"],
+        )
+        .await;
+    }
+
+    async fn test_elasticsearch_bulk(instance: &Arc<Instance>) {
+        let body = r#"
+{"index":{"_index":"app"}}
+{"@timestamp":"2023-06-01T00:00:00.000000001Z","message":"first","host":"h1"}
+{"index":{"_index":"app"}}
+{"@timestamp":"2023-06-01T00:00:00.000000002Z","message":"second","host":"h1"}
+"#;
+        let request = BulkRequest::parse(None, body).unwrap();
+        instance.bulk(&request, QueryContext::arc()).await.unwrap();
+
+        assert_messages(
+            instance,
+            "select message from app order by greptime_timestamp",
+            &["first", "second"],
+        )
+        .await;
+    }
+
+    async fn assert_messages(instance: &Arc<Instance>, sql: &str, messages: &[&str]) {
+        let output = instance
+            .do_query(sql, QueryContext::arc())
+            .await
+            .remove(0)
+            .unwrap();
+        let Output::Stream(stream) = output else {
+            unreachable!()
+        };
+        let recordbatches = RecordBatches::try_collect(stream).await.unwrap();
+        let expected = [
+            "+---------+".to_string(),
+            "| message |".to_string(),
+            "+---------+".to_string(),
+        ]
+        .into_iter()
+        .chain(messages.iter().map(|m| format!("| {m:<7} |")))
+        .chain(["+---------+".to_string()])
+        .join("\n");
+        assert_eq!(expected, recordbatches.pretty_print().unwrap());
+    }
+}
@@ -557,6 +557,7 @@ pub async fn setup_grpc_server(
     let fe_grpc_server = Arc::new(GrpcServer::new(
         ServerGrpcQueryHandlerAdaptor::arc(fe_instance_ref.clone()),
         Some(fe_instance_ref.clone()),
+        Some(fe_instance_ref.clone()),
         None,
         runtime,
     ));
@@ -16,9 +16,12 @@ use std::env;
 use std::sync::Arc;
 
 use common_catalog::consts::DEFAULT_CATALOG_NAME;
+use common_error::ext::ErrorExt;
+use common_error::status_code::StatusCode;
 use common_query::Output;
 use common_recordbatch::util;
 use common_telemetry::logging;
+use datatypes::value::Value;
 use datatypes::vectors::{Int64Vector, StringVector, UInt64Vector, VectorRef};
 use frontend::error::{Error, Result};
 use frontend::instance::Instance;
@@ -1286,6 +1289,50 @@ async fn test_execute_copy_from_orc(instance: Arc<dyn MockInstance>) {
     check_output_stream(output, expected).await;
 }
 
+#[apply(both_instances_cases)]
+async fn test_copy_jobs(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+
+    assert!(matches!(execute_sql(
+        &instance,
+        "create table jobs_demo(hostname STRING, environment STRING, usage_user DOUBLE, usage_system DOUBLE, usage_idle DOUBLE, usage_nice DOUBLE, usage_iowait DOUBLE, usage_irq DOUBLE, usage_softirq DOUBLE, usage_steal DOUBLE, usage_guest DOUBLE, usage_guest_nice DOUBLE, ts TIMESTAMP TIME INDEX, PRIMARY KEY(hostname));",
+    )
+    .await, Output::AffectedRows(0)));
+    let filepath = get_data_dir("../src/common/datasource/tests/csv/type_cast.csv")
+        .canonicalize()
+        .unwrap()
+        .display()
+        .to_string();
+    let output = execute_sql(
+        &instance,
+        &format!("copy jobs_demo from '{}' WITH(FORMAT='csv');", &filepath),
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(5)));
+
+    let Output::RecordBatches(jobs) = execute_sql(&instance, "show jobs").await else { unreachable!() };
+    let jobs = jobs.take();
+    assert_eq!(1, jobs[0].num_rows());
+    assert_eq!(Value::String("Done".into()), jobs[0].column(1).get(0));
+    let Value::String(job_id) = jobs[0].column(0).get(0) else { unreachable!() };
+
+    let output = execute_sql(
+        &instance,
+        &format!(
+            "select catalog, state from information_schema.jobs where job_id = '{}'",
+            job_id.as_utf8()
+        ),
+    )
+    .await;
+    let expected = "\
++----------+-------+
+| catalog  | state |
++----------+-------+
+| greptime | Done  |
++----------+-------+";
+    check_output_stream(output, expected).await;
+}
+
 #[apply(both_instances_cases)]
 async fn test_cast_type_issue_1594(instance: Arc<dyn MockInstance>) {
     let instance = instance.frontend();
@@ -1410,6 +1457,9 @@ async fn test_information_schema_dot_columns(instance: Arc<dyn MockInstance>) {
 | greptime      | public       | scripts    | timestamp    | TimestampMillisecond | TIME INDEX    |
 | greptime      | public       | scripts    | gmt_created  | TimestampMillisecond | FIELD         |
 | greptime      | public       | scripts    | gmt_modified | TimestampMillisecond | FIELD         |
+| greptime      | public       | scripts    | schedule     | String               | FIELD         |
+| greptime      | public       | scripts    | source_table | String               | FIELD         |
+| greptime      | public       | scripts    | target_table | String               | FIELD         |
 +---------------+--------------+------------+--------------+----------------------+---------------+";
 
     check_output_stream(output, expected).await;
@@ -1425,6 +1475,51 @@ async fn test_information_schema_dot_columns(instance: Arc<dyn MockInstance>) {
     check_output_stream(output, expected).await;
 }
 
+#[apply(both_instances_cases)]
+async fn test_kill_query(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+
+    let sql = "select number from numbers where number > 42";
+    let query_ctx = QueryContext::arc();
+    query_ctx.set_current_user("alice");
+    let Output::Stream(stream) = execute_sql_with(&instance, sql, query_ctx.clone()).await else {
+        unreachable!()
+    };
+
+    // The query stays in the process list until its stream is dropped.
+    let output = execute_sql(
+        &instance,
+        &format!("select id, state from information_schema.processlist where info = '{sql}'"),
+    )
+    .await;
+    let Output::Stream(processes) = output else { unreachable!() };
+    let processes = util::collect(processes).await.unwrap();
+    assert_eq!(1, processes.len());
+    assert_eq!(1, processes[0].num_rows());
+    assert_eq!(
+        Value::String("Streaming".into()),
+        processes[0].column(1).get(0)
+    );
+    let Value::UInt32(id) = processes[0].column(0).get(0) else { unreachable!() };
+
+    // Only the user running the query may kill it.
+    let err = try_execute_sql(&instance, &format!("KILL QUERY {id}"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::KillQueryDenied { .. }));
+
+    let output = execute_sql_with(&instance, &format!("KILL QUERY {id}"), query_ctx.clone()).await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+
+    let err = util::collect(stream).await.unwrap_err();
+    assert_eq!(StatusCode::Cancelled, err.status_code());
+
+    let err = try_execute_sql_with(&instance, &format!("KILL QUERY {id}"), query_ctx)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::ProcessNotFound { .. }));
+}
+
 async fn execute_sql(instance: &Arc<Instance>, sql: &str) -> Output {
     execute_sql_with(instance, sql, QueryContext::arc()).await
 }
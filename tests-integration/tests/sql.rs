@@ -13,8 +13,8 @@
 // limitations under the License.
 use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
 use sqlx::mysql::MySqlPoolOptions;
-use sqlx::postgres::PgPoolOptions;
-use sqlx::Row;
+use sqlx::postgres::{PgConnection, PgPoolOptions};
+use sqlx::{Connection, Row};
 use tests_integration::test_util::{setup_mysql_server, setup_pg_server, StorageType};
 
 #[macro_export]
@@ -49,6 +49,7 @@ macro_rules! sql_tests {
 
                 test_mysql_crud,
                 test_postgres_crud,
+                test_postgres_plan_cache,
             );
         )*
     };
@@ -211,3 +212,47 @@ pub async fn test_postgres_crud(store_type: StorageType) {
     let _ = fe_pg_server.shutdown().await;
     guard.remove_all().await;
 }
+
+pub async fn test_postgres_plan_cache(store_type: StorageType) {
+    let (addr, mut guard, fe_pg_server) = setup_pg_server(store_type, "sql_plan_cache").await;
+
+    let url = format!("postgres://{addr}/public");
+    let mut conn = PgConnection::connect(&url).await.unwrap();
+    let mut other_conn = PgConnection::connect(&url).await.unwrap();
+
+    sqlx::query("create table plan_cache(i bigint, ts timestamp time index)")
+        .execute(&mut conn)
+        .await
+        .unwrap();
+    sqlx::query("insert into plan_cache values(1, 1)")
+        .execute(&mut conn)
+        .await
+        .unwrap();
+
+    // Not persistent, so the statement is parsed by the server every time.
+    let select = "select * from plan_cache where i = $1";
+    let row = sqlx::query(select)
+        .bind(1)
+        .persistent(false)
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(row.columns().len(), 2);
+
+    // The plan cached by the first session must not be reused after DDL from another session.
+    sqlx::query("alter table plan_cache add column j bigint")
+        .execute(&mut other_conn)
+        .await
+        .unwrap();
+
+    let row = sqlx::query(select)
+        .bind(1)
+        .persistent(false)
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(row.columns().len(), 3);
+
+    let _ = fe_pg_server.shutdown().await;
+    guard.remove_all().await;
+}
@@ -0,0 +1,36 @@
+-- Aggregates are split into partial ones on datanodes and final ones on the frontend.
+CREATE TABLE aggregate_test (
+  host STRING,
+  n INT,
+  v INT,
+  ts TIMESTAMP TIME INDEX,
+  PRIMARY KEY (host)
+)
+PARTITION BY RANGE COLUMNS (n) (
+    PARTITION r0 VALUES LESS THAN (5),
+    PARTITION r1 VALUES LESS THAN (9),
+    PARTITION r2 VALUES LESS THAN (MAXVALUE),
+);
+
+-- Empty table
+SELECT count(*), count(v), sum(v), avg(v), min(v), max(v) FROM aggregate_test;
+
+SELECT host, count(*), count(v), sum(v), avg(v), min(v), max(v) FROM aggregate_test GROUP BY host ORDER BY host;
+
+INSERT INTO aggregate_test VALUES
+    ('a', 1, 10, 1000),
+    ('a', 6, 20, 2000),
+    ('a', 10, 30, 3000),
+    ('b', 2, NULL, 1000),
+    ('b', 7, NULL, 2000),
+    ('c', 3, 5, 1000),
+    ('c', 12, 6, 2000);
+
+SELECT count(*), count(v), sum(v), avg(v), min(v), max(v) FROM aggregate_test;
+
+-- All values of group b are NULL
+SELECT host, count(*), count(v), sum(v), avg(v), min(v), max(v) FROM aggregate_test GROUP BY host ORDER BY host;
+
+SELECT count(*), count(v), sum(v), avg(v), min(v), max(v) FROM aggregate_test WHERE host = 'b';
+
+DROP TABLE aggregate_test;
@@ -8,4 +8,6 @@ Copy demo TO '/tmp/export/demo.csv' with (format='csv');
 
 Copy demo TO '/tmp/export/demo.json' with (format='json');
 
+Copy demo TO '/tmp/export/demo.orc' with (format='orc');
+
 drop table demo;
@@ -0,0 +1,28 @@
+CREATE TABLE logs (
+  ts TIMESTAMP(3) TIME INDEX,
+  host STRING PRIMARY KEY,
+  message STRING FULLTEXT,
+);
+
+INSERT INTO TABLE logs VALUES
+    (0, 'h1', 'Connection refused'),
+    (1, 'h2', 'disk is full'),
+    (2, 'h1', 'connection reset by peer'),
+    (3, 'h2', NULL);
+
+-- SQLNESS ARG flush=logs
+SELECT ts, host, message FROM logs WHERE matches(message, 'connection') ORDER BY ts;
+
+SELECT ts, message FROM logs WHERE message @@ 'Disk full' ORDER BY ts;
+
+SELECT ts, message FROM logs WHERE message @@ 'connection' AND message @@ 'peer' ORDER BY ts;
+
+SELECT ts, message FROM logs WHERE matches(message, 'timeout') ORDER BY ts;
+
+SELECT ts, message FROM logs WHERE matches(message, 'Connection', 'Whitespace') ORDER BY ts;
+
+INSERT INTO TABLE logs VALUES (4, 'h3', 'connection timeout');
+
+SELECT ts, message FROM logs WHERE message @@ 'connection' ORDER BY ts;
+
+DROP TABLE logs;
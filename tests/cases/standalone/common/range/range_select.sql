@@ -0,0 +1,33 @@
+CREATE TABLE host (
+  ts TIMESTAMP(3) TIME INDEX,
+  host STRING PRIMARY KEY,
+  val DOUBLE
+);
+
+INSERT INTO TABLE host VALUES
+    (0, 'host1', 0),
+    (5000, 'host1', null),
+    (10000, 'host1', 1),
+    (15000, 'host1', null),
+    (20000, 'host1', 2),
+    (0, 'host2', 3),
+    (5000, 'host2', null),
+    (10000, 'host2', 4),
+    (15000, 'host2', null),
+    (20000, 'host2', 5);
+
+SELECT ts, host, min(val) RANGE '5s' FROM host ALIGN '5s' BY (host) ORDER BY host, ts;
+
+SELECT ts, host, min(val) RANGE '5s' FILL PREV FROM host ALIGN '5s' BY (host) ORDER BY host, ts;
+
+SELECT ts, host, min(val) RANGE '5s' FILL LINEAR FROM host ALIGN '5s' BY (host) ORDER BY host, ts;
+
+-- every range covers the 10s up to the aligned timestamp, i.e. (ts - 10s, ts] like PromQL
+SELECT ts, host, max(val) RANGE '10s' FROM host ALIGN '5s' BY (host) ORDER BY host, ts;
+
+-- FILL of ALIGN is the default of range expressions without FILL, no BY aggregates all hosts
+SELECT ts, min(val) RANGE '5s', max(val) RANGE '5s' FILL 6 FROM host ALIGN '5s' FILL PREV ORDER BY ts;
+
+SELECT ts, host, count(val) RANGE '5s' AS c FROM host WHERE host = 'host2' ALIGN '10s' BY (host) ORDER BY ts;
+
+DROP TABLE host;
@@ -23,6 +23,7 @@ use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use async_trait::async_trait;
+use client::api::v1::FlushTableExpr;
 use client::{
     Client, Database as DB, Error as ClientError, DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME,
 };
@@ -333,6 +334,18 @@ impl Database for GreptimeDB {
             client.set_schema(database);
         }
 
+        if let Some(table_name) = ctx.context.get("flush") {
+            let expr = FlushTableExpr {
+                catalog_name: client.catalog().clone(),
+                schema_name: client.schema().clone(),
+                table_name: table_name.clone(),
+                ..Default::default()
+            };
+            if let Err(e) = client.flush_table(expr).await {
+                return Box::new(ResultDisplayer { result: Err(e) }) as _;
+            }
+        }
+
         let result = client.sql(&query).await;
         Box::new(ResultDisplayer { result }) as _
     }